@@ -43,6 +43,7 @@
 //! - **Duplicate attributes**: Both `#[serde(...)]` and `#[ts(...)]` needed since
 //!   they control different things (runtime serialization vs compile-time codegen)
 
+use crate::capability::{PluginCapability, PluginCapabilityPolicy};
 use crate::command::{Command, Suggestion};
 use crate::file_explorer::{FileExplorerDecoration, FileExplorerSlotEntry};
 use crate::hooks::{HookCallback, HookRegistry};
@@ -1659,6 +1660,16 @@ pub struct EditorStateSnapshot {
     /// every snapshot tick; read by `editor.listMacros()` / `editor.getMacro()`.
     #[serde(default)]
     pub macros: Vec<MacroSnapshot>,
+
+    /// Which plugins are restricted to the capabilities declared in their
+    /// package manifest, and which of those the user has approved. Read by
+    /// the plugin runtime before each gated call (`spawnProcess`,
+    /// `httpFetch`, `writeFile` outside the workspace, authority changes);
+    /// a call that isn't allowed is routed through
+    /// `PluginCommand::RequestPluginCapability` instead. Host-only.
+    #[serde(skip)]
+    #[ts(skip)]
+    pub plugin_capabilities: PluginCapabilityPolicy,
 }
 
 /// One recorded macro, exposed to plugins. `register` is the single-character
@@ -1722,6 +1733,7 @@ impl EditorStateSnapshot {
             terminal_height: 0,
             has_active_search: false,
             macros: Vec::new(),
+            plugin_capabilities: PluginCapabilityPolicy::default(),
         }
     }
 }
@@ -4940,6 +4952,30 @@ pub enum PluginCommand {
         callback_id: JsCallbackId,
     },
 
+    /// Project-wide structural search (async)
+    /// Parses every project file in the pattern's language with its
+    /// tree-sitter grammar and matches the pattern against the syntax tree.
+    /// Open buffers with dirty edits are searched in-memory.
+    StructuralSearchProject {
+        /// Which plugin asked. A newer search replaces that plugin's own
+        /// in-flight one, as with `GrepProject`.
+        plugin_name: String,
+        /// Code pattern with `$NAME` / `$$$NAME` metavariables
+        pattern: String,
+        /// Language id of the pattern (e.g. "typescript"); only files of this
+        /// language are searched
+        language: String,
+        /// Replacement template using the pattern's metavariables. When set,
+        /// each match carries its rewritten text.
+        rewrite: Option<String>,
+        /// Optional comma-separated file globs, as for `BeginSearch`
+        file_glob: String,
+        /// Maximum number of results to return
+        max_results: usize,
+        /// Callback ID for async response
+        callback_id: JsCallbackId,
+    },
+
     /// Project-wide streaming search using a pull-based handle.
     ///
     /// The plugin allocates `handle_id` and registers an `Arc<SearchHandleState>`
@@ -4985,6 +5021,9 @@ pub enum PluginCommand {
         matches: Vec<(usize, usize)>,
         /// Replacement text
         replacement: String,
+        /// Replacement text for each match, parallel to `matches`. When
+        /// empty, every match is replaced with `replacement`.
+        replacements: Vec<String>,
         /// Callback ID for async response
         callback_id: JsCallbackId,
     },
@@ -5118,6 +5157,21 @@ pub enum PluginCommand {
     /// `DEVCONTAINER_SPEC_GAP_PLAN.md`).
     KillHostProcess { process_id: u64 },
 
+    /// A package plugin made a call that needs `capability`, which its
+    /// manifest doesn't declare or the user hasn't approved yet (see
+    /// `EditorStateSnapshot::plugin_capabilities`). The editor asks the
+    /// user once if the capability is declared but undecided, records the
+    /// answer, and then either dispatches `command` or fails it the way a
+    /// Workspace-Trust denial fails it. `command` is `None` for synchronous
+    /// calls (`writeFile`) that already returned an error to the plugin;
+    /// the request then only triggers the approval prompt.
+    RequestPluginCapability {
+        plugin_name: String,
+        capability: PluginCapability,
+        #[ts(type = "unknown")]
+        command: Option<Box<PluginCommand>>,
+    },
+
     /// Mount a declarative widget panel inside an existing virtual
     /// buffer. The host renders the `WidgetSpec` and writes the
     /// resulting text-property entries into the buffer. `panel_id`
@@ -5547,6 +5601,37 @@ pub struct GrepMatch {
     pub context: String,
 }
 
+/// A single match from a project-wide structural search
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export, rename_all = "camelCase")]
+pub struct StructuralMatch {
+    /// Absolute file path
+    pub file: String,
+    /// Buffer ID if the file is open (0 if not)
+    #[ts(type = "number")]
+    pub buffer_id: usize,
+    /// Byte offset of the matched node in the file/buffer content
+    #[ts(type = "number")]
+    pub byte_offset: usize,
+    /// Length of the matched node in bytes
+    #[ts(type = "number")]
+    pub length: usize,
+    /// 1-indexed line number of the match start
+    #[ts(type = "number")]
+    pub line: usize,
+    /// 1-indexed column number of the match start
+    #[ts(type = "number")]
+    pub column: usize,
+    /// The line the match starts on (for display)
+    pub context: String,
+    /// The matched source text
+    pub text: String,
+    /// `text` rewritten by the search's replacement template, if one was given
+    #[ts(optional, type = "string | null")]
+    pub replacement: Option<String>,
+}
+
 /// Per-call result from `SearchHandle.take()` — the matches accumulated since
 /// the previous call plus terminal-state flags.
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
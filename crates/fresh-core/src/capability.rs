@@ -0,0 +1,131 @@
+//! Plugin capabilities: classes of side effect a plugin installed from a
+//! package has to declare in its manifest (`fresh.capabilities` in
+//! `package.json`) and the user has to approve before the plugin may use
+//! them.
+//!
+//! Plugins that don't come from a package — the bundled ones, the user's own
+//! `plugins/` directory, `init.ts` — are not restricted. The editor decides
+//! which plugins are, and mirrors the decision into
+//! [`EditorStateSnapshot::plugin_capabilities`](crate::api::EditorStateSnapshot::plugin_capabilities)
+//! so the plugin runtime can check each gated call where it is made.
+
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// A capability a package plugin must declare and have approved.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, JsonSchema, TS,
+)]
+#[serde(rename_all = "kebab-case")]
+#[ts(export)]
+pub enum PluginCapability {
+    /// Run external programs: `spawnProcess`, `spawnHostProcess`,
+    /// `spawnBackgroundProcess`, and terminals created with a `command`.
+    Process,
+    /// Reach the network: `httpFetch`.
+    Network,
+    /// Write outside the workspace and the temp directory: `writeFile`,
+    /// `createDir`, `renamePath`, `copyPath`, `saveBufferToPath`; and
+    /// `removePath` outside the temp directory.
+    Filesystem,
+    /// Change where the editor runs things: `setAuthority`,
+    /// `clearAuthority`, `attachRemoteAgent`.
+    Authority,
+}
+
+impl PluginCapability {
+    pub const ALL: [PluginCapability; 4] = [
+        PluginCapability::Process,
+        PluginCapability::Network,
+        PluginCapability::Filesystem,
+        PluginCapability::Authority,
+    ];
+
+    /// The manifest spelling, e.g. `"network"`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PluginCapability::Process => "process",
+            PluginCapability::Network => "network",
+            PluginCapability::Filesystem => "filesystem",
+            PluginCapability::Authority => "authority",
+        }
+    }
+
+    /// Parse the manifest spelling.
+    pub fn from_str_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|c| c.as_str() == name)
+    }
+}
+
+impl fmt::Display for PluginCapability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Which plugins are restricted, and which capabilities each currently
+/// holds (declared *and* approved). Plugins the policy doesn't mention are
+/// unrestricted.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PluginCapabilityPolicy {
+    granted: HashMap<String, BTreeSet<PluginCapability>>,
+}
+
+impl PluginCapabilityPolicy {
+    /// Restrict `plugin` to `granted`.
+    pub fn restrict(
+        &mut self,
+        plugin: impl Into<String>,
+        granted: impl IntoIterator<Item = PluginCapability>,
+    ) {
+        self.granted
+            .insert(plugin.into(), granted.into_iter().collect());
+    }
+
+    /// Whether `plugin` is restricted at all.
+    pub fn is_restricted(&self, plugin: &str) -> bool {
+        self.granted.contains_key(plugin)
+    }
+
+    /// Whether `plugin` may use `capability` without asking.
+    pub fn allows(&self, plugin: &str, capability: PluginCapability) -> bool {
+        self.granted
+            .get(plugin)
+            .is_none_or(|granted| granted.contains(&capability))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unlisted_plugins_are_unrestricted() {
+        let mut policy = PluginCapabilityPolicy::default();
+        policy.restrict("pkg", [PluginCapability::Network]);
+
+        assert!(policy.allows("bundled", PluginCapability::Process));
+        assert!(policy.allows("pkg", PluginCapability::Network));
+        assert!(!policy.allows("pkg", PluginCapability::Process));
+        assert!(policy.is_restricted("pkg"));
+        assert!(!policy.is_restricted("bundled"));
+    }
+
+    #[test]
+    fn manifest_names_round_trip() {
+        for capability in PluginCapability::ALL {
+            assert_eq!(
+                PluginCapability::from_str_name(capability.as_str()),
+                Some(capability)
+            );
+            assert_eq!(
+                serde_json::to_value(capability).unwrap(),
+                serde_json::json!(capability.as_str())
+            );
+        }
+    }
+}
@@ -55,6 +55,7 @@ pub enum SplitDirection {
 
 pub mod action;
 pub mod api;
+pub mod capability;
 pub mod command;
 pub mod hooks;
 
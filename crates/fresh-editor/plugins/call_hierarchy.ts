@@ -0,0 +1,467 @@
+/// <reference path="./lib/fresh.d.ts" />
+
+/**
+ * Call / Type Hierarchy Plugin
+ *
+ * Shows LSP call hierarchies (incoming and outgoing calls) and type
+ * hierarchies (supertypes and subtypes) as an expandable tree in the
+ * Utility Dock, the same dock the references and diagnostics lists use.
+ *
+ * The tree is lazy: `textDocument/prepareCallHierarchy` (or
+ * `prepareTypeHierarchy`) resolves the symbol at the cursor into root
+ * items, and each node's children are only requested
+ * (`callHierarchy/incomingCalls`, `typeHierarchy/subtypes`, ...) the first
+ * time it is expanded. Expansion itself is host-owned (Right/Left, or a
+ * click on the disclosure glyph); the plugin reacts to the resulting
+ * `widget_event` "expand".
+ */
+
+import { getRelativePath } from "./lib/finder.ts";
+import {
+  col,
+  hintBar,
+  key as widgetKey,
+  raw,
+  styledRow,
+  tree,
+  treeNode,
+  WidgetPanel,
+} from "./lib/widgets.ts";
+
+const editor = getEditor();
+
+// =============================================================================
+// LSP shapes
+// =============================================================================
+
+interface LspPosition {
+  line: number;
+  character: number;
+}
+
+interface LspRange {
+  start: LspPosition;
+  end: LspPosition;
+}
+
+/** `CallHierarchyItem` and `TypeHierarchyItem` share this shape. */
+interface HierarchyItem {
+  name: string;
+  kind: number;
+  detail?: string;
+  uri: string;
+  range: LspRange;
+  selectionRange: LspRange;
+  data?: unknown;
+}
+
+type Direction = "incoming" | "outgoing" | "supertypes" | "subtypes";
+
+interface DirectionInfo {
+  prepare: string;
+  children: string;
+  title: string;
+  empty: string;
+}
+
+const DIRECTIONS: Record<Direction, DirectionInfo> = {
+  incoming: {
+    prepare: "textDocument/prepareCallHierarchy",
+    children: "callHierarchy/incomingCalls",
+    title: "panel.incoming",
+    empty: "status.no_call_item",
+  },
+  outgoing: {
+    prepare: "textDocument/prepareCallHierarchy",
+    children: "callHierarchy/outgoingCalls",
+    title: "panel.outgoing",
+    empty: "status.no_call_item",
+  },
+  supertypes: {
+    prepare: "textDocument/prepareTypeHierarchy",
+    children: "typeHierarchy/supertypes",
+    title: "panel.supertypes",
+    empty: "status.no_type_item",
+  },
+  subtypes: {
+    prepare: "textDocument/prepareTypeHierarchy",
+    children: "typeHierarchy/subtypes",
+    title: "panel.subtypes",
+    empty: "status.no_type_item",
+  },
+};
+
+// =============================================================================
+// State
+// =============================================================================
+
+interface HierarchyNode {
+  key: string;
+  item: HierarchyItem;
+  depth: number;
+  /**
+   * Where Enter jumps to. For incoming calls this is the call site inside
+   * the caller (`fromRanges[0]`); otherwise the item's own name.
+   */
+  target: { uri: string; position: LspPosition };
+  /** Child node keys; `null` until the node is first expanded. */
+  children: string[] | null;
+  /** Set once a fetch came back empty, so the disclosure glyph goes away. */
+  leaf: boolean;
+}
+
+interface HierarchyPanel {
+  bufferId: number;
+  splitId: number;
+  panel: WidgetPanel;
+  direction: Direction;
+  language: string;
+  rootName: string;
+  roots: string[];
+  nodes: Map<string, HierarchyNode>;
+  /** Keys with a children request in flight. */
+  loading: Set<string>;
+}
+
+const MODE = "call-hierarchy";
+const TREE_KEY = "hierarchy";
+
+let state: HierarchyPanel | null = null;
+
+// =============================================================================
+// Rendering
+// =============================================================================
+
+function itemLocation(node: HierarchyNode): string {
+  const path = editor.fileUriToPath(node.item.uri);
+  const line = node.item.selectionRange.start.line + 1;
+  return `${getRelativePath(editor, path)}:${line}`;
+}
+
+function nodeEntry(node: HierarchyNode): TextPropertyEntry {
+  const segments: StyledSegment[] = [{ text: node.item.name, style: { bold: true } }];
+  if (node.item.detail) {
+    segments.push({ text: `  ${node.item.detail}`, style: { fg: "ui.menu_disabled_fg" } });
+  }
+  segments.push({ text: `  ${itemLocation(node)}`, style: { fg: "ui.menu_disabled_fg" } });
+  return styledRow(segments);
+}
+
+function collectRows(
+  keys: string[],
+  nodes: TreeNode[],
+  itemKeys: string[],
+): void {
+  if (!state) return;
+  for (const key of keys) {
+    const node = state.nodes.get(key);
+    if (!node) continue;
+    nodes.push(treeNode(nodeEntry(node), { depth: node.depth, hasChildren: !node.leaf }));
+    itemKeys.push(key);
+    if (node.children) collectRows(node.children, nodes, itemKeys);
+  }
+}
+
+function visibleRows(): number {
+  const vp = editor.getViewport();
+  const height = vp && vp.height > 0 ? vp.height : 20;
+  // Header row + hint bar.
+  return Math.max(3, height - 2);
+}
+
+function render(): void {
+  if (!state) return;
+  const nodes: TreeNode[] = [];
+  const itemKeys: string[] = [];
+  collectRows(state.roots, nodes, itemKeys);
+
+  const title = editor.t(DIRECTIONS[state.direction].title, { name: state.rootName });
+  state.panel.set(
+    col(
+      raw([styledRow([{ text: title, style: { bold: true } }])]),
+      tree({
+        nodes,
+        itemKeys,
+        selectedIndex: 0,
+        visibleRows: visibleRows(),
+        expandedKeys: state.roots,
+        key: TREE_KEY,
+      }),
+      hintBar([
+        { keys: "→", label: editor.t("hint.expand") },
+        { keys: "←", label: editor.t("hint.collapse") },
+        { keys: "RET", label: editor.t("hint.open") },
+        { keys: "q", label: editor.t("hint.close") },
+      ]),
+    ),
+  );
+}
+
+// =============================================================================
+// Requests
+// =============================================================================
+
+function describeError(err: unknown): string {
+  return err instanceof Error ? err.message : String(err);
+}
+
+function makeNode(
+  key: string,
+  item: HierarchyItem,
+  depth: number,
+  callSite?: LspRange,
+): HierarchyNode {
+  return {
+    key,
+    item,
+    depth,
+    target: {
+      uri: item.uri,
+      position: (callSite ?? item.selectionRange).start,
+    },
+    children: null,
+    leaf: false,
+  };
+}
+
+/** Fetch the children of `key` once; later expansions reuse them. */
+async function loadChildren(key: string): Promise<void> {
+  const panel = state;
+  if (!panel) return;
+  const node = panel.nodes.get(key);
+  if (!node || node.children !== null || panel.loading.has(key)) return;
+
+  panel.loading.add(key);
+  const method = DIRECTIONS[panel.direction].children;
+  let result: unknown;
+  try {
+    result = await editor.sendLspRequest(panel.language, method, { item: node.item });
+  } catch (err) {
+    editor.setStatus(editor.t("status.request_failed", { method, error: describeError(err) }));
+    return;
+  } finally {
+    panel.loading.delete(key);
+  }
+  // The panel may have been closed or retargeted while waiting.
+  if (state !== panel) return;
+
+  const children: string[] = [];
+  const entries = Array.isArray(result) ? result : [];
+  entries.forEach((entry, index) => {
+    const childKey = `${key}/${index}`;
+    let child: HierarchyNode;
+    if (panel.direction === "incoming") {
+      // CallHierarchyIncomingCall: the caller, with the call sites in it.
+      const call = entry as { from: HierarchyItem; fromRanges: LspRange[] };
+      child = makeNode(childKey, call.from, node.depth + 1, call.fromRanges[0]);
+    } else if (panel.direction === "outgoing") {
+      // CallHierarchyOutgoingCall: the callee; `fromRanges` are in the
+      // parent, so jump to the callee's definition instead.
+      const call = entry as { to: HierarchyItem };
+      child = makeNode(childKey, call.to, node.depth + 1);
+    } else {
+      child = makeNode(childKey, entry as HierarchyItem, node.depth + 1);
+    }
+    panel.nodes.set(childKey, child);
+    children.push(childKey);
+  });
+  node.children = children;
+  node.leaf = children.length === 0;
+  render();
+}
+
+async function cursorPosition(bufferId: number): Promise<LspPosition | null> {
+  const cursor = editor.getPrimaryCursor();
+  if (!cursor || cursor.line === null) return null;
+  const lineStart = await editor.getLineStartPosition(cursor.line);
+  if (lineStart === null) return null;
+  // LSP characters are UTF-16 code units, which is exactly what a JS
+  // string's length counts.
+  const prefix = await editor.getBufferText(bufferId, lineStart, cursor.position);
+  return { line: cursor.line, character: prefix.length };
+}
+
+async function openPanelBuffer(): Promise<{ bufferId: number; splitId: number } | null> {
+  try {
+    const result = await editor.createVirtualBufferInSplit({
+      name: "*Hierarchy*",
+      mode: MODE,
+      readOnly: true,
+      entries: [],
+      ratio: 0.65,
+      panelId: "call-hierarchy-panel",
+      role: "utility_dock",
+      showLineNumbers: false,
+      showCursors: false,
+      editingDisabled: true,
+      // The tree owns its scroll window.
+      scrollable: false,
+    });
+    return { bufferId: result.bufferId, splitId: result.splitId ?? editor.getActiveSplitId() };
+  } catch (err) {
+    editor.setStatus(editor.t("status.failed_open_panel", { error: describeError(err) }));
+    return null;
+  }
+}
+
+async function showHierarchy(direction: Direction): Promise<void> {
+  const bufferId = editor.getActiveBufferId();
+  const path = editor.getBufferPath(bufferId);
+  const language = editor.getBufferInfo(bufferId)?.language;
+  if (!path || !language) {
+    editor.setStatus(editor.t("status.no_file"));
+    return;
+  }
+  const position = await cursorPosition(bufferId);
+  if (!position) return;
+
+  const info = DIRECTIONS[direction];
+  let result: unknown;
+  try {
+    result = await editor.sendLspRequest(language, info.prepare, {
+      textDocument: { uri: editor.pathToFileUri(path) },
+      position,
+    });
+  } catch (err) {
+    editor.setStatus(
+      editor.t("status.request_failed", { method: info.prepare, error: describeError(err) }),
+    );
+    return;
+  }
+  const items = Array.isArray(result) ? (result as HierarchyItem[]) : [];
+  if (items.length === 0) {
+    editor.setStatus(editor.t(info.empty));
+    return;
+  }
+
+  let bufferAndSplit: { bufferId: number; splitId: number } | null;
+  if (state) {
+    bufferAndSplit = { bufferId: state.bufferId, splitId: state.splitId };
+    editor.focusSplit(state.splitId);
+  } else {
+    bufferAndSplit = await openPanelBuffer();
+  }
+  if (!bufferAndSplit) return;
+
+  const nodes = new Map<string, HierarchyNode>();
+  const roots = items.map((item, index) => {
+    const key = String(index);
+    nodes.set(key, makeNode(key, item, 0));
+    return key;
+  });
+  state = {
+    bufferId: bufferAndSplit.bufferId,
+    splitId: bufferAndSplit.splitId,
+    panel: state?.panel ?? new WidgetPanel(bufferAndSplit.bufferId),
+    direction,
+    language,
+    rootName: items[0].name,
+    roots,
+    nodes,
+    loading: new Set(),
+  };
+  render();
+  // A reused panel keeps the previous tree's expansion; start over with
+  // just the roots open.
+  state.panel.setExpandedKeys(TREE_KEY, roots);
+  await Promise.all(roots.map((key) => loadChildren(key)));
+}
+
+function openNode(key: string): void {
+  const node = state?.nodes.get(key);
+  if (!node) return;
+  const { uri, position } = node.target;
+  editor.openFile(editor.fileUriToPath(uri), position.line + 1, position.character + 1);
+}
+
+function closePanel(): void {
+  if (!state) return;
+  const panel = state;
+  state = null;
+  panel.panel.unmount();
+  editor.closeBuffer(panel.bufferId);
+}
+
+// =============================================================================
+// Events and keys
+// =============================================================================
+
+editor.on("widget_event", (args) => {
+  if (!state || args.panel_id !== state.panel.id() || args.widget_key !== TREE_KEY) return;
+  const payload = (args.payload ?? {}) as { key?: string; expanded?: boolean };
+  if (typeof payload.key !== "string") return;
+  if (args.event_type === "expand" && payload.expanded) {
+    void loadChildren(payload.key);
+  } else if (args.event_type === "activate") {
+    openNode(payload.key);
+  }
+});
+
+editor.on("buffer_closed", (data) => {
+  if (state && data.buffer_id === state.bufferId) {
+    state = null;
+  }
+});
+
+function dispatch(name: string): void {
+  state?.panel.command(widgetKey(name));
+}
+
+registerHandler("call_hierarchy_up", () => dispatch("Up"));
+registerHandler("call_hierarchy_down", () => dispatch("Down"));
+registerHandler("call_hierarchy_page_up", () => dispatch("PageUp"));
+registerHandler("call_hierarchy_page_down", () => dispatch("PageDown"));
+registerHandler("call_hierarchy_left", () => dispatch("Left"));
+registerHandler("call_hierarchy_right", () => dispatch("Right"));
+registerHandler("call_hierarchy_enter", () => dispatch("Enter"));
+registerHandler("call_hierarchy_close", closePanel);
+
+editor.defineMode(
+  MODE,
+  [
+    ["Up", "call_hierarchy_up"],
+    ["Down", "call_hierarchy_down"],
+    ["k", "call_hierarchy_up"],
+    ["j", "call_hierarchy_down"],
+    ["PageUp", "call_hierarchy_page_up"],
+    ["PageDown", "call_hierarchy_page_down"],
+    ["Left", "call_hierarchy_left"],
+    ["Right", "call_hierarchy_right"],
+    ["Return", "call_hierarchy_enter"],
+    ["q", "call_hierarchy_close"],
+    ["Escape", "call_hierarchy_close"],
+  ],
+  true, // read-only
+  false, // allow_text_input
+);
+
+// =============================================================================
+// Commands
+// =============================================================================
+
+function show_incoming_calls(): Promise<void> {
+  return showHierarchy("incoming");
+}
+registerHandler("show_incoming_calls", show_incoming_calls);
+
+function show_outgoing_calls(): Promise<void> {
+  return showHierarchy("outgoing");
+}
+registerHandler("show_outgoing_calls", show_outgoing_calls);
+
+function show_supertypes(): Promise<void> {
+  return showHierarchy("supertypes");
+}
+registerHandler("show_supertypes", show_supertypes);
+
+function show_subtypes(): Promise<void> {
+  return showHierarchy("subtypes");
+}
+registerHandler("show_subtypes", show_subtypes);
+
+editor.registerCommand("%cmd.show_incoming_calls", "%cmd.show_incoming_calls_desc", "show_incoming_calls", null);
+editor.registerCommand("%cmd.show_outgoing_calls", "%cmd.show_outgoing_calls_desc", "show_outgoing_calls", null);
+editor.registerCommand("%cmd.show_supertypes", "%cmd.show_supertypes_desc", "show_supertypes", null);
+editor.registerCommand("%cmd.show_subtypes", "%cmd.show_subtypes_desc", "show_subtypes", null);
+
+editor.debug("Call hierarchy plugin loaded");
@@ -2145,7 +2145,10 @@ type AuthoritySpawner = {
 	user?: string | null;
 	workspace?: string | null;
 	env?: [string, string][];
-};
+} | (ExecCarrier & {
+	kind: "exec";
+	env?: [string, string][];
+});
 type AuthorityTerminalWrapper = {
 	kind: "host-shell";
 } | {
@@ -2153,6 +2156,28 @@ type AuthorityTerminalWrapper = {
 	command: string;
 	args: string[];
 	manages_cwd?: boolean;
+} | (ExecCarrier & {
+	kind: "exec";
+	env?: [string, string][];
+});
+/**
+* Generic exec carrier (Podman, Incus, Distrobox, a custom sandbox, …):
+* argv templates with a `{cmd}` placeholder for the carried command. An
+* element that is exactly `{cmd}` is replaced by the command's argv; an
+* element containing it gets the shell-quoted command string; a template
+* without it has the argv appended.
+*/
+type ExecCarrier = {
+	/** One-shot runs, e.g. `["podman", "exec", "dev", "{cmd}"]`. */
+	run: string[];
+	/** Integrated-terminal runs (needs a TTY). Defaults to `run`. */
+	interactive?: string[] | null;
+	/** Long-lived piped runs: the remote agent and LSP servers. Defaults to `run`. */
+	stdio?: string[] | null;
+	/** Far-side workspace root (terminal `cd` target, default spawn cwd). */
+	workspace?: string | null;
+	/** Status-bar label; derived from `run` when omitted. */
+	label?: string | null;
 };
 type AuthorityPayload = {
 	filesystem: AuthorityFilesystem;
@@ -2543,7 +2568,9 @@ type RemoteAgentTransport = {
 	/** Extra `ssh` arguments (e.g. `-J jump`, `-o ProxyCommand=…`) applied to
 	* every ssh invocation for this session. */
 	extra_args?: string[];
-};
+} | (ExecCarrier & {
+	kind: "exec";
+});
 type RemoteAgentSpec = {
 	transport: RemoteAgentTransport;
 	/**
@@ -2628,6 +2655,44 @@ type SpawnResult = {
 	*/
 	exit_code: number;
 };
+type StructuralMatch = {
+	/**
+	* Absolute file path
+	*/
+	file: string;
+	/**
+	* Buffer ID if the file is open (0 if not)
+	*/
+	bufferId: number;
+	/**
+	* Byte offset of the matched node in the file/buffer content
+	*/
+	byteOffset: number;
+	/**
+	* Length of the matched node in bytes
+	*/
+	length: number;
+	/**
+	* 1-indexed line number of the match start
+	*/
+	line: number;
+	/**
+	* 1-indexed column number of the match start
+	*/
+	column: number;
+	/**
+	* The line the match starts on (for display)
+	*/
+	context: string;
+	/**
+	* The matched source text
+	*/
+	text: string;
+	/**
+	* `text` rewritten by the search's replacement template, if one was given
+	*/
+	replacement?: string | null;
+};
 type StyledText = {
 	text: string;
 	style?: Partial<OverlayOptions>;
@@ -4706,6 +4771,13 @@ interface EditorAPI {
 	*/
 	grepProject(pattern: string, fixedString: boolean | null, caseSensitive: boolean | null, maxResults: number | null, wholeWords: boolean | null): Promise<GrepMatch[]>;
 	/**
+	* Project-wide structural search (async)
+	* Matches a code pattern with `$NAME` / `$$$NAME` metavariables against
+	* the syntax tree of every project file in `language`. With `rewrite`,
+	* each match also carries its replacement text.
+	*/
+	structuralSearch(pattern: string, language: string, rewrite: string | null, fileGlob: string | null, maxResults: number | null): Promise<StructuralMatch[]>;
+	/**
 	* Begin a streaming project-wide search and return a `SearchHandle`.
 	* The producer (host) writes matches at full speed into shared state;
 	* the consumer drains via `handle.take()` at its own cadence. Call
@@ -4726,6 +4798,11 @@ interface EditorAPI {
 	*/
 	replaceInFile(filePath: string, matches: number[][], replacement: string, bufferId?: number): Promise<ReplaceResult>;
 	/**
+	* Replace matches in a file's buffer, each with its own text (async)
+	* Like `replaceInFile`, but `replacements[i]` replaces `matches[i]`.
+	*/
+	replaceMatchesInFile(filePath: string, matches: number[][], replacements: string[], bufferId?: number): Promise<ReplaceResult>;
+	/**
 	* Send LSP request (async, returns request_id)
 	*/
 	sendLspRequest(language: string, method: string, params: Record<string, unknown> | null): Promise<unknown>;
@@ -49,6 +49,11 @@ interface SearchResult {
   // can't be edited, so the grep position stays valid as a fallback).
   markerKey?: string;
   markerBufferId?: number;
+  // Structural mode only: the matched source text, and the rewrite
+  // template expanded with this match's captures. Replacing writes
+  // `replacement` instead of `panel.replaceText`.
+  matchText?: string;
+  replacement?: string;
 }
 
 interface FileGroup {
@@ -82,6 +87,11 @@ interface PanelState {
   caseSensitive: boolean;
   useRegex: boolean;
   wholeWords: boolean;
+  // Structural mode: the search field holds a tree-sitter pattern with
+  // `$NAME` metavariables, matched in files of `sourceLanguage` (the
+  // source buffer's language); the replace field is its rewrite.
+  structural: boolean;
+  sourceLanguage: string;
   // Scope (§1): when false, results are restricted to the source buffer.
   // `sourceBufferPath` is the absolute path of the buffer that was
   // active when the panel opened; `sourceBufferRelPath` is the
@@ -313,6 +323,7 @@ const modeBindings: [string, string][] = [
   ["M-c", "search_replace_toggle_case"],
   ["M-r", "search_replace_toggle_regex"],
   ["M-w", "search_replace_toggle_whole_word"],
+  ["M-t", "search_replace_toggle_structural"],
   ["M-Return", "search_replace_replace_all"],
   ["S-Return", "search_replace_replace_scoped"],
   // Match navigation (issue #2434). Also bound in keymaps/default.json
@@ -487,20 +498,21 @@ function getViewportHeight(): number {
 // Panel content builder — compact two-line control bar + match tree
 // =============================================================================
 
-// Build the typed Row spec for the options line (3 toggles + Replace
+// Build the typed Row spec for the options line (5 toggles + Replace
 // All button). Was previously hand-built into entries with manual
 // byte-offset overlay arithmetic (see git history pre-widget); now
 // dispatched through the host's Toggle/Button widgets so styling,
 // theme keys, and focus affordance match every other plugin.
 function buildOptionsRowSpec(): WidgetSpec {
   if (!panel) return col();
-  const { focusPanel, optionIndex, caseSensitive, useRegex, wholeWords, allFiles } = panel;
+  const { focusPanel, optionIndex, caseSensitive, useRegex, wholeWords, structural, allFiles } = panel;
   const W = Math.max(MIN_WIDTH, panel.viewportWidth - 2);
   const oFocus = focusPanel === "options";
 
   const caseLabel = editor.t("panel.case_toggle");
   const regexLabel = editor.t("panel.regex_toggle");
   const wholeLabel = editor.t("panel.whole_toggle");
+  const structuralLabel = editor.t("panel.structural_toggle");
   const allFilesLabel = editor.t("panel.all_files_toggle");
   // Replace All button label tracks scope (§1):
   //   * allFiles=true  → "Replace All (Alt+Ret)"
@@ -524,6 +536,8 @@ function buildOptionsRowSpec(): WidgetSpec {
     toggle(useRegex, regexLabel, { key: "regex" }),
     spacer(2),
     toggle(wholeWords, wholeLabel, { key: "whole" }),
+    spacer(2),
+    toggle(structural, structuralLabel, { key: "structural" }),
     flexSpacer(),
     button(replLabel, { intent: "primary", key: "replaceAll" }),
   );
@@ -727,6 +741,32 @@ function renderFlatItemEntry(item: FlatItem, W: number): TextPropertyEntry {
   // over-counting on rare non-BMP filenames just trims a little
   // more of the context, which is fine.
   const maxCtx = innerWidth - location.length - 3;
+
+  // Structural matches preview the matched code and what it becomes,
+  // first line of each.
+  if (result.matchText !== undefined) {
+    const before = result.matchText.split("\n")[0].trim();
+    const segments: StyledSegment[] = [
+      { text: location, style: { fg: C.lineNum } },
+      { text: " - " },
+    ];
+    if (result.replacement !== undefined && panel.replaceText) {
+      const half = Math.max(10, Math.floor((maxCtx - 3) / 2));
+      const after = result.replacement.split("\n")[0].trim();
+      segments.push(
+        { text: truncate(before, half), style: { fg: C.matchFg, bg: C.matchBg } },
+        { text: " → " },
+        { text: truncate(after, half), style: { fg: C.statusOk } },
+      );
+    } else {
+      segments.push({ text: truncate(before, Math.max(10, maxCtx)), style: { fg: C.matchFg, bg: C.matchBg } });
+    }
+    return styledRow(segments, {
+      padToChars: innerWidth,
+      properties: { type: "match-row", fileIndex: item.fileIndex, matchIndex: item.matchIndex },
+    });
+  }
+
   const displayCtx = truncate(context, Math.max(10, maxCtx));
 
   // Pattern-match highlights inside the context substring. Emitted
@@ -1160,6 +1200,8 @@ async function performSearch(pattern: string, silent?: boolean): Promise<SearchR
     activeSearchHandle = null;
   }
 
+  if (panel.structural) return performStructuralSearch(pattern, generation, silent);
+
   try {
     const fixedString = !panel.useRegex;
     const allResults: SearchResult[] = [];
@@ -1221,16 +1263,7 @@ async function performSearch(pattern: string, silent?: boolean): Promise<SearchR
       const deltaItems: FlatItem[] = [];
       const newExpandedKeys: string[] = []; // file rows added this batch
       for (const m of chunk) {
-        // §1 scope filter: when scope is "current file only", drop
-        // matches from any other source. Done client-side because the
-        // host grep API is project-wide. A named buffer matches by path;
-        // an unnamed/unsaved buffer (empty sourceBufferPath) matches by
-        // buffer id instead — the host tags its in-memory matches with it.
-        if (!panel.allFiles) {
-          const sameFile = !!panel.sourceBufferPath && m.file === panel.sourceBufferPath;
-          const sameBuffer = !!panel.sourceBufferId && m.bufferId === panel.sourceBufferId;
-          if (!sameFile && !sameBuffer) continue;
-        }
+        if (!inScope(m)) continue;
         const result: SearchResult = { match: m, selected: true };
         // Anchor the match to its buffer if the file is already open, so
         // edits made while stepping keep it in sync (#2583). Files opened
@@ -1373,24 +1406,101 @@ async function performSearch(pattern: string, silent?: boolean): Promise<SearchR
 
     panel.truncated = truncated;
 
+    if (!silent) reportSearchStatus(pattern, allResults.length);
+    return allResults;
+  } catch (e) {
     if (!silent) {
-      if (allResults.length === 0) {
-        editor.setStatus(editor.t("status.no_matches", { pattern }));
-      } else if (panel.truncated) {
-        editor.setStatus(editor.t("status.found_matches", { count: String(allResults.length) }) + " " + editor.t("panel.limited"));
-      } else {
-        editor.setStatus(editor.t("status.found_matches", { count: String(allResults.length) }));
+      editor.setStatus(editor.t("status.search_error", { error: String(e) }));
+    }
+    return [];
+  }
+}
+
+/**
+ * Structural counterpart of `performSearch`. The host parses every file
+ * of the source buffer's language and returns all matches at once, each
+ * with its rewrite already expanded, so there is nothing to stream.
+ */
+async function performStructuralSearch(
+  pattern: string,
+  generation: number,
+  silent?: boolean,
+): Promise<SearchResult[]> {
+  if (!panel) return [];
+  try {
+    const matches = await editor.structuralSearch(
+      pattern,
+      panel.sourceLanguage,
+      panel.replaceText,
+      panel.fileGlob,
+      MAX_RESULTS,
+    );
+    if (generation !== currentSearchGeneration || !panel) return [];
+
+    const results: SearchResult[] = [];
+    const groupIndexByPath = new Map<string, number>();
+    for (const m of matches) {
+      if (!inScope(m)) continue;
+      const result: SearchResult = {
+        match: m,
+        selected: true,
+        matchText: m.text,
+        replacement: m.replacement ?? undefined,
+      };
+      ensureMatchMarker(result);
+      results.push(result);
+      let fileIdx = groupIndexByPath.get(m.file);
+      if (fileIdx === undefined) {
+        fileIdx = panel.fileGroups.length;
+        groupIndexByPath.set(m.file, fileIdx);
+        panel.fileGroups.push({
+          relPath: getRelativePath(m.file),
+          absPath: m.file,
+          expanded: true,
+          matches: [],
+        });
+        panel.expandedFileKeys.add(`file:${fileIdx}`);
+        panel.knownFileKeys.add(`file:${fileIdx}`);
       }
+      panel.fileGroups[fileIdx].matches.push(result);
     }
-    return allResults;
+    panel.searchResults = results;
+    panel.truncated = matches.length >= MAX_RESULTS;
+    if (!silent) reportSearchStatus(pattern, results.length);
+    return results;
   } catch (e) {
-    if (!silent) {
+    // A newer search supersedes this one host-side too; its rejection
+    // is expected and not worth reporting.
+    if (generation === currentSearchGeneration && !silent) {
       editor.setStatus(editor.t("status.search_error", { error: String(e) }));
     }
     return [];
   }
 }
 
+// §1 scope filter: when scope is "current file only", drop matches from
+// any other source. Done client-side because the host search APIs are
+// project-wide. A named buffer matches by path; an unnamed/unsaved buffer
+// (empty sourceBufferPath) matches by buffer id instead — the host tags
+// its in-memory matches with it.
+function inScope(m: GrepMatch): boolean {
+  if (!panel || panel.allFiles) return true;
+  const sameFile = !!panel.sourceBufferPath && m.file === panel.sourceBufferPath;
+  const sameBuffer = !!panel.sourceBufferId && m.bufferId === panel.sourceBufferId;
+  return sameFile || sameBuffer;
+}
+
+function reportSearchStatus(pattern: string, count: number): void {
+  if (!panel) return;
+  if (count === 0) {
+    editor.setStatus(editor.t("status.no_matches", { pattern }));
+  } else if (panel.truncated) {
+    editor.setStatus(editor.t("status.found_matches", { count: String(count) }) + " " + editor.t("panel.limited"));
+  } else {
+    editor.setStatus(editor.t("status.found_matches", { count: String(count) }));
+  }
+}
+
 // =============================================================================
 // Panel lifecycle
 // =============================================================================
@@ -1400,10 +1510,12 @@ async function openPanel(opts?: { allFiles?: boolean }): Promise<void> {
   let prefill = "";
   let sourceBufferPath = "";
   let sourceBufferId = 0;
+  let sourceLanguage = "";
   try {
     const activeId = editor.getActiveBufferId();
     sourceBufferId = activeId;
     sourceBufferPath = editor.getBufferPath(activeId) || "";
+    sourceLanguage = editor.getBufferInfo(activeId)?.language ?? "";
     const cursor = editor.getPrimaryCursor();
     if (cursor && cursor.selection) {
       const start = Math.min(cursor.selection.start, cursor.selection.end);
@@ -1430,6 +1542,7 @@ async function openPanel(opts?: { allFiles?: boolean }): Promise<void> {
     panel.sourceBufferPath = sourceBufferPath;
     panel.sourceBufferRelPath = sourceBufferRelPath;
     panel.sourceBufferId = sourceBufferId;
+    panel.sourceLanguage = sourceLanguage;
     updatePanelContent();
     if (panel.searchPattern) rerunSearchDebounced();
     return;
@@ -1453,6 +1566,8 @@ async function openPanel(opts?: { allFiles?: boolean }): Promise<void> {
     caseSensitive: false,
     useRegex: false,
     wholeWords: false,
+    structural: false,
+    sourceLanguage,
     allFiles,
     sourceBufferPath,
     sourceBufferRelPath,
@@ -1521,17 +1636,23 @@ async function executeReplacements(results?: SearchResult[]): Promise<string> {
   // "[No Name]" label) still resolves to the right buffer rather than
   // colliding with another unnamed buffer's matches. On-disk files
   // (bufferId 0) key by path and are opened/saved by the host as before.
-  type Group = { filePath: string; bufferId: number; matches: Array<[number, number]> };
+  type Group = {
+    filePath: string;
+    bufferId: number;
+    matches: Array<[number, number]>;
+    replacements: string[];
+  };
   const groups: Map<string, Group> = new Map();
   for (const result of toReplace) {
     const bufferId = result.match.bufferId || 0;
     const key = bufferId > 0 ? `buf:${bufferId}` : result.match.file;
     let group = groups.get(key);
     if (!group) {
-      group = { filePath: result.match.file, bufferId, matches: [] };
+      group = { filePath: result.match.file, bufferId, matches: [], replacements: [] };
       groups.set(key, group);
     }
     group.matches.push([result.match.byteOffset, result.match.length]);
+    group.replacements.push(result.replacement ?? panel.replaceText);
   }
 
   let filesModified = 0;
@@ -1542,12 +1663,19 @@ async function executeReplacements(results?: SearchResult[]): Promise<string> {
   groups.forEach((g) => groupList.push(g));
   for (const group of groupList) {
     try {
-      const result = await editor.replaceInFile(
-        group.filePath,
-        group.matches,
-        panel.replaceText,
-        group.bufferId
-      );
+      const result = panel.structural
+        ? await editor.replaceMatchesInFile(
+          group.filePath,
+          group.matches,
+          group.replacements,
+          group.bufferId
+        )
+        : await editor.replaceInFile(
+          group.filePath,
+          group.matches,
+          panel.replaceText,
+          group.bufferId
+        );
       replacementsCount += result.replacements;
       if (result.replacements > 0) filesModified++;
     } catch (e) {
@@ -1745,7 +1873,7 @@ registerHandler("search_replace_shift_tab", () => dispatch(widgetKey("Shift+Tab"
 // focused). Plugin no longer needs separate file-row expand
 // handling.
 
-// Global option toggles (Alt+C, Alt+R, Alt+W)
+// Global option toggles (Alt+C, Alt+R, Alt+W, Alt+T)
 function search_replace_toggle_case(): void {
   if (!panel) return;
   panel.caseSensitive = !panel.caseSensitive;
@@ -1770,6 +1898,14 @@ function search_replace_toggle_whole_word(): void {
 }
 registerHandler("search_replace_toggle_whole_word", search_replace_toggle_whole_word);
 
+function search_replace_toggle_structural(): void {
+  if (!panel) return;
+  panel.structural = !panel.structural;
+  updatePanelContent();
+  rerunSearchDebounced();
+}
+registerHandler("search_replace_toggle_structural", search_replace_toggle_structural);
+
 function search_replace_replace_all(): void {
   doReplaceAll();
 }
@@ -2280,7 +2416,7 @@ editor.on("after_file_open", (args) => {
 // mounted widget panel and fires `widget_event` for clicks that land
 // on a Toggle or Button. We dispatch on `widget_key` (set in
 // `buildOptionsRowSpec`); the existing keyboard-driven path
-// (Alt+C / Alt+R / Alt+W / Alt+Ret) still works unchanged.
+// (Alt+C / Alt+R / Alt+W / Alt+T / Alt+Ret) still works unchanged.
 //
 // Mouse-click on a toggle should also focus it, so the user's next
 // Tab cycle starts from the clicked control. We do that by syncing
@@ -2338,8 +2474,12 @@ editor.on("widget_event", (args) => {
       // the right place.
       panel.cursorPos = byteToCharOffset(payload.value, cursorByte);
     } else if (args.widget_key === "replaceField") {
+      const changed = panel.replaceText !== payload.value;
       panel.replaceText = payload.value;
       panel.cursorPos = byteToCharOffset(payload.value, cursorByte);
+      // Structural previews show each match's expanded rewrite, which
+      // the host computes during the search.
+      if (changed && panel.structural && panel.searchPattern) rerunSearchDebounced();
     } else if (args.widget_key === "fileGlobField") {
       if (panel.fileGlob !== payload.value) {
         panel.fileGlob = payload.value;
@@ -2442,6 +2582,11 @@ editor.on("widget_event", (args) => {
         panel.widgetPanel?.setChecked("whole", newChecked);
         rerunSearchDebounced();
         break;
+      case "structural":
+        panel.structural = newChecked;
+        panel.widgetPanel?.setChecked("structural", newChecked);
+        rerunSearchDebounced();
+        break;
       case "matchTree": {
         // The `[v]`/`[ ]` glyph on a tree row was clicked. Plugin
         // owns the source-of-truth (`result.selected`) — flip it
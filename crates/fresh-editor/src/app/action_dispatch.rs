@@ -8,6 +8,7 @@
 //! the split from `app/input.rs` keeps *deciding what a key means*
 //! separate from *doing what the action says*.
 
+use super::dap_actions::DebugStep;
 use super::*;
 use anyhow::Result as AnyhowResult;
 use rust_i18n::t;
@@ -99,6 +100,16 @@ impl Editor {
             }
         }
 
+        // Tab / Shift+Tab step through the placeholders of an inserted
+        // snippet while its session lasts.
+        if matches!(action, Action::InsertTab | Action::DedentSelection)
+            && !self.is_prompting()
+            && self.active_window().snippet_session.is_some()
+            && self.navigate_snippet_tabstop(matches!(action, Action::InsertTab))
+        {
+            return Ok(());
+        }
+
         match action {
             Action::Quit => self.quit(),
             Action::ForceQuit => {
@@ -200,6 +211,9 @@ impl Editor {
                 );
                 self.init_folder_open_state();
             }
+            Action::GotoLine if self.active_hex_view().is_some() => {
+                self.start_hex_goto_offset_prompt();
+            }
             Action::GotoLine => {
                 let has_line_index = self.active_buffer_has_line_index();
                 if has_line_index {
@@ -454,6 +468,20 @@ impl Editor {
                 }
                 self.paste()
             }
+            Action::PasteFromHistory => {
+                if self.active_window().is_editing_disabled() {
+                    self.set_status_message(t!("buffer.editing_disabled").to_string());
+                    return Ok(());
+                }
+                self.paste_from_history()
+            }
+            Action::YankPop => {
+                if self.active_window().is_editing_disabled() {
+                    self.set_status_message(t!("buffer.editing_disabled").to_string());
+                    return Ok(());
+                }
+                self.yank_pop()
+            }
             Action::SelectAll => {
                 // Focused widget Text wins over the buffer's
                 // select-all. SelectAll on the buffer is then
@@ -466,6 +494,7 @@ impl Editor {
                 }
                 self.apply_action_as_events(Action::SelectAll)?;
             }
+            Action::ExpandSelection => self.expand_selection()?,
             Action::YankWordForward => self.yank_word_forward(),
             Action::YankWordBackward => self.yank_word_backward(),
             Action::YankToLineEnd => self.yank_to_line_end(),
@@ -477,6 +506,9 @@ impl Editor {
             Action::Redo => {
                 self.handle_redo();
             }
+            Action::ShowUndoTree => self.show_undo_tree(),
+            Action::UndoTreeJump => self.undo_tree_jump(),
+            Action::UndoTreePreview => self.undo_tree_preview(),
             Action::ShowHelp => {
                 self.ensure_help_panel_mode_registered();
                 self.active_window_mut().open_help_manual();
@@ -491,6 +523,14 @@ impl Editor {
             Action::ShowStatusLog => {
                 self.open_status_log();
             }
+            Action::ShowPluginProfiler => {
+                #[cfg(feature = "plugins")]
+                self.open_plugin_profiler();
+            }
+            Action::ShowPluginPermissions => {
+                #[cfg(feature = "plugins")]
+                self.show_plugin_permissions();
+            }
             Action::ShowLspStatus => {
                 self.show_lsp_status_popup();
             }
@@ -523,6 +563,12 @@ impl Editor {
                 }
                 self.start_quick_open_with_prefix("#");
             }
+            Action::QuickOpenSymbols => {
+                if self.close_quick_open_if_open() {
+                    return Ok(());
+                }
+                self.start_quick_open_with_prefix("@");
+            }
             Action::QuickOpenFiles => {
                 if self.close_quick_open_if_open() {
                     return Ok(());
@@ -802,6 +848,9 @@ impl Editor {
             Action::TogglePageView => {
                 self.active_window_mut().handle_toggle_page_view();
             }
+            Action::ToggleHexView => {
+                self.toggle_hex_view();
+            }
             Action::SetPageWidth => {
                 let active_split = self
                     .windows
@@ -889,9 +938,22 @@ impl Editor {
             Action::LspToggleForBuffer => {
                 self.handle_lsp_toggle_for_buffer();
             }
+            Action::ListDocumentLinks => self.list_document_links(),
             Action::ToggleInlayHints => {
                 self.toggle_inlay_hints();
             }
+            Action::DebugStart => self.debug_start(),
+            Action::DebugStop => self.debug_stop(),
+            Action::DebugContinue => self.debug_continue(),
+            Action::DebugStepOver => self.debug_step(DebugStep::Over),
+            Action::DebugStepInto => self.debug_step(DebugStep::Into),
+            Action::DebugStepOut => self.debug_step(DebugStep::Out),
+            Action::DebugPause => self.debug_pause(),
+            Action::DebugToggleBreakpoint => self.debug_toggle_breakpoint(),
+            Action::DebugShowPanel => self.debug_show_panel(),
+            Action::DebugSelectFrame => self.debug_select_frame(),
+            Action::RunTask => self.run_task(),
+            Action::RerunLastTask => self.rerun_last_task(),
             Action::DumpConfig => {
                 self.dump_config();
             }
@@ -1053,6 +1115,8 @@ impl Editor {
             Action::TogglePromptLine => self.toggle_prompt_line(),
             Action::ToggleVerticalScrollbar => self.toggle_vertical_scrollbar(),
             Action::ToggleHorizontalScrollbar => self.toggle_horizontal_scrollbar(),
+            Action::ToggleMinimap => self.toggle_minimap(),
+            Action::ToggleStickyScroll => self.toggle_sticky_scroll(),
             Action::ToggleLineNumbers => self.toggle_line_numbers(),
             Action::ToggleLineNumbersCurrentBuffer => self.toggle_line_numbers_current_buffer(),
             Action::ToggleLineWrapCurrentBuffer => self.toggle_line_wrap_current_buffer(),
@@ -1188,6 +1252,7 @@ impl Editor {
                 }
             }
             Action::ResetBufferSettings => self.reset_buffer_settings(),
+            Action::ShowBufferSettings => self.show_buffer_settings(),
             Action::FocusFileExplorer => self.focus_file_explorer(),
             Action::FocusEditor => self.active_window_mut().focus_editor(),
             Action::ToggleDockFocus => {
@@ -1245,6 +1310,7 @@ impl Editor {
             }
             Action::FileExplorerSelectAll => self.active_window_mut().file_explorer_select_all(),
             Action::RemoveSecondaryCursors => {
+                self.end_snippet_session();
                 // Convert action to events and apply them
                 if let Some(events) = self
                     .active_window_mut()
@@ -1847,6 +1913,14 @@ impl Editor {
             Action::CompletionDismiss => {
                 self.handle_popup_cancel();
             }
+            Action::InsertNewline if !self.is_prompting() => {
+                self.end_linked_editing();
+                let version = self.active_state().buffer.version();
+                self.apply_action_as_events(action)?;
+                if self.active_state().buffer.version() != version {
+                    self.maybe_request_on_type_formatting('\n');
+                }
+            }
             Action::InsertChar(c) => {
                 if self.is_prompting() {
                     return self.handle_insert_char_prompt(c);
@@ -1905,8 +1979,9 @@ impl Editor {
     }
 
     /// If the Quick Open prompt is currently open, cancel it and return `true`.
-    /// All four Quick Open variants (CommandPalette, QuickOpen, QuickOpenBuffers,
-    /// QuickOpenFiles) toggle off when invoked while the picker is already visible.
+    /// All Quick Open variants (CommandPalette, QuickOpen, QuickOpenBuffers,
+    /// QuickOpenSymbols, QuickOpenFiles) toggle off when invoked while the
+    /// picker is already visible.
     fn close_quick_open_if_open(&mut self) -> bool {
         if let Some(prompt) = &self.active_window_mut().prompt {
             if prompt.prompt_type == PromptType::QuickOpen {
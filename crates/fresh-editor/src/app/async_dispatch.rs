@@ -137,6 +137,11 @@ impl Editor {
         // reading them on the editor loop.
         self.drive_pending_content_loads();
 
+        // A package plugin's capability request that arrived while another
+        // prompt was open is asked about as soon as that prompt closes.
+        #[cfg(feature = "plugins")]
+        self.prompt_pending_plugin_capability();
+
         let Some(bridge) = &self.async_bridge else {
             return false;
         };
@@ -285,6 +290,15 @@ impl Editor {
                         }
                     }
                 }
+                AsyncMessage::LspOnTypeFormatting { request_id, edits } => {
+                    self.handle_lsp_on_type_formatting(request_id, edits);
+                }
+                AsyncMessage::LspSelectionRanges { request_id, ranges } => {
+                    self.handle_lsp_selection_ranges(request_id, ranges);
+                }
+                AsyncMessage::LspLinkedEditingRanges { request_id, ranges } => {
+                    self.handle_lsp_linked_editing_ranges(request_id, ranges);
+                }
                 AsyncMessage::LspPrepareRename {
                     request_id: _,
                     result,
@@ -307,6 +321,14 @@ impl Editor {
                         unchanged,
                     );
                 }
+                AsyncMessage::LspWorkspaceDiagnostics {
+                    request_id,
+                    server_name,
+                    reports,
+                    done,
+                } => {
+                    self.handle_lsp_workspace_diagnostics(request_id, server_name, reports, done);
+                }
                 AsyncMessage::LspInlayHints {
                     request_id,
                     uri,
@@ -321,6 +343,36 @@ impl Editor {
                 } => {
                     self.handle_lsp_folding_ranges(request_id, uri, ranges);
                 }
+                AsyncMessage::LspDocumentSymbols {
+                    request_id,
+                    symbols,
+                    ..
+                } => {
+                    self.handle_lsp_document_symbols(request_id, symbols);
+                }
+                AsyncMessage::LspDocumentLinks {
+                    request_id,
+                    uri,
+                    links,
+                } => {
+                    self.handle_lsp_document_links(request_id, uri, links);
+                }
+                AsyncMessage::LspWorkspaceSymbols {
+                    request_id,
+                    symbols,
+                } => {
+                    self.handle_lsp_workspace_symbols(request_id, symbols);
+                }
+                AsyncMessage::LspCodeLenses {
+                    request_id,
+                    uri,
+                    lenses,
+                } => {
+                    self.handle_lsp_code_lenses(request_id, uri, lenses);
+                }
+                AsyncMessage::LspCodeLensResolved { request_id, lens } => {
+                    self.handle_lsp_code_lens_resolved(request_id, lens);
+                }
                 AsyncMessage::LspSemanticTokens {
                     request_id,
                     uri,
@@ -337,6 +389,9 @@ impl Editor {
                 AsyncMessage::LspInlayHintRefresh { language } => {
                     self.handle_lsp_inlay_hint_refresh(language);
                 }
+                AsyncMessage::LspCodeLensRefresh { language } => {
+                    self.handle_lsp_code_lens_refresh(language);
+                }
                 AsyncMessage::LspSemanticTokensRefresh { language } => {
                     self.handle_lsp_semantic_tokens_refresh(language);
                 }
@@ -353,6 +408,62 @@ impl Editor {
                         registrations,
                     );
                 }
+                AsyncMessage::DapInitialized { session_id } => {
+                    self.handle_dap_initialized(session_id);
+                }
+                AsyncMessage::DapStopped {
+                    session_id,
+                    thread_id,
+                    reason,
+                    description,
+                } => {
+                    self.handle_dap_stopped(session_id, thread_id, reason, description);
+                }
+                AsyncMessage::DapContinued { session_id } => {
+                    self.handle_dap_continued(session_id);
+                }
+                AsyncMessage::DapStackTrace {
+                    session_id,
+                    thread_id,
+                    frames,
+                    frame_id,
+                    scopes,
+                } => {
+                    self.handle_dap_stack_trace(session_id, thread_id, frames, frame_id, scopes);
+                }
+                AsyncMessage::DapScopes {
+                    session_id,
+                    frame_id,
+                    scopes,
+                } => {
+                    self.handle_dap_scopes(session_id, frame_id, scopes);
+                }
+                AsyncMessage::DapOutput {
+                    session_id,
+                    category,
+                    output,
+                } => {
+                    self.handle_dap_output(session_id, category, output);
+                }
+                AsyncMessage::DapExited {
+                    session_id,
+                    exit_code,
+                } => {
+                    self.handle_dap_exited(session_id, exit_code);
+                }
+                AsyncMessage::DapTerminated { session_id } => {
+                    self.handle_dap_terminated(session_id);
+                }
+                AsyncMessage::DapError { session_id, error } => {
+                    self.handle_dap_error(session_id, error);
+                }
+                AsyncMessage::TaskFinished {
+                    run_id,
+                    output,
+                    exit_code,
+                } => {
+                    self.handle_task_finished(run_id, output, Some(exit_code));
+                }
                 AsyncMessage::FileChanged { path } => {
                     self.handle_async_file_changed(path);
                 }
@@ -436,6 +547,8 @@ impl Editor {
                         self.self_update_terminal = None;
                     }
                     self.handle_terminal_exited(terminal, exit_code);
+                    // A task's terminal: its buffer now holds the full output.
+                    self.handle_task_terminal_exited(terminal.terminal, exit_code);
                 }
 
                 AsyncMessage::LspServerRequest {
@@ -591,7 +704,12 @@ impl Editor {
             let _ = checker.poll_result();
         }
 
-        // Poll for file changes (auto-revert) and file tree changes
+        // Act on settled file-watch events, then poll whatever isn't
+        // watched (auto-revert and file tree changes)
+        let watch_changes = {
+            let _s = tracing::info_span!("process_file_watch_events").entered();
+            self.process_file_watch_events()
+        };
         let file_changes = {
             let _s = tracing::info_span!("poll_file_changes").entered();
             self.poll_file_changes()
@@ -610,6 +728,7 @@ impl Editor {
         needs_render
             || processed_any_commands
             || plugin_render
+            || watch_changes
             || file_changes
             || tree_changes
             || backlogged
@@ -658,7 +777,11 @@ impl Editor {
         // through `handle_for_feature_mut`, so servers that
         // didn't advertise the capability are skipped.
         self.request_inlay_hints_for_language(&language);
+        self.request_code_lenses_for_language(&language);
+        self.request_document_symbols_for_language(&language);
+        self.request_document_links_for_language(&language);
         self.pull_diagnostics_for_language(&language);
+        self.request_workspace_diagnostics(&language);
     }
 
     /// Handle an LSP server crash/spawn failure: surface it, fire the
@@ -937,13 +1060,20 @@ impl Editor {
         );
     }
 
-    /// Forward a watched-path filesystem event to the `path_changed` hook.
+    /// Forward a watched-path filesystem event to the `path_changed` hook,
+    /// or — for the editor's own watches — queue it for auto-revert and
+    /// file explorer refresh.
     fn handle_path_changed(
         &mut self,
         handle: u64,
         path: std::path::PathBuf,
         kind: crate::services::async_bridge::PathChangeKind,
     ) {
+        if self.file_watcher_manager.is_editor_handle(handle) {
+            let now = self.time_source.now();
+            self.watch_debouncer.record(path, now);
+            return;
+        }
         self.path_changes_for_test
             .push((handle, path.clone(), kind.as_str()));
         self.plugin_manager.read().unwrap().run_hook(
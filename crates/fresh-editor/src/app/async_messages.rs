@@ -118,7 +118,7 @@ impl Editor {
     /// Anchor freshly received diagnostics to the open buffer for `uri` (if
     /// any), stamping each with the buffer's current version so `CoordMap` can
     /// carry it forward across later edits (#2602).
-    fn anchor_diagnostics(
+    pub(super) fn anchor_diagnostics(
         &self,
         uri: &str,
         diagnostics: Vec<Diagnostic>,
@@ -136,7 +136,7 @@ impl Editor {
 
     /// Materialise the merged push + pull view (positions mapped to the buffer's
     /// current version) and rebuild the overlays from it.
-    fn merge_and_apply_diagnostics(&mut self, uri: &str) {
+    pub(super) fn merge_and_apply_diagnostics(&mut self, uri: &str) {
         let merged = self.active_window_mut().recompute_merged_diagnostics(uri);
 
         if let Some((buffer_id, updated)) = self.apply_diagnostics_to_buffer(uri, &merged) {
@@ -861,6 +861,11 @@ impl Editor {
         // Re-pull diagnostics for all open buffers — the initial pull likely
         // returned empty results because the server hadn't loaded the project yet
         self.pull_diagnostics_for_language(&language);
+        self.request_workspace_diagnostics(&language);
+
+        // Lenses such as reference counts are only accurate once the whole
+        // project is loaded.
+        self.request_code_lenses_for_language(&language);
 
         // Skip inlay hints if disabled
         if !self.config.editor.enable_inlay_hints {
@@ -937,13 +942,15 @@ impl Editor {
     }
 
     /// Handle workspace/diagnostic/refresh request from the LSP server.
-    /// Re-pulls diagnostics for all open documents of the given language.
+    /// Re-pulls diagnostics for all open documents of the given language,
+    /// and for the whole workspace where the server supports it.
     pub(super) fn handle_lsp_diagnostic_refresh(&mut self, language: String) {
         tracing::info!(
             "LSP ({}) diagnostic refresh requested, re-pulling diagnostics",
             language
         );
         self.pull_diagnostics_for_language(&language);
+        self.request_workspace_diagnostics(&language);
     }
 
     pub(super) fn handle_lsp_inlay_hint_refresh(&mut self, language: String) {
@@ -1000,7 +1007,10 @@ impl Editor {
             self.request_semantic_tokens_for_language(&language);
             self.request_folding_ranges_for_language(&language);
             self.request_inlay_hints_for_language(&language);
+            self.request_code_lenses_for_language(&language);
+            self.request_document_links_for_language(&language);
             self.pull_diagnostics_for_language(&language);
+            self.request_workspace_diagnostics(&language);
         }
     }
 
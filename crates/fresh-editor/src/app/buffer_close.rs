@@ -105,6 +105,10 @@ impl Editor {
             });
         }
 
+        // Keep the undo history for the next time this file is opened
+        // (`editor.persistent_undo`).
+        self.active_window_mut().persist_undo_history(id);
+
         // Delete recovery data for explicitly closed buffers (including unnamed)
         if let Err(e) = self.delete_buffer_recovery(id) {
             tracing::debug!("Failed to delete buffer recovery on close: {}", e);
@@ -452,8 +456,8 @@ impl Editor {
 
     /// Remove every trace of a now-closed buffer from the active window's
     /// per-buffer maps: the buffer registry, cross-window attachments, event
-    /// logs, semantic-token bookkeeping, the panel-id mapping, and each
-    /// split's open-buffers / focus-history lists.
+    /// logs, undo-tree browser links, semantic-token bookkeeping, the
+    /// panel-id mapping, and each split's open-buffers / focus-history lists.
     fn purge_buffer_state(&mut self, id: BufferId) {
         self.windows
             .get_mut(&self.active_window)
@@ -462,6 +466,11 @@ impl Editor {
             .remove(&id);
         self.detach_buffer_from_all_windows(id);
         self.active_window_mut().event_logs.remove(&id);
+        // Closing either end of a browser link ends it: a browser whose
+        // source is gone has no history left to list.
+        self.active_window_mut()
+            .undo_tree_sources
+            .retain(|tree, source| *tree != id && *source != id);
         self.active_window_mut().seen_byte_ranges.remove(&id);
         self.active_window_mut().buffer_metadata.remove(&id);
         self.active_window_mut().status_bar_values.remove(&id);
@@ -492,6 +501,10 @@ impl Editor {
         self.active_window_mut()
             .semantic_tokens_full_debounce
             .remove(&id);
+        self.active_window_mut().clear_code_lenses_for_buffer(id);
+        self.active_window_mut()
+            .clear_document_symbols_for_buffer(id);
+        self.active_window_mut().clear_document_links_for_buffer(id);
 
         // Remove buffer from the active window's panel_ids mapping
         // if it was a panel buffer. Prevents stale entries when the
@@ -5,20 +5,14 @@
 //! The group appears as one entry in the tab bar and buffer list.
 
 use crate::app::types::BufferGroupId;
-#[cfg(feature = "plugins")]
 use crate::app::types::{BufferGroup, GroupLayoutNode};
-#[cfg(feature = "plugins")]
 use crate::model::event::SplitDirection;
 use crate::model::event::{BufferId, LeafId};
-#[cfg(feature = "plugins")]
 use crate::view::split::SplitViewState;
-#[cfg(feature = "plugins")]
 use fresh_core::api::BufferGroupResult;
-#[cfg(feature = "plugins")]
 use std::collections::HashMap;
 
 /// Layout description deserialized from plugin JSON.
-#[cfg(feature = "plugins")]
 #[derive(Debug, serde::Deserialize)]
 #[serde(tag = "type")]
 enum LayoutDesc {
@@ -56,7 +50,6 @@ impl super::Editor {
     /// entry to the current split's tab bar. The main split tree is NOT
     /// modified — the group's subtree is dispatched to at render time when
     /// the current split's active target is this group.
-    #[cfg(feature = "plugins")]
     pub(super) fn create_buffer_group(
         &mut self,
         name: String,
@@ -223,7 +216,6 @@ impl super::Editor {
 
     /// Build a SplitNode tree directly from a GroupLayoutNode.
     /// Populates panel_splits with leaf_id for each panel.
-    #[cfg(feature = "plugins")]
     fn build_split_tree(
         &mut self,
         node: &GroupLayoutNode,
@@ -292,7 +284,6 @@ impl super::Editor {
     }
 
     /// Build a GroupLayoutNode from a LayoutDesc, creating buffers for each leaf.
-    #[cfg(feature = "plugins")]
     fn build_group_layout(
         &mut self,
         desc: &LayoutDesc,
@@ -381,7 +372,6 @@ impl super::Editor {
     }
 
     /// Set content on a panel within a buffer group.
-    #[cfg(feature = "plugins")]
     pub(super) fn set_panel_content(
         &mut self,
         group_id: usize,
@@ -875,7 +865,6 @@ impl crate::app::window::Window {
 }
 
 /// Get the fixed height of a layout node if it's a Fixed leaf.
-#[cfg(feature = "plugins")]
 fn fixed_height_of(node: &GroupLayoutNode) -> Option<u16> {
     match node {
         GroupLayoutNode::Fixed { height, .. } => Some(*height),
@@ -887,7 +876,6 @@ fn fixed_height_of(node: &GroupLayoutNode) -> Option<u16> {
 // callers reach it via `self.active_window().is_non_scrollable_buffer(...)`.
 
 /// Find the first scrollable leaf in the layout tree.
-#[cfg(feature = "plugins")]
 fn find_first_scrollable_name(node: &GroupLayoutNode) -> Option<String> {
     match node {
         GroupLayoutNode::Scrollable { id, .. } => Some(id.clone()),
@@ -899,7 +887,6 @@ fn find_first_scrollable_name(node: &GroupLayoutNode) -> Option<String> {
 }
 
 /// Find the first scrollable leaf's LeafId from the panel_splits map.
-#[cfg(feature = "plugins")]
 fn find_first_scrollable_leaf(
     node: &GroupLayoutNode,
     panel_splits: &HashMap<String, LeafId>,
@@ -71,11 +71,33 @@ impl crate::app::window::Window {
             return self.terminal_grid_cols(buffer_id);
         }
         match self.buffers.get(&buffer_id) {
-            Some(state) => buffer_config_resolve::wrap_column(&state.language, self.config()),
+            // An `.editorconfig` `max_line_length` is the project's own limit.
+            Some(state) => state
+                .buffer_settings
+                .editorconfig
+                .max_line_length
+                .or_else(|| buffer_config_resolve::wrap_column(&state.language, self.config())),
             None => self.config().editor.wrap_column,
         }
     }
 
+    /// Resolve the rulers for a buffer: the configured ones, plus one at the
+    /// `.editorconfig` `max_line_length` when the file has one.
+    pub(crate) fn resolve_rulers_for_buffer(&self, buffer_id: BufferId) -> Vec<usize> {
+        let mut rulers = self.config().editor.rulers.clone();
+        let max_line_length = self
+            .buffers
+            .get(&buffer_id)
+            .and_then(|state| state.buffer_settings.editorconfig.max_line_length);
+        if let Some(column) = max_line_length {
+            if !rulers.contains(&column) {
+                rulers.push(column);
+                rulers.sort_unstable();
+            }
+        }
+        rulers
+    }
+
     /// Get the preferred split for opening a file.
     /// If the active split has no label, use it (normal case).
     /// Otherwise find an unlabeled leaf so files don't open in labeled splits (e.g., sidebars).
@@ -0,0 +1,158 @@
+//! Per-buffer bookkeeping for LSP requests that cover a whole document —
+//! code lenses, document symbols, document links.
+//!
+//! Each buffer has at most one such request in flight, tagged with the
+//! buffer version it was sent for. A response for an older version is
+//! dropped and a refresh scheduled instead: the buffer moved on while the
+//! server was answering, so the ranges would land in the wrong places.
+//! Edits schedule a refresh that fires once typing pauses.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crate::model::event::BufferId;
+
+/// How long a buffer must stay unedited before it is re-requested.
+const REFRESH_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// A request that was sent: its buffer and the version it describes.
+#[derive(Clone, Copy, Debug)]
+struct SentRequest {
+    buffer_id: BufferId,
+    version: u64,
+}
+
+/// Request tracking for one whole-document LSP feature.
+#[derive(Debug, Default)]
+pub(crate) struct BufferRequests {
+    /// Requests awaiting a response, by request id.
+    pending: HashMap<u64, SentRequest>,
+    /// The request id in flight for each buffer.
+    in_flight: HashMap<BufferId, u64>,
+    /// The buffer version last asked for, whether or not a server took it.
+    requested: HashMap<BufferId, u64>,
+    /// When each edited buffer is due for a refresh.
+    debounce: HashMap<BufferId, Instant>,
+}
+
+impl BufferRequests {
+    /// Re-request `buffer_id` once it has been left alone for a moment.
+    pub(crate) fn schedule_refresh(&mut self, buffer_id: BufferId) {
+        self.debounce
+            .insert(buffer_id, Instant::now() + REFRESH_DEBOUNCE);
+    }
+
+    /// Whether a request for `buffer_id` should go out now: a scheduled
+    /// refresh is due, or — with `first_request` — the buffer was never
+    /// asked for. A due refresh is consumed.
+    pub(crate) fn take_due(&mut self, buffer_id: BufferId, first_request: bool) -> bool {
+        match self.debounce.get(&buffer_id) {
+            Some(ready_at) if Instant::now() < *ready_at => false,
+            Some(_) => {
+                self.debounce.remove(&buffer_id);
+                true
+            }
+            None => first_request && !self.requested.contains_key(&buffer_id),
+        }
+    }
+
+    pub(crate) fn is_in_flight(&self, buffer_id: BufferId) -> bool {
+        self.in_flight.contains_key(&buffer_id)
+    }
+
+    /// Record that `version` of `buffer_id` was asked for, so a buffer no
+    /// server answers for is not retried every frame.
+    pub(crate) fn mark_requested(&mut self, buffer_id: BufferId, version: u64) {
+        self.requested.insert(buffer_id, version);
+    }
+
+    /// Record a request sent to a server.
+    pub(crate) fn sent(&mut self, request_id: u64, buffer_id: BufferId, version: u64) {
+        self.pending
+            .insert(request_id, SentRequest { buffer_id, version });
+        self.in_flight.insert(buffer_id, request_id);
+    }
+
+    /// Settle the response to `request_id`. Returns its buffer and version
+    /// if the response still describes it; `current_version` reports a buffer's
+    /// version, or `None` once it is gone. `feature` names the responses in
+    /// the log.
+    pub(crate) fn settle(
+        &mut self,
+        request_id: u64,
+        feature: &str,
+        current_version: impl FnOnce(BufferId) -> Option<u64>,
+    ) -> Option<(BufferId, u64)> {
+        let Some(request) = self.pending.remove(&request_id) else {
+            tracing::debug!(
+                "Ignoring {} response without pending request (request_id={})",
+                feature,
+                request_id
+            );
+            return None;
+        };
+        self.in_flight.remove(&request.buffer_id);
+        let current = current_version(request.buffer_id)?;
+        if current != request.version {
+            tracing::debug!(
+                "Ignoring stale {} for {:?} (request_id={}, version={}, current={})",
+                feature,
+                request.buffer_id,
+                request_id,
+                request.version,
+                current
+            );
+            self.schedule_refresh(request.buffer_id);
+            return None;
+        }
+        Some((request.buffer_id, request.version))
+    }
+
+    /// Forget everything about `buffer_id`; a late response is ignored.
+    pub(crate) fn clear(&mut self, buffer_id: BufferId) {
+        self.in_flight.remove(&buffer_id);
+        self.requested.remove(&buffer_id);
+        self.debounce.remove(&buffer_id);
+        self.pending.retain(|_, req| req.buffer_id != buffer_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stale_responses_are_dropped_and_rescheduled() {
+        let buffer = BufferId(1);
+        let mut requests = BufferRequests::default();
+        assert!(requests.take_due(buffer, true));
+        assert!(!requests.take_due(buffer, false));
+
+        requests.mark_requested(buffer, 3);
+        requests.sent(7, buffer, 3);
+        assert!(requests.is_in_flight(buffer));
+        assert!(!requests.take_due(buffer, true), "already asked for");
+
+        assert_eq!(requests.settle(7, "test", |_| Some(4)), None);
+        assert!(!requests.is_in_flight(buffer));
+        assert!(requests.debounce.contains_key(&buffer), "refresh scheduled");
+
+        requests.sent(8, buffer, 4);
+        assert_eq!(requests.settle(8, "test", |_| Some(4)), Some((buffer, 4)));
+        assert_eq!(
+            requests.settle(8, "test", |_| Some(4)),
+            None,
+            "settled once"
+        );
+    }
+
+    #[test]
+    fn cleared_buffers_ignore_late_responses() {
+        let buffer = BufferId(1);
+        let mut requests = BufferRequests::default();
+        requests.sent(7, buffer, 3);
+        requests.clear(buffer);
+        assert!(!requests.is_in_flight(buffer));
+        assert_eq!(requests.settle(7, "test", |_| Some(3)), None);
+    }
+}
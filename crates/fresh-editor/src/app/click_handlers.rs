@@ -489,6 +489,42 @@ impl Editor {
         } else {
             return Ok(());
         };
+        // A click on a code lens title runs the lens instead of moving the
+        // cursor onto the virtual row.
+        let left_column = self
+            .windows
+            .get(&self.active_window)
+            .and_then(|w| w.buffers.splits())
+            .map(|(_, vs)| vs)
+            .expect("active window must have a populated split layout")
+            .get(&split_id)
+            .map(|vs| vs.viewport.left_column)
+            .unwrap_or(0);
+        if let Some(command) = cached_mappings.as_deref().and_then(|mappings| {
+            self.code_lens_command_at(
+                buffer_id,
+                mappings,
+                row.saturating_sub(content_rect.y) as usize,
+                left_column + click_target.text_col,
+            )
+        }) {
+            self.execute_code_lens_command(buffer_id, command);
+            return Ok(());
+        }
+
+        // Ctrl+Click on a document link opens it. Anywhere else Ctrl keeps
+        // extending the selection, as below.
+        if modifiers.contains(KeyModifiers::CONTROL)
+            && !modifiers.contains(KeyModifiers::SHIFT)
+            && click_target.col_overshoot == 0
+            && click_target.row_overshoot == 0
+        {
+            if let Some(target) = self.document_link_at(buffer_id, click_target.position) {
+                self.open_document_link(&target);
+                return Ok(());
+            }
+        }
+
         let mut target_position = click_target.position;
         let click_overshoot = click_target.col_overshoot;
 
@@ -153,57 +153,54 @@ impl Editor {
             .any(|(_, cursor)| cursor.selection_range().is_some());
 
         if has_selection {
-            // Original behavior: copy selected text
-            let ranges: Vec<_> = self
+            // Original behavior: copy selected text, one piece per cursor in
+            // buffer order
+            let mut ranges: Vec<_> = self
                 .active_cursors()
                 .iter()
                 .filter_map(|(_, cursor)| cursor.selection_range())
                 .collect();
-
-            let mut text = String::new();
-            let state = self.active_state_mut();
-            for range in ranges {
-                if !text.is_empty() {
-                    text.push('\n');
-                }
-                let range_text = state.get_text_range(range.start, range.end);
-                text.push_str(&range_text);
-            }
+            ranges.sort_by_key(|r| r.start);
 
             // Strip ANSI escape codes: ANSI-aware buffers render escapes as
             // zero-width styling, so the user sees colored text — the plain
             // copy should carry that visible text, not the control codes.
-            let text = strip_ansi_codes(&text);
-            if !text.is_empty() {
-                self.clipboard.copy(text);
+            let state = self.active_state_mut();
+            let parts: Vec<String> = ranges
+                .into_iter()
+                .map(|range| strip_ansi_codes(&state.get_text_range(range.start, range.end)))
+                .filter(|part| !part.is_empty())
+                .collect();
+
+            if !parts.is_empty() {
+                self.clipboard.copy_parts(parts);
                 self.active_window_mut().status_message = Some(t!("clipboard.copied").to_string());
             }
         } else {
             // No selection: copy entire line(s) for each cursor
             let estimated_line_length = 80;
-            let mut text = String::new();
+            let mut parts = Vec::new();
 
             // Collect cursor positions first
-            let positions: Vec<_> = self
+            let mut positions: Vec<_> = self
                 .active_cursors()
                 .iter()
                 .map(|(_, c)| c.position)
                 .collect();
+            positions.sort_unstable();
             let state = self.active_state_mut();
 
             for pos in positions {
                 let mut iter = state.buffer.line_iterator(pos, estimated_line_length);
                 if let Some((_start, content)) = iter.next_line() {
-                    if !text.is_empty() {
-                        text.push('\n');
+                    if !content.is_empty() {
+                        parts.push(strip_ansi_codes(&content));
                     }
-                    text.push_str(&content);
                 }
             }
 
-            let text = strip_ansi_codes(&text);
-            if !text.is_empty() {
-                self.clipboard.copy(text);
+            if !parts.is_empty() {
+                self.clipboard.copy_parts(parts);
                 self.active_window_mut().status_message =
                     Some(t!("clipboard.copied_line").to_string());
             }
@@ -1295,25 +1292,40 @@ impl Editor {
         };
         cursor_data.sort_by_key(|(_, _, pos, _)| std::cmp::Reverse(*pos));
 
-        // Decide whether to distribute one clipboard line per cursor
-        // (column-mode paste). We split on LF (after normalization above) and
-        // ignore a single trailing empty entry from a trailing newline so that
-        // "a\nb\nc" and "a\nb\nc\n" both yield 3 lines.
-        let mut lines_for_distribution: Vec<&str> = normalized.split('\n').collect();
-        if lines_for_distribution.len() > 1 && lines_for_distribution.last() == Some(&"") {
+        // Decide whether to distribute one piece per cursor. A multi-cursor
+        // copy still in the clipboard history carries its per-cursor pieces,
+        // which may span several lines each. Otherwise fall back to
+        // column-mode paste: split on LF (after normalization above),
+        // ignoring a single trailing empty entry from a trailing newline so
+        // that "a\nb\nc" and "a\nb\nc\n" both yield 3 lines.
+        let mut lines_for_distribution: Vec<String> = match self
+            .clipboard
+            .parts_for(&paste_text)
+            .filter(|parts| parts.len() == cursor_data.len())
+        {
+            Some(parts) => parts
+                .iter()
+                .map(|part| part.replace("\r\n", "\n").replace('\r', "\n"))
+                .collect(),
+            None => normalized.split('\n').map(str::to_string).collect(),
+        };
+        if lines_for_distribution.len() > 1
+            && lines_for_distribution.last().is_some_and(|l| l.is_empty())
+        {
             lines_for_distribution.pop();
         }
         let use_column_paste = cursor_data.len() > 1
             && lines_for_distribution.len() > 1
             && lines_for_distribution.len() == cursor_data.len();
 
-        // Convert to buffer's line ending format (only used in non-column mode;
-        // a single column-paste line never contains an embedded newline).
-        let paste_text_full = match self.active_state().buffer.line_ending() {
-            crate::model::buffer::LineEnding::LF => normalized.clone(),
-            crate::model::buffer::LineEnding::CRLF => normalized.replace('\n', "\r\n"),
-            crate::model::buffer::LineEnding::CR => normalized.replace('\n', "\r"),
+        // Convert to buffer's line ending format.
+        let line_ending = self.active_state().buffer.line_ending();
+        let to_buffer_line_endings = |text: &str| match line_ending {
+            crate::model::buffer::LineEnding::LF => text.to_string(),
+            crate::model::buffer::LineEnding::CRLF => text.replace('\n', "\r\n"),
+            crate::model::buffer::LineEnding::CR => text.replace('\n', "\r"),
         };
+        let paste_text_full = to_buffer_line_endings(&normalized);
 
         // Get deleted text for each selection
         let cursor_data_with_text: Vec<_> = {
@@ -1344,6 +1356,7 @@ impl Editor {
         // the back when iterating.
         let total = cursor_data_with_text.len();
         let mut events = Vec::new();
+        let mut pasted_lengths = Vec::with_capacity(total);
         for (i, (cursor_id, selection, insert_position, deleted_text, virtual_gap)) in
             cursor_data_with_text.into_iter().enumerate()
         {
@@ -1355,10 +1368,11 @@ impl Editor {
                 });
             }
             let mut text = if use_column_paste {
-                lines_for_distribution[total - 1 - i].to_string()
+                to_buffer_line_endings(&lines_for_distribution[total - 1 - i])
             } else {
                 paste_text_full.clone()
             };
+            pasted_lengths.push((cursor_id, text.len()));
             if !virtual_gap.is_empty() {
                 text = format!("{}{}", virtual_gap, text);
             }
@@ -1379,6 +1393,7 @@ impl Editor {
             self.log_and_apply_event(&event);
         }
 
+        self.record_yank(&paste_text, &pasted_lengths);
         self.active_window_mut().status_message = Some(t!("clipboard.pasted").to_string());
     }
 
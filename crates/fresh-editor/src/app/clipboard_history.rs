@@ -0,0 +1,200 @@
+//! Clipboard history: Paste from History and Yank Pop.
+//!
+//! Every copy and cut is recorded in the clipboard's bounded history (see
+//! [`crate::services::clipboard::Clipboard::history`]). Paste from History
+//! offers the entries in a popup; picking one makes it the current clipboard
+//! and pastes it. Yank Pop, right after a paste, swaps the pasted text for
+//! the next older entry in place, Emacs style. Both keep multi-cursor
+//! entries per cursor when the cursor count matches.
+
+use super::Editor;
+use crate::model::cursor::Cursor;
+use crate::model::event::{BufferId, CursorId};
+use crate::services::clipboard::ClipboardEntry;
+use crate::view::popup::{Popup, PopupListItem, PopupPosition, PopupResolver};
+use ratatui::style::Style;
+use rust_i18n::t;
+use std::ops::Range;
+
+/// Longest preview shown for one history entry, in characters.
+const PREVIEW_CHARS: usize = 60;
+
+/// Where the last paste landed in its buffer.
+#[derive(Debug, Clone)]
+pub(crate) struct LastYank {
+    buffer_id: BufferId,
+    /// Buffer version right after the paste; any later edit ends the chance
+    /// to yank-pop it.
+    version: u64,
+    /// The pasted text of each cursor.
+    ranges: Vec<(CursorId, Range<usize>)>,
+    /// History index of the pasted entry, or `None` when the text came from
+    /// outside the editor.
+    index: Option<usize>,
+}
+
+impl Editor {
+    /// Remember a paste of `text` that just inserted `pasted_lengths` bytes
+    /// before each cursor.
+    pub(super) fn record_yank(&mut self, text: &str, pasted_lengths: &[(CursorId, usize)]) {
+        let cursors = self.active_cursors();
+        let ranges = pasted_lengths
+            .iter()
+            .filter_map(|&(id, len)| {
+                let end = cursors.get(id)?.position;
+                Some((id, end.saturating_sub(len)..end))
+            })
+            .collect();
+        let yank = LastYank {
+            buffer_id: self.active_buffer(),
+            version: self.active_state().buffer.version(),
+            ranges,
+            index: self.clipboard.history_position(text),
+        };
+        self.active_window_mut().last_yank = Some(yank);
+    }
+
+    /// Show the clipboard history in a popup below the cursor.
+    pub fn paste_from_history(&mut self) {
+        if self.clipboard.history().len() == 0 {
+            self.set_status_message(t!("clipboard.history_empty").to_string());
+            return;
+        }
+        let items: Vec<PopupListItem> = self
+            .clipboard
+            .history()
+            .enumerate()
+            .map(|(index, entry)| PopupListItem {
+                text: history_preview(&entry.text),
+                detail: history_detail(entry),
+                icon: None,
+                data: Some(index.to_string()),
+                disabled: false,
+            })
+            .collect();
+        let width = items
+            .iter()
+            .map(|item| {
+                item.text.chars().count()
+                    + item.detail.as_ref().map_or(0, |d| d.chars().count() + 2)
+            })
+            .max()
+            .unwrap_or(0);
+
+        let theme = self.theme();
+        let mut popup = Popup::list(items, &theme);
+        popup.title = Some(t!("clipboard.history_title").to_string());
+        popup.position = PopupPosition::BelowCursor;
+        popup.width = (width + 4).clamp(30, 80) as u16;
+        popup.max_height = 12;
+        popup.border_style = Style::default().fg(theme.popup_border_fg);
+        popup.background_style = Style::default().bg(theme.popup_bg);
+        popup.resolver = PopupResolver::ClipboardHistory;
+        popup.focused = true;
+        drop(theme);
+        self.active_state_mut().popups.show_or_replace(popup);
+    }
+
+    /// Paste history entry `index`, making it the current clipboard.
+    pub(super) fn paste_history_entry(&mut self, index: usize) {
+        if let Some(entry) = self.clipboard.promote_history_entry(index) {
+            self.paste_text(entry.text);
+        }
+    }
+
+    /// Replace the text of the paste just made with the next older history
+    /// entry. Only works while the paste is the last edit and the cursors
+    /// are still at its end.
+    pub fn yank_pop(&mut self) {
+        let Some(yank) = self
+            .active_window()
+            .last_yank
+            .clone()
+            .filter(|yank| self.yank_still_in_place(yank))
+        else {
+            self.set_status_message(t!("clipboard.yank_pop_unavailable").to_string());
+            return;
+        };
+        let count = self.clipboard.history().len();
+        if count == 0 {
+            self.set_status_message(t!("clipboard.history_empty").to_string());
+            return;
+        }
+        let next = yank.index.map_or(0, |i| (i + 1) % count);
+        let Some(entry) = self.clipboard.history_entry(next).cloned() else {
+            return;
+        };
+
+        // Select each cursor's pasted text so pasting the entry replaces it.
+        let cursors = self.active_cursors_mut();
+        for (id, range) in &yank.ranges {
+            if let Some(cursor) = cursors.get_mut(*id) {
+                *cursor = Cursor::with_selection(range.start, range.end);
+            }
+        }
+        self.paste_text(entry.text);
+        self.set_status_message(
+            t!("clipboard.yank_pop", index = next + 1, count = count).to_string(),
+        );
+    }
+
+    /// Whether `yank` still describes the active buffer: nothing edited it
+    /// since, and every cursor still sits at the end of its pasted text.
+    fn yank_still_in_place(&self, yank: &LastYank) -> bool {
+        if yank.buffer_id != self.active_buffer()
+            || yank.version != self.active_state().buffer.version()
+        {
+            return false;
+        }
+        let cursors = self.active_cursors();
+        cursors.count() == yank.ranges.len()
+            && yank.ranges.iter().all(|(id, range)| {
+                cursors
+                    .get(*id)
+                    .is_some_and(|c| c.position == range.end && c.selection_range().is_none())
+            })
+    }
+}
+
+/// One-line preview of a history entry: its first non-blank line, shortened
+/// to [`PREVIEW_CHARS`].
+fn history_preview(text: &str) -> String {
+    let line = text
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or_default()
+        .replace('\t', " ");
+    if line.chars().count() > PREVIEW_CHARS {
+        let mut preview: String = line.chars().take(PREVIEW_CHARS - 1).collect();
+        preview.push('…');
+        preview
+    } else {
+        line
+    }
+}
+
+/// Size of a history entry: its cursor count for multi-cursor copies,
+/// otherwise its line count when it spans several lines.
+fn history_detail(entry: &ClipboardEntry) -> Option<String> {
+    if !entry.parts.is_empty() {
+        return Some(t!("clipboard.history_cursors", count = entry.parts.len()).to_string());
+    }
+    let lines = entry.text.lines().count();
+    (lines > 1).then(|| t!("clipboard.history_lines", count = lines).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history_preview_uses_first_non_blank_line() {
+        assert_eq!(history_preview("\n  fn main() {\n}\n"), "fn main() {");
+        assert_eq!(history_preview("\ta\tb"), "a b");
+        let long = "x".repeat(100);
+        let preview = history_preview(&long);
+        assert_eq!(preview.chars().count(), PREVIEW_CHARS);
+        assert!(preview.ends_with('…'));
+    }
+}
@@ -0,0 +1,507 @@
+//! LSP code lenses (`textDocument/codeLens`), drawn as virtual lines above
+//! the line each lens annotates.
+//!
+//! Lenses are requested for the whole document — the protocol has no range
+//! parameter — once the buffer settles after an edit, but only the lenses in
+//! a visible viewport are resolved: servers such as rust-analyzer send
+//! reference counts unresolved because they are expensive to compute.
+//! Clicking a lens title sends its command through `workspace/executeCommand`.
+
+use std::ops::Range;
+
+use super::Editor;
+use crate::app::types::ViewLineMapping;
+use crate::model::event::BufferId;
+use crate::primitives::display_width::str_width;
+use crate::types::LspFeature;
+use crate::view::virtual_text::{VirtualTextId, VirtualTextNamespace, VirtualTextPosition};
+
+/// Namespace owning every code lens line.
+const CODE_LENS_NAMESPACE: &str = "lsp-code-lens";
+
+/// Drawn between lenses that share a line.
+const CODE_LENS_SEPARATOR: &str = " | ";
+
+/// Lens lines sort after every other `LineAbove` virtual line at the same
+/// anchor, so the lens sits directly on top of the code it annotates. Click
+/// handling relies on this to tell the lens row apart from other headers.
+const CODE_LENS_PRIORITY: i32 = i32::MAX;
+
+pub(crate) fn code_lens_namespace() -> VirtualTextNamespace {
+    VirtualTextNamespace::from_string(CODE_LENS_NAMESPACE.to_string())
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct CodeLensResolveRequest {
+    pub(crate) buffer_id: BufferId,
+    pub(crate) version: u64,
+    /// Index into [`BufferCodeLenses::lenses`].
+    pub(crate) index: usize,
+}
+
+/// Clickable display-column span of each title on a lens line.
+type LensSpans = Vec<(Range<usize>, lsp_types::Command)>;
+
+/// The code lenses last received for one buffer.
+#[derive(Debug, Default)]
+pub(crate) struct BufferCodeLenses {
+    /// Buffer version the lens ranges refer to.
+    pub(crate) version: u64,
+    pub(crate) lenses: Vec<lsp_types::CodeLens>,
+    /// Parallel to `lenses`: a `codeLens/resolve` was already sent.
+    resolve_sent: Vec<bool>,
+    /// One entry per drawn lens line: its virtual text and the clickable
+    /// column span of each title on it.
+    lines: Vec<(VirtualTextId, LensSpans)>,
+}
+
+/// Lay out the lenses of one source line: `indent` followed by the titles
+/// joined with [`CODE_LENS_SEPARATOR`]. Returns the line text and the
+/// display-column span of each title.
+fn code_lens_line(indent: &str, commands: &[&lsp_types::Command]) -> (String, LensSpans) {
+    let mut text = indent.to_string();
+    let mut col = str_width(indent);
+    let mut spans = Vec::with_capacity(commands.len());
+    for (i, command) in commands.iter().enumerate() {
+        if i > 0 {
+            text.push_str(CODE_LENS_SEPARATOR);
+            col += str_width(CODE_LENS_SEPARATOR);
+        }
+        let width = str_width(&command.title);
+        text.push_str(&command.title);
+        spans.push((col..col + width, (*command).clone()));
+        col += width;
+    }
+    (text, spans)
+}
+
+impl crate::app::window::Window {
+    /// Forget everything code-lens related for a buffer and remove its lines.
+    pub(crate) fn clear_code_lenses_for_buffer(&mut self, buffer_id: BufferId) {
+        self.code_lenses.remove(&buffer_id);
+        self.code_lens_requests.clear(buffer_id);
+        self.pending_code_lens_resolves
+            .retain(|_, req| req.buffer_id != buffer_id);
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state
+                .virtual_texts
+                .clear_namespace(&mut state.marker_list, &code_lens_namespace());
+        }
+    }
+
+    /// Handle a `textDocument/codeLens` response.
+    pub fn handle_lsp_code_lenses(
+        &mut self,
+        request_id: u64,
+        uri: String,
+        lenses: Vec<lsp_types::CodeLens>,
+    ) {
+        let buffers = &self.buffers;
+        let Some((buffer_id, version)) =
+            self.code_lens_requests
+                .settle(request_id, "code lenses", |id| {
+                    buffers.get(&id).map(|s| s.buffer.version())
+                })
+        else {
+            return;
+        };
+
+        tracing::info!(
+            "Received {} code lenses for {} (request_id={})",
+            lenses.len(),
+            uri,
+            request_id
+        );
+
+        let mut lenses = lenses;
+        lenses.sort_by_key(|lens| (lens.range.start.line, lens.range.start.character));
+        // Resolve requests for the previous set would index into the new one.
+        self.pending_code_lens_resolves
+            .retain(|_, req| req.buffer_id != buffer_id);
+        self.code_lenses.insert(
+            buffer_id,
+            BufferCodeLenses {
+                version,
+                resolve_sent: vec![false; lenses.len()],
+                lenses,
+                lines: Vec::new(),
+            },
+        );
+        self.render_code_lenses(buffer_id);
+    }
+
+    /// Handle a `codeLens/resolve` response by filling in the lens it was
+    /// sent for and redrawing the buffer's lens lines.
+    pub fn handle_lsp_code_lens_resolved(
+        &mut self,
+        request_id: u64,
+        lens: Result<lsp_types::CodeLens, String>,
+    ) {
+        let Some(request) = self.pending_code_lens_resolves.remove(&request_id) else {
+            return;
+        };
+        let lens = match lens {
+            Ok(lens) => lens,
+            Err(e) => {
+                tracing::debug!("codeLens/resolve failed: {}", e);
+                return;
+            }
+        };
+        let current_version = self
+            .buffers
+            .get(&request.buffer_id)
+            .map(|s| s.buffer.version());
+        let Some(entry) = self.code_lenses.get_mut(&request.buffer_id) else {
+            return;
+        };
+        if entry.version != request.version || current_version != Some(request.version) {
+            return;
+        }
+        let Some(slot) = entry.lenses.get_mut(request.index) else {
+            return;
+        };
+        *slot = lens;
+        self.render_code_lenses(request.buffer_id);
+    }
+
+    /// Rebuild the virtual lines for a buffer's code lenses. Lenses without
+    /// a command (not resolved yet) take no space.
+    fn render_code_lenses(&mut self, buffer_id: BufferId) {
+        let Some(entry) = self.code_lenses.get_mut(&buffer_id) else {
+            return;
+        };
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        let namespace = code_lens_namespace();
+        state
+            .virtual_texts
+            .clear_namespace(&mut state.marker_list, &namespace);
+        entry.lines.clear();
+
+        let tab_size = state.buffer_settings.tab_size;
+        let fallback_style =
+            ratatui::style::Style::default().fg(ratatui::style::Color::Rgb(128, 128, 128));
+
+        let mut i = 0;
+        while i < entry.lenses.len() {
+            let line = entry.lenses[i].range.start.line;
+            let mut end = i;
+            while end < entry.lenses.len() && entry.lenses[end].range.start.line == line {
+                end += 1;
+            }
+            let commands: Vec<&lsp_types::Command> = entry.lenses[i..end]
+                .iter()
+                .filter_map(|lens| lens.command.as_ref())
+                .filter(|command| !command.title.is_empty())
+                .collect();
+            i = end;
+
+            if commands.is_empty() {
+                continue;
+            }
+            let Some(line_start) = state.buffer.line_start_offset(line as usize) else {
+                continue;
+            };
+            // Indent the lens like the code below it, with tabs expanded so
+            // the virtual line lines up whatever the tab width.
+            let indent: String = state
+                .buffer
+                .get_line(line as usize)
+                .unwrap_or_default()
+                .iter()
+                .take_while(|b| **b == b' ' || **b == b'\t')
+                .map(|b| {
+                    if *b == b'\t' {
+                        " ".repeat(tab_size)
+                    } else {
+                        " ".to_string()
+                    }
+                })
+                .collect();
+
+            let (text, spans) = code_lens_line(&indent, &commands);
+            let id = state.virtual_texts.add_line_with_theme_keys(
+                &mut state.marker_list,
+                line_start,
+                text,
+                fallback_style,
+                Some("editor.line_number_fg".to_string()),
+                None,
+                VirtualTextPosition::LineAbove,
+                namespace.clone(),
+                CODE_LENS_PRIORITY,
+                None,
+                None,
+                Vec::new(),
+            );
+            entry.lines.push((id, spans));
+        }
+    }
+}
+
+impl Editor {
+    /// Issue a debounced code lens request if the timer has elapsed.
+    pub(crate) fn maybe_request_code_lenses_debounced(&mut self, buffer_id: BufferId) {
+        if self
+            .active_window_mut()
+            .code_lens_requests
+            .take_due(buffer_id, false)
+        {
+            self.request_code_lenses_for_buffer(buffer_id);
+        }
+    }
+
+    /// Request code lenses for a buffer if a server provides them.
+    pub(crate) fn request_code_lenses_for_buffer(&mut self, buffer_id: BufferId) {
+        if self
+            .active_window()
+            .code_lens_requests
+            .is_in_flight(buffer_id)
+        {
+            return;
+        }
+        let Some(version) = self
+            .active_window()
+            .buffers
+            .get(&buffer_id)
+            .map(|s| s.buffer.version())
+        else {
+            return;
+        };
+        let request_id = self.active_window().next_lsp_request_id;
+
+        let sent = self
+            .with_lsp_for_buffer(buffer_id, LspFeature::CodeLens, |handle, uri, _language| {
+                match handle.code_lenses(request_id, uri.as_uri().clone()) {
+                    Ok(()) => true,
+                    Err(e) => {
+                        tracing::debug!("Failed to request code lenses: {}", e);
+                        false
+                    }
+                }
+            })
+            .unwrap_or(false);
+
+        if sent {
+            let win = self.active_window_mut();
+            win.next_lsp_request_id += 1;
+            win.code_lens_requests.sent(request_id, buffer_id, version);
+        }
+    }
+
+    /// Request code lenses for all open buffers matching a language.
+    pub(super) fn request_code_lenses_for_language(&mut self, language: &str) {
+        let buffer_ids: Vec<_> = self
+            .buffers_for_language(language)
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        for buffer_id in buffer_ids {
+            self.request_code_lenses_for_buffer(buffer_id);
+        }
+    }
+
+    pub(super) fn handle_lsp_code_lens_refresh(&mut self, language: String) {
+        tracing::info!(
+            "LSP ({}) code-lens refresh requested, re-pulling code lenses",
+            language
+        );
+        self.request_code_lenses_for_language(&language);
+    }
+
+    /// Send `codeLens/resolve` for the unresolved lenses on lines
+    /// `start_line..=end_line`. Each lens is resolved at most once per
+    /// response, so scrolling back over it costs nothing.
+    pub(crate) fn resolve_visible_code_lenses(
+        &mut self,
+        buffer_id: BufferId,
+        start_line: usize,
+        end_line: usize,
+    ) {
+        let win = self.active_window_mut();
+        let Some(state) = win.buffers.get(&buffer_id) else {
+            return;
+        };
+        let Some(entry) = win.code_lenses.get_mut(&buffer_id) else {
+            return;
+        };
+        // Ranges of an out-of-date set no longer match what is on screen;
+        // a refresh is already on its way.
+        if entry.version != state.buffer.version() {
+            return;
+        }
+        let language = state.language.clone();
+        let wanted: Vec<usize> = entry
+            .lenses
+            .iter()
+            .enumerate()
+            .filter(|(i, lens)| {
+                let line = lens.range.start.line as usize;
+                lens.command.is_none()
+                    && !entry.resolve_sent[*i]
+                    && line >= start_line
+                    && line <= end_line
+            })
+            .map(|(i, _)| i)
+            .collect();
+        if wanted.is_empty() {
+            return;
+        }
+        let version = entry.version;
+
+        let Some(sh) = win
+            .lsp
+            .handle_for_feature_mut(&language, LspFeature::CodeLens)
+        else {
+            return;
+        };
+        if !sh.capabilities.code_lens_resolve {
+            return;
+        }
+        for index in wanted {
+            let request_id = win.next_lsp_request_id;
+            let lens = entry.lenses[index].clone();
+            if let Err(e) = sh.handle.code_lens_resolve(request_id, lens) {
+                tracing::debug!("Failed to resolve code lens: {}", e);
+                break;
+            }
+            win.next_lsp_request_id += 1;
+            entry.resolve_sent[index] = true;
+            win.pending_code_lens_resolves.insert(
+                request_id,
+                CodeLensResolveRequest {
+                    buffer_id,
+                    version,
+                    index,
+                },
+            );
+        }
+    }
+
+    /// The command of the code lens title under a click, if the clicked
+    /// view row is a lens line. `visual_row` indexes `mappings`; `text_col`
+    /// is the clicked column relative to the text area.
+    pub(super) fn code_lens_command_at(
+        &self,
+        buffer_id: BufferId,
+        mappings: &[ViewLineMapping],
+        visual_row: usize,
+        column: usize,
+    ) -> Option<lsp_types::Command> {
+        // Lens lines are drawn last among a line's headers, so the clicked
+        // row must be virtual and directly followed by the real line.
+        if !mappings.get(visual_row)?.is_plugin_virtual {
+            return None;
+        }
+        let next = mappings.get(visual_row + 1)?;
+        if next.is_plugin_virtual {
+            return None;
+        }
+        let byte = next.char_source_bytes.iter().find_map(|b| *b)?;
+
+        let win = self.active_window();
+        let state = win.buffers.get(&buffer_id)?;
+        let entry = win.code_lenses.get(&buffer_id)?;
+        let line = state.buffer.get_line_number(byte);
+        let line_start = state.buffer.line_start_offset(line)?;
+        let line_end = state
+            .buffer
+            .line_start_offset(line + 1)
+            .unwrap_or(state.buffer.len() + 1);
+
+        let namespace = code_lens_namespace();
+        let marker_id = state
+            .virtual_texts
+            .query_lines_in_range(&state.marker_list, line_start, line_end)
+            .into_iter()
+            .find(|(_, vtext)| {
+                vtext.position == VirtualTextPosition::LineAbove
+                    && vtext.namespace.as_ref() == Some(&namespace)
+            })?
+            .1
+            .marker_id;
+
+        let (_, spans) = entry
+            .lines
+            .iter()
+            .find(|(id, _)| state.virtual_texts.marker_id_of(*id) == Some(marker_id))?;
+        spans
+            .iter()
+            .find(|(span, _)| span.contains(&column))
+            .map(|(_, command)| command.clone())
+    }
+
+    /// Run a code lens command on the server that provided the lens.
+    pub(super) fn execute_code_lens_command(
+        &mut self,
+        buffer_id: BufferId,
+        command: lsp_types::Command,
+    ) {
+        tracing::info!(
+            "Executing code lens command: {} ({})",
+            command.title,
+            command.command
+        );
+        let sent = self.with_lsp_for_buffer(buffer_id, LspFeature::CodeLens, |handle, _, _| {
+            handle.execute_command(command.command.clone(), command.arguments.clone())
+        });
+        if let Some(Err(e)) = sent {
+            tracing::warn!("Failed to send code lens command: {}", e);
+        }
+    }
+
+    pub(super) fn handle_lsp_code_lenses(
+        &mut self,
+        request_id: u64,
+        uri: String,
+        lenses: Vec<lsp_types::CodeLens>,
+    ) {
+        self.active_window_mut()
+            .handle_lsp_code_lenses(request_id, uri, lenses);
+    }
+
+    pub(super) fn handle_lsp_code_lens_resolved(
+        &mut self,
+        request_id: u64,
+        lens: Result<lsp_types::CodeLens, String>,
+    ) {
+        self.active_window_mut()
+            .handle_lsp_code_lens_resolved(request_id, lens);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command(title: &str) -> lsp_types::Command {
+        lsp_types::Command {
+            title: title.to_string(),
+            command: format!("cmd.{title}"),
+            arguments: None,
+        }
+    }
+
+    #[test]
+    fn code_lens_line_joins_titles_after_indent() {
+        let run = command("Run");
+        let debug = command("Debug");
+        let (text, spans) = code_lens_line("    ", &[&run, &debug]);
+        assert_eq!(text, "    Run | Debug");
+        assert_eq!(spans[0].0, 4..7);
+        assert_eq!(spans[0].1.command, "cmd.Run");
+        assert_eq!(spans[1].0, 10..15);
+        assert_eq!(spans[1].1.command, "cmd.Debug");
+    }
+
+    #[test]
+    fn code_lens_line_spans_use_display_width() {
+        let run = command("▶ 実行");
+        let refs = command("2 references");
+        let (_, spans) = code_lens_line("", &[&run, &refs]);
+        assert_eq!(spans[0].0, 0..str_width("▶ 実行"));
+        let second_start = str_width("▶ 実行") + CODE_LENS_SEPARATOR.len();
+        assert_eq!(spans[1].0.start, second_start);
+    }
+}
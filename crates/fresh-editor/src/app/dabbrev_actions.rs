@@ -276,7 +276,7 @@ impl Editor {
             scan_range,
             viewport_top_byte,
             viewport_bottom_byte,
-            language_id: None,
+            language_id: Some(self.active_state().language.clone()),
             word_chars_extra,
             prefix_has_uppercase: prefix_has_upper,
             other_buffers,
@@ -292,7 +292,7 @@ impl Editor {
             .map(|c| PopupListItemData {
                 text: c.label.clone(),
                 detail: c.detail.clone(),
-                icon: Some("w".to_string()),
+                icon: c.icon.or_else(|| Some("w".to_string())),
                 data: c.insert_text.or(Some(c.label)),
             })
             .collect()
@@ -0,0 +1,884 @@
+//! Debugger actions and Debug Adapter Protocol event handlers.
+//!
+//! Breakpoints are margin line indicators in the [`BREAKPOINT_NS`]
+//! namespace, so they follow edits like any other marker and need no
+//! separate store: the lines sent to the adapter are read back from the
+//! markers whenever a session is configured or a breakpoint is toggled.
+//!
+//! While the debuggee is stopped the editor shows the stop line in the
+//! margin, the values of the innermost frame's variables as virtual text
+//! at the end of the lines that mention them, and — when open — a
+//! stack / variables / output panel built as a buffer group.
+
+use crossterm::event::{KeyCode, KeyModifiers};
+use ratatui::style::{Color, Style};
+use rust_i18n::t;
+
+use super::Editor;
+use crate::app::types::BufferGroupId;
+use crate::input::buffer_mode::BufferMode;
+use crate::input::commands::Suggestion;
+use crate::input::keybindings::{Action, KeyContext};
+use crate::primitives::text_property::TextPropertyEntry;
+use crate::services::dap::async_handler::DapCommand;
+use crate::services::dap::manager::{DapSession, DapSessionState};
+use crate::services::dap::protocol::{ScopeVariables, StackFrame};
+use crate::state::EditorState;
+use crate::view::margin::LineIndicator;
+use crate::view::prompt::{Prompt, PromptType};
+use crate::view::virtual_text::VirtualTextPosition;
+
+/// Margin namespace of breakpoint indicators.
+const BREAKPOINT_NS: &str = "dap-breakpoint";
+
+/// Margin namespace of the "execution stopped here" indicator.
+const STOPPED_NS: &str = "dap-stopped";
+
+/// String-id prefix of inline variable values.
+const INLINE_VALUE_PREFIX: &str = "dap-value:";
+
+/// Mode of the debug panel's buffers.
+const DEBUG_PANEL_MODE: &str = "debug-panel";
+
+/// Text property holding a stack panel row's frame id.
+const FRAME_PROPERTY: &str = "dap_frame";
+
+/// How far above the stop line inline values are shown.
+const INLINE_VALUE_LOOKBACK: usize = 50;
+
+/// Longest inline value before it is cut with an ellipsis.
+const MAX_INLINE_VALUE_LEN: usize = 40;
+
+/// Step granularity for [`Editor::debug_step`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DebugStep {
+    Over,
+    Into,
+    Out,
+}
+
+impl Editor {
+    /// Start debugging. With several `debug_configurations` a prompt picks
+    /// one; a single configuration starts right away.
+    pub fn debug_start(&mut self) {
+        let configurations = self.config().debug_configurations.clone();
+        match configurations.len() {
+            0 => self.set_status_message(t!("debug.no_configurations").to_string()),
+            1 => self.start_debug_configuration(0),
+            _ => {
+                let suggestions = configurations
+                    .iter()
+                    .enumerate()
+                    .map(|(index, configuration)| Suggestion {
+                        description_spans: None,
+                        text: configuration.name.clone(),
+                        description: Some(format!(
+                            "{} ({})",
+                            configuration.adapter,
+                            configuration.request.command()
+                        )),
+                        value: Some(index.to_string()),
+                        disabled: false,
+                        keybinding: None,
+                        source: None,
+                    })
+                    .collect();
+                self.active_window_mut().prompt = Some(Prompt::with_suggestions(
+                    t!("debug.select_configuration").to_string(),
+                    PromptType::SelectDebugConfiguration,
+                    suggestions,
+                ));
+            }
+        }
+    }
+
+    /// Start the debug configuration at `index` in the active window.
+    pub(crate) fn start_debug_configuration(&mut self, index: usize) {
+        let Some(configuration) = self.config().debug_configurations.get(index).cloned() else {
+            return;
+        };
+        let Some(adapter) = self
+            .config()
+            .debug_adapters
+            .get(&configuration.adapter)
+            .cloned()
+        else {
+            self.set_status_message(
+                t!("debug.unknown_adapter", adapter = configuration.adapter).to_string(),
+            );
+            return;
+        };
+
+        self.clear_debug_stop_markers(self.active_window);
+        let file = self
+            .active_state()
+            .buffer
+            .file_path()
+            .map(|p| p.to_path_buf());
+        let window = self.active_window_mut();
+        let root = window.root.clone();
+        match window
+            .dap
+            .start(&configuration, &adapter, &root, file.as_deref())
+        {
+            Ok(_) => {
+                self.set_status_message(t!("debug.starting", name = configuration.name).to_string())
+            }
+            Err(e) => self.set_status_message(t!("debug.start_failed", error = e).to_string()),
+        }
+    }
+
+    /// End the active window's debug session.
+    pub fn debug_stop(&mut self) {
+        if self.active_window_mut().dap.stop() {
+            self.clear_debug_stop_markers(self.active_window);
+            self.refresh_debug_panel(self.active_window);
+            self.set_status_message(t!("debug.stopped").to_string());
+        } else {
+            self.set_status_message(t!("debug.no_session").to_string());
+        }
+    }
+
+    pub fn debug_continue(&mut self) {
+        self.resume_debuggee(|thread_id| DapCommand::Continue { thread_id });
+    }
+
+    pub(crate) fn debug_step(&mut self, step: DebugStep) {
+        self.resume_debuggee(|thread_id| match step {
+            DebugStep::Over => DapCommand::Next { thread_id },
+            DebugStep::Into => DapCommand::StepIn { thread_id },
+            DebugStep::Out => DapCommand::StepOut { thread_id },
+        });
+    }
+
+    /// Interrupt a running debuggee.
+    pub fn debug_pause(&mut self) {
+        let Some(session) = self.active_window().dap.session() else {
+            self.set_status_message(t!("debug.no_session").to_string());
+            return;
+        };
+        if session.state != DapSessionState::Running {
+            self.set_status_message(t!("debug.not_running").to_string());
+            return;
+        }
+        // A thread that never stopped has no id yet; adapters treat the
+        // first thread as "all threads" for `pause`.
+        let thread_id = session.thread_id.unwrap_or(1);
+        if let Err(e) = self
+            .active_window()
+            .dap
+            .send(DapCommand::Pause { thread_id })
+        {
+            self.set_status_message(t!("debug.error", error = e).to_string());
+        }
+    }
+
+    /// Send a command that resumes the stopped thread, then forget the
+    /// stop: its markers, inline values and panel contents go stale.
+    fn resume_debuggee(&mut self, command: impl FnOnce(i64) -> DapCommand) {
+        let Some(session) = self.active_window().dap.session() else {
+            self.set_status_message(t!("debug.no_session").to_string());
+            return;
+        };
+        let (true, Some(thread_id)) = (session.is_stopped(), session.thread_id) else {
+            self.set_status_message(t!("debug.not_stopped").to_string());
+            return;
+        };
+        if let Err(e) = self.active_window().dap.send(command(thread_id)) {
+            self.set_status_message(t!("debug.error", error = e).to_string());
+            return;
+        }
+        if let Some(session) = self.active_window_mut().dap.session_mut() {
+            session.state = DapSessionState::Running;
+            session.clear_stop();
+        }
+        self.clear_debug_stop_markers(self.active_window);
+        self.refresh_debug_panel(self.active_window);
+    }
+
+    /// Toggle a breakpoint on the cursor line of the active file buffer.
+    pub fn debug_toggle_breakpoint(&mut self) {
+        let Some(path) = self
+            .active_state()
+            .buffer
+            .file_path()
+            .map(|p| p.to_path_buf())
+        else {
+            self.set_status_message(t!("debug.breakpoint_no_file").to_string());
+            return;
+        };
+        let position = self.active_cursors().primary().position;
+        let state = self.active_state_mut();
+        let line = state.buffer.get_line_number(position);
+        let existing = state
+            .margins
+            .line_indicators_in_namespace(BREAKPOINT_NS)
+            .into_iter()
+            .find(|&(_, pos)| state.buffer.get_line_number(pos) == line);
+        match existing {
+            Some((marker, _)) => {
+                state.margins.remove_line_indicator(marker, BREAKPOINT_NS);
+                self.set_status_message(
+                    t!("debug.breakpoint_removed", line = line + 1).to_string(),
+                );
+            }
+            None => {
+                let line_start = state.buffer.line_start_offset(line).unwrap_or(position);
+                state.margins.set_line_indicator(
+                    line_start,
+                    BREAKPOINT_NS.to_string(),
+                    LineIndicator::new("●", Color::Red, 50),
+                );
+                self.set_status_message(t!("debug.breakpoint_set", line = line + 1).to_string());
+            }
+        }
+
+        if self.active_window().dap.session().is_some() {
+            let lines = breakpoint_lines(self.active_state());
+            if let Err(e) = self
+                .active_window()
+                .dap
+                .send(DapCommand::SetBreakpoints { path, lines })
+            {
+                tracing::warn!("Failed to update breakpoints: {}", e);
+            }
+        }
+    }
+
+    /// Open the debug panel, or switch to it if it is already open.
+    pub fn debug_show_panel(&mut self) {
+        if let Some(group_id) = self.active_window().debug_panel {
+            if self.active_window().buffer_groups.contains_key(&group_id) {
+                self.refresh_debug_panel(self.active_window);
+                self.focus_debug_panel(group_id);
+                return;
+            }
+        }
+
+        self.ensure_debug_panel_mode_registered();
+        let layout = serde_json::json!({
+            "type": "split",
+            "direction": "v",
+            "ratio": 0.7,
+            "first": {
+                "type": "split",
+                "direction": "h",
+                "ratio": 0.5,
+                "first": {"type": "scrollable", "id": "stack"},
+                "second": {"type": "scrollable", "id": "variables"},
+            },
+            "second": {"type": "scrollable", "id": "output"},
+        });
+        match self.create_buffer_group(
+            t!("debug.panel_name").to_string(),
+            DEBUG_PANEL_MODE.to_string(),
+            layout.to_string(),
+        ) {
+            Ok(result) => {
+                let group_id = BufferGroupId(result.group_id as usize);
+                // Frames are picked with the cursor, so the stack panel shows it.
+                if let Some(&buffer) = result.panels.get("stack") {
+                    let buffer = crate::model::event::BufferId(buffer as usize);
+                    if let Some(state) = self.active_window_mut().buffers.get_mut(&buffer) {
+                        state.show_cursors = true;
+                    }
+                }
+                self.active_window_mut().debug_panel = Some(group_id);
+                self.refresh_debug_panel(self.active_window);
+            }
+            Err(e) => tracing::warn!("Failed to create debug panel: {}", e),
+        }
+    }
+
+    /// Show the frame under the cursor in the stack panel: jump to its
+    /// source and fetch its variables.
+    pub fn debug_select_frame(&mut self) {
+        let Some(frame_id) = self
+            .get_text_properties_at_cursor()
+            .and_then(|props| props.iter().find_map(|p| p.get_as::<i64>(FRAME_PROPERTY)))
+        else {
+            return;
+        };
+        let Some(session) = self.active_window_mut().dap.session_mut() else {
+            return;
+        };
+        session.selected_frame = Some(frame_id);
+        let frame = session.current_frame().cloned();
+        if let Err(e) = self
+            .active_window()
+            .dap
+            .send(DapCommand::Scopes { frame_id })
+        {
+            self.set_status_message(t!("debug.error", error = e).to_string());
+        }
+        self.refresh_debug_panel(self.active_window);
+        if let Some(frame) = frame {
+            self.show_debug_frame(&frame);
+        }
+    }
+
+    // ---- adapter events ----------------------------------------------
+
+    /// The window whose debug session has `session_id`.
+    fn dap_window(&self, session_id: u64) -> Option<fresh_core::WindowId> {
+        self.windows
+            .iter()
+            .find(|(_, w)| w.dap.session().is_some_and(|s| s.id() == session_id))
+            .map(|(id, _)| *id)
+    }
+
+    fn dap_session_mut(&mut self, session_id: u64) -> Option<&mut DapSession> {
+        let window_id = self.dap_window(session_id)?;
+        self.windows
+            .get_mut(&window_id)?
+            .dap
+            .session_by_id(session_id)
+    }
+
+    /// Configuration phase: send every breakpoint, then let the debuggee run.
+    pub(crate) fn handle_dap_initialized(&mut self, session_id: u64) {
+        let Some(window_id) = self.dap_window(session_id) else {
+            return;
+        };
+        let Some(window) = self.windows.get_mut(&window_id) else {
+            return;
+        };
+        let breakpoints: Vec<_> = window
+            .buffers
+            .iter()
+            .filter_map(|(_, state)| {
+                let path = state.buffer.file_path()?.to_path_buf();
+                let lines = breakpoint_lines(state);
+                (!lines.is_empty()).then_some((path, lines))
+            })
+            .collect();
+        for (path, lines) in breakpoints {
+            if let Err(e) = window.dap.send(DapCommand::SetBreakpoints { path, lines }) {
+                tracing::warn!("Failed to send breakpoints: {}", e);
+            }
+        }
+        if let Err(e) = window.dap.send(DapCommand::ConfigurationDone) {
+            tracing::warn!("Failed to finish debug configuration: {}", e);
+        }
+        if let Some(session) = window.dap.session_by_id(session_id) {
+            session.state = DapSessionState::Running;
+        }
+    }
+
+    pub(crate) fn handle_dap_stopped(
+        &mut self,
+        session_id: u64,
+        thread_id: Option<i64>,
+        reason: String,
+        description: Option<String>,
+    ) {
+        let Some(window_id) = self.dap_window(session_id) else {
+            return;
+        };
+        let Some(window) = self.windows.get_mut(&window_id) else {
+            return;
+        };
+        let Some(session) = window.dap.session_by_id(session_id) else {
+            return;
+        };
+        session.state = DapSessionState::Stopped {
+            reason: reason.clone(),
+        };
+        session.thread_id = thread_id.or(session.thread_id);
+        let thread_id = session.thread_id;
+        if let Err(e) = window.dap.send(DapCommand::StackTrace { thread_id }) {
+            tracing::warn!("Failed to request stack trace: {}", e);
+        }
+        let message = match description {
+            Some(description) => {
+                t!(
+                    "debug.paused_with_description",
+                    reason = reason,
+                    description = description
+                )
+            }
+            None => t!("debug.paused", reason = reason),
+        };
+        window.set_status_message(message.to_string());
+    }
+
+    pub(crate) fn handle_dap_continued(&mut self, session_id: u64) {
+        let Some(window_id) = self.dap_window(session_id) else {
+            return;
+        };
+        if let Some(session) = self.dap_session_mut(session_id) {
+            session.state = DapSessionState::Running;
+            session.clear_stop();
+        }
+        self.clear_debug_stop_markers(window_id);
+        self.refresh_debug_panel(window_id);
+    }
+
+    /// A stop's call stack arrived: show where execution is and what the
+    /// top frame's variables hold.
+    pub(crate) fn handle_dap_stack_trace(
+        &mut self,
+        session_id: u64,
+        thread_id: i64,
+        frames: Vec<StackFrame>,
+        frame_id: Option<i64>,
+        scopes: Vec<ScopeVariables>,
+    ) {
+        let Some(window_id) = self.dap_window(session_id) else {
+            return;
+        };
+        let Some(session) = self.dap_session_mut(session_id) else {
+            return;
+        };
+        if !session.is_stopped() {
+            // Resumed while the request was in flight.
+            return;
+        }
+        session.thread_id = Some(thread_id);
+        session.frames = frames;
+        session.selected_frame = frame_id;
+        session.scopes = scopes;
+        let frame = session.current_frame().cloned();
+
+        self.refresh_debug_panel(window_id);
+        if let Some(frame) = frame {
+            if window_id == self.active_window {
+                self.show_debug_frame(&frame);
+            }
+            self.mark_debug_stop(window_id, &frame);
+        }
+    }
+
+    pub(crate) fn handle_dap_scopes(
+        &mut self,
+        session_id: u64,
+        frame_id: i64,
+        scopes: Vec<ScopeVariables>,
+    ) {
+        let Some(window_id) = self.dap_window(session_id) else {
+            return;
+        };
+        let Some(session) = self.dap_session_mut(session_id) else {
+            return;
+        };
+        if session.selected_frame != Some(frame_id) {
+            return;
+        }
+        session.scopes = scopes;
+        let frame = session.current_frame().cloned();
+        self.refresh_debug_panel(window_id);
+        if let Some(frame) = frame {
+            self.mark_debug_stop(window_id, &frame);
+        }
+    }
+
+    pub(crate) fn handle_dap_output(
+        &mut self,
+        session_id: u64,
+        category: Option<String>,
+        output: String,
+    ) {
+        // Adapter telemetry is not meant for the user.
+        if category.as_deref() == Some("telemetry") {
+            return;
+        }
+        let Some(window_id) = self.dap_window(session_id) else {
+            return;
+        };
+        if let Some(session) = self.dap_session_mut(session_id) {
+            session.push_output(&output);
+        }
+        self.refresh_debug_panel(window_id);
+    }
+
+    pub(crate) fn handle_dap_exited(&mut self, session_id: u64, exit_code: i64) {
+        let Some(window_id) = self.dap_window(session_id) else {
+            return;
+        };
+        if let Some(session) = self.dap_session_mut(session_id) {
+            session.push_output(&t!("debug.exited", code = exit_code));
+        }
+        if let Some(window) = self.windows.get_mut(&window_id) {
+            window.set_status_message(t!("debug.exited", code = exit_code).to_string());
+        }
+    }
+
+    pub(crate) fn handle_dap_terminated(&mut self, session_id: u64) {
+        let Some(window_id) = self.dap_window(session_id) else {
+            return;
+        };
+        if let Some(window) = self.windows.get_mut(&window_id) {
+            window.dap.end_session(session_id);
+        }
+        self.clear_debug_stop_markers(window_id);
+        self.refresh_debug_panel(window_id);
+        if let Some(window) = self.windows.get_mut(&window_id) {
+            window.set_status_message(t!("debug.terminated").to_string());
+        }
+    }
+
+    pub(crate) fn handle_dap_error(&mut self, session_id: u64, error: String) {
+        tracing::warn!("Debug session {}: {}", session_id, error);
+        let Some(window_id) = self.dap_window(session_id) else {
+            return;
+        };
+        if let Some(window) = self.windows.get_mut(&window_id) {
+            window.set_status_message(t!("debug.error", error = error).to_string());
+        }
+    }
+
+    // ---- rendering -------------------------------------------------
+
+    /// Open the frame's source at its line in the active window.
+    fn show_debug_frame(&mut self, frame: &StackFrame) {
+        let Some(path) = frame.path() else {
+            return;
+        };
+        if let Err(e) = self.handle_open_file_at_location(
+            path.into(),
+            Some(frame.line.max(1)),
+            Some(frame.column.max(1)),
+        ) {
+            tracing::warn!("Failed to open {} for debug frame: {}", path, e);
+        }
+    }
+
+    /// Mark the frame's line in the margin and annotate the lines above it
+    /// with the values of the variables they mention.
+    fn mark_debug_stop(&mut self, window_id: fresh_core::WindowId, frame: &StackFrame) {
+        self.clear_debug_stop_markers(window_id);
+        let Some(path) = frame.path() else {
+            return;
+        };
+        let Some(window) = self.windows.get_mut(&window_id) else {
+            return;
+        };
+        let values: Vec<(String, String)> = window
+            .dap
+            .session()
+            .map(|s| {
+                s.scopes
+                    .iter()
+                    .flat_map(|scope| &scope.variables)
+                    .map(|v| (v.name.clone(), v.value.clone()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let Some(buffer_id) = window
+            .buffers
+            .find_id(|_, state| state.buffer.file_path() == Some(std::path::Path::new(path)))
+        else {
+            return;
+        };
+        let Some(state) = window.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        let stop_line = frame.line.saturating_sub(1);
+        let Some(line_start) = state.buffer.line_start_offset(stop_line) else {
+            return;
+        };
+        state.margins.set_line_indicator(
+            line_start,
+            STOPPED_NS.to_string(),
+            LineIndicator::new("▶", Color::Yellow, 60),
+        );
+        add_inline_values(state, stop_line, &values);
+    }
+
+    /// Remove the stop indicator and inline values from every buffer of
+    /// the window.
+    fn clear_debug_stop_markers(&mut self, window_id: fresh_core::WindowId) {
+        let Some(window) = self.windows.get_mut(&window_id) else {
+            return;
+        };
+        for state in window.buffers.as_map_mut().values_mut() {
+            state
+                .margins
+                .clear_line_indicators_for_namespace(STOPPED_NS);
+            state
+                .virtual_texts
+                .remove_by_prefix(&mut state.marker_list, INLINE_VALUE_PREFIX);
+        }
+    }
+
+    /// Redraw the debug panel of a window from its session, if the panel
+    /// is open.
+    fn refresh_debug_panel(&mut self, window_id: fresh_core::WindowId) {
+        let Some(window) = self.windows.get_mut(&window_id) else {
+            return;
+        };
+        let Some(group) = window
+            .debug_panel
+            .and_then(|id| window.buffer_groups.get(&id))
+        else {
+            window.debug_panel = None;
+            return;
+        };
+        let panels: Vec<_> = ["stack", "variables", "output"]
+            .into_iter()
+            .filter_map(|name| group.panel_buffers.get(name).map(|&b| (name, b)))
+            .collect();
+        let session = window.dap.session();
+        let contents: Vec<_> = panels
+            .into_iter()
+            .map(|(name, buffer_id)| {
+                let entries = match name {
+                    "stack" => stack_panel_entries(session),
+                    "variables" => variables_panel_entries(session),
+                    _ => output_panel_entries(session),
+                };
+                (name, buffer_id, entries)
+            })
+            .collect();
+        for (name, buffer_id, entries) in contents {
+            if let Err(e) = window.set_virtual_buffer_content(buffer_id, entries) {
+                tracing::warn!("Failed to fill debug panel '{}': {}", name, e);
+            }
+        }
+    }
+
+    /// Make the debug panel's group tab the active one.
+    fn focus_debug_panel(&mut self, group_id: BufferGroupId) {
+        let Some(leaf) = self
+            .active_window()
+            .buffer_groups
+            .get(&group_id)
+            .and_then(|g| g.representative_split)
+        else {
+            return;
+        };
+        let host = self
+            .active_window()
+            .buffers
+            .splits()
+            .and_then(|(_, view_states)| {
+                view_states
+                    .iter()
+                    .find(|(_, vs)| vs.has_group(leaf))
+                    .map(|(id, _)| *id)
+            });
+        if let Some(host) = host {
+            self.activate_group_tab(host, leaf);
+        }
+    }
+
+    /// Register the built-in `"debug-panel"` buffer mode: `Enter` selects
+    /// a stack frame, `c`/`n`/`s`/`o` continue and step, `q` closes.
+    fn ensure_debug_panel_mode_registered(&mut self) {
+        let mode_ctx = KeyContext::Mode(DEBUG_PANEL_MODE.to_string());
+        {
+            let mut kb = self.keybindings.write().unwrap();
+            kb.clear_plugin_defaults_for_mode(DEBUG_PANEL_MODE);
+            kb.set_mode_inherits_normal_bindings(DEBUG_PANEL_MODE, true);
+            for (code, action) in [
+                (KeyCode::Enter, Action::DebugSelectFrame),
+                (KeyCode::Char('c'), Action::DebugContinue),
+                (KeyCode::Char('n'), Action::DebugStepOver),
+                (KeyCode::Char('s'), Action::DebugStepInto),
+                (KeyCode::Char('o'), Action::DebugStepOut),
+                (KeyCode::Char('q'), Action::CloseTab),
+            ] {
+                kb.load_plugin_default(mode_ctx.clone(), code, KeyModifiers::NONE, action);
+            }
+        }
+        self.mode_registry.register(
+            BufferMode::new(DEBUG_PANEL_MODE)
+                .with_read_only(true)
+                .with_inherit_normal_bindings(true),
+        );
+    }
+}
+
+/// The 1-based lines of a buffer's breakpoints, sorted and deduplicated.
+fn breakpoint_lines(state: &EditorState) -> Vec<usize> {
+    let mut lines: Vec<usize> = state
+        .margins
+        .line_indicators_in_namespace(BREAKPOINT_NS)
+        .into_iter()
+        .map(|(_, pos)| state.buffer.get_line_number(pos) + 1)
+        .collect();
+    lines.dedup();
+    lines
+}
+
+/// Show `name = value` at the end of each line, from a few lines above the
+/// stop line down to it, that mentions one of the variables.
+fn add_inline_values(state: &mut EditorState, stop_line: usize, values: &[(String, String)]) {
+    if values.is_empty() {
+        return;
+    }
+    let style = Style::default().fg(Color::Rgb(128, 128, 128));
+    for line in stop_line.saturating_sub(INLINE_VALUE_LOOKBACK)..=stop_line {
+        let (Some(start), Some(bytes)) = (
+            state.buffer.line_start_offset(line),
+            state.buffer.get_line(line),
+        ) else {
+            continue;
+        };
+        let Some(last_char) = last_char_start(&bytes) else {
+            continue;
+        };
+        let text = String::from_utf8_lossy(&bytes);
+        let content = text.trim_end_matches(['\n', '\r']);
+        let shown: Vec<String> = values
+            .iter()
+            .filter(|(name, _)| mentions_word(content, name))
+            .map(|(name, value)| format!("{} = {}", name, truncate_value(value)))
+            .collect();
+        if shown.is_empty() {
+            continue;
+        }
+        state.virtual_texts.add_with_id_and_theme_keys(
+            &mut state.marker_list,
+            start + last_char,
+            format!(" {}", shown.join(", ")),
+            style,
+            Some("editor.line_number_fg".to_string()),
+            None,
+            VirtualTextPosition::AfterChar,
+            0,
+            format!("{}{}", INLINE_VALUE_PREFIX, line),
+        );
+    }
+}
+
+/// Byte offset of the last character of a line (less its line ending), read
+/// from the raw bytes so a multibyte or invalid UTF-8 ending can't shift it.
+/// `None` for an empty line.
+fn last_char_start(line: &[u8]) -> Option<usize> {
+    let end = line.iter().rposition(|b| !matches!(b, b'\n' | b'\r'))? + 1;
+    // Back over UTF-8 continuation bytes to the lead byte, at most three.
+    let mut start = end - 1;
+    while start > 0 && end - start < 4 && line[start] & 0xC0 == 0x80 {
+        start -= 1;
+    }
+    Some(start)
+}
+
+/// Whether `word` occurs in `text` delimited by non-identifier characters.
+fn mentions_word(text: &str, word: &str) -> bool {
+    if word.is_empty() {
+        return false;
+    }
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    text.match_indices(word).any(|(i, _)| {
+        let before = text[..i].chars().next_back();
+        let after = text[i + word.len()..].chars().next();
+        !before.is_some_and(is_ident) && !after.is_some_and(is_ident)
+    })
+}
+
+fn truncate_value(value: &str) -> String {
+    let value = value.replace('\n', " ");
+    if value.chars().count() <= MAX_INLINE_VALUE_LEN {
+        return value;
+    }
+    let cut: String = value.chars().take(MAX_INLINE_VALUE_LEN - 1).collect();
+    format!("{}…", cut)
+}
+
+fn stack_panel_entries(session: Option<&DapSession>) -> Vec<TextPropertyEntry> {
+    let mut entries = vec![TextPropertyEntry::text(format!(
+        "{}\n",
+        t!("debug.panel_stack")
+    ))];
+    let Some(session) = session else {
+        entries.push(TextPropertyEntry::text(format!(
+            "  {}\n",
+            t!("debug.panel_no_session")
+        )));
+        return entries;
+    };
+    if !session.is_stopped() {
+        entries.push(TextPropertyEntry::text(format!(
+            "  {}\n",
+            t!("debug.panel_running", name = session.name)
+        )));
+        return entries;
+    }
+    for frame in &session.frames {
+        let marker = if session.selected_frame == Some(frame.id) {
+            '▶'
+        } else {
+            ' '
+        };
+        let location = frame
+            .source
+            .as_ref()
+            .and_then(|s| s.name.clone().or_else(|| s.path.clone()))
+            .map(|name| format!("  {}:{}", name, frame.line))
+            .unwrap_or_default();
+        let mut entry = TextPropertyEntry::text(format!("{} {}{}\n", marker, frame.name, location));
+        entry
+            .properties
+            .insert(FRAME_PROPERTY.to_string(), serde_json::json!(frame.id));
+        entries.push(entry);
+    }
+    entries
+}
+
+fn variables_panel_entries(session: Option<&DapSession>) -> Vec<TextPropertyEntry> {
+    let mut text = format!("{}\n", t!("debug.panel_variables"));
+    for scope in session.map(|s| s.scopes.as_slice()).unwrap_or_default() {
+        text.push_str(&format!("▾ {}\n", scope.scope.name));
+        for variable in &scope.variables {
+            let type_name = variable
+                .type_name
+                .as_ref()
+                .map(|t| format!(": {}", t))
+                .unwrap_or_default();
+            text.push_str(&format!(
+                "    {}{} = {}\n",
+                variable.name,
+                type_name,
+                variable.value.replace('\n', " ")
+            ));
+        }
+    }
+    vec![TextPropertyEntry::text(text)]
+}
+
+fn output_panel_entries(session: Option<&DapSession>) -> Vec<TextPropertyEntry> {
+    let mut text = format!("{}\n", t!("debug.panel_output"));
+    for line in session.map(|s| s.output.as_slice()).unwrap_or_default() {
+        text.push_str(line);
+        text.push('\n');
+    }
+    vec![TextPropertyEntry::text(text)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mentions_word() {
+        assert!(mentions_word("let y = x + 1;", "x"));
+        assert!(mentions_word("x", "x"));
+        assert!(!mentions_word("let max = 1;", "x"));
+        assert!(!mentions_word("let x_1 = 1;", "x"));
+        assert!(!mentions_word("anything", ""));
+    }
+
+    #[test]
+    fn test_last_char_start() {
+        assert_eq!(last_char_start(b"let x = 1;\n"), Some(9));
+        assert_eq!(last_char_start("s = \"é\"\r\n".as_bytes()), Some(7));
+        assert_eq!(last_char_start("x = é".as_bytes()), Some(4));
+        assert_eq!(last_char_start("name = 日本".as_bytes()), Some(10));
+        // Invalid UTF-8 keeps its raw offsets.
+        assert_eq!(last_char_start(b"x = \xff\xfe\n"), Some(5));
+        assert_eq!(last_char_start(b"\r\n"), None);
+        assert_eq!(last_char_start(b""), None);
+    }
+
+    #[test]
+    fn test_truncate_value() {
+        assert_eq!(truncate_value("42"), "42");
+        assert_eq!(truncate_value("a\nb"), "a b");
+        let long = "x".repeat(100);
+        let cut = truncate_value(&long);
+        assert_eq!(cut.chars().count(), MAX_INLINE_VALUE_LEN);
+        assert!(cut.ends_with('…'));
+    }
+}
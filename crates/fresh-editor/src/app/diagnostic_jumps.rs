@@ -2,8 +2,11 @@
 //!
 //! `jump_to_next_error` and `jump_to_previous_error` walk the active
 //! buffer's diagnostic overlays, find the nearest one in each direction,
-//! and emit a MoveCursor event. Status messages report the diagnostic at
-//! the new cursor position.
+//! and emit a MoveCursor event. Past the buffer's last (or before its
+//! first) diagnostic they continue into the next file with diagnostics —
+//! opening it if needed, since a workspace pull reports on files that are
+//! not open — and only wrap within the buffer when no other file has any.
+//! Status messages report the diagnostic at the new cursor position.
 
 use rust_i18n::t;
 
@@ -14,144 +17,159 @@ use super::Editor;
 impl Editor {
     /// Jump to next error/diagnostic
     pub(super) fn jump_to_next_error(&mut self) {
-        let diagnostic_ns = self.active_window().lsp_diagnostic_namespace.clone();
         let cursor_pos = self.active_cursors().primary().position;
-        let cursor_id = self.active_cursors().primary_id();
-        let cursor = *self.active_cursors().primary();
-        let state = self.active_state_mut();
-
-        // Get all diagnostic overlay positions
-        let mut diagnostic_positions: Vec<usize> = state
-            .overlays
-            .all()
-            .iter()
-            .filter_map(|overlay| {
-                // Only consider LSP diagnostics (those in the diagnostic namespace)
-                if overlay.namespace.as_ref() == Some(&diagnostic_ns) {
-                    Some(overlay.range(&state.marker_list).start)
-                } else {
-                    None
-                }
-            })
-            .collect();
+        let positions = self.diagnostic_positions();
 
-        if diagnostic_positions.is_empty() {
-            self.set_status_message(t!("diagnostics.none").to_string());
+        if let Some(&pos) = positions.iter().find(|&&pos| pos > cursor_pos) {
+            self.move_cursor_to_diagnostic(pos);
             return;
         }
-
-        // Sort positions
-        diagnostic_positions.sort_unstable();
-        diagnostic_positions.dedup();
-
-        // Find next diagnostic after cursor position
-        let next_pos = diagnostic_positions
-            .iter()
-            .find(|&&pos| pos > cursor_pos)
-            .or_else(|| diagnostic_positions.first()) // Wrap around
-            .copied();
-
-        if let Some(new_pos) = next_pos {
-            let event = Event::MoveCursor {
-                cursor_id,
-                old_position: cursor.position,
-                new_position: new_pos,
-                old_anchor: cursor.anchor,
-                new_anchor: None,
-                old_sticky_column: cursor.sticky_column,
-                new_sticky_column: None,
-            };
-            self.active_event_log_mut().append(event.clone());
-            self.apply_event_to_active_buffer(&event);
-            // Diagnostics can be on any line; the viewport must scroll so the
-            // user actually sees the error after pressing F8 (#1689).
-            self.active_window_mut()
-                .ensure_active_cursor_visible_for_navigation(true);
-
-            // Show diagnostic message in status bar
-            let state = self.active_state();
-            if let Some(msg) = state.overlays.all().iter().find_map(|overlay| {
-                let range = overlay.range(&state.marker_list);
-                if range.start == new_pos && overlay.namespace.as_ref() == Some(&diagnostic_ns) {
-                    overlay.message.clone()
-                } else {
-                    None
-                }
-            }) {
-                self.set_status_message(msg);
-            }
+        if self.jump_to_diagnostic_in_other_file(true) {
+            return;
+        }
+        // Wrap around
+        match positions.first() {
+            Some(&pos) => self.move_cursor_to_diagnostic(pos),
+            None => self.set_status_message(t!("diagnostics.none").to_string()),
         }
     }
 
     /// Jump to previous error/diagnostic
     pub(super) fn jump_to_previous_error(&mut self) {
-        let diagnostic_ns = self.active_window().lsp_diagnostic_namespace.clone();
         let cursor_pos = self.active_cursors().primary().position;
-        let cursor_id = self.active_cursors().primary_id();
-        let cursor = *self.active_cursors().primary();
-        let state = self.active_state_mut();
+        let positions = self.diagnostic_positions();
+
+        if let Some(&pos) = positions.iter().rev().find(|&&pos| pos < cursor_pos) {
+            self.move_cursor_to_diagnostic(pos);
+            return;
+        }
+        if self.jump_to_diagnostic_in_other_file(false) {
+            return;
+        }
+        // Wrap around
+        match positions.last() {
+            Some(&pos) => self.move_cursor_to_diagnostic(pos),
+            None => self.set_status_message(t!("diagnostics.none").to_string()),
+        }
+    }
 
-        // Get all diagnostic overlay positions
-        let mut diagnostic_positions: Vec<usize> = state
+    /// Start positions of the active buffer's diagnostic overlays, sorted and
+    /// deduplicated.
+    fn diagnostic_positions(&self) -> Vec<usize> {
+        let diagnostic_ns = &self.active_window().lsp_diagnostic_namespace;
+        let state = self.active_state();
+        let mut positions: Vec<usize> = state
             .overlays
             .all()
             .iter()
-            .filter_map(|overlay| {
-                // Only consider LSP diagnostics (those in the diagnostic namespace)
-                if overlay.namespace.as_ref() == Some(&diagnostic_ns) {
-                    Some(overlay.range(&state.marker_list).start)
-                } else {
-                    None
-                }
-            })
+            // Only consider LSP diagnostics (those in the diagnostic namespace)
+            .filter(|overlay| overlay.namespace.as_ref() == Some(diagnostic_ns))
+            .map(|overlay| overlay.range(&state.marker_list).start)
             .collect();
+        positions.sort_unstable();
+        positions.dedup();
+        positions
+    }
 
-        if diagnostic_positions.is_empty() {
-            self.set_status_message(t!("diagnostics.none").to_string());
-            return;
+    /// Move the primary cursor to `pos` and show the diagnostic there.
+    fn move_cursor_to_diagnostic(&mut self, pos: usize) {
+        self.move_primary_cursor_to(pos);
+
+        // Show diagnostic message in status bar
+        let diagnostic_ns = self.active_window().lsp_diagnostic_namespace.clone();
+        let state = self.active_state();
+        if let Some(msg) = state.overlays.all().iter().find_map(|overlay| {
+            let range = overlay.range(&state.marker_list);
+            if range.start == pos && overlay.namespace.as_ref() == Some(&diagnostic_ns) {
+                overlay.message.clone()
+            } else {
+                None
+            }
+        }) {
+            self.set_status_message(msg);
         }
+    }
 
-        // Sort positions
-        diagnostic_positions.sort_unstable();
-        diagnostic_positions.dedup();
+    fn move_primary_cursor_to(&mut self, pos: usize) {
+        let cursor_id = self.active_cursors().primary_id();
+        let cursor = *self.active_cursors().primary();
+        let event = Event::MoveCursor {
+            cursor_id,
+            old_position: cursor.position,
+            new_position: pos,
+            old_anchor: cursor.anchor,
+            new_anchor: None,
+            old_sticky_column: cursor.sticky_column,
+            new_sticky_column: None,
+        };
+        self.active_event_log_mut().append(event.clone());
+        self.apply_event_to_active_buffer(&event);
+        // Diagnostics can be on any line; the viewport must scroll so the
+        // user actually sees the error after pressing F8 (#1689).
+        self.active_window_mut()
+            .ensure_active_cursor_visible_for_navigation(true);
+    }
 
-        // Find previous diagnostic before cursor position
-        let prev_pos = diagnostic_positions
+    /// Open the next (or, going backwards, the previous) file in path order
+    /// that has diagnostics and put the cursor on its first (or last) one.
+    /// Returns false when no other file has diagnostics.
+    fn jump_to_diagnostic_in_other_file(&mut self, forward: bool) -> bool {
+        let current = self
+            .active_window()
+            .buffer_metadata
+            .get(&self.active_buffer())
+            .and_then(|meta| meta.file_uri())
+            .map(|uri| uri.as_str().to_string());
+        let translation = self.authority().path_translation.clone();
+
+        let mut files: Vec<(std::path::PathBuf, lsp_types::Diagnostic)> = self
+            .active_window()
+            .stored_diagnostics
             .iter()
-            .rev()
-            .find(|&&pos| pos < cursor_pos)
-            .or_else(|| diagnostic_positions.last()) // Wrap around
-            .copied();
-
-        if let Some(new_pos) = prev_pos {
-            let event = Event::MoveCursor {
-                cursor_id,
-                old_position: cursor.position,
-                new_position: new_pos,
-                old_anchor: cursor.anchor,
-                new_anchor: None,
-                old_sticky_column: cursor.sticky_column,
-                new_sticky_column: None,
-            };
-            self.active_event_log_mut().append(event.clone());
-            self.apply_event_to_active_buffer(&event);
-            // Diagnostics can be on any line; the viewport must scroll so the
-            // user actually sees the error after pressing F8 (#1689).
-            self.active_window_mut()
-                .ensure_active_cursor_visible_for_navigation(true);
-
-            // Show diagnostic message in status bar
-            let state = self.active_state();
-            if let Some(msg) = state.overlays.all().iter().find_map(|overlay| {
-                let range = overlay.range(&state.marker_list);
-                if range.start == new_pos && overlay.namespace.as_ref() == Some(&diagnostic_ns) {
-                    overlay.message.clone()
+            .filter(|(uri, _)| Some(uri.as_str()) != current.as_deref())
+            .filter_map(|(uri, diagnostics)| {
+                let path = uri.parse::<lsp_types::Uri>().ok().and_then(|uri| {
+                    crate::app::types::LspUri::from_wire(uri).to_host_path(translation.as_ref())
+                })?;
+                let key =
+                    |d: &&lsp_types::Diagnostic| (d.range.start.line, d.range.start.character);
+                let diagnostic = if forward {
+                    diagnostics.iter().min_by_key(key)
                 } else {
-                    None
-                }
-            }) {
-                self.set_status_message(msg);
-            }
+                    diagnostics.iter().max_by_key(key)
+                }?;
+                Some((path, diagnostic.clone()))
+            })
+            .collect();
+        if files.is_empty() {
+            return false;
+        }
+        files.sort_by(|a, b| a.0.cmp(&b.0));
+
+        // The first file past the current one, wrapping around the list.
+        let current_path = self
+            .active_state()
+            .buffer
+            .file_path()
+            .map(|p| p.to_path_buf());
+        let index = match &current_path {
+            Some(current) if forward => files.iter().position(|(p, _)| p > current),
+            Some(current) => files.iter().rposition(|(p, _)| p < current),
+            None => None,
+        };
+        let index = index.unwrap_or(if forward { 0 } else { files.len() - 1 });
+        let (path, diagnostic) = files.swap_remove(index);
+
+        if let Err(e) = self.open_file(&path) {
+            self.set_status_message(t!("file.error_opening", error = e.to_string()).to_string());
+            return true;
         }
+        let pos = self.active_state().buffer.lsp_position_to_byte(
+            diagnostic.range.start.line as usize,
+            diagnostic.range.start.character as usize,
+        );
+        self.move_primary_cursor_to(pos);
+        self.set_status_message(diagnostic.message);
+        true
     }
 }
@@ -0,0 +1,717 @@
+//! Document links: URLs and file references in editor buffers.
+//!
+//! Links come from two places. A built-in detector scans the visible lines
+//! of each split whenever the buffer or the viewport changed, finding URLs
+//! and compiler-style `path:line:col` references. A server providing
+//! `textDocument/documentLink` is asked for the whole document once per
+//! buffer version, after the buffer settles. Both kinds get an underline
+//! overlay whose `url` is the link target; file targets are `file://` URIs
+//! with a `#L<line>,<col>` fragment.
+//!
+//! The detector is purely textual, since it runs on the render path and a
+//! stat through a remote authority is a network round trip. A `path:line`
+//! reference is only resolved to a file when it is opened; one that names
+//! no file is underlined but inert.
+//!
+//! Ctrl+Click on a link, or picking it from "List Links in File", opens it:
+//! a file in the editor at the linked position, a URI whose scheme a plugin
+//! registered through the `lsp_open_external_uri` hook, anything else with
+//! the system's default handler.
+
+use std::ops::Range;
+use std::path::Path;
+
+use ratatui::style::{Modifier, Style};
+use rust_i18n::t;
+
+use super::Editor;
+use crate::app::types::LspUri;
+use crate::input::commands::Suggestion;
+use crate::model::event::BufferId;
+use crate::services::terminal::path_link;
+use crate::types::LspFeature;
+use crate::view::overlay::{Overlay, OverlayFace, OverlayNamespace};
+use crate::view::prompt::{Prompt, PromptType};
+
+/// Namespace of the URLs found by the built-in detector.
+const DETECTED_LINKS_NAMESPACE: &str = "document-links";
+
+/// Namespace of the detector's `path:line:col` references, whose `url` is
+/// the unresolved reference (see [`location_reference`]).
+const LOCATION_LINKS_NAMESPACE: &str = "document-location-links";
+
+/// Namespace of the links reported by the language server.
+const LSP_LINKS_NAMESPACE: &str = "lsp-document-links";
+
+/// Schemes the built-in detector turns into links.
+const URL_SCHEMES: &[&str] = &["http", "https", "ftp", "file"];
+
+/// Longer lines (minified code, data files) are not scanned.
+const MAX_SCANNED_LINE_LEN: usize = 4096;
+
+fn detected_links_namespace() -> OverlayNamespace {
+    OverlayNamespace::from_string(DETECTED_LINKS_NAMESPACE.to_string())
+}
+
+fn location_links_namespace() -> OverlayNamespace {
+    OverlayNamespace::from_string(LOCATION_LINKS_NAMESPACE.to_string())
+}
+
+fn lsp_links_namespace() -> OverlayNamespace {
+    OverlayNamespace::from_string(LSP_LINKS_NAMESPACE.to_string())
+}
+
+fn link_face() -> OverlayFace {
+    OverlayFace::Style {
+        style: Style::default().add_modifier(Modifier::UNDERLINED),
+    }
+}
+
+/// Byte ranges of the URLs in `line`: a known scheme, `://`, and everything
+/// up to whitespace or a quote, less trailing sentence punctuation and any
+/// closing bracket without an opening partner inside the URL (so Markdown's
+/// `[text](https://…)` and a parenthesised URL both stop at the right place).
+fn find_urls(line: &str) -> Vec<Range<usize>> {
+    let mut urls = Vec::new();
+    let mut from = 0;
+    while let Some(found) = line[from..].find("://") {
+        let separator = from + found;
+        from = separator + 3;
+        let start = line[..separator]
+            .char_indices()
+            .rev()
+            .take_while(|(_, c)| c.is_ascii_alphanumeric())
+            .last()
+            .map_or(separator, |(i, _)| i);
+        let scheme = &line[start..separator];
+        if !URL_SCHEMES.iter().any(|s| s.eq_ignore_ascii_case(scheme)) {
+            continue;
+        }
+        let rest = &line[from..];
+        let mut end = from
+            + rest
+                .find(|c: char| c.is_whitespace() || matches!(c, '"' | '\'' | '`' | '<' | '>'))
+                .unwrap_or(rest.len());
+        while let Some(last) = line[start..end].chars().last() {
+            let url = &line[start..end];
+            let unbalanced =
+                |open: char, close: char| url.matches(close).count() > url.matches(open).count();
+            let trim = match last {
+                '.' | ',' | ';' | ':' | '!' | '?' => true,
+                ')' => unbalanced('(', ')'),
+                ']' => unbalanced('[', ']'),
+                '}' => unbalanced('{', '}'),
+                _ => false,
+            };
+            if !trim {
+                break;
+            }
+            end -= last.len_utf8();
+        }
+        if end > from {
+            urls.push(start..end);
+            from = end;
+        }
+    }
+    urls
+}
+
+/// A link found by the built-in detector.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum DetectedLink {
+    /// A URL, which is its own target.
+    Url(String),
+    /// A `path:line[:col]` reference, not yet resolved to a file.
+    Location {
+        path: String,
+        line: Option<usize>,
+        column: Option<usize>,
+    },
+}
+
+/// The built-in links in `text`, which starts at byte `offset` of a buffer:
+/// its URLs and its `path:line:col` references. Textual only — nothing is
+/// looked up on disk.
+fn detect_links(text: &str, offset: usize) -> Vec<(Range<usize>, DetectedLink)> {
+    let mut links = Vec::new();
+    let mut line_start = offset;
+    for line in text.split_inclusive('\n') {
+        let content = line.trim_end_matches(['\n', '\r']);
+        if content.len() <= MAX_SCANNED_LINE_LEN {
+            let urls = find_urls(content);
+            for url in &urls {
+                links.push((
+                    line_start + url.start..line_start + url.end,
+                    DetectedLink::Url(content[url.clone()].to_string()),
+                ));
+            }
+            for link in path_link::detect_location_links(content) {
+                let bytes = char_range_to_bytes(content, &link.range);
+                if urls
+                    .iter()
+                    .any(|u| u.start < bytes.end && bytes.start < u.end)
+                {
+                    continue;
+                }
+                links.push((
+                    line_start + bytes.start..line_start + bytes.end,
+                    DetectedLink::Location {
+                        path: link.path,
+                        line: link.line,
+                        column: link.column,
+                    },
+                ));
+            }
+        }
+        line_start += line.len();
+    }
+    links.sort_by_key(|(range, _)| range.start);
+    links
+}
+
+/// `#L<line>[,<col>]`, the position fragment of a file link.
+fn position_fragment(line: Option<usize>, column: Option<usize>) -> String {
+    match (line, column) {
+        (Some(line), Some(column)) => format!("#L{line},{column}"),
+        (Some(line), None) => format!("#L{line}"),
+        _ => String::new(),
+    }
+}
+
+/// How an unresolved `path:line:col` reference is kept in an overlay's
+/// `url`: the path with its position fragment. The fragment is split off
+/// the last `#`, so a path containing one survives.
+fn location_reference(path: &str, line: Option<usize>, column: Option<usize>) -> String {
+    format!("{path}{}", position_fragment(line, column))
+}
+
+/// Byte range within `line` of a character range.
+fn char_range_to_bytes(line: &str, range: &Range<usize>) -> Range<usize> {
+    let byte_at = |col: usize| line.char_indices().nth(col).map_or(line.len(), |(b, _)| b);
+    byte_at(range.start)..byte_at(range.end)
+}
+
+/// Split a `#L<line>[,<col>]` (or `#<line>[:<col>]`) position fragment off
+/// a link target. Targets without one come back whole.
+fn split_link_position(target: &str) -> (&str, Option<usize>, Option<usize>) {
+    match target
+        .split_once('#')
+        .and_then(|(uri, fragment)| Some((uri, parse_position_fragment(fragment)?)))
+    {
+        Some((uri, (line, column))) => (uri, Some(line), column),
+        None => (target, None, None),
+    }
+}
+
+/// Split the position fragment off a [`location_reference`].
+fn split_location_reference(reference: &str) -> (&str, Option<usize>, Option<usize>) {
+    match reference
+        .rsplit_once('#')
+        .and_then(|(path, fragment)| Some((path, parse_position_fragment(fragment)?)))
+    {
+        Some((path, (line, column))) => (path, Some(line), column),
+        None => (reference, None, None),
+    }
+}
+
+/// The line and column of a `L<line>[,<col>]` or `<line>[:<col>]` fragment.
+fn parse_position_fragment(fragment: &str) -> Option<(usize, Option<usize>)> {
+    let fragment = fragment.strip_prefix('L').unwrap_or(fragment);
+    match fragment.split_once([',', ':']) {
+        Some((line, column)) => Some((line.parse().ok()?, column.parse().ok())),
+        None => Some((fragment.parse().ok()?, None)),
+    }
+}
+
+impl crate::app::window::Window {
+    /// Forget everything document-link related for a buffer and remove its
+    /// underlines. The visible part is scanned again on the next frame.
+    pub(crate) fn clear_document_links_for_buffer(&mut self, buffer_id: BufferId) {
+        self.document_link_requests.clear(buffer_id);
+        self.document_links_scanned.remove(&buffer_id);
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            for ns in [
+                lsp_links_namespace(),
+                detected_links_namespace(),
+                location_links_namespace(),
+            ] {
+                state.overlays.clear_namespace(&ns, &mut state.marker_list);
+            }
+        }
+    }
+
+    /// Handle a `textDocument/documentLink` response. Links the server left
+    /// without a target are skipped.
+    pub fn handle_lsp_document_links(
+        &mut self,
+        request_id: u64,
+        uri: String,
+        links: Vec<lsp_types::DocumentLink>,
+    ) {
+        let buffers = &self.buffers;
+        let Some((buffer_id, _)) =
+            self.document_link_requests
+                .settle(request_id, "document links", |id| {
+                    buffers.get(&id).map(|s| s.buffer.version())
+                })
+        else {
+            return;
+        };
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+
+        tracing::info!(
+            "Received {} document links for {} (request_id={})",
+            links.len(),
+            uri,
+            request_id
+        );
+        let ns = lsp_links_namespace();
+        state.overlays.clear_namespace(&ns, &mut state.marker_list);
+        let overlays: Vec<Overlay> = links
+            .into_iter()
+            .filter_map(|link| {
+                let target = link.target?;
+                let start = state.buffer.lsp_position_to_byte(
+                    link.range.start.line as usize,
+                    link.range.start.character as usize,
+                );
+                let end = state.buffer.lsp_position_to_byte(
+                    link.range.end.line as usize,
+                    link.range.end.character as usize,
+                );
+                if start >= end {
+                    return None;
+                }
+                let mut overlay = Overlay::with_namespace_fixed_end(
+                    &mut state.marker_list,
+                    start..end,
+                    link_face(),
+                    ns.clone(),
+                );
+                overlay.url = Some(target.as_str().to_string());
+                Some(overlay)
+            })
+            .collect();
+        state.overlays.extend(overlays);
+    }
+}
+
+impl Editor {
+    /// Underline the built-in links in the visible part of every split.
+    /// Called once per frame; a buffer is only scanned again when it or
+    /// its visible range changed.
+    pub(super) fn update_document_links(&mut self) {
+        if !self.config.editor.document_links {
+            self.clear_detected_links();
+            return;
+        }
+        let url_ns = detected_links_namespace();
+        let location_ns = location_links_namespace();
+        for (buffer_id, range) in self.visible_line_ranges() {
+            let win = self.active_window_mut();
+            if win
+                .buffer_metadata
+                .get(&buffer_id)
+                .is_none_or(|m| m.is_virtual() || m.binary)
+            {
+                continue;
+            }
+            let Some(state) = win.buffers.get_mut(&buffer_id) else {
+                continue;
+            };
+            let scanned = (state.buffer.version(), range.clone());
+            if win.document_links_scanned.get(&buffer_id) == Some(&scanned) {
+                continue;
+            }
+            let text = state.get_text_range(range.start, range.end);
+            let mut urls = Vec::new();
+            let mut locations = Vec::new();
+            for (r, link) in detect_links(&text, range.start) {
+                match link {
+                    DetectedLink::Url(url) => urls.push((r, url)),
+                    DetectedLink::Location { path, line, column } => {
+                        locations.push((r, location_reference(&path, line, column)))
+                    }
+                }
+            }
+            for (ns, links) in [(url_ns.clone(), urls), (location_ns.clone(), locations)] {
+                let overlays = links
+                    .into_iter()
+                    .map(|(r, target)| {
+                        let mut overlay = Overlay::with_namespace_fixed_end(
+                            &mut state.marker_list,
+                            r,
+                            link_face(),
+                            ns.clone(),
+                        );
+                        overlay.url = Some(target);
+                        overlay
+                    })
+                    .collect();
+                state.overlays.replace_range_in_namespace(
+                    &ns,
+                    &range,
+                    overlays,
+                    &mut state.marker_list,
+                );
+            }
+            win.document_links_scanned.insert(buffer_id, scanned);
+        }
+    }
+
+    /// Remove every built-in link underline so the next frame scans afresh.
+    fn clear_detected_links(&mut self) {
+        let win = self.active_window_mut();
+        if win.document_links_scanned.is_empty() {
+            return;
+        }
+        let buffer_ids: Vec<BufferId> = win
+            .document_links_scanned
+            .drain()
+            .map(|(id, _)| id)
+            .collect();
+        for buffer_id in buffer_ids {
+            if let Some(state) = win.buffers.get_mut(&buffer_id) {
+                for ns in [detected_links_namespace(), location_links_namespace()] {
+                    state.overlays.clear_namespace(&ns, &mut state.marker_list);
+                }
+            }
+        }
+    }
+
+    /// Resolve a `path:line:col` reference written in a buffer in directory
+    /// `dir` to a `file://` link target — against `dir` first, then the
+    /// working directory. `None` when it names no file.
+    fn resolve_location_link(
+        &self,
+        path: &str,
+        line: Option<usize>,
+        column: Option<usize>,
+        dir: Option<&Path>,
+    ) -> Option<String> {
+        let path = self.resolve_terminal_path(path, dir)?;
+        let translation = self.authority().path_translation.clone();
+        let uri = LspUri::from_host_path(&path, translation.as_ref())?;
+        Some(format!(
+            "{}{}",
+            uri.as_str(),
+            position_fragment(line, column)
+        ))
+    }
+
+    /// The target of the link at byte `position` of a buffer. The server's
+    /// links win over the built-in ones. A `path:line:col` reference is
+    /// resolved here, when it is opened; `None` if it names no file.
+    pub(super) fn document_link_at(&self, buffer_id: BufferId, position: usize) -> Option<String> {
+        let state = self.active_window().buffers.get(&buffer_id)?;
+        let at_position = state.overlays.at_position(position, &state.marker_list);
+        let url_in = |ns: &OverlayNamespace| {
+            at_position
+                .iter()
+                .find(|o| o.namespace.as_ref() == Some(ns))
+                .and_then(|o| o.url.clone())
+        };
+        if let Some(target) =
+            url_in(&lsp_links_namespace()).or_else(|| url_in(&detected_links_namespace()))
+        {
+            return Some(target);
+        }
+        let reference = url_in(&location_links_namespace())?;
+        let (path, line, column) = split_location_reference(&reference);
+        let dir = state.buffer.file_path().and_then(|p| p.parent());
+        self.resolve_location_link(path, line, column, dir)
+    }
+
+    /// Open a link target: a `file://` URI in the editor, at the position
+    /// its fragment names; a URI whose scheme a plugin claimed through the
+    /// `lsp_open_external_uri` hook; anything else with the system's
+    /// default handler.
+    pub(crate) fn open_document_link(&mut self, target: &str) {
+        let scheme = target
+            .split_once(':')
+            .map(|(scheme, _)| scheme.to_ascii_lowercase())
+            .unwrap_or_default();
+        let (uri, line, column) = split_link_position(target);
+
+        if scheme == "file" {
+            let Ok(uri) = uri.parse::<lsp_types::Uri>() else {
+                self.set_status_message(t!("links.invalid", url = target).to_string());
+                return;
+            };
+            match self.open_lsp_uri_target(&LspUri::from_wire(uri)) {
+                Ok(_) => {
+                    if line.is_some() {
+                        self.jump_to_line_column(line, column);
+                    }
+                }
+                Err(e) => self.set_status_message(
+                    t!("file.error_opening", error = e.to_string()).to_string(),
+                ),
+            }
+            return;
+        }
+
+        if self.lsp_uri_schemes.contains(&scheme) {
+            let language = self.active_state().language.clone();
+            let server_name = self
+                .lsp()
+                .and_then(|lsp| lsp.server_names_for_language(&language).into_iter().next())
+                .unwrap_or_default();
+            tracing::info!("Link '{}' handled by plugin for scheme '{}'", uri, scheme);
+            self.plugin_manager.read().unwrap().run_hook(
+                "lsp_open_external_uri",
+                crate::services::plugins::hooks::HookArgs::LspOpenExternalUri {
+                    uri: uri.to_string(),
+                    scheme,
+                    line: line.map_or(0, |l| l.saturating_sub(1)) as u32,
+                    character: column.map_or(0, |c| c.saturating_sub(1)) as u32,
+                    language,
+                    server_name,
+                },
+            );
+            return;
+        }
+
+        #[cfg(feature = "runtime")]
+        match open::that(target) {
+            Ok(()) => self.set_status_message(t!("links.opening", url = target).to_string()),
+            Err(e) => self.set_status_message(
+                t!("links.open_failed", url = target, error = e.to_string()).to_string(),
+            ),
+        }
+    }
+
+    /// Pick one of the current file's links and open it: the server's
+    /// links, plus the built-in ones found anywhere in the file (not only
+    /// where it was scrolled to).
+    pub fn list_document_links(&mut self) {
+        let buffer_id = self.active_buffer();
+        let lsp_ns = lsp_links_namespace();
+        let state = self.active_state();
+        let mut links: Vec<(Range<usize>, String)> = state
+            .overlays
+            .all()
+            .iter()
+            .filter(|o| o.namespace.as_ref() == Some(&lsp_ns))
+            .filter_map(|o| Some((o.range(&state.marker_list), o.url.clone()?)))
+            .collect();
+
+        let state = self.active_state_mut();
+        if !state.buffer.is_large_file() {
+            let text = state.get_text_range(0, state.buffer.len());
+            let dir = state
+                .buffer
+                .file_path()
+                .and_then(|p| p.parent())
+                .map(Path::to_path_buf);
+            // An explicit request, so references are resolved here; ones
+            // that name no file are left out.
+            let detected: Vec<(Range<usize>, String)> = detect_links(&text, 0)
+                .into_iter()
+                .filter_map(|(range, link)| match link {
+                    DetectedLink::Url(url) => Some((range, url)),
+                    DetectedLink::Location { path, line, column } => self
+                        .resolve_location_link(&path, line, column, dir.as_deref())
+                        .map(|target| (range, target)),
+                })
+                .collect();
+            for (range, target) in detected {
+                if !links
+                    .iter()
+                    .any(|(r, _)| r.start < range.end && range.start < r.end)
+                {
+                    links.push((range, target));
+                }
+            }
+        }
+        if links.is_empty() {
+            self.set_status_message(t!("links.none").to_string());
+            return;
+        }
+        links.sort_by_key(|(range, _)| range.start);
+
+        let state = self
+            .active_window_mut()
+            .buffers
+            .get_mut(&buffer_id)
+            .expect("active buffer present");
+        let suggestions = links
+            .into_iter()
+            .map(|(range, target)| {
+                let line = state.buffer.get_line_number(range.start) + 1;
+                Suggestion {
+                    description_spans: None,
+                    text: state.get_text_range(range.start, range.end),
+                    description: Some(t!("links.line", line = line).to_string()),
+                    value: Some(target),
+                    disabled: false,
+                    keybinding: None,
+                    source: None,
+                }
+            })
+            .collect();
+        self.active_window_mut().prompt = Some(Prompt::with_suggestions(
+            t!("links.select").to_string(),
+            PromptType::SelectDocumentLink,
+            suggestions,
+        ));
+    }
+
+    /// Request document links for a visible buffer: the buffer has never
+    /// been asked for, or an edit's debounce has elapsed.
+    pub(crate) fn maybe_request_document_links(&mut self, buffer_id: BufferId) {
+        if !self.config.editor.document_links {
+            return;
+        }
+        if self
+            .active_window_mut()
+            .document_link_requests
+            .take_due(buffer_id, true)
+        {
+            self.request_document_links_for_buffer(buffer_id);
+        }
+    }
+
+    /// Request document links for a buffer if a server provides them.
+    pub(crate) fn request_document_links_for_buffer(&mut self, buffer_id: BufferId) {
+        if self
+            .active_window()
+            .document_link_requests
+            .is_in_flight(buffer_id)
+        {
+            return;
+        }
+        let Some(version) = self
+            .active_window()
+            .buffers
+            .get(&buffer_id)
+            .map(|s| s.buffer.version())
+        else {
+            return;
+        };
+        // Server start-up re-requests.
+        self.active_window_mut()
+            .document_link_requests
+            .mark_requested(buffer_id, version);
+        let request_id = self.active_window().next_lsp_request_id;
+
+        let sent = self
+            .with_lsp_for_buffer(
+                buffer_id,
+                LspFeature::DocumentLink,
+                |handle, uri, _language| match handle
+                    .document_links(request_id, uri.as_uri().clone())
+                {
+                    Ok(()) => true,
+                    Err(e) => {
+                        tracing::debug!("Failed to request document links: {}", e);
+                        false
+                    }
+                },
+            )
+            .unwrap_or(false);
+
+        if sent {
+            let win = self.active_window_mut();
+            win.next_lsp_request_id += 1;
+            win.document_link_requests
+                .sent(request_id, buffer_id, version);
+        }
+    }
+
+    /// Request document links for all open buffers matching a language.
+    pub(super) fn request_document_links_for_language(&mut self, language: &str) {
+        if !self.config.editor.document_links {
+            return;
+        }
+        let buffer_ids: Vec<_> = self
+            .buffers_for_language(language)
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        for buffer_id in buffer_ids {
+            self.request_document_links_for_buffer(buffer_id);
+        }
+    }
+
+    pub(super) fn handle_lsp_document_links(
+        &mut self,
+        request_id: u64,
+        uri: String,
+        links: Vec<lsp_types::DocumentLink>,
+    ) {
+        self.active_window_mut()
+            .handle_lsp_document_links(request_id, uri, links);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn urls(line: &str) -> Vec<&str> {
+        find_urls(line).into_iter().map(|r| &line[r]).collect()
+    }
+
+    #[test]
+    fn find_urls_trims_punctuation_and_unbalanced_closers() {
+        assert_eq!(
+            urls("See https://example.com/a_(b), or [docs](http://x.org/y)."),
+            vec!["https://example.com/a_(b)", "http://x.org/y"]
+        );
+        assert_eq!(urls("<https://a.dev/>"), vec!["https://a.dev/"]);
+        assert_eq!(urls("\"file:///tmp/x.txt\""), vec!["file:///tmp/x.txt"]);
+    }
+
+    #[test]
+    fn find_urls_ignores_unknown_schemes_and_bare_separators() {
+        assert!(urls("git+ssh://host/repo and ://nothing and http://").is_empty());
+    }
+
+    #[test]
+    fn detect_links_is_textual_and_keeps_references_unresolved() {
+        let text = "see http://x.org/a\nno/such/file.rs:4:2 here\n";
+        assert_eq!(
+            detect_links(text, 10),
+            vec![
+                (14..28, DetectedLink::Url("http://x.org/a".to_string())),
+                (
+                    29..48,
+                    DetectedLink::Location {
+                        path: "no/such/file.rs".to_string(),
+                        line: Some(4),
+                        column: Some(2),
+                    }
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn location_reference_round_trips_paths_with_hashes() {
+        let reference = location_reference("a#b/c.rs", Some(7), Some(3));
+        assert_eq!(
+            split_location_reference(&reference),
+            ("a#b/c.rs", Some(7), Some(3))
+        );
+        assert_eq!(split_location_reference("x.rs"), ("x.rs", None, None));
+    }
+
+    #[test]
+    fn split_link_position_reads_line_fragments() {
+        assert_eq!(
+            split_link_position("file:///a.rs#L12,5"),
+            ("file:///a.rs", Some(12), Some(5))
+        );
+        assert_eq!(
+            split_link_position("file:///a.rs#3"),
+            ("file:///a.rs", Some(3), None)
+        );
+        assert_eq!(
+            split_link_position("https://x.org/page#intro"),
+            ("https://x.org/page#intro", None, None)
+        );
+    }
+}
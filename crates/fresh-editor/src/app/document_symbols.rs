@@ -0,0 +1,134 @@
+//! LSP document symbols (`textDocument/documentSymbol`), used as a scope
+//! source for the sticky scroll header in buffers without a tree-sitter
+//! grammar (see [`crate::view::sticky_scroll`]).
+//!
+//! The outline is only requested while the header is enabled: once per
+//! buffer version, after the buffer settles following an edit.
+
+use super::Editor;
+use crate::model::event::BufferId;
+use crate::types::LspFeature;
+
+impl crate::app::window::Window {
+    /// Forget everything document-symbol related for a buffer and drop its
+    /// outline.
+    pub(crate) fn clear_document_symbols_for_buffer(&mut self, buffer_id: BufferId) {
+        self.document_symbol_requests.clear(buffer_id);
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.sticky_scopes.clear_lsp(&mut state.marker_list);
+        }
+    }
+
+    /// Handle a `textDocument/documentSymbol` response.
+    pub fn handle_lsp_document_symbols(
+        &mut self,
+        request_id: u64,
+        symbols: lsp_types::DocumentSymbolResponse,
+    ) {
+        let buffers = &self.buffers;
+        let Some((buffer_id, _)) =
+            self.document_symbol_requests
+                .settle(request_id, "document symbols", |id| {
+                    buffers.get(&id).map(|s| s.buffer.version())
+                })
+        else {
+            return;
+        };
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        state
+            .sticky_scopes
+            .set_from_lsp(&state.buffer, &mut state.marker_list, symbols);
+    }
+}
+
+impl Editor {
+    /// Request document symbols for a visible buffer when the sticky scroll
+    /// header needs them: the buffer has never been asked for, or an edit's
+    /// debounce has elapsed.
+    pub(crate) fn maybe_request_document_symbols(&mut self, buffer_id: BufferId) {
+        if !self.config.editor.sticky_scroll {
+            return;
+        }
+        if self
+            .active_window_mut()
+            .document_symbol_requests
+            .take_due(buffer_id, true)
+        {
+            self.request_document_symbols_for_buffer(buffer_id);
+        }
+    }
+
+    /// Request document symbols for a buffer if a server provides them.
+    pub(crate) fn request_document_symbols_for_buffer(&mut self, buffer_id: BufferId) {
+        if self
+            .active_window()
+            .document_symbol_requests
+            .is_in_flight(buffer_id)
+        {
+            return;
+        }
+        let Some(version) = self
+            .active_window()
+            .buffers
+            .get(&buffer_id)
+            .map(|s| s.buffer.version())
+        else {
+            return;
+        };
+        // Server start-up re-requests.
+        self.active_window_mut()
+            .document_symbol_requests
+            .mark_requested(buffer_id, version);
+        let request_id = self.active_window().next_lsp_request_id;
+
+        let sent = self
+            .with_lsp_for_buffer(
+                buffer_id,
+                LspFeature::DocumentSymbols,
+                |handle, uri, _language| match handle
+                    .document_symbols(request_id, uri.as_uri().clone())
+                {
+                    Ok(()) => true,
+                    Err(e) => {
+                        tracing::debug!("Failed to request document symbols: {}", e);
+                        false
+                    }
+                },
+            )
+            .unwrap_or(false);
+
+        if sent {
+            let win = self.active_window_mut();
+            win.next_lsp_request_id += 1;
+            win.document_symbol_requests
+                .sent(request_id, buffer_id, version);
+        }
+    }
+
+    /// Request document symbols for all open buffers matching a language,
+    /// when the sticky scroll header is enabled.
+    pub(super) fn request_document_symbols_for_language(&mut self, language: &str) {
+        if !self.config.editor.sticky_scroll {
+            return;
+        }
+        let buffer_ids: Vec<_> = self
+            .buffers_for_language(language)
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        for buffer_id in buffer_ids {
+            self.request_document_symbols_for_buffer(buffer_id);
+        }
+    }
+
+    pub(super) fn handle_lsp_document_symbols(
+        &mut self,
+        request_id: u64,
+        symbols: lsp_types::DocumentSymbolResponse,
+    ) {
+        self.active_window_mut()
+            .handle_lsp_document_symbols(request_id, symbols);
+    }
+}
@@ -566,7 +566,7 @@ impl Editor {
                 })
                 .collect();
         installs.push((active_id, authority));
-        // Re-point each window's LSP backend, then **move** the authority into
+        // Re-point each window's LSP and debugger backends, then **move** the authority into
         // the window (single owner — never cloned).
         for (id, a) in installs {
             if let Some(w) = self.windows.get_mut(&id) {
@@ -574,6 +574,8 @@ impl Editor {
                     .set_long_running_spawner(a.long_running_spawner.clone());
                 w.lsp.set_path_translation(a.path_translation.clone());
                 w.lsp.set_workspace_trust(a.workspace_trust.clone());
+                w.dap
+                    .set_long_running_spawner(a.long_running_spawner.clone());
                 w.authority = a;
             }
         }
@@ -648,12 +650,14 @@ impl Editor {
     ) {
         let is_active = self.active_window == window_id;
         if let Some(w) = self.windows.get_mut(&window_id) {
-            // Re-point this window's LSP backend, then **move** the authority
+            // Re-point this window's LSP and debugger backends, then **move** the authority
             // into the window it owns (single owner — never cloned).
             let lsp = &mut w.lsp;
             lsp.set_long_running_spawner(authority.long_running_spawner.clone());
             lsp.set_path_translation(authority.path_translation.clone());
             lsp.set_workspace_trust(authority.workspace_trust.clone());
+            w.dap
+                .set_long_running_spawner(authority.long_running_spawner.clone());
             w.authority = authority;
         }
         if is_active {
@@ -927,6 +931,20 @@ impl Editor {
         self.last_watch_response_for_test.as_ref()
     }
 
+    /// Whether `dir` is covered by the editor's own file watch (as
+    /// opposed to mtime polling). Test-only.
+    #[doc(hidden)]
+    pub fn is_dir_watched_for_test(&self, dir: &std::path::Path) -> bool {
+        self.file_watcher_manager.is_editor_watched(dir)
+    }
+
+    /// Stop watching directories, leaving them all to mtime polling.
+    /// Test-only — for tests of the poll path.
+    #[doc(hidden)]
+    pub fn disable_file_watches_for_test(&mut self) {
+        self.file_watches_disabled_for_test = true;
+    }
+
     /// Inject an mtime entry into the active session's mod-time
     /// cache. Used by tests to populate `Window.file_mod_times`
     /// without going through real file I/O. (Pre-0b this was
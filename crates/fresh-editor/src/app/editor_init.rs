@@ -400,6 +400,9 @@ pub(super) struct EditorParts {
     /// Per-plugin config schemas discovered from `<plugin>.schema.json` sidecars.
     pub(super) plugin_schemas: HashMap<String, serde_json::Value>,
 
+    /// Package-plugin capability declarations and the user's decisions.
+    pub(super) plugin_permissions: crate::services::plugin_capabilities::PluginPermissions,
+
     /// Editor-wide event broadcaster, shared with every WindowResources.
     pub(super) event_broadcaster: crate::model::control_event::EventBroadcaster,
 }
@@ -623,6 +626,7 @@ impl Editor {
             ansi_background_path: None,
             background_fade: crate::primitives::ansi_background::DEFAULT_BACKGROUND_FADE,
             clipboard: crate::services::clipboard::Clipboard::new(),
+            spell_dictionary: None,
             should_quit: false,
             workspace_trust_prompt_cancellable: false,
             workspace_trust_markers: Vec::new(),
@@ -644,6 +648,7 @@ impl Editor {
             host_process_handles: HashMap::new(),
             status_bar_token_registry: Mutex::new(HashMap::new()),
             plugin_schemas: std::sync::Arc::new(std::sync::RwLock::new(parts.plugin_schemas)),
+            plugin_permissions: parts.plugin_permissions,
             event_broadcaster: parts.event_broadcaster,
             #[cfg(feature = "plugins")]
             line_targets: std::collections::HashMap::new(),
@@ -657,6 +662,8 @@ impl Editor {
             #[cfg(feature = "plugins")]
             grep_project_cancel: std::collections::HashMap::new(),
             #[cfg(feature = "plugins")]
+            structural_search_cancel: std::collections::HashMap::new(),
+            #[cfg(feature = "plugins")]
             plugin_timers: Vec::new(),
             #[cfg(feature = "plugins")]
             diff_baselines: crate::app::diff_baselines::BaselineStore::default(),
@@ -674,10 +681,11 @@ impl Editor {
             self_update_phase: crate::services::release_checker::SelfUpdatePhase::default(),
             self_update_terminal: None,
             self_update_output: None,
-            #[cfg(feature = "plugins")]
             file_watcher_manager: crate::services::file_watcher::FileWatcherManager::new(),
+            watch_debouncer: Default::default(),
             path_changes_for_test: Vec::new(),
             last_watch_response_for_test: None,
+            file_watches_disabled_for_test: false,
             preview_window_id: None,
             settings_state: None,
             calibration_wizard: None,
@@ -1147,6 +1155,7 @@ impl Editor {
         )));
         quick_open_registry.register(Box::new(BufferProvider::new()));
         quick_open_registry.register(Box::new(GotoLineProvider::new()));
+        quick_open_registry.register(Box::new(WorkspaceSymbolProvider::new()));
 
         // Build shared theme cache for plugin access
         let theme_cache = Arc::new(RwLock::new(theme_registry.to_json_map()));
@@ -1164,6 +1173,21 @@ impl Editor {
             Arc::clone(&orchestrator_filesystem),
         )));
         t.phase("PluginManager::new");
+        plugin_manager.read().unwrap().set_limits(
+            config.editor.plugin_execution_timeout_ms,
+            config.editor.plugin_memory_limit_mb,
+        );
+
+        // Package plugins are held to the capabilities their manifest
+        // declares; the policy has to be in the snapshot before they load.
+        let plugin_permissions = crate::services::plugin_capabilities::PluginPermissions::new(
+            scan_result.plugin_capabilities.clone(),
+            Some(
+                crate::services::plugin_capabilities::PluginCapabilityStore::in_data_dir(
+                    &dir_context.data_dir,
+                ),
+            ),
+        );
 
         // Update the plugin state snapshot with working_dir BEFORE loading plugins
         // This ensures plugins can call getCwd() correctly during initialization
@@ -1171,6 +1195,7 @@ impl Editor {
         if let Some(snapshot_handle) = plugin_manager.read().unwrap().state_snapshot_handle() {
             let mut snapshot = snapshot_handle.write().unwrap();
             snapshot.working_dir = working_dir.clone();
+            snapshot.plugin_capabilities = plugin_permissions.policy();
             // Pre-populate keybinding labels for the static built-in
             // keymap so `editor.getKeybindingLabel(action, context)`
             // works for actions that aren't behind a plugin-defined
@@ -1493,6 +1518,7 @@ impl Editor {
             time_source: time_source.clone(),
             plugin_global_state,
             plugin_schemas,
+            plugin_permissions,
             event_broadcaster: event_broadcaster.clone(),
         };
 
@@ -2023,6 +2049,10 @@ impl Editor {
             if !self.plugin_manager.read().unwrap().is_active() {
                 return;
             }
+            self.plugin_manager.read().unwrap().set_limits(
+                self.config.editor.plugin_execution_timeout_ms,
+                self.config.editor.plugin_memory_limit_mb,
+            );
             self.update_plugin_state_snapshot();
             self.plugin_manager.read().unwrap().run_hook(
                 "config_changed",
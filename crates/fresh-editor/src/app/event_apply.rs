@@ -134,6 +134,9 @@ impl Editor {
                 win.invalidate_layouts_for_buffer(buf);
                 win.schedule_semantic_tokens_full_refresh(buf);
                 win.schedule_folding_ranges_refresh(buf);
+                win.code_lens_requests.schedule_refresh(buf);
+                win.document_symbol_requests.schedule_refresh(buf);
+                win.document_link_requests.schedule_refresh(buf);
             }
             Event::Batch { events, .. } => {
                 let has_edits = events
@@ -145,6 +148,9 @@ impl Editor {
                     win.invalidate_layouts_for_buffer(buf);
                     win.schedule_semantic_tokens_full_refresh(buf);
                     win.schedule_folding_ranges_refresh(buf);
+                    win.code_lens_requests.schedule_refresh(buf);
+                    win.document_symbol_requests.schedule_refresh(buf);
+                    win.document_link_requests.schedule_refresh(buf);
                 }
             }
             _ => {}
@@ -183,6 +183,12 @@ impl Editor {
         // the user actually sees the file they just opened.
         self.reveal_active_split_if_hidden_by_maximize();
 
+        // A workspace diagnostics pull may already have reported on this
+        // file while it was closed.
+        if is_new_buffer || !active_had_path {
+            self.apply_stored_diagnostics(buffer_id);
+        }
+
         // If the initial empty buffer was replaced in-place with file content,
         // set_active_buffer is a no-op (same buffer ID). Fire buffer_activated
         // explicitly so plugins see the newly loaded file.
@@ -959,24 +965,33 @@ impl crate::app::window::Window {
             state.editing_disabled = true;
         }
 
-        // Apply the global + per-language buffer settings (whitespace
-        // visibility, tabs, auto-close/surround, guides, …).
-        state.apply_buffer_config(&self.resources.config);
-
-        // Apply `.editorconfig` overrides. Project-level indentation settings
-        // take precedence over the language/global defaults resolved above.
-        // Resolved through the FileSystem abstraction so it also works on
-        // remote hosts; matched against the canonical on-disk path.
+        // Resolve `.editorconfig` first: project-level settings take
+        // precedence over the language/global defaults, and
+        // `apply_buffer_config` layers them on top. Resolved through the
+        // FileSystem abstraction so it also works on remote hosts; matched
+        // against the canonical on-disk path.
         let editorconfig = crate::services::editorconfig::resolve_for_file(
             self.authority().filesystem.as_ref(),
             canonical_path,
         );
-        if let Some(use_tabs) = editorconfig.use_tabs {
-            state.buffer_settings.use_tabs = use_tabs;
-        }
-        if let Some(tab_size) = editorconfig.tab_size {
-            state.buffer_settings.tab_size = tab_size;
+        if file_exists {
+            if let Some(encoding) = editorconfig.encoding.filter(|_| !is_binary) {
+                self.apply_editorconfig_charset(&mut state, canonical_path, encoding);
+            }
+        } else {
+            // A new file starts out in the project's format.
+            if let Some(line_ending) = editorconfig.line_ending {
+                state.buffer.set_default_line_ending(line_ending);
+            }
+            if let Some(encoding) = editorconfig.encoding {
+                state.buffer.set_default_encoding(encoding);
+            }
         }
+        state.buffer_settings.editorconfig = editorconfig;
+
+        // Apply the global + per-language buffer settings (whitespace
+        // visibility, tabs, auto-close/surround, guides, …).
+        state.apply_buffer_config(&self.resources.config);
 
         // Apply line_numbers default from config
         state
@@ -1028,6 +1043,59 @@ impl crate::app::window::Window {
 
         // Store metadata for this buffer
         self.buffer_metadata.insert(buffer_id, metadata);
+
+        // Bring back the undo history from a previous session, if enabled
+        if file_exists {
+            self.restore_persisted_undo(buffer_id);
+        }
+    }
+
+    /// Make an existing file's buffer use the `.editorconfig` `charset`.
+    ///
+    /// Encodings that decode the same bytes alike (UTF-8 with or without a
+    /// BOM, ASCII) only switch what the next save writes. Any other mismatch
+    /// means the detector guessed differently from what the project declares,
+    /// so the file is read again in the declared encoding — unless it is too
+    /// large to load whole, where the detected encoding stays.
+    fn apply_editorconfig_charset(
+        &self,
+        state: &mut EditorState,
+        path: &Path,
+        encoding: crate::model::buffer::Encoding,
+    ) {
+        use crate::model::buffer::Encoding;
+        let utf8_family =
+            |e: Encoding| matches!(e, Encoding::Utf8 | Encoding::Utf8Bom | Encoding::Ascii);
+        let current = state.buffer.encoding();
+        if current == encoding {
+            return;
+        }
+        if utf8_family(current) && utf8_family(encoding) {
+            state.buffer.set_default_encoding(encoding);
+            return;
+        }
+        if state.buffer.is_large_file() {
+            return;
+        }
+        match crate::model::buffer::Buffer::load_from_file_with_encoding(
+            path,
+            encoding,
+            Arc::clone(&self.authority().filesystem),
+            crate::model::buffer::BufferConfig {
+                estimated_line_length: self.resources.config.editor.estimated_line_length,
+            },
+        ) {
+            Ok(buffer) => {
+                state.buffer = buffer;
+                state.highlighter.invalidate_all();
+            }
+            Err(e) => tracing::warn!(
+                "Failed to read {} as {} (.editorconfig charset): {}",
+                path.display(),
+                encoding.display_name(),
+                e
+            ),
+        }
     }
 
     fn open_file_no_focus_inner(
@@ -1213,7 +1281,12 @@ impl crate::app::window::Window {
         let target_split = self.preferred_split_for_file();
         let line_wrap = self.resolve_line_wrap_for_buffer(buffer_id);
         let wrap_column = self.resolve_wrap_column_for_buffer(buffer_id);
+        let rulers = self.resolve_rulers_for_buffer(buffer_id);
         let page_view = self.resolve_page_view_for_buffer(buffer_id);
+        let is_binary = self
+            .buffer_metadata
+            .get(&buffer_id)
+            .is_some_and(|m| m.binary);
         // Snapshot config values before taking the mutable view-states borrow
         // so the closure body doesn't have to re-borrow `self.resources`.
         let cfg = self.resources.config.editor.clone();
@@ -1231,13 +1304,17 @@ impl crate::app::window::Window {
                 line_wrap,
                 wrap_indent: cfg.wrap_indent,
                 wrap_column,
-                rulers: cfg.rulers,
+                rulers,
                 scroll_offset: cfg.scroll_offset,
             });
             // Auto-activate page view if configured for this language
             if let Some(page_width) = page_view {
                 buf_state.activate_page_view(page_width);
             }
+            // Binary files make no sense as text; show their bytes instead
+            if is_binary {
+                buf_state.hex_view = Some(crate::view::hex_view::HexViewState::default());
+            }
         }
 
         // Restore global file state (scroll/cursor position) if available
@@ -12,6 +12,7 @@ use crate::model::buffer::SudoSaveRequired;
 use crate::model::filesystem::FileSystem;
 use crate::view::file_tree::FileTreeView;
 use crate::view::prompt::PromptType;
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 
 use lsp_types::TextDocumentContentChangeEvent;
@@ -43,7 +44,7 @@ impl Editor {
             .file_path()
             .map(|p| p.to_path_buf());
 
-        match self.active_state_mut().buffer.save() {
+        match self.active_state_mut().save() {
             Ok(()) => self.finalize_save(path),
             Err(e) => {
                 if let Some(sudo_info) = e.downcast_ref::<SudoSaveRequired>() {
@@ -128,6 +129,7 @@ impl Editor {
         if let Some(event_log) = self.active_window_mut().event_logs.get_mut(&buffer_id) {
             event_log.mark_saved();
         }
+        self.active_window_mut().persist_undo_history(buffer_id);
 
         // Update file modification time after save
         if let Some(ref p) = path {
@@ -256,7 +258,7 @@ impl Editor {
                 .expect("active window present")
                 .get_mut(&id)
             {
-                match state.buffer.save() {
+                match state.save() {
                     Ok(()) => {
                         self.finalize_save_buffer(id, Some(path), true)?;
                         count += 1;
@@ -372,7 +374,7 @@ impl Editor {
                 .expect("active window present")
                 .get_mut(&id)
             {
-                match state.buffer.save() {
+                match state.save() {
                     Ok(()) => {
                         self.finalize_save_buffer(id, Some(path), true)?;
                         count += 1;
@@ -439,7 +441,7 @@ impl Editor {
                 .map(|w| &mut w.buffers)
                 .expect("active window present")
                 .get_mut(&id)
-                .map(|state| state.buffer.save());
+                .map(|state| state.save());
             match result {
                 Some(Ok(())) => {
                     self.finalize_save_buffer(id, Some(path), true)?;
@@ -627,10 +629,184 @@ impl Editor {
         });
     }
 
+    /// Directories the active window wants watched: the parents of open
+    /// auto-revert buffers, the expanded file explorer directories, and the
+    /// `.git` directories holding the watched git indexes.
+    fn desired_watch_dirs(&self) -> HashSet<PathBuf> {
+        let window = self.active_window();
+        let mut dirs: HashSet<PathBuf> = window
+            .buffers
+            .iter()
+            .filter(|(id, _)| window.buffer_auto_revert_enabled(**id))
+            .filter_map(|(_, state)| state.buffer.file_path()?.parent().map(PathBuf::from))
+            .collect();
+        if let Some(explorer) = self.file_explorer() {
+            dirs.extend(
+                explorer
+                    .tree()
+                    .all_nodes()
+                    .filter(|node| node.is_dir() && node.is_expanded())
+                    .map(|node| node.entry.path.clone()),
+            );
+        }
+        dirs.extend(
+            window
+                .watched_git_indexes
+                .iter()
+                .filter_map(|index| index.parent().map(PathBuf::from)),
+        );
+        dirs
+    }
+
+    /// Bring the editor's file watches in line with what the active window
+    /// has open. Runs on the poll intervals, so a newly opened file or
+    /// expanded directory is polled until the next one picks it up.
+    ///
+    /// On a remote filesystem the agent does the watching; a watch lost
+    /// with the connection is set up again here once it is back.
+    fn sync_file_watches(&mut self) {
+        let Some(bridge) = self.async_bridge.clone() else {
+            return;
+        };
+        let dirs = if self.file_watches_disabled_for_test {
+            HashSet::new()
+        } else {
+            self.desired_watch_dirs()
+        };
+        let fs = self.authority().filesystem.clone();
+        let added = self
+            .file_watcher_manager
+            .sync_editor_watches(&bridge, &dirs, &fs);
+        // Check newly watched directories once: anything that changed
+        // between the last poll and the watch starting sent no event.
+        let now = self.time_source.now();
+        for dir in added {
+            self.watch_debouncer.record(dir, now);
+        }
+    }
+
+    /// Whether `path`'s directory is covered by a file watch, so polling can
+    /// skip it.
+    fn is_watched_file(&self, path: &Path) -> bool {
+        path.parent()
+            .is_some_and(|dir| self.file_watcher_manager.is_editor_watched(dir))
+    }
+
+    /// Act on file-watch events that have settled (see
+    /// `WatchEventDebouncer`): stat the open files, expanded directories and
+    /// git indexes they touch, and run the results through the same checks
+    /// as a poll. Returns true if anything was reverted or refreshed.
+    ///
+    /// Local stats run inline — a settled batch touches a handful of paths.
+    /// Remote ones are round trips, so they run on a background thread and
+    /// deliver through the poll result channels, like a poll.
+    pub fn process_file_watch_events(&mut self) -> bool {
+        use crate::view::file_tree::NodeId;
+
+        let now = self.time_source.now();
+        let settled: HashSet<PathBuf> =
+            self.watch_debouncer.take_settled(now).into_iter().collect();
+        if settled.is_empty() {
+            return false;
+        }
+        // An event names either an entry (file created, written, renamed)
+        // or a watched directory itself (attribute change, rescan, newly
+        // watched) — the latter stands for everything in it.
+        let affected = |path: &Path| {
+            settled.contains(path) || path.parent().is_some_and(|d| settled.contains(d))
+        };
+        // Directories whose listing may have changed.
+        let touched_dirs: HashSet<&Path> = settled
+            .iter()
+            .flat_map(|path| [Some(path.as_path()), path.parent()])
+            .flatten()
+            .collect();
+
+        let files: Vec<PathBuf> = {
+            let window = self.active_window();
+            window
+                .buffers
+                .iter()
+                .filter(|(id, _)| window.buffer_auto_revert_enabled(**id))
+                .filter_map(|(_, state)| state.buffer.file_path().map(PathBuf::from))
+                .filter(|path| affected(path))
+                .collect()
+        };
+        let dirs: Vec<(NodeId, PathBuf)> = self
+            .file_explorer()
+            .map(|explorer| {
+                explorer
+                    .tree()
+                    .all_nodes()
+                    .filter(|node| node.is_dir() && node.is_expanded())
+                    .filter(|node| touched_dirs.contains(node.entry.path.as_path()))
+                    .map(|node| (node.id, node.entry.path.clone()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let git_indexes: Vec<PathBuf> = self
+            .active_window()
+            .watched_git_indexes
+            .iter()
+            .filter(|index| affected(index))
+            .cloned()
+            .collect();
+
+        // A poll's first look at a directory only records its mtime. Here
+        // an event says the listing may already differ from what the
+        // explorer loaded, so a directory without a recorded mtime counts
+        // as changed.
+        for (_, path) in &dirs {
+            self.active_window_mut()
+                .dir_mod_times
+                .entry(path.clone())
+                .or_insert(std::time::SystemTime::UNIX_EPOCH);
+        }
+
+        let fs = self.authority().filesystem.clone();
+        if fs.remote_connection_info().is_some() {
+            let window = self.active_window();
+            if window.pending_file_poll_rx.is_some() || window.pending_dir_poll_rx.is_some() {
+                // A poll holds the result channels; retry once it's done.
+                for path in settled {
+                    self.watch_debouncer.record(path, now);
+                }
+                return false;
+            }
+            let (file_tx, file_rx) = std::sync::mpsc::channel();
+            let (dir_tx, dir_rx) = std::sync::mpsc::channel();
+            std::thread::Builder::new()
+                .name("stat-watch-events".to_string())
+                .spawn(move || {
+                    let (file_results, dir_results, git_index_mtimes) =
+                        stat_watch_targets(fs.as_ref(), files, dirs, git_indexes);
+                    // Receivers may have been dropped during shutdown.
+                    #[allow(clippy::let_underscore_must_use)]
+                    let _ = file_tx.send(file_results);
+                    #[allow(clippy::let_underscore_must_use)]
+                    let _ = dir_tx.send((dir_results, git_index_mtimes));
+                })
+                .ok();
+            let window = self.active_window_mut();
+            window.pending_file_poll_rx = Some(file_rx);
+            window.pending_dir_poll_rx = Some(dir_rx);
+            return false;
+        }
+
+        let (file_results, dir_results, git_index_mtimes) =
+            stat_watch_targets(fs.as_ref(), files, dirs, git_indexes);
+        let files_changed = self.process_file_poll_results(file_results);
+        let tree_changed = (!dir_results.is_empty() || !git_index_mtimes.is_empty())
+            && self.process_dir_poll_results(dir_results, git_index_mtimes);
+        files_changed || tree_changed
+    }
+
     /// Poll for file changes (called from main loop)
     ///
     /// Checks modification times of open files to detect external changes.
-    /// Returns true if any file was changed (requires re-render).
+    /// Returns true if any file was changed (requires re-render). Files in
+    /// watched directories are skipped — their changes arrive as events (see
+    /// `process_file_watch_events`); polling covers the rest.
     ///
     /// To avoid blocking the event loop, metadata checks run on a background
     /// thread. This method launches a poll if the interval has elapsed and no
@@ -669,6 +845,7 @@ impl Editor {
             return any_changed;
         }
         self.active_window_mut().last_auto_revert_poll = self.time_source.now();
+        self.sync_file_watches();
 
         // Collect paths of open files that need checking. Auto-revert is a
         // per-buffer property, so only poll files whose buffer opts in — this
@@ -682,6 +859,7 @@ impl Editor {
                 .iter()
                 .filter(|(id, _)| window.buffer_auto_revert_enabled(**id))
                 .filter_map(|(_, state)| state.buffer.file_path().map(PathBuf::from))
+                .filter(|path| !self.is_watched_file(path))
                 .collect()
         };
 
@@ -744,7 +922,7 @@ impl Editor {
     /// Returns true if any directory was refreshed (requires re-render).
     ///
     /// Like poll_file_changes, metadata checks run on a background thread to
-    /// avoid blocking the event loop.
+    /// avoid blocking the event loop, and watched directories are skipped.
     pub fn poll_file_tree_changes(&mut self) -> bool {
         use crate::view::file_tree::NodeId;
 
@@ -778,6 +956,7 @@ impl Editor {
             return any_refreshed;
         }
         self.active_window_mut().last_file_tree_poll = self.time_source.now();
+        self.sync_file_watches();
 
         // Re-stat every loaded .gitignore and reload/drop as needed, so
         // external edits (git pull, sed, another editor) and deletions take
@@ -807,13 +986,24 @@ impl Editor {
             .tree()
             .all_nodes()
             .filter(|node| node.is_dir() && node.is_expanded())
+            .filter(|node| {
+                !self
+                    .file_watcher_manager
+                    .is_editor_watched(&node.entry.path)
+            })
             .map(|node| (node.id, node.entry.path.clone()))
             .collect();
 
         // The watch set is stored explicitly (populated once above), not
         // re-derived from `dir_mod_times` by pattern-matching `.git/index`.
         // In a monorepo there may be several; in a single-repo just one.
-        let git_index_paths: Vec<PathBuf> = self.active_window().watched_git_indexes.clone();
+        let git_index_paths: Vec<PathBuf> = self
+            .active_window()
+            .watched_git_indexes
+            .iter()
+            .filter(|path| !self.is_watched_file(path))
+            .cloned()
+            .collect();
 
         if expanded_dirs.is_empty() && git_index_paths.is_empty() {
             return any_refreshed;
@@ -1424,6 +1614,48 @@ pub(crate) fn load_gitignore_via_fs(fs: &dyn FileSystem, explorer: &mut FileTree
     explorer.load_gitignore_from_bytes(dir, &bytes, meta.modified);
 }
 
+/// Modification times of the files, explorer directories and git indexes a
+/// batch of watch events touched, shaped like poll results.
+#[allow(clippy::type_complexity)]
+fn stat_watch_targets(
+    fs: &dyn FileSystem,
+    files: Vec<PathBuf>,
+    dirs: Vec<(crate::view::file_tree::NodeId, PathBuf)>,
+    git_indexes: Vec<PathBuf>,
+) -> (
+    Vec<(PathBuf, Option<std::time::SystemTime>)>,
+    Vec<(
+        crate::view::file_tree::NodeId,
+        PathBuf,
+        Option<std::time::SystemTime>,
+    )>,
+    Vec<(PathBuf, std::time::SystemTime)>,
+) {
+    let mtime = |path: &Path| fs.metadata(path).ok().and_then(|m| m.modified);
+    let file_results = files
+        .into_iter()
+        .map(|path| {
+            let modified = mtime(&path);
+            (path, modified)
+        })
+        .collect();
+    let dir_results = dirs
+        .into_iter()
+        .map(|(node_id, path)| {
+            let modified = mtime(&path);
+            (node_id, path, modified)
+        })
+        .collect();
+    let git_index_mtimes = git_indexes
+        .into_iter()
+        .filter_map(|path| {
+            let modified = mtime(&path)?;
+            Some((path, modified))
+        })
+        .collect();
+    (file_results, dir_results, git_index_mtimes)
+}
+
 impl crate::app::window::Window {
     /// Notify this window's LSP servers about a newly opened file.
     ///
@@ -1605,8 +1837,9 @@ impl crate::app::window::Window {
                     }
                 }
 
-                // Schedule folding range refresh
+                // Schedule folding range and code lens refreshes
                 self.schedule_folding_ranges_refresh(buffer_id);
+                self.code_lens_requests.schedule_refresh(buffer_id);
             }
             LspSpawnResult::NotAutoStart => {
                 tracing::debug!(
@@ -0,0 +1,337 @@
+//! Hex view: toggling it, and the cursor movement and byte editing keys
+//! routed to it by [`HexInputRouter`].
+//!
+//! Byte edits bypass the text `Insert`/`Delete` events — their payload is a
+//! `String`, which cannot carry arbitrary bytes — and are recorded as a
+//! `BulkEdit` with buffer snapshots, so undo/redo work as for any other edit.
+
+use anyhow::Result as AnyhowResult;
+use rust_i18n::t;
+
+use super::Editor;
+use crate::input::hex_router::{HexInputRouter, HexMove, HexRoutedEvent};
+use crate::model::buffer::TextBuffer;
+use crate::model::event::{CursorId, Event};
+use crate::view::hex_view::{HexPane, HexViewState, BYTES_PER_ROW};
+use crate::view::prompt::PromptType;
+
+/// Start of the line after the one holding `offset`, or the end of the
+/// buffer on its last line.
+fn next_line_start(buffer: &TextBuffer, offset: usize) -> usize {
+    buffer
+        .line_start_offset(buffer.get_line_number(offset) + 1)
+        .unwrap_or(buffer.len())
+}
+
+impl Editor {
+    /// Hex view state of the active buffer in the active split, if it is
+    /// shown in hex view.
+    pub(crate) fn active_hex_view(&self) -> Option<&HexViewState> {
+        let split_id = self.effective_active_split();
+        self.active_window()
+            .buffers
+            .splits()
+            .and_then(|(_, vs)| vs.get(&split_id))
+            .and_then(|vs| vs.hex_view.as_ref())
+    }
+
+    fn active_hex_view_mut(&mut self) -> Option<&mut HexViewState> {
+        let split_id = self.effective_active_split();
+        self.split_view_states_mut()
+            .get_mut(&split_id)
+            .and_then(|vs| vs.hex_view.as_mut())
+    }
+
+    /// Switch the active split between the text and hex views of its buffer.
+    pub(super) fn toggle_hex_view(&mut self) {
+        let split_id = self.effective_active_split();
+        let Some(view_state) = self.split_view_states_mut().get_mut(&split_id) else {
+            return;
+        };
+        let enabled = view_state.hex_view.is_none();
+        view_state.hex_view = enabled.then(HexViewState::default);
+        view_state.layout_dirty = true;
+        if !enabled {
+            // The hex cursor can sit inside a multi-byte character; the
+            // text view needs it on a character boundary.
+            let position = self.active_cursors().primary().position;
+            let position = self.active_state().buffer.snap_to_char_boundary(position);
+            let cursor = self.active_cursors_mut().primary_mut();
+            cursor.position = position;
+            cursor.anchor = None;
+        }
+        let state = if enabled {
+            t!("view.state_enabled")
+        } else {
+            t!("view.state_disabled")
+        };
+        self.set_status_message(t!("hex.view_state", state = state).to_string());
+    }
+
+    /// Open the Go To prompt for a byte offset, used instead of Go To Line
+    /// while the hex view is active.
+    pub(super) fn start_hex_goto_offset_prompt(&mut self) {
+        self.start_prompt(
+            t!("hex.goto_offset_prompt").to_string(),
+            PromptType::GotoByteOffset,
+        );
+    }
+
+    /// Route a key event through the [`HexInputRouter`] when the active
+    /// buffer is shown in hex view. Returns `Some(Ok(()))` if the event was
+    /// handled, `None` to let normal dispatch continue.
+    pub(super) fn try_route_hex_key(
+        &mut self,
+        key_event: &crossterm::event::KeyEvent,
+    ) -> Option<AnyhowResult<()>> {
+        let view_state = self.active_hex_view()?;
+        match HexInputRouter::route_key_event(view_state, key_event) {
+            HexRoutedEvent::Unhandled => return None,
+            HexRoutedEvent::Ignored => {}
+            HexRoutedEvent::Move(movement) => self.hex_move(movement),
+            HexRoutedEvent::SwitchPane => {
+                if let Some(view_state) = self.active_hex_view_mut() {
+                    view_state.pane = match view_state.pane {
+                        HexPane::Hex => HexPane::Text,
+                        HexPane::Text => HexPane::Hex,
+                    };
+                    view_state.low_nibble = false;
+                }
+            }
+            HexRoutedEvent::ToggleInsertMode => {
+                if let Some(view_state) = self.active_hex_view_mut() {
+                    view_state.insert_mode = !view_state.insert_mode;
+                    view_state.low_nibble = false;
+                }
+            }
+            HexRoutedEvent::Nibble(digit) => self.hex_type_nibble(digit),
+            HexRoutedEvent::Char(c) => {
+                let mut bytes = [0; 4];
+                self.hex_type_bytes(c.encode_utf8(&mut bytes).as_bytes());
+            }
+            HexRoutedEvent::Backspace => {
+                let position = self.hex_cursor();
+                if position > 0 {
+                    self.hex_delete(position - 1);
+                }
+            }
+            HexRoutedEvent::Delete => {
+                let position = self.hex_cursor();
+                if position < self.active_state().buffer.len() {
+                    self.hex_delete(position);
+                }
+            }
+        }
+        Some(Ok(()))
+    }
+
+    /// The primary cursor's byte offset, clamped to the buffer.
+    fn hex_cursor(&self) -> usize {
+        self.active_cursors()
+            .primary()
+            .position
+            .min(self.active_state().buffer.len())
+    }
+
+    fn set_hex_cursor(&mut self, position: usize) {
+        let cursor = self.active_cursors_mut().primary_mut();
+        cursor.position = position;
+        cursor.anchor = None;
+        cursor.sticky_column = None;
+    }
+
+    fn hex_move(&mut self, movement: HexMove) {
+        let len = self.active_state().buffer.len();
+        let position = self.hex_cursor();
+        let page = self.active_hex_view().map_or(1, |v| v.visible_rows.max(1)) * BYTES_PER_ROW;
+        let row_start = position - position % BYTES_PER_ROW;
+        let target = match movement {
+            HexMove::Left => position.saturating_sub(1),
+            HexMove::Right => position + 1,
+            HexMove::Up => position.checked_sub(BYTES_PER_ROW).unwrap_or(position),
+            HexMove::Down if position + BYTES_PER_ROW <= len => position + BYTES_PER_ROW,
+            HexMove::Down => position,
+            HexMove::PageUp => position
+                .checked_sub(page)
+                .unwrap_or(position % BYTES_PER_ROW),
+            HexMove::PageDown => position + page,
+            HexMove::RowStart => row_start,
+            HexMove::RowEnd => row_start + BYTES_PER_ROW - 1,
+            HexMove::Top => 0,
+            HexMove::Bottom => len,
+        };
+        self.set_hex_cursor(target.min(len));
+        if let Some(view_state) = self.active_hex_view_mut() {
+            view_state.low_nibble = false;
+        }
+    }
+
+    /// Byte at `position`, if it is inside the buffer.
+    fn hex_byte_at(&mut self, position: usize) -> Option<u8> {
+        let state = self.active_state_mut();
+        if position >= state.buffer.len() {
+            return None;
+        }
+        state
+            .buffer
+            .get_text_range_mut(position, 1)
+            .ok()
+            .and_then(|bytes| bytes.first().copied())
+    }
+
+    /// A hex digit typed in the hex pane. The first digit sets the high
+    /// nibble of the byte under the cursor (or of a new byte in insert mode
+    /// and at the end of the buffer), the second its low nibble, after
+    /// which the cursor moves on.
+    fn hex_type_nibble(&mut self, digit: u8) {
+        if self.refuse_if_editing_disabled() {
+            return;
+        }
+        let Some(view_state) = self.active_hex_view() else {
+            return;
+        };
+        let (low_nibble, insert_mode) = (view_state.low_nibble, view_state.insert_mode);
+        let position = self.hex_cursor();
+        let current = self.hex_byte_at(position);
+        match (low_nibble, current) {
+            (true, Some(byte)) => {
+                self.apply_hex_edit(position, 1, &[(byte & 0xF0) | digit], position + 1);
+            }
+            (false, Some(byte)) if !insert_mode => {
+                self.apply_hex_edit(position, 1, &[(digit << 4) | (byte & 0x0F)], position);
+            }
+            _ => self.apply_hex_edit(position, 0, &[digit << 4], position),
+        }
+        if let Some(view_state) = self.active_hex_view_mut() {
+            view_state.low_nibble = !low_nibble;
+        }
+    }
+
+    /// Bytes typed in the text pane: overwrite the bytes under the cursor,
+    /// or insert them in insert mode and at the end of the buffer.
+    fn hex_type_bytes(&mut self, bytes: &[u8]) {
+        if self.refuse_if_editing_disabled() {
+            return;
+        }
+        let Some(insert_mode) = self.active_hex_view().map(|v| v.insert_mode) else {
+            return;
+        };
+        let position = self.hex_cursor();
+        let overwritten = if insert_mode {
+            0
+        } else {
+            bytes.len().min(self.active_state().buffer.len() - position)
+        };
+        self.apply_hex_edit(position, overwritten, bytes, position + bytes.len());
+    }
+
+    fn hex_delete(&mut self, position: usize) {
+        if self.refuse_if_editing_disabled() {
+            return;
+        }
+        self.apply_hex_edit(position, 1, &[], position);
+        if let Some(view_state) = self.active_hex_view_mut() {
+            view_state.low_nibble = false;
+        }
+    }
+
+    /// Replace `delete_len` bytes at `position` with `bytes`, move the
+    /// primary cursor to `cursor_after`, and record the change for undo.
+    fn apply_hex_edit(
+        &mut self,
+        position: usize,
+        delete_len: usize,
+        bytes: &[u8],
+        cursor_after: usize,
+    ) {
+        self.active_window_mut()
+            .promote_active_buffer_from_preview();
+        let buffer_id = self.active_buffer();
+        let old_cursors: Vec<(CursorId, usize, Option<usize>)> = self
+            .active_cursors()
+            .iter()
+            .map(|(id, c)| (id, c.position, c.anchor))
+            .collect();
+        let primary_id = self.active_cursors().primary_id();
+
+        // A text file viewed in hex keeps its language server in sync;
+        // binary and large-file buffers have none. The change replaces the
+        // whole lines around the edit: a byte edit can split a UTF-8
+        // sequence, a line break never does.
+        let lsp_lines = {
+            let buffer = &self.active_state().buffer;
+            (!buffer.is_binary() && !buffer.is_large_file()).then(|| {
+                let start = buffer
+                    .line_start_offset(buffer.get_line_number(position))
+                    .unwrap_or(position);
+                let (start_line, start_char) = buffer.position_to_lsp_position(start);
+                let (end_line, end_char) =
+                    buffer.position_to_lsp_position(next_line_start(buffer, position + delete_len));
+                let range = lsp_types::Range::new(
+                    lsp_types::Position::new(start_line as u32, start_char as u32),
+                    lsp_types::Position::new(end_line as u32, end_char as u32),
+                );
+                (start, range)
+            })
+        };
+
+        let edits = vec![(position, delete_len, bytes.len())];
+        let state = self.active_state_mut();
+        let old_snapshot = state.buffer.snapshot_buffer_state();
+        let displaced_markers =
+            state.capture_displaced_markers_bulk(&[(position, delete_len, String::new())]);
+        if delete_len > 0 {
+            state.buffer.delete_bytes(position, delete_len);
+        }
+        if !bytes.is_empty() {
+            state.buffer.insert_bytes(position, bytes.to_vec());
+        }
+        state.replay_bulk_marker_adjustments(&edits);
+        state.highlighter.notify_edits(&edits);
+        let new_snapshot = state.buffer.snapshot_buffer_state();
+
+        self.set_hex_cursor(cursor_after);
+        let new_cursors = old_cursors
+            .iter()
+            .map(|&(id, pos, anchor)| {
+                if id == primary_id {
+                    (id, cursor_after, None)
+                } else {
+                    (id, pos, anchor)
+                }
+            })
+            .collect();
+
+        #[cfg(feature = "plugins")]
+        self.shift_plugin_markers_for_edit(buffer_id, position, delete_len, bytes.len());
+
+        let bulk_edit = Event::BulkEdit {
+            old_snapshot: Some(old_snapshot),
+            new_snapshot: Some(new_snapshot),
+            old_cursors,
+            new_cursors,
+            description: t!("hex.edit_description").to_string(),
+            edits,
+            displaced_markers,
+        };
+        let win = self.active_window_mut();
+        win.invalidate_layouts_for_buffer(buffer_id);
+        win.adjust_other_split_cursors_for_event(&bulk_edit);
+
+        if let Some((start, range)) = lsp_lines {
+            let buffer = &mut self.active_state_mut().buffer;
+            let end = next_line_start(buffer, position + bytes.len());
+            if let Ok(text) = buffer.get_text_range_mut(start, end - start) {
+                self.active_window_mut().send_lsp_changes_for_buffer(
+                    buffer_id,
+                    vec![lsp_types::TextDocumentContentChangeEvent {
+                        range: Some(range),
+                        range_length: None,
+                        text: String::from_utf8_lossy(&text).into_owned(),
+                    }],
+                );
+            }
+        }
+        self.active_event_log_mut().append(bulk_edit);
+    }
+}
@@ -323,6 +323,16 @@ impl Editor {
             }
         }
 
+        // --- Hex view input routing ---
+        // Cursor movement and byte editing in a split showing its buffer in
+        // hex view. Keys the hex view leaves alone (save, undo, palette, ...)
+        // fall through to regular keybinding resolution.
+        if context == crate::input::keybindings::KeyContext::Normal {
+            if let Some(handled) = self.try_route_hex_key(&key_event) {
+                return handled;
+            }
+        }
+
         // Resolve the key against the current context, chords first —
         // the decision is [`router::chord_or_key`]. An abandoned chord
         // prefix is cleared so it can't poison the next key.
@@ -255,6 +255,10 @@ impl Editor {
         // Cancel any pending LSP requests since the text is changing
         self.active_window_mut().cancel_pending_lsp_requests();
 
+        // A character that can't be part of a linked range (a space after
+        // a tag name) must not be mirrored into the other ranges.
+        self.end_linked_editing_unless_accepts(c);
+
         if let Some(events) = self
             .active_window_mut()
             .action_to_events(Action::InsertChar(c))
@@ -283,6 +287,8 @@ impl Editor {
         // Auto-trigger completion on trigger characters
         self.maybe_trigger_completion(c);
 
+        self.maybe_request_on_type_formatting(c);
+
         Ok(())
     }
 
@@ -0,0 +1,329 @@
+//! LSP linked editing ranges (`textDocument/linkedEditingRange`).
+//!
+//! When the single cursor settles inside a range the server links to others
+//! (an HTML/JSX tag name and its closing tag), a temporary multi-cursor
+//! session adds a cursor at the same offset in every other range, so typing
+//! renames all of them at once. The session ends — removing only the cursors
+//! it added — when the original cursor leaves its range, a character outside
+//! the range's word pattern is typed, or the cursors are changed by anything
+//! else (Escape, clicking, switching buffers).
+
+use std::ops::Range;
+
+use super::Editor;
+use crate::input::multi_cursor::linked_range_cursors;
+use crate::model::cursor::Cursor;
+use crate::model::event::{BufferId, CursorId, LeafId};
+use crate::model::marker::MarkerId;
+use crate::types::LspFeature;
+
+#[derive(Debug, Default)]
+pub(crate) struct LinkedEditingState {
+    /// The request awaiting a response. Only one is in flight at a time.
+    pending: Option<LinkedEditingRequest>,
+    /// Buffer, version and cursor the last request was sent for, so a cursor
+    /// that stays put is not re-queried every frame.
+    last_probe: Option<(BufferId, u64, Cursor)>,
+    session: Option<LinkedEditingSession>,
+}
+
+#[derive(Debug)]
+struct LinkedEditingRequest {
+    request_id: u64,
+    buffer_id: BufferId,
+    version: u64,
+    cursor: Cursor,
+}
+
+#[derive(Debug)]
+struct LinkedEditingSession {
+    split_id: LeafId,
+    buffer_id: BufferId,
+    /// The cursor the session was started from.
+    origin: CursorId,
+    /// Cursors added in the other ranges, removed when the session ends.
+    linked: Vec<CursorId>,
+    /// Bounds of the origin's range. The start marker has left gravity and
+    /// the end marker right gravity, so text typed at either edge stays
+    /// inside the range.
+    range: (MarkerId, MarkerId),
+    /// The server's `wordPattern`, anchored to the whole range text.
+    word_pattern: Option<regex::Regex>,
+}
+
+/// Whether `c` may be typed into a linked range when the server gave no
+/// word pattern: identifier characters plus the separators tag names use.
+fn default_linked_word_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '-' | ':' | '.')
+}
+
+impl Editor {
+    /// Keep the linked editing session in step with the cursors: end it once
+    /// it no longer applies, or ask the server for linked ranges when the
+    /// cursor has settled somewhere new. Called once per frame.
+    pub(super) fn update_linked_editing(&mut self) {
+        if self.active_window().linked_editing.session.is_some() {
+            if !self.linked_editing_session_holds() {
+                self.end_linked_editing();
+            }
+            return;
+        }
+        if self.active_window().linked_editing.pending.is_some() {
+            return;
+        }
+
+        let cursors = self.active_cursors();
+        if cursors.count() != 1 {
+            return;
+        }
+        let cursor = *cursors.primary();
+        let buffer_id = self.active_buffer();
+        let Some(state) = self.active_window().buffers.get(&buffer_id) else {
+            return;
+        };
+        let version = state.buffer.version();
+        let probe = (buffer_id, version, cursor);
+        if self.active_window().linked_editing.last_probe.as_ref() == Some(&probe) {
+            return;
+        }
+        // Only ask a server that is already up and offers the feature; this
+        // runs every frame and must not spawn servers on its own.
+        let language = state.language.clone();
+        if self
+            .lsp()
+            .and_then(|lsp| lsp.handle_for_feature(&language, LspFeature::LinkedEditingRange))
+            .is_none()
+        {
+            return;
+        }
+        let (line, character) = state.buffer.position_to_lsp_position(cursor.position);
+        self.active_window_mut().linked_editing.last_probe = Some(probe);
+
+        let request_id = self.active_window().next_lsp_request_id;
+        let sent = self
+            .with_lsp_for_buffer(
+                buffer_id,
+                LspFeature::LinkedEditingRange,
+                |handle, uri, _language| match handle.linked_editing_range(
+                    request_id,
+                    uri.as_uri().clone(),
+                    line as u32,
+                    character as u32,
+                ) {
+                    Ok(()) => true,
+                    Err(e) => {
+                        tracing::debug!("Failed to request linked editing ranges: {}", e);
+                        false
+                    }
+                },
+            )
+            .unwrap_or(false);
+
+        if sent {
+            let win = self.active_window_mut();
+            win.next_lsp_request_id += 1;
+            win.linked_editing.pending = Some(LinkedEditingRequest {
+                request_id,
+                buffer_id,
+                version,
+                cursor,
+            });
+        }
+    }
+
+    /// Start a session from a `textDocument/linkedEditingRange` response if
+    /// the cursor is still where the request was made.
+    pub(super) fn handle_lsp_linked_editing_ranges(
+        &mut self,
+        request_id: u64,
+        ranges: Option<lsp_types::LinkedEditingRanges>,
+    ) {
+        let linked_editing = &mut self.active_window_mut().linked_editing;
+        if linked_editing
+            .pending
+            .as_ref()
+            .is_none_or(|p| p.request_id != request_id)
+        {
+            return;
+        }
+        let Some(request) = linked_editing.pending.take() else {
+            return;
+        };
+        let Some(ranges) = ranges.filter(|r| r.ranges.len() > 1) else {
+            return;
+        };
+
+        let buffer_id = self.active_buffer();
+        let cursors = self.active_cursors();
+        if buffer_id != request.buffer_id
+            || cursors.count() != 1
+            || *cursors.primary() != request.cursor
+            || self.active_window().linked_editing.session.is_some()
+        {
+            return;
+        }
+        let origin = cursors.primary_id();
+        let first_id = cursors.ids().iter().map(|id| id.0).max().unwrap_or(0) + 1;
+
+        let Some(state) = self.active_window_mut().buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        if state.buffer.version() != request.version {
+            return;
+        }
+        let byte_ranges: Vec<Range<usize>> = ranges
+            .ranges
+            .iter()
+            .map(|r| {
+                let start = state
+                    .buffer
+                    .lsp_position_to_byte(r.start.line as usize, r.start.character as usize);
+                let end = state
+                    .buffer
+                    .lsp_position_to_byte(r.end.line as usize, r.end.character as usize);
+                start..end.max(start)
+            })
+            .collect();
+        let Some((origin_index, mirrored)) = linked_range_cursors(&byte_ranges, &request.cursor)
+        else {
+            return;
+        };
+        let origin_range = &byte_ranges[origin_index];
+        let range = (
+            state.marker_list.create_left_gravity(origin_range.start),
+            state.marker_list.create(origin_range.end, false),
+        );
+        let word_pattern = ranges.word_pattern.as_deref().and_then(|pattern| {
+            regex::Regex::new(&format!("^(?:{pattern})$"))
+                .map_err(|e| tracing::debug!("Ignoring linked editing word pattern: {}", e))
+                .ok()
+        });
+
+        let split_id = self.active_window().effective_active_split();
+        let cursors = self.active_cursors_mut();
+        let mut linked = Vec::with_capacity(mirrored.len());
+        for (id, cursor) in (first_id..).map(CursorId).zip(mirrored) {
+            cursors.insert_with_id(id, cursor);
+            linked.push(id);
+        }
+        // Adding a cursor makes it primary; keep the view on the one the
+        // user is typing with.
+        cursors.set_primary(origin);
+
+        self.active_window_mut().linked_editing.session = Some(LinkedEditingSession {
+            split_id,
+            buffer_id,
+            origin,
+            linked,
+            range,
+            word_pattern,
+        });
+    }
+
+    /// Before `c` is typed: end the session if `c` would take the origin's
+    /// range outside the server's word pattern (e.g. a space after a tag
+    /// name starts an attribute, which must not be mirrored).
+    pub(super) fn end_linked_editing_unless_accepts(&mut self, c: char) {
+        let Some(session) = self.active_window().linked_editing.session.as_ref() else {
+            return;
+        };
+        let Some(pattern) = session.word_pattern.clone() else {
+            if !default_linked_word_char(c) {
+                self.end_linked_editing();
+            }
+            return;
+        };
+        let markers = &self.active_state().marker_list;
+        let bounds = (
+            self.active_cursors().get(session.origin).copied(),
+            markers.get_position(session.range.0),
+            markers.get_position(session.range.1),
+        );
+        let accepts = match bounds {
+            (Some(origin), Some(start), Some(end)) if start <= end => {
+                let edit = origin
+                    .selection_range()
+                    .unwrap_or(origin.position..origin.position);
+                let mut text = self.active_state_mut().get_text_range(start, end);
+                if start <= edit.start && edit.end <= end {
+                    text.replace_range(edit.start - start..edit.end - start, "");
+                    text.insert(edit.start - start, c);
+                }
+                pattern.is_match(&text)
+            }
+            _ => false,
+        };
+        if !accepts {
+            self.end_linked_editing();
+        }
+    }
+
+    /// End the session, removing the cursors it added from the split that
+    /// holds them.
+    pub(super) fn end_linked_editing(&mut self) {
+        let Some(session) = self.active_window_mut().linked_editing.session.take() else {
+            return;
+        };
+        if let Some(state) = self.active_window_mut().buffers.get_mut(&session.buffer_id) {
+            state.marker_list.delete(session.range.0);
+            state.marker_list.delete(session.range.1);
+        }
+        let Some(cursors) = self
+            .split_view_states_mut()
+            .get_mut(&session.split_id)
+            .and_then(|vs| vs.keyed_states.get_mut(&session.buffer_id))
+            .map(|bvs| &mut bvs.cursors)
+        else {
+            return;
+        };
+        for id in session.linked {
+            cursors.remove(id);
+        }
+        cursors.set_primary(session.origin);
+    }
+
+    /// Whether the session still describes the cursors on screen: same split
+    /// and buffer, exactly the cursors it created, and the origin (selection
+    /// included) still inside its range.
+    fn linked_editing_session_holds(&self) -> bool {
+        let Some(session) = self.active_window().linked_editing.session.as_ref() else {
+            return false;
+        };
+        if self.active_window().effective_active_split() != session.split_id
+            || self.active_buffer() != session.buffer_id
+        {
+            return false;
+        }
+        let cursors = self.active_cursors();
+        if cursors.count() != session.linked.len() + 1
+            || session.linked.iter().any(|id| cursors.get(*id).is_none())
+        {
+            return false;
+        }
+        let Some(origin) = cursors.get(session.origin) else {
+            return false;
+        };
+        let markers = &self.active_state().marker_list;
+        let (Some(start), Some(end)) = (
+            markers.get_position(session.range.0),
+            markers.get_position(session.range.1),
+        ) else {
+            return false;
+        };
+        let span = origin
+            .selection_range()
+            .unwrap_or(origin.position..origin.position);
+        start <= span.start && span.end <= end
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_word_chars_cover_tag_names_only() {
+        assert!("my-el:x.y_z9".chars().all(default_linked_word_char));
+        assert!(!" >/=\"".chars().any(default_linked_word_char));
+    }
+}
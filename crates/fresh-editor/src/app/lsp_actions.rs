@@ -1098,6 +1098,12 @@ impl Editor {
         self.active_window_mut()
             .pending_inlay_hints_requests
             .retain(|_, req| req.buffer_id != buffer_id);
+        self.active_window_mut()
+            .clear_code_lenses_for_buffer(buffer_id);
+        self.active_window_mut()
+            .clear_document_symbols_for_buffer(buffer_id);
+        self.active_window_mut()
+            .clear_document_links_for_buffer(buffer_id);
 
         // Clear all LSP-related overlays for this buffer (diagnostics + inlay hints)
         let diagnostic_ns = crate::services::lsp::diagnostics::lsp_diagnostic_namespace();
@@ -1234,12 +1240,15 @@ impl Editor {
             }
         }
 
-        // Schedule folding range refresh
+        // Schedule folding range and code lens refreshes
         let _ = __next_id;
         let _ = lsp;
         let _ = handle;
         self.active_window_mut()
             .schedule_folding_ranges_refresh(buffer_id);
+        self.active_window_mut()
+            .code_lens_requests
+            .schedule_refresh(buffer_id);
     }
 
     /// Set up a plugin development workspace for LSP support on a buffer.
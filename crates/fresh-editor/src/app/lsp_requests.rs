@@ -1425,8 +1425,8 @@ impl Editor {
         use crate::view::virtual_text::VirtualTextPosition;
         use ratatui::style::{Color, Style};
 
-        // Clear existing inlay hints
-        state.virtual_texts.clear(&mut state.marker_list);
+        // Clear existing inlay hints (virtual lines belong to other features)
+        state.virtual_texts.clear_inline(&mut state.marker_list);
 
         if hints.is_empty() {
             return;
@@ -1823,6 +1823,15 @@ impl Editor {
             .clear();
         self.active_window_mut().pending_code_actions = None;
 
+        // Spelling fixes for a misspelled word under the cursor come first
+        // and don't wait for any server; server actions are appended as
+        // they arrive.
+        let spelling_actions = self.spelling_code_actions();
+        if !spelling_actions.is_empty() {
+            self.active_window_mut().pending_code_actions = Some(spelling_actions);
+            self.show_code_actions_popup();
+        }
+
         // Get the current buffer and cursor position
         let cursor_pos = self.active_cursors().primary().position;
         let selection_range = self.active_cursors().primary().selection_range();
@@ -1983,6 +1992,12 @@ impl Editor {
             }
         }
 
+        self.show_code_actions_popup();
+    }
+
+    /// Show (or refresh) the code-action popup listing every action in
+    /// `pending_code_actions`.
+    fn show_code_actions_popup(&mut self) {
         // Build list items from all accumulated code actions
         use crate::view::popup::{Popup, PopupListItem, PopupPosition};
         use ratatui::style::Style;
@@ -2070,15 +2085,22 @@ impl Editor {
     /// Execute a code action by index from the stored pending_code_actions.
     pub(crate) fn execute_code_action(&mut self, index: usize) {
         let action = match &self.active_window_mut().pending_code_actions {
-            Some(actions) => actions.get(index).map(|(_, a)| a.clone()),
+            Some(actions) => actions.get(index).cloned(),
             None => None,
         };
 
-        let Some(action) = action else {
+        let Some((source, action)) = action else {
             tracing::warn!("Code action index {} out of range", index);
             return;
         };
 
+        if source == super::spell_check::SPELLING_ACTION_SOURCE {
+            if let lsp_types::CodeActionOrCommand::Command(cmd) = action {
+                self.execute_spelling_action(cmd);
+            }
+            return;
+        }
+
         match action {
             lsp_types::CodeActionOrCommand::CodeAction(ca) => {
                 // If the action has no edit and no command, it may need resolve first.
@@ -11,15 +11,21 @@ mod buffer_close;
 mod buffer_config_resolve;
 mod buffer_groups;
 mod buffer_management;
+pub(crate) mod buffer_requests;
 mod calibration_actions;
 pub mod calibration_wizard;
 mod click_geometry;
 mod click_handlers;
 mod clipboard;
+pub(crate) mod clipboard_history;
+pub(crate) mod code_lens;
 mod composite_buffer_actions;
 mod dabbrev_actions;
+mod dap_actions;
 mod diagnostic_jumps;
 pub(crate) mod diff_baselines;
+pub(crate) mod document_links;
+pub(crate) mod document_symbols;
 mod editor_accessors;
 mod editor_init;
 mod event_apply;
@@ -34,6 +40,7 @@ mod file_operations;
 mod git_index;
 mod help;
 mod help_actions;
+mod hex_view;
 mod hover;
 mod input;
 mod input_dispatch;
@@ -42,6 +49,7 @@ pub mod keybinding_editor;
 mod keybinding_editor_actions;
 mod lifecycle;
 mod line_scan;
+pub(crate) mod linked_editing;
 mod lsp_actions;
 mod lsp_event_notify;
 mod lsp_requests;
@@ -54,6 +62,7 @@ mod menu_context;
 mod mouse_input;
 mod navigation;
 mod on_save_actions;
+pub(crate) mod on_type_formatting;
 mod orchestrator_persistence;
 mod overlay;
 mod path_utils;
@@ -64,6 +73,10 @@ mod plugin_dispatch;
 #[cfg(feature = "plugins")]
 mod plugin_offloop;
 #[cfg(feature = "plugins")]
+mod plugin_permissions;
+#[cfg(feature = "plugins")]
+mod plugin_profiler;
+#[cfg(feature = "plugins")]
 pub(crate) mod plugin_timers;
 mod popup_actions;
 mod popup_dialogs;
@@ -74,18 +87,23 @@ mod recovery_actions;
 mod regex_replace;
 pub(crate) mod render;
 mod scan_orchestrators;
+pub mod script_batch;
 mod scroll_sync;
 mod scrollbar_input;
 mod scrollbar_math;
 mod search_ops;
 mod search_scan;
+pub(crate) mod selection_range;
 mod settings_actions;
 mod settings_prompts;
 mod shell_command;
 mod smart_home;
+pub(crate) mod snippet_session;
+pub(crate) mod spell_check;
 mod split_actions;
 mod stdin_stream;
 mod tab_drag;
+mod task_actions;
 mod terminal;
 pub use terminal::PluginTerminalSpec;
 mod terminal_input;
@@ -96,6 +114,7 @@ mod theme_inspect;
 mod toggle_actions;
 pub mod types;
 mod undo_actions;
+mod undo_persistence;
 mod view_actions;
 mod virtual_buffers;
 pub mod warning_domains;
@@ -104,6 +123,8 @@ pub mod window;
 mod window_actions;
 pub mod window_resources;
 pub mod workspace;
+mod workspace_diagnostics;
+mod workspace_symbols;
 
 use anyhow::Result as AnyhowResult;
 use rust_i18n::t;
@@ -224,6 +245,7 @@ use crate::input::command_registry::CommandRegistry;
 use crate::input::keybindings::{Action, KeyContext, KeybindingResolver};
 use crate::input::quick_open::{
     BufferProvider, CommandProvider, FileProvider, GotoLineProvider, QuickOpenRegistry,
+    WorkspaceSymbolProvider,
 };
 use crate::model::cursor::Cursors;
 use crate::model::event::{Event, EventLog, LeafId, SplitDirection};
@@ -551,6 +573,11 @@ pub struct Editor {
     /// Shared clipboard (handles both internal and system clipboard)
     clipboard: crate::services::clipboard::Clipboard,
 
+    /// Spell-check dictionary and the language it was loaded for. Loaded on
+    /// first use and shared by all windows; `None` inside when the
+    /// dictionary files are missing.
+    spell_dictionary: Option<(String, Option<Arc<crate::services::spell::Dictionary>>)>,
+
     /// Should the editor quit?
     should_quit: bool,
 
@@ -933,6 +960,11 @@ pub struct Editor {
     pub(crate) plugin_schemas:
         std::sync::Arc<std::sync::RwLock<HashMap<String, serde_json::Value>>>,
 
+    /// Capabilities declared by package plugins, the user's decisions on
+    /// them, and the plugin calls waiting for a decision. See
+    /// `services::plugin_capabilities`.
+    pub(crate) plugin_permissions: crate::services::plugin_capabilities::PluginPermissions,
+
     // `seen_byte_ranges` moved onto `Window` — keyed by `BufferId`
     // which lives on `Window`, so the tracker follows the buffers.
 
@@ -1020,6 +1052,10 @@ pub struct Editor {
     #[cfg(feature = "plugins")]
     grep_project_cancel: std::collections::HashMap<String, Arc<std::sync::atomic::AtomicBool>>,
 
+    /// Cancellation flag for each plugin's in-flight `structuralSearch`.
+    #[cfg(feature = "plugins")]
+    structural_search_cancel: std::collections::HashMap<String, Arc<std::sync::atomic::AtomicBool>>,
+
     /// Live `editor.setInterval` / `setTimeout` timers, checked once per
     /// tick by `check_plugin_timers`. Held on the editor rather than in the
     /// plugin runtime on purpose: the editor's tick is what runs whether or
@@ -1150,13 +1186,16 @@ pub struct Editor {
     // closeWindow joins the threads. Access through methods on Window
     // (called via `self.windows.get_mut(&id).unwrap().method(...)`),
     // not via accessors on Editor.
-    /// Plugin-driven filesystem watchers (lazily constructed —
-    /// the underlying notify backend spawns a thread, so it's
-    /// nicer to defer until the first `watchPath` call). See
-    /// `services/file_watcher.rs`.
-    #[cfg(feature = "plugins")]
+    /// Filesystem watchers for plugins and for auto-revert / file
+    /// explorer refresh (lazily constructed — the underlying notify
+    /// backend spawns a thread, so it's nicer to defer until the
+    /// first watch). See `services/file_watcher.rs`.
     file_watcher_manager: crate::services::file_watcher::FileWatcherManager,
 
+    /// Editor-watch events waiting out their debounce window before
+    /// `process_file_watch_events` acts on them.
+    watch_debouncer: crate::services::file_watcher::WatchEventDebouncer,
+
     /// Test-only log of `path_changed` plugin events. Captured by
     /// `async_dispatch` whenever a PathChanged AsyncMessage arrives,
     /// so e2e tests can assert filesystem events reached the editor
@@ -1169,6 +1208,10 @@ pub struct Editor {
     /// `watch_path` e2e tests to read back the allocated handle.
     pub(crate) last_watch_response_for_test: Option<(u64, Result<u64, String>)>,
 
+    /// Test-only: keep every directory on mtime polling, so e2e tests can
+    /// cover the poll path on filesystems that do deliver watch events.
+    pub(crate) file_watches_disabled_for_test: bool,
+
     /// Plugin-driven session preview override. When `Some(sid)`
     /// and the floating-overlay prompt is open, the overlay's
     /// preview pane renders the *entire* split tree of session
@@ -1402,6 +1402,17 @@ impl Editor {
             }
         }
 
+        // The minimap has nothing to select: a quick second press jumps and
+        // starts a drag like the first one.
+        if let Some(r) = self.handle_click_minimap(col, row) {
+            return r;
+        }
+        // Nor does the sticky scroll header, which repeats lines shown
+        // elsewhere: a second press jumps to the scope again.
+        if let Some(r) = self.handle_click_sticky_scroll(col, row) {
+            return r;
+        }
+
         // Find which split/buffer was clicked and handle double-click
         let split_areas = self.active_layout().split_areas.clone();
         for (split_id, buffer_id, content_rect, _scrollbar_rect, _thumb_start, _thumb_end) in
@@ -1831,6 +1842,12 @@ impl Editor {
         if let Some(r) = self.handle_click_horizontal_scrollbar(col, row) {
             return r;
         }
+        if let Some(r) = self.handle_click_minimap(col, row) {
+            return r;
+        }
+        if let Some(r) = self.handle_click_sticky_scroll(col, row) {
+            return r;
+        }
         if let Some(r) = self.handle_click_status_bar(col, row) {
             return r;
         }
@@ -2405,6 +2422,92 @@ impl Editor {
         Some(Ok(()))
     }
 
+    fn handle_click_minimap(&mut self, col: u16, row: u16) -> Option<AnyhowResult<()>> {
+        let (split_id, buffer_id, target_byte) = self.minimap_target_at(col, row, None)?;
+        self.focus_split(split_id, buffer_id);
+        self.active_window_mut().mouse_state.dragging_minimap = Some(split_id);
+        Some(
+            self.active_window_mut()
+                .handle_minimap_jump(target_byte, split_id, buffer_id),
+        )
+    }
+
+    /// Jump to the scope of the sticky scroll header row at (`col`, `row`):
+    /// the cursor moves to the start of the header line, which is scrolled
+    /// to the row it was clicked on so it stays under the mouse.
+    fn handle_click_sticky_scroll(&mut self, col: u16, row: u16) -> Option<AnyhowResult<()>> {
+        let (split_id, buffer_id, header_row, header) =
+            self.active_layout().sticky_scroll_areas.iter().find_map(
+                |(split_id, buffer_id, rect, headers)| {
+                    if !in_rect(col, row, *rect) {
+                        return None;
+                    }
+                    let header_row = (row - rect.y) as usize;
+                    let header = *headers.get(header_row)?;
+                    Some((*split_id, *buffer_id, header_row, header))
+                },
+            )?;
+        self.focus_split(split_id, buffer_id);
+
+        let win = self.active_window_mut();
+        let Some(state) = win.buffers.get_mut(&buffer_id) else {
+            return Some(Ok(()));
+        };
+        let header = header.min(state.buffer.len());
+        let mut iter = state.buffer.line_iterator(header, 80);
+        for _ in 0..header_row {
+            if iter.prev().is_none() {
+                break;
+            }
+        }
+        let top_byte = iter.current_position();
+        if let Some(pos) = state.buffer.offset_to_position(header) {
+            state.primary_cursor_line_number = crate::model::buffer::LineNumber::Absolute(pos.line);
+        }
+        if let Some(view_state) = win
+            .split_view_states_mut()
+            .and_then(|vs| vs.get_mut(&split_id))
+        {
+            let cursor = view_state.cursors.primary_mut();
+            cursor.position = header;
+            cursor.anchor = None;
+            cursor.sticky_column = None;
+            view_state.viewport.set_top_byte(top_byte);
+            view_state.viewport.set_top_view_line_offset(0);
+            // Skip ensure_visible so the scroll position isn't undone during render
+            view_state.viewport.set_skip_ensure_visible();
+        }
+        Some(Ok(()))
+    }
+
+    /// The split, buffer and scroll target of the minimap row at (`col`,
+    /// `row`). While dragging, `dragging` names the split whose minimap is
+    /// being dragged: the row is clamped to that minimap, so dragging past
+    /// its edges keeps scrolling to the first or last row.
+    fn minimap_target_at(
+        &self,
+        col: u16,
+        row: u16,
+        dragging: Option<LeafId>,
+    ) -> Option<(LeafId, BufferId, usize)> {
+        self.active_layout().minimap_areas.iter().find_map(
+            |(split_id, buffer_id, minimap_rect, row_targets)| {
+                let hit = match dragging {
+                    Some(dragging) => dragging == *split_id,
+                    None => in_rect(col, row, *minimap_rect),
+                };
+                if !hit {
+                    return None;
+                }
+                let relative_row = row
+                    .saturating_sub(minimap_rect.y)
+                    .min(minimap_rect.height.saturating_sub(1));
+                let target = row_targets.get(relative_row as usize)?;
+                Some((*split_id, *buffer_id, *target))
+            },
+        )
+    }
+
     fn handle_click_horizontal_scrollbar(
         &mut self,
         col: u16,
@@ -2903,6 +3006,17 @@ impl Editor {
             }
         }
 
+        // If dragging in a minimap, scroll to the row under the mouse
+        if let Some(dragging_split_id) = self.active_window().mouse_state.dragging_minimap {
+            if let Some((split_id, buffer_id, target_byte)) =
+                self.minimap_target_at(col, row, Some(dragging_split_id))
+            {
+                self.active_window_mut()
+                    .handle_minimap_jump(target_byte, split_id, buffer_id)?;
+            }
+            return Ok(());
+        }
+
         // If dragging horizontal scrollbar, update horizontal scroll position
         if let Some(dragging_split_id) = self
             .active_window_mut()
@@ -4692,6 +4806,7 @@ impl Editor {
         ms.drag_start_row = None;
         ms.drag_start_top_byte = None;
         ms.dragging_horizontal_scrollbar = None;
+        ms.dragging_minimap = None;
         ms.drag_start_hcol = None;
         ms.drag_start_left_column = None;
         ms.dragging_separator = None;
@@ -37,18 +37,26 @@ impl Editor {
 
         let mut ran_any_action = false;
 
-        // Run whitespace cleanup actions first (before formatter)
-        if self.config.editor.trim_trailing_whitespace_on_save && self.trim_trailing_whitespace()? {
+        // Run whitespace cleanup actions first (before formatter). The
+        // file's `.editorconfig` overrides the config either way.
+        let editorconfig = &self.active_state().buffer_settings.editorconfig;
+        let trim = editorconfig
+            .trim_trailing_whitespace
+            .unwrap_or(self.config.editor.trim_trailing_whitespace_on_save);
+        let final_newline = editorconfig
+            .insert_final_newline
+            .unwrap_or(self.config.editor.ensure_final_newline_on_save);
+        if trim && self.trim_trailing_whitespace()? {
             ran_any_action = true;
         }
 
-        if self.config.editor.ensure_final_newline_on_save && self.ensure_final_newline()? {
+        if final_newline && self.ensure_final_newline()? {
             ran_any_action = true;
         }
 
         // If whitespace cleanup made changes, re-save
         if ran_any_action {
-            if let Err(e) = self.active_state_mut().buffer.save() {
+            if let Err(e) = self.active_state_mut().save() {
                 return Err(format!("Failed to re-save after whitespace cleanup: {}", e));
             }
             self.active_event_log_mut().mark_saved();
@@ -75,7 +83,7 @@ impl Editor {
                     ActionResult::Success(output) => {
                         self.replace_buffer_with_output(&output)?;
                         // Re-save after formatting
-                        if let Err(e) = self.active_state_mut().buffer.save() {
+                        if let Err(e) = self.active_state_mut().save() {
                             return Err(format!("Failed to re-save after format: {}", e));
                         }
                         self.active_event_log_mut().mark_saved();
@@ -0,0 +1,139 @@
+//! LSP on-type formatting (`textDocument/onTypeFormatting`).
+//!
+//! Typing one of the server's trigger characters (commonly `;`, `}` or a
+//! newline) asks it for formatting edits around the cursor. The edits are
+//! applied only if the buffer is unchanged since the keystroke, and are then
+//! joined to the keystroke's undo unit so a single undo reverts both.
+
+use super::Editor;
+use crate::model::event::BufferId;
+use crate::types::LspFeature;
+
+#[derive(Clone, Debug)]
+pub(crate) struct OnTypeFormattingRequest {
+    pub(crate) request_id: u64,
+    pub(crate) buffer_id: BufferId,
+    /// Buffer version right after the keystroke.
+    pub(crate) version: u64,
+}
+
+impl Editor {
+    /// Ask for on-type formatting after `ch` was typed, if the server
+    /// registered it as a trigger. Single cursor only: the edits are
+    /// computed for one position.
+    pub(super) fn maybe_request_on_type_formatting(&mut self, ch: char) {
+        let cursors = self.active_cursors();
+        if cursors.count() != 1 || cursors.primary().selection_range().is_some() {
+            return;
+        }
+        let position = cursors.primary().position;
+        let buffer_id = self.active_buffer();
+        let state = self.active_state();
+        let language = state.language.clone();
+        let version = state.buffer.version();
+        let (line, character) = state.buffer.position_to_lsp_position(position);
+
+        let ch = ch.to_string();
+        let is_trigger = self
+            .lsp()
+            .and_then(|lsp| lsp.handle_for_feature(&language, LspFeature::OnTypeFormatting))
+            .is_some_and(|sh| sh.capabilities.on_type_formatting_triggers.contains(&ch));
+        if !is_trigger {
+            return;
+        }
+
+        let tab_size = self.config.editor.tab_size as u32;
+        let insert_spaces = !self.config.editor.use_tabs;
+        let request_id = self.active_window().next_lsp_request_id;
+        let sent = self
+            .with_lsp_for_buffer(
+                buffer_id,
+                LspFeature::OnTypeFormatting,
+                |handle, uri, _language| match handle.on_type_formatting(
+                    request_id,
+                    uri.as_uri().clone(),
+                    line as u32,
+                    character as u32,
+                    ch,
+                    tab_size,
+                    insert_spaces,
+                ) {
+                    Ok(()) => true,
+                    Err(e) => {
+                        tracing::debug!("Failed to request on-type formatting: {}", e);
+                        false
+                    }
+                },
+            )
+            .unwrap_or(false);
+
+        if sent {
+            let win = self.active_window_mut();
+            win.next_lsp_request_id += 1;
+            // A newer keystroke supersedes any request still in flight.
+            win.pending_on_type_formatting = Some(OnTypeFormattingRequest {
+                request_id,
+                buffer_id,
+                version,
+            });
+        }
+    }
+
+    /// Apply a `textDocument/onTypeFormatting` response in the keystroke's
+    /// undo unit. Dropped when the buffer changed since the request.
+    pub(super) fn handle_lsp_on_type_formatting(
+        &mut self,
+        request_id: u64,
+        edits: Vec<lsp_types::TextEdit>,
+    ) {
+        let win = self.active_window_mut();
+        if win
+            .pending_on_type_formatting
+            .as_ref()
+            .is_none_or(|p| p.request_id != request_id)
+        {
+            return;
+        }
+        let Some(request) = win.pending_on_type_formatting.take() else {
+            return;
+        };
+        if edits.is_empty() {
+            return;
+        }
+        let current_version = win
+            .buffers
+            .get(&request.buffer_id)
+            .map(|s| s.buffer.version());
+        if current_version != Some(request.version) {
+            tracing::debug!(
+                "Dropping stale on-type formatting for {:?} (version {} != {:?})",
+                request.buffer_id,
+                request.version,
+                current_version
+            );
+            return;
+        }
+
+        let logged_before = self.event_log_index(request.buffer_id);
+        if let Err(e) = self.apply_lsp_text_edits(request.buffer_id, edits) {
+            tracing::error!("Failed to apply on-type formatting: {}", e);
+            return;
+        }
+        if self.event_log_index(request.buffer_id) != logged_before {
+            if let Some(log) = self
+                .active_window_mut()
+                .event_logs
+                .get_mut(&request.buffer_id)
+            {
+                log.join_last_with_previous_undo_unit();
+            }
+        }
+    }
+
+    fn event_log_index(&self, buffer_id: BufferId) -> Option<usize> {
+        self.active_window()
+            .event_logs
+            .get(&buffer_id)
+            .map(|log| log.current_index())
+    }
+}
@@ -77,7 +77,7 @@ pub(super) struct BeginSearchArgs {
 /// Patterns with a path separator match the whole relative path (`src/**`),
 /// while patterns without one match the basename at any depth (`*.rs`).
 /// Whitespace around comma-separated patterns is ignored.
-fn search_file_glob_matches(file_glob: &str, relative_path: &str) -> bool {
+pub(super) fn search_file_glob_matches(file_glob: &str, relative_path: &str) -> bool {
     let relative_path = relative_path.trim_start_matches(['/', '\\']);
     let file_name = relative_path
         .rsplit(['/', '\\'])
@@ -2523,7 +2523,7 @@ impl Editor {
                     "LSP server for '{}' is not running (auto_start disabled)",
                     language
                 ))
-            } else if let Some(handle) = lsp.get_handle_mut(&language) {
+            } else if let Some(handle) = lsp.handle_for_request_mut(&language, &method) {
                 handle.send_plugin_request(request_id, method, params).err()
             } else {
                 Some(format!("LSP server for '{}' is unavailable", language))
@@ -3131,6 +3131,112 @@ impl Editor {
         );
     }
 
+    /// Handle StructuralSearchProject: parse the pattern, snapshot the open
+    /// buffers in its language, then hand the walk to
+    /// [`plugin_offloop::structural_search`]. A second search from the same
+    /// plugin supersedes the first, as with `grepProject`.
+    ///
+    /// Unlike grep, dirty buffers are copied as full text: the file is
+    /// parsed whole either way.
+    #[allow(clippy::too_many_arguments)]
+    pub(super) fn handle_structural_search_project(
+        &mut self,
+        plugin_name: String,
+        pattern: String,
+        language: String,
+        rewrite: Option<String>,
+        file_glob: String,
+        max_results: usize,
+        callback_id: JsCallbackId,
+    ) {
+        let parsed = crate::primitives::highlighter::Language::from_id(&language)
+            .ok_or_else(|| format!("Unknown language: {}", language))
+            .and_then(|lang| {
+                crate::primitives::structural_pattern::StructuralPattern::new(&pattern, lang)
+            })
+            .and_then(|pattern| {
+                let rewrite = rewrite
+                    .map(|r| crate::primitives::structural_pattern::Rewrite::new(&r, &pattern))
+                    .transpose()?;
+                Ok((pattern, rewrite))
+            });
+        let (pattern, rewrite) = match parsed {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                self.plugin_manager
+                    .read()
+                    .unwrap()
+                    .reject_callback(callback_id, e);
+                return;
+            }
+        };
+
+        let lang = pattern.language();
+        let mut dirty_buffers = std::collections::HashMap::new();
+        let mut clean_buffers = std::collections::HashMap::new();
+        for (bid, state) in self
+            .windows
+            .get_mut(&self.active_window)
+            .map(|w| &mut w.buffers)
+            .expect("active window present")
+        {
+            let Some(path) = state.buffer.file_path().map(|p| p.to_path_buf()) else {
+                continue;
+            };
+            if state.buffer.is_modified()
+                && crate::primitives::highlighter::Language::from_path(&path) == Some(lang)
+            {
+                if let Some(text) = state.buffer.to_string() {
+                    dirty_buffers.insert(path, (*bid, text));
+                    continue;
+                }
+            }
+            clean_buffers.insert(path, *bid);
+        }
+
+        let Some(runtime) = self.tokio_runtime.clone() else {
+            self.plugin_manager
+                .read()
+                .unwrap()
+                .reject_callback(callback_id, "No tokio runtime available".to_string());
+            return;
+        };
+        let Some(sender) = self.async_bridge.as_ref().map(|b| b.sender()) else {
+            self.plugin_manager
+                .read()
+                .unwrap()
+                .reject_callback(callback_id, "No async bridge available".to_string());
+            return;
+        };
+
+        if let Some(prev) = self.structural_search_cancel.remove(&plugin_name) {
+            prev.store(true, std::sync::atomic::Ordering::Relaxed);
+        }
+        let cancel = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        self.structural_search_cancel
+            .insert(plugin_name, Arc::clone(&cancel));
+
+        super::plugin_offloop::structural_search(
+            super::plugin_offloop::OffLoop {
+                filesystem: self.authority().filesystem.clone(),
+                runtime,
+                sender,
+            },
+            super::plugin_offloop::StructuralSearchRequest {
+                pattern,
+                rewrite,
+                file_glob,
+                max_results,
+                root: self.working_dir().to_path_buf(),
+                ignored_dirs: IGNORED_DIRS,
+                callback_id,
+                dirty_buffers,
+                clean_buffers,
+                cancel,
+            },
+        );
+    }
+
     // ==================== Pull-Based Streaming Search ====================
 
     /// Handle BeginSearch: spawn the parallel searcher tasks for a streaming
@@ -3531,8 +3637,20 @@ impl Editor {
         buffer_id: usize,
         matches: Vec<(usize, usize)>,
         replacement: String,
+        replacements: Vec<String>,
         callback_id: JsCallbackId,
     ) {
+        if !replacements.is_empty() && replacements.len() != matches.len() {
+            self.plugin_manager.read().unwrap().reject_callback(
+                callback_id,
+                format!(
+                    "{} replacements given for {} matches",
+                    replacements.len(),
+                    matches.len()
+                ),
+            );
+            return;
+        }
         if matches.is_empty() {
             let result = ReplaceResult {
                 replacements: 0,
@@ -3599,24 +3717,30 @@ impl Editor {
             }
         };
 
-        // Sort matches by byte offset descending — editing from end backwards
-        // prevents earlier edits from shifting later offsets
-        let mut sorted_matches = matches;
-        sorted_matches.sort_by_key(|a| std::cmp::Reverse(a.0));
+        // Pair each match with its text, then sort by byte offset descending —
+        // editing from end backwards prevents earlier edits from shifting
+        // later offsets
+        let mut edits_owned: Vec<(usize, usize, String)> = if replacements.is_empty() {
+            matches
+                .into_iter()
+                .map(|(offset, len)| (offset, len, replacement.clone()))
+                .collect()
+        } else {
+            matches
+                .into_iter()
+                .zip(replacements)
+                .map(|((offset, len), text)| (offset, len, text))
+                .collect()
+        };
+        edits_owned.sort_by_key(|a| std::cmp::Reverse(a.0));
 
         // Build bulk edits: (start, del_len, replacement)
-        let edits: Vec<(usize, usize, &str)> = sorted_matches
+        let edits: Vec<(usize, usize, &str)> = edits_owned
             .iter()
-            .map(|&(offset, len)| (offset, len, replacement.as_str()))
+            .map(|(offset, len, text)| (*offset, *len, text.as_str()))
             .collect();
 
         let replacements = edits.len();
-
-        // Owned tuples for helpers that don't take references.
-        let edits_owned: Vec<(usize, usize, String)> = sorted_matches
-            .iter()
-            .map(|&(offset, len)| (offset, len, replacement.clone()))
-            .collect();
         // Merged edit-lengths list for marker/margin replay on undo/redo.
         // Mirrors the merging logic in `apply_events_as_bulk_edit`.
         let edit_lengths: Vec<(usize, usize, usize)> = {
@@ -3690,7 +3814,7 @@ impl Editor {
             // it as an external change, and reverts the buffer from disk,
             // wiping the event log we're about to append (see bug #1).
             if let Some(path) = state.buffer.file_path().map(|p| p.to_path_buf()) {
-                if let Err(e) = state.buffer.save_to_file(&path) {
+                if let Err(e) = state.save_to_file(&path) {
                     self.plugin_manager.read().unwrap().reject_callback(
                         callback_id,
                         format!("Failed to save file {:?}: {}", path, e),
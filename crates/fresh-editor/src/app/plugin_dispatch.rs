@@ -1243,6 +1243,14 @@ impl Editor {
                 self.handle_kill_host_process(process_id);
             }
 
+            PluginCommand::RequestPluginCapability {
+                plugin_name,
+                capability,
+                command,
+            } => {
+                self.handle_request_plugin_capability(plugin_name, capability, command.map(|c| *c));
+            }
+
             PluginCommand::SetAuthority { payload } => {
                 self.handle_set_authority(payload);
             }
@@ -1829,6 +1837,26 @@ impl Editor {
                 );
             }
 
+            PluginCommand::StructuralSearchProject {
+                plugin_name,
+                pattern,
+                language,
+                rewrite,
+                file_glob,
+                max_results,
+                callback_id,
+            } => {
+                self.handle_structural_search_project(
+                    plugin_name,
+                    pattern,
+                    language,
+                    rewrite,
+                    file_glob,
+                    max_results,
+                    callback_id,
+                );
+            }
+
             PluginCommand::BeginSearch {
                 pattern,
                 fixed_string,
@@ -1856,6 +1884,7 @@ impl Editor {
                 buffer_id,
                 matches,
                 replacement,
+                replacements,
                 callback_id,
             } => {
                 self.handle_replace_in_buffer(
@@ -1863,6 +1892,7 @@ impl Editor {
                     buffer_id,
                     matches,
                     replacement,
+                    replacements,
                     callback_id,
                 );
             }
@@ -2399,6 +2429,10 @@ impl Editor {
 
     /// Save a buffer to a specific file path (for :w filename)
     fn handle_save_buffer_to_path(&mut self, buffer_id: BufferId, path: std::path::PathBuf) {
+        let editorconfig = crate::services::editorconfig::resolve_for_file(
+            self.authority().filesystem.as_ref(),
+            &path,
+        );
         if let Some(state) = self
             .windows
             .get_mut(&self.active_window)
@@ -2406,8 +2440,10 @@ impl Editor {
             .expect("active window present")
             .get_mut(&buffer_id)
         {
-            // Save to the specified path
-            match state.buffer.save_to_file(&path) {
+            // Save to the specified path, in the line endings its
+            // `.editorconfig` asks for
+            state.buffer_settings.editorconfig = editorconfig;
+            match state.save_to_file(&path) {
                 Ok(()) => {
                     // save_to_file already updates file_path internally via finalize_save
                     // Run on-save actions (formatting, etc.)
@@ -3799,7 +3835,7 @@ impl Editor {
                     wrap_column: self
                         .active_window()
                         .resolve_wrap_column_for_buffer(buffer_id),
-                    rulers: self.config.editor.rulers.clone(),
+                    rulers: self.active_window().resolve_rulers_for_buffer(buffer_id),
                     scroll_offset: self.config.editor.scroll_offset,
                 });
                 view_state.ensure_buffer_state(buffer_id).show_line_numbers = show_line_numbers;
@@ -4885,6 +4921,42 @@ impl Editor {
                     let _ = sender.send(msg);
                 });
             }
+            RemoteTransportSpec::Exec { carrier } => {
+                let label = carrier.display();
+                // Far-side workspace to re-root at (e.g. `/src`).
+                let workspace = carrier.workspace.clone().map(std::path::PathBuf::from);
+                let mode = mode_for(&label);
+                self.set_status_message(format!("Connecting to {label}…"));
+                runtime.spawn(async move {
+                    let outcome = crate::services::authority::connect_exec_authority(
+                        carrier,
+                        base_env,
+                        trust,
+                        env,
+                        Some(cancel_rx),
+                    )
+                    .await;
+                    let msg = match outcome {
+                        Ok((authority, keepalive)) => AsyncMessage::RemoteAttachReady(
+                            crate::services::async_bridge::RemoteAttachReady {
+                                authority,
+                                keepalive: Box::new(keepalive),
+                                working_dir: workspace,
+                                mode,
+                                spec: session_spec,
+                                request_id,
+                            },
+                        ),
+                        Err(e) => AsyncMessage::RemoteAttachFailed {
+                            error: e.to_string(),
+                            request_id,
+                            reconnect_window,
+                        },
+                    };
+                    #[allow(clippy::let_underscore_must_use)]
+                    let _ = sender.send(msg);
+                });
+            }
             RemoteTransportSpec::Ssh {
                 user,
                 host,
@@ -19,11 +19,14 @@ use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
-use fresh_core::api::{GrepMatch, JsCallbackId, PluginAsyncMessage};
+use fresh_core::api::{GrepMatch, JsCallbackId, PluginAsyncMessage, StructuralMatch};
 use fresh_core::BufferId;
 
 use crate::model::buffer::HybridSearchPlan;
 use crate::model::filesystem::{FileSearchCursor, FileSearchOptions, FileSystem};
+use crate::primitives::highlighter::Language;
+use crate::primitives::structural_pattern::{Rewrite, StructuralPattern};
+use crate::primitives::syntax_tree::MAX_SYNTAX_PARSE_BYTES;
 use crate::services::async_bridge::AsyncMessage;
 
 /// Capability handle for plugin work that must not run on the editor thread.
@@ -287,6 +290,202 @@ fn search_one(
         .collect()
 }
 
+// ============================================================================
+// Structural search
+// ============================================================================
+
+/// Editor-thread-collected inputs for a project structural search. Files are
+/// parsed whole, so dirty buffers are snapshotted as their full text.
+pub(crate) struct StructuralSearchRequest {
+    pub pattern: StructuralPattern,
+    pub rewrite: Option<Rewrite>,
+    pub file_glob: String,
+    pub max_results: usize,
+    pub root: PathBuf,
+    pub ignored_dirs: &'static [&'static str],
+    pub callback_id: JsCallbackId,
+    /// Open, modified buffers in the pattern's language, keyed by path.
+    pub dirty_buffers: HashMap<PathBuf, (BufferId, String)>,
+    /// Open, clean buffers keyed by path — read from disk, attributed to
+    /// their buffer id.
+    pub clean_buffers: HashMap<PathBuf, BufferId>,
+    /// Flips when a newer structural search from the same plugin
+    /// supersedes this one.
+    pub cancel: Arc<AtomicBool>,
+}
+
+/// Walk `root`, parse every file in the pattern's language and collect its
+/// matches, entirely off the editor thread. Files over
+/// [`MAX_SYNTAX_PARSE_BYTES`] are skipped.
+pub(crate) fn structural_search(cap: OffLoop, req: StructuralSearchRequest) {
+    let runtime = Arc::clone(&cap.runtime);
+    runtime.spawn(async move {
+        let StructuralSearchRequest {
+            pattern,
+            rewrite,
+            file_glob,
+            max_results,
+            root,
+            ignored_dirs,
+            callback_id,
+            mut dirty_buffers,
+            clean_buffers,
+            cancel,
+        } = req;
+        let language = pattern.language();
+        let pattern = Arc::new(pattern);
+        let rewrite = Arc::new(rewrite);
+
+        let (path_tx, mut path_rx) = tokio::sync::mpsc::channel::<PathBuf>(256);
+        let walk_fs = Arc::clone(&cap.filesystem);
+        let walk_cancel = Arc::clone(&cancel);
+        tokio::task::spawn_blocking(move || {
+            if let Err(e) =
+                walk_fs.walk_files(&root, ignored_dirs, &walk_cancel, &mut |path, rel| {
+                    if Language::from_path(path) != Some(language)
+                        || !super::plugin_commands::search_file_glob_matches(&file_glob, rel)
+                    {
+                        return true;
+                    }
+                    path_tx.blocking_send(path.to_path_buf()).is_ok()
+                })
+            {
+                tracing::warn!("structuralSearch: walk_files failed: {}", e);
+            }
+        });
+
+        let semaphore = Arc::new(tokio::sync::Semaphore::new(8));
+        let mut joins: Vec<tokio::task::JoinHandle<Vec<StructuralMatch>>> = Vec::new();
+        let mut results: Vec<StructuralMatch> = Vec::new();
+        let mut enough_results = false;
+
+        while let Some(file_path) = path_rx.recv().await {
+            if cancel.load(Ordering::Relaxed) {
+                break;
+            }
+            let Ok(permit) = Arc::clone(&semaphore).acquire_owned().await else {
+                break;
+            };
+            let fs = Arc::clone(&cap.filesystem);
+            let pattern = Arc::clone(&pattern);
+            let rewrite = Arc::clone(&rewrite);
+            let task_cancel = Arc::clone(&cancel);
+            let dirty = dirty_buffers.remove(&file_path);
+            let clean_buffer_id = clean_buffers.get(&file_path).map(|b| b.0).unwrap_or(0);
+            joins.push(tokio::task::spawn_blocking(move || {
+                let _permit = permit;
+                if task_cancel.load(Ordering::Relaxed) {
+                    return Vec::new();
+                }
+                let (buffer_id, content) = match dirty {
+                    Some((bid, text)) => (bid.0, text.into_bytes()),
+                    None => match fs.read_file(&file_path) {
+                        Ok(bytes) => (clean_buffer_id, bytes),
+                        Err(_) => return Vec::new(),
+                    },
+                };
+                structural_search_one(&file_path, buffer_id, &content, &pattern, &rewrite)
+            }));
+
+            if joins.len() >= 32 {
+                drain_structural_joins(&mut joins, &mut results, max_results).await;
+                if results.len() >= max_results {
+                    enough_results = true;
+                    cancel.store(true, Ordering::Relaxed);
+                    break;
+                }
+            }
+        }
+        drain_structural_joins(&mut joins, &mut results, max_results).await;
+
+        // Dirty buffers the walk never reached (outside the workspace root).
+        for (file_path, (bid, text)) in dirty_buffers {
+            if results.len() >= max_results || cancel.load(Ordering::Relaxed) {
+                break;
+            }
+            results.extend(structural_search_one(
+                &file_path,
+                bid.0,
+                text.as_bytes(),
+                &pattern,
+                &rewrite,
+            ));
+        }
+
+        if cancel.load(Ordering::Relaxed) && !enough_results {
+            cap.settle(
+                callback_id,
+                Err("structuralSearch superseded by a newer call from this plugin".to_string()),
+            );
+            return;
+        }
+
+        results.truncate(max_results);
+        let json = serde_json::to_string(&results).unwrap_or_else(|_| "[]".to_string());
+        cap.settle(callback_id, Ok(json));
+    });
+}
+
+async fn drain_structural_joins(
+    joins: &mut Vec<tokio::task::JoinHandle<Vec<StructuralMatch>>>,
+    results: &mut Vec<StructuralMatch>,
+    max_results: usize,
+) {
+    for join in joins.drain(..) {
+        if let Ok(matches) = join.await {
+            if results.len() < max_results {
+                results.extend(matches);
+            }
+        }
+    }
+}
+
+fn structural_search_one(
+    file_path: &std::path::Path,
+    buffer_id: usize,
+    content: &[u8],
+    pattern: &StructuralPattern,
+    rewrite: &Option<Rewrite>,
+) -> Vec<StructuralMatch> {
+    if content.len() > MAX_SYNTAX_PARSE_BYTES {
+        return Vec::new();
+    }
+    let file = file_path.to_string_lossy().to_string();
+    let mut line = 1;
+    let mut counted_to = 0;
+    pattern
+        .find_matches(content)
+        .into_iter()
+        .map(|m| {
+            let start = m.range.start;
+            line += content[counted_to..start]
+                .iter()
+                .filter(|&&b| b == b'\n')
+                .count();
+            counted_to = start;
+            let line_start = content[..start]
+                .iter()
+                .rposition(|&b| b == b'\n')
+                .map_or(0, |p| p + 1);
+            let line_end = content[start..]
+                .iter()
+                .position(|&b| b == b'\n')
+                .map_or(content.len(), |p| start + p);
+            StructuralMatch {
+                file: file.clone(),
+                buffer_id,
+                byte_offset: start,
+                length: m.range.len(),
+                line,
+                column: start - line_start + 1,
+                context: String::from_utf8_lossy(&content[line_start..line_end]).into_owned(),
+                text: String::from_utf8_lossy(&content[m.range.clone()]).into_owned(),
+                replacement: rewrite.as_ref().map(|r| r.expand(content, &m)),
+            }
+        })
+        .collect()
+}
+
 // ============================================================================
 // Diff-baseline loading
 // ============================================================================
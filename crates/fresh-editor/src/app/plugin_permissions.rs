@@ -0,0 +1,284 @@
+//! Package-plugin capability requests: the one-time approval prompt, the
+//! calls parked while it is open, and the "Plugin Permissions" list that
+//! revokes earlier answers.
+//!
+//! The plugin runtime only sends `PluginCommand::RequestPluginCapability`
+//! when the published policy doesn't already allow a call; the decision
+//! logic and persistence live in `services::plugin_capabilities`.
+
+use fresh_core::api::PluginCommand;
+use fresh_core::capability::PluginCapability;
+use rust_i18n::t;
+
+use crate::input::commands::Suggestion;
+use crate::services::plugin_capabilities::{CapabilityDecision, CapabilityStatus};
+use crate::view::prompt::{Prompt, PromptType};
+
+use super::Editor;
+
+/// What a capability lets a plugin do, phrased to follow "wants to".
+fn describe(capability: PluginCapability) -> String {
+    match capability {
+        PluginCapability::Process => t!("plugin_permissions.process"),
+        PluginCapability::Network => t!("plugin_permissions.network"),
+        PluginCapability::Filesystem => t!("plugin_permissions.filesystem"),
+        PluginCapability::Authority => t!("plugin_permissions.authority"),
+    }
+    .to_string()
+}
+
+impl Editor {
+    /// Handle a plugin call that needs a capability the plugin doesn't hold
+    /// yet: run it, refuse it, or park it behind the approval prompt.
+    pub(super) fn handle_request_plugin_capability(
+        &mut self,
+        plugin_name: String,
+        capability: PluginCapability,
+        command: Option<PluginCommand>,
+    ) {
+        match self.plugin_permissions.status(&plugin_name, capability) {
+            CapabilityStatus::Allowed => {
+                if let Some(command) = command {
+                    self.dispatch_permitted_plugin_command(command);
+                }
+            }
+            CapabilityStatus::Undeclared => {
+                let reason = t!(
+                    "plugin_permissions.undeclared",
+                    plugin = &plugin_name,
+                    capability = describe(capability)
+                )
+                .to_string();
+                self.refuse_plugin_command(command, reason);
+            }
+            CapabilityStatus::Denied => {
+                let reason = t!(
+                    "plugin_permissions.refused",
+                    plugin = &plugin_name,
+                    capability = describe(capability)
+                )
+                .to_string();
+                self.refuse_plugin_command(command, reason);
+            }
+            CapabilityStatus::Undecided => {
+                self.plugin_permissions
+                    .queue(&plugin_name, capability, command);
+                self.prompt_pending_plugin_capability();
+            }
+        }
+    }
+
+    /// Ask about the oldest parked capability request, unless a prompt is
+    /// already open — in which case this is retried every frame until it
+    /// closes.
+    pub(crate) fn prompt_pending_plugin_capability(&mut self) {
+        if self.active_window().prompt.is_some() {
+            return;
+        }
+        let Some((plugin, capability)) = self.plugin_permissions.next_pending() else {
+            return;
+        };
+        self.start_prompt(
+            t!(
+                "plugin_permissions.prompt",
+                plugin = &plugin,
+                capability = describe(capability)
+            )
+            .to_string(),
+            PromptType::ConfirmPluginCapability { plugin, capability },
+        );
+    }
+
+    /// The user answered the approval prompt. Anything other than yes or no
+    /// is treated like cancelling: the parked calls fail, nothing is stored,
+    /// and the next use asks again.
+    pub(super) fn answer_plugin_capability(
+        &mut self,
+        plugin: String,
+        capability: PluginCapability,
+        input: &str,
+    ) {
+        let decision = match input.trim().to_lowercase().as_str() {
+            "y" | "yes" => CapabilityDecision::Granted,
+            "n" | "no" => CapabilityDecision::Denied,
+            _ => {
+                self.cancel_plugin_capability(plugin, capability);
+                return;
+            }
+        };
+
+        if let Err(e) = self
+            .plugin_permissions
+            .decide(&plugin, capability, decision)
+        {
+            tracing::warn!("Failed to persist plugin capability decision: {}", e);
+            self.set_status_message(
+                t!("plugin_permissions.save_failed", error = e.to_string()).to_string(),
+            );
+        }
+        self.publish_plugin_capabilities();
+
+        let parked = self.plugin_permissions.take_pending(&plugin, capability);
+        match decision {
+            CapabilityDecision::Granted => {
+                self.set_status_message(
+                    t!(
+                        "plugin_permissions.granted",
+                        plugin = &plugin,
+                        capability = describe(capability)
+                    )
+                    .to_string(),
+                );
+                for command in parked {
+                    self.dispatch_permitted_plugin_command(command);
+                }
+            }
+            CapabilityDecision::Denied => {
+                let reason = t!(
+                    "plugin_permissions.refused",
+                    plugin = &plugin,
+                    capability = describe(capability)
+                )
+                .to_string();
+                self.set_status_message(reason.clone());
+                for command in parked {
+                    self.refuse_plugin_command(Some(command), reason.clone());
+                }
+            }
+        }
+    }
+
+    /// The approval prompt was dismissed: fail the parked calls without
+    /// recording anything.
+    pub(crate) fn cancel_plugin_capability(
+        &mut self,
+        plugin: String,
+        capability: PluginCapability,
+    ) {
+        let reason = t!(
+            "plugin_permissions.refused",
+            plugin = &plugin,
+            capability = describe(capability)
+        )
+        .to_string();
+        for command in self.plugin_permissions.take_pending(&plugin, capability) {
+            self.refuse_plugin_command(Some(command), reason.clone());
+        }
+    }
+
+    /// List every recorded decision; picking one revokes it.
+    pub(super) fn show_plugin_permissions(&mut self) {
+        let decisions = self.plugin_permissions.decisions();
+        if decisions.is_empty() {
+            self.set_status_message(t!("plugin_permissions.none").to_string());
+            return;
+        }
+        let suggestions = decisions
+            .into_iter()
+            .map(|(plugin, capability, decision)| {
+                let state = match decision {
+                    CapabilityDecision::Granted => t!("plugin_permissions.allowed_label"),
+                    CapabilityDecision::Denied => t!("plugin_permissions.denied_label"),
+                };
+                Suggestion {
+                    description_spans: None,
+                    text: format!("{}: {}", plugin, capability),
+                    description: Some(format!("{} ({})", describe(capability), state)),
+                    value: Some(format!("{}\t{}", plugin, capability)),
+                    disabled: false,
+                    keybinding: None,
+                    source: None,
+                }
+            })
+            .collect();
+        self.active_window_mut().prompt = Some(Prompt::with_suggestions(
+            t!("plugin_permissions.select").to_string(),
+            PromptType::RevokePluginPermission,
+            suggestions,
+        ));
+    }
+
+    /// Revoke the decision picked in the "Plugin Permissions" list (`value`
+    /// is `"<plugin>\t<capability>"`).
+    pub(super) fn revoke_plugin_permission(&mut self, value: &str) {
+        let Some((plugin, capability)) = value
+            .split_once('\t')
+            .and_then(|(p, c)| Some((p, PluginCapability::from_str_name(c)?)))
+        else {
+            return;
+        };
+        match self.plugin_permissions.revoke(plugin, capability) {
+            Ok(()) => self.set_status_message(
+                t!(
+                    "plugin_permissions.revoked",
+                    plugin = plugin,
+                    capability = describe(capability)
+                )
+                .to_string(),
+            ),
+            Err(e) => self.set_status_message(
+                t!("plugin_permissions.save_failed", error = e.to_string()).to_string(),
+            ),
+        }
+        self.publish_plugin_capabilities();
+    }
+
+    /// Push the current policy to the plugin runtime.
+    fn publish_plugin_capabilities(&mut self) {
+        let policy = self.plugin_permissions.policy();
+        if let Some(handle) = self.plugin_manager.read().unwrap().state_snapshot_handle() {
+            if let Ok(mut snapshot) = handle.write() {
+                snapshot.plugin_capabilities = policy;
+            }
+        }
+    }
+
+    fn dispatch_permitted_plugin_command(&mut self, command: PluginCommand) {
+        if let Err(e) = self.handle_plugin_command(command) {
+            tracing::error!("Error handling permitted plugin command: {}", e);
+        }
+    }
+
+    /// Fail a refused call the way its API reports failures, so the plugin's
+    /// promise settles instead of hanging. Fire-and-forget calls only get the
+    /// status message.
+    fn refuse_plugin_command(&mut self, command: Option<PluginCommand>, reason: String) {
+        tracing::warn!("{}", reason);
+        match command {
+            Some(
+                PluginCommand::SpawnProcess { callback_id, .. }
+                | PluginCommand::SpawnHostProcess { callback_id, .. }
+                | PluginCommand::HttpFetch { callback_id, .. },
+            ) => {
+                self.handle_plugin_process_output(callback_id, String::new(), reason, -1);
+            }
+            Some(PluginCommand::SpawnBackgroundProcess {
+                process_id,
+                callback_id,
+                ..
+            }) => {
+                let result = fresh_core::api::BackgroundProcessResult {
+                    process_id,
+                    exit_code: -1,
+                };
+                self.plugin_manager
+                    .read()
+                    .unwrap()
+                    .resolve_callback(callback_id, serde_json::to_string(&result).unwrap());
+            }
+            Some(PluginCommand::AttachRemoteAgent { request_id, .. }) => {
+                self.reject_remote_attach(request_id, reason);
+            }
+            Some(
+                PluginCommand::CreateTerminal { request_id, .. }
+                | PluginCommand::CreateWindowWithTerminal { request_id, .. },
+            ) => {
+                self.plugin_manager
+                    .read()
+                    .unwrap()
+                    .reject_callback(fresh_core::api::JsCallbackId::from(request_id), reason);
+            }
+            _ => self.set_status_message(reason),
+        }
+    }
+}
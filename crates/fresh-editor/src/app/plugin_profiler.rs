@@ -0,0 +1,141 @@
+//! "Plugin Profiler": cumulative time each plugin spent running, broken
+//! down by hook and command.
+//!
+//! The plugin runtime's watchdog brackets every run of plugin code (see
+//! `fresh_plugin_runtime::backend::watchdog`); this view just formats what
+//! it accumulated into a read-only buffer. Reopening the view refreshes it.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use fresh_plugin_runtime::backend::{PluginProfileEntry, RunKind};
+
+use super::help_actions::HELP_PANEL_MODE;
+use super::Editor;
+
+/// The name of the plugin profiler buffer
+pub const PLUGIN_PROFILER_BUFFER_NAME: &str = "*Plugin Profiler*";
+
+impl Editor {
+    /// Open (or refresh) the plugin profiler buffer.
+    pub(super) fn open_plugin_profiler(&mut self) {
+        let entries = self.plugin_manager.read().unwrap().plugin_profile();
+        let content = format_plugin_profile(&entries);
+
+        self.ensure_help_panel_mode_registered();
+        let window = self.active_window_mut();
+        let existing_buffer = window
+            .buffer_metadata
+            .iter()
+            .find(|(_, m)| m.display_name == PLUGIN_PROFILER_BUFFER_NAME)
+            .map(|(id, _)| *id);
+        let buffer_id = existing_buffer.unwrap_or_else(|| {
+            window.create_virtual_buffer(
+                PLUGIN_PROFILER_BUFFER_NAME.to_string(),
+                HELP_PANEL_MODE.to_string(),
+                true,
+            )
+        });
+
+        if let Some(state) = window.buffers.get_mut(&buffer_id) {
+            state.buffer.replace_content(&content);
+            state.buffer.clear_modified();
+            state.editing_disabled = true;
+            state.margins.configure_for_line_numbers(false);
+        }
+
+        window.set_active_buffer(buffer_id);
+    }
+}
+
+/// Render the profile as text: one section per plugin, slowest plugin
+/// first, each listing its hooks and commands slowest first.
+fn format_plugin_profile(entries: &[PluginProfileEntry]) -> String {
+    let mut content = String::from("Plugin Profiler\n");
+    content.push_str("===============\n\n");
+    content.push_str("Time spent running plugin code since startup.\n");
+    content
+        .push_str("Run \"Plugin Profiler\" again to refresh. Press 'q' to close this buffer.\n\n");
+
+    if entries.is_empty() {
+        content.push_str("No plugin code has run yet.\n");
+        return content;
+    }
+
+    let mut by_plugin: BTreeMap<&str, Vec<&PluginProfileEntry>> = BTreeMap::new();
+    for entry in entries {
+        by_plugin.entry(&entry.plugin).or_default().push(entry);
+    }
+    let mut plugins: Vec<(&str, Duration, Vec<&PluginProfileEntry>)> = by_plugin
+        .into_iter()
+        .map(|(plugin, rows)| (plugin, rows.iter().map(|e| e.total).sum(), rows))
+        .collect();
+    plugins.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+
+    for (plugin, total, mut rows) in plugins {
+        rows.sort_by_key(|row| std::cmp::Reverse(row.total));
+        content.push_str(&format!("── {} ── {} total\n\n", plugin, format_ms(total)));
+        content.push_str(&format!(
+            "  {:8} {:32} {:>8} {:>12} {:>12}\n",
+            "kind", "name", "calls", "total", "max"
+        ));
+        for row in rows {
+            let name = match row.kind {
+                RunKind::Load => "(plugin load)",
+                RunKind::Async => "(async continuations)",
+                RunKind::Hook | RunKind::Command => row.name.as_str(),
+            };
+            content.push_str(&format!(
+                "  {:8} {:32} {:>8} {:>12} {:>12}\n",
+                row.kind.to_string(),
+                name,
+                row.calls,
+                format_ms(row.total),
+                format_ms(row.max)
+            ));
+        }
+        content.push('\n');
+    }
+    content
+}
+
+fn format_ms(duration: Duration) -> String {
+    format!("{:.1} ms", duration.as_secs_f64() * 1000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(plugin: &str, kind: RunKind, name: &str, total_ms: u64) -> PluginProfileEntry {
+        PluginProfileEntry {
+            plugin: plugin.to_string(),
+            kind,
+            name: name.to_string(),
+            calls: 2,
+            total: Duration::from_millis(total_ms),
+            max: Duration::from_millis(total_ms / 2),
+        }
+    }
+
+    #[test]
+    fn plugins_are_listed_slowest_first_with_their_breakdown() {
+        let text = format_plugin_profile(&[
+            entry("fast", RunKind::Hook, "cursor_moved", 3),
+            entry("slow", RunKind::Command, "build_index", 40),
+            entry("slow", RunKind::Load, "", 5),
+        ]);
+
+        let slow = text.find("── slow ── 45.0 ms total").unwrap();
+        let fast = text.find("── fast ── 3.0 ms total").unwrap();
+        assert!(slow < fast, "{text}");
+        assert!(text.contains("build_index"));
+        assert!(text.contains("(plugin load)"));
+        assert!(text.find("build_index").unwrap() < text.find("(plugin load)").unwrap());
+    }
+
+    #[test]
+    fn empty_profile_says_so() {
+        assert!(format_plugin_profile(&[]).contains("No plugin code has run yet."));
+    }
+}
@@ -5,7 +5,7 @@
 use super::Editor;
 use crate::app::window::LspCompletionCandidate;
 use crate::model::event::Event;
-use crate::primitives::snippet::{expand_snippet, is_snippet};
+use crate::primitives::snippet::{is_snippet, parse_snippet};
 use crate::primitives::word_navigation::find_completion_word_start;
 use rust_i18n::t;
 
@@ -168,6 +168,34 @@ impl Editor {
                 PopupConfirmResult::EarlyReturn
             }
 
+            Some(PopupResolver::SnippetChoice) => {
+                let choice = self
+                    .active_state()
+                    .popups
+                    .top()
+                    .and_then(|p| p.selected_item())
+                    .and_then(|item| item.data.clone());
+                self.hide_popup();
+                if let Some(choice) = choice {
+                    self.apply_snippet_choice(&choice);
+                }
+                PopupConfirmResult::EarlyReturn
+            }
+
+            Some(PopupResolver::ClipboardHistory) => {
+                let index = self
+                    .active_state()
+                    .popups
+                    .top()
+                    .and_then(|p| p.selected_item())
+                    .and_then(|item| item.data.as_deref()?.parse::<usize>().ok());
+                self.hide_popup();
+                if let Some(index) = index {
+                    self.paste_history_entry(index);
+                }
+                PopupConfirmResult::EarlyReturn
+            }
+
             Some(PopupResolver::Completion) => {
                 // Grab the selected *row* — its insert-text and its index —
                 // before we mutate the popup stack: insert_completion_text
@@ -182,13 +210,15 @@ impl Editor {
                     p.selected_index()
                         .zip(p.selected_item().map(|item| item.data.clone()))
                 });
+                // Closed first: a snippet's choice placeholder opens a
+                // popup of its own on insertion.
+                self.hide_popup();
                 if let Some((row, insert_text)) = selection {
                     if let Some(text) = insert_text {
                         self.insert_completion_text(text);
                     }
                     self.apply_completion_additional_edits(row);
                 }
-                self.hide_popup();
                 PopupConfirmResult::Done
             }
 
@@ -210,7 +240,8 @@ impl Editor {
     }
 
     /// Insert completion text, replacing the word prefix at *every* cursor.
-    /// If the text contains LSP snippet syntax, it will be expanded.
+    /// If the text contains LSP snippet syntax, it will be expanded; with a
+    /// single cursor, a snippet with placeholders starts a snippet session.
     ///
     /// Multi-cursor: each cursor's own word prefix is replaced, so cursors
     /// stay in lock-step after the accept (issue #1901, accept path). All
@@ -220,11 +251,10 @@ impl Editor {
         use crate::model::event::CursorId;
 
         // Check if this is a snippet and expand it
-        let (insert_text, cursor_offset) = if is_snippet(&text) {
-            let expanded = expand_snippet(&text);
-            (expanded.text, Some(expanded.cursor_offset))
-        } else {
-            (text, None)
+        let snippet = is_snippet(&text).then(|| parse_snippet(&text));
+        let (insert_text, cursor_offset) = match &snippet {
+            Some(snippet) => (snippet.text.clone(), Some(snippet.final_offset())),
+            None => (text, None),
         };
 
         // Collect per-cursor data: id, current position, word_start, prefix text.
@@ -285,6 +315,14 @@ impl Editor {
             }
         }
 
+        // A single snippet with placeholders to fill in: step through them.
+        if let [(_, _, word_start, _)] = cursor_data.as_slice() {
+            if let Some(snippet) = snippet.filter(|s| s.has_placeholders()) {
+                self.start_snippet_session(*word_start, &snippet);
+                return;
+            }
+        }
+
         // Snippet placement: after the bulk edit, each cursor sits at the end
         // of its own inserted text; the snippet's $0 sits `cursor_offset` bytes
         // into that text. Walk each cursor back to its $0 placeholder.
@@ -421,6 +459,15 @@ impl Editor {
                 self.hide_popup();
             }
 
+            // Keeps the option already in place.
+            Some(PopupResolver::SnippetChoice) => {
+                self.hide_popup();
+            }
+
+            Some(PopupResolver::ClipboardHistory) => {
+                self.hide_popup();
+            }
+
             Some(PopupResolver::WorkspaceTrust) => {
                 // The trust prompt is a forced choice: there is no "undecided"
                 // outcome, so Escape does nothing. The user must pick Trust /
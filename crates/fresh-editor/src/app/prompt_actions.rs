@@ -166,20 +166,15 @@ impl Editor {
                 }
             }
             PromptType::GotoByteOffset => {
-                // Parse byte offset — strip optional trailing 'B' or 'b' suffix
-                let trimmed = input.trim();
-                let num_str = trimmed
-                    .strip_suffix('B')
-                    .or_else(|| trimmed.strip_suffix('b'))
-                    .unwrap_or(trimmed);
-                match num_str.parse::<usize>() {
-                    Ok(offset) => {
+                // Decimal with an optional 'B' suffix, or hex with '0x'
+                match crate::view::hex_view::parse_offset(&input) {
+                    Some(offset) => {
                         self.goto_byte_offset(offset);
                         self.set_status_message(
                             t!("goto.jumped_byte", offset = offset).to_string(),
                         );
                     }
-                    Err(_) => {
+                    None => {
                         self.set_status_message(
                             t!("goto.invalid_byte_offset", input = &input).to_string(),
                         );
@@ -642,6 +637,33 @@ impl Editor {
                     self.switch_to_tab(BufferId(id));
                 }
             }
+            PromptType::SelectDebugConfiguration => {
+                if let Ok(index) = input.trim().parse::<usize>() {
+                    self.start_debug_configuration(index);
+                }
+            }
+            PromptType::SelectTask => {
+                let label = input.trim();
+                if !label.is_empty() {
+                    self.run_task_by_label(label);
+                }
+            }
+            PromptType::SelectDocumentLink => {
+                let target = input.trim();
+                if !target.is_empty() {
+                    self.open_document_link(target);
+                }
+            }
+            PromptType::ConfirmPluginCapability { plugin, capability } => {
+                #[cfg(feature = "plugins")]
+                self.answer_plugin_capability(plugin, capability, &input);
+                #[cfg(not(feature = "plugins"))]
+                let _ = (plugin, capability);
+            }
+            PromptType::RevokePluginPermission => {
+                #[cfg(feature = "plugins")]
+                self.revoke_plugin_permission(input.trim());
+            }
             PromptType::QueryReplaceConfirm => {
                 // This is handled by InsertChar, not PromptConfirm
                 // But if somehow Enter is pressed, treat it as skip (n)
@@ -757,12 +779,30 @@ impl Editor {
             before_len
         );
 
-        match self.active_state_mut().buffer.save_to_file(&full_path) {
+        // The new path may fall under different `.editorconfig` sections
+        // (or a different project entirely), so re-resolve before saving.
+        let editorconfig = crate::services::editorconfig::resolve_for_file(
+            self.authority().filesystem.as_ref(),
+            &full_path,
+        );
+        let buffer_id = self.active_buffer();
+        if let Some(state) = self
+            .windows
+            .get_mut(&self.active_window)
+            .map(|w| &mut w.buffers)
+            .expect("active window present")
+            .get_mut(&buffer_id)
+        {
+            state.buffer_settings.editorconfig = editorconfig;
+            state.apply_buffer_config(&self.config);
+        }
+
+        match self.active_state_mut().save_to_file(&full_path) {
             Ok(()) => {
                 let after_save_idx = self.active_event_log().current_index();
                 let after_save_len = self.active_event_log().len();
                 tracing::debug!(
-                    "SaveFileAs AFTER buffer.save_to_file: event_log index={}, len={}",
+                    "SaveFileAs AFTER save_to_file: event_log index={}, len={}",
                     after_save_idx,
                     after_save_len
                 );
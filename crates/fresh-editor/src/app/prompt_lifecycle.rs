@@ -157,6 +157,12 @@ impl Editor {
         self.active_window_mut().status_message = None;
         self.active_window_mut().goto_line_preview = None;
 
+        // Search workspace symbols afresh rather than showing the last
+        // session's (possibly outdated) results.
+        if let Some(provider) = self.quick_open_registry.workspace_symbols() {
+            provider.reset();
+        }
+
         let mut prompt = Prompt::with_suggestions(String::new(), PromptType::QuickOpen, vec![]);
         prompt.input = prefix.to_string();
         prompt.cursor_pos = prefix.len();
@@ -246,15 +252,34 @@ impl Editor {
 
     /// Update Quick Open suggestions based on current input, dispatching through the registry
     pub(super) fn update_quick_open_suggestions(&mut self, input: &str) {
+        // Workspace symbols come from language servers: search again when
+        // the query changes. Replies refresh the list as they arrive.
+        let symbol_query = self
+            .quick_open_registry
+            .get_provider_for_input(input)
+            .and_then(|(provider, query)| {
+                let symbols = provider
+                    .as_any()
+                    .downcast_ref::<crate::input::quick_open::WorkspaceSymbolProvider>()?;
+                symbols.needs_search(query).then(|| query.to_string())
+            });
+        if let Some(query) = symbol_query {
+            self.request_workspace_symbols(&query);
+        }
+
         let context = self.build_quick_open_context();
-        let suggestions = if let Some((provider, query)) =
+        let (suggestions, previews_location) = if let Some((provider, query)) =
             self.quick_open_registry.get_provider_for_input(input)
         {
-            provider.suggestions(query, &context)
+            (
+                provider.suggestions(query, &context),
+                provider.previews_location(),
+            )
         } else {
-            vec![]
+            (vec![], false)
         };
 
+        let mut close_preview = false;
         if let Some(prompt) = &mut self.active_window_mut().prompt {
             prompt.suggestions = suggestions;
             prompt.selected_suggestion = if prompt.suggestions.is_empty() {
@@ -262,6 +287,13 @@ impl Editor {
             } else {
                 Some(0)
             };
+            // Location providers get the floating overlay with a preview
+            // pane; switching to another prefix returns to the bottom prompt.
+            close_preview = prompt.overlay && !previews_location;
+            prompt.overlay = previews_location;
+        }
+        if close_preview {
+            self.cleanup_overlay_preview();
         }
 
         // Live preview for the goto-line provider: if the input is ":<N>" for a
@@ -868,6 +900,10 @@ impl Editor {
                             });
                     }
                 }
+                #[cfg(feature = "plugins")]
+                PromptType::ConfirmPluginCapability { plugin, capability } => {
+                    self.cancel_plugin_capability(plugin.clone(), *capability);
+                }
                 PromptType::LspRename { overlay_handle, .. } => {
                     // Remove the rename overlay when cancelling
                     let remove_overlay_event = crate::model::event::Event::RemoveOverlay {
@@ -1054,6 +1090,10 @@ impl Editor {
                     | PromptType::SelectTheme { .. }
                     | PromptType::SelectLocale
                     | PromptType::SwitchToTab
+                    | PromptType::SelectDebugConfiguration
+                    | PromptType::SelectTask
+                    | PromptType::SelectDocumentLink
+                    | PromptType::RevokePluginPermission
                     | PromptType::SetLanguage
                     | PromptType::SetEncoding
                     | PromptType::SetLineEnding
@@ -1356,6 +1396,10 @@ impl Editor {
                 }
             }
             PromptType::SwitchToTab
+            | PromptType::SelectDebugConfiguration
+            | PromptType::SelectTask
+            | PromptType::SelectDocumentLink
+            | PromptType::RevokePluginPermission
             | PromptType::SelectTheme { .. }
             | PromptType::StopLspServer
             | PromptType::RestartLspServer
@@ -28,11 +28,16 @@ impl Editor {
 
     /// Start the recovery session (call on editor startup after recovery check)
     pub fn start_recovery_session(&mut self) -> AnyhowResult<()> {
+        self.active_window().prune_undo_history();
         Ok(self.recovery_service.lock().unwrap().start_session()?)
     }
 
     /// End the recovery session cleanly (call on normal shutdown)
     pub fn end_recovery_session(&mut self) -> AnyhowResult<()> {
+        for window in self.windows.values_mut() {
+            window.persist_all_undo_history();
+        }
+
         let hot_exit = self.config.editor.hot_exit;
 
         if hot_exit {
@@ -118,6 +118,11 @@ impl Editor {
             self.request_semantic_ranges_for_visible_splits();
         }
 
+        self.update_linked_editing();
+        self.update_snippet_session();
+        self.update_spell_check();
+        self.update_document_links();
+
         {
             let _s = tracing::info_span!("prepare_visible_buffers").entered();
             self.prepare_visible_buffers_for_render();
@@ -503,6 +508,8 @@ impl Editor {
             view_line_mappings,
             horizontal_scrollbar_areas,
             grouped_separator_areas,
+            minimap_areas,
+            sticky_scroll_areas,
         ) = __win
             .buffers
             .with_all_mut(|__buffers_mut, __mgr, __vs_map| {
@@ -724,6 +731,8 @@ impl Editor {
 
         self.active_layout_mut().split_areas = split_areas;
         self.active_layout_mut().horizontal_scrollbar_areas = horizontal_scrollbar_areas;
+        self.active_layout_mut().minimap_areas = minimap_areas;
+        self.active_layout_mut().sticky_scroll_areas = sticky_scroll_areas;
         self.active_layout_mut().tab_layouts = tab_layouts;
         self.active_layout_mut().close_split_areas = close_split_areas;
         self.active_layout_mut().maximize_split_areas = maximize_split_areas;
@@ -2210,7 +2219,8 @@ impl Editor {
     }
 
     /// Compute the visible byte range for each split and issue debounced LSP
-    /// requests for semantic tokens and folding ranges.
+    /// requests for semantic tokens, folding ranges and code lenses, resolving
+    /// the code lenses that are on screen.
     fn request_semantic_ranges_for_visible_splits(&mut self) {
         let mut semantic_ranges: std::collections::HashMap<BufferId, (usize, usize)> =
             std::collections::HashMap::new();
@@ -2258,6 +2268,10 @@ impl Editor {
             self.maybe_request_semantic_tokens_range(buffer_id, start_line, end_line);
             self.maybe_request_semantic_tokens_full_debounced(buffer_id);
             self.maybe_request_folding_ranges_debounced(buffer_id);
+            self.maybe_request_code_lenses_debounced(buffer_id);
+            self.maybe_request_document_symbols(buffer_id);
+            self.maybe_request_document_links(buffer_id);
+            self.resolve_visible_code_lenses(buffer_id, start_line, end_line);
         }
     }
 
@@ -2735,13 +2749,16 @@ impl Editor {
         // without the dead embed.
         let preview_draw_tab_bar = !self.suppress_chrome_cells;
         // Same immutable render settings as the live editor, but with
-        // scrollbars and tildes suppressed — they're noisy in a small
-        // preview rect where the active session's chrome is authoritative.
+        // scrollbars, the minimap, the sticky scroll header and tildes
+        // suppressed — they're noisy in a small preview rect where the
+        // active session's chrome is authoritative.
         // Built before the `&mut self.windows` borrow (it only borrows
         // `self.config`).
         let preview_cfg = crate::view::ui::EditorRenderConfig {
             show_vertical_scrollbar: false,
             show_horizontal_scrollbar: false,
+            show_minimap: false,
+            sticky_scroll: false,
             show_tilde: false,
             ..crate::view::ui::EditorRenderConfig::new(
                 &self.config.editor,
@@ -4315,7 +4332,13 @@ impl Editor {
             .get_mut(&active_window_id)
             .expect("active window must exist");
         let tab_bar_visible = __win_l.tab_bar_visible;
+        let buffer_metadata = &__win_l.buffer_metadata;
         let theme = self.theme.read().unwrap().clone();
+        let minimap_width = if self.config.editor.show_minimap {
+            self.config.editor.minimap_width
+        } else {
+            0
+        };
         let view_line_mappings = __win_l
             .buffers
             .with_all_mut(|buffers, mgr, vs_map| {
@@ -4323,6 +4346,7 @@ impl Editor {
                     editor_content_area,
                     &*mgr,
                     buffers,
+                    buffer_metadata,
                     vs_map,
                     &theme,
                     false, // lsp_waiting — not relevant for layout
@@ -4335,6 +4359,7 @@ impl Editor {
                     tab_bar_visible,
                     self.config.editor.show_vertical_scrollbar,
                     self.config.editor.show_horizontal_scrollbar,
+                    minimap_width,
                     self.config.editor.diagnostics_inline_text,
                     self.config.editor.show_tilde,
                     crate::view::bracket_highlight_overlay::BracketHighlightSettings::from_config(
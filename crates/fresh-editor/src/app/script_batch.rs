@@ -0,0 +1,393 @@
+//! Headless batch runs: `fresh --cmd script batch <script.ts> <files...>`.
+//!
+//! Builds an editor with no terminal (the cells render into an in-memory
+//! backend, as the web bridge does), opens each file in turn and runs the
+//! script against it with the full plugin API — the same wrapper and runtime
+//! path `fresh --cmd script run` takes, minus the session and the token. A
+//! script that settles successfully has every modified buffer saved; one that
+//! throws has its edits discarded, so a failed file is never half-written.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use ratatui::backend::TestBackend;
+use ratatui::Terminal;
+
+use super::Editor;
+use crate::config::Config;
+use crate::config_io::DirectoryContext;
+use crate::model::event::BufferId;
+use crate::model::filesystem::StdFileSystem;
+use crate::server::command_access::{self, CommandDispatch};
+
+/// Size of the in-memory screen. Only matters to scripts that ask about the
+/// viewport; wide enough that nothing wraps by accident.
+const SCREEN_SIZE: (u16, u16) = (160, 48);
+
+/// How a file's run ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileStatus {
+    /// The script settled and every modified buffer was written.
+    Ok,
+    /// The file could not be opened, the script threw or timed out, or a
+    /// buffer could not be saved.
+    Failed,
+    /// Not run: an earlier script timed out and may still be editing.
+    Skipped,
+}
+
+/// The outcome of running the script against one file.
+#[derive(Debug, Clone)]
+pub struct FileReport {
+    pub path: PathBuf,
+    pub status: FileStatus,
+    /// Buffers written after the script settled — the file itself and any
+    /// other file the script edited (an LSP rename, say).
+    pub saved: usize,
+    /// What the script returned, JSON-encoded.
+    pub output: Option<String>,
+    pub error: Option<FileError>,
+}
+
+/// Why a file failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileError {
+    /// The path is not an existing file.
+    NoSuchFile,
+    /// The editor could not open it.
+    Open(String),
+    /// The script threw; its message.
+    Script(String),
+    /// The script did not settle in time.
+    TimedOut,
+    /// An edited buffer could not be written.
+    Save(String),
+}
+
+impl std::fmt::Display for FileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoSuchFile => f.write_str("no such file"),
+            Self::Open(e) => write!(f, "could not open: {e}"),
+            Self::Script(e) | Self::Save(e) => f.write_str(e),
+            Self::TimedOut => f.write_str("script timed out"),
+        }
+    }
+}
+
+impl FileReport {
+    fn failed(path: &Path, error: FileError) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            status: FileStatus::Failed,
+            saved: 0,
+            output: None,
+            error: Some(error),
+        }
+    }
+
+    /// One report line: status, path, then what was saved or what went wrong.
+    pub fn line(&self) -> String {
+        let path = self.path.display();
+        match self.status {
+            FileStatus::Ok => {
+                let saved = match self.saved {
+                    0 => "unchanged".to_string(),
+                    1 => "saved".to_string(),
+                    n => format!("saved {} files", n),
+                };
+                match &self.output {
+                    Some(output) => format!("ok      {path}  ({saved})  {output}"),
+                    None => format!("ok      {path}  ({saved})"),
+                }
+            }
+            FileStatus::Failed => match &self.error {
+                Some(error) => format!("FAILED  {path}  {error}"),
+                None => format!("FAILED  {path}  script failed"),
+            },
+            FileStatus::Skipped => format!("skipped {path}"),
+        }
+    }
+}
+
+/// Every file's outcome, in the order the files were given.
+#[derive(Debug, Clone, Default)]
+pub struct BatchReport {
+    pub files: Vec<FileReport>,
+}
+
+impl BatchReport {
+    /// Whether every file ran and saved cleanly.
+    pub fn success(&self) -> bool {
+        self.files.iter().all(|f| f.status == FileStatus::Ok)
+    }
+
+    /// The closing line: how many files passed, failed and were skipped.
+    pub fn summary(&self) -> String {
+        let count = |status| self.files.iter().filter(|f| f.status == status).count();
+        let mut summary = format!(
+            "{} files: {} ok, {} failed",
+            self.files.len(),
+            count(FileStatus::Ok),
+            count(FileStatus::Failed)
+        );
+        let skipped = count(FileStatus::Skipped);
+        if skipped > 0 {
+            summary.push_str(&format!(", {} skipped", skipped));
+        }
+        summary
+    }
+}
+
+/// Build a headless editor for the current directory and run `script` over
+/// `files`, giving each file's script at most `timeout` to settle.
+/// `on_file` sees each file's report as soon as that file is done.
+///
+/// The editor boots like the terminal one: the user's config layers, the
+/// plugins, and `init.ts`, so a script sees the same commands and language
+/// servers it would interactively.
+pub fn run(
+    script: &str,
+    files: &[PathBuf],
+    timeout: Duration,
+    on_file: impl FnMut(&FileReport),
+) -> anyhow::Result<BatchReport> {
+    let dir_context = DirectoryContext::from_system()?;
+    let working_dir = std::env::current_dir()?;
+    let mut config = Config::load_with_layers(&dir_context, &working_dir);
+    // Nobody watches the frames.
+    config.editor.animations = false;
+    let mut editor = Editor::with_working_dir(
+        config,
+        SCREEN_SIZE.0,
+        SCREEN_SIZE.1,
+        Some(working_dir),
+        dir_context,
+        true,
+        crate::view::color_support::ColorCapability::TrueColor,
+        std::sync::Arc::new(StdFileSystem),
+    )?;
+    editor.load_init_script_async(true);
+    editor.fire_plugins_loaded_hook();
+    Ok(run_with(&mut editor, script, files, timeout, on_file))
+}
+
+/// Run `script` over `files` in an already-built editor.
+pub fn run_with(
+    editor: &mut Editor,
+    script: &str,
+    files: &[PathBuf],
+    timeout: Duration,
+    mut on_file: impl FnMut(&FileReport),
+) -> BatchReport {
+    let mut terminal =
+        Terminal::new(TestBackend::new(SCREEN_SIZE.0, SCREEN_SIZE.1)).expect("in-memory terminal");
+    let mut report = BatchReport::default();
+    let mut timed_out = false;
+    for path in files {
+        let file = if timed_out {
+            FileReport {
+                path: path.clone(),
+                status: FileStatus::Skipped,
+                saved: 0,
+                output: None,
+                error: None,
+            }
+        } else {
+            editor.run_batch_file(&mut terminal, script, path, timeout)
+        };
+        // The runtime cannot cancel a script, so one that timed out keeps
+        // running and could edit whatever file comes next.
+        timed_out |= file.error == Some(FileError::TimedOut);
+        on_file(&file);
+        report.files.push(file);
+    }
+    report
+}
+
+/// The script as submitted for one file: the batch globals, then the body.
+/// The path is JSON-encoded so no file name can break out of the literal.
+fn batch_source(script: &str, path: &Path, buffer_id: BufferId) -> String {
+    let encoded =
+        serde_json::to_string(&path.to_string_lossy()).unwrap_or_else(|_| "\"\"".to_string());
+    format!(
+        "const FRESH_BATCH_FILE = {encoded};\nconst FRESH_BATCH_BUFFER_ID = {};\n{script}",
+        buffer_id.0
+    )
+}
+
+impl Editor {
+    /// Open `path`, run `script` against it and save or discard the result.
+    fn run_batch_file(
+        &mut self,
+        terminal: &mut Terminal<TestBackend>,
+        script: &str,
+        path: &Path,
+        timeout: Duration,
+    ) -> FileReport {
+        // Opening a missing path would make a new buffer, and saving it would
+        // create the file: a typo in the file list must fail instead.
+        if !path.is_file() {
+            return FileReport::failed(path, FileError::NoSuchFile);
+        }
+        let buffer_id = match self.open_file(path) {
+            Ok(id) => id,
+            Err(e) => return FileReport::failed(path, FileError::Open(e.to_string())),
+        };
+        self.render_batch_frame(terminal);
+
+        let source = batch_source(script, path, buffer_id);
+        let window_id = self.active_window_id().0;
+        let script_error = |error: Option<String>| {
+            FileError::Script(error.unwrap_or_else(|| "script failed".to_string()))
+        };
+        let (ok, output, error) = match command_access::start_script(self, &source, window_id) {
+            CommandDispatch::Settled { ok, error, output } => (ok, output, script_error(error)),
+            CommandDispatch::Pending { request_id } => {
+                match self.wait_for_script(terminal, request_id, timeout) {
+                    Some(outcome) => (outcome.ok, outcome.output, script_error(outcome.error)),
+                    None => (false, None, FileError::TimedOut),
+                }
+            }
+        };
+
+        let file = if !ok {
+            self.discard_modified_buffers();
+            FileReport {
+                output,
+                ..FileReport::failed(path, error)
+            }
+        } else {
+            match self.save_all() {
+                Ok((saved, 0)) => FileReport {
+                    path: path.to_path_buf(),
+                    status: FileStatus::Ok,
+                    saved,
+                    output,
+                    error: None,
+                },
+                Ok((_, failed)) => {
+                    self.discard_modified_buffers();
+                    FileReport::failed(
+                        path,
+                        FileError::Save(format!(
+                            "could not save every edited file ({failed} failed)"
+                        )),
+                    )
+                }
+                Err(e) => {
+                    self.discard_modified_buffers();
+                    FileReport::failed(path, FileError::Save(format!("save failed: {e}")))
+                }
+            }
+        };
+
+        // Keep memory flat over a long file list. The script may have closed
+        // the buffer itself already.
+        if let Err(e) = self.force_close_buffer(buffer_id) {
+            tracing::debug!("batch: buffer for {} already closed: {}", path.display(), e);
+        }
+        file
+    }
+
+    /// Drive the editor loop until the script `request_id` settles, or
+    /// `timeout` passes.
+    fn wait_for_script(
+        &mut self,
+        terminal: &mut Terminal<TestBackend>,
+        request_id: u64,
+        timeout: Duration,
+    ) -> Option<command_access::CommandOutcome> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            match super::editor_tick(self, || Ok(())) {
+                Ok(true) => self.render_batch_frame(terminal),
+                Ok(false) => {}
+                Err(e) => tracing::warn!("batch: editor tick failed: {}", e),
+            }
+            if let Some(outcome) = command_access::take_completed_where(|id| id == request_id).pop()
+            {
+                return Some(outcome);
+            }
+            if Instant::now() >= deadline {
+                return None;
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    /// Render into the in-memory screen so layout-dependent API calls (the
+    /// viewport, visible lines) see what a real frame would have laid out.
+    fn render_batch_frame(&mut self, terminal: &mut Terminal<TestBackend>) {
+        if let Err(e) = terminal.draw(|frame| self.render(frame)) {
+            tracing::warn!("batch: render failed: {}", e);
+        }
+    }
+
+    /// Close every modified file-backed buffer without saving: the edits of a
+    /// script that failed must not be written by the next file's save.
+    fn discard_modified_buffers(&mut self) {
+        let modified: Vec<BufferId> = self
+            .active_window()
+            .buffers
+            .iter()
+            .filter(|(_, state)| state.buffer.is_modified() && state.buffer.file_path().is_some())
+            .map(|(id, _)| *id)
+            .collect();
+        for id in modified {
+            if let Err(e) = self.force_close_buffer(id) {
+                tracing::warn!("batch: could not discard buffer {:?}: {}", id, e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(status: FileStatus, saved: usize, error: Option<FileError>) -> FileReport {
+        FileReport {
+            path: PathBuf::from("src/lib.rs"),
+            status,
+            saved,
+            output: None,
+            error,
+        }
+    }
+
+    #[test]
+    fn test_batch_source_defines_file_globals() {
+        let source = batch_source("return 1;", Path::new("a \"b\".rs"), BufferId(4));
+        assert!(source.contains(r#"const FRESH_BATCH_FILE = "a \"b\".rs";"#));
+        assert!(source.contains("const FRESH_BATCH_BUFFER_ID = 4;"));
+        assert!(source.ends_with("return 1;"));
+    }
+
+    #[test]
+    fn test_report_lines_and_summary() {
+        let batch = BatchReport {
+            files: vec![
+                report(FileStatus::Ok, 1, None),
+                report(FileStatus::Ok, 0, None),
+                report(
+                    FileStatus::Failed,
+                    0,
+                    Some(FileError::Script("boom".into())),
+                ),
+                report(FileStatus::Failed, 0, Some(FileError::TimedOut)),
+                report(FileStatus::Skipped, 0, None),
+            ],
+        };
+        assert_eq!(batch.files[0].line(), "ok      src/lib.rs  (saved)");
+        assert_eq!(batch.files[1].line(), "ok      src/lib.rs  (unchanged)");
+        assert_eq!(batch.files[2].line(), "FAILED  src/lib.rs  boom");
+        assert_eq!(
+            batch.files[3].line(),
+            "FAILED  src/lib.rs  script timed out"
+        );
+        assert_eq!(batch.files[4].line(), "skipped src/lib.rs");
+        assert_eq!(batch.summary(), "5 files: 2 ok, 2 failed, 1 skipped");
+        assert!(!batch.success());
+    }
+}
@@ -188,6 +188,22 @@ impl crate::app::window::Window {
             return Ok(());
         }
 
+        // Hex view scrolls by rows of bytes, independently of the text viewport.
+        let buffer_len = self
+            .buffers
+            .get(&buffer_id)
+            .map(|s| s.buffer.len())
+            .unwrap_or(0);
+        if let Some(hex_view) = self
+            .split_view_states_mut()
+            .and_then(|vs| vs.get_mut(&target_split))
+            .and_then(|vs| vs.keyed_states.get_mut(&buffer_id))
+            .and_then(|bvs| bvs.hex_view.as_mut())
+        {
+            hex_view.scroll(delta as isize, crate::view::hex_view::row_count(buffer_len));
+            return Ok(());
+        }
+
         self.scroll_split_by_lines(buffer_id, target_split, delta);
 
         Ok(())
@@ -676,6 +692,63 @@ impl crate::app::window::Window {
         Ok(())
     }
 
+    /// Scroll so the line holding `target_byte` sits in the middle of the
+    /// split — a click or drag on the minimap row showing that line.
+    ///
+    /// Only walks the lines between the target and the new top of the
+    /// viewport (and from the end of the buffer, to clamp), so it stays cheap
+    /// on large files.
+    pub(super) fn handle_minimap_jump(
+        &mut self,
+        target_byte: usize,
+        split_id: LeafId,
+        buffer_id: BufferId,
+    ) -> AnyhowResult<()> {
+        let viewport_height = self
+            .buffers
+            .splits()
+            .map(|(_, vs)| vs)
+            .expect("active window must have a populated split layout")
+            .get(&split_id)
+            .map(|vs| vs.viewport.height as usize)
+            .unwrap_or(10);
+
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return Ok(());
+        };
+        let buffer_len = state.buffer.len();
+        let mut iter = state.buffer.line_iterator(target_byte.min(buffer_len), 80);
+        for _ in 0..viewport_height / 2 {
+            if iter.prev().is_none() {
+                break;
+            }
+        }
+        let top_byte = iter.current_position();
+        // The last full page starts `viewport_height - 1` lines above the end.
+        let mut iter = state.buffer.line_iterator(buffer_len, 80);
+        for _ in 1..viewport_height {
+            if iter.prev().is_none() {
+                break;
+            }
+        }
+        let top_byte = top_byte.min(iter.current_position());
+
+        if let Some(view_state) = self
+            .split_view_states_mut()
+            .expect("active window must have a populated split layout")
+            .get_mut(&split_id)
+        {
+            view_state.viewport.set_top_byte(top_byte);
+            view_state.viewport.set_top_view_line_offset(0);
+            // Skip ensure_visible so the scroll position isn't undone during render
+            view_state.viewport.set_skip_ensure_visible();
+        }
+
+        self.move_cursor_to_visible_area(split_id, buffer_id);
+
+        Ok(())
+    }
+
     /// Handle scrollbar jump (click on track) for composite buffers.
     /// Maps the click ratio to a row-based scroll position.
     fn handle_composite_scrollbar_jump(
@@ -0,0 +1,159 @@
+//! LSP selection ranges (`textDocument/selectionRange`) for Expand
+//! Selection.
+//!
+//! When the buffer's server offers selection ranges, expanding first asks it
+//! for the chain of ranges enclosing each cursor that has none cached for
+//! the current buffer version, then expands once the answer arrives. A
+//! failed or empty answer is cached too, so those cursors fall back to the
+//! syntax tree or brackets without asking again (see
+//! `input::selection_range`).
+
+use super::Editor;
+use crate::input::keybindings::Action;
+use crate::input::selection_range::lsp_selection_chain;
+use crate::model::event::BufferId;
+use crate::types::LspFeature;
+
+#[derive(Clone, Debug)]
+pub(crate) struct SelectionRangeRequest {
+    pub(crate) request_id: u64,
+    pub(crate) buffer_id: BufferId,
+    /// Buffer version the positions belong to.
+    pub(crate) version: u64,
+    /// Byte offset of each position asked about, in request order.
+    pub(crate) positions: Vec<usize>,
+}
+
+impl Editor {
+    /// `Action::ExpandSelection`: expand right away when every cursor's
+    /// ranges are known, otherwise ask the server first.
+    pub(super) fn expand_selection(&mut self) -> anyhow::Result<()> {
+        let buffer_id = self.active_buffer();
+        let version = self.active_state().buffer.version();
+        if let Some(pending) = &self.active_window().pending_selection_range {
+            if pending.buffer_id == buffer_id && pending.version == version {
+                // The expansion runs when the answer arrives.
+                return Ok(());
+            }
+        }
+        if self.request_selection_ranges() {
+            return Ok(());
+        }
+        self.apply_action_as_events(Action::ExpandSelection)
+    }
+
+    /// Ask the server for the selection ranges of the cursors that have
+    /// none cached. Returns whether a request was sent.
+    fn request_selection_ranges(&mut self) -> bool {
+        let buffer_id = self.active_buffer();
+        let state = self.active_state();
+        let version = state.buffer.version();
+        let mut positions: Vec<usize> = self
+            .active_cursors()
+            .iter()
+            .map(|(_, cursor)| (cursor.selection_start(), cursor.selection_end()))
+            .filter(|&(start, end)| state.selection_ranges.chain(version, start, end).is_none())
+            .map(|(start, _)| start)
+            .collect();
+        if positions.is_empty() {
+            return false;
+        }
+        positions.sort_unstable();
+        positions.dedup();
+
+        // Only ask a server that is already up and offers the feature.
+        let language = state.language.clone();
+        if self
+            .lsp()
+            .and_then(|lsp| lsp.handle_for_feature(&language, LspFeature::SelectionRange))
+            .is_none()
+        {
+            return false;
+        }
+        let lsp_positions: Vec<(u32, u32)> = positions
+            .iter()
+            .map(|&position| {
+                let (line, character) = state.buffer.position_to_lsp_position(position);
+                (line as u32, character as u32)
+            })
+            .collect();
+
+        let request_id = self.active_window().next_lsp_request_id;
+        let sent = self
+            .with_lsp_for_buffer(
+                buffer_id,
+                LspFeature::SelectionRange,
+                |handle, uri, _language| match handle.selection_range(
+                    request_id,
+                    uri.as_uri().clone(),
+                    lsp_positions,
+                ) {
+                    Ok(()) => true,
+                    Err(e) => {
+                        tracing::debug!("Failed to request selection ranges: {}", e);
+                        false
+                    }
+                },
+            )
+            .unwrap_or(false);
+
+        if sent {
+            let win = self.active_window_mut();
+            win.next_lsp_request_id += 1;
+            win.pending_selection_range = Some(SelectionRangeRequest {
+                request_id,
+                buffer_id,
+                version,
+                positions,
+            });
+        }
+        sent
+    }
+
+    /// Cache a `textDocument/selectionRange` response and run the expansion
+    /// it was requested for. Dropped when the buffer changed since.
+    pub(super) fn handle_lsp_selection_ranges(
+        &mut self,
+        request_id: u64,
+        ranges: Option<Vec<lsp_types::SelectionRange>>,
+    ) {
+        let win = self.active_window_mut();
+        if win
+            .pending_selection_range
+            .as_ref()
+            .is_none_or(|p| p.request_id != request_id)
+        {
+            return;
+        }
+        let Some(request) = win.pending_selection_range.take() else {
+            return;
+        };
+        let Some(state) = win.buffers.get_mut(&request.buffer_id) else {
+            return;
+        };
+        if state.buffer.version() != request.version {
+            return;
+        }
+
+        let ranges = ranges.unwrap_or_default();
+        let chains = request
+            .positions
+            .iter()
+            .enumerate()
+            .map(|(i, &position)| {
+                let chain = ranges
+                    .get(i)
+                    .map(|range| lsp_selection_chain(&state.buffer, range))
+                    .unwrap_or_default();
+                (position, chain)
+            })
+            .collect();
+        state.selection_ranges.insert(request.version, chains);
+
+        if self.active_buffer() == request.buffer_id {
+            if let Err(e) = self.apply_action_as_events(Action::ExpandSelection) {
+                tracing::warn!("Failed to expand selection: {}", e);
+            }
+        }
+    }
+}
@@ -185,6 +185,12 @@ impl Editor {
         }
 
         // Propagate editor config to all split and buffer view states
+        let window = self.active_window();
+        let rulers: std::collections::HashMap<_, _> = window
+            .buffers
+            .iter()
+            .map(|(&id, _)| (id, window.resolve_rulers_for_buffer(id)))
+            .collect();
         for view_state in self
             .windows
             .get_mut(&self.active_window)
@@ -193,8 +199,11 @@ impl Editor {
             .values_mut()
         {
             view_state.show_line_numbers = self.config.editor.line_numbers;
-            for buf_state in view_state.keyed_states.values_mut() {
-                buf_state.rulers = self.config.editor.rulers.clone();
+            for (id, buf_state) in view_state.keyed_states.iter_mut() {
+                buf_state.rulers = rulers
+                    .get(id)
+                    .cloned()
+                    .unwrap_or_else(|| self.config.editor.rulers.clone());
             }
         }
 
@@ -0,0 +1,308 @@
+//! Snippet sessions: stepping through the tabstops of an inserted snippet.
+//!
+//! Accepting a completion whose text is a snippet with placeholders starts a
+//! session. Each tabstop occurrence is tracked by a pair of markers so it
+//! follows edits, and Tab / Shift+Tab select the next / previous tabstop —
+//! every occurrence of it at once, one cursor each, so mirrored placeholders
+//! are edited in sync. Entering a choice placeholder offers its options in a
+//! popup. The session ends at the final tabstop (`$0`), on Escape, or once
+//! the cursor leaves the snippet or the split shows something else.
+
+use super::Editor;
+use crate::model::cursor::Cursor;
+use crate::model::event::{BufferId, Event, LeafId};
+use crate::model::marker::MarkerId;
+use crate::primitives::snippet::ParsedSnippet;
+use crate::view::popup::{Popup, PopupListItem, PopupPosition, PopupResolver};
+use ratatui::style::Style;
+use rust_i18n::t;
+use std::ops::Range;
+
+#[derive(Debug)]
+pub(crate) struct SnippetSession {
+    split_id: LeafId,
+    buffer_id: BufferId,
+    /// Tabstops in navigation order, the final one (`$0`) last.
+    tabstops: Vec<SessionTabstop>,
+    /// Index into `tabstops` of the selected tabstop.
+    current: usize,
+    /// Bounds of the whole snippet; the session ends once the cursor leaves
+    /// them. Left-gravity start, right-gravity end, like each tabstop's.
+    range: (MarkerId, MarkerId),
+}
+
+#[derive(Debug)]
+struct SessionTabstop {
+    /// Start and end markers of every occurrence, first occurrence first.
+    ranges: Vec<(MarkerId, MarkerId)>,
+    choices: Vec<String>,
+}
+
+impl Editor {
+    /// Start a session for `snippet`, just inserted at `start` in the active
+    /// buffer, and select its first tabstop. Ends any earlier session.
+    pub(super) fn start_snippet_session(&mut self, start: usize, snippet: &ParsedSnippet) {
+        self.end_snippet_session();
+        let split_id = self.active_window().effective_active_split();
+        let buffer_id = self.active_buffer();
+        let markers = &mut self.active_state_mut().marker_list;
+        let mut create = |range: Range<usize>| {
+            (
+                markers.create_left_gravity(start + range.start),
+                markers.create(start + range.end, false),
+            )
+        };
+        let tabstops = snippet
+            .tabstops
+            .iter()
+            .map(|stop| SessionTabstop {
+                ranges: stop.ranges.iter().cloned().map(&mut create).collect(),
+                choices: stop.choices.clone(),
+            })
+            .collect();
+        let range = create(0..snippet.text.len());
+
+        self.active_window_mut().snippet_session = Some(SnippetSession {
+            split_id,
+            buffer_id,
+            tabstops,
+            current: 0,
+            range,
+        });
+        self.select_snippet_tabstop(0);
+    }
+
+    /// Handle Tab (`forward`) or Shift+Tab while a session is active.
+    /// Returns `false` when there is no session to navigate, leaving the key
+    /// to its usual action.
+    pub(super) fn navigate_snippet_tabstop(&mut self, forward: bool) -> bool {
+        if !self.snippet_session_holds() {
+            self.end_snippet_session();
+            return false;
+        }
+        let Some(current) = self
+            .active_window()
+            .snippet_session
+            .as_ref()
+            .map(|s| s.current)
+        else {
+            return false;
+        };
+        let target = if forward {
+            current + 1
+        } else {
+            current.saturating_sub(1)
+        };
+        self.select_snippet_tabstop(target);
+        true
+    }
+
+    /// Keep the session in step with the cursors: end it once the cursor has
+    /// left the snippet or the split shows another buffer. Called once per
+    /// frame.
+    pub(super) fn update_snippet_session(&mut self) {
+        if self.active_window().snippet_session.is_some() && !self.snippet_session_holds() {
+            self.end_snippet_session();
+        }
+    }
+
+    /// End the session and drop its markers. The cursors stay where they are.
+    pub(super) fn end_snippet_session(&mut self) {
+        let Some(session) = self.active_window_mut().snippet_session.take() else {
+            return;
+        };
+        if let Some(state) = self.active_window_mut().buffers.get_mut(&session.buffer_id) {
+            let markers = session
+                .tabstops
+                .iter()
+                .flat_map(|stop| stop.ranges.iter())
+                .chain(std::iter::once(&session.range));
+            for (start, end) in markers {
+                state.marker_list.delete(*start);
+                state.marker_list.delete(*end);
+            }
+        }
+    }
+
+    /// Replace every occurrence of the selected tabstop with `choice`, picked
+    /// from the choice popup, and select it again.
+    pub(super) fn apply_snippet_choice(&mut self, choice: &str) {
+        if !self.snippet_session_holds() {
+            self.end_snippet_session();
+            return;
+        }
+        let Some(session) = self.active_window().snippet_session.as_ref() else {
+            return;
+        };
+        let current = session.current;
+        let occurrences = session.tabstops[current].ranges.clone();
+        let ranges = self.snippet_ranges(&occurrences);
+        if ranges.is_empty() {
+            return;
+        }
+
+        let cursor_id = self.active_cursors().primary_id();
+        let mut events = Vec::new();
+        for range in &ranges {
+            if !range.is_empty() {
+                let deleted_text = self
+                    .active_state_mut()
+                    .get_text_range(range.start, range.end);
+                events.push(Event::Delete {
+                    range: range.clone(),
+                    deleted_text,
+                    cursor_id,
+                });
+            }
+            events.push(Event::Insert {
+                position: range.start,
+                text: choice.to_string(),
+                cursor_id,
+            });
+        }
+        if let Some(bulk_edit) =
+            self.apply_events_as_bulk_edit(events, "Choose snippet option".to_string())
+        {
+            self.active_event_log_mut().append(bulk_edit);
+        }
+
+        // Re-anchor the occurrences on their new text: a replacement leaves
+        // the markers of a range it covers wherever the edit happened to.
+        let mut shift = 0isize;
+        let mut replaced = Vec::with_capacity(ranges.len());
+        let markers = &mut self.active_state_mut().marker_list;
+        for ((old_start, old_end), range) in occurrences.iter().zip(&ranges) {
+            markers.delete(*old_start);
+            markers.delete(*old_end);
+            let start = range.start.saturating_add_signed(shift);
+            replaced.push((
+                markers.create_left_gravity(start),
+                markers.create(start + choice.len(), false),
+            ));
+            shift += choice.len() as isize - range.len() as isize;
+        }
+        if let Some(session) = self.active_window_mut().snippet_session.as_mut() {
+            session.tabstops[current].ranges = replaced;
+        }
+        self.select_tabstop_ranges(current);
+    }
+
+    /// Select tabstop `index` (clamped to the last one). Reaching the final
+    /// tabstop places the cursor there and ends the session.
+    fn select_snippet_tabstop(&mut self, index: usize) {
+        let Some(session) = self.active_window_mut().snippet_session.as_mut() else {
+            return;
+        };
+        let index = index.min(session.tabstops.len() - 1);
+        session.current = index;
+        let is_final = index + 1 == session.tabstops.len();
+        let choices = session.tabstops[index].choices.clone();
+
+        self.select_tabstop_ranges(index);
+        if is_final {
+            let position = self.active_cursors().primary().selection_range();
+            if let Some(range) = position {
+                *self.active_cursors_mut().primary_mut() = Cursor::new(range.start);
+            }
+            self.end_snippet_session();
+        } else if !choices.is_empty() {
+            self.show_snippet_choice_popup(&choices);
+        }
+    }
+
+    /// Put one cursor on each occurrence of tabstop `index`, selecting its
+    /// text, with the first occurrence primary.
+    fn select_tabstop_ranges(&mut self, index: usize) {
+        let Some(session) = self.active_window().snippet_session.as_ref() else {
+            return;
+        };
+        let occurrences = session.tabstops[index].ranges.clone();
+        let ranges = self.snippet_ranges(&occurrences);
+        let Some(first) = ranges.first() else {
+            return;
+        };
+        let select = |range: &Range<usize>| {
+            if range.is_empty() {
+                Cursor::new(range.start)
+            } else {
+                Cursor::with_selection(range.start, range.end)
+            }
+        };
+
+        let cursors = self.active_cursors_mut();
+        cursors.remove_secondary();
+        let primary = cursors.primary_id();
+        *cursors.primary_mut() = select(first);
+        for range in &ranges[1..] {
+            cursors.add(select(range));
+        }
+        // Adding a cursor makes it primary; keep the view on the first
+        // occurrence.
+        cursors.set_primary(primary);
+    }
+
+    /// Offer the options of a choice placeholder. Confirming one goes
+    /// through `apply_snippet_choice`.
+    fn show_snippet_choice_popup(&mut self, choices: &[String]) {
+        let items: Vec<PopupListItem> = choices
+            .iter()
+            .map(|choice| PopupListItem {
+                text: choice.clone(),
+                detail: None,
+                icon: None,
+                data: Some(choice.clone()),
+                disabled: false,
+            })
+            .collect();
+        let theme = self.theme();
+        let mut popup = Popup::list(items, &theme);
+        popup.title = Some(t!("snippet.choose_option").to_string());
+        popup.position = PopupPosition::BelowCursor;
+        popup.width =
+            (choices.iter().map(|c| c.chars().count()).max().unwrap_or(0) + 4).clamp(20, 60) as u16;
+        popup.max_height = 10;
+        popup.border_style = Style::default().fg(theme.popup_border_fg);
+        popup.background_style = Style::default().bg(theme.popup_bg);
+        popup.resolver = PopupResolver::SnippetChoice;
+        // The options replace the placeholder the cursor is on, so they are
+        // picked from straight away.
+        popup.focused = true;
+        drop(theme);
+        self.active_state_mut().popups.show_or_replace(popup);
+    }
+
+    /// Current byte ranges of tracked occurrences, skipping any whose markers
+    /// are gone.
+    fn snippet_ranges(&self, occurrences: &[(MarkerId, MarkerId)]) -> Vec<Range<usize>> {
+        let markers = &self.active_state().marker_list;
+        occurrences
+            .iter()
+            .filter_map(|(start, end)| {
+                let start = markers.get_position(*start)?;
+                let end = markers.get_position(*end)?;
+                Some(start..end.max(start))
+            })
+            .collect()
+    }
+
+    /// Whether the session still applies: same split and buffer, and the
+    /// primary cursor (selection included) inside the snippet.
+    fn snippet_session_holds(&self) -> bool {
+        let Some(session) = self.active_window().snippet_session.as_ref() else {
+            return false;
+        };
+        if self.active_window().effective_active_split() != session.split_id
+            || self.active_buffer() != session.buffer_id
+        {
+            return false;
+        }
+        let Some(range) = self.snippet_ranges(&[session.range]).pop() else {
+            return false;
+        };
+        let primary = self.active_cursors().primary();
+        let span = primary
+            .selection_range()
+            .unwrap_or(primary.position..primary.position);
+        range.start <= span.start && span.end <= range.end
+    }
+}
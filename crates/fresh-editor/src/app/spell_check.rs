@@ -0,0 +1,372 @@
+//! Spell checking of the visible text.
+//!
+//! Every frame, the visible lines of each split are re-checked if the
+//! buffer or the viewport changed since the last pass: comments and
+//! strings (by highlight category) in code, the whole text in Markdown and
+//! plain-text buffers. Misspelled words get an underline overlay in their
+//! own namespace. With the cursor on one, the code-action popup offers the
+//! dictionary's suggestions and adding the word to the project word list.
+
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::Arc;
+
+use ratatui::style::{Modifier, Style};
+use rust_i18n::t;
+use serde_json::{json, Value};
+
+use super::Editor;
+use crate::model::event::{BufferId, Event};
+use crate::primitives::highlighter::HighlightCategory;
+use crate::services::spell::{self, Dictionary, ProjectWordList};
+use crate::view::overlay::{Overlay, OverlayFace, OverlayNamespace};
+
+/// Source the spelling entries of the code-action popup are tagged with.
+pub(crate) const SPELLING_ACTION_SOURCE: &str = "spelling";
+
+const SPELL_CHECK_NAMESPACE: &str = "spell-check";
+const REPLACE_WORD_COMMAND: &str = "spelling.replaceWord";
+const ADD_WORD_COMMAND: &str = "spelling.addToProject";
+const MAX_SUGGESTIONS: usize = 5;
+
+fn spell_check_namespace() -> OverlayNamespace {
+    OverlayNamespace::from_string(SPELL_CHECK_NAMESPACE.to_string())
+}
+
+/// Languages whose whole text is checked, not only comments and strings.
+fn is_prose_language(language: &str) -> bool {
+    matches!(language, "markdown" | "text")
+}
+
+/// Sort `ranges` and merge the ones that touch, so a word split across
+/// two highlight spans is still seen whole.
+fn merge_ranges(mut ranges: Vec<Range<usize>>) -> Vec<Range<usize>> {
+    ranges.sort_by_key(|r| r.start);
+    let mut merged: Vec<Range<usize>> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct SpellCheckState {
+    /// The project's word list, read on first use.
+    project_words: Option<ProjectWordList>,
+    /// Buffer version and byte range each buffer was last checked at.
+    checked: HashMap<BufferId, (u64, Range<usize>)>,
+}
+
+impl Editor {
+    /// Underline misspellings in the visible part of every split. Called
+    /// once per frame; a buffer is only re-checked when it or its visible
+    /// range changed.
+    pub(super) fn update_spell_check(&mut self) {
+        let Some(dictionary) = self.spell_dictionary() else {
+            self.clear_spell_check();
+            return;
+        };
+        let targets = self.visible_line_ranges();
+        let context_bytes = self.config.editor.highlight_context_bytes;
+        let theme = self.theme.read().unwrap();
+        let face = OverlayFace::Style {
+            style: Style::default()
+                .add_modifier(Modifier::UNDERLINED)
+                .underline_color(theme.diagnostic_info_fg),
+        };
+        let ns = spell_check_namespace();
+
+        let win = self
+            .windows
+            .get_mut(&self.active_window)
+            .expect("active window present");
+        let project_words = win
+            .spell_check
+            .project_words
+            .get_or_insert_with(|| ProjectWordList::load(&win.root));
+
+        for (buffer_id, range) in targets {
+            if win
+                .buffer_metadata
+                .get(&buffer_id)
+                .is_none_or(|m| m.is_virtual() || m.read_only || m.binary)
+            {
+                continue;
+            }
+            let Some(state) = win.buffers.get_mut(&buffer_id) else {
+                continue;
+            };
+            let checked = (state.buffer.version(), range.clone());
+            if win.spell_check.checked.get(&buffer_id) == Some(&checked) {
+                continue;
+            }
+
+            let segments = if is_prose_language(&state.language) {
+                vec![range.clone()]
+            } else {
+                merge_ranges(
+                    state
+                        .highlighter
+                        .highlight_viewport(
+                            &state.buffer,
+                            range.start,
+                            range.end,
+                            &theme,
+                            context_bytes,
+                        )
+                        .into_iter()
+                        .filter(|span| {
+                            matches!(
+                                span.category,
+                                Some(HighlightCategory::Comment | HighlightCategory::String)
+                            )
+                        })
+                        .map(|span| {
+                            span.range.start.max(range.start)..span.range.end.min(range.end)
+                        })
+                        .filter(|r| r.start < r.end)
+                        .collect(),
+                )
+            };
+
+            let mut misspelled = Vec::new();
+            for segment in segments {
+                let text = state.get_text_range(segment.start, segment.end);
+                for word in spell::checkable_words(&text) {
+                    if !spell::is_correct(&dictionary, project_words, &text[word.clone()]) {
+                        misspelled.push(segment.start + word.start..segment.start + word.end);
+                    }
+                }
+            }
+            let overlays = misspelled
+                .into_iter()
+                .map(|r| {
+                    Overlay::with_namespace(&mut state.marker_list, r, face.clone(), ns.clone())
+                })
+                .collect();
+            state.overlays.replace_range_in_namespace(
+                &ns,
+                &range,
+                overlays,
+                &mut state.marker_list,
+            );
+            win.spell_check.checked.insert(buffer_id, checked);
+        }
+    }
+
+    /// The dictionary for the configured language, or `None` when spell
+    /// checking is off or the dictionary files are missing. Read from disk
+    /// on first use and again only when the language setting changes.
+    fn spell_dictionary(&mut self) -> Option<Arc<Dictionary>> {
+        if !self.config.editor.spell_check {
+            return None;
+        }
+        let language = self.config.editor.spell_check_language.clone();
+        if self
+            .spell_dictionary
+            .as_ref()
+            .is_none_or(|(loaded, _)| *loaded != language)
+        {
+            let dictionary =
+                spell::load_dictionary(&self.dir_context.dictionaries_dir(), &language)
+                    .map(Arc::new);
+            self.spell_dictionary = Some((language, dictionary));
+        }
+        self.spell_dictionary
+            .as_ref()
+            .and_then(|(_, dictionary)| dictionary.clone())
+    }
+
+    /// Remove every spelling underline so the next frame checks afresh.
+    fn clear_spell_check(&mut self) {
+        let win = self.active_window_mut();
+        if win.spell_check.checked.is_empty() {
+            return;
+        }
+        let ns = spell_check_namespace();
+        let buffer_ids: Vec<BufferId> = win.spell_check.checked.drain().map(|(id, _)| id).collect();
+        for buffer_id in buffer_ids {
+            if let Some(state) = win.buffers.get_mut(&buffer_id) {
+                state.overlays.clear_namespace(&ns, &mut state.marker_list);
+            }
+        }
+    }
+
+    /// Byte range of the visible lines of each buffer shown in a split,
+    /// merged across splits showing the same buffer. Shared with the
+    /// document link detector.
+    pub(super) fn visible_line_ranges(&self) -> Vec<(BufferId, Range<usize>)> {
+        let context_bytes = self.config.editor.highlight_context_bytes;
+        let win = self.active_window();
+        let Some((mgr, view_states)) = win.buffers.splits() else {
+            return Vec::new();
+        };
+        let mut ranges: HashMap<BufferId, Range<usize>> = HashMap::new();
+        for (split_id, view_state) in view_states {
+            let Some(buffer_id) = mgr.get_buffer_id((*split_id).into()) else {
+                continue;
+            };
+            let Some(state) = win.buffers.get(&buffer_id) else {
+                continue;
+            };
+            let start = view_state.viewport.top_byte();
+            let last_line =
+                state.buffer.get_line_number(start) + view_state.viewport.visible_line_count() + 1;
+            // A large file has no line index to find the last visible line
+            // in; stop a highlighting context past the top instead of
+            // reading to the end.
+            let end = state
+                .buffer
+                .line_start_offset(last_line)
+                .unwrap_or_else(|| {
+                    if state.buffer.is_large_file() {
+                        (start + context_bytes).min(state.buffer.len())
+                    } else {
+                        state.buffer.len()
+                    }
+                })
+                .max(start);
+            ranges
+                .entry(buffer_id)
+                .and_modify(|r| *r = r.start.min(start)..r.end.max(end))
+                .or_insert(start..end);
+        }
+        ranges.into_iter().collect()
+    }
+
+    /// Code actions for the misspelled word under the primary cursor: one
+    /// per suggestion, then adding the word to the project word list.
+    pub(super) fn spelling_code_actions(
+        &mut self,
+    ) -> Vec<(String, lsp_types::CodeActionOrCommand)> {
+        let Some(dictionary) = self.spell_dictionary() else {
+            return Vec::new();
+        };
+        let position = self.active_cursors().primary().position;
+        let ns = spell_check_namespace();
+        let state = self.active_state_mut();
+        let Some(range) = state
+            .overlays
+            .all()
+            .iter()
+            .filter(|o| o.namespace.as_ref() == Some(&ns))
+            .map(|o| o.range(&state.marker_list))
+            .find(|r| r.start < r.end && r.start <= position && position <= r.end)
+        else {
+            return Vec::new();
+        };
+        let word = state.get_text_range(range.start, range.end);
+
+        let action = |title: String, command: &str, arguments: Vec<Value>| {
+            (
+                SPELLING_ACTION_SOURCE.to_string(),
+                lsp_types::CodeActionOrCommand::Command(lsp_types::Command {
+                    title,
+                    command: command.to_string(),
+                    arguments: Some(arguments),
+                }),
+            )
+        };
+        let mut actions: Vec<_> = dictionary
+            .suggest(&word, MAX_SUGGESTIONS)
+            .into_iter()
+            .map(|suggestion| {
+                action(
+                    t!("spell.change_to", word = &suggestion).to_string(),
+                    REPLACE_WORD_COMMAND,
+                    vec![
+                        json!(range.start),
+                        json!(range.end),
+                        json!(word),
+                        json!(suggestion),
+                    ],
+                )
+            })
+            .collect();
+        actions.push(action(
+            t!("spell.add_to_project", word = &word).to_string(),
+            ADD_WORD_COMMAND,
+            vec![json!(word)],
+        ));
+        actions
+    }
+
+    /// Run a spelling entry picked from the code-action popup.
+    pub(super) fn execute_spelling_action(&mut self, command: lsp_types::Command) {
+        let args = command.arguments.unwrap_or_default();
+        match command.command.as_str() {
+            REPLACE_WORD_COMMAND => {
+                let (Some(start), Some(end), Some(word), Some(replacement)) = (
+                    args.first().and_then(Value::as_u64),
+                    args.get(1).and_then(Value::as_u64),
+                    args.get(2).and_then(Value::as_str),
+                    args.get(3).and_then(Value::as_str),
+                ) else {
+                    return;
+                };
+                let (start, end) = (start as usize, end as usize);
+                // Only replace the word the popup was opened on.
+                let state = self.active_state_mut();
+                if end > state.buffer.len() || state.get_text_range(start, end) != word {
+                    return;
+                }
+                let cursor_id = self.active_cursors().primary_id();
+                let batch = Event::Batch {
+                    events: vec![
+                        Event::Delete {
+                            range: start..end,
+                            deleted_text: word.to_string(),
+                            cursor_id,
+                        },
+                        Event::Insert {
+                            position: start,
+                            text: replacement.to_string(),
+                            cursor_id,
+                        },
+                    ],
+                    description: format!("Change '{word}' to '{replacement}'"),
+                };
+                self.log_and_apply_event(&batch);
+            }
+            ADD_WORD_COMMAND => {
+                let Some(word) = args.first().and_then(Value::as_str) else {
+                    return;
+                };
+                let win = self.active_window_mut();
+                let project_words = win
+                    .spell_check
+                    .project_words
+                    .get_or_insert_with(|| ProjectWordList::load(&win.root));
+                match project_words.add(word) {
+                    Ok(()) => {
+                        let path = project_words.path().display().to_string();
+                        self.clear_spell_check();
+                        self.set_status_message(
+                            t!("spell.word_added", word = word, path = path).to_string(),
+                        );
+                    }
+                    Err(e) => self.set_status_message(
+                        t!("spell.word_add_failed", error = e.to_string()).to_string(),
+                    ),
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_ranges_joins_touching_spans() {
+        assert_eq!(
+            merge_ranges(vec![10..12, 0..4, 4..6, 11..15]),
+            vec![0..6, 10..15]
+        );
+    }
+}
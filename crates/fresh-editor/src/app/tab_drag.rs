@@ -502,7 +502,7 @@ impl Editor {
                     wrap_column: self
                         .active_window()
                         .resolve_wrap_column_for_buffer(buffer_id),
-                    rulers: self.config.editor.rulers.clone(),
+                    rulers: self.active_window().resolve_rulers_for_buffer(buffer_id),
                     scroll_offset: self.config.editor.scroll_offset,
                 });
 
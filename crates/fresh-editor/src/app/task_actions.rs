@@ -0,0 +1,290 @@
+//! Task actions: "Run Task", "Rerun Last Task" and publishing the problems
+//! a finished task reports.
+//!
+//! A terminal task runs in a new terminal tab through the window
+//! authority, so it runs wherever the window's shells run; when the
+//! terminal exits, the tab's scroll-back is the task's output. A
+//! background task runs through the authority's process spawner and
+//! reports back with `AsyncMessage::TaskFinished`.
+//!
+//! Problems found by the task's matchers are stored like a language
+//! server's push diagnostics, under the name `task:<label>`, so they show
+//! as overlays, in the diagnostics panel and for `JumpToNextError`. Each
+//! run of a task replaces the problems of its previous run.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use rust_i18n::t;
+
+use super::Editor;
+use crate::input::commands::Suggestion;
+use crate::model::event::BufferId;
+use crate::services::async_bridge::AsyncMessage;
+use crate::services::tasks::problem_matcher::{CompiledMatcher, TaskProblem};
+use crate::services::tasks::{self, RunningTask, VSCODE_TASKS_PATH};
+use crate::services::terminal::TerminalId;
+use crate::types::{TaskConfig, TaskRunIn};
+use crate::view::prompt::{Prompt, PromptType};
+
+impl Editor {
+    /// The tasks "Run Task" offers: the configured ones, then those from
+    /// the workspace's `.vscode/tasks.json`.
+    fn available_tasks(&self) -> Vec<TaskConfig> {
+        let path = self.active_window().root.join(VSCODE_TASKS_PATH);
+        let imported = match self.authority().filesystem.read_file(&path) {
+            Ok(bytes) => tasks::parse_vscode_tasks(&String::from_utf8_lossy(&bytes))
+                .unwrap_or_else(|e| {
+                    tracing::warn!("Failed to parse {}: {}", path.display(), e);
+                    Vec::new()
+                }),
+            Err(_) => Vec::new(),
+        };
+        tasks::collect_tasks(&self.config().tasks, imported)
+    }
+
+    /// Pick a task to run.
+    pub fn run_task(&mut self) {
+        let tasks = self.available_tasks();
+        if tasks.is_empty() {
+            self.set_status_message(t!("task.none").to_string());
+            return;
+        }
+        let root = self.active_window().root.clone();
+        let suggestions = tasks
+            .iter()
+            .map(|task| Suggestion {
+                description_spans: None,
+                text: task.label.clone(),
+                description: Some(task.command_line(&root, None)),
+                value: Some(task.label.clone()),
+                disabled: false,
+                keybinding: None,
+                source: None,
+            })
+            .collect();
+        self.active_window_mut().prompt = Some(Prompt::with_suggestions(
+            t!("task.select").to_string(),
+            PromptType::SelectTask,
+            suggestions,
+        ));
+    }
+
+    /// Run the last task again.
+    pub fn rerun_last_task(&mut self) {
+        match self.active_window().last_task.clone() {
+            Some(label) => self.run_task_by_label(&label),
+            None => self.set_status_message(t!("task.no_last").to_string()),
+        }
+    }
+
+    /// Run the task named `label`.
+    pub(crate) fn run_task_by_label(&mut self, label: &str) {
+        match self
+            .available_tasks()
+            .into_iter()
+            .find(|task| task.label == label)
+        {
+            Some(task) => self.start_task(task),
+            None => self.set_status_message(t!("task.unknown", label = label).to_string()),
+        }
+    }
+
+    fn start_task(&mut self, task: TaskConfig) {
+        let root = self.active_window().root.clone();
+        let file = self
+            .active_state()
+            .buffer
+            .file_path()
+            .map(|p| p.to_path_buf());
+        let command_line = task.command_line(&root, file.as_deref());
+        let cwd = task.working_dir(&root);
+        let argv = tasks::shell_argv(&command_line);
+        tracing::info!("Running task '{}': {}", task.label, command_line);
+
+        let window = self.active_window_mut();
+        let run_id = window.next_task_run_id;
+        window.next_task_run_id += 1;
+        window.last_task = Some(task.label.clone());
+
+        let terminal = match task.run_in {
+            TaskRunIn::Terminal => {
+                match self.open_task_terminal(argv, cwd.clone(), task.label.clone()) {
+                    Some(terminal) => Some(terminal),
+                    None => {
+                        self.set_status_message(
+                            t!("task.start_failed", label = &task.label).to_string(),
+                        );
+                        return;
+                    }
+                }
+            }
+            TaskRunIn::Background => {
+                let (Some(runtime), Some(bridge)) = (&self.tokio_runtime, &self.async_bridge)
+                else {
+                    self.set_status_message(
+                        t!("task.start_failed", label = &task.label).to_string(),
+                    );
+                    return;
+                };
+                let sender = bridge.sender();
+                let spawner = self.authority().process_spawner.clone();
+                let mut argv = argv.into_iter();
+                let command = argv.next().unwrap_or_default();
+                let args: Vec<String> = argv.collect();
+                let task_cwd = cwd.to_string_lossy().to_string();
+                runtime.spawn(async move {
+                    let (output, exit_code) =
+                        match spawner.spawn(command, args, Some(task_cwd)).await {
+                            Ok(result) => (result.stdout + &result.stderr, result.exit_code),
+                            Err(e) => (e.to_string(), -1),
+                        };
+                    #[allow(clippy::let_underscore_must_use)]
+                    let _ = sender.send(AsyncMessage::TaskFinished {
+                        run_id,
+                        output,
+                        exit_code,
+                    });
+                });
+                None
+            }
+        };
+
+        self.set_status_message(t!("task.running", label = &task.label).to_string());
+        self.active_window_mut().running_tasks.insert(
+            run_id,
+            RunningTask {
+                task,
+                cwd,
+                terminal,
+            },
+        );
+    }
+
+    /// Open a terminal tab titled `title` running `argv` in `cwd`.
+    fn open_task_terminal(
+        &mut self,
+        argv: Vec<String>,
+        cwd: PathBuf,
+        title: String,
+    ) -> Option<(TerminalId, BufferId)> {
+        let window = self.active_window_mut();
+        let terminal_id =
+            window.spawn_terminal_session(Some(cwd), false, Some(argv), HashMap::new())?;
+        let split_id = window
+            .buffers
+            .splits()
+            .map(|(mgr, _)| mgr.active_split())
+            .expect("window must have a populated split layout");
+        let buffer_id = window.create_terminal_buffer_attached(terminal_id, split_id);
+        if let Some(meta) = window.buffer_metadata.get_mut(&buffer_id) {
+            meta.display_name = title;
+        }
+        window.terminal_explicit_titles.insert(buffer_id);
+        window.set_active_buffer(buffer_id);
+        window.key_context = crate::input::keybindings::KeyContext::Terminal;
+        window.resize_visible_terminals();
+        Some((terminal_id, buffer_id))
+    }
+
+    /// A terminal exited; if it was running a task, match the output it
+    /// left in its buffer.
+    pub(super) fn handle_task_terminal_exited(
+        &mut self,
+        terminal_id: TerminalId,
+        exit_code: Option<i32>,
+    ) {
+        let window = self.active_window();
+        let Some((run_id, buffer_id)) = window.running_tasks.iter().find_map(|(&run_id, run)| {
+            run.terminal
+                .filter(|(id, _)| *id == terminal_id)
+                .map(|(_, buffer_id)| (run_id, buffer_id))
+        }) else {
+            return;
+        };
+        let output = window
+            .buffers
+            .get(&buffer_id)
+            .and_then(|state| state.buffer.to_string())
+            .map(|text| crate::primitives::ansi::strip_ansi_codes(&text))
+            .unwrap_or_default();
+        self.handle_task_finished(run_id, output, exit_code);
+    }
+
+    /// A task finished: publish the problems its matchers find in `output`.
+    pub(super) fn handle_task_finished(
+        &mut self,
+        run_id: u64,
+        output: String,
+        exit_code: Option<i32>,
+    ) {
+        let Some(run) = self.active_window_mut().running_tasks.remove(&run_id) else {
+            return;
+        };
+        let label = run.task.label.clone();
+
+        let mut problems: Vec<TaskProblem> = Vec::new();
+        for config in &run.task.problem_matcher {
+            match CompiledMatcher::new(config) {
+                Ok(matcher) => problems.extend(matcher.match_output(&output, &run.cwd, &label)),
+                Err(e) => {
+                    tracing::warn!("Task '{}': {}", label, e);
+                    self.set_status_message(
+                        t!("task.matcher_error", label = &label, error = e).to_string(),
+                    );
+                }
+            }
+        }
+        let count = problems.len();
+        self.publish_task_problems(&label, problems);
+
+        let message = match exit_code {
+            Some(code) => t!(
+                "task.finished_with_code",
+                label = &label,
+                code = code,
+                count = count
+            ),
+            None => t!("task.finished", label = &label, count = count),
+        };
+        self.set_status_message(message.to_string());
+    }
+
+    /// Replace the diagnostics from the previous run of task `label` with
+    /// `problems`.
+    fn publish_task_problems(&mut self, label: &str, problems: Vec<TaskProblem>) {
+        let server_name = tasks::diagnostic_source(label);
+        self.clear_diagnostics_for_server(&server_name);
+
+        let translation = self.authority().path_translation.clone();
+        let mut by_uri: HashMap<String, Vec<lsp_types::Diagnostic>> = HashMap::new();
+        for problem in problems {
+            // Tools in a container report container paths.
+            let path = translation
+                .as_ref()
+                .and_then(|t| t.remote_to_host(&problem.path))
+                .unwrap_or(problem.path);
+            let Some(uri) = crate::app::types::LspUri::from_host_path(&path, translation.as_ref())
+            else {
+                continue;
+            };
+            by_uri
+                .entry(uri.as_str().to_string())
+                .or_default()
+                .push(problem.diagnostic);
+        }
+
+        let mut uris: Vec<String> = by_uri.keys().cloned().collect();
+        uris.sort();
+        for uri in uris {
+            let diagnostics = by_uri.remove(&uri).unwrap_or_default();
+            let anchored = self.anchor_diagnostics(&uri, diagnostics);
+            self.active_window_mut()
+                .stored_push_diagnostics
+                .entry(uri.clone())
+                .or_default()
+                .insert(server_name.clone(), anchored);
+            self.merge_and_apply_diagnostics(&uri);
+        }
+    }
+}
@@ -102,14 +102,19 @@ impl Editor {
         })
     }
 
-    /// Resolve a path printed by a terminal program to an existing file.
+    /// Resolve a path printed by a terminal program (or written in a buffer,
+    /// see [`super::document_links`]) to an existing file.
     ///
     /// Tries, in order: the path as-is if absolute (after `~` expansion), then
-    /// joined against the terminal's OSC 7 cwd, then against Fresh's working
-    /// directory. Returns the first candidate that exists and is a regular
-    /// file. Directories and non-existent paths yield `None` (so the link is
-    /// inert).
-    fn resolve_terminal_path(&self, raw: &str, term_cwd: Option<&Path>) -> Option<PathBuf> {
+    /// joined against the terminal's OSC 7 cwd (or the buffer's directory),
+    /// then against Fresh's working directory. Returns the first candidate
+    /// that exists and is a regular file. Directories and non-existent paths
+    /// yield `None` (so the link is inert).
+    pub(super) fn resolve_terminal_path(
+        &self,
+        raw: &str,
+        term_cwd: Option<&Path>,
+    ) -> Option<PathBuf> {
         let expanded = expand_tilde(raw);
 
         let candidates: Vec<PathBuf> = if expanded.is_absolute() {
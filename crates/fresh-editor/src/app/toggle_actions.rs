@@ -3,7 +3,7 @@
 //! This module contains toggle methods and configuration operations:
 //! - Toggle line numbers, debug highlights, menu bar
 //! - Toggle mouse capture, mouse hover, inlay hints
-//! - Reset buffer settings
+//! - Reset and show buffer settings
 //! - Config dump, save, and reload
 
 use crate::types::LspServerConfig;
@@ -12,6 +12,7 @@ use rust_i18n::t;
 use crate::config::{Config, FileExplorerSide};
 use crate::config_io::{ConfigLayer, ConfigResolver};
 use crate::config_keys::{self, SettingKey};
+use crate::view::popup::{Popup, PopupPosition};
 
 use super::Editor;
 
@@ -584,6 +585,32 @@ impl Editor {
         self.set_status_message(status.to_string());
     }
 
+    /// Toggle minimap visibility
+    pub fn toggle_minimap(&mut self) {
+        let new_value = !self.config.editor.show_minimap;
+        self.config_mut().editor.show_minimap = new_value;
+        self.persist_config_change(config_keys::EDITOR_SHOW_MINIMAP, new_value);
+        let status = if new_value {
+            t!("toggle.minimap_shown")
+        } else {
+            t!("toggle.minimap_hidden")
+        };
+        self.set_status_message(status.to_string());
+    }
+
+    /// Toggle sticky scroll
+    pub fn toggle_sticky_scroll(&mut self) {
+        let new_value = !self.config.editor.sticky_scroll;
+        self.config_mut().editor.sticky_scroll = new_value;
+        self.persist_config_change(config_keys::EDITOR_STICKY_SCROLL, new_value);
+        let status = if new_value {
+            t!("toggle.sticky_scroll_enabled")
+        } else {
+            t!("toggle.sticky_scroll_disabled")
+        };
+        self.set_status_message(status.to_string());
+    }
+
     /// Resolve the whitespace-indicator visibility a buffer would get from the
     /// current config: the flat editor config, refined by the buffer language's
     /// `show_whitespace_tabs` override. This is the same resolution used when a
@@ -654,6 +681,7 @@ impl Editor {
         let wrap_column = self
             .active_window()
             .resolve_wrap_column_for_buffer(buffer_id);
+        let rulers = self.active_window().resolve_rulers_for_buffer(buffer_id);
         let leaf_ids: Vec<_> = self
             .windows
             .get(&self.active_window)
@@ -685,7 +713,7 @@ impl Editor {
                     line_wrap,
                     wrap_indent: self.config.editor.wrap_indent,
                     wrap_column,
-                    rulers: self.config.editor.rulers.clone(),
+                    rulers: rulers.clone(),
                     scroll_offset: self.config.editor.scroll_offset,
                 });
             }
@@ -694,6 +722,124 @@ impl Editor {
         self.set_status_message(t!("toggle.buffer_settings_reset").to_string());
     }
 
+    /// Show the active buffer's effective settings in a popup, each with
+    /// where it came from: the `.editorconfig` that set it, a per-buffer
+    /// toggle, the file itself, or the config.
+    pub fn show_buffer_settings(&mut self) {
+        let buffer_id = self.active_buffer();
+        let window = self.active_window();
+        let wrap_column = window.resolve_wrap_column_for_buffer(buffer_id);
+        let rulers = window.resolve_rulers_for_buffer(buffer_id);
+        let state = self.active_state();
+        let settings = &state.buffer_settings;
+        let editorconfig = &settings.editorconfig;
+
+        // The `.editorconfig` that set `property`, else `fallback`.
+        let source = |property: &str, fallback: String| match editorconfig.source_of(property) {
+            Some(path) => path.display().to_string(),
+            None => fallback,
+        };
+        let config = || t!("buffer_settings.source_config").to_string();
+        let on_off = |on: bool| {
+            if on {
+                t!("buffer_settings.on").to_string()
+            } else {
+                t!("buffer_settings.off").to_string()
+            }
+        };
+
+        let indentation = if settings.use_tabs {
+            t!("buffer_settings.tabs")
+        } else {
+            t!("buffer_settings.spaces")
+        };
+        let indentation_source = if settings.use_tabs_override.is_some() {
+            t!("buffer_settings.source_buffer").to_string()
+        } else {
+            source("indent_style", config())
+        };
+        let trim = editorconfig
+            .trim_trailing_whitespace
+            .unwrap_or(self.config.editor.trim_trailing_whitespace_on_save);
+        let final_newline = editorconfig
+            .insert_final_newline
+            .unwrap_or(self.config.editor.ensure_final_newline_on_save);
+        let wrap_column = wrap_column
+            .map(|column| column.to_string())
+            .unwrap_or_else(|| t!("buffer_settings.none").to_string());
+        let rulers = if rulers.is_empty() {
+            t!("buffer_settings.none").to_string()
+        } else {
+            rulers
+                .iter()
+                .map(|column| column.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        let rows = [
+            (
+                t!("buffer_settings.indentation"),
+                indentation.to_string(),
+                indentation_source,
+            ),
+            (
+                t!("buffer_settings.tab_size"),
+                settings.tab_size.to_string(),
+                source("indent_size", config()),
+            ),
+            (
+                t!("buffer_settings.line_ending"),
+                state.buffer.line_ending().display_name().to_string(),
+                source("end_of_line", t!("buffer_settings.source_file").to_string()),
+            ),
+            (
+                t!("buffer_settings.encoding"),
+                state.buffer.encoding().display_name().to_string(),
+                source("charset", t!("buffer_settings.source_file").to_string()),
+            ),
+            (
+                t!("buffer_settings.trim_on_save"),
+                on_off(trim),
+                source("trim_trailing_whitespace", config()),
+            ),
+            (
+                t!("buffer_settings.final_newline_on_save"),
+                on_off(final_newline),
+                source("insert_final_newline", config()),
+            ),
+            (
+                t!("buffer_settings.wrap_column"),
+                wrap_column,
+                source("max_line_length", config()),
+            ),
+            (
+                t!("buffer_settings.rulers"),
+                rulers,
+                source("max_line_length", config()),
+            ),
+        ];
+        let label_width = rows
+            .iter()
+            .map(|(label, _, _)| label.chars().count())
+            .max()
+            .unwrap_or(0);
+        let lines: Vec<String> = rows
+            .iter()
+            .map(|(label, value, source)| format!("{label:<label_width$}  {value}  ({source})"))
+            .collect();
+
+        let popup = {
+            let theme = self.theme.read().unwrap();
+            Popup::text(lines, &theme)
+                .with_title(t!("buffer_settings.title").to_string())
+                .with_position(PopupPosition::Centered)
+                .with_width(80)
+                .with_transient(true)
+        };
+        self.active_state_mut().popups.show(popup);
+    }
+
     /// Toggle mouse capture on/off
     pub fn toggle_mouse_capture(&mut self) {
         use std::io::stdout;
@@ -804,7 +950,7 @@ impl Editor {
                 .map(|w| &mut w.buffers)
                 .expect("active window present")
             {
-                state.virtual_texts.clear(&mut state.marker_list);
+                state.virtual_texts.clear_inline(&mut state.marker_list);
             }
             self.set_status_message(t!("toggle.inlay_hints_disabled").to_string());
         }
@@ -235,6 +235,14 @@ pub(crate) struct WindowLayoutCache {
     /// Horizontal scrollbar areas per split
     /// (split_id, buffer_id, horizontal_scrollbar_rect, max_content_width, thumb_start_col, thumb_end_col)
     pub horizontal_scrollbar_areas: Vec<(LeafId, BufferId, Rect, usize, usize, usize)>,
+    /// Minimap areas per split, with the byte offset each minimap row
+    /// scrolls to when clicked
+    /// (split_id, buffer_id, minimap_rect, row_targets)
+    pub minimap_areas: Vec<(LeafId, BufferId, Rect, Vec<usize>)>,
+    /// Sticky scroll headers per split, with the line start of the scope
+    /// each header row shows
+    /// (split_id, buffer_id, header_rect, header_lines)
+    pub sticky_scroll_areas: Vec<(LeafId, BufferId, Rect, Vec<usize>)>,
     /// Split separator positions for drag resize
     /// (container_id, direction, x, y, length)
     pub separator_areas: Vec<(ContainerId, SplitDirection, u16, u16, u16)>,
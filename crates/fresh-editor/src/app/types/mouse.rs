@@ -10,6 +10,8 @@ pub struct MouseState {
     pub dragging_scrollbar: Option<LeafId>,
     /// Whether we're currently dragging a horizontal scrollbar
     pub dragging_horizontal_scrollbar: Option<LeafId>,
+    /// Whether we're currently dragging in a split's minimap
+    pub dragging_minimap: Option<LeafId>,
     /// Initial mouse column when starting horizontal scrollbar drag
     pub drag_start_hcol: Option<u16>,
     /// Initial left_column when starting horizontal scrollbar drag
@@ -1,8 +1,29 @@
-//! Undo and redo action handlers.
+//! Undo and redo action handlers, plus the undo-tree browser.
+//!
+//! The browser is a read-only virtual buffer listing every state in the
+//! active buffer's undo tree (see [`crate::model::event::EventLog::undo_tree`]).
+//! Each row carries its node id as a text property, so `Enter` / `p` act on
+//! the row under the cursor without re-deriving the layout.
 
-use super::Editor;
+use crossterm::event::{KeyCode, KeyModifiers};
 use rust_i18n::t;
 
+use super::Editor;
+use crate::input::buffer_mode::BufferMode;
+use crate::input::keybindings::{Action, KeyContext};
+use crate::model::event::{BufferId, Event, UndoNodeId, UndoTreeRow};
+use crate::primitives::text_property::TextPropertyEntry;
+use crate::view::popup::{Popup, PopupPosition};
+
+/// Mode name carried on undo-tree browser buffers.
+pub(super) const UNDO_TREE_MODE: &str = "undo-tree";
+
+/// Text property holding a row's node id (`null` for the original content).
+const UNDO_NODE_PROPERTY: &str = "undo_node";
+
+/// Text property holding a row's sequence number, echoed in status messages.
+const UNDO_SEQ_PROPERTY: &str = "undo_seq";
+
 impl Editor {
     /// Handle Undo action - revert the last edit operation.
     pub fn handle_undo(&mut self) {
@@ -25,22 +46,7 @@ impl Editor {
             events.len()
         );
 
-        // Apply all inverse events collected during undo.
-        // Each event may carry displaced markers that need restoration after apply.
-        for (event, displaced_markers) in &events {
-            tracing::debug!("Undo applying event: {:?}", event);
-            self.apply_event_to_active_buffer(event);
-
-            // Restore displaced markers from LogEntry (for single Delete events).
-            // Skip for BulkEdit — they handle displaced markers internally
-            // in state.apply(BulkEdit) via the Event's own displaced_markers field.
-            if !displaced_markers.is_empty()
-                && !matches!(event, crate::model::event::Event::BulkEdit { .. })
-            {
-                self.active_state_mut()
-                    .restore_displaced_markers(displaced_markers);
-            }
-        }
+        self.apply_undo_events(&events);
 
         // Update modified status based on event log position
         self.active_window_mut().update_modified_from_event_log();
@@ -63,4 +69,260 @@ impl Editor {
         // Update modified status based on event log position
         self.active_window_mut().update_modified_from_event_log();
     }
+
+    /// Apply inverse events collected by the event log to the active buffer.
+    /// Each event may carry displaced markers that need restoration after apply.
+    fn apply_undo_events(&mut self, events: &[(Event, Vec<(u64, usize)>)]) {
+        for (event, displaced_markers) in events {
+            tracing::debug!("Undo applying event: {:?}", event);
+            self.apply_event_to_active_buffer(event);
+
+            // Restore displaced markers from LogEntry (for single Delete events).
+            // Skip for BulkEdit — they handle displaced markers internally
+            // in state.apply(BulkEdit) via the Event's own displaced_markers field.
+            if !displaced_markers.is_empty() && !matches!(event, Event::BulkEdit { .. }) {
+                self.active_state_mut()
+                    .restore_displaced_markers(displaced_markers);
+            }
+        }
+    }
+
+    /// Open (or refresh) the undo-tree browser for the active buffer.
+    ///
+    /// Invoked from a browser buffer, this refreshes that browser in place.
+    pub fn show_undo_tree(&mut self) {
+        let active = self.active_buffer();
+        let source = self
+            .active_window()
+            .undo_tree_sources
+            .get(&active)
+            .copied()
+            .unwrap_or(active);
+        if !self.active_window().event_logs.contains_key(&source) {
+            return;
+        }
+
+        self.ensure_undo_tree_mode_registered();
+
+        let existing = self
+            .active_window()
+            .undo_tree_sources
+            .iter()
+            .find(|(tree, src)| **src == source && self.buffers().contains_key(tree))
+            .map(|(tree, _)| *tree);
+        let tree_buffer = match existing {
+            Some(tree) => tree,
+            None => {
+                let source_name = self
+                    .active_window()
+                    .buffer_metadata
+                    .get(&source)
+                    .map(|m| m.display_name.clone())
+                    .unwrap_or_default();
+                let name = t!("undo_tree.buffer_name", name = source_name).to_string();
+                let window = self.active_window_mut();
+                let tree = window.create_virtual_buffer(name, UNDO_TREE_MODE.to_string(), true);
+                window.undo_tree_sources.insert(tree, source);
+                if let Some(state) = window.buffers.get_mut(&tree) {
+                    state.editing_disabled = true;
+                    state.margins.configure_for_line_numbers(false);
+                }
+                tree
+            }
+        };
+
+        let current_row_start = self.refresh_undo_tree(tree_buffer);
+        self.set_active_buffer(tree_buffer);
+        if let Some(position) = current_row_start {
+            let splits = self
+                .windows
+                .get(&self.active_window)
+                .and_then(|w| w.buffers.splits())
+                .map(|(mgr, _)| mgr.splits_for_buffer(tree_buffer))
+                .unwrap_or_default();
+            self.active_window_mut()
+                .set_buffer_cursor_in_splits(tree_buffer, position, &splits);
+        }
+    }
+
+    /// Jump the source buffer to the undo state under the browser cursor.
+    pub fn undo_tree_jump(&mut self) {
+        let Some((source, node, seq)) = self.undo_tree_selection() else {
+            return;
+        };
+        if self
+            .buffers()
+            .get(&source)
+            .is_some_and(|state| state.editing_disabled)
+        {
+            self.set_status_message(t!("buffer.editing_disabled").to_string());
+            return;
+        }
+
+        let tree_buffer = self.active_buffer();
+        self.set_active_buffer(source);
+        let Some(jump) = self.active_event_log_mut().jump_to(node) else {
+            return;
+        };
+        self.apply_undo_events(&jump.undo);
+        for event in &jump.redo {
+            self.apply_event_to_active_buffer(event);
+        }
+        self.active_window_mut().update_modified_from_event_log();
+
+        self.refresh_undo_tree(tree_buffer);
+        self.set_status_message(t!("undo_tree.jumped", seq = seq).to_string());
+    }
+
+    /// Show the edits recorded in the undo state under the browser cursor.
+    pub fn undo_tree_preview(&mut self) {
+        let Some((source, node, seq)) = self.undo_tree_selection() else {
+            return;
+        };
+        let Some(node) = node else {
+            self.set_status_message(t!("undo_tree.preview_original").to_string());
+            return;
+        };
+        let Some(log) = self.active_window().event_logs.get(&source) else {
+            return;
+        };
+
+        const MAX_LINES_PER_EDIT: usize = 8;
+        let mut lines = Vec::new();
+        for entry in log.unit_entries(node) {
+            let (sign, text) = match &entry.event {
+                Event::Insert { text, .. } => ('+', text.as_str()),
+                Event::Delete { deleted_text, .. } => ('-', deleted_text.as_str()),
+                other => {
+                    lines.push(format!("  {}", other.summary()));
+                    continue;
+                }
+            };
+            let mut text_lines = text.split('\n');
+            for line in text_lines.by_ref().take(MAX_LINES_PER_EDIT) {
+                lines.push(format!("{sign} {line}"));
+            }
+            if text_lines.next().is_some() {
+                lines.push(format!("{sign} …"));
+            }
+        }
+
+        let popup = {
+            let theme = self.theme.read().unwrap();
+            Popup::text(lines, &theme)
+                .with_title(t!("undo_tree.preview_title", seq = seq).to_string())
+                .with_position(PopupPosition::Centered)
+                .with_width(70)
+                .with_transient(true)
+        };
+        self.active_state_mut().popups.show(popup);
+    }
+
+    /// Rebuild a browser buffer's rows from its source's event log.
+    /// Returns the byte offset of the row for the current state.
+    fn refresh_undo_tree(&mut self, tree_buffer: BufferId) -> Option<usize> {
+        let source = *self.active_window().undo_tree_sources.get(&tree_buffer)?;
+        let rows = self.active_window().event_logs.get(&source)?.undo_tree();
+
+        let mut current_row_start = None;
+        let mut offset = 0;
+        let mut entries = vec![TextPropertyEntry::text(format!(
+            "{}\n\n",
+            t!("undo_tree.header")
+        ))];
+        offset += entries[0].text.len();
+        for row in &rows {
+            if row.is_current {
+                current_row_start = Some(offset);
+            }
+            let mut entry = TextPropertyEntry::text(format_undo_tree_row(row));
+            entry.properties.insert(
+                UNDO_NODE_PROPERTY.to_string(),
+                serde_json::json!(row.node.map(|n| n.0)),
+            );
+            entry
+                .properties
+                .insert(UNDO_SEQ_PROPERTY.to_string(), serde_json::json!(row.seq));
+            offset += entry.text.len();
+            entries.push(entry);
+        }
+
+        if let Err(e) = self.set_virtual_buffer_content(tree_buffer, entries) {
+            tracing::warn!("Failed to fill undo tree buffer: {e}");
+            return None;
+        }
+        current_row_start
+    }
+
+    /// Resolve the browser row under the cursor into
+    /// `(source buffer, node, sequence number)`.
+    fn undo_tree_selection(&self) -> Option<(BufferId, Option<UndoNodeId>, usize)> {
+        let source = *self
+            .active_window()
+            .undo_tree_sources
+            .get(&self.active_buffer())?;
+        let props = self.get_text_properties_at_cursor()?;
+        let prop = props.iter().find(|p| p.get(UNDO_NODE_PROPERTY).is_some())?;
+        let node = prop.get_as::<Option<usize>>(UNDO_NODE_PROPERTY)?;
+        let seq = prop.get_as::<usize>(UNDO_SEQ_PROPERTY).unwrap_or(0);
+        Some((source, node.map(UndoNodeId), seq))
+    }
+
+    /// Register the built-in `"undo-tree"` buffer mode: `Enter` jumps,
+    /// `p`/`Space` preview, `g` refreshes and `q` closes. Idempotent, like
+    /// [`Self::ensure_help_panel_mode_registered`].
+    fn ensure_undo_tree_mode_registered(&mut self) {
+        let mode_ctx = KeyContext::Mode(UNDO_TREE_MODE.to_string());
+        {
+            let mut kb = self.keybindings.write().unwrap();
+            kb.clear_plugin_defaults_for_mode(UNDO_TREE_MODE);
+            kb.set_mode_inherits_normal_bindings(UNDO_TREE_MODE, true);
+            for (code, action) in [
+                (KeyCode::Enter, Action::UndoTreeJump),
+                (KeyCode::Char('p'), Action::UndoTreePreview),
+                (KeyCode::Char(' '), Action::UndoTreePreview),
+                (KeyCode::Char('g'), Action::ShowUndoTree),
+                (KeyCode::Char('q'), Action::CloseTab),
+            ] {
+                kb.load_plugin_default(mode_ctx.clone(), code, KeyModifiers::NONE, action);
+            }
+        }
+        self.mode_registry.register(
+            BufferMode::new(UNDO_TREE_MODE)
+                .with_read_only(true)
+                .with_inherit_normal_bindings(true),
+        );
+    }
+}
+
+/// Render one browser row: branch indent, state marker, sequence number,
+/// local time, summary and (when grouped) the undo group id.
+fn format_undo_tree_row(row: &UndoTreeRow) -> String {
+    let indent = "│ ".repeat(row.indent);
+    let marker = if row.is_current {
+        '●'
+    } else if row.on_active_branch {
+        '○'
+    } else {
+        '◌'
+    };
+    if row.node.is_none() {
+        return format!("{indent}{marker} #0    {}\n", t!("undo_tree.original"));
+    }
+    let time = chrono::DateTime::from_timestamp_millis(row.timestamp as i64)
+        .map(|t| {
+            t.with_timezone(&chrono::Local)
+                .format("%H:%M:%S")
+                .to_string()
+        })
+        .unwrap_or_default();
+    let group = row
+        .group_id
+        .map(|g| format!("  [{}]", t!("undo_tree.group", id = g)))
+        .unwrap_or_default();
+    format!(
+        "{indent}{marker} #{:<4} {time}  {}{group}\n",
+        row.seq,
+        row.summary.replace('\n', "⏎")
+    )
 }
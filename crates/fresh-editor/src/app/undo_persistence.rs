@@ -0,0 +1,168 @@
+//! Persistent undo history (`editor.persistent_undo`).
+//!
+//! A file's undo tree is written to the recovery directory whenever the
+//! buffer matches the file on disk — right after a save, and again when the
+//! buffer is closed or the editor exits without unsaved changes. Reopening
+//! the file restores the tree if the content still hashes the same; a file
+//! changed outside the editor invalidates (and deletes) the stored history.
+//! Histories of files that were deleted or changed while closed are pruned
+//! at startup, along with any beyond an age and count cap.
+//!
+//! All bodies live on `impl Window`: they only touch this window's buffers
+//! and event logs, plus the shared recovery service.
+
+use std::path::PathBuf;
+
+use crate::app::window::Window;
+use crate::model::event::{BufferId, EventLog};
+use crate::services::recovery::{content_hash, path_hash, PersistedUndoHistory};
+
+/// Undo histories not rewritten for this long are dropped at startup.
+const UNDO_HISTORY_MAX_AGE_SECS: u64 = 90 * 24 * 60 * 60;
+
+/// At most this many undo histories are kept, newest first.
+const UNDO_HISTORY_MAX_FILES: usize = 500;
+
+impl Window {
+    /// Swap a freshly opened file buffer's empty event log for its persisted
+    /// undo history, when there is one matching the buffer's content.
+    pub(crate) fn restore_persisted_undo(&mut self, buffer_id: BufferId) {
+        if !self.resources.config.editor.persistent_undo {
+            return;
+        }
+        let Some((path, hash)) = self.undo_persistence_key(buffer_id) else {
+            return;
+        };
+
+        let recovery = self.resources.recovery_service.lock().unwrap();
+        let storage = recovery.storage();
+        // Recovered unsaved changes (hot exit, crash) are written over the
+        // file's content without going through the event log, so a history
+        // restored here would no longer describe the buffer.
+        if matches!(storage.read_metadata(&path_hash(&path)), Ok(Some(_))) {
+            return;
+        }
+        let record = match storage.load_undo_history(&path) {
+            Ok(Some(record)) => record,
+            Ok(None) => return,
+            Err(e) => {
+                tracing::warn!("Failed to read undo history for {}: {}", path.display(), e);
+                return;
+            }
+        };
+        let restored = (record.content_hash == hash)
+            .then(|| EventLog::from_history(record.history))
+            .flatten();
+        let Some(log) = restored else {
+            tracing::debug!("Discarding stale undo history for {}", path.display());
+            if let Err(e) = storage.delete_undo_history(&path) {
+                tracing::warn!("Failed to delete undo history: {}", e);
+            }
+            return;
+        };
+        drop(recovery);
+
+        tracing::debug!("Restored undo history for {}", path.display());
+        self.event_logs.insert(buffer_id, log);
+    }
+
+    /// Write a file buffer's undo history to disk. Does nothing unless the
+    /// buffer currently matches its last save, since that is the only
+    /// content the history can be validated against on the next open.
+    pub(crate) fn persist_undo_history(&mut self, buffer_id: BufferId) {
+        if !self.resources.config.editor.persistent_undo {
+            return;
+        }
+        let history = match self.event_logs.get(&buffer_id) {
+            Some(log) if log.is_at_saved_position() => log.export_history(),
+            _ => None,
+        };
+        let Some(history) = history else {
+            return;
+        };
+        let Some((path, hash)) = self.undo_persistence_key(buffer_id) else {
+            return;
+        };
+
+        let record = PersistedUndoHistory::new(path, hash, history);
+        let recovery = self.resources.recovery_service.lock().unwrap();
+        if let Err(e) = recovery.storage().save_undo_history(&record) {
+            tracing::warn!(
+                "Failed to save undo history for {}: {}",
+                record.path.display(),
+                e
+            );
+        }
+    }
+
+    /// Persist the undo history of every file buffer in this window.
+    pub(crate) fn persist_all_undo_history(&mut self) {
+        if !self.resources.config.editor.persistent_undo {
+            return;
+        }
+        let ids: Vec<BufferId> = self.event_logs.keys().copied().collect();
+        for buffer_id in ids {
+            self.persist_undo_history(buffer_id);
+        }
+    }
+
+    /// Delete stored undo histories that can never be restored: the file is
+    /// gone or no longer hashes the same, or the history is past the age or
+    /// count cap. The file is loaded the way opening it would, so its hash
+    /// is comparable with the buffer content a history was saved against.
+    pub(crate) fn prune_undo_history(&self) {
+        let threshold = self.resources.config.editor.large_file_threshold_bytes as usize;
+        let fs = &self.resources.local_filesystem;
+        let recovery = self.resources.recovery_service.lock().unwrap();
+        let pruned = recovery.storage().prune_undo_history(
+            UNDO_HISTORY_MAX_AGE_SECS,
+            UNDO_HISTORY_MAX_FILES,
+            |record| {
+                let Ok(mut buffer) = crate::model::buffer::Buffer::load_from_file_for_editing(
+                    &record.path,
+                    threshold,
+                    std::sync::Arc::clone(fs),
+                ) else {
+                    return false;
+                };
+                if buffer.is_large_file() || buffer.is_binary() {
+                    return false;
+                }
+                let total_bytes = buffer.total_bytes();
+                buffer
+                    .get_text_range_mut(0, total_bytes)
+                    .is_ok_and(|content| content_hash(&content) == record.content_hash)
+            },
+        );
+        match pruned {
+            Ok(0) => {}
+            Ok(n) => tracing::debug!("Pruned {} stale undo histories", n),
+            Err(e) => tracing::warn!("Failed to prune undo history: {}", e),
+        }
+    }
+
+    /// The file path and content hash a buffer's history is stored under,
+    /// or `None` for buffers whose history is never persisted (virtual,
+    /// unnamed, composite, binary or large files).
+    fn undo_persistence_key(&mut self, buffer_id: BufferId) -> Option<(PathBuf, String)> {
+        if self
+            .buffer_metadata
+            .get(&buffer_id)
+            .is_none_or(|meta| meta.is_virtual())
+        {
+            return None;
+        }
+        let state = self.buffers.get_mut(&buffer_id)?;
+        if state.is_composite_buffer || state.buffer.is_large_file() || state.buffer.is_binary() {
+            return None;
+        }
+        let path = state
+            .buffer
+            .file_path()
+            .filter(|p| !p.as_os_str().is_empty())?
+            .to_path_buf();
+        let total_bytes = state.buffer.total_bytes();
+        let content = state.buffer.get_text_range_mut(0, total_bytes).ok()?;
+        Some((path, content_hash(&content)))
+    }
+}
@@ -48,8 +48,10 @@ pub use process_group::{LocalSignaller, ProcessGroupEntry, ProcessGroups, Signal
 use crate::app::types::{ChromeLayout, WindowLayoutCache};
 use crate::app::window_resources::WindowResources;
 use crate::model::event::{Event, LeafId};
+use crate::services::dap::manager::DapManager;
 use crate::services::lsp::diagnostics::AnchoredDiagnostic;
 use crate::services::lsp::manager::LspManager;
+use crate::services::tasks::RunningTask;
 use crate::types::LspFeature;
 use crate::view::file_tree::FileTreeView;
 use crate::view::split::{SplitManager, SplitViewState};
@@ -336,6 +338,20 @@ pub struct Window {
     /// lazily on demand; an idle window's manager holds only config.
     pub lsp: LspManager,
 
+    /// This window's debug session manager, built alongside `lsp` and
+    /// spawning adapters through the same authority.
+    pub dap: DapManager,
+
+    /// Tasks started from "Run Task" that have not finished, keyed by run
+    /// id (background tasks report back with it).
+    pub(crate) running_tasks: std::collections::HashMap<u64, RunningTask>,
+
+    /// Next id handed to a task run.
+    pub(crate) next_task_run_id: u64,
+
+    /// Label of the last task run, for "Rerun Last Task".
+    pub(crate) last_task: Option<String>,
+
     /// Utility-dock panel-id → buffer-id occupancy. Each window
     /// gets its own dock — when one window has the search panel
     /// claimed and the user dives elsewhere, the new window starts
@@ -360,6 +376,10 @@ pub struct Window {
     /// drops the buffer and its log together.
     pub event_logs: HashMap<BufferId, crate::model::event::EventLog>,
 
+    /// Open undo-tree browser buffers, keyed by the browser's id and
+    /// mapping to the buffer whose history they list.
+    pub(crate) undo_tree_sources: HashMap<BufferId, BufferId>,
+
     /// File buffers restored as **empty placeholders** because this window's
     /// authority is remote: reading their content synchronously during restore
     /// would freeze the single-threaded editor loop over a slow link. The editor
@@ -466,6 +486,52 @@ pub struct Window {
     pub folding_ranges_in_flight: std::collections::HashMap<BufferId, (u64, u64)>,
     pub folding_ranges_debounce: std::collections::HashMap<BufferId, std::time::Instant>,
 
+    /// Code lenses per buffer and pending resolve requests.
+    pub(crate) code_lenses:
+        std::collections::HashMap<BufferId, crate::app::code_lens::BufferCodeLenses>,
+    pub(crate) pending_code_lens_resolves:
+        std::collections::HashMap<u64, crate::app::code_lens::CodeLensResolveRequest>,
+
+    /// Versioned per-buffer tracking of code lens, document symbol (for the
+    /// sticky scroll header) and document link requests.
+    pub(crate) code_lens_requests: crate::app::buffer_requests::BufferRequests,
+    pub(crate) document_symbol_requests: crate::app::buffer_requests::BufferRequests,
+    pub(crate) document_link_requests: crate::app::buffer_requests::BufferRequests,
+    /// Buffer version and byte range each buffer was last scanned for
+    /// built-in links at.
+    pub(crate) document_links_scanned:
+        std::collections::HashMap<BufferId, (u64, std::ops::Range<usize>)>,
+
+    /// Pending `workspace/symbol` requests for Quick Open, keyed by request
+    /// ID, with the query each one searches.
+    pub(crate) pending_workspace_symbol_requests: std::collections::HashMap<u64, String>,
+
+    /// `workspace/diagnostic` pulls in flight, keyed by request ID, with the
+    /// server each one was sent to.
+    pub(crate) pending_workspace_diagnostic_requests: std::collections::HashMap<u64, String>,
+
+    /// Linked editing ranges: the in-flight request and the temporary
+    /// multi-cursor session mirroring edits into the linked ranges.
+    pub(crate) linked_editing: crate::app::linked_editing::LinkedEditingState,
+
+    /// The snippet whose tabstops Tab / Shift+Tab step through, if any.
+    pub(crate) snippet_session: Option<crate::app::snippet_session::SnippetSession>,
+
+    /// Where the last paste landed, so Yank Pop can swap it for an older
+    /// clipboard history entry.
+    pub(crate) last_yank: Option<crate::app::clipboard_history::LastYank>,
+
+    /// The on-type formatting request awaiting its edits, if any.
+    pub(crate) pending_on_type_formatting:
+        Option<crate::app::on_type_formatting::OnTypeFormattingRequest>,
+
+    /// The selection range request an Expand Selection is waiting for, if
+    /// any.
+    pub(crate) pending_selection_range: Option<crate::app::selection_range::SelectionRangeRequest>,
+
+    /// Spell check: the project word list and what was last checked.
+    pub(crate) spell_check: crate::app::spell_check::SpellCheckState,
+
     /// Pending semantic-tokens-full requests + per-buffer in-flight tracking +
     /// the next-allowed-refresh debounce.
     pub(crate) pending_semantic_token_requests:
@@ -1066,6 +1132,8 @@ pub struct Window {
     pub buffer_to_group: HashMap<BufferId, crate::app::types::BufferGroupId>,
     /// Next buffer group id within this window.
     pub next_buffer_group_id: usize,
+    /// The debug stack/variables/output panel, while it is open.
+    pub(crate) debug_panel: Option<crate::app::types::BufferGroupId>,
 
     /// Plugin keystroke-callback queue (in-flight `getNextKey()` callbacks).
     pub pending_next_key_callbacks: std::collections::VecDeque<fresh_core::api::JsCallbackId>,
@@ -1223,6 +1291,22 @@ pub(crate) fn build_window_lsp(
     lsp
 }
 
+/// Build the [`DapManager`] every window owns. Adapters are spawned through
+/// the window authority's long-running spawner, like language servers.
+pub(crate) fn build_window_dap(
+    id: WindowId,
+    authority: &crate::services::authority::Authority,
+    resources: &crate::app::window_resources::WindowResources,
+    bridge: &crate::services::async_bridge::AsyncBridge,
+) -> DapManager {
+    let mut dap = DapManager::new(id);
+    if let Some(runtime) = resources.tokio_runtime.as_ref() {
+        dap.set_runtime(runtime.handle().clone(), bridge.clone());
+    }
+    dap.set_long_running_spawner(authority.long_running_spawner.clone());
+    dap
+}
+
 /// Outcome of [`Window::claim_next_key`].
 pub(crate) enum NextKeyClaim {
     /// A plugin callback was pending: resolve it with this payload.
@@ -2144,6 +2228,7 @@ impl Window {
                     .clear_namespace(diagnostic_namespace, &mut state.marker_list);
                 state.virtual_texts.clear(&mut state.marker_list);
                 state.folding_ranges.clear(&mut state.marker_list);
+                state.sticky_scopes.clear_lsp(&mut state.marker_list);
                 for view_state in vs_map.values_mut() {
                     if let Some(buf_state) = view_state.keyed_states.get_mut(&buffer_id) {
                         buf_state.folds.clear(&mut state.marker_list);
@@ -2209,6 +2294,7 @@ impl Window {
         // are borrowed here, then moved into the struct below.
         let bridge = crate::services::async_bridge::AsyncBridge::new();
         let lsp = build_window_lsp(id, &root, &authority, &resources, &bridge);
+        let dap = build_window_dap(id, &authority, &resources, &bridge);
         Self {
             id,
             label,
@@ -2221,6 +2307,10 @@ impl Window {
             authority_spec: crate::services::authority::SessionAuthoritySpec::Local,
             remote_reconnect_error: None,
             lsp,
+            dap,
+            running_tasks: std::collections::HashMap::new(),
+            next_task_run_id: 0,
+            last_task: None,
             panel_ids: HashMap::new(),
             buffers: WindowBuffers::new(),
             buffer_metadata: HashMap::new(),
@@ -2233,6 +2323,7 @@ impl Window {
             terminal_fg_poll_at: None,
             terminal_fg_cache: HashMap::new(),
             event_logs: HashMap::new(),
+            undo_tree_sources: HashMap::new(),
             status_message: None,
             plugin_status_message: None,
             prompt: None,
@@ -2257,6 +2348,20 @@ impl Window {
             pending_folding_range_requests: std::collections::HashMap::new(),
             folding_ranges_in_flight: std::collections::HashMap::new(),
             folding_ranges_debounce: std::collections::HashMap::new(),
+            code_lenses: std::collections::HashMap::new(),
+            pending_code_lens_resolves: std::collections::HashMap::new(),
+            code_lens_requests: Default::default(),
+            document_symbol_requests: Default::default(),
+            document_link_requests: Default::default(),
+            document_links_scanned: std::collections::HashMap::new(),
+            pending_workspace_symbol_requests: std::collections::HashMap::new(),
+            pending_workspace_diagnostic_requests: std::collections::HashMap::new(),
+            linked_editing: Default::default(),
+            snippet_session: None,
+            last_yank: None,
+            pending_on_type_formatting: None,
+            pending_selection_range: None,
+            spell_check: Default::default(),
             pending_semantic_token_requests: std::collections::HashMap::new(),
             semantic_tokens_in_flight: std::collections::HashMap::new(),
             semantic_tokens_full_debounce: std::collections::HashMap::new(),
@@ -2318,7 +2423,15 @@ impl Window {
             editor_mode: None,
             prompt_histories: HashMap::new(),
             pending_close_buffer: None,
-            completion_service: crate::services::completion::CompletionService::new(),
+            completion_service: {
+                let mut service = crate::services::completion::CompletionService::new();
+                service.register(Box::new(
+                    crate::services::completion::snippets::UserSnippetProvider::new(
+                        resources.dir_context.snippets_dir(),
+                    ),
+                ));
+                service
+            },
             lsp_diagnostic_namespace: crate::view::overlay::OverlayNamespace::from_string(
                 "lsp-diagnostic".to_string(),
             ),
@@ -2374,6 +2487,7 @@ impl Window {
             buffer_groups: HashMap::new(),
             buffer_to_group: HashMap::new(),
             next_buffer_group_id: 0,
+            debug_panel: None,
             pending_next_key_callbacks: std::collections::VecDeque::new(),
             key_capture_active: false,
             pending_key_capture_buffer: std::collections::VecDeque::new(),
@@ -3289,8 +3403,8 @@ impl Window {
         max_byte_pos
     }
 
-    /// Find the split whose content or scrollbar area contains the
-    /// screen cell `(col, row)`. Returns the split id and its buffer
+    /// Find the split whose content, minimap or scrollbar area contains
+    /// the screen cell `(col, row)`. Returns the split id and its buffer
     /// id, or `None` when the position falls outside every split's
     /// content rect and outside every scrollbar gutter.
     pub fn split_at_position(&self, col: u16, row: u16) -> Option<(LeafId, BufferId)> {
@@ -3311,7 +3425,16 @@ impl Window {
                 return Some((split_id, buffer_id));
             }
         }
-        None
+        self.layout_cache
+            .minimap_areas
+            .iter()
+            .find(|(_, _, rect, _)| {
+                col >= rect.x
+                    && col < rect.x + rect.width
+                    && row >= rect.y
+                    && row < rect.y + rect.height
+            })
+            .map(|&(split_id, buffer_id, _, _)| (split_id, buffer_id))
     }
 
     /// If a per-edit diagnostic-pull debounce has fired, send a fresh
@@ -3340,6 +3463,7 @@ impl Window {
         };
 
         self.pull_diagnostics_for_uri(&language, &uri);
+        self.request_workspace_diagnostics(&language);
 
         false
     }
@@ -3489,6 +3613,7 @@ impl Window {
         let target_split = self.preferred_split_for_file();
         let line_wrap = self.resolve_line_wrap_for_buffer(buffer_id);
         let wrap_column = self.resolve_wrap_column_for_buffer(buffer_id);
+        let rulers = self.resolve_rulers_for_buffer(buffer_id);
         // Snapshot config values before taking the mutable view-states borrow
         // so the closure body doesn't have to re-borrow `self`.
         let cfg = self.config().editor.clone();
@@ -3505,7 +3630,7 @@ impl Window {
                 line_wrap,
                 wrap_indent: cfg.wrap_indent,
                 wrap_column,
-                rulers: cfg.rulers,
+                rulers,
                 scroll_offset: cfg.scroll_offset,
             });
         }
@@ -3972,6 +4097,9 @@ impl Window {
                 }
             }
 
+            // A workspace pull in flight is reporting on the old text; the
+            // debounced pull below asks again once typing settles.
+            self.cancel_workspace_diagnostics(&language);
             self.scheduled_diagnostic_pull = Some((
                 buffer_id,
                 std::time::Instant::now() + std::time::Duration::from_millis(1000),
@@ -458,7 +458,12 @@ impl Editor {
             return Ok(());
         }
 
-        let workspace = win.capture_workspace();
+        let mut workspace = win.capture_workspace();
+        // The clipboard is editor-global; every window's workspace carries
+        // the same history.
+        if self.config.clipboard.persist_history {
+            workspace.clipboard_history = self.clipboard.history().cloned().collect();
+        }
 
         // Refuse to overwrite a non-empty on-disk workspace with an
         // all-virtual snapshot (issue #2027). The protection is for
@@ -527,6 +532,12 @@ impl Editor {
 
         // Editor-global config overrides (the shared `Config`).
         self.restore_config_overrides(&workspace.config_overrides);
+        // The clipboard history is editor-global too: take it from the first
+        // workspace restored, never over copies made since.
+        if self.config.clipboard.persist_history && self.clipboard.history().len() == 0 {
+            self.clipboard
+                .restore_history(workspace.clipboard_history.clone());
+        }
         // Editor-global plugin state is NOT taken from per-window
         // workspace files: it has a single canonical home in the
         // global `orchestrator/state/` store, loaded once at boot.
@@ -1326,7 +1337,7 @@ impl crate::app::window::Window {
                             line_wrap: self.resolve_line_wrap_for_buffer(second_buffer_id),
                             wrap_indent: self.resources.config.editor.wrap_indent,
                             wrap_column: self.resolve_wrap_column_for_buffer(second_buffer_id),
-                            rulers: self.resources.config.editor.rulers.clone(),
+                            rulers: self.resolve_rulers_for_buffer(second_buffer_id),
                             scroll_offset: self.resources.config.editor.scroll_offset,
                         });
                         self.buffers
@@ -2875,6 +2886,7 @@ impl crate::app::window::Window {
             read_only_files,
             unnamed_buffers,
             plugin_global_state: HashMap::new(),
+            clipboard_history: Vec::new(),
             saved_at: std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap_or_default()
@@ -0,0 +1,173 @@
+//! Workspace diagnostics pull (`workspace/diagnostic`, LSP 3.17+).
+//!
+//! Document pulls only cover open buffers, so problems in files nobody has
+//! opened would never show up. Servers that advertise `workspaceDiagnostics`
+//! are also asked for the whole workspace: when they initialize, when they
+//! send `workspace/diagnostic/refresh`, once the project has loaded, and
+//! after each edit settles. An edit cancels the pull in flight, since the
+//! server is now reporting on text that no longer exists.
+//!
+//! Reports stream back as partial results and land in the same per-server
+//! pull store as document pulls, with the same per-file result IDs, so the
+//! diagnostics panel lists them and `JumpToNextError` can open their files.
+
+use super::window::Window;
+use super::Editor;
+use crate::services::async_bridge::FileDiagnosticReport;
+use crate::types::LspFeature;
+
+impl Window {
+    /// Send `workspace/diagnostic` to every server for `language` that
+    /// answers it, replacing any of their pulls still in flight.
+    pub(crate) fn request_workspace_diagnostics(&mut self, language: &str) {
+        self.cancel_workspace_diagnostics(language);
+
+        for sh in self
+            .lsp
+            .handles_for_feature(language, LspFeature::Diagnostics)
+        {
+            if !sh.capabilities.workspace_diagnostics {
+                continue;
+            }
+            // Every file this server has reported on, with its last result
+            // ID, so files it has nothing new for come back "unchanged".
+            let previous_result_ids: Vec<lsp_types::PreviousResultId> = self
+                .diagnostic_result_ids
+                .iter()
+                .filter_map(|(uri, ids)| {
+                    Some(lsp_types::PreviousResultId {
+                        uri: uri.parse().ok()?,
+                        value: ids.get(&sh.name)?.clone(),
+                    })
+                })
+                .collect();
+            let request_id = self.next_lsp_request_id;
+            self.next_lsp_request_id += 1;
+            if let Err(e) = sh
+                .handle
+                .workspace_diagnostic(request_id, previous_result_ids)
+            {
+                tracing::debug!(
+                    "Failed to pull workspace diagnostics from '{}': {}",
+                    sh.name,
+                    e
+                );
+            } else {
+                tracing::debug!(
+                    "Pulling workspace diagnostics from '{}' (request_id={})",
+                    sh.name,
+                    request_id
+                );
+                self.pending_workspace_diagnostic_requests
+                    .insert(request_id, sh.name.clone());
+            }
+        }
+    }
+
+    /// Cancel the workspace pulls in flight to the servers for `language`.
+    /// Reports that still arrive for them are dropped.
+    pub(crate) fn cancel_workspace_diagnostics(&mut self, language: &str) {
+        if self.pending_workspace_diagnostic_requests.is_empty() {
+            return;
+        }
+        for sh in self
+            .lsp
+            .handles_for_feature(language, LspFeature::Diagnostics)
+        {
+            self.pending_workspace_diagnostic_requests
+                .retain(|&request_id, server_name| {
+                    if *server_name != sh.name {
+                        return true;
+                    }
+                    if let Err(e) = sh.handle.cancel_request(request_id) {
+                        tracing::debug!(
+                            "Failed to cancel workspace diagnostics from '{}': {}",
+                            sh.name,
+                            e
+                        );
+                    }
+                    false
+                });
+        }
+    }
+}
+
+impl Editor {
+    /// Pull workspace diagnostics from the servers for `language`.
+    pub(super) fn request_workspace_diagnostics(&mut self, language: &str) {
+        self.active_window_mut()
+            .request_workspace_diagnostics(language);
+    }
+
+    /// Handle one batch of `workspace/diagnostic` reports: a partial result,
+    /// or the final response when `done` is set.
+    pub(super) fn handle_lsp_workspace_diagnostics(
+        &mut self,
+        request_id: u64,
+        server_name: String,
+        reports: Vec<FileDiagnosticReport>,
+        done: bool,
+    ) {
+        let pending = &mut self
+            .active_window_mut()
+            .pending_workspace_diagnostic_requests;
+        let known = if done {
+            pending.remove(&request_id).is_some()
+        } else {
+            pending.contains_key(&request_id)
+        };
+        if !known {
+            tracing::debug!(
+                "Ignoring workspace diagnostics for cancelled or unknown request {}",
+                request_id
+            );
+            return;
+        }
+
+        tracing::debug!(
+            "Received {} workspace diagnostic reports from '{}'{}",
+            reports.len(),
+            server_name,
+            if done { " (done)" } else { "" }
+        );
+        for report in reports {
+            self.handle_lsp_pulled_diagnostics(
+                report.uri,
+                server_name.clone(),
+                report.result_id,
+                report.diagnostics,
+                report.unchanged,
+            );
+        }
+    }
+
+    /// Show the diagnostics already known for a freshly opened file. A
+    /// workspace pull may have reported on it while it was closed; its next
+    /// document pull can then answer "unchanged", which re-applies nothing.
+    pub(super) fn apply_stored_diagnostics(&mut self, buffer_id: crate::model::event::BufferId) {
+        let window = self.active_window();
+        let Some(uri) = window
+            .buffer_metadata
+            .get(&buffer_id)
+            .and_then(|meta| meta.file_uri())
+            .map(|uri| uri.as_str().to_string())
+        else {
+            return;
+        };
+        if !window.stored_push_diagnostics.contains_key(&uri)
+            && !window.stored_pull_diagnostics.contains_key(&uri)
+        {
+            return;
+        }
+        // The overlay cache is keyed by path and may remember what the file
+        // showed before it was last closed.
+        if let Some(path) = window
+            .buffers
+            .get(&buffer_id)
+            .and_then(|state| state.buffer.file_path())
+        {
+            crate::services::lsp::diagnostics::invalidate_cache_for_file(&path.to_string_lossy());
+        }
+        self.merge_and_apply_diagnostics(&uri);
+    }
+}
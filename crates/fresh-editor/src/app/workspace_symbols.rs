@@ -0,0 +1,171 @@
+//! Workspace symbol search (`workspace/symbol`) behind Quick Open's `@`
+//! provider (see [`crate::input::quick_open::WorkspaceSymbolProvider`]).
+//!
+//! Each new query is sent to every running server that supports workspace
+//! symbols. Replies are merged into the provider as they arrive and the
+//! prompt is refreshed, so results from fast servers show up without waiting
+//! for slow ones.
+
+use super::Editor;
+use crate::input::quick_open::{WorkspaceSymbolEntry, WorkspaceSymbolProvider};
+use crate::types::LspFeature;
+use crate::view::prompt::PromptType;
+
+impl Editor {
+    /// Send `workspace/symbol` for `query` to every server that supports it,
+    /// replacing any search still in flight.
+    pub(super) fn request_workspace_symbols(&mut self, query: &str) {
+        let query = query.trim().to_string();
+        let mut request_id = self.active_window().next_lsp_request_id;
+        let mut sent = Vec::new();
+        if let Some(lsp) = self.lsp() {
+            for sh in lsp.all_handles_for_feature(LspFeature::WorkspaceSymbols) {
+                match sh.handle.workspace_symbols(request_id, query.clone()) {
+                    Ok(()) => {
+                        sent.push(request_id);
+                        request_id += 1;
+                    }
+                    Err(e) => {
+                        tracing::debug!(
+                            "Failed to request workspace symbols from {}: {}",
+                            sh.name,
+                            e
+                        );
+                    }
+                }
+            }
+        }
+
+        let win = self.active_window_mut();
+        win.next_lsp_request_id = request_id;
+        // Replies to an earlier query are no longer wanted.
+        win.pending_workspace_symbol_requests.clear();
+        for id in &sent {
+            win.pending_workspace_symbol_requests
+                .insert(*id, query.clone());
+        }
+        if let Some(provider) = self.quick_open_registry.workspace_symbols() {
+            provider.begin_search(&query, sent.len());
+        }
+    }
+
+    /// Handle one server's `workspace/symbol` reply.
+    pub(super) fn handle_lsp_workspace_symbols(
+        &mut self,
+        request_id: u64,
+        symbols: lsp_types::WorkspaceSymbolResponse,
+    ) {
+        let Some(query) = self
+            .active_window_mut()
+            .pending_workspace_symbol_requests
+            .remove(&request_id)
+        else {
+            tracing::debug!(
+                "Ignoring workspace symbols response without pending request (request_id={})",
+                request_id
+            );
+            return;
+        };
+
+        let entries = self.workspace_symbol_entries(symbols);
+        let accepted = self
+            .quick_open_registry
+            .workspace_symbols()
+            .is_some_and(|provider| provider.add_results(&query, entries));
+        if accepted {
+            self.refresh_workspace_symbol_suggestions();
+        }
+    }
+
+    /// Resolve a reply's symbols to openable locations. Symbols in documents
+    /// without a file path (e.g. `jdt://` class files) are dropped.
+    fn workspace_symbol_entries(
+        &self,
+        symbols: lsp_types::WorkspaceSymbolResponse,
+    ) -> Vec<WorkspaceSymbolEntry> {
+        use lsp_types::{OneOf, WorkspaceSymbolResponse};
+
+        let symbols: Vec<_> = match symbols {
+            WorkspaceSymbolResponse::Flat(symbols) => symbols
+                .into_iter()
+                .map(|s| {
+                    (
+                        s.name,
+                        s.container_name,
+                        s.location.uri,
+                        s.location.range.start,
+                    )
+                })
+                .collect(),
+            WorkspaceSymbolResponse::Nested(symbols) => symbols
+                .into_iter()
+                .map(|s| {
+                    let (uri, start) = match s.location {
+                        OneOf::Left(location) => (location.uri, location.range.start),
+                        // Only a document: the symbol's range needs a
+                        // `workspaceSymbol/resolve`, so land on its first line.
+                        OneOf::Right(location) => (location.uri, lsp_types::Position::default()),
+                    };
+                    (s.name, s.container_name, uri, start)
+                })
+                .collect(),
+        };
+
+        let translation = self.authority().path_translation.as_ref();
+        let cwd = self.working_dir();
+        symbols
+            .into_iter()
+            .filter_map(|(name, container, uri, start)| {
+                let path = crate::app::types::LspUri::from_wire(uri).to_host_path(translation)?;
+                let path = path
+                    .strip_prefix(cwd)
+                    .map(|p| p.to_path_buf())
+                    .unwrap_or(path);
+                Some(WorkspaceSymbolEntry {
+                    name,
+                    container: container.filter(|c| !c.is_empty()),
+                    path: path.display().to_string(),
+                    line: start.line as usize + 1,
+                    column: start.character as usize + 1,
+                })
+            })
+            .collect()
+    }
+
+    /// Re-rank the open `@` Quick Open list after new symbols arrived,
+    /// keeping the selected symbol selected when it is still listed.
+    fn refresh_workspace_symbol_suggestions(&mut self) {
+        let Some(prompt) = self
+            .active_window()
+            .prompt
+            .as_ref()
+            .filter(|p| p.prompt_type == PromptType::QuickOpen)
+        else {
+            return;
+        };
+        let shows_symbols = self
+            .quick_open_registry
+            .get_provider_for_input(&prompt.input)
+            .is_some_and(|(provider, _)| provider.as_any().is::<WorkspaceSymbolProvider>());
+        if !shows_symbols {
+            return;
+        }
+        let input = prompt.input.clone();
+        let selected = prompt
+            .selected_suggestion
+            .and_then(|i| prompt.suggestions.get(i))
+            .and_then(|s| s.value.clone());
+
+        self.update_quick_open_suggestions(&input);
+
+        if let (Some(value), Some(prompt)) = (selected, self.active_window_mut().prompt.as_mut()) {
+            if let Some(index) = prompt
+                .suggestions
+                .iter()
+                .position(|s| s.value.as_deref() == Some(value.as_str()))
+            {
+                prompt.selected_suggestion = Some(index);
+            }
+        }
+    }
+}
@@ -1,4 +1,7 @@
-use crate::types::{context_keys, LspFeature, LspLanguageConfig, LspServerConfig, ProcessLimits};
+use crate::types::{
+    context_keys, DebugAdapterConfig, DebugConfiguration, LspFeature, LspLanguageConfig,
+    LspServerConfig, ProcessLimits, TaskConfig,
+};
 
 use rust_i18n::t;
 use schemars::JsonSchema;
@@ -542,6 +545,19 @@ pub struct Config {
     #[serde(default)]
     pub universal_lsp: HashMap<String, LspLanguageConfig>,
 
+    /// Debug adapters (Debug Adapter Protocol servers) by name.
+    /// Debug configurations refer to these by key.
+    #[serde(default)]
+    pub debug_adapters: HashMap<String, DebugAdapterConfig>,
+
+    /// Launch/attach configurations offered by "Debug: Start".
+    #[serde(default)]
+    pub debug_configurations: Vec<DebugConfiguration>,
+
+    /// Tasks offered by "Run Task" (builds, tests, linters).
+    #[serde(default)]
+    pub tasks: Vec<TaskConfig>,
+
     /// Warning notification settings
     #[serde(default)]
     pub warnings: WarningsConfig,
@@ -1280,6 +1296,41 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "Display"))]
     pub show_horizontal_scrollbar: bool,
 
+    /// Whether a minimap — a zoomed-out overview of the buffer — is shown
+    /// next to the vertical scrollbar in each split pane.
+    /// Click or drag in the minimap to scroll. Very large files are sampled
+    /// rather than drawn line by line.
+    /// Can be toggled at runtime via command palette or the View menu.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Display"))]
+    pub show_minimap: bool,
+
+    /// Width of the minimap in columns. Each column shows two characters of
+    /// text. The minimap is hidden in panes too narrow to fit it.
+    /// Default: 12
+    #[serde(default = "default_minimap_width")]
+    #[schemars(extend("x-section" = "Display"))]
+    pub minimap_width: u16,
+
+    /// Whether the lines that open the scopes enclosing the top of the view
+    /// (functions, impls, classes, ...) stay pinned to the top of each split
+    /// pane. Scopes come from tree-sitter where the language has a grammar,
+    /// otherwise from the language server's document symbols or from
+    /// indentation. Click a pinned line to jump to it.
+    /// Can be toggled at runtime via command palette or the View menu.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Display"))]
+    pub sticky_scroll: bool,
+
+    /// Most scope lines pinned by sticky scroll; the outermost scopes are
+    /// kept when more are open.
+    /// Default: 5
+    #[serde(default = "default_sticky_scroll_max_lines")]
+    #[schemars(extend("x-section" = "Display"))]
+    pub sticky_scroll_max_lines: u16,
+
     /// Show tilde (~) markers on lines after the end of the file.
     /// These vim-style markers indicate lines that are not part of the file content.
     /// Default: true
@@ -1524,6 +1575,30 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "Editing"))]
     pub auto_read_only: bool,
 
+    /// Underline URLs and `path:line:col` references in buffers, along with
+    /// the links the language server reports, and open them with Ctrl+Click.
+    /// Default: true
+    #[serde(default = "default_true")]
+    #[schemars(extend("x-section" = "Editing"))]
+    pub document_links: bool,
+
+    // ===== Spell Check =====
+    /// Underline misspelled words in comments and strings of code files,
+    /// and anywhere in Markdown and plain-text files. Needs a Hunspell
+    /// dictionary (`<language>.dic` and `<language>.aff`) in the
+    /// `dictionaries` folder of the config directory.
+    /// Default: true
+    #[serde(default = "default_true")]
+    #[schemars(extend("x-section" = "Spell Check"))]
+    pub spell_check: bool,
+
+    /// Name of the Hunspell dictionary to check against, e.g. "en_US" for
+    /// `dictionaries/en_US.dic` and `dictionaries/en_US.aff`.
+    /// Default: "en_US"
+    #[serde(default = "default_spell_check_language")]
+    #[schemars(extend("x-section" = "Spell Check"))]
+    pub spell_check_language: String,
+
     // ===== Bracket Matching =====
     /// Highlight matching bracket pairs when cursor is on a bracket.
     /// Default: true
@@ -1729,9 +1804,20 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "Recovery"))]
     pub auto_recovery_save_interval_secs: u32,
 
+    /// Whether to keep each file's undo history across editor restarts.
+    /// The history is written next to the recovery files when a file is
+    /// closed (or the editor exits) and restored when the file is opened
+    /// again, as long as its content hasn't changed in the meantime.
+    /// Large files are never persisted.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Recovery"))]
+    pub persistent_undo: bool,
+
     /// Poll interval in milliseconds for auto-reverting open buffers.
-    /// When auto-revert is enabled, file modification times are checked at this interval.
-    /// Lower values detect external changes faster but use more CPU.
+    /// Open files are watched and revert as soon as they change on disk. Files that
+    /// can't be watched (remote sessions, network filesystems, OS watch limit reached)
+    /// have their modification times checked at this interval instead.
     /// Default: 2000ms (2 seconds)
     #[serde(default = "default_auto_revert_poll_interval")]
     #[schemars(extend("x-section" = "Recovery"))]
@@ -1811,12 +1897,30 @@ pub struct EditorConfig {
     pub read_concurrency: usize,
 
     /// Poll interval in milliseconds for refreshing expanded directories in the file explorer.
-    /// Directory modification times are checked at this interval to detect new/deleted files.
-    /// Lower values detect changes faster but use more CPU.
+    /// Expanded directories are watched and refresh as soon as files are added or removed.
+    /// Directories that can't be watched have their modification times checked at this
+    /// interval instead.
     /// Default: 3000ms (3 seconds)
     #[serde(default = "default_file_tree_poll_interval")]
     #[schemars(extend("x-section" = "Performance"))]
     pub file_tree_poll_interval_ms: u64,
+
+    /// Longest a plugin may run without yielding (one hook handler, command,
+    /// or stretch of code between `await`s), in milliseconds. A plugin that
+    /// exceeds it is interrupted and disabled, with a warning in the log.
+    /// 0 disables the limit.
+    /// Default: 5000ms (5 seconds)
+    #[serde(default = "default_plugin_execution_timeout")]
+    #[schemars(extend("x-section" = "Performance"))]
+    pub plugin_execution_timeout_ms: u64,
+
+    /// Memory cap, in megabytes, for the JavaScript heap all plugins share.
+    /// The plugin whose allocation fails against it is disabled, with a
+    /// warning in the log. 0 leaves the heap unlimited.
+    /// Default: 512
+    #[serde(default = "default_plugin_memory_limit")]
+    #[schemars(extend("x-section" = "Performance"))]
+    pub plugin_memory_limit_mb: u64,
 }
 
 fn default_tab_size() -> usize {
@@ -1867,6 +1971,14 @@ fn default_false() -> bool {
     false
 }
 
+fn default_minimap_width() -> u16 {
+    12
+}
+
+fn default_sticky_scroll_max_lines() -> u16 {
+    5
+}
+
 fn default_quick_suggestions_delay() -> u64 {
     150 // 150ms — fast enough to feel responsive, slow enough to not interrupt typing
 }
@@ -1883,6 +1995,10 @@ fn default_estimated_line_length() -> usize {
     80
 }
 
+fn default_clipboard_history_size() -> usize {
+    30
+}
+
 fn default_auto_save_interval() -> u32 {
     30 // 30 seconds between persistent auto-saves
 }
@@ -1899,6 +2015,10 @@ pub fn default_ephemeral_file_patterns() -> Vec<String> {
     vec!["**/.git/**".to_string()]
 }
 
+fn default_spell_check_language() -> String {
+    "en_US".to_string()
+}
+
 fn default_highlight_context_bytes() -> usize {
     10_000 // 10KB context for accurate syntax highlighting
 }
@@ -1923,6 +2043,14 @@ fn default_file_tree_poll_interval() -> u64 {
     3000 // 3 seconds between directory mtime checks
 }
 
+fn default_plugin_execution_timeout() -> u64 {
+    5000
+}
+
+fn default_plugin_memory_limit() -> u64 {
+    512
+}
+
 impl Default for EditorConfig {
     fn default() -> Self {
         Self {
@@ -1962,6 +2090,7 @@ impl Default for EditorConfig {
             auto_create_empty_buffer_on_last_buffer_close: true,
             recovery_enabled: true,
             auto_recovery_save_interval_secs: default_auto_recovery_save_interval(),
+            persistent_undo: false,
             highlight_context_bytes: default_highlight_context_bytes(),
             mouse_hover_enabled: default_mouse_hover_enabled(),
             mouse_hover_delay_ms: default_mouse_hover_delay(),
@@ -1969,10 +2098,15 @@ impl Default for EditorConfig {
             auto_revert_poll_interval_ms: default_auto_revert_poll_interval(),
             read_concurrency: default_read_concurrency(),
             file_tree_poll_interval_ms: default_file_tree_poll_interval(),
+            plugin_execution_timeout_ms: default_plugin_execution_timeout(),
+            plugin_memory_limit_mb: default_plugin_memory_limit(),
             default_line_ending: LineEndingOption::default(),
             trim_trailing_whitespace_on_save: false,
             ensure_final_newline_on_save: false,
             auto_read_only: true,
+            document_links: true,
+            spell_check: true,
+            spell_check_language: default_spell_check_language(),
             highlight_matching_brackets: true,
             rainbow_brackets: true,
             cursor_style: CursorStyle::default(),
@@ -1994,6 +2128,10 @@ impl Default for EditorConfig {
             show_prompt_line: false,
             show_vertical_scrollbar: true,
             show_horizontal_scrollbar: false,
+            show_minimap: false,
+            minimap_width: default_minimap_width(),
+            sticky_scroll: false,
+            sticky_scroll_max_lines: default_sticky_scroll_max_lines(),
             show_tilde: true,
             nerd_font_icons: false,
             use_terminal_bg: false,
@@ -2366,6 +2504,17 @@ pub struct ClipboardConfig {
     /// Disable this if you don't have a display server or it causes issues
     #[serde(default = "default_true")]
     pub use_system_clipboard: bool,
+
+    /// Number of recent copies and cuts kept in the clipboard history
+    /// (default: 30). Paste from History and Yank Pop pick from this list.
+    /// Set to 0 to keep only the current clipboard.
+    #[serde(default = "default_clipboard_history_size")]
+    pub history_size: usize,
+
+    /// Save the clipboard history in the workspace session file so it
+    /// survives restarts (default: false)
+    #[serde(default)]
+    pub persist_history: bool,
 }
 
 impl Default for ClipboardConfig {
@@ -2373,6 +2522,8 @@ impl Default for ClipboardConfig {
         Self {
             use_osc52: true,
             use_system_clipboard: true,
+            history_size: default_clipboard_history_size(),
+            persist_history: false,
         }
     }
 }
@@ -3186,6 +3337,9 @@ impl Default for Config {
             lsp_enabled: true,
             lsp: Self::default_lsp_config(),
             universal_lsp: Self::default_universal_lsp_config(),
+            debug_adapters: HashMap::new(),
+            debug_configurations: Vec::new(),
+            tasks: Vec::new(),
             warnings: WarningsConfig::default(),
             plugins: HashMap::new(),
             packages: PackagesConfig::default(),
@@ -3477,6 +3631,20 @@ impl MenuConfig {
                         when: None,
                         checkbox: Some(context_keys::HORIZONTAL_SCROLLBAR.to_string()),
                     },
+                    MenuItem::Action {
+                        label: t!("menu.view.minimap").to_string(),
+                        action: "toggle_minimap".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: Some(context_keys::MINIMAP.to_string()),
+                    },
+                    MenuItem::Action {
+                        label: t!("menu.view.sticky_scroll").to_string(),
+                        action: "toggle_sticky_scroll".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: Some(context_keys::STICKY_SCROLL.to_string()),
+                    },
                     MenuItem::Separator { separator: true },
                     MenuItem::Action {
                         label: t!("menu.view.set_background").to_string(),
@@ -3706,6 +3874,13 @@ impl MenuConfig {
                         when: None,
                         checkbox: None,
                     },
+                    MenuItem::Action {
+                        label: t!("menu.selection.shrink_selection").to_string(),
+                        action: "shrink_selection".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: None,
+                    },
                     MenuItem::Separator { separator: true },
                     MenuItem::Action {
                         label: t!("menu.selection.add_cursor_above").to_string(),
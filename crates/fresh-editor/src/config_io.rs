@@ -1183,6 +1183,16 @@ impl DirectoryContext {
         self.config_dir.join("grammars")
     }
 
+    /// Get the spell-check dictionaries directory path
+    pub fn dictionaries_dir(&self) -> std::path::PathBuf {
+        self.config_dir.join("dictionaries")
+    }
+
+    /// Get the user snippets directory path
+    pub fn snippets_dir(&self) -> std::path::PathBuf {
+        self.config_dir.join("snippets")
+    }
+
     /// Get the plugins directory path
     pub fn plugins_dir(&self) -> std::path::PathBuf {
         self.config_dir.join("plugins")
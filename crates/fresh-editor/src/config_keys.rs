@@ -91,6 +91,8 @@ setting_keys! {
     EDITOR_SHOW_PROMPT_LINE: bool = "/editor/show_prompt_line";
     EDITOR_SHOW_VERTICAL_SCROLLBAR: bool = "/editor/show_vertical_scrollbar";
     EDITOR_SHOW_HORIZONTAL_SCROLLBAR: bool = "/editor/show_horizontal_scrollbar";
+    EDITOR_SHOW_MINIMAP: bool = "/editor/show_minimap";
+    EDITOR_STICKY_SCROLL: bool = "/editor/sticky_scroll";
     FILE_EXPLORER_SHOW_HIDDEN: bool = "/file_explorer/show_hidden";
     FILE_EXPLORER_SHOW_GITIGNORED: bool = "/file_explorer/show_gitignored";
     FILE_EXPLORER_SIDE: crate::config::FileExplorerSide = "/file_explorer/side";
@@ -2,6 +2,7 @@
 
 use crate::input::keybindings::Action;
 use crate::input::line_move::{move_lines, LineMoveDirection};
+use crate::input::selection_range::expand_range;
 use crate::model::buffer::{Buffer, LineEnding};
 use crate::model::buffer_position::{byte_to_2d, pos_2d_to_byte};
 use crate::model::cursor::{Cursor, Cursors, Position2D, SelectionMode};
@@ -2449,49 +2450,47 @@ fn select_line(
     }
 }
 
-/// `Action::ExpandSelection` — grow each cursor's selection by a word. With an
-/// existing selection, extend one word to the right; otherwise select from the
-/// cursor to the end of the current (or next) word.
-fn expand_selection(state: &mut EditorState, cursors: &Cursors, events: &mut Vec<Event>) {
-    for (cursor_id, cursor) in cursors.iter() {
-        if let Some(anchor) = cursor.anchor {
-            // Already selecting — expand by one word to the right.
-            let next_word_start = find_word_start_right(&state.buffer, cursor.position);
-            let new_end = find_word_end(&state.buffer, next_word_start);
-            add_move_cursor_event(
-                events,
-                cursor_id,
-                cursor.position,
-                new_end,
-                cursor.anchor,
-                Some(anchor),
-                cursor.sticky_column,
-            );
-        } else {
-            // No selection — select from the cursor to the end of the word.
-            let word_start = find_word_start(&state.buffer, cursor.position);
-            let word_end = find_word_end(&state.buffer, cursor.position);
-
-            // On a non-word char, or at a word end, jump to the next word's end.
-            let (final_start, final_end) = if word_start == word_end || cursor.position == word_end
-            {
-                let next_start = find_word_start_right(&state.buffer, cursor.position);
-                let next_end = find_word_end(&state.buffer, next_start);
-                (cursor.position, next_end)
-            } else {
-                (cursor.position, word_end)
-            };
+/// `Action::ExpandSelection` — grow each cursor's selection to the smallest
+/// syntax-aware range strictly containing it (see
+/// [`selection_range`](super::selection_range)), remembering the old
+/// selection for `ShrinkSelection`.
+fn expand_selection(state: &mut EditorState, cursors: &mut Cursors, events: &mut Vec<Event>) {
+    let current: Vec<(CursorId, Cursor)> = cursors.iter().map(|(id, c)| (id, *c)).collect();
+    for (cursor_id, cursor) in current {
+        let (start, end) = (cursor.selection_start(), cursor.selection_end());
+        let Some(range) = expand_range(state, start, end) else {
+            continue;
+        };
+        cursors.push_expansion(cursor_id, cursor, range.clone());
+        add_move_cursor_event(
+            events,
+            cursor_id,
+            cursor.position,
+            range.end,
+            cursor.anchor,
+            Some(range.start),
+            cursor.sticky_column,
+        );
+    }
+}
 
-            add_move_cursor_event(
-                events,
-                cursor_id,
-                cursor.position,
-                final_end,
-                cursor.anchor,
-                Some(final_start),
-                cursor.sticky_column,
-            );
-        }
+/// `Action::ShrinkSelection` — undo each cursor's last `ExpandSelection`,
+/// as long as its selection has not changed since.
+fn shrink_selection(cursors: &mut Cursors, events: &mut Vec<Event>) {
+    let current: Vec<(CursorId, Cursor)> = cursors.iter().map(|(id, c)| (id, *c)).collect();
+    for (cursor_id, cursor) in current {
+        let Some(previous) = cursors.pop_expansion(cursor_id) else {
+            continue;
+        };
+        add_move_cursor_event(
+            events,
+            cursor_id,
+            cursor.position,
+            previous.position,
+            cursor.anchor,
+            previous.anchor,
+            cursor.sticky_column,
+        );
     }
 }
 
@@ -3317,6 +3316,8 @@ pub fn action_to_events(
         | Action::CopyRelativeFilePath
         | Action::Cut
         | Action::Paste
+        | Action::PasteFromHistory
+        | Action::YankPop
         | Action::YankWordForward
         | Action::YankWordBackward
         | Action::YankToLineEnd
@@ -3329,6 +3330,7 @@ pub fn action_to_events(
         | Action::CommandPalette
         | Action::QuickOpen
         | Action::QuickOpenBuffers
+        | Action::QuickOpenSymbols
         | Action::QuickOpenFiles
         | Action::OpenLiveGrep
         | Action::ResumeLiveGrep
@@ -3342,17 +3344,23 @@ pub fn action_to_events(
         | Action::ToggleReadOnly
         | Action::TogglePageView
         | Action::SetPageWidth
+        | Action::ToggleHexView
         | Action::IncreaseSplitSize
         | Action::DecreaseSplitSize
         | Action::ToggleMaximizeSplit
         | Action::Undo
         | Action::Redo
+        | Action::ShowUndoTree
+        | Action::UndoTreeJump
+        | Action::UndoTreePreview
         | Action::GoToMatchingBracket
         | Action::JumpToNextError
         | Action::JumpToPreviousError
         | Action::ShowKeyboardShortcuts
         | Action::ShowWarnings
         | Action::ShowStatusLog
+        | Action::ShowPluginProfiler
+        | Action::ShowPluginPermissions
         | Action::ShowLspStatus
         | Action::ShowRemoteIndicatorMenu
         | Action::ShowReadOnlyMenu
@@ -3430,6 +3438,8 @@ pub fn action_to_events(
         | Action::TogglePromptLine
         | Action::ToggleVerticalScrollbar
         | Action::ToggleHorizontalScrollbar
+        | Action::ToggleMinimap
+        | Action::ToggleStickyScroll
         | Action::FocusFileExplorer
         | Action::FocusEditor
         | Action::ToggleDockFocus
@@ -3472,8 +3482,21 @@ pub fn action_to_events(
         | Action::LspRestart
         | Action::LspStop
         | Action::LspToggleForBuffer
+        | Action::ListDocumentLinks
         | Action::ToggleInlayHints
         | Action::ToggleMouseHover
+        | Action::DebugStart
+        | Action::DebugStop
+        | Action::DebugContinue
+        | Action::DebugStepOver
+        | Action::DebugStepInto
+        | Action::DebugStepOut
+        | Action::DebugPause
+        | Action::DebugToggleBreakpoint
+        | Action::DebugShowPanel
+        | Action::DebugSelectFrame
+        | Action::RunTask
+        | Action::RerunLastTask
         | Action::ToggleLineNumbers
         | Action::ToggleLineNumbersCurrentBuffer
         | Action::ToggleLineWrapCurrentBuffer
@@ -3552,6 +3575,7 @@ pub fn action_to_events(
         | Action::ToggleWhitespaceIndicators
         | Action::ToggleDebugHighlights
         | Action::ResetBufferSettings
+        | Action::ShowBufferSettings
         | Action::ShellCommand
         | Action::ShellCommandReplace
         | Action::CalibrateInput
@@ -3595,6 +3619,10 @@ pub fn action_to_events(
         Action::ExpandSelection => {
             expand_selection(state, cursors, &mut events);
         }
+
+        Action::ShrinkSelection => {
+            shrink_selection(cursors, &mut events);
+        }
     }
 
     Some(events)
@@ -335,6 +335,13 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.quick_open_symbols",
+        desc_key: "cmd.quick_open_symbols_desc",
+        action: || Action::QuickOpenSymbols,
+        contexts: &[],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.quick_open_files",
         desc_key: "cmd.quick_open_files_desc",
@@ -357,6 +364,13 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.show_undo_tree",
+        desc_key: "cmd.show_undo_tree_desc",
+        action: || Action::ShowUndoTree,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.copy",
         desc_key: "cmd.copy_desc",
@@ -399,6 +413,20 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.paste_from_history",
+        desc_key: "cmd.paste_from_history_desc",
+        action: || Action::PasteFromHistory,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.yank_pop",
+        desc_key: "cmd.yank_pop_desc",
+        action: || Action::YankPop,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.delete_line",
         desc_key: "cmd.delete_line_desc",
@@ -526,6 +554,13 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.shrink_selection",
+        desc_key: "cmd.shrink_selection_desc",
+        action: || Action::ShrinkSelection,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     // Multi-cursor
     CommandDef {
         name_key: "cmd.add_cursor_above",
@@ -852,6 +887,13 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.show_buffer_settings",
+        desc_key: "cmd.show_buffer_settings_desc",
+        action: || Action::ShowBufferSettings,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.scroll_up",
         desc_key: "cmd.scroll_up_desc",
@@ -944,6 +986,20 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal, FileExplorer, Terminal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.toggle_minimap",
+        desc_key: "cmd.toggle_minimap_desc",
+        action: || Action::ToggleMinimap,
+        contexts: &[Normal, FileExplorer, Terminal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.toggle_sticky_scroll",
+        desc_key: "cmd.toggle_sticky_scroll_desc",
+        action: || Action::ToggleStickyScroll,
+        contexts: &[Normal, FileExplorer, Terminal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.focus_file_explorer",
         desc_key: "cmd.focus_file_explorer_desc",
@@ -1050,6 +1106,13 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.toggle_hex_view",
+        desc_key: "cmd.toggle_hex_view_desc",
+        action: || Action::ToggleHexView,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.toggle_read_only",
         desc_key: "cmd.toggle_read_only_desc",
@@ -1227,6 +1290,90 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.list_document_links",
+        desc_key: "cmd.list_document_links_desc",
+        action: || Action::ListDocumentLinks,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.debug_start",
+        desc_key: "cmd.debug_start_desc",
+        action: || Action::DebugStart,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.debug_stop",
+        desc_key: "cmd.debug_stop_desc",
+        action: || Action::DebugStop,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.debug_continue",
+        desc_key: "cmd.debug_continue_desc",
+        action: || Action::DebugContinue,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.debug_step_over",
+        desc_key: "cmd.debug_step_over_desc",
+        action: || Action::DebugStepOver,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.debug_step_into",
+        desc_key: "cmd.debug_step_into_desc",
+        action: || Action::DebugStepInto,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.debug_step_out",
+        desc_key: "cmd.debug_step_out_desc",
+        action: || Action::DebugStepOut,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.debug_pause",
+        desc_key: "cmd.debug_pause_desc",
+        action: || Action::DebugPause,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.debug_toggle_breakpoint",
+        desc_key: "cmd.debug_toggle_breakpoint_desc",
+        action: || Action::DebugToggleBreakpoint,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.debug_show_panel",
+        desc_key: "cmd.debug_show_panel_desc",
+        action: || Action::DebugShowPanel,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.run_task",
+        desc_key: "cmd.run_task_desc",
+        action: || Action::RunTask,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.rerun_last_task",
+        desc_key: "cmd.rerun_last_task_desc",
+        action: || Action::RerunLastTask,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.toggle_mouse_hover",
         desc_key: "cmd.toggle_mouse_hover_desc",
@@ -1386,6 +1533,20 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.plugin_profiler",
+        desc_key: "cmd.plugin_profiler_desc",
+        action: || Action::ShowPluginProfiler,
+        contexts: &[],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.plugin_permissions",
+        desc_key: "cmd.plugin_permissions_desc",
+        action: || Action::ShowPluginPermissions,
+        contexts: &[],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.show_lsp_status",
         desc_key: "cmd.show_lsp_status_desc",
@@ -0,0 +1,128 @@
+//! Input routing for the hex view
+//!
+//! Maps keys to hex-view cursor movement and byte editing. Keys the hex
+//! view has no use for (save, undo, the command palette, ...) come back as
+//! `Unhandled` and go through the editor's normal key dispatch.
+
+use crate::view::hex_view::{HexPane, HexViewState};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// Result of routing a key event in the hex view
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HexRoutedEvent {
+    /// Move the cursor
+    Move(HexMove),
+    /// Switch typing between the hex digits and the decoded text
+    SwitchPane,
+    /// Toggle between inserting and overwriting bytes
+    ToggleInsertMode,
+    /// A hex digit typed in the hex pane
+    Nibble(u8),
+    /// A character typed in the text pane
+    Char(char),
+    /// Delete the byte before the cursor
+    Backspace,
+    /// Delete the byte under the cursor
+    Delete,
+    /// Key swallowed without effect (e.g. a non-hex letter in the hex pane)
+    Ignored,
+    /// Event not handled by the hex view
+    Unhandled,
+}
+
+/// Cursor movement in the hex view
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HexMove {
+    Left,
+    Right,
+    Up,
+    Down,
+    PageUp,
+    PageDown,
+    RowStart,
+    RowEnd,
+    Top,
+    Bottom,
+}
+
+/// Routes key events for a buffer shown in hex view
+pub struct HexInputRouter;
+
+impl HexInputRouter {
+    /// Route a key event to the appropriate hex-view action.
+    pub fn route_key_event(view_state: &HexViewState, event: &KeyEvent) -> HexRoutedEvent {
+        match (event.modifiers, event.code) {
+            (KeyModifiers::NONE, KeyCode::Left) => HexRoutedEvent::Move(HexMove::Left),
+            (KeyModifiers::NONE, KeyCode::Right) => HexRoutedEvent::Move(HexMove::Right),
+            (KeyModifiers::NONE, KeyCode::Up) => HexRoutedEvent::Move(HexMove::Up),
+            (KeyModifiers::NONE, KeyCode::Down) => HexRoutedEvent::Move(HexMove::Down),
+            (KeyModifiers::NONE, KeyCode::PageUp) => HexRoutedEvent::Move(HexMove::PageUp),
+            (KeyModifiers::NONE, KeyCode::PageDown) => HexRoutedEvent::Move(HexMove::PageDown),
+            (KeyModifiers::NONE, KeyCode::Home) => HexRoutedEvent::Move(HexMove::RowStart),
+            (KeyModifiers::NONE, KeyCode::End) => HexRoutedEvent::Move(HexMove::RowEnd),
+            (KeyModifiers::CONTROL, KeyCode::Home) => HexRoutedEvent::Move(HexMove::Top),
+            (KeyModifiers::CONTROL, KeyCode::End) => HexRoutedEvent::Move(HexMove::Bottom),
+
+            (KeyModifiers::NONE, KeyCode::Tab) | (KeyModifiers::SHIFT, KeyCode::BackTab) => {
+                HexRoutedEvent::SwitchPane
+            }
+            (KeyModifiers::NONE, KeyCode::Insert) => HexRoutedEvent::ToggleInsertMode,
+            (KeyModifiers::NONE, KeyCode::Backspace) => HexRoutedEvent::Backspace,
+            (KeyModifiers::NONE, KeyCode::Delete) => HexRoutedEvent::Delete,
+
+            (KeyModifiers::NONE | KeyModifiers::SHIFT, KeyCode::Char(c)) => {
+                match view_state.pane {
+                    HexPane::Hex => match c.to_digit(16) {
+                        Some(digit) => HexRoutedEvent::Nibble(digit as u8),
+                        // Swallow other characters rather than letting them
+                        // fall through to text insertion.
+                        None => HexRoutedEvent::Ignored,
+                    },
+                    HexPane::Text => HexRoutedEvent::Char(c),
+                }
+            }
+
+            _ => HexRoutedEvent::Unhandled,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn route(pane: HexPane, code: KeyCode) -> HexRoutedEvent {
+        let state = HexViewState {
+            pane,
+            ..Default::default()
+        };
+        HexInputRouter::route_key_event(&state, &KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn typed_characters_depend_on_the_pane() {
+        assert_eq!(
+            route(HexPane::Hex, KeyCode::Char('b')),
+            HexRoutedEvent::Nibble(0xB)
+        );
+        assert_eq!(
+            route(HexPane::Hex, KeyCode::Char('z')),
+            HexRoutedEvent::Ignored
+        );
+        assert_eq!(
+            route(HexPane::Text, KeyCode::Char('b')),
+            HexRoutedEvent::Char('b')
+        );
+    }
+
+    #[test]
+    fn other_keys_fall_through() {
+        let state = HexViewState::default();
+        let save = KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL);
+        assert_eq!(
+            HexInputRouter::route_key_event(&state, &save),
+            HexRoutedEvent::Unhandled
+        );
+        assert_eq!(route(HexPane::Hex, KeyCode::Esc), HexRoutedEvent::Unhandled);
+    }
+}
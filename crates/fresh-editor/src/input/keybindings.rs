@@ -413,6 +413,7 @@ pub enum Action {
     SelectWord,
     SelectLine,
     ExpandSelection,
+    ShrinkSelection,
 
     // Block/rectangular selection (column-wise)
     BlockSelectLeft,
@@ -446,6 +447,11 @@ pub enum Action {
     CopyWithTheme(String),
     Cut,
     Paste,
+    /// Pick an entry from the clipboard history and paste it.
+    PasteFromHistory,
+    /// Replace the text just pasted with the next older clipboard history
+    /// entry (Emacs `yank-pop`).
+    YankPop,
     /// Copy the absolute filesystem path of the active buffer's file to the clipboard.
     CopyFilePath,
     /// Copy the active buffer's file path relative to the workspace root, falling
@@ -534,6 +540,9 @@ pub enum Action {
     // Undo/redo
     Undo,
     Redo,
+    ShowUndoTree,
+    UndoTreeJump,
+    UndoTreePreview,
 
     // View
     ScrollUp,
@@ -542,6 +551,8 @@ pub enum Action {
     ShowKeyboardShortcuts,
     ShowWarnings,
     ShowStatusLog,
+    ShowPluginProfiler,
+    ShowPluginPermissions,
     ShowLspStatus,
     ShowRemoteIndicatorMenu,
     ShowReadOnlyMenu,
@@ -555,6 +566,8 @@ pub enum Action {
     QuickOpen,
     /// Quick Open - buffers (prefix: "#")
     QuickOpenBuffers,
+    /// Quick Open - workspace symbols (prefix: "@")
+    QuickOpenSymbols,
     /// Quick Open - files (empty prefix)
     QuickOpenFiles,
     /// Open Live Grep as a floating overlay (issue #1796).
@@ -579,6 +592,7 @@ pub enum Action {
     ToggleReadOnly,
     TogglePageView,
     SetPageWidth,
+    ToggleHexView,
     InspectThemeAtCursor,
     SelectTheme,
     SelectKeybindingMap,
@@ -686,6 +700,10 @@ pub enum Action {
     // Scrollbar visibility
     ToggleVerticalScrollbar,
     ToggleHorizontalScrollbar,
+    // Minimap visibility
+    ToggleMinimap,
+    // Sticky scroll header
+    ToggleStickyScroll,
     FocusFileExplorer,
     FocusEditor,
     /// Toggle keyboard focus between the editor/explorer area and the
@@ -731,9 +749,26 @@ pub enum Action {
     LspRestart,
     LspStop,
     LspToggleForBuffer,
+    ListDocumentLinks,
     ToggleInlayHints,
     ToggleMouseHover,
 
+    // Debugger (Debug Adapter Protocol)
+    DebugStart,
+    DebugStop,
+    DebugContinue,
+    DebugStepOver,
+    DebugStepInto,
+    DebugStepOut,
+    DebugPause,
+    DebugToggleBreakpoint,
+    DebugShowPanel,
+    DebugSelectFrame,
+
+    // Tasks
+    RunTask,
+    RerunLastTask,
+
     // View toggles
     ToggleLineNumbers,
     /// Toggle line-number visibility for the current buffer only (per-buffer
@@ -778,6 +813,7 @@ pub enum Action {
     ToggleTabIndicators,
     ToggleWhitespaceIndicators,
     ResetBufferSettings,
+    ShowBufferSettings,
     AddRuler,
     RemoveRuler,
 
@@ -1005,6 +1041,7 @@ impl Action {
             "select_word" => SelectWord,
             "select_line" => SelectLine,
             "expand_selection" => ExpandSelection,
+            "shrink_selection" => ShrinkSelection,
 
             "block_select_left" => BlockSelectLeft,
             "block_select_right" => BlockSelectRight,
@@ -1030,6 +1067,8 @@ impl Action {
             "copy" => Copy,
             "cut" => Cut,
             "paste" => Paste,
+            "paste_from_history" => PasteFromHistory,
+            "yank_pop" => YankPop,
             "copy_file_path" => CopyFilePath,
             "copy_relative_file_path" => CopyRelativeFilePath,
 
@@ -1094,6 +1133,9 @@ impl Action {
 
             "undo" => Undo,
             "redo" => Redo,
+            "show_undo_tree" => ShowUndoTree,
+            "undo_tree_jump" => UndoTreeJump,
+            "undo_tree_preview" => UndoTreePreview,
 
             "scroll_up" => ScrollUp,
             "scroll_down" => ScrollDown,
@@ -1101,6 +1143,8 @@ impl Action {
             "keyboard_shortcuts" => ShowKeyboardShortcuts,
             "show_warnings" => ShowWarnings,
             "show_status_log" => ShowStatusLog,
+            "show_plugin_profiler" => ShowPluginProfiler,
+            "show_plugin_permissions" => ShowPluginPermissions,
             "show_lsp_status" => ShowLspStatus,
             "show_remote_indicator_menu" => ShowRemoteIndicatorMenu,
             "show_read_only_menu" => ShowReadOnlyMenu,
@@ -1110,6 +1154,7 @@ impl Action {
             "command_palette" => CommandPalette,
             "quick_open" => QuickOpen,
             "quick_open_buffers" => QuickOpenBuffers,
+            "quick_open_symbols" => QuickOpenSymbols,
             "quick_open_files" => QuickOpenFiles,
             "open_live_grep" => OpenLiveGrep,
             "resume_live_grep" => ResumeLiveGrep,
@@ -1122,6 +1167,7 @@ impl Action {
             "toggle_read_only" => ToggleReadOnly,
             "toggle_page_view" => TogglePageView,
             "set_page_width" => SetPageWidth,
+            "toggle_hex_view" => ToggleHexView,
 
             "next_buffer" => NextBuffer,
             "prev_buffer" => PrevBuffer,
@@ -1197,6 +1243,8 @@ impl Action {
             "toggle_prompt_line" => TogglePromptLine,
             "toggle_vertical_scrollbar" => ToggleVerticalScrollbar,
             "toggle_horizontal_scrollbar" => ToggleHorizontalScrollbar,
+            "toggle_minimap" => ToggleMinimap,
+            "toggle_sticky_scroll" => ToggleStickyScroll,
             "focus_file_explorer" => FocusFileExplorer,
             "focus_editor" => FocusEditor,
             "toggle_dock_focus" => ToggleDockFocus,
@@ -1238,8 +1286,21 @@ impl Action {
             "lsp_restart" => LspRestart,
             "lsp_stop" => LspStop,
             "lsp_toggle_for_buffer" => LspToggleForBuffer,
+            "list_document_links" => ListDocumentLinks,
             "toggle_inlay_hints" => ToggleInlayHints,
             "toggle_mouse_hover" => ToggleMouseHover,
+            "debug_start" => DebugStart,
+            "debug_stop" => DebugStop,
+            "debug_continue" => DebugContinue,
+            "debug_step_over" => DebugStepOver,
+            "debug_step_into" => DebugStepInto,
+            "debug_step_out" => DebugStepOut,
+            "debug_pause" => DebugPause,
+            "debug_toggle_breakpoint" => DebugToggleBreakpoint,
+            "debug_show_panel" => DebugShowPanel,
+            "debug_select_frame" => DebugSelectFrame,
+            "run_task" => RunTask,
+            "rerun_last_task" => RerunLastTask,
 
             "toggle_line_numbers" => ToggleLineNumbers,
             "toggle_line_numbers_current_buffer" => ToggleLineNumbersCurrentBuffer,
@@ -1270,6 +1331,7 @@ impl Action {
             "toggle_tab_indicators" => ToggleTabIndicators,
             "toggle_whitespace_indicators" => ToggleWhitespaceIndicators,
             "reset_buffer_settings" => ResetBufferSettings,
+            "show_buffer_settings" => ShowBufferSettings,
             "add_ruler" => AddRuler,
             "remove_ruler" => RemoveRuler,
 
@@ -1535,6 +1597,7 @@ impl Action {
                 | Action::SelectWord
                 | Action::SelectLine
                 | Action::ExpandSelection
+                | Action::ShrinkSelection
                 // Block selection
                 | Action::BlockSelectLeft
                 | Action::BlockSelectRight
@@ -1559,6 +1622,8 @@ impl Action {
                 // Clipboard editing (but not Copy)
                 | Action::Cut
                 | Action::Paste
+                | Action::PasteFromHistory
+                | Action::YankPop
                 // Undo/Redo
                 | Action::Undo
                 | Action::Redo
@@ -1588,6 +1653,7 @@ impl Action {
                 | Action::MoveLineDown
                 | Action::Cut
                 | Action::Paste
+                | Action::YankPop
         )
     }
 }
@@ -1945,6 +2011,8 @@ impl KeybindingResolver {
                 | Action::Redo
                 | Action::Cut
                 | Action::Paste
+                | Action::PasteFromHistory
+                | Action::YankPop
                 | Action::DeleteLine
                 | Action::DeleteWordBackward
                 | Action::DeleteWordForward
@@ -1973,6 +2041,7 @@ impl KeybindingResolver {
             Action::CommandPalette
                 | Action::QuickOpen
                 | Action::QuickOpenBuffers
+                | Action::QuickOpenSymbols
                 | Action::QuickOpenFiles
                 | Action::OpenLiveGrep
                 | Action::ResumeLiveGrep
@@ -2692,6 +2761,7 @@ impl KeybindingResolver {
             Action::SelectWord => t!("action.select_word"),
             Action::SelectLine => t!("action.select_line"),
             Action::ExpandSelection => t!("action.expand_selection"),
+            Action::ShrinkSelection => t!("action.shrink_selection"),
             Action::BlockSelectLeft => t!("action.block_select_left"),
             Action::BlockSelectRight => t!("action.block_select_right"),
             Action::BlockSelectUp => t!("action.block_select_up"),
@@ -2716,6 +2786,8 @@ impl KeybindingResolver {
             Action::CopyWithTheme(theme) => t!("action.copy_with_theme", theme = theme),
             Action::Cut => t!("action.cut"),
             Action::Paste => t!("action.paste"),
+            Action::PasteFromHistory => t!("action.paste_from_history"),
+            Action::YankPop => t!("action.yank_pop"),
             Action::CopyFilePath => t!("action.copy_file_path"),
             Action::CopyRelativeFilePath => t!("action.copy_relative_file_path"),
             Action::YankWordForward => t!("action.yank_word_forward"),
@@ -2777,12 +2849,17 @@ impl KeybindingResolver {
             Action::PromptJumpToBookmark => t!("action.prompt_jump_to_bookmark"),
             Action::Undo => t!("action.undo"),
             Action::Redo => t!("action.redo"),
+            Action::ShowUndoTree => t!("action.show_undo_tree"),
+            Action::UndoTreeJump => t!("action.undo_tree_jump"),
+            Action::UndoTreePreview => t!("action.undo_tree_preview"),
             Action::ScrollUp => t!("action.scroll_up"),
             Action::ScrollDown => t!("action.scroll_down"),
             Action::ShowHelp => t!("action.show_help"),
             Action::ShowKeyboardShortcuts => t!("action.show_keyboard_shortcuts"),
             Action::ShowWarnings => t!("action.show_warnings"),
             Action::ShowStatusLog => t!("action.show_status_log"),
+            Action::ShowPluginProfiler => t!("action.show_plugin_profiler"),
+            Action::ShowPluginPermissions => t!("action.show_plugin_permissions"),
             Action::ShowLspStatus => t!("action.show_lsp_status"),
             Action::ShowRemoteIndicatorMenu => t!("action.show_remote_indicator_menu"),
             Action::ShowReadOnlyMenu => t!("action.show_read_only_menu"),
@@ -2792,6 +2869,7 @@ impl KeybindingResolver {
             Action::CommandPalette => t!("action.command_palette"),
             Action::QuickOpen => t!("action.quick_open"),
             Action::QuickOpenBuffers => t!("action.quick_open_buffers"),
+            Action::QuickOpenSymbols => t!("action.quick_open_symbols"),
             Action::QuickOpenFiles => t!("action.quick_open_files"),
             Action::OpenLiveGrep => t!("action.open_live_grep"),
             Action::ResumeLiveGrep => t!("action.resume_live_grep"),
@@ -2805,6 +2883,7 @@ impl KeybindingResolver {
             Action::ToggleReadOnly => t!("action.toggle_read_only"),
             Action::TogglePageView => t!("action.toggle_page_view"),
             Action::SetPageWidth => t!("action.set_page_width"),
+            Action::ToggleHexView => t!("action.toggle_hex_view"),
             Action::NextBuffer => t!("action.next_buffer"),
             Action::PrevBuffer => t!("action.prev_buffer"),
             Action::NavigateBack => t!("action.navigate_back"),
@@ -2874,6 +2953,8 @@ impl KeybindingResolver {
             Action::TogglePromptLine => t!("action.toggle_prompt_line"),
             Action::ToggleVerticalScrollbar => t!("action.toggle_vertical_scrollbar"),
             Action::ToggleHorizontalScrollbar => t!("action.toggle_horizontal_scrollbar"),
+            Action::ToggleMinimap => t!("action.toggle_minimap"),
+            Action::ToggleStickyScroll => t!("action.toggle_sticky_scroll"),
             Action::FocusFileExplorer => t!("action.focus_file_explorer"),
             Action::FocusEditor => t!("action.focus_editor"),
             Action::ToggleDockFocus => t!("action.toggle_dock_focus"),
@@ -2916,8 +2997,21 @@ impl KeybindingResolver {
             Action::LspRestart => t!("action.lsp_restart"),
             Action::LspStop => t!("action.lsp_stop"),
             Action::LspToggleForBuffer => t!("action.lsp_toggle_for_buffer"),
+            Action::ListDocumentLinks => t!("action.list_document_links"),
             Action::ToggleInlayHints => t!("action.toggle_inlay_hints"),
             Action::ToggleMouseHover => t!("action.toggle_mouse_hover"),
+            Action::DebugStart => t!("action.debug_start"),
+            Action::DebugStop => t!("action.debug_stop"),
+            Action::DebugContinue => t!("action.debug_continue"),
+            Action::DebugStepOver => t!("action.debug_step_over"),
+            Action::DebugStepInto => t!("action.debug_step_into"),
+            Action::DebugStepOut => t!("action.debug_step_out"),
+            Action::DebugPause => t!("action.debug_pause"),
+            Action::DebugToggleBreakpoint => t!("action.debug_toggle_breakpoint"),
+            Action::DebugShowPanel => t!("action.debug_show_panel"),
+            Action::DebugSelectFrame => t!("action.debug_select_frame"),
+            Action::RunTask => t!("action.run_task"),
+            Action::RerunLastTask => t!("action.rerun_last_task"),
             Action::ToggleLineNumbers => t!("action.toggle_line_numbers"),
             Action::ToggleLineNumbersCurrentBuffer => {
                 t!("action.toggle_line_numbers_current_buffer")
@@ -2955,6 +3049,7 @@ impl KeybindingResolver {
             Action::ToggleTabIndicators => t!("action.toggle_tab_indicators"),
             Action::ToggleWhitespaceIndicators => t!("action.toggle_whitespace_indicators"),
             Action::ResetBufferSettings => t!("action.reset_buffer_settings"),
+            Action::ShowBufferSettings => t!("action.show_buffer_settings"),
             Action::DumpConfig => t!("action.dump_config"),
             Action::RedrawScreen => t!("action.redraw_screen"),
             Action::Search => t!("action.search"),
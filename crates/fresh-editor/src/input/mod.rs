@@ -27,6 +27,7 @@ pub mod commands;
 pub mod composite_router;
 pub mod fuzzy;
 pub mod handler;
+pub mod hex_router;
 pub mod input_history;
 pub mod key_translator;
 pub mod keybindings;
@@ -35,6 +36,7 @@ pub mod multi_cursor;
 pub mod position_history;
 pub mod quick_open;
 pub mod router;
+pub mod selection_range;
 
 #[cfg(test)]
 pub mod tests_language_features;
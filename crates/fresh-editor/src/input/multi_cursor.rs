@@ -1,5 +1,7 @@
 //! Multi-cursor operations for adding cursors at various positions
 
+use std::ops::Range;
+
 use crate::model::cursor::{Cursor, Cursors};
 use crate::primitives::word_navigation::{find_word_end, find_word_start};
 use crate::state::EditorState;
@@ -301,3 +303,69 @@ pub fn add_cursor_below(state: &mut EditorState, cursors: &Cursors) -> AddCursor
         }
     }
 }
+
+/// Mirror `cursor` into a set of linked ranges (LSP linked editing, e.g. the
+/// name in an HTML opening tag and the one in its closing tag).
+///
+/// Returns the index of the range holding the cursor (and its whole
+/// selection, if any) together with one cursor per other range, placed at
+/// the same offsets from that range's start. `None` when the cursor is not
+/// inside any range. Offsets are clamped to each range's end, although the
+/// protocol guarantees the ranges hold identical text.
+pub fn linked_range_cursors(
+    ranges: &[Range<usize>],
+    cursor: &Cursor,
+) -> Option<(usize, Vec<Cursor>)> {
+    let span = cursor
+        .selection_range()
+        .unwrap_or(cursor.position..cursor.position);
+    let origin = ranges
+        .iter()
+        .position(|r| r.start <= span.start && span.end <= r.end)?;
+    let base = ranges[origin].start;
+    let mirrored = ranges
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| *i != origin)
+        .map(|(_, r)| {
+            let place = |pos: usize| (r.start + (pos - base)).min(r.end);
+            match cursor.anchor {
+                Some(anchor) => Cursor::with_selection(place(anchor), place(cursor.position)),
+                None => Cursor::new(place(cursor.position)),
+            }
+        })
+        .collect();
+    Some((origin, mirrored))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn linked_range_cursors_mirrors_offset_into_other_ranges() {
+        // `<div>x</div>`: names at 1..4 and 8..11.
+        let ranges = [1..4, 8..11];
+        let (origin, mirrored) = linked_range_cursors(&ranges, &Cursor::new(3)).unwrap();
+        assert_eq!(origin, 0);
+        assert_eq!(mirrored.len(), 1);
+        assert_eq!(mirrored[0].position, 10);
+        assert_eq!(mirrored[0].anchor, None);
+
+        // Starting from the closing tag mirrors back into the opening one,
+        // selection included.
+        let (origin, mirrored) =
+            linked_range_cursors(&ranges, &Cursor::with_selection(8, 11)).unwrap();
+        assert_eq!(origin, 1);
+        assert_eq!(mirrored[0].anchor, Some(1));
+        assert_eq!(mirrored[0].position, 4);
+    }
+
+    #[test]
+    fn linked_range_cursors_requires_cursor_inside_a_range() {
+        let ranges = [1..4, 8..11];
+        assert!(linked_range_cursors(&ranges, &Cursor::new(6)).is_none());
+        // A selection spilling out of the range does not count.
+        assert!(linked_range_cursors(&ranges, &Cursor::with_selection(2, 6)).is_none());
+    }
+}
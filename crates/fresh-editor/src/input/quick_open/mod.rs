@@ -4,8 +4,8 @@
 //! Inspired by VSCode's Quick Open (Ctrl+P) which supports:
 //! - Empty prefix: file finder
 //! - `>`: command palette
-//! - `#`: symbol finder (future)
-//! - `@`: go to symbol in file (future)
+//! - `#`: buffer switcher
+//! - `@`: workspace symbols (from language servers)
 //! - `:`: go to line
 //!
 //! Providers are registered with a prefix and handle suggestion generation
@@ -13,7 +13,10 @@
 
 pub mod providers;
 
-pub use providers::{BufferProvider, CommandProvider, FileProvider, GotoLineProvider};
+pub use providers::{
+    BufferProvider, CommandProvider, FileProvider, GotoLineProvider, WorkspaceSymbolEntry,
+    WorkspaceSymbolProvider,
+};
 
 use crate::input::commands::Suggestion;
 use crate::input::keybindings::Action;
@@ -206,6 +209,13 @@ pub trait QuickOpenProvider: Send + Sync {
         context: &QuickOpenContext,
     ) -> QuickOpenResult;
 
+    /// Whether suggestions are `path:line:col` locations that should be
+    /// previewed while navigating. The prompt is then drawn as a floating
+    /// overlay with a preview pane, like Live Grep.
+    fn previews_location(&self) -> bool {
+        false
+    }
+
     /// Downcast support for concrete provider access (e.g., updating cache).
     fn as_any(&self) -> &dyn std::any::Any;
 
@@ -257,6 +267,14 @@ impl QuickOpenRegistry {
         }
     }
 
+    /// The workspace symbol provider, which the editor feeds with language
+    /// server replies.
+    pub fn workspace_symbols(&self) -> Option<&WorkspaceSymbolProvider> {
+        self.providers
+            .values()
+            .find_map(|p| p.as_any().downcast_ref::<WorkspaceSymbolProvider>())
+    }
+
     /// Get the provider for a given input
     ///
     /// Returns (provider, query_without_prefix)
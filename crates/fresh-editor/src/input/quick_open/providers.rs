@@ -5,6 +5,7 @@
 //! - CommandProvider: Command palette (prefix: ">")
 //! - BufferProvider: Switch between open buffers (prefix: "#")
 //! - GotoLineProvider: Go to a specific line (prefix: ":")
+//! - WorkspaceSymbolProvider: Search symbols across the workspace (prefix: "@")
 
 use super::{
     parse_goto_line_input, GotoLineTarget, QuickOpenContext, QuickOpenProvider, QuickOpenResult,
@@ -270,6 +271,181 @@ impl QuickOpenProvider for GotoLineProvider {
     }
 }
 
+// ============================================================================
+// Workspace Symbol Provider (prefix: "@")
+// ============================================================================
+
+/// A symbol found by a language server's `workspace/symbol` search, resolved
+/// to a location the editor can open.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WorkspaceSymbolEntry {
+    pub name: String,
+    /// Name of the enclosing symbol (module, class, …), if the server sent one
+    pub container: Option<String>,
+    /// Path relative to the working directory, or absolute outside of it
+    pub path: String,
+    /// 1-based line
+    pub line: usize,
+    /// 1-based column
+    pub column: usize,
+}
+
+/// State of the current workspace symbol search.
+#[derive(Default)]
+struct SymbolSearch {
+    /// The query the results answer, `None` until a search is sent.
+    query: Option<String>,
+    /// Merged results from every server that has answered so far.
+    symbols: Vec<WorkspaceSymbolEntry>,
+    /// Servers the search was sent to.
+    servers: usize,
+    /// Servers that have not answered yet.
+    pending: usize,
+}
+
+/// Provider for searching symbols across the workspace.
+///
+/// Language servers do the searching: when the query changes the editor sends
+/// `workspace/symbol` to every running server that supports it (see
+/// [`Self::begin_search`]) and feeds each reply back through
+/// [`Self::add_results`] as it arrives, refreshing the prompt so results
+/// stream in. The provider merges the replies and ranks them against the
+/// query with the same fuzzy matcher as the other providers.
+#[derive(Default)]
+pub struct WorkspaceSymbolProvider {
+    search: std::sync::Mutex<SymbolSearch>,
+}
+
+impl WorkspaceSymbolProvider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether `query` needs a new search, i.e. it is not empty and differs
+    /// from the query of the current results.
+    pub fn needs_search(&self, query: &str) -> bool {
+        let query = query.trim();
+        !query.is_empty() && self.search.lock().unwrap().query.as_deref() != Some(query)
+    }
+
+    /// Start a search for `query` that was sent to `servers` servers,
+    /// dropping the results of the previous one.
+    pub fn begin_search(&self, query: &str, servers: usize) {
+        *self.search.lock().unwrap() = SymbolSearch {
+            query: Some(query.trim().to_string()),
+            symbols: Vec::new(),
+            servers,
+            pending: servers,
+        };
+    }
+
+    /// Merge one server's reply to the search for `query`. Replies to an
+    /// outdated query are ignored; returns whether the reply was used.
+    pub fn add_results(&self, query: &str, symbols: Vec<WorkspaceSymbolEntry>) -> bool {
+        let mut search = self.search.lock().unwrap();
+        if search.query.as_deref() != Some(query.trim()) {
+            return false;
+        }
+        search.pending = search.pending.saturating_sub(1);
+        for symbol in symbols {
+            // Servers sharing a workspace often report the same symbol.
+            if !search.symbols.contains(&symbol) {
+                search.symbols.push(symbol);
+            }
+        }
+        true
+    }
+
+    /// Forget the current search, so the next query is searched afresh.
+    pub fn reset(&self) {
+        *self.search.lock().unwrap() = SymbolSearch::default();
+    }
+}
+
+impl QuickOpenProvider for WorkspaceSymbolProvider {
+    fn prefix(&self) -> &str {
+        "@"
+    }
+
+    fn suggestions(&self, query: &str, _context: &QuickOpenContext) -> Vec<Suggestion> {
+        let query = query.trim();
+        if query.is_empty() {
+            return vec![Suggestion::disabled(
+                t!("quick_open.symbol_hint").to_string(),
+            )];
+        }
+
+        let search = self.search.lock().unwrap();
+        let mut matcher = FuzzyMatcher::new(query);
+        let mut scored: Vec<(&WorkspaceSymbolEntry, i32)> = search
+            .symbols
+            .iter()
+            .filter_map(|symbol| {
+                let m = matcher.match_target(&symbol.name);
+                m.matched.then_some((symbol, m.score))
+            })
+            .collect();
+
+        if scored.is_empty() {
+            let message = if search.servers == 0 {
+                t!("quick_open.no_symbol_servers")
+            } else if search.pending > 0 {
+                t!("quick_open.searching_symbols")
+            } else {
+                t!("quick_open.no_symbols")
+            };
+            return vec![Suggestion::disabled(message.to_string())];
+        }
+
+        // Best match first; ties keep a stable order by location.
+        scored.sort_by(|(a, a_score), (b, b_score)| {
+            b_score
+                .cmp(a_score)
+                .then_with(|| a.name.cmp(&b.name))
+                .then_with(|| a.path.cmp(&b.path))
+                .then_with(|| a.line.cmp(&b.line))
+        });
+        scored
+            .into_iter()
+            .map(|(symbol, _)| {
+                let location = format!("{}:{}", symbol.path, symbol.line);
+                let description = match &symbol.container {
+                    Some(container) => format!("{container}  {location}"),
+                    None => location,
+                };
+                Suggestion::new(symbol.name.clone())
+                    .with_description(description)
+                    .with_value(format!("{}:{}:{}", symbol.path, symbol.line, symbol.column))
+            })
+            .collect()
+    }
+
+    fn on_select(
+        &self,
+        suggestion: Option<&Suggestion>,
+        _query: &str,
+        _context: &QuickOpenContext,
+    ) -> QuickOpenResult {
+        let Some(value) = suggestion.and_then(|s| s.value.as_deref()) else {
+            return QuickOpenResult::None;
+        };
+        let (path, line, column) = super::parse_path_line_col(value);
+        QuickOpenResult::OpenFile { path, line, column }
+    }
+
+    fn previews_location(&self) -> bool {
+        true
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
 // ============================================================================
 // File Provider (default, no prefix)
 // ============================================================================
@@ -1207,6 +1383,88 @@ mod tests {
         }
     }
 
+    // ====================================================================
+    // WorkspaceSymbolProvider tests
+    // ====================================================================
+
+    fn symbol(name: &str, path: &str, line: usize) -> WorkspaceSymbolEntry {
+        WorkspaceSymbolEntry {
+            name: name.to_string(),
+            container: None,
+            path: path.to_string(),
+            line,
+            column: 1,
+        }
+    }
+
+    #[test]
+    fn test_workspace_symbols_merge_and_rank() {
+        let provider = WorkspaceSymbolProvider::new();
+        let context = make_test_context("/tmp");
+        assert!(provider.needs_search("conf"));
+        provider.begin_search("conf", 2);
+        assert!(!provider.needs_search(" conf "));
+
+        // The first server answers; the second is still pending.
+        assert!(provider.add_results("conf", vec![symbol("parse_config", "src/config.rs", 10)]));
+        // Replies to an older query are dropped.
+        assert!(!provider.add_results("con", vec![symbol("stale", "a.rs", 1)]));
+        // Duplicates from the second server are merged.
+        assert!(provider.add_results(
+            "conf",
+            vec![
+                symbol("Config", "src/config.rs", 3),
+                symbol("parse_config", "src/config.rs", 10),
+            ]
+        ));
+
+        let suggestions = provider.suggestions("conf", &context);
+        let names: Vec<_> = suggestions.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(names, ["Config", "parse_config"]);
+        assert_eq!(suggestions[0].value.as_deref(), Some("src/config.rs:3:1"));
+
+        match provider.on_select(suggestions.get(1), "conf", &context) {
+            QuickOpenResult::OpenFile { path, line, column } => {
+                assert_eq!(path, "src/config.rs");
+                assert_eq!(line, Some(10));
+                assert_eq!(column, Some(1));
+            }
+            other => panic!("expected OpenFile, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_workspace_symbols_placeholders() {
+        let provider = WorkspaceSymbolProvider::new();
+        let context = make_test_context("/tmp");
+        let placeholder = |query: &str| {
+            let suggestions = provider.suggestions(query, &context);
+            assert_eq!(suggestions.len(), 1);
+            assert!(suggestions[0].disabled);
+            suggestions[0].text.clone()
+        };
+
+        assert!(!provider.needs_search(""));
+        assert_eq!(placeholder(""), t!("quick_open.symbol_hint").to_string());
+
+        provider.begin_search("x", 0);
+        assert_eq!(
+            placeholder("x"),
+            t!("quick_open.no_symbol_servers").to_string()
+        );
+
+        provider.begin_search("x", 1);
+        assert_eq!(
+            placeholder("x"),
+            t!("quick_open.searching_symbols").to_string()
+        );
+        provider.add_results("x", vec![symbol("main", "main.rs", 1)]);
+        assert_eq!(placeholder("x"), t!("quick_open.no_symbols").to_string());
+
+        provider.reset();
+        assert!(provider.needs_search("x"));
+    }
+
     // ====================================================================
     // FileProvider tests
     // ====================================================================
@@ -0,0 +1,365 @@
+//! Syntax-aware ranges for `ExpandSelection` / `ShrinkSelection`.
+//!
+//! Expanding a selection picks the smallest range that strictly contains
+//! it. Ranges come from, in order of preference:
+//!
+//! 1. **LSP**: the chain of enclosing ranges the language server returned
+//!    for the cursor (`textDocument/selectionRange`), cached per buffer
+//!    version in [`LspSelectionRanges`].
+//! 2. **Tree-sitter**: the nodes enclosing the selection in the syntax tree
+//!    of buffers whose language has a grammar compiled in.
+//! 3. **Text**: the word, the inside and then the whole of each enclosing
+//!    quote and bracket pair, the line, and finally the whole buffer.
+//!
+//! Once a source runs out of ranges, the whole buffer is the last step.
+
+use std::ops::Range;
+
+use crate::model::buffer::Buffer;
+use crate::primitives::word_navigation::{find_word_end, find_word_start};
+use crate::state::EditorState;
+
+/// Bytes scanned on each side of the selection for enclosing brackets.
+const MAX_BRACKET_SCAN_BYTES: usize = 256 * 1024;
+
+/// Longest line scanned for enclosing quotes.
+const MAX_QUOTE_LINE_BYTES: usize = 16 * 1024;
+
+const BRACKET_PAIRS: [(u8, u8); 3] = [(b'(', b')'), (b'[', b']'), (b'{', b'}')];
+
+const QUOTES: [u8; 3] = [b'"', b'\'', b'`'];
+
+/// Selection ranges the language server returned for one buffer version.
+#[derive(Debug, Clone, Default)]
+pub struct LspSelectionRanges {
+    /// Buffer version the ranges were computed for.
+    version: u64,
+    /// The byte offset each request position was at, and the ranges
+    /// enclosing it, innermost first. An empty chain means the server had
+    /// nothing for that position.
+    chains: Vec<(usize, Vec<Range<usize>>)>,
+}
+
+impl LspSelectionRanges {
+    /// Record the chains for `version`, replacing those of older versions.
+    pub fn insert(&mut self, version: u64, chains: Vec<(usize, Vec<Range<usize>>)>) {
+        if self.version != version {
+            self.version = version;
+            self.chains.clear();
+        }
+        self.chains.extend(chains);
+    }
+
+    /// The chain for a selection of `start..end` in buffer `version`: one
+    /// asked for at a position inside the selection.
+    pub fn chain(&self, version: u64, start: usize, end: usize) -> Option<&[Range<usize>]> {
+        if self.version != version {
+            return None;
+        }
+        self.chains
+            .iter()
+            .find(|(position, _)| (start..=end).contains(position))
+            .map(|(_, chain)| chain.as_slice())
+    }
+}
+
+/// Convert a `textDocument/selectionRange` result into byte ranges,
+/// innermost first.
+pub fn lsp_selection_chain(
+    buffer: &Buffer,
+    range: &lsp_types::SelectionRange,
+) -> Vec<Range<usize>> {
+    let mut chain = Vec::new();
+    let mut current = Some(range);
+    while let Some(range) = current {
+        let start = buffer.lsp_position_to_byte(
+            range.range.start.line as usize,
+            range.range.start.character as usize,
+        );
+        let end = buffer.lsp_position_to_byte(
+            range.range.end.line as usize,
+            range.range.end.character as usize,
+        );
+        if start <= end {
+            chain.push(start..end);
+        }
+        current = range.parent.as_deref();
+    }
+    chain
+}
+
+/// The range `ExpandSelection` grows a selection of `start..end` to, or
+/// `None` when it already covers the whole buffer.
+pub fn expand_range(state: &mut EditorState, start: usize, end: usize) -> Option<Range<usize>> {
+    let whole = 0..state.buffer.len();
+    let version = state.buffer.version();
+    let found = match state.selection_ranges.chain(version, start, end) {
+        Some(chain) if !chain.is_empty() => smallest_enclosing(chain.iter().cloned(), start, end),
+        _ => match syntax_range(state, start, end) {
+            Some(found) => found,
+            None => smallest_enclosing(text_ranges(&state.buffer, start, end), start, end),
+        },
+    };
+    found
+        .or_else(|| encloses(&whole, start, end).then_some(whole))
+        .filter(|range| range.end > range.start)
+}
+
+/// Whether `range` strictly contains `start..end`.
+fn encloses(range: &Range<usize>, start: usize, end: usize) -> bool {
+    range.start <= start && range.end >= end && (range.start, range.end) != (start, end)
+}
+
+/// The smallest of `ranges` that strictly contains `start..end`.
+fn smallest_enclosing(
+    ranges: impl IntoIterator<Item = Range<usize>>,
+    start: usize,
+    end: usize,
+) -> Option<Range<usize>> {
+    ranges
+        .into_iter()
+        .filter(|range| range.end > range.start && encloses(range, start, end))
+        .min_by_key(|range| range.end - range.start)
+}
+
+/// The innermost syntax node strictly containing `start..end`: `None` when
+/// the buffer has no syntax tree, `Some(None)` when no node does.
+#[cfg(feature = "tree-sitter")]
+fn syntax_range(state: &mut EditorState, start: usize, end: usize) -> Option<Option<Range<usize>>> {
+    let language = state.highlighter.language().copied();
+    let tree = state.syntax_tree.tree(&state.buffer, language.as_ref())?;
+    Some(enclosing_node(tree, start, end))
+}
+
+/// The range of the innermost node of `tree` strictly containing
+/// `start..end`.
+#[cfg(feature = "tree-sitter")]
+fn enclosing_node(
+    tree: &fresh_languages::tree_sitter::Tree,
+    start: usize,
+    end: usize,
+) -> Option<Range<usize>> {
+    let mut node = tree.root_node().descendant_for_byte_range(start, end);
+    while let Some(current) = node {
+        let range = current.byte_range();
+        if range.end > range.start && encloses(&range, start, end) {
+            return Some(range);
+        }
+        node = current.parent();
+    }
+    None
+}
+
+#[cfg(not(feature = "tree-sitter"))]
+fn syntax_range(
+    _state: &mut EditorState,
+    _start: usize,
+    _end: usize,
+) -> Option<Option<Range<usize>>> {
+    None
+}
+
+/// Candidate ranges around `start..end` for buffers without a syntax tree:
+/// the word, enclosing quote and bracket pairs (inside, then including the
+/// delimiters) and the line.
+fn text_ranges(buffer: &Buffer, start: usize, end: usize) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+
+    let word_start = find_word_start(buffer, start);
+    let word_end = find_word_end(buffer, word_start);
+    if word_start <= start && end <= word_end {
+        ranges.push(word_start..word_end);
+    }
+
+    let window_start = start.saturating_sub(MAX_BRACKET_SCAN_BYTES);
+    let window_end = (end + MAX_BRACKET_SCAN_BYTES).min(buffer.len());
+    let window = buffer.slice_bytes(window_start..window_end);
+    let (start_in, end_in) = (start - window_start, end - window_start);
+
+    let line_start = window[..start_in]
+        .iter()
+        .rposition(|&b| b == b'\n')
+        .map_or(0, |i| i + 1);
+    let mut line_end = window[end_in..]
+        .iter()
+        .position(|&b| b == b'\n')
+        .map_or(window.len(), |i| end_in + i);
+    if line_end > line_start && window[line_end - 1] == b'\r' && line_end < window.len() {
+        line_end -= 1;
+    }
+    let single_line = !window[start_in..end_in].contains(&b'\n');
+    if single_line {
+        ranges.push(window_start + line_start..window_start + line_end);
+        if line_end - line_start <= MAX_QUOTE_LINE_BYTES {
+            for (open, close) in quote_pairs(&window[line_start..line_end]) {
+                let (open, close) = (
+                    window_start + line_start + open,
+                    window_start + line_start + close,
+                );
+                ranges.push(open + 1..close);
+                ranges.push(open..close + 1);
+            }
+        }
+    }
+
+    if let Some((open, close)) = enclosing_brackets(&window, start_in, end_in) {
+        let (open, close) = (window_start + open, window_start + close);
+        ranges.push(open + 1..close);
+        ranges.push(open..close + 1);
+    }
+    ranges
+}
+
+/// Byte offsets of the opening and closing quote of each string on `line`.
+/// Quotes of another kind inside a string and escaped quotes are skipped.
+fn quote_pairs(line: &[u8]) -> Vec<(usize, usize)> {
+    let mut pairs = Vec::new();
+    let mut open: Option<(u8, usize)> = None;
+    let mut escaped = false;
+    for (i, &c) in line.iter().enumerate() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match open {
+            Some(_) if c == b'\\' => escaped = true,
+            Some((quote, at)) if c == quote => {
+                pairs.push((at, i));
+                open = None;
+            }
+            None if QUOTES.contains(&c) => open = Some((c, i)),
+            _ => {}
+        }
+    }
+    pairs
+}
+
+/// Offsets in `text` of the innermost bracket pair around `start..end`,
+/// skipping balanced pairs on the way out.
+fn enclosing_brackets(text: &[u8], start: usize, end: usize) -> Option<(usize, usize)> {
+    let mut closers: Vec<u8> = Vec::new();
+    let mut open = None;
+    for i in (0..start).rev() {
+        let b = text[i];
+        if BRACKET_PAIRS.iter().any(|&(_, close)| close == b) {
+            closers.push(b);
+        } else if let Some(&(_, close)) = BRACKET_PAIRS.iter().find(|&&(o, _)| o == b) {
+            match closers.last() {
+                Some(&last) if last == close => {
+                    closers.pop();
+                }
+                Some(_) => {}
+                None => {
+                    open = Some((i, close));
+                    break;
+                }
+            }
+        }
+    }
+    let (open, close) = open?;
+
+    let mut openers: Vec<u8> = Vec::new();
+    for (i, &b) in text.iter().enumerate().skip(end) {
+        if BRACKET_PAIRS.iter().any(|&(o, _)| o == b) {
+            openers.push(b);
+        } else if BRACKET_PAIRS.iter().any(|&(_, c)| c == b) {
+            match openers.pop() {
+                Some(_) => {}
+                None if b == close => return Some((open, i)),
+                None => return None,
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn buffer_ranges(text: &str, start: usize, end: usize) -> Option<Range<usize>> {
+        let buffer = Buffer::from_str_test(text);
+        smallest_enclosing(text_ranges(&buffer, start, end), start, end)
+    }
+
+    /// Expand repeatedly from `start..end`, collecting the selected text.
+    fn expansions(text: &str, start: usize, end: usize) -> Vec<&str> {
+        let mut selected = Vec::new();
+        let mut range = start..end;
+        while let Some(next) = buffer_ranges(text, range.start, range.end) {
+            selected.push(&text[next.clone()]);
+            range = next;
+        }
+        selected
+    }
+
+    #[test]
+    fn text_ranges_grow_through_words_quotes_and_brackets() {
+        let text = "call(a, \"x y\", [b])";
+        let x = text.find('x').unwrap();
+        assert_eq!(
+            expansions(text, x, x),
+            vec![
+                "x",
+                "x y",
+                "\"x y\"",
+                "a, \"x y\", [b]",
+                "(a, \"x y\", [b])",
+                text
+            ]
+        );
+    }
+
+    #[test]
+    fn brackets_skip_balanced_pairs_and_cross_lines() {
+        let text = "f {\n    g(1);\n    h[2];\n}\n";
+        let h = text.find('h').unwrap();
+        assert_eq!(
+            expansions(text, h, h),
+            vec![
+                "h",
+                "    h[2];",
+                "\n    g(1);\n    h[2];\n",
+                "{\n    g(1);\n    h[2];\n}"
+            ]
+        );
+    }
+
+    #[test]
+    fn quote_pairs_skip_escapes_and_other_quotes() {
+        assert_eq!(quote_pairs(br#"a "b \" 'c" 'd'"#), vec![(2, 10), (12, 14)]);
+    }
+
+    #[test]
+    fn lsp_chains_belong_to_a_buffer_version() {
+        let mut cache = LspSelectionRanges::default();
+        cache.insert(3, vec![(5, vec![4..6, 0..10])]);
+        assert_eq!(cache.chain(3, 4, 6), Some(&[4..6, 0..10][..]));
+        assert_eq!(cache.chain(3, 7, 8), None);
+        assert_eq!(cache.chain(4, 4, 6), None);
+
+        cache.insert(4, vec![(7, Vec::new())]);
+        assert_eq!(cache.chain(4, 4, 6), None);
+        assert_eq!(cache.chain(4, 7, 7), Some(&[][..]));
+    }
+
+    #[cfg(feature = "tree-sitter")]
+    #[test]
+    fn syntax_ranges_follow_the_tree() {
+        use crate::primitives::highlighter::Language;
+
+        let text = "function f() {\n  return g(a + b);\n}\n";
+        let buffer = Buffer::from_str_test(text);
+        let mut cache = crate::primitives::syntax_tree::SyntaxTreeCache::default();
+        let tree = cache.tree(&buffer, Some(&Language::JavaScript)).unwrap();
+
+        let a = text.find('a').unwrap();
+        let mut selected = Vec::new();
+        let mut range = a..a;
+        while let Some(next) = enclosing_node(tree, range.start, range.end) {
+            selected.push(&text[next.clone()]);
+            range = next;
+        }
+        assert_eq!(&selected[..4], &["a", "a + b", "(a + b)", "g(a + b)"]);
+    }
+}
@@ -3646,6 +3646,7 @@ fn run_cmd_command(tokens: &[&str]) -> AnyhowResult<()> {
         Some("script") => {
             match &rest[1..] {
                 ["run", from @ ..] => script_run(session, from),
+                ["batch", script, files @ ..] if !files.is_empty() => script_batch(script, files),
                 ["check", from @ ..] => script_check(from),
                 ["api", query, flags @ ..] => script_api(query, flags),
                 ["api"] => {
@@ -3655,6 +3656,7 @@ fn run_cmd_command(tokens: &[&str]) -> AnyhowResult<()> {
                 ["types"] => script_types(),
                 _ => {
                     eprintln!("usage: fresh --cmd script api <query>     search the API by name or description");
+                    eprintln!("       fresh --cmd script batch FILE PATH...  run over files headlessly, saving results");
                     eprintln!("       fresh --cmd script check [FILE|-]  parse + check editor.* names, without running");
                     eprintln!("       fresh --cmd script run [FILE|-]    evaluate against this workspace (default: stdin)");
                     eprintln!("       fresh --cmd script types           paths of the API declaration files");
@@ -3871,6 +3873,7 @@ the body of an async function with an `editor` global; whatever it
 returns is printed as JSON. Source comes from a file or stdin.
 
   fresh --cmd script run [FILE|-]           evaluate against this workspace
+  fresh --cmd script batch FILE PATH...     run over each PATH with no UI, save
   fresh --cmd script check [FILE|-]         parse + check editor.* names, no run
   fresh --cmd script api <query> [--json]   search the API by name/description
   fresh --cmd script types                  paths of the API declaration files
@@ -3885,6 +3888,12 @@ Those three are the author → reload → test loop for customizing the editor:
 edit init.ts, `init reload`, then `command run` the command you registered.
 No keystroke from the user is needed at any point.
 
+`script batch` needs no running editor: it builds a headless one, opens
+each PATH in turn and runs the script with FRESH_BATCH_FILE (the path) and
+FRESH_BATCH_BUFFER_ID (its buffer) defined. Edited buffers are saved when
+the script returns and discarded when it throws; one line per file is
+printed, and any failure exits 1 — so it fits a CI step.
+
 Target a specific daemon with --session NAME (default: the daemon of the
 current working directory). Inside a Fresh terminal the right session is
 already in `$FRESH_SESSION`, so no flag is needed — reach for --session only
@@ -3895,6 +3904,7 @@ Examples:
   echo 'return editor.listBuffers().map(b => b.path)' | fresh --cmd script run
   fresh --cmd script api splitWindow
   fresh --cmd command list dashboard     # did my registerCommand land?
+  fresh --cmd script batch fmt.ts src/*.rs   # format every file, headless
   fresh --cmd script run make-tour.ts    # author + open a code tour
                                          # (see: fresh --cmd help tour)
 
@@ -4191,6 +4201,28 @@ fn script_run(session: Option<&str>, from: &[&str]) -> AnyhowResult<()> {
     submit_script(session, source, false)
 }
 
+/// `fresh --cmd script batch <script> <files...>` — run a script over each
+/// file in a headless editor of its own, for CI refactors.
+///
+/// No session is involved: the editor is built here, boots its plugins and
+/// `init.ts`, and is dropped when the last file is done. Each file's line goes
+/// to stdout as it finishes; any failure makes the exit status 1.
+fn script_batch(script: &str, files: &[&str]) -> AnyhowResult<()> {
+    let source = read_script_source(&[script])?;
+    if source.trim().is_empty() {
+        anyhow::bail!("empty script: {}", script);
+    }
+    let files: Vec<std::path::PathBuf> = files.iter().map(std::path::PathBuf::from).collect();
+    let report = fresh::app::script_batch::run(&source, &files, cmd_build_timeout(), |file| {
+        println!("{}", file.line())
+    })?;
+    println!("{}", report.summary());
+    if !report.success() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
 /// Send `source` to a live editor's script channel and report the outcome:
 /// whatever the script returned on stdout, a throw on stderr with exit 1.
 ///
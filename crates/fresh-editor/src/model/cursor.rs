@@ -209,6 +209,20 @@ pub struct Cursors {
 
     /// Primary cursor ID (the most recently added/active one)
     primary_id: CursorId,
+
+    /// Per-cursor selections from before each `ExpandSelection`, so
+    /// `ShrinkSelection` can step back through them
+    expansions: HashMap<CursorId, SelectionExpansion>,
+}
+
+/// The selections a cursor went through while expanding its selection.
+#[derive(Debug, Clone)]
+struct SelectionExpansion {
+    /// The selection the last expansion produced. The history only applies
+    /// while the cursor still has exactly this selection.
+    current: Range<usize>,
+    /// The cursor before each expansion, oldest first.
+    previous: Vec<Cursor>,
 }
 
 impl Cursors {
@@ -222,6 +236,7 @@ impl Cursors {
             cursors,
             next_id: 1,
             primary_id,
+            expansions: HashMap::new(),
         }
     }
 
@@ -244,6 +259,16 @@ impl Cursors {
         self.primary_id
     }
 
+    /// Make an existing cursor the primary one. Returns `false` (and changes
+    /// nothing) when no cursor has that ID.
+    pub fn set_primary(&mut self, id: CursorId) -> bool {
+        if !self.cursors.contains_key(&id) {
+            return false;
+        }
+        self.primary_id = id;
+        true
+    }
+
     /// Get a cursor by ID
     pub fn get(&self, id: CursorId) -> Option<&Cursor> {
         self.cursors.get(&id)
@@ -281,6 +306,7 @@ impl Cursors {
         }
 
         let cursor = self.cursors.remove(&id);
+        self.expansions.remove(&id);
 
         // If we removed the primary cursor, pick a new primary
         if id == self.primary_id {
@@ -313,6 +339,43 @@ impl Cursors {
         self.primary_id = first_id; // Update primary to be the first cursor
     }
 
+    /// Record that cursor `id` is about to grow from `previous` to select
+    /// `expanded`. The history restarts if the cursor's selection changed
+    /// since its last expansion.
+    pub fn push_expansion(&mut self, id: CursorId, previous: Cursor, expanded: Range<usize>) {
+        let entry = self
+            .expansions
+            .entry(id)
+            .or_insert_with(|| SelectionExpansion {
+                current: expanded.clone(),
+                previous: Vec::new(),
+            });
+        if previous.selection_range().as_ref() != Some(&entry.current) {
+            entry.previous.clear();
+        }
+        entry.previous.push(previous);
+        entry.current = expanded;
+    }
+
+    /// The cursor as it was before its last expansion, if cursor `id`
+    /// still has the selection that expansion produced.
+    pub fn pop_expansion(&mut self, id: CursorId) -> Option<Cursor> {
+        let selection = self.cursors.get(&id)?.selection_range()?;
+        let entry = self.expansions.get_mut(&id)?;
+        if entry.current != selection {
+            self.expansions.remove(&id);
+            return None;
+        }
+        let previous = entry.previous.pop()?;
+        match previous.selection_range() {
+            Some(range) if !entry.previous.is_empty() => entry.current = range,
+            _ => {
+                self.expansions.remove(&id);
+            }
+        }
+        Some(previous)
+    }
+
     /// Get all cursor IDs
     pub fn ids(&self) -> Vec<CursorId> {
         self.cursors.keys().copied().collect()
@@ -522,6 +522,16 @@ impl Event {
         }
     }
 
+    /// Returns false for events that cannot be applied again once reloaded
+    /// from disk (`BulkEdit` keeps its buffer snapshots in memory only)
+    pub fn is_replayable(&self) -> bool {
+        match self {
+            Self::BulkEdit { .. } => false,
+            Self::Batch { events, .. } => events.iter().all(|e| e.is_replayable()),
+            _ => true,
+        }
+    }
+
     /// Returns true if this event is a write action (modifies state in a way that should be undoable)
     /// Returns false for readonly actions like cursor movement, scrolling, viewport changes, etc.
     ///
@@ -550,6 +560,33 @@ impl Event {
         }
     }
 
+    /// Short one-line description of the event, for history listings
+    pub fn summary(&self) -> String {
+        fn quoted(text: &str) -> String {
+            const MAX_CHARS: usize = 40;
+            let mut shown: String = text.chars().take(MAX_CHARS).collect();
+            if text.chars().nth(MAX_CHARS).is_some() {
+                shown.push('…');
+            }
+            format!("{shown:?}")
+        }
+
+        match self {
+            Self::Insert { text, .. } => format!("Insert {}", quoted(text)),
+            Self::Delete { deleted_text, .. } => format!("Delete {}", quoted(deleted_text)),
+            Self::Batch { description, .. } | Self::BulkEdit { description, .. } => {
+                description.clone()
+            }
+            Self::AddCursor { .. } => "Add cursor".to_string(),
+            Self::RemoveCursor { .. } => "Remove cursor".to_string(),
+            other => format!("{other:?}")
+                .split([' ', '{', '('])
+                .next()
+                .unwrap_or_default()
+                .to_string(),
+        }
+    }
+
     /// Returns the cursor ID associated with this event, if any
     pub fn cursor_id(&self) -> Option<CursorId> {
         match self {
@@ -584,9 +621,9 @@ pub struct LogEntry {
 
     /// Undo-group id. Entries sharing the same id are undone/redone as a
     /// single atomic unit (e.g. a whole macro replay). `None` means the entry
-    /// stands on its own. Runtime-only — not persisted, so reloaded logs fall
-    /// back to per-entry undo.
-    #[serde(skip)]
+    /// stands on its own. Persisted with the entry, so a reloaded history
+    /// still undoes a group in one step.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub group_id: Option<u64>,
 }
 
@@ -624,12 +661,111 @@ pub struct Snapshot {
     pub cursor_positions: Vec<(CursorId, usize, Option<usize>)>,
 }
 
-/// The event log - append-only log of all events
+/// Stable handle on one entry of an [`EventLog`]'s undo tree.
+///
+/// Ids are arena indices: they never move, so a handle taken from
+/// [`EventLog::undo_tree`] stays valid until the log is cleared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct UndoNodeId(pub usize);
+
+/// A logged entry plus its place in the undo tree.
+#[derive(Debug, Clone)]
+struct UndoNode {
+    entry: LogEntry,
+    /// Entry this one was appended after; `None` for entries made on top
+    /// of the original buffer content.
+    parent: Option<usize>,
+    /// Distance from the root (1 for a root-level entry). A node sits at
+    /// `path[depth - 1]` whenever it lies on the active branch.
+    depth: usize,
+    /// Entries appended after this one, oldest first. More than one child
+    /// means the user undid past this point and then edited again.
+    children: Vec<usize>,
+}
+
+/// A point in the undo tree: before any edit, or right after an entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TreePoint {
+    Root,
+    Node(usize),
+}
+
+/// One undoable state in the undo tree, as listed by the history browser.
+///
+/// Rows are collapsed to write units: an ungrouped write action, or every
+/// write action of one undo group (e.g. a macro replay), is a single row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UndoTreeRow {
+    /// Where [`EventLog::jump_to`] lands for this row; `None` is the
+    /// original buffer content.
+    pub node: Option<UndoNodeId>,
+    /// Branch nesting level. The newest line of history through a fork
+    /// keeps its parent's level; older alternatives are nested one deeper.
+    pub indent: usize,
+    /// 1-based creation order of the row's first entry (0 for the root).
+    pub seq: usize,
+    /// Timestamp of the row's first entry (milliseconds since epoch).
+    pub timestamp: u64,
+    /// Undo group the row's entries belong to, if any.
+    pub group_id: Option<u64>,
+    /// Short human-readable summary of the edit.
+    pub summary: String,
+    /// Whether the buffer currently reflects exactly this state.
+    pub is_current: bool,
+    /// Whether the state lies on the active branch (reachable by undo/redo).
+    pub on_active_branch: bool,
+}
+
+/// Events needed to move a buffer from one undo-tree state to another.
+#[derive(Debug, Default)]
+pub struct UndoJump {
+    /// Inverse events (with displaced markers) walking back to the common
+    /// ancestor, in application order — same shape as [`EventLog::undo`].
+    pub undo: Vec<(Event, Vec<(u64, usize)>)>,
+    /// Events replayed from the common ancestor down to the target, in
+    /// application order — same shape as [`EventLog::redo`].
+    pub redo: Vec<Event>,
+}
+
+/// Serializable form of an [`EventLog`]'s undo tree, produced by
+/// [`EventLog::export_history`] to persist undo across editor restarts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UndoHistory {
+    /// Entries in creation order; parents always precede their children.
+    pub nodes: Vec<UndoHistoryNode>,
+    /// Entry whose state the history was exported at (`None` = before
+    /// the first entry). The buffer must hold exactly that content.
+    pub anchor: Option<usize>,
+    /// Next undo-group id, so groups opened after a reload stay distinct.
+    pub next_group_id: u64,
+}
+
+/// One entry of an [`UndoHistory`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UndoHistoryNode {
+    /// Index of the parent entry in [`UndoHistory::nodes`]
+    pub parent: Option<usize>,
+    pub entry: LogEntry,
+}
+
+/// The event log - an undo tree of all events.
+///
+/// Nothing is ever discarded: an edit made after undoing starts a new
+/// branch instead of truncating the redo history. Undo/redo walk the
+/// *active branch* (`path`), which runs from the root through the current
+/// position to the newest leaf below it; [`Self::jump_to`] re-roots the
+/// active branch through any other node.
 pub struct EventLog {
-    /// All logged events
-    entries: Vec<LogEntry>,
+    /// Every logged entry, in creation order (arena of tree nodes)
+    nodes: Vec<UndoNode>,
+
+    /// Entries whose parent is the root, oldest first
+    roots: Vec<usize>,
 
-    /// Current position in the log (for undo/redo)
+    /// The active branch, as arena indices from the root down
+    path: Vec<usize>,
+
+    /// Current position in the active branch (for undo/redo)
     current_index: usize,
 
     /// Periodic snapshots for fast seeking
@@ -642,9 +778,9 @@ pub struct EventLog {
     #[cfg(feature = "runtime")]
     stream_file: Option<std::fs::File>,
 
-    /// Index at which the buffer was last saved (for tracking modified status)
-    /// When current_index equals saved_at_index, the buffer is not modified
-    saved_at_index: Option<usize>,
+    /// Tree point at which the buffer was last saved (for tracking modified
+    /// status). `None` means no point in the tree matches the file on disk.
+    saved_at: Option<TreePoint>,
 
     /// Monotonic allocator for undo-group ids.
     next_group_id: u64,
@@ -661,13 +797,15 @@ impl EventLog {
     /// Create a new empty event log
     pub fn new() -> Self {
         Self {
-            entries: Vec::new(),
+            nodes: Vec::new(),
+            roots: Vec::new(),
+            path: Vec::new(),
             current_index: 0,
             snapshots: Vec::new(),
             snapshot_interval: 100,
             #[cfg(feature = "runtime")]
             stream_file: None,
-            saved_at_index: Some(0), // New buffer starts at "saved" state (index 0)
+            saved_at: Some(TreePoint::Root), // New buffer starts at "saved" state
             next_group_id: 0,
             current_group: None,
             group_depth: 0,
@@ -695,41 +833,84 @@ impl EventLog {
         }
     }
 
+    /// Fold the newest entry into the undo unit of the write action before
+    /// it, so one undo reverts both. For edits that finish a keystroke after
+    /// the fact, such as a language server's on-type formatting.
+    ///
+    /// No-op while redo history exists or when no earlier write action is
+    /// logged.
+    pub fn join_last_with_previous_undo_unit(&mut self) {
+        if self.current_index != self.path.len() || self.path.len() < 2 {
+            return;
+        }
+        let last = self.path.len() - 1;
+        let Some(start) = self.path[..last]
+            .iter()
+            .rposition(|&idx| self.nodes[idx].entry.event.is_write_action())
+        else {
+            return;
+        };
+        let group = match self.nodes[self.path[start]].entry.group_id {
+            Some(group) => group,
+            None => {
+                self.next_group_id += 1;
+                self.next_group_id - 1
+            }
+        };
+        for &idx in &self.path[start..] {
+            self.nodes[idx].entry.group_id = Some(group);
+        }
+    }
+
     /// Mark the current position as the saved point
     /// Call this when the buffer is saved to disk
     pub fn mark_saved(&mut self) {
-        self.saved_at_index = Some(self.current_index);
+        self.saved_at = Some(self.point_at(self.current_index));
     }
 
     /// Invalidate the saved position so the buffer is always considered modified.
     /// Call this after hot exit recovery, where the buffer content differs from
     /// disk but the event log has no record of the changes.
     pub fn clear_saved_position(&mut self) {
-        self.saved_at_index = None;
+        self.saved_at = None;
     }
 
     /// Check if the buffer is at the saved position (not modified)
-    /// Returns true if we're at the saved position OR if all events between
-    /// saved_at_index and current_index are readonly (don't modify buffer content)
+    /// Returns true if we're at the saved position OR if every entry on the
+    /// tree path between the saved point and the current point is readonly
+    /// (doesn't modify buffer content). The saved point may sit on another
+    /// branch, in which case the path runs through their common ancestor.
     pub fn is_at_saved_position(&self) -> bool {
-        match self.saved_at_index {
-            None => false,
-            Some(saved_idx) if saved_idx == self.current_index => true,
-            Some(saved_idx) => {
-                // Check if all events between saved position and current position
-                // are readonly (don't modify buffer content)
-                let (start, end) = if saved_idx < self.current_index {
-                    (saved_idx, self.current_index)
-                } else {
-                    (self.current_index, saved_idx)
-                };
-
-                // All events in range [start, end) must be readonly
-                self.entries[start..end]
-                    .iter()
-                    .all(|entry| !entry.event.modifies_buffer())
+        let Some(saved) = self.saved_at else {
+            return false;
+        };
+        let current = self.point_at(self.current_index);
+        if saved == current {
+            return true;
+        }
+
+        let mut a = Self::point_node(saved);
+        let mut b = Self::point_node(current);
+        while a != b {
+            let (depth_a, depth_b) = (self.node_depth(a), self.node_depth(b));
+            // Step whichever side is deeper (both when level) towards the
+            // common ancestor, checking every entry we leave behind.
+            if depth_a >= depth_b {
+                let idx = a.expect("deeper side is never the root");
+                if self.nodes[idx].entry.event.modifies_buffer() {
+                    return false;
+                }
+                a = self.nodes[idx].parent;
+            }
+            if depth_b >= depth_a {
+                let idx = b.expect("deeper side is never the root");
+                if self.nodes[idx].entry.event.modifies_buffer() {
+                    return false;
+                }
+                b = self.nodes[idx].parent;
             }
         }
+        true
     }
 
     /// Enable streaming events to a file (runtime only)
@@ -817,17 +998,12 @@ impl EventLog {
         // editor state but not recorded in the log, preserving redo history.
         // This matches standard editor behavior (VS Code, Sublime, etc.) where
         // navigation after undo does not destroy the redo chain.
-        if self.current_index < self.entries.len() {
+        if self.current_index < self.path.len() {
             if event.is_write_action() {
-                // Write action: truncate redo history and log normally
-                self.entries.truncate(self.current_index);
-
-                // Invalidate saved_at_index if it pointed to a truncated entry
-                if let Some(saved_idx) = self.saved_at_index {
-                    if saved_idx > self.current_index {
-                        self.saved_at_index = None;
-                    }
-                }
+                // Write action: fork a new branch at the current position.
+                // The undone entries stay in the tree as a sibling branch
+                // (reachable through `jump_to`) instead of being discarded.
+                self.path.truncate(self.current_index);
             } else {
                 // Non-write event while redo exists: skip logging to preserve redo
                 return self.current_index;
@@ -840,7 +1016,7 @@ impl EventLog {
             use std::io::Write;
 
             let stream_entry = serde_json::json!({
-                "index": self.entries.len(),
+                "index": self.nodes.len(),
                 "timestamp": chrono::Local::now().to_rfc3339(),
                 "event": event,
             });
@@ -856,11 +1032,13 @@ impl EventLog {
 
         let mut entry = LogEntry::new(event);
         entry.group_id = self.current_group;
-        self.entries.push(entry);
-        self.current_index = self.entries.len();
+        let parent = self.path.last().copied();
+        let idx = self.push_node(entry, parent);
+        self.path.push(idx);
+        self.current_index = self.path.len();
 
         // Check if we should create a snapshot
-        if self.entries.len().is_multiple_of(self.snapshot_interval) {
+        if self.nodes.len().is_multiple_of(self.snapshot_interval) {
             // Snapshot creation will be implemented when we have Buffer
             // For now, just track that we'd create one here
         }
@@ -872,24 +1050,24 @@ impl EventLog {
     /// Call this right after `append()` to record markers that were inside
     /// the deleted range, so undo can restore them to exact positions.
     pub fn set_displaced_markers_on_last(&mut self, markers: Vec<(u64, usize)>) {
-        if let Some(entry) = self.entries.last_mut() {
-            entry.displaced_markers = markers;
+        if let Some(&idx) = self.path.last() {
+            self.nodes[idx].entry.displaced_markers = markers;
         }
     }
 
-    /// Get the current event index
+    /// Get the current event index (position within the active branch)
     pub fn current_index(&self) -> usize {
         self.current_index
     }
 
-    /// Get the number of events in the log
+    /// Get the number of events in the active branch
     pub fn len(&self) -> usize {
-        self.entries.len()
+        self.path.len()
     }
 
-    /// Check if the event log is empty
+    /// Check if the active branch has no events
     pub fn is_empty(&self) -> bool {
-        self.entries.is_empty()
+        self.path.is_empty()
     }
 
     /// Can we undo?
@@ -899,7 +1077,7 @@ impl EventLog {
 
     /// Can we redo?
     pub fn can_redo(&self) -> bool {
-        self.current_index < self.entries.len()
+        self.current_index < self.path.len()
     }
 
     /// Move back through events (for undo)
@@ -917,8 +1095,9 @@ impl EventLog {
 
         while self.can_undo() {
             let idx = self.current_index - 1;
-            let is_write = self.entries[idx].event.is_write_action();
-            let entry_group = self.entries[idx].group_id;
+            let entry = &self.nodes[self.path[idx]].entry;
+            let is_write = entry.event.is_write_action();
+            let entry_group = entry.group_id;
 
             if found_write_action {
                 match group {
@@ -938,8 +1117,8 @@ impl EventLog {
             }
 
             // Try to get the inverse of this event
-            if let Some(inverse) = self.entries[idx].event.inverse() {
-                inverse_events.push((inverse, self.entries[idx].displaced_markers.clone()));
+            if let Some(inverse) = entry.event.inverse() {
+                inverse_events.push((inverse, entry.displaced_markers.clone()));
             }
             // If no inverse exists (like MoveCursor), we just skip it
         }
@@ -960,8 +1139,9 @@ impl EventLog {
         // Keep moving forward to collect write action and subsequent readonly events
         while self.can_redo() {
             let idx = self.current_index;
-            let is_write = self.entries[idx].event.is_write_action();
-            let entry_group = self.entries[idx].group_id;
+            let entry = &self.nodes[self.path[idx]].entry;
+            let is_write = entry.event.is_write_action();
+            let entry_group = entry.group_id;
 
             // If we've already found a write action and this is another write action, stop
             // (unless it belongs to the same open undo group).
@@ -978,27 +1158,302 @@ impl EventLog {
                 group = entry_group;
             }
 
-            events.push(self.entries[idx].event.clone());
+            events.push(entry.event.clone());
             self.current_index = idx + 1;
         }
 
         events
     }
 
-    /// Get all events from the log
-    pub fn entries(&self) -> &[LogEntry] {
-        &self.entries
+    /// The node the buffer currently reflects, or `None` before any edit.
+    pub fn current_node(&self) -> Option<UndoNodeId> {
+        Self::point_node(self.point_at(self.current_index)).map(UndoNodeId)
+    }
+
+    /// Move to an arbitrary state in the undo tree.
+    ///
+    /// Walks back from the current position to the common ancestor with
+    /// `target` and then down to `target` (`None` = the original content).
+    /// The active branch is re-rooted through `target`, continuing down its
+    /// newest descendants so they stay reachable with plain redo. Returns
+    /// the events to apply, or `None` if `target` is not in this log.
+    pub fn jump_to(&mut self, target: Option<UndoNodeId>) -> Option<UndoJump> {
+        let target = match target {
+            Some(UndoNodeId(idx)) if idx >= self.nodes.len() => return None,
+            Some(UndoNodeId(idx)) => TreePoint::Node(idx),
+            None => TreePoint::Root,
+        };
+
+        // Root-to-target chain of arena indices.
+        let mut chain = Vec::new();
+        let mut cursor = Self::point_node(target);
+        while let Some(idx) = cursor {
+            chain.push(idx);
+            cursor = self.nodes[idx].parent;
+        }
+        chain.reverse();
+
+        let common = chain
+            .iter()
+            .zip(&self.path[..self.current_index])
+            .take_while(|(a, b)| a == b)
+            .count();
+
+        let mut jump = UndoJump::default();
+        for &idx in self.path[common..self.current_index].iter().rev() {
+            let entry = &self.nodes[idx].entry;
+            if let Some(inverse) = entry.event.inverse() {
+                jump.undo.push((inverse, entry.displaced_markers.clone()));
+            }
+        }
+        jump.redo = chain[common..]
+            .iter()
+            .map(|&idx| self.nodes[idx].entry.event.clone())
+            .collect();
+
+        self.current_index = chain.len();
+        self.path = chain;
+        // Keep the newest line of history below the target redoable.
+        loop {
+            let children = match self.path.last() {
+                Some(&idx) => &self.nodes[idx].children,
+                None => &self.roots,
+            };
+            match children.last() {
+                Some(&child) => self.path.push(child),
+                None => break,
+            }
+        }
+
+        Some(jump)
+    }
+
+    /// Entries making up the write unit that ends at `node`, oldest first.
+    /// Readonly entries in between (cursor moves, scrolling) are skipped.
+    pub fn unit_entries(&self, node: UndoNodeId) -> Vec<&LogEntry> {
+        let Some(last) = self.nodes.get(node.0) else {
+            return Vec::new();
+        };
+        let group = last.entry.group_id;
+        let mut entries = vec![&last.entry];
+        if group.is_some() {
+            let mut cursor = last.parent;
+            while let Some(idx) = cursor {
+                let entry = &self.nodes[idx].entry;
+                if entry.event.is_write_action() {
+                    if entry.group_id != group {
+                        break;
+                    }
+                    entries.push(entry);
+                }
+                cursor = self.nodes[idx].parent;
+            }
+        }
+        entries.reverse();
+        entries
+    }
+
+    /// Flatten the undo tree into browser rows, in pre-order.
+    ///
+    /// The first row is always the original content. At every fork the
+    /// older alternatives are listed (one level deeper) right after the
+    /// fork point, followed by the newest line of history at the same level.
+    pub fn undo_tree(&self) -> Vec<UndoTreeRow> {
+        // Pass 1: collapse write actions into units. A write entry continues
+        // its nearest write ancestor's unit when both share an undo group.
+        // Per unit: (head node, last write node, child units).
+        let mut units: Vec<(usize, usize, Vec<usize>)> = Vec::new();
+        let mut root_units = Vec::new();
+        // Nearest write ancestor (inclusive) of each node, as a unit index.
+        let mut write_anchor: Vec<Option<usize>> = vec![None; self.nodes.len()];
+
+        for idx in 0..self.nodes.len() {
+            let node = &self.nodes[idx];
+            let inherited = node.parent.and_then(|p| write_anchor[p]);
+            if !node.entry.event.is_write_action() {
+                write_anchor[idx] = inherited;
+                continue;
+            }
+            let continues = match (inherited, node.entry.group_id) {
+                (Some(unit), Some(group)) => {
+                    self.nodes[units[unit].1].entry.group_id == Some(group)
+                }
+                _ => false,
+            };
+            let unit = match inherited {
+                Some(unit) if continues => {
+                    units[unit].1 = idx;
+                    unit
+                }
+                _ => {
+                    let unit = units.len();
+                    units.push((idx, idx, Vec::new()));
+                    match inherited {
+                        Some(parent) => units[parent].2.push(unit),
+                        None => root_units.push(unit),
+                    }
+                    unit
+                }
+            };
+            write_anchor[idx] = Some(unit);
+        }
+
+        // Pass 2: pre-order walk, newest child last at the parent's level.
+        let current = self.point_at(self.current_index);
+        let current_unit = Self::point_node(current).and_then(|idx| write_anchor[idx]);
+        let on_path = |idx: usize| {
+            let depth = self.nodes[idx].depth;
+            self.path.get(depth - 1) == Some(&idx)
+        };
+
+        let mut rows = vec![UndoTreeRow {
+            node: None,
+            indent: 0,
+            seq: 0,
+            timestamp: self.nodes.first().map_or(0, |n| n.entry.timestamp),
+            group_id: None,
+            summary: String::new(),
+            is_current: current_unit.is_none(),
+            on_active_branch: true,
+        }];
+
+        // Stack of (unit, indent), popped in pre-order.
+        let mut stack: Vec<(usize, usize)> = Vec::new();
+        let push_children = |stack: &mut Vec<(usize, usize)>, children: &[usize], indent: usize| {
+            if let Some((&newest, older)) = children.split_last() {
+                stack.push((newest, indent));
+                for &child in older.iter().rev() {
+                    stack.push((child, indent + 1));
+                }
+            }
+        };
+        push_children(&mut stack, &root_units, 0);
+
+        while let Some((unit, indent)) = stack.pop() {
+            let (head, last, ref children) = units[unit];
+            let head_entry = &self.nodes[head].entry;
+            rows.push(UndoTreeRow {
+                node: Some(UndoNodeId(last)),
+                indent,
+                seq: head + 1,
+                timestamp: head_entry.timestamp,
+                group_id: head_entry.group_id,
+                summary: head_entry
+                    .description
+                    .clone()
+                    .unwrap_or_else(|| head_entry.event.summary()),
+                is_current: current_unit == Some(unit),
+                on_active_branch: on_path(last),
+            });
+            push_children(&mut stack, children, indent);
+        }
+
+        rows
+    }
+
+    /// Export the undo tree for persisting across editor restarts, anchored
+    /// at the saved point (the state that matches the file on disk).
+    ///
+    /// Only content edits are kept: cursor moves and cursor add/remove
+    /// entries are spliced out, since cursor ids do not survive a restart.
+    /// `BulkEdit` entries cannot be replayed after a reload (their buffer
+    /// snapshots are in-memory only), so the history stops at the newest
+    /// one on the way to the saved point, and branches through any other
+    /// one are dropped. Returns `None` when no saved point is known.
+    pub fn export_history(&self) -> Option<UndoHistory> {
+        let anchor = Self::point_node(self.saved_at?);
+
+        // Deepest non-replayable entry between the root and the anchor: the
+        // reloaded history can never be undone past it.
+        let mut barrier = None;
+        let mut cursor = anchor;
+        while let Some(idx) = cursor {
+            if barrier.is_none() && !self.nodes[idx].entry.event.is_replayable() {
+                barrier = Some(idx);
+            }
+            cursor = self.nodes[idx].parent;
+        }
+
+        // Parents always precede their children in the arena, so one pass in
+        // creation order resolves each node against its already-seen parent.
+        // `attach[i]` is the exported node that entries below `i` hang off
+        // (`Some(None)` = the root, `None` = `i` and its subtree are dropped).
+        let mut attach: Vec<Option<Option<usize>>> = Vec::with_capacity(self.nodes.len());
+        let mut nodes = Vec::new();
+        for (idx, node) in self.nodes.iter().enumerate() {
+            let inherited = match node.parent {
+                Some(parent) if Some(parent) == barrier => Some(None),
+                Some(parent) => attach[parent],
+                None if barrier.is_some() => None,
+                None => Some(None),
+            };
+            let resolved = match inherited {
+                Some(_) if !node.entry.event.is_replayable() => None,
+                Some(parent) if node.entry.event.modifies_buffer() => {
+                    let mut entry = node.entry.clone();
+                    // Marker ids are per-session; restoring them later could
+                    // move unrelated markers.
+                    entry.displaced_markers.clear();
+                    nodes.push(UndoHistoryNode { parent, entry });
+                    Some(Some(nodes.len() - 1))
+                }
+                other => other,
+            };
+            debug_assert_eq!(attach.len(), idx);
+            attach.push(resolved);
+        }
+
+        Some(UndoHistory {
+            nodes,
+            anchor: anchor.and_then(|idx| attach[idx]).flatten(),
+            next_group_id: self.next_group_id,
+        })
+    }
+
+    /// Rebuild a log from [`Self::export_history`] output. The buffer is
+    /// assumed to hold the anchor's content, which becomes the saved point;
+    /// the newest history below it stays redoable. Returns `None` if the
+    /// history is malformed.
+    pub fn from_history(history: UndoHistory) -> Option<Self> {
+        let mut log = Self::new();
+        for (idx, node) in history.nodes.into_iter().enumerate() {
+            if node.parent.is_some_and(|parent| parent >= idx) {
+                return None;
+            }
+            log.push_node(node.entry, node.parent);
+        }
+        if history
+            .anchor
+            .is_some_and(|anchor| anchor >= log.nodes.len())
+        {
+            return None;
+        }
+        log.next_group_id = history.next_group_id;
+        log.jump_to(history.anchor.map(UndoNodeId))?;
+        log.saved_at = Some(log.point_at(log.current_index));
+        Some(log)
+    }
+
+    /// Get all events on the active branch
+    pub fn entries(&self) -> Vec<&LogEntry> {
+        self.path
+            .iter()
+            .map(|&idx| &self.nodes[idx].entry)
+            .collect()
     }
 
-    /// Get events in a range
-    pub fn range(&self, range: Range<usize>) -> &[LogEntry] {
-        &self.entries[range]
+    /// Get events in a range of the active branch
+    pub fn range(&self, range: Range<usize>) -> Vec<&LogEntry> {
+        self.path[range]
+            .iter()
+            .map(|&idx| &self.nodes[idx].entry)
+            .collect()
     }
 
     /// Get the most recent event
     pub fn last_event(&self) -> Option<&Event> {
         if self.current_index > 0 {
-            Some(&self.entries[self.current_index - 1].event)
+            Some(&self.nodes[self.path[self.current_index - 1]].entry.event)
         } else {
             None
         }
@@ -1006,19 +1461,21 @@ impl EventLog {
 
     /// Clear all events (for testing or reset)
     pub fn clear(&mut self) {
-        self.entries.clear();
+        self.nodes.clear();
+        self.roots.clear();
+        self.path.clear();
         self.current_index = 0;
         self.snapshots.clear();
     }
 
-    /// Save event log to JSON Lines format
+    /// Save the active branch of the event log to JSON Lines format
     pub fn save_to_file(&self, path: &std::path::Path) -> std::io::Result<()> {
         use std::io::Write;
         let file = std::fs::File::create(path)?;
         let mut writer = std::io::BufWriter::new(file);
 
-        for entry in &self.entries {
-            let json = serde_json::to_string(entry)?;
+        for &idx in &self.path {
+            let json = serde_json::to_string(&self.nodes[idx].entry)?;
             writeln!(writer, "{json}")?;
         }
 
@@ -1039,10 +1496,12 @@ impl EventLog {
                 continue;
             }
             let entry: LogEntry = serde_json::from_str(&line)?;
-            log.entries.push(entry);
+            let parent = log.path.last().copied();
+            let idx = log.push_node(entry, parent);
+            log.path.push(idx);
         }
 
-        log.current_index = log.entries.len();
+        log.current_index = log.path.len();
 
         Ok(log)
     }
@@ -1051,6 +1510,42 @@ impl EventLog {
     pub fn set_snapshot_interval(&mut self, interval: usize) {
         self.snapshot_interval = interval;
     }
+
+    /// Add `entry` to the arena as the newest child of `parent`.
+    fn push_node(&mut self, entry: LogEntry, parent: Option<usize>) -> usize {
+        let idx = self.nodes.len();
+        let depth = parent.map_or(0, |p| self.nodes[p].depth) + 1;
+        self.nodes.push(UndoNode {
+            entry,
+            parent,
+            depth,
+            children: Vec::new(),
+        });
+        match parent {
+            Some(p) => self.nodes[p].children.push(idx),
+            None => self.roots.push(idx),
+        }
+        idx
+    }
+
+    /// The tree point reached after the first `index` entries of the active branch.
+    fn point_at(&self, index: usize) -> TreePoint {
+        match index {
+            0 => TreePoint::Root,
+            i => TreePoint::Node(self.path[i - 1]),
+        }
+    }
+
+    fn point_node(point: TreePoint) -> Option<usize> {
+        match point {
+            TreePoint::Root => None,
+            TreePoint::Node(idx) => Some(idx),
+        }
+    }
+
+    fn node_depth(&self, node: Option<usize>) -> usize {
+        node.map_or(0, |idx| self.nodes[idx].depth)
+    }
 }
 
 impl Default for EventLog {
@@ -1426,6 +1921,26 @@ mod tests {
         assert_eq!(log.current_index(), 0);
     }
 
+    #[test]
+    fn test_join_last_with_previous_undo_unit() {
+        // A late edit (on-type formatting) joins the keystroke it completes,
+        // leaving the edit before that as its own unit.
+        let mut log = EventLog::new();
+        for (position, text) in [(0, "a"), (1, ";"), (0, "  ")] {
+            log.append(Event::Insert {
+                position,
+                text: text.to_string(),
+                cursor_id: CursorId(0),
+            });
+        }
+        log.join_last_with_previous_undo_unit();
+
+        assert_eq!(log.undo().len(), 2, "formatting and ';' revert together");
+        assert_eq!(log.undo().len(), 1, "'a' reverts on its own");
+        assert_eq!(log.redo().len(), 1);
+        assert_eq!(log.redo().len(), 2);
+    }
+
     #[test]
     fn test_write_action_after_undo_clears_redo() {
         // Write actions after undo SHOULD still clear redo history
@@ -1508,4 +2023,211 @@ mod tests {
             "Should not be at saved position after undo + new edit"
         );
     }
+
+    fn insert(position: usize, text: &str) -> Event {
+        Event::Insert {
+            position,
+            text: text.to_string(),
+            cursor_id: CursorId(0),
+        }
+    }
+
+    #[test]
+    fn test_edit_after_undo_keeps_old_branch() {
+        let mut log = EventLog::new();
+        log.append(insert(0, "a"));
+        log.append(insert(1, "b"));
+        log.undo();
+        log.append(insert(1, "c"));
+
+        // Active branch is a, c — but b survives as a sibling branch.
+        let rows = log.undo_tree();
+        let summaries: Vec<(usize, &str, bool)> = rows
+            .iter()
+            .map(|r| (r.indent, r.summary.as_str(), r.is_current))
+            .collect();
+        assert_eq!(
+            summaries,
+            vec![
+                (0, "", false),
+                (0, "Insert \"a\"", false),
+                (1, "Insert \"b\"", false),
+                (0, "Insert \"c\"", true),
+            ]
+        );
+        assert!(!rows[2].on_active_branch);
+    }
+
+    #[test]
+    fn test_jump_to_other_branch() {
+        let mut log = EventLog::new();
+        log.append(insert(0, "a"));
+        log.append(insert(1, "b"));
+        let b = log.current_node().unwrap();
+        log.undo();
+        log.append(insert(1, "c"));
+
+        let jump = log.jump_to(Some(b)).unwrap();
+        assert_eq!(jump.undo.len(), 1);
+        assert!(
+            matches!(&jump.undo[0].0, Event::Delete { deleted_text, .. } if deleted_text == "c")
+        );
+        assert_eq!(jump.redo.len(), 1);
+        assert!(matches!(&jump.redo[0], Event::Insert { text, .. } if text == "b"));
+        assert_eq!(log.current_node(), Some(b));
+
+        // Plain undo/redo now walk the re-rooted branch.
+        assert!(!log.can_redo());
+        log.undo();
+        assert!(log.can_redo());
+        assert!(matches!(&log.redo()[0], Event::Insert { text, .. } if text == "b"));
+
+        // Jumping to the root undoes everything.
+        let jump = log.jump_to(None).unwrap();
+        assert_eq!(jump.undo.len(), 2);
+        assert!(jump.redo.is_empty());
+        assert!(!log.can_undo());
+        // The newest line of history below the root stays redoable.
+        assert!(log.can_redo());
+    }
+
+    #[test]
+    fn test_len_and_is_empty_describe_the_active_branch() {
+        let mut log = EventLog::new();
+        assert!(log.is_empty());
+        assert_eq!(log.len(), 0);
+        log.append(insert(0, "a"));
+        let a = log.current_node().unwrap();
+        log.undo();
+        log.append(insert(0, "b"));
+        log.jump_to(Some(a)).unwrap();
+        log.jump_to(None).unwrap();
+        assert_eq!(log.len(), 1, "the newest branch stays redoable");
+        assert!(!log.is_empty());
+    }
+
+    #[test]
+    fn test_saved_position_on_other_branch() {
+        let mut log = EventLog::new();
+        log.append(insert(0, "a"));
+        let saved = log.current_node().unwrap();
+        log.mark_saved();
+        log.undo();
+        log.append(insert(0, "b"));
+        assert!(!log.is_at_saved_position());
+
+        log.jump_to(Some(saved)).unwrap();
+        assert!(log.is_at_saved_position());
+    }
+
+    #[test]
+    fn test_undo_tree_collapses_undo_groups() {
+        let mut log = EventLog::new();
+        log.begin_undo_group();
+        log.append(insert(0, "a"));
+        log.append(Event::MoveCursor {
+            cursor_id: CursorId(0),
+            old_position: 0,
+            new_position: 1,
+            old_anchor: None,
+            new_anchor: None,
+            old_sticky_column: None,
+            new_sticky_column: None,
+        });
+        log.append(insert(1, "b"));
+        log.end_undo_group();
+        log.append(insert(2, "c"));
+
+        let rows = log.undo_tree();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[1].group_id, Some(0));
+        assert_eq!(log.unit_entries(rows[1].node.unwrap()).len(), 2);
+        assert_eq!(rows[2].group_id, None);
+        assert!(rows[2].is_current);
+    }
+
+    #[test]
+    fn test_history_round_trip_keeps_undo_groups() {
+        let mut log = EventLog::new();
+        log.append(insert(0, "a"));
+        log.begin_undo_group();
+        log.append(insert(1, "b"));
+        log.append(insert(2, "c"));
+        log.end_undo_group();
+        log.mark_saved();
+
+        let json = serde_json::to_string(&log.export_history().unwrap()).unwrap();
+        let mut reloaded = EventLog::from_history(serde_json::from_str(&json).unwrap()).unwrap();
+        assert!(reloaded.is_at_saved_position());
+
+        assert_eq!(reloaded.undo().len(), 2, "the group undoes in one step");
+        assert_eq!(reloaded.undo().len(), 1);
+        assert!(!reloaded.can_undo());
+        assert!(!reloaded.is_at_saved_position());
+        assert_eq!(reloaded.redo().len(), 1);
+        assert_eq!(reloaded.redo().len(), 2);
+        assert!(reloaded.is_at_saved_position());
+
+        // Groups opened after the reload get fresh ids.
+        reloaded.begin_undo_group();
+        reloaded.append(insert(3, "d"));
+        reloaded.end_undo_group();
+        reloaded.append(insert(4, "e"));
+        assert_eq!(reloaded.undo().len(), 1);
+        assert_eq!(reloaded.undo().len(), 1);
+    }
+
+    #[test]
+    fn test_history_is_anchored_at_saved_point() {
+        let mut log = EventLog::new();
+        log.append(insert(0, "a"));
+        log.mark_saved();
+        log.append(insert(1, "b"));
+        assert!(!log.is_at_saved_position());
+
+        let mut reloaded = EventLog::from_history(log.export_history().unwrap()).unwrap();
+        assert!(reloaded.is_at_saved_position());
+        assert_eq!(reloaded.current_index(), 1);
+        // The unsaved edit is still reachable as redo.
+        assert_eq!(reloaded.redo().len(), 1);
+
+        log.clear_saved_position();
+        assert!(log.export_history().is_none());
+    }
+
+    #[test]
+    fn test_history_stops_at_bulk_edit() {
+        let bulk_edit = Event::BulkEdit {
+            old_snapshot: None,
+            new_snapshot: None,
+            old_cursors: Vec::new(),
+            new_cursors: Vec::new(),
+            description: "Indent".to_string(),
+            edits: vec![(0, 0, 4)],
+            displaced_markers: Vec::new(),
+        };
+        let mut log = EventLog::new();
+        log.append(insert(0, "a"));
+        log.append(bulk_edit);
+        log.append(Event::MoveCursor {
+            cursor_id: CursorId(0),
+            old_position: 0,
+            new_position: 5,
+            old_anchor: None,
+            new_anchor: None,
+            old_sticky_column: None,
+            new_sticky_column: None,
+        });
+        log.append(insert(5, "b"));
+        log.mark_saved();
+
+        let history = log.export_history().unwrap();
+        assert_eq!(history.nodes.len(), 1, "only the edit after the bulk edit");
+        assert_eq!(history.nodes[0].parent, None);
+        assert_eq!(history.anchor, Some(0));
+
+        let mut reloaded = EventLog::from_history(history).unwrap();
+        assert_eq!(reloaded.undo().len(), 1);
+        assert!(!reloaded.can_undo());
+    }
 }
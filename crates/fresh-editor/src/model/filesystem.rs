@@ -408,6 +408,21 @@ pub struct SearchMatch {
     pub context: String,
 }
 
+/// What a [`FileSystem::watch_dir`] watch reports.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DirWatchEvent {
+    /// The watch is in place; changes from here on are reported.
+    Ready,
+    /// The directory itself or one of its entries changed.
+    Changed(PathBuf),
+    /// The watch ended for good: the directory went away or couldn't be
+    /// watched.
+    Stopped,
+    /// The connection dropped. The watch can be set up again once it's
+    /// back.
+    Disconnected,
+}
+
 // ============================================================================
 // FileSystem Trait
 // ============================================================================
@@ -679,6 +694,29 @@ pub trait FileSystem: Send + Sync {
         None
     }
 
+    /// Watch `dir` (not recursively) from the filesystem's own side,
+    /// reporting through `on_event`. Returns an id for [`Self::unwatch_dir`].
+    ///
+    /// For filesystems a local watcher can't see (remote ones). Local
+    /// filesystems don't implement it — the editor watches them with
+    /// `notify` directly.
+    fn watch_dir(
+        &self,
+        dir: &Path,
+        on_event: Box<dyn Fn(DirWatchEvent) + Send + Sync>,
+    ) -> io::Result<u64> {
+        let _ = (dir, on_event);
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "directory watching is not supported by this filesystem",
+        ))
+    }
+
+    /// Stop a watch started by [`Self::watch_dir`]. Unknown ids are ignored.
+    fn unwatch_dir(&self, id: u64) {
+        let _ = id;
+    }
+
     /// Get the home directory for this filesystem
     ///
     /// For local filesystems, returns the local home directory.
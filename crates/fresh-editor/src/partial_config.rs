@@ -8,7 +8,7 @@ use crate::config::{
     IndentationGuideMode, Keybinding, KeybindingMapName, KeymapConfig, LanguageConfig,
     LineEndingOption, OnSaveAction, PluginConfig, TerminalConfig, ThemeName, WarningsConfig,
 };
-use crate::types::LspLanguageConfig;
+use crate::types::{DebugAdapterConfig, DebugConfiguration, LspLanguageConfig, TaskConfig};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -91,6 +91,9 @@ pub struct PartialConfig {
     pub lsp_enabled: Option<bool>,
     pub lsp: Option<HashMap<String, LspLanguageConfig>>,
     pub universal_lsp: Option<HashMap<String, LspLanguageConfig>>,
+    pub debug_adapters: Option<HashMap<String, DebugAdapterConfig>>,
+    pub debug_configurations: Option<Vec<DebugConfiguration>>,
+    pub tasks: Option<Vec<TaskConfig>>,
     pub warnings: Option<PartialWarningsConfig>,
     pub plugins: Option<HashMap<String, PartialPluginConfig>>,
     pub packages: Option<PartialPackagesConfig>,
@@ -120,6 +123,9 @@ impl Merge for PartialConfig {
 
         // Lists: higher precedence replaces (per design doc)
         self.keybindings.merge_from(&other.keybindings);
+        self.debug_configurations
+            .merge_from(&other.debug_configurations);
+        self.tasks.merge_from(&other.tasks);
 
         // HashMaps: merge entries, higher precedence wins on key collision
         merge_hashmap(&mut self.keybinding_maps, &other.keybinding_maps);
@@ -128,6 +134,7 @@ impl Merge for PartialConfig {
         self.lsp_enabled.merge_from(&other.lsp_enabled);
         merge_hashmap(&mut self.lsp, &other.lsp);
         merge_hashmap(&mut self.universal_lsp, &other.universal_lsp);
+        merge_hashmap(&mut self.debug_adapters, &other.debug_adapters);
         merge_hashmap_recursive(&mut self.plugins, &other.plugins);
 
         self.active_keybinding_map
@@ -176,6 +183,7 @@ pub struct PartialEditorConfig {
     pub diagnostics_inline_text: Option<bool>,
     pub recovery_enabled: Option<bool>,
     pub auto_recovery_save_interval_secs: Option<u32>,
+    pub persistent_undo: Option<bool>,
     pub auto_save_enabled: Option<bool>,
     pub auto_save_interval_secs: Option<u32>,
     pub hot_exit: Option<bool>,
@@ -191,10 +199,15 @@ pub struct PartialEditorConfig {
     pub auto_revert_poll_interval_ms: Option<u64>,
     pub read_concurrency: Option<usize>,
     pub file_tree_poll_interval_ms: Option<u64>,
+    pub plugin_execution_timeout_ms: Option<u64>,
+    pub plugin_memory_limit_mb: Option<u64>,
     pub default_line_ending: Option<LineEndingOption>,
     pub trim_trailing_whitespace_on_save: Option<bool>,
     pub ensure_final_newline_on_save: Option<bool>,
     pub auto_read_only: Option<bool>,
+    pub document_links: Option<bool>,
+    pub spell_check: Option<bool>,
+    pub spell_check_language: Option<String>,
     pub highlight_matching_brackets: Option<bool>,
     pub rainbow_brackets: Option<bool>,
     pub cursor_style: Option<CursorStyle>,
@@ -216,6 +229,10 @@ pub struct PartialEditorConfig {
     pub show_prompt_line: Option<bool>,
     pub show_vertical_scrollbar: Option<bool>,
     pub show_horizontal_scrollbar: Option<bool>,
+    pub show_minimap: Option<bool>,
+    pub minimap_width: Option<u16>,
+    pub sticky_scroll: Option<bool>,
+    pub sticky_scroll_max_lines: Option<u16>,
     pub show_tilde: Option<bool>,
     pub nerd_font_icons: Option<bool>,
     pub use_terminal_bg: Option<bool>,
@@ -271,6 +288,7 @@ impl Merge for PartialEditorConfig {
         self.recovery_enabled.merge_from(&other.recovery_enabled);
         self.auto_recovery_save_interval_secs
             .merge_from(&other.auto_recovery_save_interval_secs);
+        self.persistent_undo.merge_from(&other.persistent_undo);
         self.auto_save_enabled.merge_from(&other.auto_save_enabled);
         self.auto_save_interval_secs
             .merge_from(&other.auto_save_interval_secs);
@@ -297,6 +315,10 @@ impl Merge for PartialEditorConfig {
         self.read_concurrency.merge_from(&other.read_concurrency);
         self.file_tree_poll_interval_ms
             .merge_from(&other.file_tree_poll_interval_ms);
+        self.plugin_execution_timeout_ms
+            .merge_from(&other.plugin_execution_timeout_ms);
+        self.plugin_memory_limit_mb
+            .merge_from(&other.plugin_memory_limit_mb);
         self.default_line_ending
             .merge_from(&other.default_line_ending);
         self.trim_trailing_whitespace_on_save
@@ -304,6 +326,10 @@ impl Merge for PartialEditorConfig {
         self.ensure_final_newline_on_save
             .merge_from(&other.ensure_final_newline_on_save);
         self.auto_read_only.merge_from(&other.auto_read_only);
+        self.document_links.merge_from(&other.document_links);
+        self.spell_check.merge_from(&other.spell_check);
+        self.spell_check_language
+            .merge_from(&other.spell_check_language);
         self.highlight_matching_brackets
             .merge_from(&other.highlight_matching_brackets);
         self.rainbow_brackets.merge_from(&other.rainbow_brackets);
@@ -340,6 +366,11 @@ impl Merge for PartialEditorConfig {
             .merge_from(&other.show_vertical_scrollbar);
         self.show_horizontal_scrollbar
             .merge_from(&other.show_horizontal_scrollbar);
+        self.show_minimap.merge_from(&other.show_minimap);
+        self.minimap_width.merge_from(&other.minimap_width);
+        self.sticky_scroll.merge_from(&other.sticky_scroll);
+        self.sticky_scroll_max_lines
+            .merge_from(&other.sticky_scroll_max_lines);
         self.show_tilde.merge_from(&other.show_tilde);
         self.nerd_font_icons.merge_from(&other.nerd_font_icons);
         self.use_terminal_bg.merge_from(&other.use_terminal_bg);
@@ -436,6 +467,8 @@ impl Merge for PartialFileBrowserConfig {
 pub struct PartialClipboardConfig {
     pub use_osc52: Option<bool>,
     pub use_system_clipboard: Option<bool>,
+    pub history_size: Option<usize>,
+    pub persist_history: Option<bool>,
 }
 
 impl Merge for PartialClipboardConfig {
@@ -443,6 +476,8 @@ impl Merge for PartialClipboardConfig {
         self.use_osc52.merge_from(&other.use_osc52);
         self.use_system_clipboard
             .merge_from(&other.use_system_clipboard);
+        self.history_size.merge_from(&other.history_size);
+        self.persist_history.merge_from(&other.persist_history);
     }
 }
 
@@ -627,6 +662,7 @@ impl From<&crate::config::EditorConfig> for PartialEditorConfig {
             diagnostics_inline_text: Some(cfg.diagnostics_inline_text),
             recovery_enabled: Some(cfg.recovery_enabled),
             auto_recovery_save_interval_secs: Some(cfg.auto_recovery_save_interval_secs),
+            persistent_undo: Some(cfg.persistent_undo),
             auto_save_enabled: Some(cfg.auto_save_enabled),
             auto_save_interval_secs: Some(cfg.auto_save_interval_secs),
             hot_exit: Some(cfg.hot_exit),
@@ -646,10 +682,15 @@ impl From<&crate::config::EditorConfig> for PartialEditorConfig {
             auto_revert_poll_interval_ms: Some(cfg.auto_revert_poll_interval_ms),
             read_concurrency: Some(cfg.read_concurrency),
             file_tree_poll_interval_ms: Some(cfg.file_tree_poll_interval_ms),
+            plugin_execution_timeout_ms: Some(cfg.plugin_execution_timeout_ms),
+            plugin_memory_limit_mb: Some(cfg.plugin_memory_limit_mb),
             default_line_ending: Some(cfg.default_line_ending.clone()),
             trim_trailing_whitespace_on_save: Some(cfg.trim_trailing_whitespace_on_save),
             ensure_final_newline_on_save: Some(cfg.ensure_final_newline_on_save),
             auto_read_only: Some(cfg.auto_read_only),
+            document_links: Some(cfg.document_links),
+            spell_check: Some(cfg.spell_check),
+            spell_check_language: Some(cfg.spell_check_language.clone()),
             highlight_matching_brackets: Some(cfg.highlight_matching_brackets),
             rainbow_brackets: Some(cfg.rainbow_brackets),
             cursor_style: Some(cfg.cursor_style),
@@ -673,6 +714,10 @@ impl From<&crate::config::EditorConfig> for PartialEditorConfig {
             show_prompt_line: Some(cfg.show_prompt_line),
             show_vertical_scrollbar: Some(cfg.show_vertical_scrollbar),
             show_horizontal_scrollbar: Some(cfg.show_horizontal_scrollbar),
+            show_minimap: Some(cfg.show_minimap),
+            minimap_width: Some(cfg.minimap_width),
+            sticky_scroll: Some(cfg.sticky_scroll),
+            sticky_scroll_max_lines: Some(cfg.sticky_scroll_max_lines),
             show_tilde: Some(cfg.show_tilde),
             nerd_font_icons: Some(cfg.nerd_font_icons),
             use_terminal_bg: Some(cfg.use_terminal_bg),
@@ -753,6 +798,7 @@ impl PartialEditorConfig {
             auto_recovery_save_interval_secs: self
                 .auto_recovery_save_interval_secs
                 .unwrap_or(defaults.auto_recovery_save_interval_secs),
+            persistent_undo: self.persistent_undo.unwrap_or(defaults.persistent_undo),
             auto_save_enabled: self.auto_save_enabled.unwrap_or(defaults.auto_save_enabled),
             auto_save_interval_secs: self
                 .auto_save_interval_secs
@@ -790,6 +836,12 @@ impl PartialEditorConfig {
             file_tree_poll_interval_ms: self
                 .file_tree_poll_interval_ms
                 .unwrap_or(defaults.file_tree_poll_interval_ms),
+            plugin_execution_timeout_ms: self
+                .plugin_execution_timeout_ms
+                .unwrap_or(defaults.plugin_execution_timeout_ms),
+            plugin_memory_limit_mb: self
+                .plugin_memory_limit_mb
+                .unwrap_or(defaults.plugin_memory_limit_mb),
             default_line_ending: self
                 .default_line_ending
                 .unwrap_or(defaults.default_line_ending.clone()),
@@ -800,6 +852,11 @@ impl PartialEditorConfig {
                 .ensure_final_newline_on_save
                 .unwrap_or(defaults.ensure_final_newline_on_save),
             auto_read_only: self.auto_read_only.unwrap_or(defaults.auto_read_only),
+            document_links: self.document_links.unwrap_or(defaults.document_links),
+            spell_check: self.spell_check.unwrap_or(defaults.spell_check),
+            spell_check_language: self
+                .spell_check_language
+                .unwrap_or_else(|| defaults.spell_check_language.clone()),
             highlight_matching_brackets: self
                 .highlight_matching_brackets
                 .unwrap_or(defaults.highlight_matching_brackets),
@@ -849,6 +906,12 @@ impl PartialEditorConfig {
             show_horizontal_scrollbar: self
                 .show_horizontal_scrollbar
                 .unwrap_or(defaults.show_horizontal_scrollbar),
+            show_minimap: self.show_minimap.unwrap_or(defaults.show_minimap),
+            minimap_width: self.minimap_width.unwrap_or(defaults.minimap_width),
+            sticky_scroll: self.sticky_scroll.unwrap_or(defaults.sticky_scroll),
+            sticky_scroll_max_lines: self
+                .sticky_scroll_max_lines
+                .unwrap_or(defaults.sticky_scroll_max_lines),
             show_tilde: self.show_tilde.unwrap_or(defaults.show_tilde),
             nerd_font_icons: self.nerd_font_icons.unwrap_or(defaults.nerd_font_icons),
             use_terminal_bg: self.use_terminal_bg.unwrap_or(defaults.use_terminal_bg),
@@ -965,6 +1028,8 @@ impl From<&ClipboardConfig> for PartialClipboardConfig {
         Self {
             use_osc52: Some(cfg.use_osc52),
             use_system_clipboard: Some(cfg.use_system_clipboard),
+            history_size: Some(cfg.history_size),
+            persist_history: Some(cfg.persist_history),
         }
     }
 }
@@ -976,6 +1041,8 @@ impl PartialClipboardConfig {
             use_system_clipboard: self
                 .use_system_clipboard
                 .unwrap_or(defaults.use_system_clipboard),
+            history_size: self.history_size.unwrap_or(defaults.history_size),
+            persist_history: self.persist_history.unwrap_or(defaults.persist_history),
         }
     }
 }
@@ -1194,6 +1261,9 @@ impl From<&crate::config::Config> for PartialConfig {
                     })
                     .collect(),
             ),
+            debug_adapters: Some(cfg.debug_adapters.clone()),
+            debug_configurations: Some(cfg.debug_configurations.clone()),
+            tasks: Some(cfg.tasks.clone()),
             warnings: Some(PartialWarningsConfig::from(&cfg.warnings)),
             // Only include plugins that differ from defaults
             // Path is auto-discovered at runtime and should never be saved
@@ -1377,6 +1447,15 @@ impl PartialConfig {
             lsp_enabled: self.lsp_enabled.unwrap_or(defaults.lsp_enabled),
             lsp,
             universal_lsp,
+            debug_adapters: {
+                let mut result = defaults.debug_adapters.clone();
+                result.extend(self.debug_adapters.unwrap_or_default());
+                result
+            },
+            debug_configurations: self
+                .debug_configurations
+                .unwrap_or_else(|| defaults.debug_configurations.clone()),
+            tasks: self.tasks.unwrap_or_else(|| defaults.tasks.clone()),
             warnings: self
                 .warnings
                 .map(|e| e.resolve(&defaults.warnings))
@@ -81,3 +81,7 @@ pub mod highlighter;
 pub mod indent;
 #[cfg(feature = "runtime")]
 pub mod reference_highlighter;
+#[cfg(feature = "runtime")]
+pub mod structural_pattern;
+#[cfg(feature = "runtime")]
+pub mod syntax_tree;
@@ -1,13 +1,20 @@
 //! LSP snippet parser and expander.
 //!
-//! Parses LSP snippet syntax and expands it to plain text with cursor positioning.
+//! Parses LSP snippet syntax into plain text plus the tabstops a snippet
+//! session steps through with Tab / Shift+Tab (see `app::snippet_session`).
 //! Supports:
 //! - `$0` - final cursor position
-//! - `$n` - tabstops (expanded as empty, cursor goes to $0 or end)
-//! - `${n:text}` - tabstops with default text (uses the default)
-//! - `${n|choice1,choice2|}` - choices (uses first choice)
+//! - `$n` - tabstops; repeating a number mirrors that placeholder's text
+//! - `${n:text}` - tabstops with default text, possibly nested
+//! - `${n|choice1,choice2|}` - choices (the first one is inserted)
+//! - `${name:default}` - variables (expanded to their default)
 //! - `\\$` - escaped dollar sign
 
+use std::collections::{BTreeMap, HashMap};
+use std::iter::Peekable;
+use std::ops::Range;
+use std::str::Chars;
+
 /// Result of expanding a snippet
 #[derive(Debug, Clone, PartialEq)]
 pub struct ExpandedSnippet {
@@ -17,6 +24,52 @@ pub struct ExpandedSnippet {
     pub cursor_offset: usize,
 }
 
+/// A snippet parsed into its plain text and the tabstops within it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedSnippet {
+    /// The expanded plain text
+    pub text: String,
+    /// Tabstops in navigation order: `$1`, `$2`, ... ascending, then `$0`.
+    /// Never empty: without a `$0` the last stop is the end of the text.
+    pub tabstops: Vec<Tabstop>,
+}
+
+/// One numbered tabstop of a parsed snippet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tabstop {
+    pub index: u32,
+    /// Byte ranges in the snippet text of every occurrence of this tabstop.
+    /// More than one when the placeholder is mirrored.
+    pub ranges: Vec<Range<usize>>,
+    /// Options of a choice placeholder (`${1|a,b|}`); empty otherwise.
+    pub choices: Vec<String>,
+}
+
+impl ParsedSnippet {
+    /// Offset of the final cursor position (`$0`, or the end of the text).
+    pub fn final_offset(&self) -> usize {
+        self.tabstops
+            .last()
+            .and_then(|stop| stop.ranges.first())
+            .map_or(self.text.len(), |range| range.start)
+    }
+
+    /// Whether there is anywhere to go besides the final cursor position.
+    pub fn has_placeholders(&self) -> bool {
+        self.tabstops.len() > 1
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Text(String),
+    Tabstop {
+        index: u32,
+        children: Vec<Node>,
+        choices: Vec<String>,
+    },
+}
+
 /// Expand an LSP snippet to plain text
 ///
 /// # Examples
@@ -34,141 +87,199 @@ pub struct ExpandedSnippet {
 /// assert_eq!(result.cursor_offset, 8); // cursor at end (no $0)
 /// ```
 pub fn expand_snippet(snippet: &str) -> ExpandedSnippet {
-    let mut result = String::new();
-    let mut cursor_offset: Option<usize> = None;
-    let mut chars = snippet.chars().peekable();
+    let parsed = parse_snippet(snippet);
+    ExpandedSnippet {
+        cursor_offset: parsed.final_offset(),
+        text: parsed.text,
+    }
+}
 
-    while let Some(c) = chars.next() {
-        if c == '\\' {
-            // Escape sequence
-            if let Some(&next) = chars.peek() {
-                if next == '$' || next == '\\' || next == '}' {
-                    result.push(chars.next().unwrap());
-                    continue;
+/// Parse an LSP snippet into its text and tabstops.
+///
+/// A tabstop without text of its own (`$1`) takes the text of another
+/// occurrence of the same number (`${1:name}`), wherever that one is.
+pub fn parse_snippet(snippet: &str) -> ParsedSnippet {
+    let nodes = parse_nodes(&mut snippet.chars().peekable(), false);
+
+    // First pass: the text each numbered placeholder gives itself.
+    let mut text = String::new();
+    let mut stops = BTreeMap::new();
+    render_nodes(&nodes, &HashMap::new(), &mut text, &mut stops);
+    let defaults: HashMap<u32, String> = stops
+        .values()
+        .filter_map(|stop: &Tabstop| {
+            let range = stop.ranges.iter().find(|r| !r.is_empty())?;
+            Some((stop.index, text[range.clone()].to_string()))
+        })
+        .collect();
+
+    // Second pass: mirrors filled in with that text.
+    let mut text = String::new();
+    let mut stops = BTreeMap::new();
+    render_nodes(&nodes, &defaults, &mut text, &mut stops);
+
+    let final_stop = stops.remove(&0).unwrap_or_else(|| Tabstop {
+        index: 0,
+        ranges: std::iter::once(text.len()..text.len()).collect(),
+        choices: Vec::new(),
+    });
+    let mut tabstops: Vec<Tabstop> = stops.into_values().collect();
+    tabstops.push(final_stop);
+    ParsedSnippet { text, tabstops }
+}
+
+fn render_nodes(
+    nodes: &[Node],
+    defaults: &HashMap<u32, String>,
+    text: &mut String,
+    stops: &mut BTreeMap<u32, Tabstop>,
+) {
+    for node in nodes {
+        match node {
+            Node::Text(s) => text.push_str(s),
+            Node::Tabstop {
+                index,
+                children,
+                choices,
+            } => {
+                let start = text.len();
+                if !children.is_empty() {
+                    render_nodes(children, defaults, text, stops);
+                } else if let Some(first) = choices.first() {
+                    text.push_str(first);
+                } else if let Some(default) = defaults.get(index) {
+                    text.push_str(default);
                 }
-            }
-            result.push(c);
-        } else if c == '$' {
-            // Snippet placeholder
-            if let Some(&next) = chars.peek() {
-                if next == '{' {
-                    // ${...} syntax
-                    chars.next(); // consume '{'
-                    let (expanded, is_final) = parse_brace_placeholder(&mut chars);
-                    if is_final {
-                        cursor_offset = Some(result.len());
-                    }
-                    result.push_str(&expanded);
-                } else if next.is_ascii_digit() {
-                    // $n syntax
-                    let mut num = String::new();
-                    while let Some(&d) = chars.peek() {
-                        if d.is_ascii_digit() {
-                            num.push(chars.next().unwrap());
-                        } else {
-                            break;
-                        }
-                    }
-                    if num == "0" {
-                        cursor_offset = Some(result.len());
-                    }
-                    // Other tabstops expand to nothing
-                } else {
-                    // Not a valid placeholder, keep the $
-                    result.push(c);
+                let stop = stops.entry(*index).or_insert_with(|| Tabstop {
+                    index: *index,
+                    ranges: Vec::new(),
+                    choices: Vec::new(),
+                });
+                stop.ranges.push(start..text.len());
+                if stop.choices.is_empty() {
+                    stop.choices = choices.clone();
                 }
-            } else {
-                result.push(c);
             }
-        } else {
-            result.push(c);
         }
     }
+}
 
-    ExpandedSnippet {
-        cursor_offset: cursor_offset.unwrap_or(result.len()),
-        text: result,
+fn push_text(nodes: &mut Vec<Node>, c: char) {
+    match nodes.last_mut() {
+        Some(Node::Text(s)) => s.push(c),
+        _ => nodes.push(Node::Text(c.to_string())),
     }
 }
 
-/// Parse a ${...} placeholder
-/// Returns (expanded_text, is_final_cursor)
-fn parse_brace_placeholder(chars: &mut std::iter::Peekable<std::str::Chars>) -> (String, bool) {
-    let mut content = String::new();
-    let mut depth = 1;
+fn read_number(chars: &mut Peekable<Chars>) -> u32 {
+    let mut num = String::new();
+    while let Some(d) = chars.next_if(char::is_ascii_digit) {
+        num.push(d);
+    }
+    num.parse().unwrap_or(u32::MAX)
+}
 
-    // Collect everything until matching '}'
+/// Parse snippet nodes up to the end of input or, when `nested`, up to and
+/// including the `}` closing the enclosing placeholder.
+fn parse_nodes(chars: &mut Peekable<Chars>, nested: bool) -> Vec<Node> {
+    let mut nodes = Vec::new();
     while let Some(c) = chars.next() {
-        if c == '{' {
-            depth += 1;
-            content.push(c);
-        } else if c == '}' {
-            depth -= 1;
-            if depth == 0 {
-                break;
-            }
-            content.push(c);
-        } else if c == '\\' {
-            // Handle escapes inside braces
-            if let Some(&next) = chars.peek() {
-                if next == '$' || next == '\\' || next == '}' || next == '|' {
-                    content.push(chars.next().unwrap());
-                    continue;
+        match c {
+            '}' if nested => break,
+            '\\' => match chars.next_if(|next| matches!(next, '$' | '\\' | '}')) {
+                Some(escaped) => push_text(&mut nodes, escaped),
+                None => push_text(&mut nodes, c),
+            },
+            '$' => match chars.peek() {
+                Some(d) if d.is_ascii_digit() => nodes.push(Node::Tabstop {
+                    index: read_number(chars),
+                    children: Vec::new(),
+                    choices: Vec::new(),
+                }),
+                Some('{') => {
+                    chars.next();
+                    nodes.extend(parse_brace_placeholder(chars));
                 }
-            }
-            content.push(c);
-        } else {
-            content.push(c);
+                // Not a valid placeholder, keep the $
+                _ => push_text(&mut nodes, c),
+            },
+            _ => push_text(&mut nodes, c),
         }
     }
+    nodes
+}
 
-    // Parse the content: n or n:default or n|choices|
-    let (tabstop, default) = parse_placeholder_content(&content);
+/// Parse a `${...}` placeholder, after the opening `${`.
+fn parse_brace_placeholder(chars: &mut Peekable<Chars>) -> Vec<Node> {
+    if chars.peek().is_some_and(char::is_ascii_digit) {
+        let index = read_number(chars);
+        let (children, choices) = match chars.next() {
+            Some(':') => (parse_nodes(chars, true), Vec::new()),
+            Some('|') => (Vec::new(), parse_choices(chars)),
+            Some('}') | None => (Vec::new(), Vec::new()),
+            // Transforms and anything else we don't understand: keep the
+            // tabstop, drop the rest.
+            Some(_) => {
+                skip_placeholder(chars);
+                (Vec::new(), Vec::new())
+            }
+        };
+        return vec![Node::Tabstop {
+            index,
+            children,
+            choices,
+        }];
+    }
 
-    let is_final = tabstop == Some(0);
-    (default, is_final)
+    // A variable: only its default text is inserted.
+    while chars
+        .next_if(|c| c.is_ascii_alphanumeric() || *c == '_')
+        .is_some()
+    {}
+    match chars.next() {
+        Some(':') => parse_nodes(chars, true),
+        Some('}') | None => Vec::new(),
+        Some(_) => {
+            skip_placeholder(chars);
+            Vec::new()
+        }
+    }
 }
 
-/// Parse placeholder content like "1", "1:default", or "1|a,b,c|"
-/// Returns (tabstop_number, default_text)
-fn parse_placeholder_content(content: &str) -> (Option<u32>, String) {
-    // Find the tabstop number
-    let mut chars = content.chars().peekable();
-    let mut num_str = String::new();
-
-    while let Some(&c) = chars.peek() {
-        if c.is_ascii_digit() {
-            num_str.push(chars.next().unwrap());
-        } else {
-            break;
+/// Parse the options of `${n|a,b|}`, after the opening `|`.
+fn parse_choices(chars: &mut Peekable<Chars>) -> Vec<String> {
+    let mut choices = vec![String::new()];
+    while let Some(c) = chars.next() {
+        let current = choices.last_mut().expect("choices is never empty");
+        match c {
+            '\\' => match chars.next_if(|next| matches!(next, ',' | '|' | '$' | '\\' | '}')) {
+                Some(escaped) => current.push(escaped),
+                None => current.push(c),
+            },
+            ',' => choices.push(String::new()),
+            '|' if chars.next_if_eq(&'}').is_some() => break,
+            _ => current.push(c),
         }
     }
+    choices
+}
 
-    let tabstop = num_str.parse::<u32>().ok();
-
-    // Check what follows
-    match chars.peek() {
-        Some(':') => {
-            // Default text: ${n:default}
-            chars.next(); // consume ':'
-            let default: String = chars.collect();
-            // Recursively expand nested snippets in default
-            let expanded = expand_snippet(&default);
-            (tabstop, expanded.text)
-        }
-        Some('|') => {
-            // Choices: ${n|choice1,choice2|}
-            chars.next(); // consume '|'
-            let choices: String = chars.collect();
-            // Remove trailing '|' if present
-            let choices = choices.trim_end_matches('|');
-            // Use first choice
-            let first_choice = choices.split(',').next().unwrap_or("");
-            (tabstop, first_choice.to_string())
-        }
-        _ => {
-            // Just a tabstop number, no default
-            (tabstop, String::new())
+/// Skip to just past the `}` closing the current placeholder.
+fn skip_placeholder(chars: &mut Peekable<Chars>) {
+    let mut depth = 1;
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return;
+                }
+            }
+            _ => {}
         }
     }
 }
@@ -277,4 +388,40 @@ mod tests {
         assert_eq!(result.text, "foo()");
         assert_eq!(result.cursor_offset, 5);
     }
+
+    #[test]
+    fn test_parse_tabstops_in_navigation_order() {
+        let parsed = parse_snippet("for ${2:item} in ${1:items} {\n\t$0\n}");
+        assert_eq!(parsed.text, "for item in items {\n\t\n}");
+        let order: Vec<u32> = parsed.tabstops.iter().map(|t| t.index).collect();
+        assert_eq!(order, vec![1, 2, 0]);
+        assert_eq!(parsed.tabstops[0].ranges, vec![12..17]);
+        assert_eq!(parsed.tabstops[1].ranges, vec![4..8]);
+        assert_eq!(parsed.final_offset(), 21);
+    }
+
+    #[test]
+    fn test_parse_mirrors_share_placeholder_text() {
+        let parsed = parse_snippet("<$1>${1:div}</$1>");
+        assert_eq!(parsed.text, "<div>div</div>");
+        assert_eq!(parsed.tabstops[0].ranges, vec![1..4, 5..8, 10..13]);
+        // Implicit final stop at the end.
+        assert_eq!(parsed.tabstops[1].ranges, vec![14..14]);
+    }
+
+    #[test]
+    fn test_parse_choices() {
+        let parsed = parse_snippet("${1|one,t\\,wo,three|} $0");
+        assert_eq!(parsed.text, "one ");
+        assert_eq!(parsed.tabstops[0].choices, vec!["one", "t,wo", "three"]);
+        assert!(parsed.has_placeholders());
+        assert!(!parse_snippet("foo($0)").has_placeholders());
+    }
+
+    #[test]
+    fn test_variables_expand_to_default() {
+        assert_eq!(expand_snippet("${TM_FILENAME:main.rs}!").text, "main.rs!");
+        assert_eq!(expand_snippet("${TM_FILENAME}!").text, "!");
+        assert_eq!(expand_snippet("${1/(.*)/$1/}x").text, "x");
+    }
 }
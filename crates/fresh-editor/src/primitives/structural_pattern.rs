@@ -0,0 +1,549 @@
+//! Structural (syntax tree) search patterns.
+//!
+//! A pattern is a snippet of code in the target language in which `$NAME`
+//! stands for any single syntax node, `$$$NAME` for any run of sibling nodes
+//! (possibly none), and `$_` / `$$$` for nodes that are matched but not
+//! captured. Metavariable names are upper-case, as in ast-grep. The pattern
+//! is parsed with the language's tree-sitter grammar and compared node by
+//! node against a file's parse tree, so text inside strings and comments
+//! never matches code. A metavariable used twice must capture the same text
+//! both times.
+//!
+//! Punctuation the target has but the pattern leaves out (a trailing `;`, a
+//! trailing comma) is skipped; everything written in the pattern has to be
+//! there. A [`Rewrite`] template substitutes the captures of a match into
+//! replacement text.
+
+use std::ops::Range;
+
+use fresh_languages::tree_sitter::{Node, Parser, Tree};
+
+use crate::primitives::highlighter::Language;
+
+/// Placeholders the metavariables are swapped for before the pattern is
+/// parsed: an identifier in every bundled grammar.
+const CAPTURE_PREFIX: &str = "__FRESH_META_";
+const MULTI_PREFIX: &str = "__FRESH_MULTI_";
+
+/// A parsed structural search pattern.
+#[derive(Debug, Clone)]
+pub struct StructuralPattern {
+    language: Language,
+    root: PatternNode,
+    /// Names of the capturing metavariables, in pattern order.
+    metavariables: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+enum PatternNode {
+    /// `$NAME` / `$_`: exactly one named node.
+    Capture(Option<String>),
+    /// `$$$NAME` / `$$$`: any run of sibling nodes.
+    Multi(Option<String>),
+    /// A node without children, compared by kind and text.
+    Leaf { kind: u16, text: Vec<u8> },
+    /// A node compared by kind and then child by child.
+    Node {
+        kind: u16,
+        children: Vec<PatternNode>,
+    },
+}
+
+/// One place a pattern matched.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StructuralMatch {
+    /// Byte range of the matched node.
+    pub range: Range<usize>,
+    /// Byte range captured by each metavariable.
+    pub captures: Vec<(String, Range<usize>)>,
+}
+
+impl StructuralMatch {
+    /// Byte range captured by the metavariable `name`.
+    pub fn capture(&self, name: &str) -> Option<Range<usize>> {
+        self.captures
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, r)| r.clone())
+    }
+}
+
+impl StructuralPattern {
+    /// Parse `pattern` as code in `language`.
+    pub fn new(pattern: &str, language: Language) -> Result<Self, String> {
+        let pattern = pattern.trim();
+        if pattern.is_empty() {
+            return Err("Empty pattern".to_string());
+        }
+        let Some(ts_language) = language.ts_language() else {
+            return Err(format!(
+                "No syntax tree grammar for {}",
+                language.display_name()
+            ));
+        };
+        let (prefix, suffix) = pattern_context(language);
+        let source = format!("{prefix}{}{suffix}", substitute_metavariables(pattern));
+        let mut parser = Parser::new();
+        parser.set_language(&ts_language).map_err(|e| {
+            format!(
+                "Failed to load the {} grammar: {e}",
+                language.display_name()
+            )
+        })?;
+        let tree = parser
+            .parse(source.as_bytes(), None)
+            .ok_or_else(|| "Failed to parse the pattern".to_string())?;
+        if contains_error(tree.root_node()) {
+            return Err(format!(
+                "The pattern is not valid {}",
+                language.display_name()
+            ));
+        }
+
+        // The outermost node spanning exactly the pattern, then down through
+        // wrappers that add nothing (a statement around its expression).
+        let span = prefix.len()..source.len() - suffix.len();
+        let mut node = tree
+            .root_node()
+            .descendant_for_byte_range(span.start, span.end)
+            .ok_or_else(|| "Failed to parse the pattern".to_string())?;
+        while let Some(parent) = node.parent().filter(|p| p.byte_range() == span) {
+            node = parent;
+        }
+        while let [child] = significant_children(node)[..] {
+            if !child.is_named() || child.byte_range() != node.byte_range() {
+                break;
+            }
+            node = child;
+        }
+        if node.byte_range() != span
+            || (node.parent().is_none() && significant_children(node).len() > 1)
+        {
+            return Err("The pattern must be a single expression or statement".to_string());
+        }
+
+        let mut metavariables = Vec::new();
+        let root = convert(node, source.as_bytes(), &mut metavariables);
+        Ok(Self {
+            language,
+            root,
+            metavariables,
+        })
+    }
+
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    /// Names of the capturing metavariables, in pattern order.
+    pub fn metavariables(&self) -> &[String] {
+        &self.metavariables
+    }
+
+    /// Parse `source` and find every match, outermost first, in source
+    /// order. Matches never overlap: the inside of a match is not searched.
+    pub fn find_matches(&self, source: &[u8]) -> Vec<StructuralMatch> {
+        let Some(tree) = self.parse(source) else {
+            return Vec::new();
+        };
+        self.find_in_tree(&tree, source)
+    }
+
+    /// Find every match in an already parsed tree of `source`.
+    pub fn find_in_tree(&self, tree: &Tree, source: &[u8]) -> Vec<StructuralMatch> {
+        let mut matches = Vec::new();
+        let mut cursor = tree.walk();
+        'walk: loop {
+            let node = cursor.node();
+            let mut captures = Vec::new();
+            if !node.is_extra() && match_node(&self.root, node, source, &mut captures) {
+                matches.push(StructuralMatch {
+                    range: node.byte_range(),
+                    captures,
+                });
+            } else if cursor.goto_first_child() {
+                continue;
+            }
+            while !cursor.goto_next_sibling() {
+                if !cursor.goto_parent() {
+                    break 'walk;
+                }
+            }
+        }
+        matches
+    }
+
+    fn parse(&self, source: &[u8]) -> Option<Tree> {
+        let mut parser = Parser::new();
+        parser.set_language(&self.language.ts_language()?).ok()?;
+        parser.parse(source, None)
+    }
+}
+
+/// Code the pattern is parsed inside of, for grammars where it would
+/// otherwise mean something else. Go only allows statements in a function
+/// body, and at the top level `fmt.Println(x)` parses as a type conversion.
+fn pattern_context(language: Language) -> (&'static str, &'static str) {
+    match language {
+        Language::Go => ("func _() {\n", "\n}"),
+        _ => ("", ""),
+    }
+}
+
+/// Replace `$NAME`, `$_`, `$$$NAME` and `$$$` with placeholder identifiers.
+/// A `$` not followed by an upper-case name (or `_`) is left alone.
+fn substitute_metavariables(pattern: &str) -> String {
+    let mut out = String::with_capacity(pattern.len() + 16);
+    let mut rest = pattern;
+    while let Some(pos) = rest.find('$') {
+        out.push_str(&rest[..pos]);
+        rest = &rest[pos..];
+        if let Some(after) = rest.strip_prefix("$$$") {
+            let name_len = metavariable_name_len(after);
+            out.push_str(MULTI_PREFIX);
+            out.push_str(&after[..name_len]);
+            rest = &after[name_len..];
+            continue;
+        }
+        let after = &rest[1..];
+        let name_len = metavariable_name_len(after);
+        if name_len == 0 {
+            out.push('$');
+        } else {
+            out.push_str(CAPTURE_PREFIX);
+            out.push_str(&after[..name_len]);
+        }
+        rest = &after[name_len..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Length of the metavariable name at the start of `s`: upper-case letters,
+/// digits and `_`, not starting with a digit.
+fn metavariable_name_len(s: &str) -> usize {
+    let len = s
+        .bytes()
+        .take_while(|b| b.is_ascii_uppercase() || b.is_ascii_digit() || *b == b'_')
+        .count();
+    if s.as_bytes().first().is_some_and(u8::is_ascii_digit) {
+        0
+    } else {
+        len
+    }
+}
+
+/// A captured name, or `None` for the anonymous `$_` / `$$$`.
+fn capture_name(name: &str) -> Option<String> {
+    (!name.is_empty() && name != "_").then(|| name.to_string())
+}
+
+fn contains_error(node: Node) -> bool {
+    if node.is_error() {
+        return true;
+    }
+    if !node.has_error() {
+        return false;
+    }
+    let mut cursor = node.walk();
+    let children: Vec<Node> = node.children(&mut cursor).collect();
+    children.into_iter().any(contains_error)
+}
+
+/// Children that take part in matching: comments and the zero-width tokens
+/// tree-sitter inserts to recover from a missing `;` are left out.
+fn significant_children(node: Node) -> Vec<Node> {
+    let mut cursor = node.walk();
+    node.children(&mut cursor)
+        .filter(|child| !child.is_extra() && !child.is_missing())
+        .collect()
+}
+
+fn convert(node: Node, source: &[u8], metavariables: &mut Vec<String>) -> PatternNode {
+    let text = &source[node.byte_range()];
+    let placeholder = |prefix: &str| {
+        std::str::from_utf8(text)
+            .ok()
+            .and_then(|t| t.strip_prefix(prefix))
+            .filter(|name| metavariable_name_len(name) == name.len())
+            .map(capture_name)
+    };
+    let mut declare = |name: &Option<String>| {
+        if let Some(name) = name {
+            if !metavariables.contains(name) {
+                metavariables.push(name.clone());
+            }
+        }
+    };
+    if let Some(name) = placeholder(MULTI_PREFIX) {
+        declare(&name);
+        return PatternNode::Multi(name);
+    }
+    if let Some(name) = placeholder(CAPTURE_PREFIX) {
+        declare(&name);
+        return PatternNode::Capture(name);
+    }
+    let children = significant_children(node);
+    if children.is_empty() {
+        PatternNode::Leaf {
+            kind: node.kind_id(),
+            text: text.to_vec(),
+        }
+    } else {
+        PatternNode::Node {
+            kind: node.kind_id(),
+            children: children
+                .into_iter()
+                .map(|child| convert(child, source, metavariables))
+                .collect(),
+        }
+    }
+}
+
+/// Record `name` capturing `range`, or check it against the text captured
+/// earlier under the same name.
+fn bind(
+    name: &Option<String>,
+    range: Range<usize>,
+    source: &[u8],
+    captures: &mut Vec<(String, Range<usize>)>,
+) -> bool {
+    let Some(name) = name else {
+        return true;
+    };
+    if let Some((_, previous)) = captures.iter().find(|(n, _)| n == name) {
+        return source[previous.clone()] == source[range];
+    }
+    captures.push((name.clone(), range));
+    true
+}
+
+fn match_node(
+    pattern: &PatternNode,
+    node: Node,
+    source: &[u8],
+    captures: &mut Vec<(String, Range<usize>)>,
+) -> bool {
+    match pattern {
+        PatternNode::Capture(name) => {
+            node.is_named() && bind(name, node.byte_range(), source, captures)
+        }
+        // Only reachable as a pattern's root; inside a node it is handled
+        // by `match_children`.
+        PatternNode::Multi(name) => bind(name, node.byte_range(), source, captures),
+        PatternNode::Leaf { kind, text } => {
+            node.kind_id() == *kind && source[node.byte_range()] == text[..]
+        }
+        PatternNode::Node { kind, children } => {
+            node.kind_id() == *kind
+                && match_children(children, &significant_children(node), source, captures)
+        }
+    }
+}
+
+/// Match a pattern's children against a node's, backtracking over the
+/// lengths of `$$$` runs. Unnamed target nodes the pattern does not mention
+/// are skipped.
+fn match_children(
+    pattern: &[PatternNode],
+    nodes: &[Node],
+    source: &[u8],
+    captures: &mut Vec<(String, Range<usize>)>,
+) -> bool {
+    let Some((first, rest)) = pattern.split_first() else {
+        return nodes.iter().all(|node| !node.is_named());
+    };
+    let saved = captures.len();
+    if let PatternNode::Multi(name) = first {
+        for taken in 0..=nodes.len() {
+            let range = match (nodes.first(), taken) {
+                (Some(start), 1..) => start.start_byte()..nodes[taken - 1].end_byte(),
+                (Some(next), 0) => next.start_byte()..next.start_byte(),
+                (None, _) => 0..0,
+            };
+            if bind(name, range, source, captures)
+                && match_children(rest, &nodes[taken..], source, captures)
+            {
+                return true;
+            }
+            captures.truncate(saved);
+        }
+        return false;
+    }
+    let Some((node, remaining)) = nodes.split_first() else {
+        return false;
+    };
+    if match_node(first, *node, source, captures)
+        && match_children(rest, remaining, source, captures)
+    {
+        return true;
+    }
+    captures.truncate(saved);
+    !node.is_named() && match_children(pattern, remaining, source, captures)
+}
+
+/// Replacement text with `$NAME` / `$$$NAME` standing for what a match
+/// captured.
+#[derive(Debug, Clone)]
+pub struct Rewrite {
+    parts: Vec<RewritePart>,
+}
+
+#[derive(Debug, Clone)]
+enum RewritePart {
+    Text(String),
+    Capture(String),
+}
+
+impl Rewrite {
+    /// Parse `template`, checking that every metavariable it uses is one
+    /// `pattern` captures.
+    pub fn new(template: &str, pattern: &StructuralPattern) -> Result<Self, String> {
+        let mut parts = Vec::new();
+        let mut text = String::new();
+        let mut rest = template;
+        while let Some(pos) = rest.find('$') {
+            text.push_str(&rest[..pos]);
+            let after = rest[pos..].strip_prefix("$$$").unwrap_or(&rest[pos + 1..]);
+            let name_len = metavariable_name_len(after);
+            if name_len == 0 {
+                text.push_str(&rest[pos..pos + 1]);
+                rest = &rest[pos + 1..];
+                continue;
+            }
+            let name = &after[..name_len];
+            if !pattern.metavariables.iter().any(|m| m == name) {
+                return Err(format!(
+                    "${name} in the replacement is not captured by the pattern"
+                ));
+            }
+            if !text.is_empty() {
+                parts.push(RewritePart::Text(std::mem::take(&mut text)));
+            }
+            parts.push(RewritePart::Capture(name.to_string()));
+            rest = &after[name_len..];
+        }
+        text.push_str(rest);
+        if !text.is_empty() {
+            parts.push(RewritePart::Text(text));
+        }
+        Ok(Self { parts })
+    }
+
+    /// The replacement for `m`, a match in `source`.
+    pub fn expand(&self, source: &[u8], m: &StructuralMatch) -> String {
+        let mut out = String::new();
+        for part in &self.parts {
+            match part {
+                RewritePart::Text(text) => out.push_str(text),
+                RewritePart::Capture(name) => {
+                    if let Some(range) = m.capture(name) {
+                        out.push_str(&String::from_utf8_lossy(&source[range]));
+                    }
+                }
+            }
+        }
+        out
+    }
+}
+
+#[cfg(all(test, feature = "tree-sitter"))]
+mod tests {
+    use super::*;
+
+    fn matched<'a>(pattern: &StructuralPattern, source: &'a str) -> Vec<&'a str> {
+        pattern
+            .find_matches(source.as_bytes())
+            .into_iter()
+            .map(|m| &source[m.range])
+            .collect()
+    }
+
+    fn rewrite(pattern: &str, template: &str, source: &str) -> Vec<String> {
+        let pattern = StructuralPattern::new(pattern, Language::JavaScript).unwrap();
+        let rewrite = Rewrite::new(template, &pattern).unwrap();
+        pattern
+            .find_matches(source.as_bytes())
+            .iter()
+            .map(|m| rewrite.expand(source.as_bytes(), m))
+            .collect()
+    }
+
+    #[test]
+    fn matches_code_but_not_strings_or_comments() {
+        let pattern = StructuralPattern::new("foo($A, $B)", Language::JavaScript).unwrap();
+        let source = "foo(1, bar(2));\nlet s = \"foo(1, 2)\"; // foo(3, 4)\nfoo(x);\n";
+        assert_eq!(matched(&pattern, source), vec!["foo(1, bar(2))"]);
+        assert_eq!(pattern.metavariables(), ["A", "B"]);
+    }
+
+    #[test]
+    fn rewrites_with_captured_nodes() {
+        assert_eq!(
+            rewrite("foo($A, $B)", "foo($B, $A)", "foo(a.b, g(1, 2));"),
+            vec!["foo(g(1, 2), a.b)"]
+        );
+    }
+
+    #[test]
+    fn multi_metavariables_capture_runs_of_nodes() {
+        assert_eq!(
+            rewrite(
+                "log($$$ARGS)",
+                "logger.info($$$ARGS)",
+                "log();\nlog(a);\nlog(a, b, c);"
+            ),
+            vec!["logger.info()", "logger.info(a)", "logger.info(a, b, c)"]
+        );
+        assert_eq!(
+            rewrite("f($FIRST, $$$REST)", "$REST", "f(1, 2, 3); f(1);"),
+            vec!["2, 3"]
+        );
+    }
+
+    #[test]
+    fn repeated_metavariables_must_capture_the_same_text() {
+        let pattern = StructuralPattern::new("$A === $A", Language::JavaScript).unwrap();
+        assert_eq!(matched(&pattern, "x === x; x === y;"), vec!["x === x"]);
+    }
+
+    #[test]
+    fn wildcards_match_without_capturing() {
+        let pattern = StructuralPattern::new("f($_, $_)", Language::JavaScript).unwrap();
+        assert_eq!(matched(&pattern, "f(1, 2); f(3);"), vec!["f(1, 2)"]);
+        assert!(pattern.metavariables().is_empty());
+    }
+
+    #[test]
+    fn punctuation_missing_from_the_pattern_is_skipped() {
+        let pattern = StructuralPattern::new("const $A = $B", Language::TypeScript).unwrap();
+        assert_eq!(
+            matched(&pattern, "const x = 1;\nlet y = 2;\n"),
+            vec!["const x = 1;"]
+        );
+    }
+
+    #[test]
+    fn matches_are_outermost_and_do_not_overlap() {
+        let pattern = StructuralPattern::new("f($A)", Language::JavaScript).unwrap();
+        assert_eq!(matched(&pattern, "f(f(1));"), vec!["f(f(1))"]);
+    }
+
+    #[test]
+    fn go_patterns_parse_outside_a_function() {
+        let pattern = StructuralPattern::new("fmt.Println($$$A)", Language::Go).unwrap();
+        let source = "package main\n\nfunc main() {\n\tfmt.Println(\"hi\", x)\n}\n";
+        assert_eq!(matched(&pattern, source), vec!["fmt.Println(\"hi\", x)"]);
+    }
+
+    #[test]
+    fn invalid_patterns_and_rewrites_are_rejected() {
+        assert!(StructuralPattern::new("foo(", Language::JavaScript).is_err());
+        assert!(StructuralPattern::new("a; b;", Language::JavaScript).is_err());
+        assert!(StructuralPattern::new("foo()", Language::Rust)
+            .is_err_and(|e| e.contains("No syntax tree grammar")));
+        let pattern = StructuralPattern::new("foo($A)", Language::JavaScript).unwrap();
+        assert!(Rewrite::new("bar($B)", &pattern).is_err());
+        assert!(Rewrite::new("cost $5 $A", &pattern).is_ok());
+    }
+}
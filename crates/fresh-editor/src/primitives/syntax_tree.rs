@@ -0,0 +1,256 @@
+//! Whole-buffer tree-sitter parse, cached per buffer version.
+//!
+//! Features that need the syntax tree rather than highlight spans (sticky
+//! scroll, expand selection) share one parse per buffer through
+//! [`SyntaxTreeCache`]. Only languages with a grammar compiled in get a
+//! tree, and only buffers up to [`MAX_SYNTAX_PARSE_BYTES`].
+//!
+//! Edits are fed to the cached tree as they happen ([`SyntaxTreeCache::
+//! notify_insert`] / [`SyntaxTreeCache::notify_delete`]), so the next parse
+//! is incremental and only re-examines what changed. A change the cache
+//! wasn't told about (a reload, say) falls back to a full parse.
+
+use crate::model::buffer::Buffer;
+use crate::primitives::highlighter::Language;
+
+/// Largest buffer parsed with tree-sitter.
+pub(crate) const MAX_SYNTAX_PARSE_BYTES: usize = 512 * 1024;
+
+/// The syntax tree of one buffer, reparsed when the buffer changes.
+#[derive(Default)]
+pub struct SyntaxTreeCache {
+    #[cfg(feature = "tree-sitter")]
+    parsed: Option<ParsedTree>,
+}
+
+#[cfg(feature = "tree-sitter")]
+struct ParsedTree {
+    language: Language,
+    parser: fresh_languages::tree_sitter::Parser,
+    /// Buffer version `tree` describes: the version it was parsed from, or
+    /// the one the edits fed to it since bring it to.
+    version: u64,
+    tree: Option<fresh_languages::tree_sitter::Tree>,
+    /// Edits were applied to `tree` since it was parsed, so it needs an
+    /// incremental reparse before use.
+    edited: bool,
+}
+
+/// Where a point ends up after `text` is written starting at `start`.
+#[cfg(feature = "tree-sitter")]
+fn point_after(
+    start: fresh_languages::tree_sitter::Point,
+    text: &str,
+) -> fresh_languages::tree_sitter::Point {
+    match text.rfind('\n') {
+        Some(last) => fresh_languages::tree_sitter::Point {
+            row: start.row + text.matches('\n').count(),
+            column: text.len() - last - 1,
+        },
+        None => fresh_languages::tree_sitter::Point {
+            row: start.row,
+            column: start.column + text.len(),
+        },
+    }
+}
+
+impl SyntaxTreeCache {
+    /// The syntax tree of `buffer` in `language`, or `None` when the
+    /// language has no grammar compiled in or the buffer is too large.
+    #[cfg(feature = "tree-sitter")]
+    pub fn tree(
+        &mut self,
+        buffer: &Buffer,
+        language: Option<&Language>,
+    ) -> Option<&fresh_languages::tree_sitter::Tree> {
+        let language = *language?;
+        let ts_language = language.ts_language()?;
+        if buffer.len() > MAX_SYNTAX_PARSE_BYTES {
+            return None;
+        }
+        if self
+            .parsed
+            .as_ref()
+            .is_none_or(|parsed| parsed.language != language)
+        {
+            let mut parser = fresh_languages::tree_sitter::Parser::new();
+            if parser.set_language(&ts_language).is_err() {
+                tracing::warn!("Failed to set language for syntax tree parser");
+                return None;
+            }
+            self.parsed = Some(ParsedTree {
+                language,
+                parser,
+                version: 0,
+                tree: None,
+                edited: false,
+            });
+        }
+        let parsed = self.parsed.as_mut()?;
+        let current = parsed.tree.is_some() && parsed.version == buffer.version();
+        if !current || parsed.edited {
+            let source = buffer.slice_bytes(0..buffer.len());
+            let old_tree = if current { parsed.tree.as_ref() } else { None };
+            parsed.tree = parsed.parser.parse(&source, old_tree);
+            parsed.version = buffer.version();
+            parsed.edited = false;
+        }
+        parsed.tree.as_ref()
+    }
+
+    /// Tell the cached tree about `text` inserted at byte `position` by the
+    /// edit that took `buffer` (already edited) from `version_before` to its
+    /// current version.
+    pub fn notify_insert(
+        &mut self,
+        buffer: &Buffer,
+        version_before: u64,
+        position: usize,
+        text: &str,
+    ) {
+        #[cfg(feature = "tree-sitter")]
+        self.notify_edit(buffer, version_before, position, "", text);
+        #[cfg(not(feature = "tree-sitter"))]
+        let _ = (buffer, version_before, position, text);
+    }
+
+    /// Tell the cached tree about `deleted` removed from `range` by the edit
+    /// that took `buffer` (already edited) from `version_before` to its
+    /// current version.
+    pub fn notify_delete(
+        &mut self,
+        buffer: &Buffer,
+        version_before: u64,
+        range: &std::ops::Range<usize>,
+        deleted: &str,
+    ) {
+        #[cfg(feature = "tree-sitter")]
+        {
+            if deleted.len() != range.len() {
+                // Can't describe the edit; reparse in full next time.
+                if let Some(parsed) = self.parsed.as_mut() {
+                    parsed.tree = None;
+                }
+                return;
+            }
+            self.notify_edit(buffer, version_before, range.start, deleted, "");
+        }
+        #[cfg(not(feature = "tree-sitter"))]
+        let _ = (buffer, version_before, range, deleted);
+    }
+
+    #[cfg(feature = "tree-sitter")]
+    fn notify_edit(
+        &mut self,
+        buffer: &Buffer,
+        version_before: u64,
+        start: usize,
+        removed: &str,
+        inserted: &str,
+    ) {
+        let Some(parsed) = self.parsed.as_mut() else {
+            return;
+        };
+        // A tree that was already behind is reparsed in full anyway.
+        if parsed.version != version_before {
+            return;
+        }
+        let Some(tree) = parsed.tree.as_mut() else {
+            return;
+        };
+        let Some(position) = buffer.offset_to_position(start) else {
+            parsed.tree = None;
+            return;
+        };
+        let start_point = fresh_languages::tree_sitter::Point {
+            row: position.line,
+            column: position.column,
+        };
+        tree.edit(&fresh_languages::tree_sitter::InputEdit {
+            start_byte: start,
+            old_end_byte: start + removed.len(),
+            new_end_byte: start + inserted.len(),
+            start_position: start_point,
+            old_end_position: point_after(start_point, removed),
+            new_end_position: point_after(start_point, inserted),
+        });
+        parsed.version = buffer.version();
+        parsed.edited = true;
+    }
+}
+
+#[cfg(all(test, feature = "tree-sitter"))]
+mod tests {
+    use super::*;
+
+    /// The tree's S-expression plus every node's start and end point, so a
+    /// stale row/column shows up as well as a wrong shape.
+    fn describe(tree: &fresh_languages::tree_sitter::Tree) -> Vec<String> {
+        let mut out = vec![tree.root_node().to_sexp()];
+        let mut cursor = tree.walk();
+        loop {
+            let node = cursor.node();
+            out.push(format!(
+                "{} {:?}-{:?} {:?}",
+                node.kind(),
+                node.start_position(),
+                node.end_position(),
+                node.byte_range()
+            ));
+            if cursor.goto_first_child() || cursor.goto_next_sibling() {
+                continue;
+            }
+            loop {
+                if !cursor.goto_parent() {
+                    return out;
+                }
+                if cursor.goto_next_sibling() {
+                    break;
+                }
+            }
+        }
+    }
+
+    fn full_parse(buffer: &Buffer) -> Vec<String> {
+        let mut cache = SyntaxTreeCache::default();
+        describe(cache.tree(buffer, Some(&Language::JavaScript)).unwrap())
+    }
+
+    #[test]
+    fn edits_reparse_incrementally_to_the_same_tree() {
+        let mut buffer = Buffer::from_str_test("function f() {\n  return 1;\n}\n");
+        let mut cache = SyntaxTreeCache::default();
+        cache.tree(&buffer, Some(&Language::JavaScript)).unwrap();
+
+        // A multi-line insert, then a delete spanning a newline.
+        let at = buffer.len() - 2;
+        let before = buffer.version();
+        let text = "\n  let x = [1,\n    2];";
+        buffer.insert(at, text);
+        cache.notify_insert(&buffer, before, at, text);
+        assert!(cache.parsed.as_ref().unwrap().edited);
+        let incremental = describe(cache.tree(&buffer, Some(&Language::JavaScript)).unwrap());
+        assert_eq!(incremental, full_parse(&buffer));
+
+        let deleted = ",\n    2";
+        let start = buffer.to_string().unwrap().find(deleted).unwrap();
+        let range = start..start + deleted.len();
+        let before = buffer.version();
+        buffer.delete(range.clone());
+        cache.notify_delete(&buffer, before, &range, deleted);
+        let incremental = describe(cache.tree(&buffer, Some(&Language::JavaScript)).unwrap());
+        assert_eq!(incremental, full_parse(&buffer));
+    }
+
+    #[test]
+    fn untracked_changes_fall_back_to_a_full_parse() {
+        let mut buffer = Buffer::from_str_test("let a = 1;\n");
+        let mut cache = SyntaxTreeCache::default();
+        cache.tree(&buffer, Some(&Language::JavaScript)).unwrap();
+
+        buffer.insert(0, "let b = 2;\n");
+        let tree = describe(cache.tree(&buffer, Some(&Language::JavaScript)).unwrap());
+        assert_eq!(tree, full_parse(&buffer));
+        assert_eq!(cache.parsed.as_ref().unwrap().version, buffer.version());
+    }
+}
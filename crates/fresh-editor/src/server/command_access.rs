@@ -308,7 +308,16 @@ pub fn run_script(
     let window_id = grant
         .window_id
         .unwrap_or_else(|| editor.active_window_id().0);
+    start_script(editor, source, window_id)
+}
 
+/// Evaluate `source` against `editor` with no authorization check, addressing
+/// it to `window_id`.
+///
+/// For callers that own the editor outright — `fresh --cmd script batch`
+/// builds one just to run the script — where there is no agent to hold a
+/// token. The outcome arrives exactly as for [`run_script`].
+pub fn start_script(editor: &mut Editor, source: &str, window_id: u64) -> CommandDispatch {
     let request_id = next_request_id();
     let wrapped = wrap_script(source, request_id, window_id);
     match editor.eval_agent_script(&wrapped, request_id) {
@@ -83,6 +83,18 @@ impl std::fmt::Debug for RemoteAttachReady {
     }
 }
 
+/// One file's report from a `workspace/diagnostic` pull.
+#[derive(Debug, Clone)]
+pub struct FileDiagnosticReport {
+    pub uri: String,
+    /// New result_id for the file's next pull
+    pub result_id: Option<String>,
+    /// Diagnostics (empty if unchanged)
+    pub diagnostics: Vec<Diagnostic>,
+    /// True if the file's diagnostics haven't changed since its previous result_id
+    pub unchanged: bool,
+}
+
 /// Messages sent from async tasks to the synchronous main loop
 #[derive(Debug)]
 pub enum AsyncMessage {
@@ -215,6 +227,26 @@ pub enum AsyncMessage {
         edits: Vec<lsp_types::TextEdit>,
     },
 
+    /// LSP textDocument/onTypeFormatting response
+    LspOnTypeFormatting {
+        request_id: u64,
+        edits: Vec<lsp_types::TextEdit>,
+    },
+
+    /// LSP textDocument/linkedEditingRange response (`None` when the
+    /// position has no linked ranges or the request failed)
+    LspLinkedEditingRanges {
+        request_id: u64,
+        ranges: Option<lsp_types::LinkedEditingRanges>,
+    },
+
+    /// LSP textDocument/selectionRange response: one chain of enclosing
+    /// ranges per requested position (`None` when the request failed)
+    LspSelectionRanges {
+        request_id: u64,
+        ranges: Option<Vec<lsp_types::SelectionRange>>,
+    },
+
     /// LSP textDocument/prepareRename response
     LspPrepareRename {
         request_id: u64,
@@ -238,6 +270,18 @@ pub enum AsyncMessage {
         unchanged: bool,
     },
 
+    /// LSP workspace diagnostics (workspace/diagnostic). Sent for each
+    /// partial result the server streams and once more, with `done` set,
+    /// when the request completes, fails or is cancelled.
+    LspWorkspaceDiagnostics {
+        request_id: u64,
+        /// Name of the server the reports came from (see `LspPulledDiagnostics`)
+        server_name: String,
+        reports: Vec<FileDiagnosticReport>,
+        /// True for the final message of the request
+        done: bool,
+    },
+
     /// LSP inlay hints response (textDocument/inlayHint)
     LspInlayHints {
         request_id: u64,
@@ -253,6 +297,39 @@ pub enum AsyncMessage {
         ranges: Vec<FoldingRange>,
     },
 
+    /// LSP document symbol response (textDocument/documentSymbol)
+    LspDocumentSymbols {
+        request_id: u64,
+        uri: String,
+        symbols: lsp_types::DocumentSymbolResponse,
+    },
+
+    /// LSP document link response (textDocument/documentLink)
+    LspDocumentLinks {
+        request_id: u64,
+        uri: String,
+        links: Vec<lsp_types::DocumentLink>,
+    },
+
+    /// LSP workspace symbol response (workspace/symbol)
+    LspWorkspaceSymbols {
+        request_id: u64,
+        symbols: lsp_types::WorkspaceSymbolResponse,
+    },
+
+    /// LSP code lens response (textDocument/codeLens)
+    LspCodeLenses {
+        request_id: u64,
+        uri: String,
+        lenses: Vec<lsp_types::CodeLens>,
+    },
+
+    /// LSP codeLens/resolve response
+    LspCodeLensResolved {
+        request_id: u64,
+        lens: Result<lsp_types::CodeLens, String>,
+    },
+
     /// LSP semantic tokens response (full, full/delta, or range)
     LspSemanticTokens {
         request_id: u64,
@@ -274,6 +351,10 @@ pub enum AsyncMessage {
     /// in file B, which the user never edited so was never otherwise re-pulled).
     LspInlayHintRefresh { language: String },
 
+    /// LSP server requests a code-lens refresh (workspace/codeLens/refresh).
+    /// Client should re-pull code lenses for all open documents.
+    LspCodeLensRefresh { language: String },
+
     /// LSP server requests a semantic-tokens refresh
     /// (workspace/semanticTokens/refresh). Client should re-pull semantic
     /// tokens for all open documents.
@@ -293,6 +374,61 @@ pub enum AsyncMessage {
         registrations: Vec<(String, Option<Value>)>,
     },
 
+    /// Debug adapter answered `initialize` and sent its `initialized`
+    /// event: breakpoints can be sent, followed by `configurationDone`.
+    DapInitialized { session_id: u64 },
+
+    /// Debuggee stopped (breakpoint, step, pause, exception).
+    DapStopped {
+        session_id: u64,
+        thread_id: Option<i64>,
+        reason: String,
+        description: Option<String>,
+    },
+
+    /// Debuggee resumed execution.
+    DapContinued { session_id: u64 },
+
+    /// Call stack of a stopped thread, with the scopes (and their
+    /// variables) of the requested frame.
+    DapStackTrace {
+        session_id: u64,
+        thread_id: i64,
+        frames: Vec<crate::services::dap::protocol::StackFrame>,
+        frame_id: Option<i64>,
+        scopes: Vec<crate::services::dap::protocol::ScopeVariables>,
+    },
+
+    /// Scopes and variables of a frame picked from the stack panel.
+    DapScopes {
+        session_id: u64,
+        frame_id: i64,
+        scopes: Vec<crate::services::dap::protocol::ScopeVariables>,
+    },
+
+    /// Debuggee or adapter output (`output` event).
+    DapOutput {
+        session_id: u64,
+        category: Option<String>,
+        output: String,
+    },
+
+    /// Debuggee exited with the given code.
+    DapExited { session_id: u64, exit_code: i64 },
+
+    /// Debug session ended (adapter `terminated` event or adapter exit).
+    DapTerminated { session_id: u64 },
+
+    /// A debug adapter request failed or the adapter could not be started.
+    DapError { session_id: u64, error: String },
+
+    /// A background task finished; `output` is its stdout then stderr.
+    TaskFinished {
+        run_id: u64,
+        output: String,
+        exit_code: i32,
+    },
+
     /// File changed externally (future: file watching)
     FileChanged { path: String },
 
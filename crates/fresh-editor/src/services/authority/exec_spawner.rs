@@ -0,0 +1,317 @@
+//! Generic exec-carrier process spawners.
+//!
+//! The carrier-agnostic sibling of `docker_spawner.rs` / `kube_spawner.rs`:
+//! Podman, Incus, Distrobox, `toolbox`, or any custom sandbox wrapper is
+//! described by an [`ExecCarrier`] — argv templates for one-shot and stdio
+//! runs — and every spawn is that template expanded around the command.
+//!
+//! Like `kubectl exec`, a generic carrier has no portable cwd/env flags, so
+//! both ride the same `sh -c 'cd …; exec env K=V "$0" "$@"'` hop
+//! ([`compose_sh_exec`]). When the workspace is bind-mounted at a different
+//! path on the far side, the authority's [`PathTranslation`] maps a host cwd
+//! to its far-side counterpart before the hop, so a plugin can keep using
+//! host paths exactly as it does for a devcontainer.
+
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::process::Command;
+
+use super::kube_spawner::{compose_sh_exec, shell_quote};
+use super::PathTranslation;
+use crate::services::process_hidden::HideWindow;
+use crate::services::remote::{
+    ExecCarrier, LongRunningSpawner, ProcessSpawner, SpawnError, SpawnResult, StdioChild,
+};
+use crate::services::workspace_trust::{gate, WorkspaceTrust};
+
+/// One-shot spawns through an exec carrier's `run` template.
+///
+/// `base_env` is the captured far-side env probe (notably `PATH`), replayed
+/// on every spawn and `command_exists` probe so binary discovery matches
+/// what a server will see — the same contract as the Docker spawner's
+/// `userEnvProbe` capture.
+pub(crate) struct ExecCarrierSpawner {
+    carrier: ExecCarrier,
+    base_env: Vec<(String, String)>,
+    path_translation: Option<PathTranslation>,
+    trust: Arc<WorkspaceTrust>,
+}
+
+impl ExecCarrierSpawner {
+    pub(crate) fn with_env(
+        carrier: ExecCarrier,
+        base_env: Vec<(String, String)>,
+        path_translation: Option<PathTranslation>,
+        trust: Arc<WorkspaceTrust>,
+    ) -> Self {
+        Self {
+            carrier,
+            base_env,
+            path_translation,
+            trust,
+        }
+    }
+
+    /// Test helper — no env probe, no translation, permissive trust.
+    #[cfg(test)]
+    pub(crate) fn new(carrier: ExecCarrier) -> Self {
+        Self::with_env(
+            carrier,
+            Vec::new(),
+            None,
+            Arc::new(WorkspaceTrust::permissive()),
+        )
+    }
+
+    /// The far-side working directory for a spawn: the per-call `cwd`
+    /// (translated host→far side when it lies under the mapped workspace,
+    /// passed through otherwise), falling back to the carrier's workspace.
+    fn far_cwd(&self, cwd: Option<&Path>) -> Option<PathBuf> {
+        match cwd {
+            Some(dir) => Some(
+                self.path_translation
+                    .as_ref()
+                    .and_then(|t| t.host_to_remote(dir))
+                    .unwrap_or_else(|| dir.to_path_buf()),
+            ),
+            None => self.carrier.workspace.as_deref().map(PathBuf::from),
+        }
+    }
+
+    /// The expanded host argv (program first) that runs `command args…` on
+    /// the far side in `cwd` with the merged env, through the `stdio` or
+    /// `run` template.
+    fn build_argv(
+        &self,
+        command: &str,
+        args: &[String],
+        cwd: Option<&Path>,
+        stdio: bool,
+        extra_env: &[(String, String)],
+    ) -> Vec<String> {
+        let far_cwd = self.far_cwd(cwd);
+        let (cmd, cargs) =
+            compose_sh_exec(&self.base_env, command, args, extra_env, far_cwd.as_deref());
+        let mut inner = Vec::with_capacity(cargs.len() + 1);
+        inner.push(cmd);
+        inner.extend(cargs);
+        if stdio {
+            self.carrier.stdio_argv(&inner)
+        } else {
+            self.carrier.run_argv(&inner)
+        }
+    }
+}
+
+/// Build a host [`Command`] from an expanded carrier argv.
+fn carrier_command(argv: &[String]) -> Result<Command, SpawnError> {
+    let (program, args) = argv
+        .split_first()
+        .ok_or_else(|| SpawnError::Process("exec carrier template is empty".to_string()))?;
+    let mut cmd = Command::new(program);
+    cmd.args(args);
+    cmd.hide_window();
+    Ok(cmd)
+}
+
+#[async_trait]
+impl ProcessSpawner for ExecCarrierSpawner {
+    async fn spawn(
+        &self,
+        command: String,
+        args: Vec<String>,
+        cwd: Option<String>,
+    ) -> Result<SpawnResult, SpawnError> {
+        gate(&self.trust, &command, cwd.as_deref())?;
+        let argv = self.build_argv(&command, &args, cwd.as_deref().map(Path::new), false, &[]);
+
+        let output = carrier_command(&argv)?
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .output()
+            .await
+            .map_err(|e| SpawnError::Process(e.to_string()))?;
+
+        Ok(SpawnResult {
+            stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+            stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+            exit_code: output.status.code().unwrap_or(-1),
+        })
+    }
+
+    async fn spawn_to_file(
+        &self,
+        _command: String,
+        _args: Vec<String>,
+        _cwd: Option<String>,
+        _stdout_to: std::path::PathBuf,
+    ) -> Result<SpawnResult, SpawnError> {
+        Err(SpawnError::Process(
+            "stdoutTo is not supported for exec-carrier processes".to_string(),
+        ))
+    }
+}
+
+/// Long-running spawner through an exec carrier's `stdio` template, so LSP
+/// servers run on the far side with piped JSON-RPC.
+///
+/// `spawned_locally = false` on the returned child: its PID is the host-side
+/// carrier CLI, not the server, so host resource controls skip themselves.
+pub(crate) struct ExecCarrierLongRunningSpawner {
+    inner: ExecCarrierSpawner,
+}
+
+impl ExecCarrierLongRunningSpawner {
+    pub(crate) fn with_env(
+        carrier: ExecCarrier,
+        base_env: Vec<(String, String)>,
+        path_translation: Option<PathTranslation>,
+        trust: Arc<WorkspaceTrust>,
+    ) -> Self {
+        Self {
+            inner: ExecCarrierSpawner::with_env(carrier, base_env, path_translation, trust),
+        }
+    }
+}
+
+#[async_trait]
+impl LongRunningSpawner for ExecCarrierLongRunningSpawner {
+    async fn spawn_stdio(
+        &self,
+        command: &str,
+        args: &[String],
+        env: Vec<(String, String)>,
+        cwd: Option<&Path>,
+        limits: Option<&crate::types::ProcessLimits>,
+    ) -> Result<StdioChild, SpawnError> {
+        gate(
+            &self.inner.trust,
+            command,
+            cwd.map(|p| p.to_string_lossy()).as_deref(),
+        )?;
+        // Same limitation as the Docker / K8s spawners: a cgroup or rlimit on
+        // the host-side carrier PID doesn't reach the far-side server.
+        if let Some(lim) = limits {
+            if lim.enabled && (lim.max_memory_percent.is_some() || lim.max_cpu_percent.is_some()) {
+                tracing::debug!(
+                    "ExecCarrierLongRunningSpawner: ignoring process_limits — host-side \
+                     cgroups/rlimits don't reach through the carrier (memory={:?}%, cpu={:?}%)",
+                    lim.max_memory_percent,
+                    lim.max_cpu_percent
+                );
+            }
+        }
+
+        let argv = self.inner.build_argv(command, args, cwd, true, &env);
+        let child = carrier_command(&argv)?
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| SpawnError::Process(e.to_string()))?;
+
+        Ok(StdioChild::from_tokio_child(child, false))
+    }
+
+    async fn command_exists(&self, command: &str) -> bool {
+        // `command -v` on the far side, with `base_env` replayed so the probe
+        // sees the PATH the server will (see the K8s spawner).
+        let mut script = String::new();
+        for (k, v) in &self.inner.base_env {
+            script.push_str(&format!("export {}={}; ", k, shell_quote(v)));
+        }
+        script.push_str(&format!("command -v {}", shell_quote(command)));
+        let argv = self
+            .inner
+            .carrier
+            .run_argv(&["sh".to_string(), "-c".to_string(), script]);
+
+        let Ok(mut cmd) = carrier_command(&argv) else {
+            return false;
+        };
+        match cmd
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .await
+        {
+            Ok(status) => status.success(),
+            Err(_) => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn podman() -> ExecCarrier {
+        ExecCarrier {
+            run: vec!["podman".into(), "exec".into(), "dev".into(), "{cmd}".into()],
+            interactive: None,
+            stdio: Some(vec![
+                "podman".into(),
+                "exec".into(),
+                "-i".into(),
+                "dev".into(),
+                "{cmd}".into(),
+            ]),
+            workspace: Some("/src".into()),
+            label: None,
+        }
+    }
+
+    #[test]
+    fn build_argv_defaults_cwd_to_carrier_workspace() {
+        let sp = ExecCarrierSpawner::new(podman());
+        let argv = sp.build_argv("ls", &[], None, false, &[]);
+        assert_eq!(
+            argv,
+            vec![
+                "podman",
+                "exec",
+                "dev",
+                "sh",
+                "-c",
+                "cd /src; exec \"$0\" \"$@\"",
+                "ls"
+            ]
+        );
+    }
+
+    #[test]
+    fn build_argv_translates_host_cwd_under_the_mapped_workspace() {
+        let sp = ExecCarrierSpawner::with_env(
+            podman(),
+            vec![("PATH".into(), "/usr/bin".into())],
+            Some(PathTranslation {
+                host_root: PathBuf::from("/home/me/proj"),
+                remote_root: PathBuf::from("/src"),
+            }),
+            Arc::new(WorkspaceTrust::permissive()),
+        );
+        let argv = sp.build_argv(
+            "cargo",
+            &["check".into()],
+            Some(Path::new("/home/me/proj/crates/a")),
+            true,
+            &[("RUST_LOG".into(), "info".into())],
+        );
+        assert_eq!(&argv[..4], &["podman", "exec", "-i", "dev"]);
+        assert_eq!(
+            argv[6],
+            "cd /src/crates/a; exec env PATH=/usr/bin RUST_LOG=info \"$0\" \"$@\""
+        );
+        assert_eq!(&argv[7..], &["cargo", "check"]);
+
+        // A cwd outside the mapped workspace passes through untouched.
+        let outside = sp.build_argv("ls", &[], Some(Path::new("/tmp")), false, &[]);
+        assert!(outside[5].starts_with("cd /tmp;"));
+    }
+}
@@ -67,10 +67,8 @@ impl KubectlLongRunningSpawner {
     }
 
     /// Compose the `(command, args)` to hand `kubectl exec` so the in-pod
-    /// process runs with `cwd` and the merged env applied. Returns the bare
-    /// `(command, args)` when neither is needed (no wrapper shell), otherwise
-    /// an `sh -lc '…'` wrapper. `base_env` is laid down first so per-call
-    /// `env` overrides it (`env` is last-assignment-wins).
+    /// process runs with `cwd` and the merged env applied. See
+    /// [`compose_sh_exec`].
     fn compose(
         &self,
         command: &str,
@@ -78,39 +76,55 @@ impl KubectlLongRunningSpawner {
         env: &[(String, String)],
         cwd: Option<&Path>,
     ) -> (String, Vec<String>) {
-        let mut merged: Vec<(String, String)> = self.base_env.clone();
-        merged.extend(env.iter().cloned());
+        compose_sh_exec(&self.base_env, command, args, env, cwd)
+    }
+}
 
-        if cwd.is_none() && merged.is_empty() {
-            return (command.to_string(), args.to_vec());
-        }
+/// Compose the `(command, args)` for a carrier that execs its argv directly
+/// (no remote shell, no cwd/env flags — `kubectl exec`, a generic exec
+/// carrier) so the far-side process runs with `cwd` and the merged env
+/// applied. Returns the bare `(command, args)` when neither is needed (no
+/// wrapper shell), otherwise an `sh -c '…'` wrapper. `base_env` is laid down
+/// first so per-call `env` overrides it (`env` is last-assignment-wins).
+pub(super) fn compose_sh_exec(
+    base_env: &[(String, String)],
+    command: &str,
+    args: &[String],
+    env: &[(String, String)],
+    cwd: Option<&Path>,
+) -> (String, Vec<String>) {
+    let mut merged: Vec<(String, String)> = base_env.to_vec();
+    merged.extend(env.iter().cloned());
 
-        let mut script = String::new();
-        if let Some(dir) = cwd {
-            script.push_str(&format!("cd {}; ", shell_quote(&dir.to_string_lossy())));
-        }
-        script.push_str("exec ");
-        if !merged.is_empty() {
-            script.push_str("env ");
-            for (k, v) in &merged {
-                script.push_str(&format!("{}={} ", k, shell_quote(v)));
-            }
-        }
-        // "$0" is the real command, "$@" its args — passed positionally after
-        // the script so no quoting of the user's argv is required.
-        script.push_str("\"$0\" \"$@\"");
+    if cwd.is_none() && merged.is_empty() {
+        return (command.to_string(), args.to_vec());
+    }
 
-        // `-c`, not `-lc`: env is injected explicitly via `env K=V` (from the
-        // captured probe), so a *login* shell is unwanted — it would source
-        // profile scripts that add startup latency and can leak noise onto the
-        // server's stdout. Same stance as `docker exec` (no login shell).
-        let mut a = Vec::with_capacity(args.len() + 3);
-        a.push("-c".to_string());
-        a.push(script);
-        a.push(command.to_string());
-        a.extend(args.iter().cloned());
-        ("sh".to_string(), a)
+    let mut script = String::new();
+    if let Some(dir) = cwd {
+        script.push_str(&format!("cd {}; ", shell_quote(&dir.to_string_lossy())));
     }
+    script.push_str("exec ");
+    if !merged.is_empty() {
+        script.push_str("env ");
+        for (k, v) in &merged {
+            script.push_str(&format!("{}={} ", k, shell_quote(v)));
+        }
+    }
+    // "$0" is the real command, "$@" its args — passed positionally after
+    // the script so no quoting of the user's argv is required.
+    script.push_str("\"$0\" \"$@\"");
+
+    // `-c`, not `-lc`: env is injected explicitly via `env K=V` (from the
+    // captured probe), so a *login* shell is unwanted — it would source
+    // profile scripts that add startup latency and can leak noise onto the
+    // server's stdout. Same stance as `docker exec` (no login shell).
+    let mut a = Vec::with_capacity(args.len() + 3);
+    a.push("-c".to_string());
+    a.push(script);
+    a.push(command.to_string());
+    a.extend(args.iter().cloned());
+    ("sh".to_string(), a)
 }
 
 #[async_trait]
@@ -196,7 +210,7 @@ impl LongRunningSpawner for KubectlLongRunningSpawner {
 /// Quote a single argument for POSIX `sh`. Bare when it's an obviously-safe
 /// token (matching the Docker spawner's allow-list, plus `=` for `K=V`
 /// env assignments), otherwise single-quoted with embedded quotes escaped.
-fn shell_quote(s: &str) -> String {
+pub(super) fn shell_quote(s: &str) -> String {
     if !s.is_empty()
         && s.chars().all(|c| {
             c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '/' | '+' | ':' | '@' | '=')
@@ -26,6 +26,9 @@
 //!   trust, env)` — used by the `fresh user@host:path` startup flow. `params`
 //!   and `remote_dir` build the `ssh -t …` terminal wrapper so the integrated
 //!   terminal opens on the remote host.
+//! - `Authority::kube(…)` / `Authority::exec(…)` — the `kubectl exec` and
+//!   generic exec-carrier (Podman, Incus, Distrobox, …) remote-agent stacks,
+//!   assembled by `connect_kube_authority` / `connect_exec_authority`.
 //! - `Authority::from_plugin_payload(payload, trust, env)` — built from the
 //!   `editor.setAuthority(...)` plugin op. The payload is a tagged shape
 //!   (filesystem kind + spawner kind + terminal wrapper + label); it stays
@@ -39,11 +42,13 @@ use serde::{Deserialize, Serialize};
 
 use crate::model::filesystem::{FileSystem, StdFileSystem};
 use crate::services::remote::{
-    build_kube_agent_terminal_args, build_kube_terminal_args, build_ssh_agent_terminal_args,
-    build_ssh_terminal_args, spawn_kube_reconnect_task, spawn_reconnect_task, ConnectionParams,
-    KubeConnection, KubeTarget, LocalLongRunningSpawner, LocalProcessSpawner, LongRunningSpawner,
-    ProcessSpawner, RemoteFileSystem, RemoteLongRunningSpawner, RemoteProcessSpawner,
-    SshConnection, SshError, TransportError,
+    build_exec_agent_terminal_args, build_exec_terminal_args, build_kube_agent_terminal_args,
+    build_kube_terminal_args, build_ssh_agent_terminal_args, build_ssh_terminal_args,
+    spawn_carrier_reconnect_task, spawn_reconnect_task, CarrierConnection, ConnectionParams,
+    ExecCarrier, ExecTransport, KubeTarget, KubectlExecTransport, LocalLongRunningSpawner,
+    LocalProcessSpawner, LongRunningSpawner, ProcessSpawner, RemoteFileSystem,
+    RemoteLongRunningSpawner, RemoteProcessSpawner, RemoteTransport, SshConnection, SshError,
+    TransportError,
 };
 use crate::services::workspace_trust::WorkspaceTrust;
 
@@ -159,6 +164,21 @@ impl TerminalWrapper {
         }
     }
 
+    /// Re-parent the integrated terminal through a generic exec carrier's
+    /// `interactive` template (`podman exec -it …`, `incus exec -t …`),
+    /// running `sh -lc 'cd <ws>; exec $SHELL -l'` on the far side. Same
+    /// re-parenting contract as [`Self::kube`]: cwd is pinned by the
+    /// wrapper's own args, so `manages_cwd` is true.
+    pub fn exec(carrier: &ExecCarrier, base_env: &[(String, String)]) -> Self {
+        let mut argv = build_exec_terminal_args(carrier, base_env);
+        let command = argv.remove(0);
+        Self {
+            command,
+            args: argv,
+            manages_cwd: true,
+        }
+    }
+
     /// Apply the user's `terminal.shell` config override on top of this
     /// wrapper. The override replaces `command` and `args` only when the
     /// wrapper leaves cwd management to the terminal manager
@@ -250,6 +270,18 @@ pub enum SpawnerSpec {
         #[serde(default)]
         env: Vec<(String, String)>,
     },
+    /// Run through a generic exec carrier (Podman, Incus, Distrobox, a custom
+    /// sandbox): the carrier's `run` template for one-shot spawns and its
+    /// `stdio` template for LSP servers. Pair with `path_translation` when
+    /// the workspace is mounted at a different path on the far side. `env`
+    /// is the captured far-side env probe, as for `docker-exec`. The carrier
+    /// is boxed to keep the payload (carried in async messages) small.
+    Exec {
+        #[serde(flatten)]
+        carrier: Box<ExecCarrier>,
+        #[serde(default)]
+        env: Vec<(String, String)>,
+    },
 }
 
 /// Terminal-wrapper kind chosen by a plugin payload.
@@ -267,6 +299,14 @@ pub enum TerminalWrapperSpec {
         #[serde(default = "default_true")]
         manages_cwd: bool,
     },
+    /// Open the terminal through a generic exec carrier's `interactive`
+    /// template, rooted at its `workspace` (see [`TerminalWrapper::exec`]).
+    Exec {
+        #[serde(flatten)]
+        carrier: Box<ExecCarrier>,
+        #[serde(default)]
+        env: Vec<(String, String)>,
+    },
 }
 
 fn default_true() -> bool {
@@ -324,7 +364,7 @@ pub struct Authority {
 /// How [`Authority::terminal_command`] composes an interactive argv with the
 /// authority's backend. Each backend pins the argv's working directory in the
 /// way that backend supports: a `-w` flag for `docker exec`, or a `cd <dir>`
-/// shell hop for `ssh` / `kubectl` (which have no cwd flag).
+/// shell hop for `ssh` / `kubectl` / exec carriers (which have no cwd flag).
 #[derive(Debug, Clone)]
 pub enum CommandWrap {
     /// Local: the argv is the PTY child directly; the terminal sets cwd.
@@ -349,6 +389,14 @@ pub enum CommandWrap {
         target: KubeTarget,
         base_env: Vec<(String, String)>,
     },
+    /// Generic exec carrier: re-exec the argv through the carrier's
+    /// `interactive` template rooted at its workspace, same shell-hop
+    /// contract as Kubernetes. Boxed: the templates would otherwise bloat
+    /// every `Authority` (and the messages that carry one).
+    Exec {
+        carrier: Box<ExecCarrier>,
+        base_env: Vec<(String, String)>,
+    },
 }
 
 /// A session's **execution scope**: the trust gate (*may it run?*) and env
@@ -412,6 +460,9 @@ impl Authority {
     /// `Direct` (local) and `Prefix` (container — its spec is owned by the
     /// plugin that built it) map to `Local`; the SSH and Kube wraps reconstruct
     /// their transport spec from the connection params they already hold.
+    /// `Exec` maps to `Local` too: it is built either from a plugin payload
+    /// (spec owned by the plugin) or by an `attachRemoteAgent` that records
+    /// its own spec, and there is no CLI form that would need deriving.
     pub fn session_spec(&self) -> SessionAuthoritySpec {
         match &self.command_wrap {
             CommandWrap::Ssh { params, remote_dir } => {
@@ -448,7 +499,9 @@ impl Authority {
                     command: None,
                 })
             }
-            CommandWrap::Direct | CommandWrap::Prefix(_) => SessionAuthoritySpec::Local,
+            CommandWrap::Direct | CommandWrap::Prefix(_) | CommandWrap::Exec { .. } => {
+                SessionAuthoritySpec::Local
+            }
         }
     }
 
@@ -509,6 +562,17 @@ impl Authority {
                     manages_cwd: true,
                 }
             }
+            CommandWrap::Exec { carrier, base_env } => {
+                // Exec carrier: the same shell hop through the carrier's
+                // `interactive` template.
+                let mut args = build_exec_agent_terminal_args(carrier, base_env, argv);
+                let command = args.remove(0);
+                TerminalWrapper {
+                    command,
+                    args,
+                    manages_cwd: true,
+                }
+            }
         }
     }
 
@@ -622,7 +686,7 @@ impl Authority {
         }
     }
 
-    /// Assemble a full K8s authority from a live [`KubeConnection`].
+    /// Assemble a full K8s authority from a live [`CarrierConnection`].
     ///
     /// The high-level counterpart to [`Self::k8s`]: wires the filesystem and
     /// one-shot spawner onto the connection's agent channel
@@ -631,12 +695,12 @@ impl Authority {
     /// `kubectl exec` ([`KubectlLongRunningSpawner`]). `base_env` is the
     /// captured in-pod env probe applied to LSP spawns and `command_exists`.
     ///
-    /// The caller must keep the `KubeConnection` alive (in the session
+    /// The caller must keep the `CarrierConnection` alive (in the session
     /// keepalive bundle) — dropping it kills the carrier and tears down the
     /// channel the returned authority rides on, exactly as SSH holds its
     /// `SshConnection`.
     pub fn kube_from_connection(
-        connection: &KubeConnection,
+        connection: &CarrierConnection,
         target: KubeTarget,
         base_env: Vec<(String, String)>,
         trust: Arc<WorkspaceTrust>,
@@ -669,6 +733,76 @@ impl Authority {
         )
     }
 
+    /// Build a generic exec-carrier authority: the remote-agent stack (already
+    /// wired to the agent the carrier's `stdio` template bootstrapped) plus a
+    /// terminal wrapper through the carrier's `interactive` template. Mirrors
+    /// [`Self::kube`]: identity lives in the label, and the editor operates
+    /// directly in the far side's path space, so no path translation.
+    pub fn exec(
+        filesystem: Arc<dyn FileSystem + Send + Sync>,
+        process_spawner: Arc<dyn ProcessSpawner>,
+        long_running_spawner: Arc<dyn LongRunningSpawner>,
+        carrier: &ExecCarrier,
+        base_env: &[(String, String)],
+        trust: Arc<WorkspaceTrust>,
+        env: Arc<crate::services::env_provider::EnvProvider>,
+    ) -> Self {
+        Self {
+            filesystem,
+            process_spawner,
+            long_running_spawner,
+            terminal_wrapper: TerminalWrapper::exec(carrier, base_env),
+            display_label: carrier.display(),
+            path_translation: None,
+            workspace_trust: trust,
+            env_provider: env,
+            command_wrap: CommandWrap::Exec {
+                carrier: Box::new(carrier.clone()),
+                base_env: base_env.to_vec(),
+            },
+        }
+    }
+
+    /// Assemble a full exec-carrier authority from a live [`CarrierConnection`]:
+    /// filesystem and one-shot spawner on the agent channel, LSP servers on a
+    /// per-server run of the carrier's `stdio` template
+    /// ([`exec_spawner::ExecCarrierLongRunningSpawner`]). Same keepalive
+    /// contract as [`Self::kube_from_connection`].
+    pub fn exec_from_connection(
+        connection: &CarrierConnection,
+        carrier: ExecCarrier,
+        base_env: Vec<(String, String)>,
+        trust: Arc<WorkspaceTrust>,
+        env: Arc<crate::services::env_provider::EnvProvider>,
+    ) -> Self {
+        let channel = connection.channel();
+        let filesystem: Arc<dyn FileSystem + Send + Sync> = Arc::new(RemoteFileSystem::new(
+            channel.clone(),
+            connection.connection_string().to_string(),
+        ));
+        let process_spawner: Arc<dyn ProcessSpawner> = Arc::new(RemoteProcessSpawner::new(
+            channel,
+            Arc::clone(&env),
+            Arc::clone(&trust),
+        ));
+        let long_running_spawner: Arc<dyn LongRunningSpawner> =
+            Arc::new(exec_spawner::ExecCarrierLongRunningSpawner::with_env(
+                carrier.clone(),
+                base_env.clone(),
+                None,
+                Arc::clone(&trust),
+            ));
+        Self::exec(
+            filesystem,
+            process_spawner,
+            long_running_spawner,
+            &carrier,
+            &base_env,
+            trust,
+            env,
+        )
+    }
+
     /// Build an authority from a plugin payload (the data carried by the
     /// `editor.setAuthority(...)` op), gated by `trust` (the editor passes its
     /// live trust handle so the new authority shares it). All translation from
@@ -682,6 +816,11 @@ impl Authority {
             FilesystemSpec::Local => Arc::new(StdFileSystem),
         };
 
+        let path_translation = payload.path_translation.map(|spec| PathTranslation {
+            host_root: PathBuf::from(spec.host_root),
+            remote_root: PathBuf::from(spec.remote_root),
+        });
+
         // Both spawner traits need the docker-exec params when the
         // payload is a container, so destructure once and reuse.
         let (process_spawner, long_running_spawner, command_wrap): (
@@ -740,6 +879,32 @@ impl Authority {
                     CommandWrap::Prefix(command_prefix),
                 )
             }
+            SpawnerSpec::Exec {
+                carrier,
+                env: carrier_env,
+            } => {
+                carrier.validate().map_err(AuthorityPayloadError::Invalid)?;
+                (
+                    Arc::new(exec_spawner::ExecCarrierSpawner::with_env(
+                        (*carrier).clone(),
+                        carrier_env.clone(),
+                        path_translation.clone(),
+                        Arc::clone(&trust),
+                    )),
+                    Arc::new(exec_spawner::ExecCarrierLongRunningSpawner::with_env(
+                        (*carrier).clone(),
+                        carrier_env.clone(),
+                        path_translation.clone(),
+                        Arc::clone(&trust),
+                    )),
+                    // Agent terminals go through the carrier's `interactive`
+                    // template, rooted at its workspace.
+                    CommandWrap::Exec {
+                        carrier,
+                        base_env: carrier_env,
+                    },
+                )
+            }
         };
 
         let terminal_wrapper = match payload.terminal_wrapper {
@@ -753,13 +918,12 @@ impl Authority {
                 args,
                 manages_cwd,
             },
+            TerminalWrapperSpec::Exec { carrier, env } => {
+                carrier.validate().map_err(AuthorityPayloadError::Invalid)?;
+                TerminalWrapper::exec(&carrier, &env)
+            }
         };
 
-        let path_translation = payload.path_translation.map(|spec| PathTranslation {
-            host_root: PathBuf::from(spec.host_root),
-            remote_root: PathBuf::from(spec.remote_root),
-        });
-
         Ok(Self {
             filesystem,
             process_spawner,
@@ -823,8 +987,9 @@ pub enum SessionAuthoritySpec {
     /// docker. Reconnecting it is the owning plugin's job (only it can run
     /// `devcontainer up`).
     Plugin(AuthorityPayload),
-    /// A born-attached remote agent (SSH / Kubernetes). Reconnectable from
-    /// core via `connect_ssh_authority` / `connect_kube_authority`.
+    /// A born-attached remote agent (SSH / Kubernetes / exec carrier). Reconnectable from
+    /// core via `connect_ssh_authority` / `connect_kube_authority` /
+    /// `connect_exec_authority`.
     RemoteAgent(RemoteAgentSpec),
 }
 
@@ -858,6 +1023,7 @@ impl SessionAuthoritySpec {
             RemoteTransportSpec::KubectlExec { namespace, pod, .. } => {
                 ("kubernetes", format!("{namespace}/{pod}"))
             }
+            RemoteTransportSpec::Exec { carrier } => ("exec", carrier.display()),
         };
         Some(fresh_core::api::RemoteBackendInfo {
             kind: kind.to_string(),
@@ -929,6 +1095,13 @@ pub enum RemoteTransportSpec {
         #[serde(default)]
         extra_args: Vec<String>,
     },
+    /// Run the agent through a generic exec carrier — Podman, Incus,
+    /// Distrobox, a custom sandbox, or plain `sh -c` — described by its
+    /// `run` / `interactive` / `stdio` argv templates (see [`ExecCarrier`]).
+    Exec {
+        #[serde(flatten)]
+        carrier: ExecCarrier,
+    },
 }
 
 impl RemoteAgentSpec {
@@ -953,25 +1126,26 @@ impl RemoteAgentSpec {
                 },
                 self.base_env,
             ),
-            RemoteTransportSpec::Ssh { .. } => {
+            RemoteTransportSpec::Ssh { .. } | RemoteTransportSpec::Exec { .. } => {
                 unreachable!("into_kube_target called on a non-kube transport")
             }
         }
     }
 }
 
-/// Resources that must outlive a K8s [`Authority`]: the carrier
-/// connection (its `kubectl exec` child + heartbeat task) and the
-/// reconnect task. The editor parks this in its session-keepalive slot —
-/// the same one SSH uses for its `SshConnection` — so the agent channel
-/// survives the editor rebuild on attach. Dropping it tears the session
-/// down (reconnect aborted, then the connection's carrier killed).
-pub struct KubeKeepalive {
+/// Resources that must outlive a carrier-backed (K8s or exec) [`Authority`]:
+/// the carrier connection (its `kubectl exec` / `podman exec` / … child +
+/// heartbeat task) and the reconnect task. The editor parks this in its
+/// session-keepalive slot — the same one SSH uses for its `SshConnection` —
+/// so the agent channel survives the editor rebuild on attach. Dropping it
+/// tears the session down (reconnect aborted, then the connection's carrier
+/// killed).
+pub struct CarrierKeepalive {
     // Drop runs the explicit `Drop` below first (aborting reconnect), then
     // fields drop in declaration order: the connection (kills the carrier),
     // then the runtime (shuts down its now-idle workers).
     reconnect: tokio::task::JoinHandle<()>,
-    _connection: KubeConnection,
+    _connection: CarrierConnection,
     // The load-bearing field: the dedicated runtime the agent channel +
     // heartbeat + reconnect tasks run on. Owned here so they survive the editor
     // restart the attach triggers — the editor's per-instance runtime is
@@ -981,38 +1155,77 @@ pub struct KubeKeepalive {
     _runtime: tokio::runtime::Runtime,
 }
 
-impl Drop for KubeKeepalive {
+impl Drop for CarrierKeepalive {
     fn drop(&mut self) {
         self.reconnect.abort();
     }
 }
 
 /// Connect to a K8s pod and assemble its [`Authority`] plus the
-/// [`KubeKeepalive`] that must be parked to keep it alive.
+/// [`CarrierKeepalive`] that must be parked to keep it alive.
 ///
-/// The K8s counterpart to SSH's `connect_remote`: bootstraps the agent
-/// ([`KubeConnection::connect`]) and the reconnect/heartbeat tasks **on a
-/// dedicated runtime owned by the returned keepalive** — *not* the caller's
-/// runtime. Installing the authority restarts the editor, dropping the
-/// editor's per-instance runtime; binding the channel there would kill it the
-/// moment the attach completes (regression: `agent_channel_survives_dropping_
+/// The K8s counterpart to SSH's `connect_remote`: bootstraps the agent and the
+/// reconnect/heartbeat tasks **on a dedicated runtime owned by the returned
+/// keepalive** (see [`connect_carrier`]) — *not* the caller's runtime.
+/// Installing the authority restarts the editor, dropping the editor's
+/// per-instance runtime; binding the channel there would kill it the moment
+/// the attach completes (regression: `agent_channel_survives_dropping_
 /// the_attach_runtime`). `base_env` is the captured in-pod env probe applied to
 /// LSP spawns and `command_exists`.
-///
-/// Stays `async` for callers, but the bootstrap needs `block_on` (which can't
-/// run inside the caller's async context), so it happens on a short-lived
-/// helper thread; the live runtime — with its channel/heartbeat/reconnect
-/// tasks — is handed back and parked in the keepalive.
 pub async fn connect_kube_authority(
     target: KubeTarget,
     base_env: Vec<(String, String)>,
     trust: Arc<WorkspaceTrust>,
     env: Arc<crate::services::env_provider::EnvProvider>,
     cancel: Option<tokio::sync::oneshot::Receiver<()>>,
-) -> Result<(Authority, KubeKeepalive), TransportError> {
+) -> Result<(Authority, CarrierKeepalive), TransportError> {
+    let transport = Arc::new(KubectlExecTransport::new(target.clone()));
+    let keepalive = connect_carrier(transport, "kube", "K8s remote", cancel).await?;
+    let authority =
+        Authority::kube_from_connection(&keepalive._connection, target, base_env, trust, env);
+    Ok((authority, keepalive))
+}
+
+/// Bootstrap the agent through a generic exec carrier and assemble its
+/// [`Authority`] plus the [`CarrierKeepalive`] that must be parked to keep it
+/// alive — the exec-carrier counterpart of [`connect_kube_authority`].
+/// `base_env` is the captured far-side env probe applied to LSP spawns,
+/// `command_exists` and the terminal.
+pub async fn connect_exec_authority(
+    carrier: ExecCarrier,
+    base_env: Vec<(String, String)>,
+    trust: Arc<WorkspaceTrust>,
+    env: Arc<crate::services::env_provider::EnvProvider>,
+    cancel: Option<tokio::sync::oneshot::Receiver<()>>,
+) -> Result<(Authority, CarrierKeepalive), TransportError> {
+    carrier
+        .validate()
+        .map_err(TransportError::AgentStartFailed)?;
+    let transport = Arc::new(ExecTransport::new(carrier.clone()));
+    let keepalive = connect_carrier(transport, "exec", "Exec remote", cancel).await?;
+    let authority =
+        Authority::exec_from_connection(&keepalive._connection, carrier, base_env, trust, env);
+    Ok((authority, keepalive))
+}
+
+/// Bootstrap the agent through `transport` ([`CarrierConnection::connect`])
+/// and start its reconnect task, both on a dedicated runtime handed back
+/// inside the keepalive.
+///
+/// Stays `async` for callers, but the bootstrap needs `block_on` (which can't
+/// run inside the caller's async context), so it happens on a short-lived
+/// helper thread; the live runtime — with its channel/heartbeat/reconnect
+/// tasks — is handed back and parked in the keepalive. `name` prefixes the
+/// helper and worker thread names; `label` tags the reconnect task's logs.
+async fn connect_carrier(
+    transport: Arc<dyn RemoteTransport>,
+    name: &'static str,
+    label: &'static str,
+    cancel: Option<tokio::sync::oneshot::Receiver<()>>,
+) -> Result<CarrierKeepalive, TransportError> {
     type Built = Result<
         (
-            KubeConnection,
+            CarrierConnection,
             tokio::task::JoinHandle<()>,
             tokio::runtime::Runtime,
         ),
@@ -1020,14 +1233,13 @@ pub async fn connect_kube_authority(
     >;
 
     let (tx, rx) = tokio::sync::oneshot::channel::<Built>();
-    let bootstrap_target = target.clone();
     std::thread::Builder::new()
-        .name("kube-connect".to_string())
+        .name(format!("{name}-connect"))
         .spawn(move || {
             let built: Built = (|| {
                 let runtime = tokio::runtime::Builder::new_multi_thread()
                     .worker_threads(2)
-                    .thread_name("kube-agent")
+                    .thread_name(format!("{name}-agent"))
                     .enable_all()
                     .build()
                     .map_err(|e| TransportError::AgentStartFailed(format!("runtime: {e}")))?;
@@ -1037,7 +1249,7 @@ pub async fn connect_kube_authority(
                 // exits — until the `runtime` (moved into the keepalive) drops.
                 let (connection, reconnect) = runtime.block_on(async {
                     // Race the connect against the cancel signal so a slow/hung
-                    // kubectl bootstrap can be aborted; dropping the connect
+                    // carrier bootstrap can be aborted; dropping the connect
                     // future drops the in-flight child. No signal → await it.
                     let connection = match cancel {
                         Some(cancel) => tokio::select! {
@@ -1047,12 +1259,12 @@ pub async fn connect_kube_authority(
                                     "cancelled".to_string(),
                                 ));
                             }
-                            res = KubeConnection::connect(bootstrap_target.clone()) => res?,
+                            res = CarrierConnection::connect(transport.as_ref()) => res?,
                         },
-                        None => KubeConnection::connect(bootstrap_target.clone()).await?,
+                        None => CarrierConnection::connect(transport.as_ref()).await?,
                     };
                     let reconnect =
-                        spawn_kube_reconnect_task(&connection.channel(), bootstrap_target.clone());
+                        spawn_carrier_reconnect_task(&connection.channel(), transport, label);
                     Ok::<_, TransportError>((connection, reconnect))
                 })?;
                 Ok((connection, reconnect, runtime))
@@ -1066,15 +1278,11 @@ pub async fn connect_kube_authority(
         .await
         .map_err(|_| TransportError::AgentStartFailed("connect thread vanished".to_string()))??;
 
-    let authority = Authority::kube_from_connection(&connection, target, base_env, trust, env);
-    Ok((
-        authority,
-        KubeKeepalive {
-            reconnect,
-            _connection: connection,
-            _runtime: runtime,
-        },
-    ))
+    Ok(CarrierKeepalive {
+        reconnect,
+        _connection: connection,
+        _runtime: runtime,
+    })
 }
 
 /// Resources that must outlive an SSH [`Authority`]: the `SshConnection`
@@ -1217,9 +1425,9 @@ pub async fn connect_ssh_authority(
     ))
 }
 
-/// Error from translating a plugin payload into a live authority.
-/// Reserved for future kinds that might fail to construct (e.g. invalid
-/// connection parameters); local-only payloads currently never fail.
+/// Error from translating a plugin payload into a live authority. Raised
+/// for kinds whose params can be structurally invalid (e.g. an exec carrier
+/// with an empty template); local and docker payloads never fail.
 #[derive(Debug, thiserror::Error)]
 pub enum AuthorityPayloadError {
     #[error("invalid authority payload: {0}")]
@@ -1227,6 +1435,7 @@ pub enum AuthorityPayloadError {
 }
 
 mod docker_spawner;
+mod exec_spawner;
 mod kube_spawner;
 
 pub(crate) use kube_spawner::KubectlLongRunningSpawner;
@@ -1793,4 +2002,98 @@ mod tests {
         assert_eq!(pt.host_root, PathBuf::from("/tmp/.tmpA1B2"));
         assert_eq!(pt.remote_root, PathBuf::from("/workspaces/proj"));
     }
+
+    #[test]
+    fn exec_carrier_payload_wires_spawners_terminal_and_translation() {
+        // A Podman-style payload: the workspace is bind-mounted, so the host
+        // filesystem is used directly and `path_translation` maps cwd / LSP
+        // URIs to the container-side path.
+        let carrier = serde_json::json!({
+            "run": ["podman", "exec", "dev", "{cmd}"],
+            "interactive": ["podman", "exec", "-it", "dev", "{cmd}"],
+            "stdio": ["podman", "exec", "-i", "dev", "{cmd}"],
+            "workspace": "/src"
+        });
+        let mut spawner = carrier.clone();
+        spawner["kind"] = "exec".into();
+        spawner["env"] = serde_json::json!([["LANG", "C.UTF-8"]]);
+        let mut terminal = carrier;
+        terminal["kind"] = "exec".into();
+        let json = serde_json::json!({
+            "filesystem": { "kind": "local" },
+            "spawner": spawner,
+            "terminal_wrapper": terminal,
+            "display_label": "Podman:dev",
+            "path_translation": { "host_root": "/home/me/proj", "remote_root": "/src" }
+        });
+        let payload: AuthorityPayload = serde_json::from_value(json).expect("exec payload parses");
+        let SpawnerSpec::Exec { carrier, env } = &payload.spawner else {
+            panic!("expected exec spawner");
+        };
+        assert_eq!(carrier.run[0], "podman");
+        assert_eq!(carrier.workspace.as_deref(), Some("/src"));
+        assert_eq!(env, &vec![("LANG".to_string(), "C.UTF-8".to_string())]);
+        // Round-trips through the flattened wire shape.
+        let reparsed: AuthorityPayload =
+            serde_json::from_value(serde_json::to_value(&payload).unwrap()).unwrap();
+        assert_eq!(reparsed, payload);
+
+        let auth = Authority::from_plugin_payload(
+            payload,
+            Arc::new(WorkspaceTrust::permissive()),
+            Arc::new(crate::services::env_provider::EnvProvider::inactive()),
+        )
+        .expect("exec payload is valid");
+        assert_eq!(auth.terminal_wrapper.command, "podman");
+        assert_eq!(&auth.terminal_wrapper.args[..3], &["exec", "-it", "dev"]);
+        assert!(auth.terminal_wrapper.manages_cwd);
+        assert!(auth.path_translation.is_some());
+        assert!(matches!(auth.session_spec(), SessionAuthoritySpec::Local));
+
+        // Agent terminals go through the interactive template, rooted at the
+        // carrier's workspace.
+        let w = auth.terminal_command(&["claude".into()]);
+        assert_eq!(w.command, "podman");
+        assert_eq!(&w.args[..5], &["exec", "-it", "dev", "sh", "-lc"]);
+        assert!(w.args[5].contains("'/src'") && w.args[5].contains("claude"));
+    }
+
+    #[test]
+    fn exec_carrier_payload_with_empty_template_is_invalid() {
+        let json = serde_json::json!({
+            "filesystem": { "kind": "local" },
+            "spawner": { "kind": "exec", "run": [] },
+            "terminal_wrapper": { "kind": "host-shell" }
+        });
+        let payload: AuthorityPayload = serde_json::from_value(json).expect("shape parses");
+        let err = Authority::from_plugin_payload(
+            payload,
+            Arc::new(WorkspaceTrust::permissive()),
+            Arc::new(crate::services::env_provider::EnvProvider::inactive()),
+        )
+        .err()
+        .expect("empty run template is rejected");
+        assert!(err.to_string().contains("`run` template is empty"));
+    }
+
+    #[test]
+    fn remote_agent_spec_parses_exec_transport() {
+        let json = serde_json::json!({
+            "transport": {
+                "kind": "exec",
+                "run": ["incus", "exec", "dev", "--"],
+                "label": "incus:dev"
+            }
+        });
+        let spec: RemoteAgentSpec = serde_json::from_value(json).expect("exec transport parses");
+        let RemoteTransportSpec::Exec { carrier } = &spec.transport else {
+            panic!("expected exec transport");
+        };
+        assert!(carrier.interactive.is_none() && carrier.stdio.is_none());
+        let info = SessionAuthoritySpec::RemoteAgent(spec.clone())
+            .remote_backend_info(true)
+            .expect("remote backend facet");
+        assert_eq!(info.kind, "exec");
+        assert_eq!(info.detail, "incus:dev");
+    }
 }
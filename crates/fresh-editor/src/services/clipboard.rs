@@ -7,9 +7,12 @@
 //! - Supports copying HTML-formatted text for rich text editors
 //! - Gracefully falls back to internal clipboard if system clipboard is unavailable
 //! - Respects clipboard configuration to disable problematic methods
+//! - Keeps a bounded history ("kill ring") of recent copies and cuts
 
 use crossterm::clipboard::CopyToClipboard;
 use crossterm::execute;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 use std::io::{stdout, Write};
 use std::sync::Mutex;
 
@@ -177,6 +180,38 @@ pub struct PendingClipboard {
     pub use_system_clipboard: bool,
 }
 
+/// One clipboard history entry: the text of a copy or cut, plus the text of
+/// each cursor when it came from several cursors at once.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClipboardEntry {
+    /// The copied text; per-cursor pieces are joined with newlines
+    pub text: String,
+    /// Per-cursor pieces, in buffer order. Empty for a single-cursor copy.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parts: Vec<String>,
+}
+
+impl ClipboardEntry {
+    /// Entry for a single-cursor copy
+    pub fn new(text: String) -> Self {
+        Self {
+            text,
+            parts: Vec::new(),
+        }
+    }
+
+    /// Entry for a multi-cursor copy. A single piece is stored as a plain
+    /// entry.
+    pub fn from_parts(parts: Vec<String>) -> Self {
+        let text = parts.join("\n");
+        if parts.len() > 1 {
+            Self { text, parts }
+        } else {
+            Self::new(text)
+        }
+    }
+}
+
 /// Clipboard manager that handles both internal and system clipboard
 #[derive(Debug, Clone, Default)]
 pub struct Clipboard {
@@ -193,6 +228,10 @@ pub struct Clipboard {
     session_mode: bool,
     /// Clipboard data pending delivery to clients (session mode only)
     pending_clipboard: Option<PendingClipboard>,
+    /// Recent copies and cuts, most recent first
+    history: VecDeque<ClipboardEntry>,
+    /// Maximum number of history entries (0 keeps none)
+    history_size: usize,
 }
 
 impl Clipboard {
@@ -205,6 +244,8 @@ impl Clipboard {
             use_system_clipboard: true,
             session_mode: false,
             pending_clipboard: None,
+            history: VecDeque::new(),
+            history_size: crate::config::ClipboardConfig::default().history_size,
         }
     }
 
@@ -213,6 +254,8 @@ impl Clipboard {
     pub fn apply_config(&mut self, config: &crate::config::ClipboardConfig) {
         self.use_osc52 = config.use_osc52;
         self.use_system_clipboard = config.use_system_clipboard;
+        self.history_size = config.history_size;
+        self.history.truncate(self.history_size);
     }
 
     /// Enable internal-only mode (for testing)
@@ -254,6 +297,7 @@ impl Clipboard {
     /// Returns true if successful, false otherwise.
     pub fn copy_html(&mut self, html: &str, plain_text: &str) -> bool {
         self.internal = plain_text.to_string();
+        self.record(ClipboardEntry::new(plain_text.to_string()));
 
         if !self.use_system_clipboard {
             return false;
@@ -296,7 +340,20 @@ impl Clipboard {
     ///
     /// Methods can be disabled via clipboard configuration.
     pub fn copy(&mut self, text: String) {
+        self.copy_entry(ClipboardEntry::new(text));
+    }
+
+    /// Copy the text of several cursors, keeping each cursor's piece in the
+    /// history so a paste with the same number of cursors gives each cursor
+    /// its own piece back.
+    pub fn copy_parts(&mut self, parts: Vec<String>) {
+        self.copy_entry(ClipboardEntry::from_parts(parts));
+    }
+
+    fn copy_entry(&mut self, entry: ClipboardEntry) {
+        let text = entry.text.clone();
         self.internal = text.clone();
+        self.record(entry);
 
         // In session mode, the server process has no terminal or display server.
         // Queue the text for delivery to clients via a control message instead.
@@ -359,6 +416,59 @@ impl Clipboard {
         }
     }
 
+    /// Recent copies and cuts, most recent first
+    pub fn history(&self) -> impl ExactSizeIterator<Item = &ClipboardEntry> {
+        self.history.iter()
+    }
+
+    /// History entry at `index` (0 is the most recent)
+    pub fn history_entry(&self, index: usize) -> Option<&ClipboardEntry> {
+        self.history.get(index)
+    }
+
+    /// Position of the history entry holding `text`, if any
+    pub fn history_position(&self, text: &str) -> Option<usize> {
+        self.history.iter().position(|e| e.text == text)
+    }
+
+    /// Per-cursor pieces recorded for `text`, if it came from a multi-cursor
+    /// copy that is still in the history
+    pub fn parts_for(&self, text: &str) -> Option<&[String]> {
+        self.history
+            .iter()
+            .find(|e| e.text == text && !e.parts.is_empty())
+            .map(|e| e.parts.as_slice())
+    }
+
+    /// Make history entry `index` the current clipboard, moving it to the
+    /// front of the history and copying it to the system clipboard
+    pub fn promote_history_entry(&mut self, index: usize) -> Option<ClipboardEntry> {
+        let entry = self.history.remove(index)?;
+        self.copy_entry(entry.clone());
+        Some(entry)
+    }
+
+    /// Replace the history with entries restored from a saved session
+    /// (most recent first). The current clipboard is left alone.
+    pub fn restore_history(&mut self, entries: Vec<ClipboardEntry>) {
+        self.history = entries
+            .into_iter()
+            .filter(|e| !e.text.is_empty())
+            .take(self.history_size)
+            .collect();
+    }
+
+    /// Add an entry to the front of the history, dropping an older copy of
+    /// the same text and the oldest entries past the size limit
+    fn record(&mut self, entry: ClipboardEntry) {
+        if entry.text.is_empty() || self.history_size == 0 {
+            return;
+        }
+        self.history.retain(|e| e.text != entry.text);
+        self.history.push_front(entry);
+        self.history.truncate(self.history_size);
+    }
+
     /// Check if clipboard is empty (checks both internal and system)
     pub fn is_empty(&self) -> bool {
         if !self.internal.is_empty() {
@@ -400,6 +510,7 @@ mod tests {
         let config = crate::config::ClipboardConfig {
             use_osc52: false,
             use_system_clipboard: true,
+            ..Default::default()
         };
         clipboard.apply_config(&config);
         assert!(!clipboard.use_osc52);
@@ -412,6 +523,7 @@ mod tests {
         let config = crate::config::ClipboardConfig {
             use_osc52: true,
             use_system_clipboard: false,
+            ..Default::default()
         };
         clipboard.apply_config(&config);
         assert!(clipboard.use_osc52);
@@ -424,10 +536,78 @@ mod tests {
         let config = crate::config::ClipboardConfig {
             use_osc52: false,
             use_system_clipboard: false,
+            ..Default::default()
         };
         clipboard.apply_config(&config);
 
         clipboard.copy("internal only".to_string());
         assert_eq!(clipboard.get_internal(), "internal only");
     }
+
+    /// Clipboard that never touches the host clipboard
+    fn internal_clipboard(history_size: usize) -> Clipboard {
+        let mut clipboard = Clipboard::new();
+        clipboard.apply_config(&crate::config::ClipboardConfig {
+            use_osc52: false,
+            use_system_clipboard: false,
+            history_size,
+            persist_history: false,
+        });
+        clipboard
+    }
+
+    fn history_texts(clipboard: &Clipboard) -> Vec<&str> {
+        clipboard.history().map(|e| e.text.as_str()).collect()
+    }
+
+    #[test]
+    fn test_clipboard_history_is_bounded_and_deduplicated() {
+        let mut clipboard = internal_clipboard(3);
+        for text in ["a", "b", "c", "b", "d"] {
+            clipboard.copy(text.to_string());
+        }
+        assert_eq!(history_texts(&clipboard), vec!["d", "b", "c"]);
+
+        clipboard.copy(String::new());
+        assert_eq!(clipboard.history().len(), 3);
+    }
+
+    #[test]
+    fn test_clipboard_history_keeps_cursor_parts() {
+        let mut clipboard = internal_clipboard(10);
+        clipboard.copy_parts(vec!["one\ntwo".to_string(), "three".to_string()]);
+        assert_eq!(clipboard.get_internal(), "one\ntwo\nthree");
+        assert_eq!(
+            clipboard.parts_for("one\ntwo\nthree"),
+            Some(&["one\ntwo".to_string(), "three".to_string()][..])
+        );
+
+        // A single piece is an ordinary entry.
+        clipboard.copy_parts(vec!["solo".to_string()]);
+        assert_eq!(
+            clipboard.history_entry(0),
+            Some(&ClipboardEntry::new("solo".into()))
+        );
+    }
+
+    #[test]
+    fn test_clipboard_promote_history_entry() {
+        let mut clipboard = internal_clipboard(10);
+        for text in ["a", "b", "c"] {
+            clipboard.copy(text.to_string());
+        }
+        let entry = clipboard.promote_history_entry(2).unwrap();
+        assert_eq!(entry.text, "a");
+        assert_eq!(clipboard.get_internal(), "a");
+        assert_eq!(history_texts(&clipboard), vec!["a", "c", "b"]);
+        assert!(clipboard.promote_history_entry(3).is_none());
+    }
+
+    #[test]
+    fn test_clipboard_history_disabled() {
+        let mut clipboard = internal_clipboard(0);
+        clipboard.copy("a".to_string());
+        assert_eq!(clipboard.get_internal(), "a");
+        assert_eq!(clipboard.history().len(), 0);
+    }
 }
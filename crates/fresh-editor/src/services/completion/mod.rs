@@ -2,8 +2,8 @@
 //!
 //! This module provides a framework for composing multiple completion sources
 //! (providers) into a single, ranked completion experience. It ships with
-//! two built-in providers and supports both Rust-native and TypeScript plugin
-//! providers.
+//! built-in buffer-word, dabbrev and user snippet providers and supports both
+//! Rust-native and TypeScript plugin providers.
 //!
 //! # Architecture
 //!
@@ -50,6 +50,7 @@ pub mod buffer_words;
 pub mod dabbrev;
 pub mod provider;
 pub mod service;
+pub mod snippets;
 
 // Re-export the main types that the Editor needs.
 pub use provider::{
@@ -0,0 +1,288 @@
+//! User snippet completion provider.
+//!
+//! Offers the snippets in `<config_dir>/snippets/<language>.json`, written in
+//! the same format as VS Code's per-language snippet files:
+//!
+//! ```json
+//! {
+//!     // Comments and trailing commas are allowed.
+//!     "For loop": {
+//!         "prefix": ["for", "fori"],
+//!         "body": ["for ${1:i} in ${2:0..n} {", "\t$0", "}"],
+//!         "description": "Loop over a range",
+//!     },
+//! }
+//! ```
+//!
+//! A snippet is offered when one of its prefixes starts with the typed word.
+//! Accepting it inserts the body as an LSP snippet, so its placeholders are
+//! stepped through with Tab. Lines after the first are indented like the
+//! line the snippet is inserted on.
+//!
+//! Each file is read the first time its language asks for completions, and
+//! again whenever it changes on disk.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use super::provider::{
+    case_mismatch_penalty, smart_case_matches, CompletionCandidate, CompletionContext,
+    CompletionProvider, CompletionSourceId, ProviderResult,
+};
+
+/// Base score of a snippet candidate: above buffer words, which the typed
+/// prefix of a snippet usually also matches.
+const SNIPPET_SCORE: i64 = 1_000_000;
+
+/// One snippet from a user snippet file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserSnippet {
+    /// The key the snippet is stored under.
+    pub name: String,
+    pub prefixes: Vec<String>,
+    /// LSP snippet syntax, lines joined with `\n`.
+    pub body: String,
+    pub description: Option<String>,
+}
+
+struct CachedFile {
+    modified: Option<SystemTime>,
+    snippets: Vec<UserSnippet>,
+}
+
+pub struct UserSnippetProvider {
+    dir: PathBuf,
+    /// Snippets per language, with the modification time they were read at.
+    cache: Mutex<HashMap<String, CachedFile>>,
+}
+
+impl UserSnippetProvider {
+    /// A provider reading snippet files from `dir`.
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The snippets for `language`, re-read if the file changed.
+    fn snippets_for(&self, language: &str) -> Vec<UserSnippet> {
+        let path = self.dir.join(format!("{language}.json"));
+        let modified = std::fs::metadata(&path).and_then(|m| m.modified()).ok();
+        let mut cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(cached) = cache.get(language) {
+            if cached.modified == modified {
+                return cached.snippets.clone();
+            }
+        }
+
+        let snippets = match modified {
+            Some(_) => match std::fs::read_to_string(&path) {
+                Ok(contents) => parse_snippet_file(&contents).unwrap_or_else(|e| {
+                    tracing::warn!("Ignoring snippet file {}: {}", path.display(), e);
+                    Vec::new()
+                }),
+                Err(e) => {
+                    tracing::warn!("Failed to read snippet file {}: {}", path.display(), e);
+                    Vec::new()
+                }
+            },
+            None => Vec::new(),
+        };
+        cache.insert(
+            language.to_string(),
+            CachedFile {
+                modified,
+                snippets: snippets.clone(),
+            },
+        );
+        snippets
+    }
+}
+
+/// Parse a VS Code style snippet file. Entries without a prefix or body are
+/// skipped, as VS Code does.
+pub fn parse_snippet_file(contents: &str) -> Result<Vec<UserSnippet>, String> {
+    let value = crate::config::parse_config_jsonc(contents).map_err(|e| e.to_string())?;
+    let serde_json::Value::Object(entries) = value else {
+        return Err("expected an object of snippets".to_string());
+    };
+
+    // A string, or an array of strings.
+    let strings = |value: Option<&serde_json::Value>| -> Vec<String> {
+        match value {
+            Some(serde_json::Value::String(s)) => vec![s.clone()],
+            Some(serde_json::Value::Array(items)) => items
+                .iter()
+                .filter_map(|item| item.as_str().map(str::to_string))
+                .collect(),
+            _ => Vec::new(),
+        }
+    };
+
+    Ok(entries
+        .into_iter()
+        .filter_map(|(name, entry)| {
+            let prefixes = strings(entry.get("prefix"));
+            let body = strings(entry.get("body"));
+            if prefixes.is_empty() || body.is_empty() {
+                return None;
+            }
+            Some(UserSnippet {
+                name,
+                prefixes,
+                body: body.join("\n"),
+                description: entry
+                    .get("description")
+                    .and_then(|d| d.as_str())
+                    .map(str::to_string),
+            })
+        })
+        .collect())
+}
+
+/// Leading whitespace of the line holding `offset` in `window`.
+fn line_indent(window: &[u8], offset: usize) -> String {
+    let offset = offset.min(window.len());
+    let line_start = window[..offset]
+        .iter()
+        .rposition(|&b| b == b'\n')
+        .map_or(0, |i| i + 1);
+    window[line_start..]
+        .iter()
+        .take_while(|&&b| b == b' ' || b == b'\t')
+        .map(|&b| b as char)
+        .collect()
+}
+
+impl CompletionProvider for UserSnippetProvider {
+    fn id(&self) -> CompletionSourceId {
+        CompletionSourceId("user_snippets".into())
+    }
+
+    fn display_name(&self) -> &str {
+        "User Snippets"
+    }
+
+    fn is_enabled(&self, ctx: &CompletionContext) -> bool {
+        !ctx.prefix.is_empty() && ctx.language_id.is_some()
+    }
+
+    fn provide(&self, ctx: &CompletionContext, buffer_window: &[u8]) -> ProviderResult {
+        let Some(language) = ctx.language_id.as_deref() else {
+            return ProviderResult::Ready(Vec::new());
+        };
+        let indent = line_indent(
+            buffer_window,
+            ctx.word_start_byte.saturating_sub(ctx.scan_range.start),
+        );
+
+        let candidates = self
+            .snippets_for(language)
+            .into_iter()
+            .filter_map(|snippet| {
+                let prefix = snippet
+                    .prefixes
+                    .iter()
+                    .find(|p| smart_case_matches(p, &ctx.prefix, ctx.prefix_has_uppercase))?;
+                let score = SNIPPET_SCORE
+                    + case_mismatch_penalty(prefix, &ctx.prefix, ctx.prefix_has_uppercase);
+                Some(CompletionCandidate {
+                    label: prefix.clone(),
+                    insert_text: Some(snippet.body.replace('\n', &format!("\n{indent}"))),
+                    detail: Some(snippet.description.unwrap_or(snippet.name)),
+                    icon: Some("s".to_string()),
+                    score,
+                    source: None,
+                    is_snippet: true,
+                    provider_data: None,
+                })
+            })
+            .collect();
+        ProviderResult::Ready(candidates)
+    }
+
+    fn priority(&self) -> u32 {
+        10
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_ctx(prefix: &str, text: &str) -> CompletionContext {
+        CompletionContext {
+            prefix: prefix.into(),
+            cursor_byte: text.len(),
+            word_start_byte: text.len() - prefix.len(),
+            buffer_len: text.len(),
+            is_large_file: false,
+            scan_range: 0..text.len(),
+            viewport_top_byte: 0,
+            viewport_bottom_byte: text.len(),
+            language_id: Some("rust".into()),
+            word_chars_extra: String::new(),
+            prefix_has_uppercase: prefix.chars().any(|c| c.is_uppercase()),
+            other_buffers: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn parses_vscode_snippet_file() {
+        let snippets = parse_snippet_file(
+            r#"{
+                // A comment
+                "Print": { "prefix": "pr", "body": "println!(\"$1\");" },
+                "For": {
+                    "prefix": ["for", "fori"],
+                    "body": ["for $1 in $2 {", "\t$0", "}"],
+                    "description": "For loop",
+                },
+                "No prefix": { "body": "x" },
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(snippets.len(), 2);
+        let for_loop = snippets.iter().find(|s| s.name == "For").unwrap();
+        assert_eq!(for_loop.prefixes, vec!["for", "fori"]);
+        assert_eq!(for_loop.body, "for $1 in $2 {\n\t$0\n}");
+        assert_eq!(for_loop.description.as_deref(), Some("For loop"));
+        assert!(parse_snippet_file("[1, 2]").is_err());
+    }
+
+    #[test]
+    fn offers_matching_prefixes_indented_to_the_line() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("rust.json"),
+            r#"{ "For": { "prefix": ["for", "fori"], "body": ["for $1 {", "\t$0", "}"] },
+                 "Match": { "prefix": "match", "body": "match $1 {}" } }"#,
+        )
+        .unwrap();
+        let provider = UserSnippetProvider::new(dir.path().to_path_buf());
+        let text = "fn main() {\n    fo";
+        let ProviderResult::Ready(candidates) =
+            provider.provide(&make_ctx("fo", text), text.as_bytes())
+        else {
+            panic!("expected Ready");
+        };
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].label, "for");
+        assert!(candidates[0].is_snippet);
+        assert_eq!(
+            candidates[0].insert_text.as_deref(),
+            Some("for $1 {\n    \t$0\n    }")
+        );
+
+        // Other languages have no snippets.
+        let mut ctx = make_ctx("fo", text);
+        ctx.language_id = Some("python".into());
+        let ProviderResult::Ready(candidates) = provider.provide(&ctx, text.as_bytes()) else {
+            panic!("expected Ready");
+        };
+        assert!(candidates.is_empty());
+    }
+}
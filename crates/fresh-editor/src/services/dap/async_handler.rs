@@ -0,0 +1,730 @@
+//! Async DAP client.
+//!
+//! [`DapHandle`] is the synchronous side the editor holds; it forwards
+//! [`DapCommand`]s over a tokio channel to a `DapTask` running on the
+//! editor's runtime. The task owns the adapter process, writes requests to
+//! its stdin and runs a reader task on its stdout that resolves pending
+//! requests and turns adapter events into [`AsyncMessage`]s.
+//!
+//! Requests are written in the order commands arrive — `setBreakpoints`
+//! must reach the adapter before `configurationDone` — but their responses
+//! are awaited on spawned tasks, so a slow adapter never blocks the next
+//! command (in particular `disconnect`).
+
+use crate::services::async_bridge::{AsyncBridge, AsyncMessage};
+use crate::services::dap::protocol::{
+    encode_message, read_message, ProtocolMessage, Request, Response, Scope, ScopeVariables,
+    StackFrame, Variable,
+};
+use crate::types::{DebugAdapterConfig, DebugRequest};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::{mpsc as std_mpsc, Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncWriteExt, BufReader};
+use tokio::process::{ChildStdin, ChildStdout};
+use tokio::sync::{mpsc, oneshot};
+
+/// How long to wait for an adapter response. Generous because `launch`
+/// may build or start a large program before answering.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How long `disconnect` may take before the adapter is killed anyway.
+const DISCONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Maximum number of frames requested per `stackTrace`.
+const MAX_STACK_FRAMES: usize = 64;
+
+/// Commands the editor sends to a debug session.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DapCommand {
+    /// Replace all breakpoints of one source file (1-based lines).
+    SetBreakpoints {
+        path: PathBuf,
+        lines: Vec<usize>,
+    },
+    /// Finish the configuration phase; the debuggee starts running.
+    ConfigurationDone,
+    Continue {
+        thread_id: i64,
+    },
+    Next {
+        thread_id: i64,
+    },
+    StepIn {
+        thread_id: i64,
+    },
+    StepOut {
+        thread_id: i64,
+    },
+    Pause {
+        thread_id: i64,
+    },
+    /// Fetch the call stack of a thread (the first thread reported by the
+    /// adapter when `None`), plus the scopes of its top frame.
+    StackTrace {
+        thread_id: Option<i64>,
+    },
+    /// Fetch the scopes and variables of one frame.
+    Scopes {
+        frame_id: i64,
+    },
+    /// End the session, terminating a launched debuggee.
+    Disconnect {
+        terminate_debuggee: bool,
+    },
+}
+
+/// Outstanding requests, keyed by `seq`.
+type PendingRequests = Arc<Mutex<HashMap<i64, oneshot::Sender<Result<Option<Value>, String>>>>>;
+
+/// A request that has been written and whose response is still due.
+struct PendingResponse {
+    seq: i64,
+    command: String,
+    rx: oneshot::Receiver<Result<Option<Value>, String>>,
+}
+
+/// State shared between the command loop and the tasks it spawns.
+#[derive(Clone)]
+struct DapState {
+    session_id: u64,
+    stdin: Arc<tokio::sync::Mutex<ChildStdin>>,
+    next_seq: Arc<AtomicI64>,
+    pending: PendingRequests,
+    async_tx: std_mpsc::Sender<AsyncMessage>,
+    /// Whether the adapter advertised `supportsConfigurationDoneRequest`.
+    supports_configuration_done: bool,
+}
+
+// async_tx.send() is best-effort throughout: a dropped receiver means the
+// editor is shutting down.
+#[allow(clippy::let_underscore_must_use)]
+impl DapState {
+    fn next_seq(&self) -> i64 {
+        self.next_seq.fetch_add(1, Ordering::SeqCst)
+    }
+
+    async fn write_message(&self, message: &ProtocolMessage) -> Result<(), String> {
+        let bytes = encode_message(message)?;
+        let mut stdin = self.stdin.lock().await;
+        stdin
+            .write_all(&bytes)
+            .await
+            .map_err(|e| format!("Failed to write to stdin: {}", e))?;
+        stdin
+            .flush()
+            .await
+            .map_err(|e| format!("Failed to flush stdin: {}", e))
+    }
+
+    /// Write a request; the response is collected with [`Self::await_response`].
+    async fn send_request(
+        &self,
+        command: &str,
+        arguments: Option<Value>,
+    ) -> Result<PendingResponse, String> {
+        let seq = self.next_seq();
+        let (tx, rx) = oneshot::channel();
+        self.pending.lock().unwrap().insert(seq, tx);
+
+        let message = ProtocolMessage::Request(Request {
+            seq,
+            command: command.to_string(),
+            arguments,
+        });
+        if let Err(e) = self.write_message(&message).await {
+            self.pending.lock().unwrap().remove(&seq);
+            return Err(e);
+        }
+        tracing::trace!("Sent DAP request seq={} command={}", seq, command);
+        Ok(PendingResponse {
+            seq,
+            command: command.to_string(),
+            rx,
+        })
+    }
+
+    async fn await_response(
+        &self,
+        pending: PendingResponse,
+        timeout: Duration,
+    ) -> Result<Option<Value>, String> {
+        match tokio::time::timeout(timeout, pending.rx).await {
+            Ok(Ok(result)) => result,
+            Ok(Err(_)) => Err(format!("'{}' request was dropped", pending.command)),
+            Err(_) => {
+                self.pending.lock().unwrap().remove(&pending.seq);
+                Err(format!("'{}' request timed out", pending.command))
+            }
+        }
+    }
+
+    async fn request(
+        &self,
+        command: &str,
+        arguments: Option<Value>,
+    ) -> Result<Option<Value>, String> {
+        let pending = self.send_request(command, arguments).await?;
+        self.await_response(pending, REQUEST_TIMEOUT).await
+    }
+
+    /// Await a response in the background, reporting failure as `DapError`.
+    fn report_failure(&self, pending: PendingResponse) {
+        let state = self.clone();
+        tokio::spawn(async move {
+            if let Err(e) = state.await_response(pending, REQUEST_TIMEOUT).await {
+                state.send_error(e);
+            }
+        });
+    }
+
+    fn send_error(&self, error: String) {
+        tracing::warn!("DAP session {}: {}", self.session_id, error);
+        let _ = self.async_tx.send(AsyncMessage::DapError {
+            session_id: self.session_id,
+            error,
+        });
+    }
+
+    /// Fetch the scopes of a frame, and the variables of every scope the
+    /// adapter does not flag as expensive.
+    async fn fetch_scopes(&self, frame_id: i64) -> Result<Vec<ScopeVariables>, String> {
+        let body = self
+            .request("scopes", Some(json!({ "frameId": frame_id })))
+            .await?;
+        let scopes: Vec<Scope> = parse_body_field(body, "scopes")?;
+
+        let mut result = Vec::with_capacity(scopes.len());
+        for scope in scopes {
+            let variables = if scope.expensive || scope.variables_reference <= 0 {
+                Vec::new()
+            } else {
+                let body = self
+                    .request(
+                        "variables",
+                        Some(json!({ "variablesReference": scope.variables_reference })),
+                    )
+                    .await?;
+                parse_body_field::<Vec<Variable>>(body, "variables")?
+            };
+            result.push(ScopeVariables { scope, variables });
+        }
+        Ok(result)
+    }
+
+    async fn stack_trace(&self, thread_id: Option<i64>) -> Result<(), String> {
+        let thread_id = match thread_id {
+            Some(id) => id,
+            None => {
+                let body = self.request("threads", None).await?;
+                let threads: Vec<Value> = parse_body_field(body, "threads")?;
+                threads
+                    .first()
+                    .and_then(|t| t.get("id"))
+                    .and_then(Value::as_i64)
+                    .ok_or_else(|| "Debug adapter reported no threads".to_string())?
+            }
+        };
+
+        let body = self
+            .request(
+                "stackTrace",
+                Some(json!({
+                    "threadId": thread_id,
+                    "startFrame": 0,
+                    "levels": MAX_STACK_FRAMES,
+                })),
+            )
+            .await?;
+        let frames: Vec<StackFrame> = parse_body_field(body, "stackFrames")?;
+        let frame_id = frames.first().map(|f| f.id);
+        let scopes = match frame_id {
+            Some(id) => self.fetch_scopes(id).await?,
+            None => Vec::new(),
+        };
+
+        let _ = self.async_tx.send(AsyncMessage::DapStackTrace {
+            session_id: self.session_id,
+            thread_id,
+            frames,
+            frame_id,
+            scopes,
+        });
+        Ok(())
+    }
+
+    /// Issue the request(s) for one editor command.
+    async fn handle_command(&self, command: DapCommand) -> Result<(), String> {
+        let stepping =
+            |name: &str, thread_id: i64| (name.to_string(), json!({ "threadId": thread_id }));
+        let (name, arguments) = match command {
+            DapCommand::SetBreakpoints { path, lines } => {
+                let name = path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                let breakpoints: Vec<Value> =
+                    lines.iter().map(|line| json!({ "line": line })).collect();
+                (
+                    "setBreakpoints".to_string(),
+                    json!({
+                        "source": { "name": name, "path": path.to_string_lossy() },
+                        "breakpoints": breakpoints,
+                        "lines": lines,
+                        "sourceModified": false,
+                    }),
+                )
+            }
+            DapCommand::ConfigurationDone => {
+                if !self.supports_configuration_done {
+                    return Ok(());
+                }
+                ("configurationDone".to_string(), json!({}))
+            }
+            DapCommand::Continue { thread_id } => stepping("continue", thread_id),
+            DapCommand::Next { thread_id } => stepping("next", thread_id),
+            DapCommand::StepIn { thread_id } => stepping("stepIn", thread_id),
+            DapCommand::StepOut { thread_id } => stepping("stepOut", thread_id),
+            DapCommand::Pause { thread_id } => stepping("pause", thread_id),
+            DapCommand::StackTrace { thread_id } => {
+                let state = self.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = state.stack_trace(thread_id).await {
+                        state.send_error(e);
+                    }
+                });
+                return Ok(());
+            }
+            DapCommand::Scopes { frame_id } => {
+                let state = self.clone();
+                tokio::spawn(async move {
+                    match state.fetch_scopes(frame_id).await {
+                        Ok(scopes) => {
+                            let _ = state.async_tx.send(AsyncMessage::DapScopes {
+                                session_id: state.session_id,
+                                frame_id,
+                                scopes,
+                            });
+                        }
+                        Err(e) => state.send_error(e),
+                    }
+                });
+                return Ok(());
+            }
+            DapCommand::Disconnect { terminate_debuggee } => (
+                "disconnect".to_string(),
+                json!({ "terminateDebuggee": terminate_debuggee }),
+            ),
+        };
+        let pending = self.send_request(&name, Some(arguments)).await?;
+        self.report_failure(pending);
+        Ok(())
+    }
+}
+
+/// Deserialize `body[field]` of a response.
+fn parse_body_field<T: serde::de::DeserializeOwned>(
+    body: Option<Value>,
+    field: &str,
+) -> Result<T, String> {
+    let value = body
+        .and_then(|mut b| b.get_mut(field).map(Value::take))
+        .ok_or_else(|| format!("Response is missing '{}'", field))?;
+    serde_json::from_value(value).map_err(|e| format!("Invalid '{}' in response: {}", field, e))
+}
+
+/// The error text of a failed response.
+fn response_error(response: &Response) -> String {
+    response
+        .message
+        .clone()
+        .or_else(|| {
+            response
+                .body
+                .as_ref()
+                .and_then(|b| b.pointer("/error/format"))
+                .and_then(Value::as_str)
+                .map(str::to_string)
+        })
+        .unwrap_or_else(|| format!("'{}' request failed", response.command))
+}
+
+/// Route one message read from the adapter.
+#[allow(clippy::let_underscore_must_use)] // async_tx.send() is best-effort
+async fn handle_message(message: ProtocolMessage, state: &DapState) {
+    let session_id = state.session_id;
+    match message {
+        ProtocolMessage::Response(response) => {
+            let Some(tx) = state.pending.lock().unwrap().remove(&response.request_seq) else {
+                tracing::debug!(
+                    "DAP response for unknown request seq={}",
+                    response.request_seq
+                );
+                return;
+            };
+            let result = if response.success {
+                Ok(response.body)
+            } else {
+                Err(response_error(&response))
+            };
+            let _ = tx.send(result);
+        }
+        ProtocolMessage::Event(event) => {
+            let body = event.body.unwrap_or(Value::Null);
+            let text = |key: &str| body.get(key).and_then(Value::as_str).map(str::to_string);
+            let message = match event.event.as_str() {
+                "initialized" => AsyncMessage::DapInitialized { session_id },
+                "stopped" => AsyncMessage::DapStopped {
+                    session_id,
+                    thread_id: body.get("threadId").and_then(Value::as_i64),
+                    reason: text("reason").unwrap_or_default(),
+                    description: text("description"),
+                },
+                "continued" => AsyncMessage::DapContinued { session_id },
+                "output" => AsyncMessage::DapOutput {
+                    session_id,
+                    category: text("category"),
+                    output: text("output").unwrap_or_default(),
+                },
+                "exited" => AsyncMessage::DapExited {
+                    session_id,
+                    exit_code: body.get("exitCode").and_then(Value::as_i64).unwrap_or(0),
+                },
+                "terminated" => AsyncMessage::DapTerminated { session_id },
+                other => {
+                    tracing::debug!("Unhandled DAP event: {}", other);
+                    return;
+                }
+            };
+            let _ = state.async_tx.send(message);
+        }
+        ProtocolMessage::Request(request) => {
+            // Reverse requests (`runInTerminal`, `startDebugging`) are not
+            // advertised in our capabilities; refuse anything that arrives
+            // so the adapter is not left waiting.
+            tracing::debug!("Refusing DAP reverse request: {}", request.command);
+            let reply = ProtocolMessage::Response(Response {
+                seq: state.next_seq(),
+                request_seq: request.seq,
+                success: false,
+                command: request.command,
+                message: Some("not supported by this client".to_string()),
+                body: None,
+            });
+            if let Err(e) = state.write_message(&reply).await {
+                tracing::warn!("Failed to answer DAP reverse request: {}", e);
+            }
+        }
+    }
+}
+
+/// Read adapter messages until stdout closes, then fail every pending
+/// request and report the session as terminated.
+#[allow(clippy::let_underscore_must_use)] // async_tx.send() is best-effort
+fn spawn_stdout_reader(mut stdout: BufReader<ChildStdout>, state: DapState) {
+    tokio::spawn(async move {
+        loop {
+            match read_message(&mut stdout).await {
+                Ok(message) => handle_message(message, &state).await,
+                Err(e) => {
+                    tracing::info!("DAP session {} reader exiting: {}", state.session_id, e);
+                    break;
+                }
+            }
+        }
+        for (_, tx) in state.pending.lock().unwrap().drain() {
+            let _ = tx.send(Err(
+                "Debug adapter connection closed while awaiting response".to_string(),
+            ));
+        }
+        let _ = state.async_tx.send(AsyncMessage::DapTerminated {
+            session_id: state.session_id,
+        });
+    });
+}
+
+/// The tokio side of a debug session.
+struct DapTask {
+    /// Keeps the adapter alive; it is killed when the task ends.
+    _process: crate::services::remote::StdioChild,
+    state: DapState,
+    stdout: Option<BufReader<ChildStdout>>,
+}
+
+#[allow(clippy::let_underscore_must_use)] // async_tx.send() is best-effort
+impl DapTask {
+    /// Spawn the adapter through the authority's long-running spawner, so
+    /// container and remote authorities run it where the code lives.
+    async fn spawn(
+        session_id: u64,
+        config: &DebugAdapterConfig,
+        cwd: Option<PathBuf>,
+        async_tx: std_mpsc::Sender<AsyncMessage>,
+        stderr_log_path: PathBuf,
+        long_running_spawner: Arc<dyn crate::services::remote::LongRunningSpawner>,
+    ) -> Result<Self, String> {
+        tracing::info!(
+            "Spawning debug adapter: {} {:?}",
+            config.command,
+            config.args
+        );
+
+        if !long_running_spawner.command_exists(&config.command).await {
+            return Err(format!(
+                "Debug adapter executable '{}' not found in the active authority's PATH. \
+                 Please install it or check your configuration.",
+                config.command
+            ));
+        }
+
+        let env_pairs: Vec<(String, String)> = config
+            .env
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        let mut stdio_child = long_running_spawner
+            .spawn_stdio(
+                &config.command,
+                &config.args,
+                env_pairs,
+                cwd.as_deref(),
+                Some(&config.process_limits),
+            )
+            .await
+            .map_err(|e| format!("Failed to spawn debug adapter '{}': {}", config.command, e))?;
+
+        let stdin = stdio_child
+            .take_stdin()
+            .ok_or_else(|| "Failed to get stdin".to_string())?;
+        let stdout = stdio_child
+            .take_stdout()
+            .ok_or_else(|| "Failed to get stdout".to_string())?;
+
+        if let Some(stderr_stream) = stdio_child.take_stderr() {
+            tokio::spawn(async move {
+                use tokio::io::AsyncBufReadExt;
+                let mut file = match tokio::fs::File::create(&stderr_log_path).await {
+                    Ok(f) => f,
+                    Err(e) => {
+                        tracing::warn!(
+                            "Could not create DAP stderr log {:?}: {}",
+                            stderr_log_path,
+                            e
+                        );
+                        return;
+                    }
+                };
+                let mut lines = BufReader::new(stderr_stream).lines();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    if file
+                        .write_all(format!("{}\n", line).as_bytes())
+                        .await
+                        .is_err()
+                    {
+                        return;
+                    }
+                }
+            });
+        }
+
+        Ok(Self {
+            _process: stdio_child,
+            state: DapState {
+                session_id,
+                stdin: Arc::new(tokio::sync::Mutex::new(stdin)),
+                next_seq: Arc::new(AtomicI64::new(1)),
+                pending: Arc::new(Mutex::new(HashMap::new())),
+                async_tx,
+                supports_configuration_done: false,
+            },
+            stdout: Some(BufReader::new(stdout)),
+        })
+    }
+
+    async fn run(
+        mut self,
+        adapter_id: String,
+        request: DebugRequest,
+        arguments: Value,
+        mut command_rx: mpsc::Receiver<DapCommand>,
+    ) {
+        if let Some(stdout) = self.stdout.take() {
+            spawn_stdout_reader(stdout, self.state.clone());
+        }
+
+        let initialize = self
+            .state
+            .request(
+                "initialize",
+                Some(json!({
+                    "clientID": "fresh",
+                    "clientName": "Fresh",
+                    "adapterID": adapter_id,
+                    "pathFormat": "path",
+                    "linesStartAt1": true,
+                    "columnsStartAt1": true,
+                    "supportsVariableType": true,
+                    "supportsRunInTerminalRequest": false,
+                })),
+            )
+            .await;
+        match initialize {
+            Ok(capabilities) => {
+                self.state.supports_configuration_done = capabilities
+                    .as_ref()
+                    .and_then(|c| c.get("supportsConfigurationDoneRequest"))
+                    .and_then(Value::as_bool)
+                    .unwrap_or(false);
+            }
+            Err(e) => {
+                self.state
+                    .send_error(format!("Debug adapter failed to initialize: {}", e));
+                let _ = self.state.async_tx.send(AsyncMessage::DapTerminated {
+                    session_id: self.state.session_id,
+                });
+                return;
+            }
+        }
+
+        // Many adapters only answer `launch`/`attach` after
+        // `configurationDone`, which the editor sends once the adapter's
+        // `initialized` event arrives — so the response is not awaited here.
+        match self
+            .state
+            .send_request(request.command(), Some(arguments))
+            .await
+        {
+            Ok(pending) => self.state.report_failure(pending),
+            Err(e) => self.state.send_error(e),
+        }
+
+        while let Some(command) = command_rx.recv().await {
+            tracing::trace!(
+                "DAP session {} command: {:?}",
+                self.state.session_id,
+                command
+            );
+            if let DapCommand::Disconnect { terminate_debuggee } = command {
+                let arguments = json!({ "terminateDebuggee": terminate_debuggee });
+                if let Ok(pending) = self.state.send_request("disconnect", Some(arguments)).await {
+                    let _ = self.state.await_response(pending, DISCONNECT_TIMEOUT).await;
+                }
+                break;
+            }
+            if let Err(e) = self.state.handle_command(command).await {
+                self.state.send_error(e);
+            }
+        }
+        tracing::info!("DAP session {} finished", self.state.session_id);
+    }
+}
+
+/// Synchronous handle to a debug session running on the tokio runtime.
+pub struct DapHandle {
+    session_id: u64,
+    command_tx: mpsc::Sender<DapCommand>,
+}
+
+#[allow(clippy::let_underscore_must_use)] // async_tx.send() is best-effort
+impl DapHandle {
+    /// Spawn `config`'s adapter and start a session with it: `initialize`,
+    /// then `launch` or `attach` with `arguments`. Progress and failures
+    /// arrive on `async_bridge` tagged with `session_id`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn spawn(
+        runtime: &tokio::runtime::Handle,
+        session_id: u64,
+        adapter_id: &str,
+        config: &DebugAdapterConfig,
+        cwd: Option<PathBuf>,
+        request: DebugRequest,
+        arguments: Value,
+        async_bridge: &AsyncBridge,
+        long_running_spawner: Arc<dyn crate::services::remote::LongRunningSpawner>,
+    ) -> Self {
+        let (command_tx, command_rx) = mpsc::channel(100);
+        let async_tx = async_bridge.sender();
+        let config = config.clone();
+        let adapter_id = adapter_id.to_string();
+        let stderr_log_path = crate::services::log_dirs::dap_log_path(&adapter_id);
+
+        runtime.spawn(async move {
+            match DapTask::spawn(
+                session_id,
+                &config,
+                cwd,
+                async_tx.clone(),
+                stderr_log_path,
+                long_running_spawner,
+            )
+            .await
+            {
+                Ok(task) => task.run(adapter_id, request, arguments, command_rx).await,
+                Err(e) => {
+                    tracing::error!("Failed to spawn debug adapter: {}", e);
+                    let _ = async_tx.send(AsyncMessage::DapError {
+                        session_id,
+                        error: e,
+                    });
+                    let _ = async_tx.send(AsyncMessage::DapTerminated { session_id });
+                }
+            }
+        });
+
+        Self {
+            session_id,
+            command_tx,
+        }
+    }
+
+    /// The id tagging this session's async messages.
+    pub fn session_id(&self) -> u64 {
+        self.session_id
+    }
+
+    /// Queue a command for the session.
+    pub fn send(&self, command: DapCommand) -> Result<(), String> {
+        self.command_tx
+            .try_send(command)
+            .map_err(|e| format!("Debug session is not accepting commands: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_response_error_prefers_message() {
+        let mut response = Response {
+            seq: 2,
+            request_seq: 1,
+            success: false,
+            command: "launch".to_string(),
+            message: None,
+            body: Some(json!({"error": {"id": 1, "format": "program not found"}})),
+        };
+        assert_eq!(response_error(&response), "program not found");
+
+        response.message = Some("cancelled".to_string());
+        assert_eq!(response_error(&response), "cancelled");
+
+        response.message = None;
+        response.body = None;
+        assert_eq!(response_error(&response), "'launch' request failed");
+    }
+
+    #[test]
+    fn test_parse_body_field() {
+        let frames: Vec<StackFrame> = parse_body_field(
+            Some(json!({"stackFrames": [{"id": 1, "name": "main", "line": 3, "column": 1}]})),
+            "stackFrames",
+        )
+        .unwrap();
+        assert_eq!(frames.len(), 1);
+        assert!(parse_body_field::<Vec<StackFrame>>(None, "stackFrames").is_err());
+    }
+}
@@ -0,0 +1,245 @@
+//! Per-window debug session bookkeeping.
+//!
+//! [`DapManager`] owns at most one [`DapSession`] at a time. It spawns the
+//! session's adapter through the window's authority, forwards commands to
+//! it and keeps the last state reported by the adapter (stopped thread,
+//! call stack, variables, output) so the editor can redraw the debug
+//! panel and margin markers without another round trip.
+
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use crate::services::async_bridge::AsyncBridge;
+use crate::services::dap::async_handler::{DapCommand, DapHandle};
+use crate::services::dap::protocol::{ScopeVariables, StackFrame};
+use crate::types::{DebugAdapterConfig, DebugConfiguration};
+
+/// Counter for session ids. Global rather than per-window so an async
+/// message can be routed to its window by id alone.
+static NEXT_SESSION_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Most recent output lines kept per session.
+const MAX_OUTPUT_LINES: usize = 1000;
+
+/// Execution state of the debuggee as last reported by the adapter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DapSessionState {
+    /// Adapter spawned; configuration not finished yet.
+    Starting,
+    Running,
+    Stopped {
+        reason: String,
+    },
+}
+
+/// A running debug session.
+pub struct DapSession {
+    /// Name of the debug configuration the session was started from.
+    pub name: String,
+    handle: DapHandle,
+    pub state: DapSessionState,
+    /// Thread of the last `stopped` event.
+    pub thread_id: Option<i64>,
+    /// Call stack of `thread_id`, innermost frame first.
+    pub frames: Vec<StackFrame>,
+    /// Frame whose scopes are shown (the top frame unless one was picked).
+    pub selected_frame: Option<i64>,
+    pub scopes: Vec<ScopeVariables>,
+    /// Debuggee and adapter output, oldest line first.
+    pub output: Vec<String>,
+}
+
+impl DapSession {
+    pub fn id(&self) -> u64 {
+        self.handle.session_id()
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        matches!(self.state, DapSessionState::Stopped { .. })
+    }
+
+    /// The currently selected stack frame.
+    pub fn current_frame(&self) -> Option<&StackFrame> {
+        let selected = self.selected_frame?;
+        self.frames.iter().find(|f| f.id == selected)
+    }
+
+    /// Forget everything tied to the last stop, once execution resumes.
+    pub fn clear_stop(&mut self) {
+        self.frames.clear();
+        self.selected_frame = None;
+        self.scopes.clear();
+    }
+
+    /// Append output text, keeping at most [`MAX_OUTPUT_LINES`] lines.
+    pub fn push_output(&mut self, text: &str) {
+        for line in text.lines() {
+            self.output.push(line.to_string());
+        }
+        let excess = self.output.len().saturating_sub(MAX_OUTPUT_LINES);
+        self.output.drain(..excess);
+    }
+}
+
+/// Debug session manager — one per window, like [`crate::services::lsp::manager::LspManager`].
+pub struct DapManager {
+    /// Window that owns this manager.
+    window_id: fresh_core::WindowId,
+    runtime: Option<tokio::runtime::Handle>,
+    async_bridge: Option<AsyncBridge>,
+    /// The window authority's spawner; adapters run wherever LSP servers do.
+    long_running_spawner: Option<Arc<dyn crate::services::remote::LongRunningSpawner>>,
+    session: Option<DapSession>,
+}
+
+impl DapManager {
+    pub fn new(window_id: fresh_core::WindowId) -> Self {
+        Self {
+            window_id,
+            runtime: None,
+            async_bridge: None,
+            long_running_spawner: None,
+            session: None,
+        }
+    }
+
+    /// Set the tokio runtime and bridge used to run adapters.
+    pub fn set_runtime(&mut self, runtime: tokio::runtime::Handle, async_bridge: AsyncBridge) {
+        self.runtime = Some(runtime);
+        self.async_bridge = Some(async_bridge);
+    }
+
+    /// Install the active authority's long-running spawner.
+    pub fn set_long_running_spawner(
+        &mut self,
+        spawner: Arc<dyn crate::services::remote::LongRunningSpawner>,
+    ) {
+        self.long_running_spawner = Some(spawner);
+    }
+
+    pub fn session(&self) -> Option<&DapSession> {
+        self.session.as_ref()
+    }
+
+    pub fn session_mut(&mut self) -> Option<&mut DapSession> {
+        self.session.as_mut()
+    }
+
+    /// The session with `session_id`, if it is still this window's session.
+    pub fn session_by_id(&mut self, session_id: u64) -> Option<&mut DapSession> {
+        self.session.as_mut().filter(|s| s.id() == session_id)
+    }
+
+    /// Start a session for `configuration`, ending any current one first.
+    /// `workspace_folder` and `file` resolve the configuration's variables;
+    /// the adapter runs with the workspace folder as its working directory.
+    pub fn start(
+        &mut self,
+        configuration: &DebugConfiguration,
+        adapter: &DebugAdapterConfig,
+        workspace_folder: &Path,
+        file: Option<&Path>,
+    ) -> Result<u64, String> {
+        let (Some(runtime), Some(bridge), Some(spawner)) = (
+            self.runtime.as_ref(),
+            self.async_bridge.as_ref(),
+            self.long_running_spawner.as_ref(),
+        ) else {
+            return Err("async runtime not available".to_string());
+        };
+        let runtime = runtime.clone();
+        let bridge = bridge.clone();
+        let spawner = spawner.clone();
+
+        self.stop();
+
+        let session_id = NEXT_SESSION_ID.fetch_add(1, Ordering::Relaxed);
+        tracing::info!(
+            "Starting debug session {} ('{}') in window {:?}",
+            session_id,
+            configuration.name,
+            self.window_id
+        );
+        let handle = DapHandle::spawn(
+            &runtime,
+            session_id,
+            &configuration.adapter,
+            adapter,
+            Some(workspace_folder.to_path_buf()),
+            configuration.request,
+            configuration.resolved_arguments(workspace_folder, file),
+            &bridge,
+            spawner,
+        );
+        self.session = Some(DapSession {
+            name: configuration.name.clone(),
+            handle,
+            state: DapSessionState::Starting,
+            thread_id: None,
+            frames: Vec::new(),
+            selected_frame: None,
+            scopes: Vec::new(),
+            output: Vec::new(),
+        });
+        Ok(session_id)
+    }
+
+    /// Send a command to the current session.
+    pub fn send(&self, command: DapCommand) -> Result<(), String> {
+        match &self.session {
+            Some(session) => session.handle.send(command),
+            None => Err("no debug session".to_string()),
+        }
+    }
+
+    /// Disconnect and drop the current session. Returns false if there was
+    /// none. A launched debuggee is terminated with it.
+    pub fn stop(&mut self) -> bool {
+        let Some(session) = self.session.take() else {
+            return false;
+        };
+        if let Err(e) = session.handle.send(DapCommand::Disconnect {
+            terminate_debuggee: true,
+        }) {
+            tracing::debug!("Debug session {} already gone: {}", session.id(), e);
+        }
+        true
+    }
+
+    /// Drop the session after the adapter reported it terminated. Returns
+    /// false if `session_id` is not the current session.
+    pub fn end_session(&mut self, session_id: u64) -> bool {
+        if self.session.as_ref().is_some_and(|s| s.id() == session_id) {
+            self.session = None;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_start_without_runtime_fails() {
+        let mut manager = DapManager::new(fresh_core::WindowId(1));
+        let configuration = DebugConfiguration {
+            name: "app".to_string(),
+            adapter: "fake".to_string(),
+            ..Default::default()
+        };
+        let adapter = DebugAdapterConfig {
+            command: "fake-adapter".to_string(),
+            ..Default::default()
+        };
+        assert!(manager
+            .start(&configuration, &adapter, Path::new("/tmp"), None)
+            .is_err());
+        assert!(manager.session().is_none());
+        assert!(!manager.stop());
+        assert!(manager.send(DapCommand::ConfigurationDone).is_err());
+    }
+}
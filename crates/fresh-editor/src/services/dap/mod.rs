@@ -0,0 +1,78 @@
+//! DAP (Debug Adapter Protocol) Client Architecture
+//!
+//! This module implements a debugger client for the Fresh editor. It talks to
+//! any debug adapter that speaks DAP over stdio (`lldb-dap`, `debugpy`,
+//! `dlv dap`, `js-debug`, ...) and is built like the LSP client next door:
+//! a sync handle on the editor side, an async task on the tokio runtime, and
+//! results delivered through the window's `AsyncBridge`.
+//!
+//! # Architecture Overview
+//!
+//! ```text
+//! ┌─────────────────────────────────────────────────────────────────────────┐
+//! │                           Editor (App)                                  │
+//! │                                                                         │
+//! │  app/dap_actions.rs                                                     │
+//! │  - Debug: Start/Stop/Continue/Step… commands                            │
+//! │  - breakpoints as margin indicators (namespace "dap-breakpoint")        │
+//! │  - stop location marker, inline values as virtual text                  │
+//! │  - stack/variables/output panel (buffer group)                          │
+//! └──────────────────────────────┬──────────────────────────────────────────┘
+//!                                │
+//!                                ▼
+//! ┌─────────────────────────────────────────────────────────────────────────┐
+//! │  DapManager (one per window)                                            │
+//! │  - at most one DapSession: state, thread, frames, scopes, output        │
+//! │  - spawns adapters through the window authority's spawner               │
+//! └──────────────────────────────┬──────────────────────────────────────────┘
+//!                                │ DapCommand (tokio mpsc)
+//!                                ▼
+//! ┌─────────────────────────────────────────────────────────────────────────┐
+//! │  DapTask (tokio)                                                        │
+//! │  - initialize → launch/attach, then one request per command            │
+//! │  - stdout reader: responses → pending requests, events → AsyncMessage   │
+//! └──────────────────────────────┬──────────────────────────────────────────┘
+//!                                │ stdin/stdout (Content-Length framing)
+//!                                ▼
+//!                      ┌───────────────────┐
+//!                      │  debug adapter    │
+//!                      │  (subprocess)     │
+//!                      └───────────────────┘
+//! ```
+//!
+//! # Module Structure
+//!
+//! - **`protocol`**: DAP message framing and the body types the editor
+//!   renders (stack frames, scopes, variables).
+//!
+//! - **`async_handler`**: [`async_handler::DapHandle`] and `DapTask`. The
+//!   handle queues [`async_handler::DapCommand`]s; the task owns the adapter
+//!   process and turns its events into `AsyncMessage::Dap*` messages tagged
+//!   with the session id.
+//!
+//! - **`manager`**: [`manager::DapManager`] - per-window session lifecycle
+//!   and the last state reported by the adapter.
+//!
+//! # Session Flow
+//!
+//! 1. `Debug: Start` resolves a `debug_configurations` entry and spawns its
+//!    adapter from `debug_adapters`
+//! 2. `DapTask` sends `initialize`, then `launch` or `attach` without
+//!    waiting for the reply (adapters may hold it until configuration ends)
+//! 3. The adapter's `initialized` event reaches the editor, which sends
+//!    `setBreakpoints` for every file with breakpoints, then
+//!    `configurationDone`
+//! 4. A `stopped` event makes the editor request the stack trace; the top
+//!    frame's scopes and variables come back in the same message
+//! 5. `terminated` (or the adapter exiting) ends the session
+//!
+//! # Error Handling
+//!
+//! - **Adapter missing / spawn failure**: `DapError` then `DapTerminated`
+//! - **Failed requests**: reported as `DapError`, shown in the status bar
+//! - **Request timeout**: the pending request is dropped and reported
+//! - **Reverse requests** (`runInTerminal`, ...): answered with a failure
+
+pub mod async_handler;
+pub mod manager;
+pub mod protocol;
@@ -0,0 +1,246 @@
+//! Debug Adapter Protocol wire format.
+//!
+//! DAP shares the `Content-Length` framing of LSP but not JSON-RPC: every
+//! message carries a `seq` number and a `type` of `request`, `response` or
+//! `event`, and responses refer back to their request through
+//! `request_seq`. Only the handful of body types the editor renders are
+//! modelled here; everything else stays a raw [`Value`].
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt};
+
+/// A single DAP message.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum ProtocolMessage {
+    Request(Request),
+    Response(Response),
+    Event(Event),
+}
+
+/// A request, sent by the client — or by the adapter for "reverse"
+/// requests such as `runInTerminal`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Request {
+    pub seq: i64,
+    pub command: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arguments: Option<Value>,
+}
+
+/// The reply to a request. `message` carries the error text when
+/// `success` is false.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Response {
+    pub seq: i64,
+    pub request_seq: i64,
+    pub success: bool,
+    pub command: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<Value>,
+}
+
+/// An adapter-initiated notification (`stopped`, `output`, ...).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Event {
+    pub seq: i64,
+    pub event: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<Value>,
+}
+
+/// A source file reference.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Source {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub path: Option<String>,
+}
+
+/// One frame of a `stackTrace` response. `line` and `column` are 1-based
+/// (the client announces `linesStartAt1`/`columnsStartAt1`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StackFrame {
+    pub id: i64,
+    pub name: String,
+    #[serde(default)]
+    pub source: Option<Source>,
+    #[serde(default)]
+    pub line: usize,
+    #[serde(default)]
+    pub column: usize,
+}
+
+impl StackFrame {
+    /// The frame's source file path, if the adapter reported one.
+    pub fn path(&self) -> Option<&str> {
+        self.source.as_ref().and_then(|s| s.path.as_deref())
+    }
+}
+
+/// A variable scope of a stack frame (locals, globals, registers, ...).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Scope {
+    pub name: String,
+    pub variables_reference: i64,
+    /// Adapters flag scopes that are costly to fetch; those are listed but
+    /// their variables are not requested up front.
+    #[serde(default)]
+    pub expensive: bool,
+}
+
+/// A named value inside a scope.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Variable {
+    pub name: String,
+    pub value: String,
+    #[serde(default, rename = "type")]
+    pub type_name: Option<String>,
+    #[serde(default)]
+    pub variables_reference: i64,
+}
+
+/// A scope together with the variables fetched for it (empty for
+/// expensive scopes).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScopeVariables {
+    pub scope: Scope,
+    pub variables: Vec<Variable>,
+}
+
+/// Serialize a message with its `Content-Length` header.
+pub fn encode_message(message: &ProtocolMessage) -> Result<Vec<u8>, String> {
+    let json = serde_json::to_string(message).map_err(|e| format!("Serialization error: {}", e))?;
+    let mut out = format!("Content-Length: {}\r\n\r\n", json.len()).into_bytes();
+    out.extend_from_slice(json.as_bytes());
+    Ok(out)
+}
+
+/// Read one framed message from the adapter's stdout.
+pub async fn read_message<R: AsyncBufRead + Unpin>(
+    reader: &mut R,
+) -> Result<ProtocolMessage, String> {
+    let mut content_length: Option<usize> = None;
+
+    loop {
+        let mut line = String::new();
+        let bytes_read = reader
+            .read_line(&mut line)
+            .await
+            .map_err(|e| format!("Failed to read from stdout: {}", e))?;
+
+        if bytes_read == 0 {
+            return Err("Debug adapter closed stdout (EOF)".to_string());
+        }
+
+        if line == "\r\n" {
+            break;
+        }
+
+        if let Some(len_str) = line.strip_prefix("Content-Length: ") {
+            content_length = Some(
+                len_str
+                    .trim()
+                    .parse()
+                    .map_err(|e| format!("Invalid Content-Length: {}", e))?,
+            );
+        }
+    }
+
+    let content_length =
+        content_length.ok_or_else(|| "Missing Content-Length header".to_string())?;
+
+    let mut content = vec![0u8; content_length];
+    reader
+        .read_exact(&mut content)
+        .await
+        .map_err(|e| format!("Failed to read content: {}", e))?;
+
+    let json = String::from_utf8(content).map_err(|e| format!("Invalid UTF-8: {}", e))?;
+
+    tracing::trace!("Received DAP message: {}", json);
+
+    serde_json::from_str(&json).map_err(|e| format!("Failed to deserialize message: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::BufReader;
+
+    #[tokio::test]
+    async fn test_round_trip_request() {
+        let message = ProtocolMessage::Request(Request {
+            seq: 3,
+            command: "next".to_string(),
+            arguments: Some(serde_json::json!({"threadId": 1})),
+        });
+        let bytes = encode_message(&message).unwrap();
+        let text = String::from_utf8(bytes.clone()).unwrap();
+        assert!(text.starts_with("Content-Length: "));
+        assert!(text.contains(r#""type":"request""#));
+
+        let mut reader = BufReader::new(bytes.as_slice());
+        assert_eq!(read_message(&mut reader).await.unwrap(), message);
+    }
+
+    #[tokio::test]
+    async fn test_reads_consecutive_messages() {
+        let event = r#"{"seq":1,"type":"event","event":"stopped","body":{"reason":"breakpoint","threadId":1}}"#;
+        let response = r#"{"seq":2,"type":"response","request_seq":1,"success":false,"command":"launch","message":"no program"}"#;
+        let stream = format!(
+            "Content-Length: {}\r\n\r\n{}Content-Length: {}\r\n\r\n{}",
+            event.len(),
+            event,
+            response.len(),
+            response
+        );
+        let mut reader = BufReader::new(stream.as_bytes());
+
+        match read_message(&mut reader).await.unwrap() {
+            ProtocolMessage::Event(e) => {
+                assert_eq!(e.event, "stopped");
+                assert_eq!(e.body.unwrap()["threadId"], 1);
+            }
+            other => panic!("expected event, got {:?}", other),
+        }
+        match read_message(&mut reader).await.unwrap() {
+            ProtocolMessage::Response(r) => {
+                assert_eq!(r.request_seq, 1);
+                assert!(!r.success);
+                assert_eq!(r.message.as_deref(), Some("no program"));
+            }
+            other => panic!("expected response, got {:?}", other),
+        }
+        assert!(read_message(&mut reader).await.is_err());
+    }
+
+    #[test]
+    fn test_stack_frame_and_variables_deserialize() {
+        let frame: StackFrame = serde_json::from_value(serde_json::json!({
+            "id": 1000,
+            "name": "main",
+            "source": {"name": "main.rs", "path": "/src/main.rs"},
+            "line": 12,
+            "column": 5
+        }))
+        .unwrap();
+        assert_eq!(frame.path(), Some("/src/main.rs"));
+        assert_eq!(frame.line, 12);
+
+        let variable: Variable = serde_json::from_value(serde_json::json!({
+            "name": "count",
+            "value": "3",
+            "type": "i32",
+            "variablesReference": 0
+        }))
+        .unwrap();
+        assert_eq!(variable.type_name.as_deref(), Some("i32"));
+    }
+}
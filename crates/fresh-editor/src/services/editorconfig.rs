@@ -3,10 +3,7 @@
 //! When a file is opened, Fresh walks up the directory tree from the file's
 //! location, reading any `.editorconfig` files it finds (through the
 //! [`FileSystem`] abstraction so the feature works transparently on remote
-//! hosts), and applies the matching indentation settings to the buffer.
-//!
-//! Only the indentation-related properties are mapped to Fresh's per-buffer
-//! settings today:
+//! hosts), and applies the matching settings to the buffer.
 //!
 //! | EditorConfig property | Buffer setting |
 //! |-----------------------|----------------|
@@ -14,9 +11,16 @@
 //! | `indent_style = space`| `use_tabs = false` |
 //! | `indent_size = <n>`   | `tab_size = n` |
 //! | `tab_width = <n>`     | `tab_size = n` (takes precedence over `indent_size`) |
+//! | `end_of_line`         | line ending of new files, and of every file on save |
+//! | `charset`             | encoding used to read the file and to write it |
+//! | `trim_trailing_whitespace` | overrides `editor.trim_trailing_whitespace_on_save` |
+//! | `insert_final_newline`     | overrides `editor.ensure_final_newline_on_save` |
+//! | `max_line_length = <n>`    | a ruler and the soft-wrap column at `n` |
 //!
-//! The parser keeps every key/value pair it reads, so wiring up additional
-//! properties later is a localized change in [`apply_section`].
+//! `insert_final_newline = false` turns the on-save newline off; it does not
+//! strip a newline the file already ends with. Each resolved value remembers
+//! the `.editorconfig` file it came from, so the buffer settings popup can
+//! say why a setting differs from the config.
 //!
 //! Glob matching follows the EditorConfig spec: `*`, `**`, `?`, `[seq]` /
 //! `[!seq]` character classes, and `{a,b,c}` brace alternation are supported.
@@ -27,9 +31,10 @@ use std::path::{Path, PathBuf};
 
 use regex::Regex;
 
+use crate::model::buffer::{Encoding, LineEnding};
 use crate::model::filesystem::FileSystem;
 
-/// Indentation settings resolved from `.editorconfig` for a single file.
+/// Settings resolved from `.editorconfig` for a single file.
 ///
 /// Each field is `None` when no applicable `.editorconfig` section specified
 /// it, leaving the corresponding buffer setting untouched.
@@ -39,12 +44,48 @@ pub struct EditorConfigSettings {
     pub use_tabs: Option<bool>,
     /// Effective indent / tab width (`indent_size` or `tab_width`).
     pub tab_size: Option<usize>,
+    /// `end_of_line`.
+    pub line_ending: Option<LineEnding>,
+    /// `charset`.
+    pub encoding: Option<Encoding>,
+    /// `trim_trailing_whitespace`.
+    pub trim_trailing_whitespace: Option<bool>,
+    /// `insert_final_newline`.
+    pub insert_final_newline: Option<bool>,
+    /// `max_line_length`; `off` leaves it unset.
+    pub max_line_length: Option<usize>,
+    /// The `.editorconfig` file each resolved property came from, keyed by
+    /// property name (`indent_style`, `charset`, …).
+    pub sources: Vec<(&'static str, PathBuf)>,
 }
 
 impl EditorConfigSettings {
     /// True when no setting was resolved.
     pub fn is_empty(&self) -> bool {
-        self.use_tabs.is_none() && self.tab_size.is_none()
+        self.use_tabs.is_none()
+            && self.tab_size.is_none()
+            && self.line_ending.is_none()
+            && self.encoding.is_none()
+            && self.trim_trailing_whitespace.is_none()
+            && self.insert_final_newline.is_none()
+            && self.max_line_length.is_none()
+    }
+
+    /// The `.editorconfig` file that set `property`, if any did.
+    pub fn source_of(&self, property: &str) -> Option<&Path> {
+        self.sources
+            .iter()
+            .find(|(key, _)| *key == property)
+            .map(|(_, path)| path.as_path())
+    }
+
+    /// Record `source` as the origin of `property` (`None` clears it, for a
+    /// property a later section `unset`).
+    fn set_source(&mut self, property: &'static str, source: Option<&Path>) {
+        self.sources.retain(|(key, _)| *key != property);
+        if let Some(source) = source {
+            self.sources.push((property, source.to_path_buf()));
+        }
     }
 }
 
@@ -65,7 +106,7 @@ struct ParsedFile {
     sections: Vec<Section>,
 }
 
-/// Resolve the indentation settings that apply to `file_path`.
+/// Resolve the settings that apply to `file_path`.
 ///
 /// Walks up from the file's directory, reading `.editorconfig` files via `fs`,
 /// stopping once a file declares `root = true`. Sections closer to the file
@@ -95,7 +136,7 @@ pub fn resolve_for_file(fs: &dyn FileSystem, file_path: &Path) -> EditorConfigSe
     for (ec_dir, parsed) in chain.iter().rev() {
         for section in &parsed.sections {
             if section_matches(&section.pattern, ec_dir, file_path) {
-                apply_section(&mut settings, section);
+                apply_section(&mut settings, section, &ec_dir.join(".editorconfig"));
             }
         }
     }
@@ -139,9 +180,10 @@ fn parse(content: &str) -> ParsedFile {
     file
 }
 
-/// Apply a matching section's indentation properties onto `acc`, overwriting
-/// any previously resolved values (later/closer sections win).
-fn apply_section(acc: &mut EditorConfigSettings, section: &Section) {
+/// Apply a matching section's properties onto `acc`, overwriting any
+/// previously resolved values (later/closer sections win). `source` is the
+/// `.editorconfig` file the section belongs to.
+fn apply_section(acc: &mut EditorConfigSettings, section: &Section, source: &Path) {
     let mut indent_style: Option<&str> = None;
     let mut indent_size: Option<&str> = None;
     let mut tab_width: Option<&str> = None;
@@ -150,15 +192,68 @@ fn apply_section(acc: &mut EditorConfigSettings, section: &Section) {
             "indent_style" => indent_style = Some(value),
             "indent_size" => indent_size = Some(value),
             "tab_width" => tab_width = Some(value),
+            "end_of_line" => {
+                let line_ending = match value.as_str() {
+                    "lf" => Some(LineEnding::LF),
+                    "crlf" => Some(LineEnding::CRLF),
+                    "cr" => Some(LineEnding::CR),
+                    "unset" => None,
+                    _ => continue,
+                };
+                acc.line_ending = line_ending;
+                acc.set_source("end_of_line", line_ending.map(|_| source));
+            }
+            "charset" => {
+                let encoding = match value.as_str() {
+                    "utf-8" => Some(Encoding::Utf8),
+                    "utf-8-bom" => Some(Encoding::Utf8Bom),
+                    "utf-16le" => Some(Encoding::Utf16Le),
+                    "utf-16be" => Some(Encoding::Utf16Be),
+                    "latin1" => Some(Encoding::Latin1),
+                    "unset" => None,
+                    _ => continue,
+                };
+                acc.encoding = encoding;
+                acc.set_source("charset", encoding.map(|_| source));
+            }
+            "trim_trailing_whitespace" => {
+                let Some(flag) = parse_flag(value) else {
+                    continue;
+                };
+                acc.trim_trailing_whitespace = flag;
+                acc.set_source("trim_trailing_whitespace", flag.map(|_| source));
+            }
+            "insert_final_newline" => {
+                let Some(flag) = parse_flag(value) else {
+                    continue;
+                };
+                acc.insert_final_newline = flag;
+                acc.set_source("insert_final_newline", flag.map(|_| source));
+            }
+            "max_line_length" => {
+                let length = match value.as_str() {
+                    "off" | "unset" => None,
+                    other => match parse_positive(other) {
+                        Some(n) => Some(n),
+                        None => continue,
+                    },
+                };
+                acc.max_line_length = length;
+                acc.set_source("max_line_length", length.map(|_| source));
+            }
             _ => {}
         }
     }
 
-    match indent_style {
-        Some("tab") => acc.use_tabs = Some(true),
-        Some("space") => acc.use_tabs = Some(false),
+    let use_tabs = match indent_style {
+        Some("tab") => Some(true),
+        Some("space") => Some(false),
         // "unset" and unknown values leave the current setting untouched.
-        _ => {}
+        _ => None,
+    };
+    if use_tabs.is_some() {
+        acc.use_tabs = use_tabs;
+        acc.set_source("indent_style", Some(source));
     }
 
     // `tab_width` overrides `indent_size` for the display width of a tab;
@@ -168,6 +263,18 @@ fn apply_section(acc: &mut EditorConfigSettings, section: &Section) {
         .or_else(|| indent_size.and_then(parse_positive));
     if let Some(width) = width {
         acc.tab_size = Some(width);
+        acc.set_source("indent_size", Some(source));
+    }
+}
+
+/// Parse a boolean property: `Some(Some(b))` for `true`/`false`,
+/// `Some(None)` for `unset`, `None` for anything else (ignored).
+fn parse_flag(value: &str) -> Option<Option<bool>> {
+    match value {
+        "true" => Some(Some(true)),
+        "false" => Some(Some(false)),
+        "unset" => Some(None),
+        _ => None,
     }
 }
 
@@ -587,6 +694,7 @@ mod tests {
                     ("indent_size".into(), "2".into()),
                 ],
             },
+            Path::new("/p/.editorconfig"),
         );
         assert_eq!(acc.use_tabs, Some(false));
         assert_eq!(acc.tab_size, Some(2));
@@ -604,10 +712,65 @@ mod tests {
                     ("tab_width".into(), "8".into()),
                 ],
             },
+            Path::new("/p/.editorconfig"),
         );
         assert_eq!(acc.tab_size, Some(8));
     }
 
+    #[test]
+    fn apply_file_format_and_on_save_properties() {
+        let source = Path::new("/p/.editorconfig");
+        let mut acc = EditorConfigSettings::default();
+        apply_section(
+            &mut acc,
+            &Section {
+                pattern: "*".into(),
+                props: vec![
+                    ("end_of_line".into(), "crlf".into()),
+                    ("charset".into(), "latin1".into()),
+                    ("trim_trailing_whitespace".into(), "true".into()),
+                    ("insert_final_newline".into(), "false".into()),
+                    ("max_line_length".into(), "100".into()),
+                ],
+            },
+            source,
+        );
+        assert_eq!(acc.line_ending, Some(LineEnding::CRLF));
+        assert_eq!(acc.encoding, Some(Encoding::Latin1));
+        assert_eq!(acc.trim_trailing_whitespace, Some(true));
+        assert_eq!(acc.insert_final_newline, Some(false));
+        assert_eq!(acc.max_line_length, Some(100));
+        assert_eq!(acc.source_of("charset"), Some(source));
+        assert_eq!(acc.source_of("indent_style"), None);
+    }
+
+    #[test]
+    fn apply_unset_clears_earlier_value_and_source() {
+        let mut acc = EditorConfigSettings::default();
+        let section = |props: &[(&str, &str)]| Section {
+            pattern: "*".into(),
+            props: props
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        };
+        apply_section(
+            &mut acc,
+            &section(&[("max_line_length", "80"), ("end_of_line", "lf")]),
+            Path::new("/.editorconfig"),
+        );
+        assert_eq!(acc.max_line_length, Some(80));
+        apply_section(
+            &mut acc,
+            &section(&[("max_line_length", "off"), ("end_of_line", "unset")]),
+            Path::new("/p/.editorconfig"),
+        );
+        assert_eq!(acc.max_line_length, None);
+        assert_eq!(acc.line_ending, None);
+        assert!(acc.sources.is_empty());
+        assert!(acc.is_empty());
+    }
+
     // --- resolve_for_file (real filesystem walk) ---
 
     #[test]
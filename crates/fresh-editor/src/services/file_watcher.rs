@@ -1,4 +1,4 @@
-//! Plugin-driven filesystem watching.
+//! Filesystem watching for plugins and for the editor itself.
 //!
 //! Backs the `watchPath` / `unwatchPath` plugin API and the
 //! `path_changed` plugin hook. One process-wide `notify::Watcher`
@@ -12,6 +12,16 @@
 //! `notify::Event::paths` (no canonicalisation, no debouncing —
 //! plugins decide their dedup policy).
 //!
+//! The editor registers its own handles on the same watcher (see
+//! [`FileWatcherManager::sync_editor_watches`]): the directories of
+//! open auto-revert buffers and the expanded file-explorer
+//! directories. Their events skip the plugin hook and go through
+//! [`WatchEventDebouncer`] to auto-revert and explorer refresh.
+//! Directories that can't be watched — kernel watch limit, network
+//! filesystems that don't deliver events — stay on mtime polling.
+//! On a remote filesystem the agent watches for the editor
+//! ([`FileSystem::watch_dir`]) and its events take the same route.
+//!
 //! **Why not per-plugin watchers?** notify's backends (inotify on
 //! Linux, kqueue on BSD/macOS, ReadDirectoryChangesW on Windows)
 //! all have per-process file-descriptor / handle limits. A single
@@ -20,15 +30,17 @@
 //! Orchestrator's collision radar is watching one path per worktree
 //! across N sessions.
 
+use crate::model::filesystem::{DirWatchEvent, FileSystem};
 use crate::services::async_bridge::{AsyncBridge, AsyncMessage, PathChangeKind};
 use notify::{
     event::{CreateKind, EventKind, ModifyKind, RemoveKind},
     RecommendedWatcher, RecursiveMode, Watcher,
 };
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 /// Allocate a process-globally-unique watch handle. The notify-callback
 /// lookup table ([`handle_map`]) is process-global, so handles must be
@@ -56,6 +68,47 @@ pub struct FileWatcherManager {
     /// when notify fires events for a path that was just
     /// unwatched (rare but possible — events are queued).
     handles: HashMap<u64, (PathBuf, RecursiveMode)>,
+    /// Directories the editor watches for its own use, and how.
+    editor_dirs: HashMap<PathBuf, EditorWatch>,
+    /// `remote_channel_id` of the filesystem `editor_dirs` were
+    /// watched on; a different filesystem starts over.
+    editor_fs: Option<u64>,
+}
+
+/// How one of the editor's directories is watched.
+enum EditorWatch {
+    /// By the shared `notify` watcher, under this handle.
+    Local(u64),
+    /// By the filesystem itself (a remote agent).
+    Remote(RemoteWatch),
+    /// Not at all: its filesystem doesn't report changes. The editor
+    /// polls it.
+    Polled,
+    /// Not yet: watching it failed (kernel watch limit, a directory
+    /// that came and went). Polled meanwhile; each sync tries again.
+    Failed,
+}
+
+/// A [`FileSystem::watch_dir`] watch. Its events are posted as
+/// `PathChanged` under `handle`, like a local watch's.
+struct RemoteWatch {
+    handle: u64,
+    id: u64,
+    fs: Arc<dyn FileSystem + Send + Sync>,
+    state: Arc<AtomicU8>,
+}
+
+// `RemoteWatch::state` values.
+const REMOTE_PENDING: u8 = 0;
+const REMOTE_LIVE: u8 = 1;
+const REMOTE_STOPPED: u8 = 2;
+const REMOTE_DISCONNECTED: u8 = 3;
+const REMOTE_REMOVED: u8 = 4;
+
+impl RemoteWatch {
+    fn state(&self) -> u8 {
+        self.state.load(Ordering::SeqCst)
+    }
 }
 
 impl FileWatcherManager {
@@ -63,6 +116,8 @@ impl FileWatcherManager {
         Self {
             watcher: None,
             handles: HashMap::new(),
+            editor_dirs: HashMap::new(),
+            editor_fs: None,
         }
     }
 
@@ -80,21 +135,233 @@ impl FileWatcherManager {
         path: &Path,
         recursive: bool,
     ) -> Result<u64, String> {
-        if self.watcher.is_none() {
-            self.watcher = Some(build_watcher(bridge.clone())?);
-        }
         let mode = if recursive {
             RecursiveMode::Recursive
         } else {
             RecursiveMode::NonRecursive
         };
-        let watcher = self
-            .watcher
-            .as_mut()
-            .expect("just constructed above if missing");
-        watcher
-            .watch(path, mode)
-            .map_err(|e| format!("watchPath({}): {}", path.display(), e))?;
+        self.add_handle(bridge, path, mode)
+            .map_err(|e| format!("watchPath({}): {}", path.display(), e))
+    }
+
+    /// Drop a registered watcher. Unknown handles are ignored.
+    pub fn unwatch(&mut self, handle: u64) {
+        self.remove_handle(handle);
+    }
+
+    /// Make the editor's own watch set exactly `dirs` (each watched
+    /// non-recursively) on `fs`. Returns the directories that became
+    /// watched in this call: a change that landed before the watch
+    /// existed produces no event, so the caller checks those once by
+    /// hand. A directory whose watch failed is polled and tried again
+    /// on the next call.
+    ///
+    /// Remote filesystems are watched by their agent. Such a watch
+    /// isn't in place until the agent confirms it, so it is never
+    /// returned here; instead its directory is reported as changed
+    /// once it is ready. A watch lost with the connection is set up
+    /// again once the connection is back.
+    pub fn sync_editor_watches(
+        &mut self,
+        bridge: &AsyncBridge,
+        dirs: &HashSet<PathBuf>,
+        fs: &Arc<dyn FileSystem + Send + Sync>,
+    ) -> Vec<PathBuf> {
+        let remote = fs.remote_connection_info().is_some();
+        let connected = fs.is_remote_connected();
+        if self.editor_fs != fs.remote_channel_id() {
+            self.editor_fs = fs.remote_channel_id();
+            let all: Vec<PathBuf> = self.editor_dirs.keys().cloned().collect();
+            for dir in all {
+                self.remove_editor_watch(&dir);
+            }
+        }
+        let stale: Vec<PathBuf> = self
+            .editor_dirs
+            .iter()
+            .filter(|(dir, watch)| {
+                !dirs.contains(*dir)
+                    || matches!(watch, EditorWatch::Remote(w)
+                        if connected && w.state() == REMOTE_DISCONNECTED)
+            })
+            .map(|(dir, _)| dir.clone())
+            .collect();
+        for dir in stale {
+            self.remove_editor_watch(&dir);
+        }
+
+        let mut added = Vec::new();
+        for dir in dirs {
+            if self
+                .editor_dirs
+                .get(dir)
+                .is_some_and(|watch| !matches!(watch, EditorWatch::Failed))
+            {
+                continue;
+            }
+            let watch = if remote {
+                if !connected {
+                    // Nothing can be set up until the connection is
+                    // back; try again then.
+                    continue;
+                }
+                self.add_remote_watch(bridge, dir, fs)
+            } else if delivers_events(dir) {
+                match self.add_handle(bridge, dir, RecursiveMode::NonRecursive) {
+                    Ok(handle) => {
+                        added.push(dir.clone());
+                        EditorWatch::Local(handle)
+                    }
+                    Err(e) => {
+                        tracing::debug!(
+                            "Watching {} failed ({}); polling it instead",
+                            dir.display(),
+                            e
+                        );
+                        EditorWatch::Failed
+                    }
+                }
+            } else {
+                tracing::debug!(
+                    "{} is on a filesystem without change events; polling it",
+                    dir.display()
+                );
+                EditorWatch::Polled
+            };
+            self.editor_dirs.insert(dir.clone(), watch);
+        }
+        added
+    }
+
+    /// Ask `fs` to watch `dir`, posting its events as `PathChanged`.
+    fn add_remote_watch(
+        &mut self,
+        bridge: &AsyncBridge,
+        dir: &Path,
+        fs: &Arc<dyn FileSystem + Send + Sync>,
+    ) -> EditorWatch {
+        let handle = alloc_global_handle();
+        let state = Arc::new(AtomicU8::new(REMOTE_PENDING));
+        let on_event = {
+            let sender = bridge.sender();
+            let state = state.clone();
+            let dir = dir.to_path_buf();
+            move |event: DirWatchEvent| {
+                let path = match event {
+                    // Report the directory once: anything that changed
+                    // before the watch was in place sent no event.
+                    DirWatchEvent::Ready => {
+                        if state
+                            .compare_exchange(
+                                REMOTE_PENDING,
+                                REMOTE_LIVE,
+                                Ordering::SeqCst,
+                                Ordering::SeqCst,
+                            )
+                            .is_err()
+                        {
+                            return;
+                        }
+                        dir.clone()
+                    }
+                    DirWatchEvent::Changed(path) => path,
+                    DirWatchEvent::Stopped | DirWatchEvent::Disconnected => {
+                        let ended = if event == DirWatchEvent::Stopped {
+                            REMOTE_STOPPED
+                        } else {
+                            REMOTE_DISCONNECTED
+                        };
+                        #[allow(clippy::let_underscore_must_use)]
+                        let _ = state.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |s| {
+                            (s != REMOTE_REMOVED).then_some(ended)
+                        });
+                        return;
+                    }
+                };
+                if state.load(Ordering::SeqCst) == REMOTE_LIVE {
+                    #[allow(clippy::let_underscore_must_use)]
+                    let _ = sender.send(AsyncMessage::PathChanged {
+                        handle,
+                        path,
+                        kind: PathChangeKind::Other,
+                    });
+                }
+            }
+        };
+        match fs.watch_dir(dir, Box::new(on_event)) {
+            Ok(id) => EditorWatch::Remote(RemoteWatch {
+                handle,
+                id,
+                fs: fs.clone(),
+                state,
+            }),
+            Err(e) => {
+                tracing::debug!(
+                    "Remote watch of {} failed ({}); polling it instead",
+                    dir.display(),
+                    e
+                );
+                EditorWatch::Failed
+            }
+        }
+    }
+
+    /// Stop the editor's watch of `dir`.
+    fn remove_editor_watch(&mut self, dir: &Path) {
+        match self.editor_dirs.remove(dir) {
+            Some(EditorWatch::Local(handle)) => self.remove_handle(handle),
+            Some(EditorWatch::Remote(watch)) => {
+                let previous = watch.state.swap(REMOTE_REMOVED, Ordering::SeqCst);
+                if matches!(previous, REMOTE_PENDING | REMOTE_LIVE) {
+                    watch.fs.unwatch_dir(watch.id);
+                }
+            }
+            Some(EditorWatch::Polled | EditorWatch::Failed) | None => {}
+        }
+    }
+
+    /// Whether `dir` is covered by an editor watch, i.e. doesn't need
+    /// polling.
+    pub fn is_editor_watched(&self, dir: &Path) -> bool {
+        match self.editor_dirs.get(dir) {
+            Some(EditorWatch::Local(_)) => true,
+            Some(EditorWatch::Remote(watch)) => watch.state() == REMOTE_LIVE,
+            Some(EditorWatch::Polled | EditorWatch::Failed) | None => false,
+        }
+    }
+
+    /// Whether `handle` is one of the editor's own watches rather than
+    /// a plugin's.
+    pub fn is_editor_handle(&self, handle: u64) -> bool {
+        self.editor_dirs.values().any(|watch| match watch {
+            EditorWatch::Local(h) => *h == handle,
+            EditorWatch::Remote(watch) => watch.handle == handle,
+            EditorWatch::Polled | EditorWatch::Failed => false,
+        })
+    }
+
+    /// Register `path` under a fresh handle, starting the `notify`
+    /// watch unless another handle already covers it.
+    fn add_handle(
+        &mut self,
+        bridge: &AsyncBridge,
+        path: &Path,
+        mode: RecursiveMode,
+    ) -> Result<u64, String> {
+        if self.watcher.is_none() {
+            self.watcher = Some(build_watcher(bridge.clone())?);
+        }
+        if self.is_covered(path, mode) {
+            // No new notify watch, but still refuse paths notify
+            // would have refused.
+            std::fs::metadata(path).map_err(|e| e.to_string())?;
+        } else {
+            let watcher = self
+                .watcher
+                .as_mut()
+                .expect("just constructed above if missing");
+            watcher.watch(path, mode).map_err(|e| e.to_string())?;
+        }
         let handle = alloc_global_handle();
         self.handles.insert(handle, (path.to_path_buf(), mode));
         // The notify event callback uses a shared `handles` map
@@ -102,26 +369,61 @@ impl FileWatcherManager {
         // handle owns each event. Update that here too — but the
         // callback uses a clone-on-write Arc<Mutex<>> that we
         // need to thread through. Pulled out into a closure below.
-        register_handle(handle, path);
+        register_handle(handle, path, mode);
         Ok(handle)
     }
 
-    /// Drop a registered watcher. Unknown handles are ignored.
-    pub fn unwatch(&mut self, handle: u64) {
-        if let Some((path, _mode)) = self.handles.remove(&handle) {
-            unregister_handle(handle);
-            if let Some(w) = self.watcher.as_mut() {
-                if let Err(e) = w.unwatch(&path) {
-                    tracing::debug!(
-                        "unwatchPath({}): notify returned {}; \
-                         continuing — the editor's view is now consistent",
-                        path.display(),
-                        e
-                    );
+    /// Drop `handle`, stopping its `notify` watch unless another
+    /// handle still needs it. `notify` keeps one watch per path, so
+    /// removing a recursive watch also re-establishes the watches
+    /// of any handles nested under it.
+    fn remove_handle(&mut self, handle: u64) {
+        let Some((path, mode)) = self.handles.remove(&handle) else {
+            return;
+        };
+        unregister_handle(handle);
+        if self.handles.values().any(|(p, _)| *p == path)
+            || self.is_covered(&path, RecursiveMode::NonRecursive)
+        {
+            return;
+        }
+        let Some(watcher) = self.watcher.as_mut() else {
+            return;
+        };
+        if let Err(e) = watcher.unwatch(&path) {
+            tracing::debug!(
+                "unwatchPath({}): notify returned {}; \
+                 continuing — the editor's view is now consistent",
+                path.display(),
+                e
+            );
+        }
+        if mode == RecursiveMode::NonRecursive {
+            return;
+        }
+        let nested: Vec<(PathBuf, RecursiveMode)> = self
+            .handles
+            .values()
+            .filter(|(p, _)| p.starts_with(&path))
+            .cloned()
+            .collect();
+        for (nested_path, nested_mode) in nested {
+            if let Some(watcher) = self.watcher.as_mut() {
+                if let Err(e) = watcher.watch(&nested_path, nested_mode) {
+                    tracing::debug!("rewatch({}): {}", nested_path.display(), e);
                 }
             }
         }
     }
+
+    /// Whether a registered handle's `notify` watch already delivers
+    /// events for `path` watched with `mode`.
+    fn is_covered(&self, path: &Path, mode: RecursiveMode) -> bool {
+        self.handles.values().any(|(p, m)| {
+            (*m == RecursiveMode::Recursive && path.starts_with(p))
+                || (p == path && mode == RecursiveMode::NonRecursive)
+        })
+    }
 }
 
 impl Default for FileWatcherManager {
@@ -164,14 +466,9 @@ fn handle_map() -> &'static Mutex<HandleMap> {
     MAP.get_or_init(|| Mutex::new(HashMap::new()))
 }
 
-fn register_handle(handle: u64, path: &Path) {
+fn register_handle(handle: u64, path: &Path, mode: RecursiveMode) {
     if let Ok(mut map) = handle_map().lock() {
-        // The recursive flag is recovered from the manager's own
-        // map; here we store an arbitrary mode — the lookup uses
-        // the manager's mode. (Could simplify by removing this
-        // mode from the global map; left for future readers who
-        // want the global-only fast-path.)
-        map.insert(handle, (path.to_path_buf(), RecursiveMode::Recursive));
+        map.insert(handle, (path.to_path_buf(), mode));
     }
 }
 
@@ -230,6 +527,20 @@ fn build_watcher(bridge: AsyncBridge) -> Result<RecommendedWatcher, String> {
             Ok(m) => m,
             Err(_) => return,
         };
+        // The backend dropped events (inotify queue overflow): every
+        // watch may have missed something, so report each watch root
+        // as changed and let the receivers re-check.
+        if event.need_rescan() {
+            for (handle, (watch_path, _)) in map.iter() {
+                #[allow(clippy::let_underscore_must_use)]
+                let _ = bridge.sender().send(AsyncMessage::PathChanged {
+                    handle: *handle,
+                    path: watch_path.clone(),
+                    kind: PathChangeKind::Other,
+                });
+            }
+            return;
+        }
         for path in event.paths.iter() {
             for (handle, (watch_path, mode)) in map.iter() {
                 if matches_handle(watch_path, *mode, path) {
@@ -247,6 +558,73 @@ fn build_watcher(bridge: AsyncBridge) -> Result<RecommendedWatcher, String> {
     Ok(watcher)
 }
 
+/// Whether the filesystem holding `dir` reports changes to a local
+/// watcher. Network and FUSE filesystems accept inotify watches but
+/// only see changes made through this machine's kernel, so their
+/// directories are polled instead.
+#[cfg(target_os = "linux")]
+fn delivers_events(dir: &Path) -> bool {
+    // statfs(2) f_type values; CIFS and SMB2 have no libc constant.
+    const NO_EVENT_FILESYSTEMS: &[u32] = &[
+        0x6969,     // NFS
+        0x517B,     // SMB
+        0xFF534D42, // CIFS
+        0xFE534D42, // SMB2
+        0x65735546, // FUSE (sshfs, rclone, …)
+        0x01021997, // 9P (WSL2 /mnt, VM shares)
+        0x73757245, // Coda
+        0x5346414F, // AFS
+    ];
+    match nix::sys::statfs::statfs(dir) {
+        Ok(stat) => !NO_EVENT_FILESYSTEMS.contains(&(stat.filesystem_type().0 as u32)),
+        Err(_) => true,
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn delivers_events(_dir: &Path) -> bool {
+    true
+}
+
+/// Coalesces bursts of editor-watch events. A save usually arrives
+/// as several events (truncate, write, close, or a temp-file rename);
+/// a path is handed out once it has been quiet for [`Self::QUIET`],
+/// or after [`Self::MAX_DELAY`] for a file that never stops changing.
+#[derive(Debug, Default)]
+pub struct WatchEventDebouncer {
+    /// Path → (first event, latest event) since it was last handed out.
+    pending: HashMap<PathBuf, (Instant, Instant)>,
+}
+
+impl WatchEventDebouncer {
+    pub const QUIET: Duration = Duration::from_millis(50);
+    pub const MAX_DELAY: Duration = Duration::from_millis(500);
+
+    pub fn record(&mut self, path: PathBuf, now: Instant) {
+        self.pending
+            .entry(path)
+            .and_modify(|(_, last)| *last = now)
+            .or_insert((now, now));
+    }
+
+    /// Remove and return the paths that are due at `now`.
+    pub fn take_settled(&mut self, now: Instant) -> Vec<PathBuf> {
+        let due: Vec<PathBuf> = self
+            .pending
+            .iter()
+            .filter(|(_, (first, last))| {
+                now.saturating_duration_since(*last) >= Self::QUIET
+                    || now.saturating_duration_since(*first) >= Self::MAX_DELAY
+            })
+            .map(|(path, _)| path.clone())
+            .collect();
+        for path in &due {
+            self.pending.remove(path);
+        }
+        due
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -279,6 +657,67 @@ mod tests {
         ));
     }
 
+    /// A burst of events for one path settles once, after it goes
+    /// quiet; a path that never goes quiet is still handed out.
+    #[test]
+    fn debouncer_coalesces_bursts() {
+        let start = Instant::now();
+        let ms = |n| start + Duration::from_millis(n);
+        let path = PathBuf::from("/repo/lib.rs");
+        let mut debouncer = WatchEventDebouncer::default();
+
+        debouncer.record(path.clone(), ms(0));
+        debouncer.record(path.clone(), ms(30));
+        assert!(debouncer.take_settled(ms(60)).is_empty());
+        assert_eq!(debouncer.take_settled(ms(80)), vec![path.clone()]);
+        assert!(debouncer.take_settled(ms(200)).is_empty());
+
+        for t in (1000..1600).step_by(20) {
+            debouncer.record(path.clone(), ms(t));
+        }
+        assert_eq!(debouncer.take_settled(ms(1590)), vec![path]);
+    }
+
+    /// Editor watches share the notify watcher with plugins and fall
+    /// back to polling for directories that can't be watched (yet).
+    #[test]
+    fn editor_watches_sync_to_requested_set() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let watched = temp_dir.path().join("src");
+        std::fs::create_dir(&watched).unwrap();
+        let missing = temp_dir.path().join("missing");
+        let bridge = AsyncBridge::new();
+        let fs: Arc<dyn FileSystem + Send + Sync> =
+            Arc::new(crate::model::filesystem::StdFileSystem);
+        let mut manager = FileWatcherManager::new();
+
+        let dirs = HashSet::from([watched.clone(), missing.clone()]);
+        assert_eq!(
+            manager.sync_editor_watches(&bridge, &dirs, &fs),
+            vec![watched.clone()]
+        );
+        assert!(manager.is_editor_watched(&watched));
+        assert!(!manager.is_editor_watched(&missing));
+        assert!(manager.sync_editor_watches(&bridge, &dirs, &fs).is_empty());
+
+        // A watch that failed is retried once the directory can be watched.
+        std::fs::create_dir(&missing).unwrap();
+        assert_eq!(
+            manager.sync_editor_watches(&bridge, &dirs, &fs),
+            vec![missing.clone()]
+        );
+        assert!(manager.is_editor_watched(&missing));
+        assert!(manager.sync_editor_watches(&bridge, &dirs, &fs).is_empty());
+
+        let plugin_handle = manager.watch(&bridge, temp_dir.path(), true).unwrap();
+        assert!(!manager.is_editor_handle(plugin_handle));
+
+        manager.sync_editor_watches(&bridge, &HashSet::new(), &fs);
+        assert!(!manager.is_editor_watched(&watched));
+        manager.unwatch(plugin_handle);
+        assert!(manager.handles.is_empty());
+    }
+
     /// Kind classification buckets every notify-supplied variant
     /// into one of the five exposed strings.
     #[test]
@@ -111,6 +111,24 @@ pub fn lsp_log_path(language: &str) -> PathBuf {
     lsp_log_dir().join(format!("{}-{}.log", language, std::process::id()))
 }
 
+/// Get the directory for debug adapter logs.
+///
+/// Returns `{log_dir}/dap/`, creating it if necessary.
+pub fn dap_log_dir() -> PathBuf {
+    let dir = log_dir().join("dap");
+    if let Err(e) = fs::create_dir_all(&dir) {
+        tracing::warn!("Failed to create DAP log directory {:?}: {}", dir, e);
+    }
+    dir
+}
+
+/// Get the path for a debug adapter's stderr log file for this process.
+///
+/// Returns `{log_dir}/dap/{adapter}-{PID}.log`
+pub fn dap_log_path(adapter: &str) -> PathBuf {
+    dap_log_dir().join(format!("{}-{}.log", adapter, std::process::id()))
+}
+
 /// Clean up stale log files from dead processes.
 ///
 /// This removes:
@@ -172,6 +190,12 @@ fn cleanup_stale_xdg_logs() {
     if lsp_dir.exists() {
         cleanup_stale_logs_in_dir(&lsp_dir, current_pid);
     }
+
+    // Clean debug adapter log directory
+    let dap_dir = log_dir().join("dap");
+    if dap_dir.exists() {
+        cleanup_stale_logs_in_dir(&dap_dir, current_pid);
+    }
 }
 
 /// Clean up stale log files in a specific directory
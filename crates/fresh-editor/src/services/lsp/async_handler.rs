@@ -12,8 +12,8 @@
 //! - Uses tokio channels for command/response communication
 
 use crate::services::async_bridge::{
-    AsyncBridge, AsyncMessage, LspMessageType, LspProgressValue, LspSemanticTokensResponse,
-    LspServerStatus,
+    AsyncBridge, AsyncMessage, FileDiagnosticReport, LspMessageType, LspProgressValue,
+    LspSemanticTokensResponse, LspServerStatus,
 };
 use crate::services::process_limits::ProcessLimits;
 use lsp_types::{
@@ -59,6 +59,16 @@ const DID_OPEN_GRACE_PERIOD_MS: u64 = 200;
 /// answers) from leaving features wedged in their loading state forever.
 const DEFAULT_REQUEST_TIMEOUT_MS: u64 = 30_000;
 
+/// Timeout for `workspace/diagnostic`. The request covers the whole project
+/// and its reports stream in as partial results, so it is allowed far longer
+/// than a per-document request; an edit cancels it long before this anyway.
+const WORKSPACE_DIAGNOSTIC_TIMEOUT_MS: u64 = 300_000;
+
+/// Prefix of the `partialResultToken` sent with `workspace/diagnostic`. The
+/// editor request id follows it, so the reader task can route the `$/progress`
+/// notifications that carry partial results back to their request.
+const WORKSPACE_DIAGNOSTIC_TOKEN_PREFIX: &str = "fresh/workspaceDiagnostic/";
+
 /// LSP error codes that should not surface as user-visible warnings.
 ///
 /// From [LSP 3.17 specification](https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/):
@@ -365,20 +375,23 @@ impl LspClientState {
 /// Create common LSP client capabilities with workDoneProgress support
 fn create_client_capabilities() -> ClientCapabilities {
     use lsp_types::{
-        CodeActionClientCapabilities, CodeActionKindLiteralSupport, CodeActionLiteralSupport,
-        CompletionClientCapabilities, CompletionItemCapability,
-        CompletionItemCapabilityResolveSupport, DiagnosticClientCapabilities, DiagnosticTag,
-        DiagnosticWorkspaceClientCapabilities, DocumentFormattingClientCapabilities,
-        DocumentHighlightClientCapabilities, DocumentRangeFormattingClientCapabilities,
-        DocumentSymbolClientCapabilities, DynamicRegistrationClientCapabilities,
-        FoldingRangeCapability, FoldingRangeClientCapabilities, FoldingRangeKind,
-        FoldingRangeKindCapability, GeneralClientCapabilities, GotoCapability,
-        HoverClientCapabilities, InlayHintClientCapabilities, InlayHintWorkspaceClientCapabilities,
-        MarkupKind, PublishDiagnosticsClientCapabilities, RenameClientCapabilities,
-        SemanticTokensWorkspaceClientCapabilities, SignatureHelpClientCapabilities, TagSupport,
-        TextDocumentClientCapabilities, TextDocumentSyncClientCapabilities,
-        WorkspaceClientCapabilities, WorkspaceEditClientCapabilities,
-        WorkspaceSymbolClientCapabilities,
+        CallHierarchyClientCapabilities, CodeActionClientCapabilities,
+        CodeActionKindLiteralSupport, CodeActionLiteralSupport, CodeLensClientCapabilities,
+        CodeLensWorkspaceClientCapabilities, CompletionClientCapabilities,
+        CompletionItemCapability, CompletionItemCapabilityResolveSupport,
+        DiagnosticClientCapabilities, DiagnosticTag, DiagnosticWorkspaceClientCapabilities,
+        DocumentFormattingClientCapabilities, DocumentHighlightClientCapabilities,
+        DocumentLinkClientCapabilities, DocumentOnTypeFormattingClientCapabilities,
+        DocumentRangeFormattingClientCapabilities, DocumentSymbolClientCapabilities,
+        DynamicRegistrationClientCapabilities, FoldingRangeCapability,
+        FoldingRangeClientCapabilities, FoldingRangeKind, FoldingRangeKindCapability,
+        GeneralClientCapabilities, GotoCapability, HoverClientCapabilities,
+        InlayHintClientCapabilities, InlayHintWorkspaceClientCapabilities, MarkupKind,
+        PublishDiagnosticsClientCapabilities, RenameClientCapabilities,
+        SelectionRangeClientCapabilities, SemanticTokensWorkspaceClientCapabilities,
+        SignatureHelpClientCapabilities, TagSupport, TextDocumentClientCapabilities,
+        TextDocumentSyncClientCapabilities, WorkspaceClientCapabilities,
+        WorkspaceEditClientCapabilities, WorkspaceSymbolClientCapabilities,
     };
 
     ClientCapabilities {
@@ -428,6 +441,9 @@ fn create_client_capabilities() -> ClientCapabilities {
             semantic_tokens: Some(SemanticTokensWorkspaceClientCapabilities {
                 refresh_support: Some(true),
             }),
+            code_lens: Some(CodeLensWorkspaceClientCapabilities {
+                refresh_support: Some(true),
+            }),
             ..Default::default()
         }),
         text_document: Some(TextDocumentClientCapabilities {
@@ -503,6 +519,7 @@ fn create_client_capabilities() -> ClientCapabilities {
             }),
             document_symbol: Some(DocumentSymbolClientCapabilities {
                 dynamic_registration: Some(true),
+                hierarchical_document_symbol_support: Some(true),
                 ..Default::default()
             }),
             formatting: Some(DocumentFormattingClientCapabilities {
@@ -511,6 +528,9 @@ fn create_client_capabilities() -> ClientCapabilities {
             range_formatting: Some(DocumentRangeFormattingClientCapabilities {
                 dynamic_registration: Some(true),
             }),
+            on_type_formatting: Some(DocumentOnTypeFormattingClientCapabilities {
+                dynamic_registration: Some(true),
+            }),
             code_action: Some(CodeActionClientCapabilities {
                 dynamic_registration: Some(true),
                 // Without `codeActionLiteralSupport`, rust-analyzer (and
@@ -554,6 +574,25 @@ fn create_client_capabilities() -> ClientCapabilities {
                 dynamic_registration: Some(true),
                 ..Default::default()
             }),
+            call_hierarchy: Some(CallHierarchyClientCapabilities {
+                dynamic_registration: Some(true),
+            }),
+            type_hierarchy: Some(DynamicRegistrationClientCapabilities {
+                dynamic_registration: Some(true),
+            }),
+            code_lens: Some(CodeLensClientCapabilities {
+                dynamic_registration: Some(true),
+            }),
+            document_link: Some(DocumentLinkClientCapabilities {
+                dynamic_registration: Some(true),
+                tooltip_support: Some(true),
+            }),
+            linked_editing_range: Some(DynamicRegistrationClientCapabilities {
+                dynamic_registration: Some(true),
+            }),
+            selection_range: Some(SelectionRangeClientCapabilities {
+                dynamic_registration: Some(true),
+            }),
             diagnostic: Some(DiagnosticClientCapabilities {
                 dynamic_registration: Some(true),
                 ..Default::default()
@@ -756,6 +795,54 @@ fn extract_capability_summary(caps: &ServerCapabilities) -> ServerCapabilitySumm
             lsp_types::OneOf::Right(_) => true,
         }),
         diagnostics: caps.diagnostic_provider.is_some(),
+        workspace_diagnostics: caps.diagnostic_provider.as_ref().is_some_and(|p| match p {
+            lsp_types::DiagnosticServerCapabilities::Options(opts) => opts.workspace_diagnostics,
+            lsp_types::DiagnosticServerCapabilities::RegistrationOptions(opts) => {
+                opts.diagnostic_options.workspace_diagnostics
+            }
+        }),
+        call_hierarchy: bool_or_options(&caps.call_hierarchy_provider, |p| match p {
+            lsp_types::CallHierarchyServerCapability::Simple(v) => *v,
+            lsp_types::CallHierarchyServerCapability::Options(_) => true,
+        }),
+        // `ServerCapabilities` has no `typeHierarchyProvider` field; it is
+        // read from the raw initialize result by the caller.
+        type_hierarchy: false,
+        code_lens: caps.code_lens_provider.is_some(),
+        code_lens_resolve: caps
+            .code_lens_provider
+            .as_ref()
+            .and_then(|opts| opts.resolve_provider)
+            .unwrap_or(false),
+        document_links: caps.document_link_provider.is_some(),
+        linked_editing_range: bool_or_options(&caps.linked_editing_range_provider, |p| match p {
+            lsp_types::LinkedEditingRangeServerCapabilities::Simple(v) => *v,
+            _ => true,
+        }),
+        selection_range: bool_or_options(&caps.selection_range_provider, |p| match p {
+            lsp_types::SelectionRangeProviderCapability::Simple(v) => *v,
+            _ => true,
+        }),
+        on_type_formatting_triggers: caps
+            .document_on_type_formatting_provider
+            .as_ref()
+            .map(|opts| {
+                std::iter::once(opts.first_trigger_character.clone())
+                    .chain(opts.more_trigger_character.iter().flatten().cloned())
+                    .collect()
+            })
+            .unwrap_or_default(),
+    }
+}
+
+/// Whether the raw `capabilities` object of an initialize result advertises
+/// `typeHierarchyProvider` (`boolean | TypeHierarchyOptions |
+/// TypeHierarchyRegistrationOptions`).
+fn has_type_hierarchy_provider(capabilities: &Value) -> bool {
+    match capabilities.get("typeHierarchyProvider") {
+        Some(Value::Bool(v)) => *v,
+        Some(Value::Object(_)) => true,
+        _ => false,
     }
 }
 
@@ -875,6 +962,13 @@ enum LspCommand {
         previous_result_id: Option<String>,
     },
 
+    /// Request diagnostics for the whole workspace (pull model)
+    WorkspaceDiagnostic {
+        request_id: u64,
+        /// Last result_id per file, so the server can answer "unchanged"
+        previous_result_ids: Vec<lsp_types::PreviousResultId>,
+    },
+
     /// Request inlay hints for a range (LSP 3.17+)
     InlayHints {
         request_id: u64,
@@ -889,6 +983,24 @@ enum LspCommand {
     /// Request folding ranges for a document
     FoldingRange { request_id: u64, uri: Uri },
 
+    /// Request code lenses for a document (textDocument/codeLens)
+    CodeLens { request_id: u64, uri: Uri },
+
+    /// Request the symbol outline of a document (textDocument/documentSymbol)
+    DocumentSymbol { request_id: u64, uri: Uri },
+
+    /// Request the links in a document (textDocument/documentLink)
+    DocumentLink { request_id: u64, uri: Uri },
+
+    /// Search symbols across the workspace (workspace/symbol)
+    WorkspaceSymbol { request_id: u64, query: String },
+
+    /// Resolve a code lens to fill in its command (codeLens/resolve)
+    CodeLensResolve {
+        request_id: u64,
+        lens: Box<lsp_types::CodeLens>,
+    },
+
     /// Request semantic tokens for the entire document
     SemanticTokensFull { request_id: u64, uri: Uri },
 
@@ -944,6 +1056,33 @@ enum LspCommand {
         insert_spaces: bool,
     },
 
+    /// Format around a just-typed character (textDocument/onTypeFormatting)
+    OnTypeFormatting {
+        request_id: u64,
+        uri: Uri,
+        line: u32,
+        character: u32,
+        ch: String,
+        tab_size: u32,
+        insert_spaces: bool,
+    },
+
+    /// Request the ranges linked to a position (textDocument/linkedEditingRange)
+    LinkedEditingRange {
+        request_id: u64,
+        uri: Uri,
+        line: u32,
+        character: u32,
+    },
+
+    /// Request the enclosing ranges of positions (textDocument/selectionRange)
+    SelectionRange {
+        request_id: u64,
+        uri: Uri,
+        /// (line, character) of each position
+        positions: Vec<(u32, u32)>,
+    },
+
     /// Prepare rename — validate rename at position (textDocument/prepareRename)
     PrepareRename {
         request_id: u64,
@@ -1328,9 +1467,13 @@ impl LspState {
             ..Default::default()
         };
 
-        let result: InitializeResult = self
+        // Kept as raw JSON first: `ServerCapabilities` drops fields it
+        // doesn't model (notably `typeHierarchyProvider`).
+        let raw: Value = self
             .send_request_sequential(Initialize::METHOD, Some(params), pending)
             .await?;
+        let result: InitializeResult = serde_json::from_value(raw.clone())
+            .map_err(|e| format!("Failed to deserialize response: {}", e))?;
 
         tracing::info!(
             "LSP initialize result: position_encoding={:?}",
@@ -1344,7 +1487,10 @@ impl LspState {
 
         self.initialized.store(true, Ordering::SeqCst);
 
-        let capabilities = extract_capability_summary(&result.capabilities);
+        let mut capabilities = extract_capability_summary(&result.capabilities);
+        capabilities.type_hierarchy = raw
+            .get("capabilities")
+            .is_some_and(has_type_hierarchy_provider);
 
         // Notify main loop
         let _ = self.async_tx.send(AsyncMessage::LspInitialized {
@@ -2323,6 +2469,133 @@ impl LspState {
         }
     }
 
+    /// Handle textDocument/onTypeFormatting request
+    #[allow(clippy::too_many_arguments)]
+    async fn handle_on_type_formatting(
+        &self,
+        request_id: u64,
+        uri: Uri,
+        line: u32,
+        character: u32,
+        ch: String,
+        tab_size: u32,
+        insert_spaces: bool,
+        pending: &PendingRequests,
+    ) -> Result<(), String> {
+        use lsp_types::{DocumentOnTypeFormattingParams, FormattingOptions};
+
+        let params = DocumentOnTypeFormattingParams {
+            text_document_position: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier { uri },
+                position: Position::new(line, character),
+            },
+            ch,
+            options: FormattingOptions {
+                tab_size,
+                insert_spaces,
+                ..Default::default()
+            },
+        };
+
+        match self
+            .send_request_sequential::<_, Option<Vec<lsp_types::TextEdit>>>(
+                "textDocument/onTypeFormatting",
+                Some(params),
+                pending,
+            )
+            .await
+        {
+            Ok(edits) => {
+                let _ = self.async_tx.send(AsyncMessage::LspOnTypeFormatting {
+                    request_id,
+                    edits: edits.unwrap_or_default(),
+                });
+                Ok(())
+            }
+            Err(e) => {
+                tracing::debug!("textDocument/onTypeFormatting failed: {}", e);
+                Err(e)
+            }
+        }
+    }
+
+    /// Handle textDocument/linkedEditingRange request
+    async fn handle_linked_editing_range(
+        &self,
+        request_id: u64,
+        uri: Uri,
+        line: u32,
+        character: u32,
+        pending: &PendingRequests,
+    ) -> Result<(), String> {
+        use lsp_types::LinkedEditingRangeParams;
+
+        let params = LinkedEditingRangeParams {
+            text_document_position_params: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier { uri },
+                position: Position::new(line, character),
+            },
+            work_done_progress_params: WorkDoneProgressParams::default(),
+        };
+
+        let result = self
+            .send_request_sequential::<_, Option<lsp_types::LinkedEditingRanges>>(
+                "textDocument/linkedEditingRange",
+                Some(params),
+                pending,
+            )
+            .await;
+        // Always answer, so the editor stops waiting and can ask again once
+        // the cursor moves.
+        let _ = self.async_tx.send(AsyncMessage::LspLinkedEditingRanges {
+            request_id,
+            ranges: result.as_ref().ok().cloned().flatten(),
+        });
+        if let Err(e) = &result {
+            tracing::debug!("textDocument/linkedEditingRange failed: {}", e);
+        }
+        result.map(|_| ())
+    }
+
+    /// Handle textDocument/selectionRange request
+    async fn handle_selection_range(
+        &self,
+        request_id: u64,
+        uri: Uri,
+        positions: Vec<(u32, u32)>,
+        pending: &PendingRequests,
+    ) -> Result<(), String> {
+        use lsp_types::SelectionRangeParams;
+
+        let params = SelectionRangeParams {
+            text_document: TextDocumentIdentifier { uri },
+            positions: positions
+                .into_iter()
+                .map(|(line, character)| Position::new(line, character))
+                .collect(),
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        };
+
+        let result = self
+            .send_request_sequential::<_, Option<Vec<lsp_types::SelectionRange>>>(
+                "textDocument/selectionRange",
+                Some(params),
+                pending,
+            )
+            .await;
+        // Always answer, so the editor falls back to local ranges when the
+        // server has none.
+        let _ = self.async_tx.send(AsyncMessage::LspSelectionRanges {
+            request_id,
+            ranges: result.as_ref().ok().cloned().flatten(),
+        });
+        if let Err(e) = &result {
+            tracing::debug!("textDocument/selectionRange failed: {}", e);
+        }
+        result.map(|_| ())
+    }
+
     /// Handle textDocument/prepareRename request
     async fn handle_prepare_rename(
         &self,
@@ -2494,6 +2767,92 @@ impl LspState {
         }
     }
 
+    async fn handle_workspace_diagnostic(
+        &self,
+        request_id: u64,
+        previous_result_ids: Vec<lsp_types::PreviousResultId>,
+        pending: &PendingRequests,
+    ) -> Result<(), String> {
+        use lsp_types::{
+            NumberOrString, WorkspaceDiagnosticParams, WorkspaceDiagnosticReportResult,
+        };
+
+        let done = |reports| {
+            let _ = self.async_tx.send(AsyncMessage::LspWorkspaceDiagnostics {
+                request_id,
+                server_name: (*self.server_name).clone(),
+                reports,
+                done: true,
+            });
+        };
+
+        // Same task-side gate as `handle_document_diagnostic`, narrowed to
+        // servers that said they answer workspace pulls.
+        let supports_workspace = self
+            .capabilities
+            .lock()
+            .unwrap()
+            .as_ref()
+            .and_then(|c| c.diagnostic_provider.as_ref())
+            .is_some_and(|p| match p {
+                lsp_types::DiagnosticServerCapabilities::Options(opts) => {
+                    opts.workspace_diagnostics
+                }
+                lsp_types::DiagnosticServerCapabilities::RegistrationOptions(opts) => {
+                    opts.diagnostic_options.workspace_diagnostics
+                }
+            });
+        if !supports_workspace {
+            tracing::trace!("LSP: server does not support workspace diagnostics, skipping");
+            done(Vec::new());
+            return Ok(());
+        }
+
+        tracing::trace!(
+            "LSP: workspace diagnostic request {} ({} previous result ids)",
+            request_id,
+            previous_result_ids.len()
+        );
+
+        let params = WorkspaceDiagnosticParams {
+            identifier: None,
+            previous_result_ids,
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams {
+                partial_result_token: Some(NumberOrString::String(format!(
+                    "{WORKSPACE_DIAGNOSTIC_TOKEN_PREFIX}{request_id}"
+                ))),
+            },
+        };
+
+        match self
+            .send_request_with_timeout::<_, WorkspaceDiagnosticReportResult>(
+                "workspace/diagnostic",
+                Some(params),
+                pending,
+                Some(request_id),
+                Duration::from_millis(WORKSPACE_DIAGNOSTIC_TIMEOUT_MS),
+            )
+            .await
+        {
+            Ok(result) => {
+                // Whatever was not streamed as partial results comes with the
+                // final response.
+                let items = match result {
+                    WorkspaceDiagnosticReportResult::Report(report) => report.items,
+                    WorkspaceDiagnosticReportResult::Partial(partial) => partial.items,
+                };
+                done(workspace_diagnostic_reports(items, &self.document_versions));
+                Ok(())
+            }
+            Err(e) => {
+                tracing::debug!("Workspace diagnostic request failed: {}", e);
+                done(Vec::new());
+                Err(e)
+            }
+        }
+    }
+
     /// Handle inlay hints request (LSP 3.17+)
     #[allow(clippy::too_many_arguments)]
     async fn handle_inlay_hints(
@@ -2563,63 +2922,284 @@ impl LspState {
                 // Send empty result on error
                 let _ = self.async_tx.send(AsyncMessage::LspInlayHints {
                     request_id,
-                    uri: uri.as_str().to_string(),
-                    hints: Vec::new(),
+                    uri: uri.as_str().to_string(),
+                    hints: Vec::new(),
+                });
+                Err(e)
+            }
+        }
+    }
+
+    /// Handle folding range request
+    async fn handle_folding_ranges(
+        &self,
+        request_id: u64,
+        uri: Uri,
+        pending: &PendingRequests,
+    ) -> Result<(), String> {
+        use lsp_types::FoldingRangeParams;
+
+        tracing::trace!("LSP: folding range request for {}", uri.as_str());
+
+        let params = FoldingRangeParams {
+            text_document: TextDocumentIdentifier { uri: uri.clone() },
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        };
+
+        match self
+            .send_request_sequential::<_, Option<Vec<lsp_types::FoldingRange>>>(
+                "textDocument/foldingRange",
+                Some(params),
+                pending,
+            )
+            .await
+        {
+            Ok(ranges) => {
+                let ranges = ranges.unwrap_or_default();
+                let uri_string = uri.as_str().to_string();
+
+                tracing::trace!(
+                    "LSP: received {} folding ranges for {}",
+                    ranges.len(),
+                    uri_string
+                );
+
+                let _ = self.async_tx.send(AsyncMessage::LspFoldingRanges {
+                    request_id,
+                    uri: uri_string,
+                    ranges,
+                });
+
+                Ok(())
+            }
+            Err(e) => {
+                tracing::debug!("Folding range request failed: {}", e);
+                let _ = self.async_tx.send(AsyncMessage::LspFoldingRanges {
+                    request_id,
+                    uri: uri.as_str().to_string(),
+                    ranges: Vec::new(),
+                });
+                Err(e)
+            }
+        }
+    }
+
+    /// Handle code lens request
+    async fn handle_code_lenses(
+        &self,
+        request_id: u64,
+        uri: Uri,
+        pending: &PendingRequests,
+    ) -> Result<(), String> {
+        use lsp_types::CodeLensParams;
+
+        tracing::trace!("LSP: code lens request for {}", uri.as_str());
+
+        let params = CodeLensParams {
+            text_document: TextDocumentIdentifier { uri: uri.clone() },
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        };
+
+        match self
+            .send_request_sequential::<_, Option<Vec<lsp_types::CodeLens>>>(
+                "textDocument/codeLens",
+                Some(params),
+                pending,
+            )
+            .await
+        {
+            Ok(lenses) => {
+                let lenses = lenses.unwrap_or_default();
+                let uri_string = uri.as_str().to_string();
+
+                tracing::trace!(
+                    "LSP: received {} code lenses for {}",
+                    lenses.len(),
+                    uri_string
+                );
+
+                let _ = self.async_tx.send(AsyncMessage::LspCodeLenses {
+                    request_id,
+                    uri: uri_string,
+                    lenses,
+                });
+
+                Ok(())
+            }
+            Err(e) => {
+                tracing::debug!("Code lens request failed: {}", e);
+                let _ = self.async_tx.send(AsyncMessage::LspCodeLenses {
+                    request_id,
+                    uri: uri.as_str().to_string(),
+                    lenses: Vec::new(),
+                });
+                Err(e)
+            }
+        }
+    }
+
+    /// Handle document symbol request
+    async fn handle_document_symbols(
+        &self,
+        request_id: u64,
+        uri: Uri,
+        pending: &PendingRequests,
+    ) -> Result<(), String> {
+        use lsp_types::{DocumentSymbolParams, DocumentSymbolResponse};
+
+        tracing::trace!("LSP: document symbol request for {}", uri.as_str());
+
+        let params = DocumentSymbolParams {
+            text_document: TextDocumentIdentifier { uri: uri.clone() },
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        };
+
+        match self
+            .send_request_sequential::<_, Option<DocumentSymbolResponse>>(
+                "textDocument/documentSymbol",
+                Some(params),
+                pending,
+            )
+            .await
+        {
+            Ok(symbols) => {
+                let _ = self.async_tx.send(AsyncMessage::LspDocumentSymbols {
+                    request_id,
+                    uri: uri.as_str().to_string(),
+                    symbols: symbols.unwrap_or(DocumentSymbolResponse::Nested(Vec::new())),
+                });
+                Ok(())
+            }
+            Err(e) => {
+                tracing::debug!("Document symbol request failed: {}", e);
+                let _ = self.async_tx.send(AsyncMessage::LspDocumentSymbols {
+                    request_id,
+                    uri: uri.as_str().to_string(),
+                    symbols: DocumentSymbolResponse::Nested(Vec::new()),
+                });
+                Err(e)
+            }
+        }
+    }
+
+    /// Handle document link request
+    async fn handle_document_links(
+        &self,
+        request_id: u64,
+        uri: Uri,
+        pending: &PendingRequests,
+    ) -> Result<(), String> {
+        use lsp_types::{DocumentLink, DocumentLinkParams};
+
+        tracing::trace!("LSP: document link request for {}", uri.as_str());
+
+        let params = DocumentLinkParams {
+            text_document: TextDocumentIdentifier { uri: uri.clone() },
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        };
+
+        match self
+            .send_request_sequential::<_, Option<Vec<DocumentLink>>>(
+                "textDocument/documentLink",
+                Some(params),
+                pending,
+            )
+            .await
+        {
+            Ok(links) => {
+                let _ = self.async_tx.send(AsyncMessage::LspDocumentLinks {
+                    request_id,
+                    uri: uri.as_str().to_string(),
+                    links: links.unwrap_or_default(),
+                });
+                Ok(())
+            }
+            Err(e) => {
+                tracing::debug!("Document link request failed: {}", e);
+                let _ = self.async_tx.send(AsyncMessage::LspDocumentLinks {
+                    request_id,
+                    uri: uri.as_str().to_string(),
+                    links: Vec::new(),
+                });
+                Err(e)
+            }
+        }
+    }
+
+    /// Handle workspace symbol request
+    async fn handle_workspace_symbols(
+        &self,
+        request_id: u64,
+        query: String,
+        pending: &PendingRequests,
+    ) -> Result<(), String> {
+        use lsp_types::{WorkspaceSymbolParams, WorkspaceSymbolResponse};
+
+        tracing::trace!("LSP: workspace symbol request for {:?}", query);
+
+        let params = WorkspaceSymbolParams {
+            query,
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        };
+
+        match self
+            .send_request_sequential::<_, Option<WorkspaceSymbolResponse>>(
+                "workspace/symbol",
+                Some(params),
+                pending,
+            )
+            .await
+        {
+            Ok(symbols) => {
+                let _ = self.async_tx.send(AsyncMessage::LspWorkspaceSymbols {
+                    request_id,
+                    symbols: symbols.unwrap_or(WorkspaceSymbolResponse::Flat(Vec::new())),
+                });
+                Ok(())
+            }
+            Err(e) => {
+                tracing::debug!("Workspace symbol request failed: {}", e);
+                let _ = self.async_tx.send(AsyncMessage::LspWorkspaceSymbols {
+                    request_id,
+                    symbols: WorkspaceSymbolResponse::Flat(Vec::new()),
                 });
                 Err(e)
             }
         }
     }
 
-    /// Handle folding range request
-    async fn handle_folding_ranges(
+    /// Handle codeLens/resolve request
+    async fn handle_code_lens_resolve(
         &self,
         request_id: u64,
-        uri: Uri,
+        lens: lsp_types::CodeLens,
         pending: &PendingRequests,
     ) -> Result<(), String> {
-        use lsp_types::FoldingRangeParams;
-
-        tracing::trace!("LSP: folding range request for {}", uri.as_str());
-
-        let params = FoldingRangeParams {
-            text_document: TextDocumentIdentifier { uri: uri.clone() },
-            work_done_progress_params: WorkDoneProgressParams::default(),
-            partial_result_params: PartialResultParams::default(),
-        };
-
         match self
-            .send_request_sequential::<_, Option<Vec<lsp_types::FoldingRange>>>(
-                "textDocument/foldingRange",
-                Some(params),
+            .send_request_sequential::<_, lsp_types::CodeLens>(
+                "codeLens/resolve",
+                Some(lens),
                 pending,
             )
             .await
         {
-            Ok(ranges) => {
-                let ranges = ranges.unwrap_or_default();
-                let uri_string = uri.as_str().to_string();
-
-                tracing::trace!(
-                    "LSP: received {} folding ranges for {}",
-                    ranges.len(),
-                    uri_string
-                );
-
-                let _ = self.async_tx.send(AsyncMessage::LspFoldingRanges {
+            Ok(resolved) => {
+                let _ = self.async_tx.send(AsyncMessage::LspCodeLensResolved {
                     request_id,
-                    uri: uri_string,
-                    ranges,
+                    lens: Ok(resolved),
                 });
-
                 Ok(())
             }
             Err(e) => {
-                tracing::debug!("Folding range request failed: {}", e);
-                let _ = self.async_tx.send(AsyncMessage::LspFoldingRanges {
+                tracing::debug!("codeLens/resolve failed: {}", e);
+                let _ = self.async_tx.send(AsyncMessage::LspCodeLensResolved {
                     request_id,
-                    uri: uri.as_str().to_string(),
-                    ranges: Vec::new(),
+                    lens: Err(e.clone()),
                 });
                 Err(e)
             }
@@ -3582,6 +4162,25 @@ impl LspTask {
                         });
                     }
                 }
+                LspCommand::WorkspaceDiagnostic {
+                    request_id,
+                    previous_result_ids,
+                } => {
+                    if initialized {
+                        tracing::info!("Processing WorkspaceDiagnostic request {}", request_id);
+                        spawn_request!(state, pending, |s, p| s
+                            .handle_workspace_diagnostic(request_id, previous_result_ids, &p)
+                            .await);
+                    } else {
+                        tracing::trace!("LSP not initialized, cannot pull workspace diagnostics");
+                        let _ = state.async_tx.send(AsyncMessage::LspWorkspaceDiagnostics {
+                            request_id,
+                            server_name: (*state.server_name).clone(),
+                            reports: Vec::new(),
+                            done: true,
+                        });
+                    }
+                }
                 LspCommand::InlayHints {
                     request_id,
                     uri,
@@ -3691,6 +4290,79 @@ impl LspTask {
                         });
                     }
                 }
+                LspCommand::CodeLens { request_id, uri } => {
+                    if initialized {
+                        tracing::info!("Processing CodeLens request for {}", uri.as_str());
+                        spawn_request!(state, pending, |s, p| s
+                            .handle_code_lenses(request_id, uri, &p)
+                            .await);
+                    } else {
+                        tracing::trace!("LSP not initialized, cannot get code lenses");
+                        let _ = state.async_tx.send(AsyncMessage::LspCodeLenses {
+                            request_id,
+                            uri: uri.as_str().to_string(),
+                            lenses: Vec::new(),
+                        });
+                    }
+                }
+                LspCommand::DocumentSymbol { request_id, uri } => {
+                    if initialized {
+                        tracing::info!("Processing DocumentSymbol request for {}", uri.as_str());
+                        spawn_request!(state, pending, |s, p| s
+                            .handle_document_symbols(request_id, uri, &p)
+                            .await);
+                    } else {
+                        tracing::trace!("LSP not initialized, cannot get document symbols");
+                        let _ = state.async_tx.send(AsyncMessage::LspDocumentSymbols {
+                            request_id,
+                            uri: uri.as_str().to_string(),
+                            symbols: lsp_types::DocumentSymbolResponse::Nested(Vec::new()),
+                        });
+                    }
+                }
+                LspCommand::DocumentLink { request_id, uri } => {
+                    if initialized {
+                        tracing::info!("Processing DocumentLink request for {}", uri.as_str());
+                        spawn_request!(state, pending, |s, p| s
+                            .handle_document_links(request_id, uri, &p)
+                            .await);
+                    } else {
+                        tracing::trace!("LSP not initialized, cannot get document links");
+                        let _ = state.async_tx.send(AsyncMessage::LspDocumentLinks {
+                            request_id,
+                            uri: uri.as_str().to_string(),
+                            links: Vec::new(),
+                        });
+                    }
+                }
+                LspCommand::WorkspaceSymbol { request_id, query } => {
+                    if initialized {
+                        tracing::info!("Processing WorkspaceSymbol request for {:?}", query);
+                        spawn_request!(state, pending, |s, p| s
+                            .handle_workspace_symbols(request_id, query, &p)
+                            .await);
+                    } else {
+                        tracing::trace!("LSP not initialized, cannot search workspace symbols");
+                        let _ = state.async_tx.send(AsyncMessage::LspWorkspaceSymbols {
+                            request_id,
+                            symbols: lsp_types::WorkspaceSymbolResponse::Flat(Vec::new()),
+                        });
+                    }
+                }
+                LspCommand::CodeLensResolve { request_id, lens } => {
+                    if initialized {
+                        tracing::info!("Processing CodeLensResolve (request_id={})", request_id);
+                        spawn_request!(state, pending, |s, p| s
+                            .handle_code_lens_resolve(request_id, *lens, &p)
+                            .await);
+                    } else {
+                        tracing::trace!("LSP not initialized, cannot resolve code lens");
+                        let _ = state.async_tx.send(AsyncMessage::LspCodeLensResolved {
+                            request_id,
+                            lens: Err("LSP not initialized".to_string()),
+                        });
+                    }
+                }
                 LspCommand::ExecuteCommand { command, arguments } => {
                     if initialized {
                         tracing::info!("Processing ExecuteCommand: {}", command);
@@ -3767,6 +4439,65 @@ impl LspTask {
                             .await);
                     }
                 }
+                LspCommand::OnTypeFormatting {
+                    request_id,
+                    uri,
+                    line,
+                    character,
+                    ch,
+                    tab_size,
+                    insert_spaces,
+                } => {
+                    if initialized {
+                        spawn_request!(state, pending, |s, p| s
+                            .handle_on_type_formatting(
+                                request_id,
+                                uri,
+                                line,
+                                character,
+                                ch,
+                                tab_size,
+                                insert_spaces,
+                                &p,
+                            )
+                            .await);
+                    }
+                }
+                LspCommand::LinkedEditingRange {
+                    request_id,
+                    uri,
+                    line,
+                    character,
+                } => {
+                    if initialized {
+                        spawn_request!(state, pending, |s, p| s
+                            .handle_linked_editing_range(request_id, uri, line, character, &p)
+                            .await);
+                    } else {
+                        tracing::trace!("LSP not initialized, cannot get linked editing ranges");
+                        let _ = state.async_tx.send(AsyncMessage::LspLinkedEditingRanges {
+                            request_id,
+                            ranges: None,
+                        });
+                    }
+                }
+                LspCommand::SelectionRange {
+                    request_id,
+                    uri,
+                    positions,
+                } => {
+                    if initialized {
+                        spawn_request!(state, pending, |s, p| s
+                            .handle_selection_range(request_id, uri, positions, &p)
+                            .await);
+                    } else {
+                        tracing::trace!("LSP not initialized, cannot get selection ranges");
+                        let _ = state.async_tx.send(AsyncMessage::LspSelectionRanges {
+                            request_id,
+                            ranges: None,
+                        });
+                    }
+                }
                 LspCommand::PrepareRename {
                     request_id,
                     uri,
@@ -4073,6 +4804,75 @@ fn log_lsp_message(message_type: LspMessageType, language: &str, message: &str)
     }
 }
 
+/// Recognise a `$/progress` notification carrying a partial result of one of
+/// our `workspace/diagnostic` requests, returning the editor request id it
+/// belongs to and the streamed reports.
+fn parse_workspace_diagnostic_partial(
+    params: Option<&Value>,
+) -> Option<(u64, lsp_types::WorkspaceDiagnosticReportPartialResult)> {
+    let params = params?;
+    let request_id = params
+        .get("token")?
+        .as_str()?
+        .strip_prefix(WORKSPACE_DIAGNOSTIC_TOKEN_PREFIX)?
+        .parse()
+        .ok()?;
+    let partial = serde_json::from_value(params.get("value")?.clone()).ok()?;
+    Some((request_id, partial))
+}
+
+/// Flatten the per-file items of a `workspace/diagnostic` result into
+/// reports. Items computed for an older version of a document than the one
+/// last sent with didOpen/didChange are dropped, as stale `publishDiagnostics`
+/// are.
+fn workspace_diagnostic_reports(
+    items: Vec<lsp_types::WorkspaceDocumentDiagnosticReport>,
+    document_versions: &std::sync::Mutex<HashMap<PathBuf, i64>>,
+) -> Vec<FileDiagnosticReport> {
+    use lsp_types::WorkspaceDocumentDiagnosticReport;
+
+    let is_stale = |uri: &Uri, version: Option<i64>| {
+        let Some(version) = version else {
+            return false;
+        };
+        let path = PathBuf::from(uri.path().as_str());
+        document_versions
+            .lock()
+            .unwrap()
+            .get(&path)
+            .is_some_and(|&current| version < current)
+    };
+
+    items
+        .into_iter()
+        .filter_map(|item| match item {
+            WorkspaceDocumentDiagnosticReport::Full(report) => {
+                if is_stale(&report.uri, report.version) {
+                    return None;
+                }
+                let full = report.full_document_diagnostic_report;
+                Some(FileDiagnosticReport {
+                    uri: report.uri.as_str().to_string(),
+                    result_id: full.result_id,
+                    diagnostics: full.items,
+                    unchanged: false,
+                })
+            }
+            WorkspaceDocumentDiagnosticReport::Unchanged(report) => {
+                if is_stale(&report.uri, report.version) {
+                    return None;
+                }
+                Some(FileDiagnosticReport {
+                    uri: report.uri.as_str().to_string(),
+                    result_id: Some(report.unchanged_document_diagnostic_report.result_id),
+                    diagnostics: Vec::new(),
+                    unchanged: true,
+                })
+            }
+        })
+        .collect()
+}
+
 /// Parse a `$/progress` notification into `(token, LspProgressValue)`.
 /// Returns `None` for unknown kinds or malformed params.
 fn parse_progress_notification(
@@ -4331,6 +5131,18 @@ async fn handle_message_dispatch(
                     });
                     null_response(request.id)
                 }
+                "workspace/codeLens/refresh" => {
+                    // Lens titles such as reference counts went stale (e.g.
+                    // after the project finished indexing); re-pull them.
+                    tracing::info!(
+                        "LSP ({}) requested code-lens refresh (workspace/codeLens/refresh)",
+                        language
+                    );
+                    let _ = async_tx.send(AsyncMessage::LspCodeLensRefresh {
+                        language: language.to_string(),
+                    });
+                    null_response(request.id)
+                }
                 "workspace/semanticTokens/refresh" => {
                     // Same idea as inlayHint/refresh, for semantic highlighting.
                     tracing::info!(
@@ -4477,6 +5289,19 @@ async fn handle_notification_dispatch(
             }
         }
         "$/progress" => {
+            // Partial results of a workspace diagnostic pull arrive as progress
+            // on the token we chose; they carry reports, not progress.
+            if let Some((request_id, partial)) =
+                parse_workspace_diagnostic_partial(notification.params.as_ref())
+            {
+                let _ = async_tx.send(AsyncMessage::LspWorkspaceDiagnostics {
+                    request_id,
+                    server_name: server_name.to_string(),
+                    reports: workspace_diagnostic_reports(partial.items, document_versions),
+                    done: false,
+                });
+                return Ok(());
+            }
             if let Some((token, value)) = parse_progress_notification(notification.params, language)
             {
                 let _ = async_tx.send(AsyncMessage::LspProgress {
@@ -5097,6 +5922,69 @@ impl LspHandle {
             .map_err(|_| "Failed to send document_range_formatting command".to_string())
     }
 
+    /// Ask for on-type formatting edits after typing `ch`
+    /// (textDocument/onTypeFormatting). `line`/`character` is the position
+    /// just after the typed character.
+    #[allow(clippy::too_many_arguments)]
+    pub fn on_type_formatting(
+        &self,
+        request_id: u64,
+        uri: Uri,
+        line: u32,
+        character: u32,
+        ch: String,
+        tab_size: u32,
+        insert_spaces: bool,
+    ) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::OnTypeFormatting {
+                request_id,
+                uri,
+                line,
+                character,
+                ch,
+                tab_size,
+                insert_spaces,
+            })
+            .map_err(|_| "Failed to send on_type_formatting command".to_string())
+    }
+
+    /// Request the ranges that must be edited together with the one at a
+    /// position (textDocument/linkedEditingRange)
+    pub fn linked_editing_range(
+        &self,
+        request_id: u64,
+        uri: Uri,
+        line: u32,
+        character: u32,
+    ) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::LinkedEditingRange {
+                request_id,
+                uri,
+                line,
+                character,
+            })
+            .map_err(|_| "Failed to send linked_editing_range command".to_string())
+    }
+
+    /// Request the chain of enclosing ranges at each of `positions`, given
+    /// as (line, character) (textDocument/selectionRange)
+    pub fn selection_range(
+        &self,
+        request_id: u64,
+        uri: Uri,
+        positions: Vec<(u32, u32)>,
+    ) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::SelectionRange {
+                request_id,
+                uri,
+                positions,
+            })
+            .map_err(|_| "Failed to send selection_range command".to_string())
+    }
+
     /// Validate rename at position (textDocument/prepareRename)
     pub fn prepare_rename(
         &self,
@@ -5134,6 +6022,24 @@ impl LspHandle {
             .map_err(|_| "Failed to send document_diagnostic command".to_string())
     }
 
+    /// Request diagnostics for the whole workspace (pull model)
+    ///
+    /// Sends workspace/diagnostic. Reports stream back as partial results
+    /// while the server works; `previous_result_ids` lets it skip files
+    /// whose diagnostics have not changed. Cancel with [`Self::cancel_request`].
+    pub fn workspace_diagnostic(
+        &self,
+        request_id: u64,
+        previous_result_ids: Vec<lsp_types::PreviousResultId>,
+    ) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::WorkspaceDiagnostic {
+                request_id,
+                previous_result_ids,
+            })
+            .map_err(|_| "Failed to send workspace_diagnostic command".to_string())
+    }
+
     /// Request inlay hints for a range (LSP 3.17+)
     ///
     /// Inlay hints are virtual text annotations displayed inline (e.g., type hints, parameter names).
@@ -5165,6 +6071,51 @@ impl LspHandle {
             .map_err(|_| "Failed to send folding_range command".to_string())
     }
 
+    /// Request code lenses for a document (textDocument/codeLens)
+    ///
+    /// Lenses often arrive without a `command`; those are filled in later
+    /// with [`Self::code_lens_resolve`].
+    pub fn code_lenses(&self, request_id: u64, uri: Uri) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::CodeLens { request_id, uri })
+            .map_err(|_| "Failed to send code_lens command".to_string())
+    }
+
+    /// Request the symbol outline of a document (textDocument/documentSymbol)
+    pub fn document_symbols(&self, request_id: u64, uri: Uri) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::DocumentSymbol { request_id, uri })
+            .map_err(|_| "Failed to send document_symbol command".to_string())
+    }
+
+    /// Request the links in a document (textDocument/documentLink)
+    pub fn document_links(&self, request_id: u64, uri: Uri) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::DocumentLink { request_id, uri })
+            .map_err(|_| "Failed to send document_link command".to_string())
+    }
+
+    /// Search symbols across the workspace (workspace/symbol)
+    pub fn workspace_symbols(&self, request_id: u64, query: String) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::WorkspaceSymbol { request_id, query })
+            .map_err(|_| "Failed to send workspace_symbol command".to_string())
+    }
+
+    /// Resolve a code lens to get its command (codeLens/resolve)
+    pub fn code_lens_resolve(
+        &self,
+        request_id: u64,
+        lens: lsp_types::CodeLens,
+    ) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::CodeLensResolve {
+                request_id,
+                lens: Box::new(lens),
+            })
+            .map_err(|_| "Failed to send code_lens_resolve command".to_string())
+    }
+
     /// Request semantic tokens for an entire document
     pub fn semantic_tokens_full(&self, request_id: u64, uri: Uri) -> Result<(), String> {
         self.command_tx
@@ -6489,4 +7440,77 @@ mod tests {
         assert!(!is_markdown);
         assert_eq!(range, None);
     }
+
+    #[test]
+    fn workspace_diagnostic_partial_result_is_routed_by_token() {
+        let params = serde_json::json!({
+            "token": format!("{WORKSPACE_DIAGNOSTIC_TOKEN_PREFIX}42"),
+            "value": {
+                "items": [{
+                    "kind": "unchanged",
+                    "uri": "file:///project/src/lib.rs",
+                    "version": null,
+                    "resultId": "r1"
+                }]
+            }
+        });
+        let (request_id, partial) =
+            parse_workspace_diagnostic_partial(Some(&params)).expect("partial result");
+        assert_eq!(request_id, 42);
+        assert_eq!(partial.items.len(), 1);
+
+        // Ordinary work-done progress is left for `parse_progress_notification`.
+        let progress = serde_json::json!({
+            "token": "rustAnalyzer/Indexing",
+            "value": { "kind": "begin", "title": "Indexing" }
+        });
+        assert!(parse_workspace_diagnostic_partial(Some(&progress)).is_none());
+    }
+
+    #[test]
+    fn workspace_diagnostic_reports_drop_stale_versions() {
+        let items: Vec<lsp_types::WorkspaceDocumentDiagnosticReport> =
+            serde_json::from_value(serde_json::json!([
+                {
+                    "kind": "full",
+                    "uri": "file:///project/closed.rs",
+                    "version": null,
+                    "resultId": "a",
+                    "items": [{
+                        "range": { "start": { "line": 1, "character": 0 },
+                                   "end":   { "line": 1, "character": 3 } },
+                        "message": "unused"
+                    }]
+                },
+                {
+                    "kind": "full",
+                    "uri": "file:///project/open.rs",
+                    "version": 1,
+                    "items": []
+                },
+                {
+                    "kind": "unchanged",
+                    "uri": "file:///project/other.rs",
+                    "version": null,
+                    "resultId": "b"
+                }
+            ]))
+            .unwrap();
+        let versions =
+            std::sync::Mutex::new(HashMap::from([(PathBuf::from("/project/open.rs"), 3)]));
+
+        let reports = workspace_diagnostic_reports(items, &versions);
+        assert_eq!(
+            reports.len(),
+            2,
+            "report for an older version must be dropped"
+        );
+        assert_eq!(reports[0].uri, "file:///project/closed.rs");
+        assert_eq!(reports[0].result_id.as_deref(), Some("a"));
+        assert_eq!(reports[0].diagnostics.len(), 1);
+        assert!(!reports[0].unchanged);
+        assert_eq!(reports[1].uri, "file:///project/other.rs");
+        assert_eq!(reports[1].result_id.as_deref(), Some("b"));
+        assert!(reports[1].unchanged);
+    }
 }
@@ -217,6 +217,17 @@ pub struct ServerCapabilitySummary {
     pub document_symbols: bool,
     pub workspace_symbols: bool,
     pub diagnostics: bool,
+    /// Whether the server answers `workspace/diagnostic` (the
+    /// `workspaceDiagnostics` flag of its diagnostic provider).
+    pub workspace_diagnostics: bool,
+    pub call_hierarchy: bool,
+    pub type_hierarchy: bool,
+    pub code_lens: bool,
+    pub code_lens_resolve: bool,
+    pub document_links: bool,
+    pub linked_editing_range: bool,
+    pub selection_range: bool,
+    pub on_type_formatting_triggers: Vec<String>,
 }
 
 impl ServerCapabilitySummary {
@@ -293,7 +304,45 @@ impl ServerCapabilitySummary {
             }
             "textDocument/documentSymbol" => self.document_symbols = register,
             "workspace/symbol" => self.workspace_symbols = register,
-            "textDocument/diagnostic" => self.diagnostics = register,
+            "textDocument/diagnostic" => {
+                self.diagnostics = register;
+                self.workspace_diagnostics = register
+                    && register_options
+                        .and_then(|opts| opts.get("workspaceDiagnostics"))
+                        .and_then(serde_json::Value::as_bool)
+                        .unwrap_or(false);
+            }
+            "textDocument/prepareCallHierarchy" => self.call_hierarchy = register,
+            "textDocument/prepareTypeHierarchy" => self.type_hierarchy = register,
+            "textDocument/codeLens" => {
+                self.code_lens = register;
+                self.code_lens_resolve = register
+                    && register_options
+                        .and_then(|opts| opts.get("resolveProvider"))
+                        .and_then(serde_json::Value::as_bool)
+                        .unwrap_or(false);
+            }
+            "textDocument/documentLink" => self.document_links = register,
+            "textDocument/linkedEditingRange" => self.linked_editing_range = register,
+            "textDocument/selectionRange" => self.selection_range = register,
+            "textDocument/onTypeFormatting" => {
+                self.on_type_formatting_triggers.clear();
+                if register {
+                    if let Some(opts) = register_options {
+                        self.on_type_formatting_triggers.extend(
+                            opts.get("firstTriggerCharacter")
+                                .and_then(|v| v.as_str())
+                                .map(str::to_string),
+                        );
+                        if let Some(more) =
+                            opts.get("moreTriggerCharacter").and_then(|v| v.as_array())
+                        {
+                            self.on_type_formatting_triggers
+                                .extend(more.iter().filter_map(|v| v.as_str().map(str::to_string)));
+                        }
+                    }
+                }
+            }
             "textDocument/semanticTokens" => {
                 if register {
                     // Registration options carry the legend and full/range
@@ -389,6 +438,15 @@ impl ServerHandle {
             LspFeature::DocumentSymbols => self.capabilities.document_symbols,
             LspFeature::WorkspaceSymbols => self.capabilities.workspace_symbols,
             LspFeature::Diagnostics => self.capabilities.diagnostics,
+            LspFeature::CallHierarchy => self.capabilities.call_hierarchy,
+            LspFeature::TypeHierarchy => self.capabilities.type_hierarchy,
+            LspFeature::CodeLens => self.capabilities.code_lens,
+            LspFeature::DocumentLink => self.capabilities.document_links,
+            LspFeature::LinkedEditingRange => self.capabilities.linked_editing_range,
+            LspFeature::SelectionRange => self.capabilities.selection_range,
+            LspFeature::OnTypeFormatting => {
+                !self.capabilities.on_type_formatting_triggers.is_empty()
+            }
         }
     }
 }
@@ -978,6 +1036,31 @@ impl LspManager {
             .map(|sh| &mut sh.handle)
     }
 
+    /// Get the handle a plugin's raw `method` request should go to.
+    ///
+    /// Methods that belong to a feature (see [`LspFeature::for_request_method`])
+    /// go to the first server that both allows and advertises that feature,
+    /// so a hierarchy request isn't sent to, say, a linter that happens to be
+    /// listed first. Anything else, or a feature no initialized server has,
+    /// falls back to the first server for the language.
+    pub fn handle_for_request_mut(
+        &mut self,
+        language: &str,
+        method: &str,
+    ) -> Option<&mut LspHandle> {
+        let capable = LspFeature::for_request_method(method).and_then(|feature| {
+            self.handles.iter().position(|sh| {
+                sh.handle.scope().accepts(language)
+                    && sh.feature_filter.allows(feature)
+                    && sh.has_capability(feature)
+            })
+        });
+        match capable {
+            Some(index) => Some(&mut self.handles[index].handle),
+            None => self.get_handle_mut(language),
+        }
+    }
+
     /// Get all handles that accept a language (both language-specific and universal).
     pub fn get_handles(&self, language: &str) -> Vec<&ServerHandle> {
         self.handles
@@ -1076,6 +1159,16 @@ impl LspManager {
             .collect()
     }
 
+    /// Get all handles, across every language, that allow a given feature.
+    /// For workspace-wide requests (e.g. `workspace/symbol`) that are not tied
+    /// to a document.
+    pub fn all_handles_for_feature(&self, feature: LspFeature) -> Vec<&ServerHandle> {
+        self.handles
+            .iter()
+            .filter(|sh| sh.feature_filter.allows(feature) && sh.has_capability(feature))
+            .collect()
+    }
+
     /// Consult the spawn throttle for `language` and, on `Allow`, record
     /// the attempt.
     ///
@@ -2876,4 +2969,67 @@ mod tests {
         assert!(!unknown);
         assert!(caps.hover);
     }
+
+    #[test]
+    fn dynamic_registration_enables_call_and_type_hierarchy() {
+        let mut caps = ServerCapabilitySummary::default();
+        assert!(caps.apply_dynamic_registration("textDocument/prepareCallHierarchy", None, true));
+        assert!(caps.apply_dynamic_registration("textDocument/prepareTypeHierarchy", None, true));
+        assert!(caps.call_hierarchy);
+        assert!(caps.type_hierarchy);
+
+        caps.apply_dynamic_registration("textDocument/prepareTypeHierarchy", None, false);
+        assert!(caps.call_hierarchy);
+        assert!(!caps.type_hierarchy);
+    }
+
+    #[test]
+    fn dynamic_registration_of_code_lens_reads_resolve_provider() {
+        let mut caps = ServerCapabilitySummary::default();
+        let opts = serde_json::json!({ "resolveProvider": true });
+        assert!(caps.apply_dynamic_registration("textDocument/codeLens", Some(&opts), true));
+        assert!(caps.code_lens);
+        assert!(caps.code_lens_resolve);
+
+        caps.apply_dynamic_registration("textDocument/codeLens", None, false);
+        assert!(!caps.code_lens);
+        assert!(!caps.code_lens_resolve);
+    }
+
+    #[test]
+    fn dynamic_registration_of_diagnostics_reads_workspace_flag() {
+        let mut caps = ServerCapabilitySummary::default();
+        assert!(caps.apply_dynamic_registration("textDocument/diagnostic", None, true));
+        assert!(caps.diagnostics);
+        assert!(!caps.workspace_diagnostics);
+
+        let opts = serde_json::json!({
+            "interFileDependencies": true,
+            "workspaceDiagnostics": true,
+        });
+        caps.apply_dynamic_registration("textDocument/diagnostic", Some(&opts), true);
+        assert!(caps.workspace_diagnostics);
+
+        caps.apply_dynamic_registration("textDocument/diagnostic", None, false);
+        assert!(!caps.diagnostics);
+        assert!(!caps.workspace_diagnostics);
+    }
+
+    #[test]
+    fn dynamic_registration_of_on_type_formatting_collects_triggers() {
+        let mut caps = ServerCapabilitySummary::default();
+        let opts = serde_json::json!({
+            "firstTriggerCharacter": "}",
+            "moreTriggerCharacter": [";", "\n"],
+        });
+        assert!(caps.apply_dynamic_registration(
+            "textDocument/onTypeFormatting",
+            Some(&opts),
+            true
+        ));
+        assert_eq!(caps.on_type_formatting_triggers, vec!["}", ";", "\n"]);
+
+        caps.apply_dynamic_registration("textDocument/onTypeFormatting", None, false);
+        assert!(caps.on_type_formatting_triggers.is_empty());
+    }
 }
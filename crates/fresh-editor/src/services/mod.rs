@@ -8,6 +8,7 @@ pub mod authority;
 pub mod clipboard;
 pub mod completion;
 pub mod counters;
+pub mod dap;
 pub mod editorconfig;
 pub mod env_provider;
 pub mod file_watcher;
@@ -20,6 +21,7 @@ pub mod live_grep_state;
 pub mod log_dirs;
 pub mod lsp;
 pub mod packages;
+pub mod plugin_capabilities;
 pub mod plugins;
 pub mod process_hidden;
 pub mod process_limits;
@@ -27,8 +29,10 @@ pub mod recovery;
 pub mod release_checker;
 pub mod remote;
 pub mod signal_handler;
+pub mod spell;
 pub mod status_log;
 pub mod styled_html;
+pub mod tasks;
 pub mod telemetry;
 pub mod terminal;
 pub mod terminal_modes;
@@ -4,8 +4,10 @@
 //! startup from Rust, replacing the JS-based `loadInstalledPackages()` in the
 //! pkg plugin. This eliminates async grammar rebuilds from plugin callbacks.
 
+use std::collections::{BTreeSet, HashMap};
 use std::path::{Path, PathBuf};
 
+use fresh_core::capability::PluginCapability;
 use schemars::JsonSchema;
 use serde::Deserialize;
 
@@ -129,6 +131,13 @@ pub struct FreshManifestConfig {
     /// Plugin definitions (for bundles)
     #[serde(default)]
     pub plugins: Vec<BundlePlugin>,
+
+    /// Capabilities the package's plugins need: `process`, `network`,
+    /// `filesystem` (writes outside the workspace) and `authority`. Calls
+    /// needing an undeclared capability fail; declared ones are approved by
+    /// the user on first use.
+    #[serde(default)]
+    pub capabilities: Vec<PluginCapability>,
 }
 
 /// Grammar file configuration within a package manifest.
@@ -347,6 +356,10 @@ pub struct PackageScanResult {
     pub bundle_plugin_dirs: Vec<PathBuf>,
     /// Bundle theme directories (for theme loader to scan)
     pub bundle_theme_dirs: Vec<PathBuf>,
+    /// Capabilities declared by each package plugin, keyed by plugin name
+    /// (the entry file's stem). Every plugin loaded from a package appears
+    /// here, so the ones missing from this map are not package plugins.
+    pub plugin_capabilities: HashMap<String, BTreeSet<PluginCapability>>,
 }
 
 /// Scan all installed packages and collect configs, grammars, plugin dirs, and theme dirs.
@@ -355,9 +368,16 @@ pub struct PackageScanResult {
 /// during editor startup before plugin loading. The scan covers:
 /// - `languages/packages/` — language packs with grammar, language config, LSP config
 /// - `bundles/packages/` — bundles with multiple languages, plugins, and themes
+/// - `plugins/packages/` — plugin packages, for their declared capabilities
 pub fn scan_installed_packages(config_dir: &Path) -> PackageScanResult {
     let mut result = PackageScanResult::default();
 
+    // Scan plugin packages (the plugin loader finds their directories itself)
+    let plugins_dir = config_dir.join("plugins/packages");
+    if plugins_dir.is_dir() {
+        scan_plugin_packages(&plugins_dir, &mut result);
+    }
+
     // Scan language packs
     let languages_dir = config_dir.join("languages/packages");
     if languages_dir.is_dir() {
@@ -382,6 +402,60 @@ pub fn scan_installed_packages(config_dir: &Path) -> PackageScanResult {
     result
 }
 
+/// Scan plugin packages from `plugins/packages/` for declared capabilities.
+fn scan_plugin_packages(dir: &Path, result: &mut PackageScanResult) {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(e) => e,
+        Err(e) => {
+            tracing::debug!("[package-scan] Failed to read {:?}: {}", dir, e);
+            return;
+        }
+    };
+
+    for entry in entries.flatten() {
+        let pkg_dir = entry.path();
+        let hidden = pkg_dir
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| n.starts_with('.'));
+        if !pkg_dir.is_dir() || hidden {
+            continue;
+        }
+        // A package without a readable manifest declares nothing.
+        let capabilities = read_manifest(&pkg_dir.join("package.json"))
+            .and_then(|m| m.fresh)
+            .map(|f| f.capabilities)
+            .unwrap_or_default();
+        declare_plugin_capabilities(&pkg_dir, &capabilities, result);
+    }
+}
+
+/// Record `capabilities` for every plugin the loader will find in
+/// `plugin_dir` — each `.ts`/`.js` file, named by its stem.
+fn declare_plugin_capabilities(
+    plugin_dir: &Path,
+    capabilities: &[PluginCapability],
+    result: &mut PackageScanResult,
+) {
+    let Ok(entries) = std::fs::read_dir(plugin_dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let ext = path.extension().and_then(|s| s.to_str());
+        if ext != Some("ts") && ext != Some("js") {
+            continue;
+        }
+        if let Some(name) = path.file_stem().and_then(|s| s.to_str()) {
+            result
+                .plugin_capabilities
+                .entry(name.to_string())
+                .or_default()
+                .extend(capabilities.iter().copied());
+        }
+    }
+}
+
 /// Scan language packs from `languages/packages/`.
 fn scan_language_packs(dir: &Path, result: &mut PackageScanResult) {
     let entries = match std::fs::read_dir(dir) {
@@ -506,6 +580,7 @@ fn process_bundle(pkg_dir: &Path, manifest: &PackageManifest, result: &mut Packa
         // The plugin loader expects the directory, not the entry file
         if let Some(plugin_dir) = entry_path.parent() {
             if plugin_dir.is_dir() {
+                declare_plugin_capabilities(plugin_dir, &fresh.capabilities, result);
                 result.bundle_plugin_dirs.push(plugin_dir.to_path_buf());
             }
         }
@@ -858,6 +933,38 @@ mod tests {
         assert!(result.language_configs.is_empty());
     }
 
+    #[test]
+    fn test_scan_plugin_package_capabilities() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config_dir = temp_dir.path();
+
+        let declared = config_dir.join("plugins/packages/gh-tools");
+        std::fs::create_dir_all(&declared).unwrap();
+        std::fs::write(
+            declared.join("package.json"),
+            r#"{
+                "name": "gh-tools",
+                "type": "plugin",
+                "fresh": { "entry": "gh.ts", "capabilities": ["process", "network"] }
+            }"#,
+        )
+        .unwrap();
+        std::fs::write(declared.join("gh.ts"), "").unwrap();
+
+        // No manifest: still a package plugin, with nothing declared.
+        let bare = config_dir.join("plugins/packages/bare");
+        std::fs::create_dir_all(&bare).unwrap();
+        std::fs::write(bare.join("bare.js"), "").unwrap();
+
+        let result = scan_installed_packages(config_dir);
+        assert_eq!(
+            result.plugin_capabilities["gh"],
+            BTreeSet::from([PluginCapability::Process, PluginCapability::Network])
+        );
+        assert!(result.plugin_capabilities["bare"].is_empty());
+        assert_eq!(result.plugin_capabilities.len(), 2);
+    }
+
     #[test]
     fn test_formatter_conversion() {
         let lang = LanguageManifestConfig {
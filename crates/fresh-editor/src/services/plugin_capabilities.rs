@@ -0,0 +1,373 @@
+//! Capability permissions for package plugins.
+//!
+//! A package lists the capabilities its plugins need in `package.json`
+//! (`fresh.capabilities`, collected by [`crate::services::packages`]). The
+//! first time a plugin uses a declared capability the user is asked once; the
+//! answer is kept in [`PluginCapabilityStore`]. Capabilities a package didn't
+//! declare are refused without asking.
+//!
+//! [`PluginPermissions`] combines the two into the
+//! [`PluginCapabilityPolicy`] the plugin runtime checks, and queues the calls
+//! that are waiting on the user's answer.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::io;
+use std::path::{Path, PathBuf};
+
+use fresh_core::api::PluginCommand;
+use fresh_core::capability::{PluginCapability, PluginCapabilityPolicy};
+
+/// The user's answer for one plugin capability.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CapabilityDecision {
+    Granted,
+    Denied,
+}
+
+/// Serialized form of every recorded decision, keyed by plugin name.
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+struct StoredCapabilities {
+    #[serde(default)]
+    plugins: BTreeMap<String, BTreeMap<PluginCapability, CapabilityDecision>>,
+}
+
+/// On-disk persistence of capability decisions: one JSON file
+/// (`plugin_capabilities.json`) in the user's data directory.
+///
+/// Like workspace trust, an approval is a per-user security decision and
+/// lives outside anything a package controls — a package must not be able to
+/// approve itself by shipping the file.
+#[derive(Debug, Clone)]
+pub struct PluginCapabilityStore {
+    path: PathBuf,
+}
+
+impl PluginCapabilityStore {
+    /// The store in `data_dir` (see `DirectoryContext::data_dir`).
+    pub fn in_data_dir(data_dir: &Path) -> Self {
+        Self {
+            path: data_dir.join("plugin_capabilities.json"),
+        }
+    }
+
+    /// Every recorded decision. A missing or corrupt file reads as empty
+    /// (everything undecided); the next write rewrites it cleanly.
+    fn load(&self) -> StoredCapabilities {
+        std::fs::read_to_string(&self.path)
+            .ok()
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default()
+    }
+
+    /// Write `stored` atomically (a pid-tagged temp file, then rename).
+    fn save(&self, stored: &StoredCapabilities) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(stored).map_err(io::Error::other)?;
+        let tmp = self
+            .path
+            .with_extension(format!("json.{}.tmp", std::process::id()));
+        std::fs::write(&tmp, json.as_bytes())?;
+        std::fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+
+    /// Record `decision` for `plugin`'s `capability`.
+    pub fn record(
+        &self,
+        plugin: &str,
+        capability: PluginCapability,
+        decision: CapabilityDecision,
+    ) -> io::Result<()> {
+        let mut stored = self.load();
+        stored
+            .plugins
+            .entry(plugin.to_string())
+            .or_default()
+            .insert(capability, decision);
+        self.save(&stored)
+    }
+
+    /// Forget the decision for `plugin`'s `capability`, so the next use asks
+    /// again.
+    pub fn revoke(&self, plugin: &str, capability: PluginCapability) -> io::Result<()> {
+        let mut stored = self.load();
+        if let Some(decisions) = stored.plugins.get_mut(plugin) {
+            decisions.remove(&capability);
+            if decisions.is_empty() {
+                stored.plugins.remove(plugin);
+            }
+        }
+        self.save(&stored)
+    }
+}
+
+/// Where a plugin stands with respect to one capability.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CapabilityStatus {
+    /// Not a package plugin, or declared and granted.
+    Allowed,
+    /// The package manifest doesn't declare it.
+    Undeclared,
+    /// Declared, and the user said no.
+    Denied,
+    /// Declared, and the user hasn't been asked yet.
+    Undecided,
+}
+
+/// The editor's view of package-plugin permissions: what each package
+/// declared, what the user decided, and the calls waiting on a decision.
+#[derive(Debug)]
+pub struct PluginPermissions {
+    declared: HashMap<String, BTreeSet<PluginCapability>>,
+    decisions: BTreeMap<String, BTreeMap<PluginCapability, CapabilityDecision>>,
+    store: Option<PluginCapabilityStore>,
+    /// Calls waiting on the user, in the order the capabilities were first
+    /// requested. Synchronous calls fail on the spot and only queue the
+    /// prompt, so they add no command here.
+    pending: Vec<((String, PluginCapability), Vec<PluginCommand>)>,
+}
+
+impl PluginPermissions {
+    /// Permissions for the package plugins in `declared` (see
+    /// `PackageScanResult::plugin_capabilities`), with decisions read from
+    /// `store`. Without a store decisions only last for the session.
+    pub fn new(
+        declared: HashMap<String, BTreeSet<PluginCapability>>,
+        store: Option<PluginCapabilityStore>,
+    ) -> Self {
+        let decisions = store.as_ref().map(|s| s.load().plugins).unwrap_or_default();
+        Self {
+            declared,
+            decisions,
+            store,
+            pending: Vec::new(),
+        }
+    }
+
+    pub fn status(&self, plugin: &str, capability: PluginCapability) -> CapabilityStatus {
+        let Some(declared) = self.declared.get(plugin) else {
+            return CapabilityStatus::Allowed;
+        };
+        if !declared.contains(&capability) {
+            return CapabilityStatus::Undeclared;
+        }
+        match self.decision(plugin, capability) {
+            Some(CapabilityDecision::Granted) => CapabilityStatus::Allowed,
+            Some(CapabilityDecision::Denied) => CapabilityStatus::Denied,
+            None => CapabilityStatus::Undecided,
+        }
+    }
+
+    fn decision(&self, plugin: &str, capability: PluginCapability) -> Option<CapabilityDecision> {
+        self.decisions
+            .get(plugin)
+            .and_then(|d| d.get(&capability))
+            .copied()
+    }
+
+    /// The policy to publish to the plugin runtime: every package plugin,
+    /// restricted to what it declared and the user granted.
+    pub fn policy(&self) -> PluginCapabilityPolicy {
+        let mut policy = PluginCapabilityPolicy::default();
+        for (plugin, declared) in &self.declared {
+            policy.restrict(
+                plugin.clone(),
+                declared
+                    .iter()
+                    .copied()
+                    .filter(|&c| self.decision(plugin, c) == Some(CapabilityDecision::Granted)),
+            );
+        }
+        policy
+    }
+
+    /// Park `command` until the user decides on `plugin`'s `capability`.
+    pub fn queue(
+        &mut self,
+        plugin: &str,
+        capability: PluginCapability,
+        command: Option<PluginCommand>,
+    ) {
+        let key = (plugin.to_string(), capability);
+        let index = match self.pending.iter().position(|(k, _)| *k == key) {
+            Some(index) => index,
+            None => {
+                self.pending.push((key, Vec::new()));
+                self.pending.len() - 1
+            }
+        };
+        self.pending[index].1.extend(command);
+    }
+
+    /// The oldest capability request still waiting for the user.
+    pub fn next_pending(&self) -> Option<(String, PluginCapability)> {
+        self.pending.first().map(|(key, _)| key.clone())
+    }
+
+    /// Remove and return the calls waiting on `plugin`'s `capability`.
+    pub fn take_pending(
+        &mut self,
+        plugin: &str,
+        capability: PluginCapability,
+    ) -> Vec<PluginCommand> {
+        match self
+            .pending
+            .iter()
+            .position(|((p, c), _)| p == plugin && *c == capability)
+        {
+            Some(index) => self.pending.remove(index).1,
+            None => Vec::new(),
+        }
+    }
+
+    /// Record the user's answer, in memory and in the store.
+    pub fn decide(
+        &mut self,
+        plugin: &str,
+        capability: PluginCapability,
+        decision: CapabilityDecision,
+    ) -> io::Result<()> {
+        self.decisions
+            .entry(plugin.to_string())
+            .or_default()
+            .insert(capability, decision);
+        match &self.store {
+            Some(store) => store.record(plugin, capability, decision),
+            None => Ok(()),
+        }
+    }
+
+    /// Forget a decision so the next use asks again.
+    pub fn revoke(&mut self, plugin: &str, capability: PluginCapability) -> io::Result<()> {
+        if let Some(decisions) = self.decisions.get_mut(plugin) {
+            decisions.remove(&capability);
+            if decisions.is_empty() {
+                self.decisions.remove(plugin);
+            }
+        }
+        match &self.store {
+            Some(store) => store.revoke(plugin, capability),
+            None => Ok(()),
+        }
+    }
+
+    /// Every recorded decision, sorted by plugin then capability.
+    pub fn decisions(&self) -> Vec<(String, PluginCapability, CapabilityDecision)> {
+        self.decisions
+            .iter()
+            .flat_map(|(plugin, decisions)| {
+                decisions
+                    .iter()
+                    .map(move |(&capability, &decision)| (plugin.clone(), capability, decision))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn declared() -> HashMap<String, BTreeSet<PluginCapability>> {
+        HashMap::from([(
+            "gh".to_string(),
+            BTreeSet::from([PluginCapability::Process, PluginCapability::Network]),
+        )])
+    }
+
+    #[test]
+    fn status_follows_manifest_and_decisions() {
+        let mut permissions = PluginPermissions::new(declared(), None);
+
+        assert_eq!(
+            permissions.status("bundled", PluginCapability::Authority),
+            CapabilityStatus::Allowed
+        );
+        assert_eq!(
+            permissions.status("gh", PluginCapability::Authority),
+            CapabilityStatus::Undeclared
+        );
+        assert_eq!(
+            permissions.status("gh", PluginCapability::Process),
+            CapabilityStatus::Undecided
+        );
+
+        permissions
+            .decide("gh", PluginCapability::Process, CapabilityDecision::Granted)
+            .unwrap();
+        permissions
+            .decide("gh", PluginCapability::Network, CapabilityDecision::Denied)
+            .unwrap();
+        assert_eq!(
+            permissions.status("gh", PluginCapability::Process),
+            CapabilityStatus::Allowed
+        );
+        assert_eq!(
+            permissions.status("gh", PluginCapability::Network),
+            CapabilityStatus::Denied
+        );
+
+        let policy = permissions.policy();
+        assert!(policy.allows("gh", PluginCapability::Process));
+        assert!(!policy.allows("gh", PluginCapability::Network));
+        assert!(policy.allows("bundled", PluginCapability::Network));
+    }
+
+    #[test]
+    fn decisions_persist_and_revoke() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = PluginCapabilityStore::in_data_dir(temp_dir.path());
+
+        let mut permissions = PluginPermissions::new(declared(), Some(store.clone()));
+        permissions
+            .decide("gh", PluginCapability::Network, CapabilityDecision::Granted)
+            .unwrap();
+
+        let reloaded = PluginPermissions::new(declared(), Some(store.clone()));
+        assert_eq!(
+            reloaded.decisions(),
+            vec![(
+                "gh".to_string(),
+                PluginCapability::Network,
+                CapabilityDecision::Granted
+            )]
+        );
+
+        permissions.revoke("gh", PluginCapability::Network).unwrap();
+        let reloaded = PluginPermissions::new(declared(), Some(store));
+        assert_eq!(
+            reloaded.status("gh", PluginCapability::Network),
+            CapabilityStatus::Undecided
+        );
+    }
+
+    #[test]
+    fn pending_calls_queue_per_capability() {
+        let mut permissions = PluginPermissions::new(declared(), None);
+        permissions.queue("gh", PluginCapability::Network, None);
+        permissions.queue(
+            "gh",
+            PluginCapability::Process,
+            Some(PluginCommand::ClearAuthority),
+        );
+        permissions.queue(
+            "gh",
+            PluginCapability::Network,
+            Some(PluginCommand::ClearEnv),
+        );
+
+        assert_eq!(
+            permissions.next_pending(),
+            Some(("gh".to_string(), PluginCapability::Network))
+        );
+        let taken = permissions.take_pending("gh", PluginCapability::Network);
+        assert!(matches!(taken.as_slice(), [PluginCommand::ClearEnv]));
+        assert_eq!(
+            permissions.next_pending(),
+            Some(("gh".to_string(), PluginCapability::Process))
+        );
+    }
+}
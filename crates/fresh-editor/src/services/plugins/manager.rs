@@ -409,6 +409,31 @@ impl PluginManager {
             .unwrap_or_default()
     }
 
+    /// Apply the plugin execution timeout and heap cap from the editor
+    /// config; 0 disables either limit.
+    ///
+    /// Available in all builds: without the `plugins` feature it does
+    /// nothing.
+    pub fn set_limits(&self, execution_timeout_ms: u64, memory_limit_mb: u64) {
+        #[cfg(feature = "plugins")]
+        if let Some(inner) = self.inner.as_ref() {
+            inner.set_limits(fresh_plugin_runtime::backend::PluginLimits {
+                execution_timeout: (execution_timeout_ms > 0)
+                    .then(|| std::time::Duration::from_millis(execution_timeout_ms)),
+                memory_limit_bytes: (memory_limit_mb > 0)
+                    .then(|| (memory_limit_mb as usize).saturating_mul(1024 * 1024)),
+            });
+        }
+        #[cfg(not(feature = "plugins"))]
+        let _ = (execution_timeout_ms, memory_limit_mb);
+    }
+
+    /// Cumulative hook and command time per plugin, slowest first.
+    #[cfg(feature = "plugins")]
+    pub fn plugin_profile(&self) -> Vec<fresh_plugin_runtime::backend::PluginProfileEntry> {
+        self.inner.as_ref().map(|m| m.profile()).unwrap_or_default()
+    }
+
     /// Collect the isolated-declarations `.d.ts` emit of every loaded
     /// plugin that produced one. Returns `(plugin_name, d_ts_source)`
     /// pairs — callers use this to assemble `plugins.d.ts`.
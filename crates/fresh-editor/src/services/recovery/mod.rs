@@ -19,7 +19,9 @@
 //! ├── {hash}.meta.json       # Recovery metadata with chunk index
 //! ├── {hash}.chunk.0         # Chunk 0 binary content
 //! ├── {hash}.chunk.1         # Chunk 1 binary content
-//! └── ...
+//! ├── ...
+//! └── undo/
+//!     └── {hash}.json        # Persisted undo history (opt-in)
 //! ```
 //!
 //! ## Storage Format
@@ -54,9 +56,9 @@ pub mod types;
 
 pub use storage::{RecoveryScope, RecoveryStorage};
 pub use types::{
-    generate_buffer_id, path_hash, ChunkMeta, ChunkedRecoveryData, ChunkedRecoveryIndex,
-    InplaceWriteRecovery, RecoveryChunk, RecoveryEntry, RecoveryMetadata, RecoveryResult,
-    SessionInfo, MAX_CHUNK_SIZE,
+    content_hash, generate_buffer_id, path_hash, ChunkMeta, ChunkedRecoveryData,
+    ChunkedRecoveryIndex, InplaceWriteRecovery, PersistedUndoHistory, RecoveryChunk, RecoveryEntry,
+    RecoveryMetadata, RecoveryResult, SessionInfo, MAX_CHUNK_SIZE,
 };
 
 use std::collections::HashMap;
@@ -4,8 +4,8 @@
 //! to ensure data integrity even during crashes.
 
 use super::types::{
-    generate_buffer_id, path_hash, ChunkedRecoveryData, ChunkedRecoveryIndex, RecoveryChunk,
-    RecoveryEntry, RecoveryMetadata, SessionInfo,
+    generate_buffer_id, path_hash, ChunkedRecoveryData, ChunkedRecoveryIndex, PersistedUndoHistory,
+    RecoveryChunk, RecoveryEntry, RecoveryMetadata, SessionInfo,
 };
 use crate::input::input_history::get_data_dir;
 use std::fs::{self, File};
@@ -40,6 +40,9 @@ impl RecoveryStorage {
     const CONTENT_EXT: &'static str = "content";
     /// Session lock file name
     const SESSION_LOCK: &'static str = "session.lock";
+    /// Subdirectory for persisted undo history. Kept out of the top level so
+    /// ending a session (which clears every recovery file) leaves it alone.
+    const UNDO_DIR: &'static str = "undo";
 
     /// Create a new recovery storage manager (legacy flat layout)
     pub fn new() -> io::Result<Self> {
@@ -752,6 +755,110 @@ impl RecoveryStorage {
         Ok(())
     }
 
+    // ========================================================================
+    // Persistent undo history
+    // ========================================================================
+
+    /// Get the path of the persisted undo history for a file
+    fn undo_history_path(&self, path: &Path) -> PathBuf {
+        self.recovery_dir
+            .join(Self::UNDO_DIR)
+            .join(format!("{}.json", path_hash(path)))
+    }
+
+    /// Save the undo history for a file, replacing any previous one
+    pub fn save_undo_history(&self, record: &PersistedUndoHistory) -> io::Result<()> {
+        let target = self.undo_history_path(&record.path);
+        if let Some(dir) = target.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let json = serde_json::to_vec(record)?;
+        self.atomic_write(&target, &json)
+    }
+
+    /// Load the undo history persisted for a file.
+    ///
+    /// Returns `None` if there is none, or if the record belongs to another
+    /// path (hash collision) or an unknown format version.
+    pub fn load_undo_history(&self, path: &Path) -> io::Result<Option<PersistedUndoHistory>> {
+        let source = self.undo_history_path(path);
+        if !source.exists() {
+            return Ok(None);
+        }
+        let record: PersistedUndoHistory = serde_json::from_slice(&fs::read(&source)?)?;
+        if record.path != path || record.format_version != PersistedUndoHistory::FORMAT_VERSION {
+            return Ok(None);
+        }
+        Ok(Some(record))
+    }
+
+    /// Delete the undo history persisted for a file
+    pub fn delete_undo_history(&self, path: &Path) -> io::Result<()> {
+        let target = self.undo_history_path(path);
+        if target.exists() {
+            fs::remove_file(&target)?;
+        }
+        Ok(())
+    }
+
+    /// Delete persisted undo histories that can no longer be used.
+    ///
+    /// Unreadable records and those of an unknown format version go first,
+    /// then any written more than `max_age_secs` ago, then all but the
+    /// `max_count` newest. What is left is kept only if `still_valid`
+    /// accepts it — callers check that the file exists and still hashes the
+    /// same. Returns the number of histories deleted.
+    pub fn prune_undo_history(
+        &self,
+        max_age_secs: u64,
+        max_count: usize,
+        mut still_valid: impl FnMut(&PersistedUndoHistory) -> bool,
+    ) -> io::Result<usize> {
+        let undo_dir = self.recovery_dir.join(Self::UNDO_DIR);
+        if !undo_dir.exists() {
+            return Ok(0);
+        }
+        let now = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+
+        let mut stale = Vec::new();
+        let mut records = Vec::new();
+        for entry in fs::read_dir(&undo_dir)? {
+            let file = entry?.path();
+            if file.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+            let record = fs::read(&file)
+                .ok()
+                .and_then(|bytes| serde_json::from_slice::<PersistedUndoHistory>(&bytes).ok())
+                .filter(|r| r.format_version == PersistedUndoHistory::FORMAT_VERSION)
+                .filter(|r| now.saturating_sub(r.saved_at) <= max_age_secs);
+            match record {
+                Some(record) => records.push((file, record)),
+                None => stale.push(file),
+            }
+        }
+        records.sort_by_key(|(_, r)| std::cmp::Reverse(r.saved_at));
+        for (i, (file, record)) in records.into_iter().enumerate() {
+            if i >= max_count || !still_valid(&record) {
+                stale.push(file);
+            }
+        }
+
+        let mut removed = 0;
+        for file in stale {
+            match fs::remove_file(&file) {
+                Ok(()) => removed += 1,
+                Err(e) => {
+                    tracing::warn!("Failed to remove undo history {}: {}", file.display(), e)
+                }
+            }
+        }
+        Ok(removed)
+    }
+
     // ========================================================================
     // Helper methods
     // ========================================================================
@@ -2151,4 +2258,88 @@ mod tests {
             base_dir.join("sessions").join("test-session")
         );
     }
+
+    #[test]
+    fn test_undo_history_save_load_delete() {
+        use crate::model::event::{CursorId, Event, EventLog};
+
+        let (storage, _temp) = create_test_storage();
+        let path = Path::new("/home/user/notes.txt");
+        assert!(storage.load_undo_history(path).unwrap().is_none());
+
+        let mut log = EventLog::new();
+        log.append(Event::Insert {
+            position: 0,
+            text: "hello".to_string(),
+            cursor_id: CursorId(0),
+        });
+        log.mark_saved();
+        let record = PersistedUndoHistory::new(
+            path.to_path_buf(),
+            crate::services::recovery::content_hash(b"hello"),
+            log.export_history().unwrap(),
+        );
+        storage.save_undo_history(&record).unwrap();
+
+        // Ending a session clears recovery files but keeps undo history.
+        storage.cleanup_all().unwrap();
+
+        let loaded = storage.load_undo_history(path).unwrap().unwrap();
+        assert_eq!(loaded.content_hash, record.content_hash);
+        assert_eq!(loaded.history.nodes.len(), 1);
+        assert_eq!(loaded.history.anchor, Some(0));
+        assert!(storage
+            .load_undo_history(Path::new("/home/user/other.txt"))
+            .unwrap()
+            .is_none());
+
+        storage.delete_undo_history(path).unwrap();
+        assert!(storage.load_undo_history(path).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_prune_undo_history() {
+        use crate::model::event::{CursorId, Event, EventLog};
+
+        let (storage, _temp) = create_test_storage();
+        let mut log = EventLog::new();
+        log.append(Event::Insert {
+            position: 0,
+            text: "x".to_string(),
+            cursor_id: CursorId(0),
+        });
+        log.mark_saved();
+        let save = |name: &str, age: u64| {
+            let mut record = PersistedUndoHistory::new(
+                PathBuf::from(format!("/home/user/{name}")),
+                name.to_string(),
+                log.export_history().unwrap(),
+            );
+            record.saved_at -= age;
+            storage.save_undo_history(&record).unwrap();
+        };
+        save("newest", 0);
+        save("invalid", 10);
+        save("older", 20);
+        save("oldest", 30);
+        save("expired", 1000);
+        let garbage = storage.recovery_dir.join(RecoveryStorage::UNDO_DIR);
+        fs::write(garbage.join("garbage.json"), b"not json").unwrap();
+
+        // "expired" is past the age cap, "oldest" past the count cap, and
+        // "invalid" is rejected by the caller.
+        let removed = storage
+            .prune_undo_history(100, 3, |r| r.content_hash != "invalid")
+            .unwrap();
+        assert_eq!(removed, 4);
+        let kept = |name: &str| {
+            storage
+                .load_undo_history(Path::new(&format!("/home/user/{name}")))
+                .unwrap()
+                .is_some()
+        };
+        assert!(kept("newest") && kept("older"));
+        assert!(!kept("invalid") && !kept("oldest") && !kept("expired"));
+        assert!(!garbage.join("garbage.json").exists());
+    }
 }
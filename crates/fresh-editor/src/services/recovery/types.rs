@@ -390,6 +390,13 @@ pub fn path_hash(path: &std::path::Path) -> String {
     format!("{:x}", hasher.finalize())[..16].to_string()
 }
 
+/// Hash of a file's full content, used to tell whether persisted undo
+/// history still applies to the file on disk
+pub fn content_hash(content: &[u8]) -> String {
+    use sha2::{Digest, Sha256};
+    format!("{:x}", Sha256::digest(content))
+}
+
 /// Generate a unique ID for an unsaved buffer
 pub fn generate_buffer_id() -> String {
     use std::time::SystemTime;
@@ -400,6 +407,52 @@ pub fn generate_buffer_id() -> String {
     format!("unsaved_{:x}", now)
 }
 
+/// Undo history persisted for one file (`editor.persistent_undo`).
+///
+/// Only valid while the file still has the content it was saved with: a
+/// record whose `content_hash` doesn't match is discarded, never replayed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersistedUndoHistory {
+    /// Version of the persisted format (for future compatibility)
+    pub format_version: u32,
+
+    /// File the history belongs to
+    pub path: PathBuf,
+
+    /// [`content_hash`] of the file content at the history's anchor
+    pub content_hash: String,
+
+    /// Unix timestamp when the history was written
+    pub saved_at: u64,
+
+    /// The undo tree itself
+    pub history: crate::model::event::UndoHistory,
+}
+
+impl PersistedUndoHistory {
+    /// Current persisted format version
+    pub const FORMAT_VERSION: u32 = 1;
+
+    /// Create a record for `path` whose content hashes to `content_hash`
+    pub fn new(
+        path: PathBuf,
+        content_hash: String,
+        history: crate::model::event::UndoHistory,
+    ) -> Self {
+        let saved_at = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        Self {
+            format_version: Self::FORMAT_VERSION,
+            path,
+            content_hash,
+            saved_at,
+            history,
+        }
+    }
+}
+
 /// Metadata for an in-place write operation that can be recovered after a crash.
 ///
 /// When doing an in-place write (to preserve file ownership), we write the content
@@ -10,6 +10,9 @@ import subprocess
 import re
 import threading
 import select
+import struct
+import ctypes
+import ctypes.util
 from concurrent.futures import ThreadPoolExecutor
 
 CHUNK = 65536
@@ -19,7 +22,9 @@ VERSION = 1
 procs = {}
 # Request IDs marked for cancellation
 cancelled = set()
-# Lock for thread-safe access to procs/cancelled
+# Active directory watches: id -> Event that stops the watch
+watches = {}
+# Lock for thread-safe access to procs/cancelled/watches
 lock = threading.Lock()
 # Lock for serializing stdout writes (prevents interleaved JSON lines)
 write_lock = threading.Lock()
@@ -44,14 +49,16 @@ def unb64(s):
     return base64.b64decode(s)
 
 
-def validate_path(p):
-    """Validate and canonicalize a path."""
+def absolute_path(p):
+    """Validate a path and make it absolute, keeping its symlinks."""
     if not p:
         raise ValueError("empty path")
-    expanded = os.path.expanduser(p)
-    if not os.path.isabs(expanded):
-        expanded = os.path.abspath(expanded)
-    return os.path.realpath(expanded)
+    return os.path.abspath(os.path.expanduser(p))
+
+
+def validate_path(p):
+    """Validate and canonicalize a path."""
+    return os.path.realpath(absolute_path(p))
 
 
 # === File Operations ===
@@ -160,6 +167,7 @@ def cmd_stat(id, p):
         r={
             "size": st.st_size,
             "mtime": int(st.st_mtime),
+            "mtime_ns": st.st_mtime_ns,
             "mode": st.st_mode,
             "uid": st.st_uid,
             "gid": st.st_gid,
@@ -621,6 +629,179 @@ def cmd_walk_files(id, p):
     send(id, r={"count": count})
 
 
+# === Directory Watching ===
+
+# inotify(7) event bits
+IN_MODIFY = 0x2
+IN_ATTRIB = 0x4
+IN_CLOSE_WRITE = 0x8
+IN_MOVED_FROM = 0x40
+IN_MOVED_TO = 0x80
+IN_CREATE = 0x100
+IN_DELETE = 0x200
+IN_DELETE_SELF = 0x400
+IN_MOVE_SELF = 0x800
+IN_Q_OVERFLOW = 0x4000
+IN_IGNORED = 0x8000
+IN_NONBLOCK = 0o4000
+IN_CLOEXEC = 0o2000000
+WATCH_MASK = (IN_MODIFY | IN_ATTRIB | IN_CLOSE_WRITE | IN_MOVED_FROM | IN_MOVED_TO
+              | IN_CREATE | IN_DELETE | IN_DELETE_SELF | IN_MOVE_SELF)
+# Seconds to let a burst of events (a save) finish before reporting it
+WATCH_BATCH = 0.05
+# Default seconds between scans where inotify isn't available
+WATCH_POLL = 1.0
+
+_libc = []
+
+
+def libc():
+    """The C library via ctypes, or None if it can't be loaded."""
+    if not _libc:
+        try:
+            lib = ctypes.CDLL(ctypes.util.find_library("c") or None, use_errno=True)
+        except OSError:
+            lib = None
+        _libc.append(lib)
+    return _libc[0]
+
+
+def inotify_open(path):
+    """Start an inotify watch on a directory. Returns the inotify fd, or
+    None where inotify isn't available (not Linux, no libc, watch limit)."""
+    lib = libc()
+    if lib is None or not hasattr(lib, "inotify_init1"):
+        return None
+    fd = lib.inotify_init1(IN_NONBLOCK | IN_CLOEXEC)
+    if fd < 0:
+        return None
+    if lib.inotify_add_watch(fd, os.fsencode(path), WATCH_MASK) < 0:
+        os.close(fd)
+        return None
+    return fd
+
+
+def inotify_read(fd, path):
+    """Drain queued inotify events. Returns (changed paths, whether the
+    watched directory itself went away)."""
+    changed = set()
+    gone = False
+    while True:
+        try:
+            buf = os.read(fd, 65536)
+        except BlockingIOError:
+            break
+        if not buf:
+            break
+        off = 0
+        while off + 16 <= len(buf):
+            _, mask, _, length = struct.unpack_from("iIII", buf, off)
+            name = buf[off + 16:off + 16 + length].rstrip(b"\0")
+            off += 16 + length
+            if mask & (IN_DELETE_SELF | IN_MOVE_SELF | IN_IGNORED):
+                gone = True
+            # An overflow or an event on the directory itself stands for
+            # everything in it.
+            if name and not mask & IN_Q_OVERFLOW:
+                changed.add(os.path.join(path, os.fsdecode(name)))
+            else:
+                changed.add(path)
+    return changed, gone
+
+
+def dir_snapshot(path):
+    """Map each entry of a directory (and the directory itself, under "")
+    to what a change would alter."""
+    st = os.stat(path)
+    snap = {"": (st.st_mtime_ns, st.st_mode)}
+    for entry in os.scandir(path):
+        try:
+            st = entry.stat(follow_symlinks=False)
+        except OSError:
+            continue
+        snap[entry.name] = (st.st_mtime_ns, st.st_size, st.st_ino, st.st_mode)
+    return snap
+
+
+def cmd_watch(id, p):
+    """Watch a directory (not recursively), streaming changed paths.
+
+    Sends {"ready": "inotify"|"poll"} once the watch is in place, then
+    {"paths": [...]} for each batch of changes. Runs until unwatched or
+    cancelled, or until the directory goes away.
+
+    Changed paths are reported under the path as given, not its realpath:
+    the client matches them against the paths it opened, which may go
+    through a symlink.
+    """
+    shown = absolute_path(p["path"])
+    path = os.path.realpath(shown)
+    if not stat.S_ISDIR(os.stat(path).st_mode):
+        raise NotADirectoryError(path)
+    interval = p.get("interval", WATCH_POLL)
+    fd = None if p.get("poll") else inotify_open(path)
+    snap = dir_snapshot(path) if fd is None else None
+
+    stop = threading.Event()
+    with lock:
+        watches[id] = stop
+
+    def stopped():
+        return stop.is_set() or id in cancelled
+
+    def run():
+        nonlocal snap
+        try:
+            send(id, d={"ready": "poll" if fd is None else "inotify"})
+            gone = False
+            while not gone and not stopped():
+                if fd is not None:
+                    readable, _, _ = select.select([fd], [], [], 0.25)
+                    if not readable:
+                        continue
+                    stop.wait(WATCH_BATCH)
+                    changed, gone = inotify_read(fd, shown)
+                else:
+                    if stop.wait(interval) or stopped():
+                        break
+                    try:
+                        new = dir_snapshot(path)
+                    except OSError:
+                        new, gone = {}, True
+                    changed = {
+                        os.path.join(shown, name) if name else shown
+                        for name in set(snap) | set(new)
+                        if snap.get(name) != new.get(name)
+                    }
+                    snap = new
+                if changed:
+                    send(id, d={"paths": sorted(changed)})
+            send(id, r={"gone": gone})
+        except Exception as e:
+            send(id, e=str(e))
+        finally:
+            if fd is not None:
+                os.close(fd)
+            with lock:
+                watches.pop(id, None)
+                cancelled.discard(id)
+
+    threading.Thread(target=run, daemon=True).start()
+
+
+def cmd_unwatch(id, p):
+    """Stop a directory watch started by `watch`."""
+    target_id = p["id"]
+    with lock:
+        stop = watches.get(target_id)
+        if stop is None:
+            # Not started yet (requests run concurrently); it checks this.
+            cancelled.add(target_id)
+    if stop:
+        stop.set()
+    send(id, r={})
+
+
 # === Method dispatch ===
 
 METHODS = {
@@ -647,6 +828,8 @@ METHODS = {
     "kill": cmd_kill,
     "cancel": cmd_cancel,
     "walk_files": cmd_walk_files,
+    "watch": cmd_watch,
+    "unwatch": cmd_unwatch,
 }
 
 
@@ -51,6 +51,9 @@ pub enum ChannelError {
     Remote(String),
 }
 
+/// Error every pending request fails with when the transport drops.
+pub const CONNECTION_CLOSED: &str = "connection closed";
+
 /// Pending request state
 struct PendingRequest {
     /// Channel for streaming data
@@ -279,7 +282,7 @@ impl AgentChannel {
     fn drain_pending(pending: &Arc<Mutex<HashMap<u64, PendingRequest>>>) {
         let mut pending = pending.lock().unwrap();
         for (id, req) in pending.drain() {
-            match req.result_tx.send(Err("connection closed".to_string())) {
+            match req.result_tx.send(Err(CONNECTION_CLOSED.to_string())) {
                 Ok(()) => {}
                 Err(_) => {
                     warn!("request {id}: receiver dropped during disconnect cleanup");
@@ -477,6 +480,30 @@ impl AgentChannel {
             oneshot::Receiver<Result<serde_json::Value, String>>,
         ),
         ChannelError,
+    > {
+        let (id, data_rx, result_rx) = self.register_request()?;
+
+        // Build and send request
+        let req = AgentRequest::new(id, method, params);
+        self.write_tx
+            .send(req.to_json_line())
+            .await
+            .map_err(|_| ChannelError::ChannelClosed)?;
+
+        Ok((data_rx, result_rx))
+    }
+
+    /// Allocate a request id and register its response channels.
+    #[allow(clippy::type_complexity)]
+    fn register_request(
+        &self,
+    ) -> Result<
+        (
+            u64,
+            mpsc::Receiver<serde_json::Value>,
+            oneshot::Receiver<Result<serde_json::Value, String>>,
+        ),
+        ChannelError,
     > {
         if !self.is_connected() {
             return Err(ChannelError::ChannelClosed);
@@ -494,14 +521,41 @@ impl AgentChannel {
             pending.insert(id, PendingRequest { data_tx, result_tx });
         }
 
-        // Build and send request
-        let req = AgentRequest::new(id, method, params);
-        self.write_tx
-            .send(req.to_json_line())
-            .await
-            .map_err(|_| ChannelError::ChannelClosed)?;
+        Ok((id, data_rx, result_rx))
+    }
 
-        Ok((data_rx, result_rx))
+    /// Send a streaming request without waiting on it. `on_data` gets each
+    /// data message as it arrives and `on_end` the final outcome, both on the
+    /// channel's runtime. Returns the request id, which long-lived streams
+    /// (`watch`) are stopped by.
+    ///
+    /// Never blocks, so it's safe on the editor thread. No timeout applies:
+    /// the stream may legitimately stay quiet for as long as it runs.
+    pub fn request_detached(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+        mut on_data: impl FnMut(serde_json::Value) + Send + 'static,
+        on_end: impl FnOnce(Result<serde_json::Value, ChannelError>) + Send + 'static,
+    ) -> Result<u64, ChannelError> {
+        let (id, mut data_rx, result_rx) = self.register_request()?;
+        let line = AgentRequest::new(id, method, params).to_json_line();
+        let write_tx = self.write_tx.clone();
+        self.runtime_handle.spawn(async move {
+            if write_tx.send(line).await.is_err() {
+                on_end(Err(ChannelError::ChannelClosed));
+                return;
+            }
+            while let Some(data) = data_rx.recv().await {
+                on_data(data);
+            }
+            let result = match result_rx.await {
+                Ok(result) => result.map_err(ChannelError::Remote),
+                Err(_) => Err(ChannelError::ChannelClosed),
+            };
+            on_end(result);
+        });
+        Ok(id)
     }
 
     /// Block on `fut` using the channel's runtime, safe to call whether or
@@ -3,13 +3,14 @@
 //! Implements the FileSystem trait for remote operations via SSH agent.
 
 use crate::model::filesystem::{
-    DirEntry, EntryType, FileMetadata, FilePermissions, FileReader, FileSystem, FileWriter, WriteOp,
+    DirEntry, DirWatchEvent, EntryType, FileMetadata, FilePermissions, FileReader, FileSystem,
+    FileWriter, WriteOp,
 };
-use crate::services::remote::channel::{AgentChannel, ChannelError};
+use crate::services::remote::channel::{AgentChannel, ChannelError, CONNECTION_CLOSED};
 use crate::services::remote::protocol::{
     append_params, count_lf_params, decode_base64, ls_params, patch_params, read_params,
-    stat_params, sudo_write_params, truncate_params, write_params, PatchOp, RemoteDirEntry,
-    RemoteMetadata,
+    stat_params, sudo_write_params, truncate_params, unwatch_params, watch_params, write_params,
+    PatchOp, RemoteDirEntry, RemoteMetadata,
 };
 use std::io::{self, Cursor, Read, Seek, Write};
 use std::path::{Path, PathBuf};
@@ -153,10 +154,12 @@ impl RemoteFileSystem {
 
     /// Convert remote metadata to FileMetadata
     fn convert_metadata(rm: &RemoteMetadata, name: &str) -> FileMetadata {
-        let modified = if rm.mtime > 0 {
-            Some(UNIX_EPOCH + Duration::from_secs(rm.mtime as u64))
-        } else {
-            None
+        // Prefer the nanosecond mtime: two saves within a second must still
+        // look like a change to auto-revert.
+        let modified = match rm.mtime_ns {
+            Some(ns) if ns > 0 => Some(UNIX_EPOCH + Duration::from_nanos(ns)),
+            _ if rm.mtime > 0 => Some(UNIX_EPOCH + Duration::from_secs(rm.mtime as u64)),
+            _ => None,
         };
 
         let is_hidden = name.starts_with('.');
@@ -564,6 +567,50 @@ impl FileSystem for RemoteFileSystem {
         Some(self.channel.reconnect_notify())
     }
 
+    fn watch_dir(
+        &self,
+        dir: &Path,
+        on_event: Box<dyn Fn(DirWatchEvent) + Send + Sync>,
+    ) -> io::Result<u64> {
+        // The agent watches on its side (inotify, else stat polling) and
+        // streams batches of changed paths; nothing here waits on it.
+        let on_event: Arc<dyn Fn(DirWatchEvent) + Send + Sync> = Arc::from(on_event);
+        let on_data = {
+            let on_event = on_event.clone();
+            move |data: serde_json::Value| {
+                if data.get("ready").is_some() {
+                    on_event(DirWatchEvent::Ready);
+                }
+                let paths = data.get("paths").and_then(|v| v.as_array());
+                for path in paths.into_iter().flatten().filter_map(|p| p.as_str()) {
+                    on_event(DirWatchEvent::Changed(PathBuf::from(path)));
+                }
+            }
+        };
+        let on_end = move |result: Result<serde_json::Value, ChannelError>| {
+            on_event(match result {
+                Err(ChannelError::ChannelClosed) => DirWatchEvent::Disconnected,
+                Err(ChannelError::Remote(msg)) if msg == CONNECTION_CLOSED => {
+                    DirWatchEvent::Disconnected
+                }
+                _ => DirWatchEvent::Stopped,
+            })
+        };
+        let path_str = dir.to_string_lossy();
+        self.channel
+            .request_detached("watch", watch_params(&path_str), on_data, on_end)
+            .map_err(Self::to_io_error)
+    }
+
+    fn unwatch_dir(&self, id: u64) {
+        if let Err(e) = self
+            .channel
+            .request_detached("unwatch", unwatch_params(id), |_| {}, |_| {})
+        {
+            tracing::debug!("unwatch {}: {}", id, e);
+        }
+    }
+
     fn home_dir(&self) -> io::Result<PathBuf> {
         // Served from the connect-time cache on the hot path (workspace
         // restore / file open / file explorer), so the editor thread doesn't
@@ -858,9 +905,10 @@ mod tests {
         #[cfg(not(unix))]
         let (uid, gid) = (1000u32, 1000u32);
 
-        let rm = RemoteMetadata {
+        let mut rm = RemoteMetadata {
             size: 1234,
             mtime: 1700000000,
+            mtime_ns: None,
             mode: 0o644,
             uid,
             gid,
@@ -876,6 +924,13 @@ mod tests {
 
         let meta = RemoteFileSystem::convert_metadata(&rm, ".hidden");
         assert!(meta.is_hidden);
+
+        rm.mtime_ns = Some(1_700_000_000_250_000_000);
+        let meta = RemoteFileSystem::convert_metadata(&rm, "test.txt");
+        assert_eq!(
+            meta.modified,
+            Some(UNIX_EPOCH + Duration::from_millis(1_700_000_000_250))
+        );
     }
 
     #[test]
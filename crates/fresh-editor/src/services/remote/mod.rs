@@ -32,20 +32,22 @@ pub use connection::{ConnectionParams, SshConnection, SshError};
 pub use filesystem::RemoteFileSystem;
 pub use protocol::{
     decode_base64, encode_base64, ls_params, read_params, stat_params, sudo_write_params,
-    write_params, AgentRequest, AgentResponse,
+    unwatch_params, watch_params, write_params, AgentRequest, AgentResponse,
 };
 pub use spawner::{
-    build_kube_agent_terminal_args, build_kube_terminal_args, build_ssh_agent_terminal_args,
-    build_ssh_terminal_args, ssh_remote_env_launcher, LocalLongRunningSpawner, LocalProcessSpawner,
-    LongRunningSpawner, ProcessSpawner, RemoteLongRunningSpawner, RemoteProcessSpawner, SpawnError,
-    SpawnResult, StdioChild, SSH_EXEC_LOGIN_SHELL,
+    build_exec_agent_terminal_args, build_exec_terminal_args, build_kube_agent_terminal_args,
+    build_kube_terminal_args, build_ssh_agent_terminal_args, build_ssh_terminal_args,
+    ssh_remote_env_launcher, LocalLongRunningSpawner, LocalProcessSpawner, LongRunningSpawner,
+    ProcessSpawner, RemoteLongRunningSpawner, RemoteProcessSpawner, SpawnError, SpawnResult,
+    StdioChild, SSH_EXEC_LOGIN_SHELL,
 };
 /// Shared `kubectl exec` argv builder, used by the agent transport, the
 /// terminal wrapper, and the long-running (LSP) spawner. Crate-internal.
 pub(crate) use transport::kubectl_exec_argv;
 pub use transport::{
-    bootstrap_agent, spawn_kube_reconnect_task, KubeConnection, KubeTarget, KubectlExecTransport,
-    RemoteTransport, StderrMode, TransportError,
+    bootstrap_agent, spawn_carrier_reconnect_task, spawn_kube_reconnect_task, CarrierConnection,
+    ExecCarrier, ExecTransport, KubeTarget, KubectlExecTransport, RemoteTransport, StderrMode,
+    TransportError, EXEC_CARRIER_PLACEHOLDER,
 };
 
 /// The Python agent source code, embedded at compile time.
@@ -97,6 +97,9 @@ pub struct RemoteDirEntry {
 pub struct RemoteMetadata {
     pub size: u64,
     pub mtime: i64,
+    /// Nanosecond mtime; older agents only send whole seconds (`mtime`)
+    #[serde(default)]
+    pub mtime_ns: Option<u64>,
     pub mode: u32,
     #[serde(default)]
     pub uid: u32,
@@ -209,6 +212,16 @@ pub fn cancel_params(request_id: u64) -> serde_json::Value {
     serde_json::json!({"id": request_id})
 }
 
+/// Build params for watch request (stream changes to a directory's entries)
+pub fn watch_params(path: &str) -> serde_json::Value {
+    serde_json::json!({"path": path})
+}
+
+/// Build params for unwatch request (stop the watch started by `watch_id`)
+pub fn unwatch_params(watch_id: u64) -> serde_json::Value {
+    serde_json::json!({"id": watch_id})
+}
+
 /// Build params for append request
 pub fn append_params(path: &str, data: &[u8]) -> serde_json::Value {
     serde_json::json!({
@@ -1118,16 +1118,33 @@ pub fn build_kube_agent_terminal_args(
     build_kube_remote_args(target, base_env, &agent_login_exec_tail(argv))
 }
 
-/// Shared body of the K8s integrated-terminal argv: export the in-pod env
-/// probe, land in the workspace, then run `exec_tail` inside a `sh -lc`
+/// Shared body of the K8s integrated-terminal argv: the
+/// [`workspace_shell_script`] inside a `kubectl exec -it … -- sh -lc`
 /// wrapper. The bare terminal passes `exec ${SHELL:-/bin/sh} -l`; the agent
-/// terminal passes [`agent_login_exec_tail`]. Kept as one function so the
-/// env-export + cwd-landing logic stays identical between the two.
+/// terminal passes [`agent_login_exec_tail`].
 fn build_kube_remote_args(
     target: &crate::services::remote::KubeTarget,
     base_env: &[(String, String)],
     exec_tail: &str,
 ) -> Vec<String> {
+    let remote_cmd = workspace_shell_script(base_env, target.workspace.as_deref(), exec_tail);
+    crate::services::remote::transport::kubectl_exec_argv(
+        target,
+        &["-it"],
+        "sh",
+        &["-lc".to_string(), remote_cmd],
+    )
+}
+
+/// The `sh -lc` script the K8s and exec-carrier terminals run on the far
+/// side: export the captured env probe, land in the workspace, then run
+/// `exec_tail`. Kept as one function so the env-export + cwd-landing logic
+/// stays identical between carriers and between the bare and agent terminals.
+fn workspace_shell_script(
+    base_env: &[(String, String)],
+    workspace: Option<&str>,
+    exec_tail: &str,
+) -> String {
     let mut remote_cmd = String::new();
     // Apply the captured in-pod env probe to the integrated terminal so it
     // matches what LSP / spawnProcess get in the pod (issue #2355; see
@@ -1141,19 +1158,53 @@ fn build_kube_remote_args(
     for (k, v) in base_env {
         remote_cmd.push_str(&format!("export {}={}; ", k, shell_quote(v)));
     }
-    if let Some(dir) = target.workspace.as_deref().filter(|d| !d.is_empty()) {
+    if let Some(dir) = workspace.filter(|d| !d.is_empty()) {
         let quoted = shell_quote(dir);
         remote_cmd.push_str(&format!(
             "d={quoted}; [ -d \"$d\" ] || d=$(dirname \"$d\"); cd \"$d\" 2>/dev/null; "
         ));
     }
     remote_cmd.push_str(exec_tail);
-    crate::services::remote::transport::kubectl_exec_argv(
-        target,
-        &["-it"],
-        "sh",
-        &["-lc".to_string(), remote_cmd],
-    )
+    remote_cmd
+}
+
+/// Build the full host argv (program first) that opens the integrated
+/// terminal as an interactive login shell through an exec carrier's
+/// `interactive` template, rooted at the carrier's workspace:
+///
+/// ```text
+/// <interactive template around: sh -lc 'cd WS; exec "$SHELL" -l'>
+/// ```
+///
+/// The generic-carrier analogue of [`build_kube_terminal_args`]: carriers have
+/// no common cwd/env flags, so both ride the same `sh -lc` hop and the
+/// authority's terminal wrapper sets `manages_cwd = true`.
+pub fn build_exec_terminal_args(
+    carrier: &crate::services::remote::ExecCarrier,
+    base_env: &[(String, String)],
+) -> Vec<String> {
+    build_exec_remote_args(carrier, base_env, "exec ${SHELL:-/bin/sh} -l")
+}
+
+/// Build the full host argv that runs an interactive *agent* `argv` through an
+/// exec carrier, rooted at its workspace — the agent analogue of
+/// [`build_exec_terminal_args`], with the same login-shell hand-off as
+/// [`build_kube_agent_terminal_args`].
+pub fn build_exec_agent_terminal_args(
+    carrier: &crate::services::remote::ExecCarrier,
+    base_env: &[(String, String)],
+    argv: &[String],
+) -> Vec<String> {
+    build_exec_remote_args(carrier, base_env, &agent_login_exec_tail(argv))
+}
+
+fn build_exec_remote_args(
+    carrier: &crate::services::remote::ExecCarrier,
+    base_env: &[(String, String)],
+    exec_tail: &str,
+) -> Vec<String> {
+    let remote_cmd = workspace_shell_script(base_env, carrier.workspace.as_deref(), exec_tail);
+    carrier.interactive_argv(&["sh".to_string(), "-lc".to_string(), remote_cmd])
 }
 
 /// Long-running spawner over SSH: each LSP server (or tool agent) gets its own
@@ -1903,4 +1954,34 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn build_exec_terminal_args_wraps_login_shell_in_interactive_template() {
+        let carrier = crate::services::remote::ExecCarrier {
+            run: vec!["incus".into(), "exec".into(), "dev".into(), "--".into()],
+            interactive: Some(vec![
+                "incus".into(),
+                "exec".into(),
+                "-t".into(),
+                "dev".into(),
+                "--".into(),
+                "{cmd}".into(),
+            ]),
+            stdio: None,
+            workspace: Some("/src".into()),
+            label: None,
+        };
+        let base_env = vec![("LANG".to_string(), "C.UTF-8".to_string())];
+        let a = build_exec_terminal_args(&carrier, &base_env);
+        assert_eq!(&a[..7], &["incus", "exec", "-t", "dev", "--", "sh", "-lc"]);
+        assert_eq!(
+            a[7],
+            "export LANG='C.UTF-8'; d='/src'; [ -d \"$d\" ] || d=$(dirname \"$d\"); cd \"$d\" 2>/dev/null; exec ${SHELL:-/bin/sh} -l"
+        );
+
+        // The agent terminal hands the argv to the far-side login shell.
+        let agent = build_exec_agent_terminal_args(&carrier, &[], &["claude".to_string()]);
+        assert_eq!(agent.len(), 8);
+        assert!(agent[7].contains("exec ${SHELL:-/bin/sh} -lc ") && agent[7].contains("claude"));
+    }
 }
@@ -4,8 +4,9 @@
 //! the far side, wait for its `ready` line, check the protocol version, then
 //! hand the stdio pair to an [`AgentChannel`] — is identical regardless of
 //! *how* we reach that far side. SSH spawns `ssh … python3 …`; K8s spawns
-//! `kubectl exec … -- python3 …`. The only thing that differs is the carrier
-//! command.
+//! `kubectl exec … -- python3 …`; a generic [`ExecCarrier`] spawns whatever
+//! argv template the user configured (`podman exec -i …`, `incus exec …`,
+//! `distrobox enter …`). The only thing that differs is the carrier command.
 //!
 //! A [`RemoteTransport`] supplies exactly that: a configured carrier
 //! [`Command`] whose execution runs the python bootstrap remotely. Everything
@@ -20,6 +21,7 @@
 use std::process::Stdio;
 use std::sync::Arc;
 
+use serde::{Deserialize, Serialize};
 use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt, BufReader};
 use tokio::process::{Child, ChildStdin, ChildStdout, Command};
 
@@ -95,6 +97,133 @@ pub(crate) fn kubectl_exec_argv(
     a
 }
 
+/// Placeholder a carrier template uses for the command it carries. A template
+/// element that *is* the placeholder is replaced by the command's argv
+/// (spliced, one element per argument); an element that merely *contains* it
+/// gets the POSIX-quoted, space-joined command instead, for carriers that take
+/// a single shell string. A template without the placeholder has the argv
+/// appended.
+pub const EXEC_CARRIER_PLACEHOLDER: &str = "{cmd}";
+
+/// A generic "exec into somewhere" carrier: Podman, Incus, Distrobox,
+/// `toolbox`, a custom sandbox wrapper — anything that runs a command on the
+/// far side given an argv on the host. Three templates cover the three ways
+/// the editor runs things there:
+///
+/// - `run` — one-shot commands (plugin `spawnProcess`, `command -v` probes).
+/// - `interactive` — the integrated terminal's PTY child (needs a TTY, e.g.
+///   `podman exec -it`). Defaults to `run`.
+/// - `stdio` — long-lived piped processes: the remote agent and LSP servers
+///   (needs stdin kept open, e.g. `podman exec -i`). Defaults to `run`.
+///
+/// See [`EXEC_CARRIER_PLACEHOLDER`] for how the carried command is placed.
+/// cwd and env are applied on the far side through a POSIX `sh -c` hop (as
+/// with `kubectl exec`), so the templates never need to know about either.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ExecCarrier {
+    pub run: Vec<String>,
+    #[serde(default)]
+    pub interactive: Option<Vec<String>>,
+    #[serde(default)]
+    pub stdio: Option<Vec<String>>,
+    /// Far-side workspace root (terminal `cd` target and the default cwd
+    /// for spawns); `None` = wherever the carrier lands.
+    #[serde(default)]
+    pub workspace: Option<String>,
+    /// Status-bar identity; `None` derives one from the `run` template.
+    #[serde(default)]
+    pub label: Option<String>,
+}
+
+impl ExecCarrier {
+    /// Check the templates are usable: `run` must name a program, and an
+    /// explicit `interactive` / `stdio` template must too.
+    pub fn validate(&self) -> Result<(), String> {
+        let templates = [
+            ("run", Some(&self.run)),
+            ("interactive", self.interactive.as_ref()),
+            ("stdio", self.stdio.as_ref()),
+        ];
+        for (name, template) in templates {
+            if let Some(template) = template {
+                match template.first() {
+                    None => return Err(format!("exec carrier `{name}` template is empty")),
+                    Some(program) if program.contains(EXEC_CARRIER_PLACEHOLDER) => {
+                        return Err(format!(
+                            "exec carrier `{name}` template must start with the carrier program, \
+                             not {EXEC_CARRIER_PLACEHOLDER}"
+                        ));
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Host-side argv that runs `argv` once on the far side.
+    pub fn run_argv(&self, argv: &[String]) -> Vec<String> {
+        expand_exec_template(&self.run, argv)
+    }
+
+    /// Host-side argv that runs `argv` on the far side under a TTY.
+    pub fn interactive_argv(&self, argv: &[String]) -> Vec<String> {
+        expand_exec_template(self.interactive.as_ref().unwrap_or(&self.run), argv)
+    }
+
+    /// Host-side argv that runs `argv` on the far side with stdio piped.
+    pub fn stdio_argv(&self, argv: &[String]) -> Vec<String> {
+        expand_exec_template(self.stdio.as_ref().unwrap_or(&self.run), argv)
+    }
+
+    /// Stable, human-readable identity: the configured label, else
+    /// `exec:` plus the `run` template up to the placeholder, e.g.
+    /// `exec:podman exec dev`.
+    pub fn display(&self) -> String {
+        if let Some(label) = self.label.as_deref().filter(|l| !l.is_empty()) {
+            return label.to_string();
+        }
+        let head: Vec<&str> = self
+            .run
+            .iter()
+            .take_while(|a| !a.contains(EXEC_CARRIER_PLACEHOLDER))
+            .map(String::as_str)
+            .collect();
+        format!("exec:{}", head.join(" "))
+    }
+}
+
+/// Expand a carrier template around `argv` per [`EXEC_CARRIER_PLACEHOLDER`].
+fn expand_exec_template(template: &[String], argv: &[String]) -> Vec<String> {
+    let mut out: Vec<String> = Vec::with_capacity(template.len() + argv.len());
+    let mut placed = false;
+    for part in template {
+        if part == EXEC_CARRIER_PLACEHOLDER {
+            out.extend(argv.iter().cloned());
+            placed = true;
+        } else if part.contains(EXEC_CARRIER_PLACEHOLDER) {
+            let joined = argv
+                .iter()
+                .map(|a| shell_quote(a))
+                .collect::<Vec<_>>()
+                .join(" ");
+            out.push(part.replace(EXEC_CARRIER_PLACEHOLDER, &joined));
+            placed = true;
+        } else {
+            out.push(part.clone());
+        }
+    }
+    if !placed {
+        out.extend(argv.iter().cloned());
+    }
+    out
+}
+
+/// POSIX single-quote for splicing an argument into a carrier's shell string.
+fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "'\\''"))
+}
+
 /// The python one-liner the carrier runs: read exactly `AGENT_SOURCE.len()`
 /// bytes from stdin and `exec` them, then the agent keeps reading stdin for
 /// protocol messages. Byte-count framing avoids any dependency on a remote
@@ -170,6 +299,55 @@ impl RemoteTransport for KubectlExecTransport {
     }
 }
 
+/// Generic exec carrier — runs the agent through an [`ExecCarrier`]'s `stdio`
+/// template (Podman, Incus, Distrobox, a custom sandbox, or plain `sh -c`).
+#[derive(Clone)]
+pub struct ExecTransport {
+    carrier: ExecCarrier,
+}
+
+impl ExecTransport {
+    pub fn new(carrier: ExecCarrier) -> Self {
+        Self { carrier }
+    }
+
+    pub fn carrier(&self) -> &ExecCarrier {
+        &self.carrier
+    }
+}
+
+impl RemoteTransport for ExecTransport {
+    fn build_command(&self, stderr: StderrMode) -> Command {
+        let argv = self.carrier.stdio_argv(&[
+            "python3".to_string(),
+            "-u".to_string(),
+            "-c".to_string(),
+            agent_bootstrap_pycode(),
+        ]);
+        // `validate` guarantees a program; an empty template still yields a
+        // command that fails to spawn rather than a panic here.
+        let (program, args) = argv.split_first().map_or(("", &[][..]), |(p, a)| (p, a));
+        let mut cmd = Command::new(program);
+        cmd.args(args);
+        cmd.stdin(Stdio::piped());
+        cmd.stdout(Stdio::piped());
+        match stderr {
+            StderrMode::Inherit => {
+                cmd.stderr(Stdio::inherit());
+            }
+            StderrMode::Null => {
+                cmd.stderr(Stdio::null());
+            }
+        }
+        cmd.hide_window();
+        cmd
+    }
+
+    fn display(&self) -> String {
+        self.carrier.display()
+    }
+}
+
 /// Error establishing a transport-backed agent connection.
 #[derive(Debug, thiserror::Error)]
 pub enum TransportError {
@@ -214,7 +392,7 @@ pub async fn bootstrap_agent(
         Ok(0) => {
             return Err(TransportError::AgentStartFailed(format!(
                 "{} closed the connection before the agent was ready \
-                 (is python3 present in the pod, and the context/namespace/pod correct?)",
+                 (is python3 present on the far side, and the carrier target correct?)",
                 transport.display()
             )));
         }
@@ -249,28 +427,30 @@ pub async fn bootstrap_agent(
 
 /// Active agent connection over a [`RemoteTransport`].
 ///
-/// The K8s analogue of [`SshConnection`](super::SshConnection): owns the
+/// The carrier analogue of [`SshConnection`](super::SshConnection): owns the
 /// carrier child and the [`AgentChannel`] the editor's remote filesystem and
-/// spawners ride on. Dropping it kills the carrier.
-pub struct KubeConnection {
+/// spawners ride on. Dropping it kills the carrier. Shared by every
+/// non-SSH carrier (K8s, generic exec) — only the transport differs.
+pub struct CarrierConnection {
     process: Child,
     channel: Arc<AgentChannel>,
     display: String,
-    /// Keeps the idle `kubectl exec` stream warm against LB/NAT idle
-    /// timeouts. Aborted on drop so a dead carrier stops being pinged.
+    /// Keeps an idle carrier stream (`kubectl exec`, `podman exec`, …) warm
+    /// against LB/NAT idle timeouts. Aborted on drop so a dead carrier stops
+    /// being pinged.
     heartbeat: tokio::task::JoinHandle<()>,
 }
 
-impl KubeConnection {
-    /// Bootstrap the agent inside the pod named by `target`.
-    pub async fn connect(target: KubeTarget) -> Result<Self, TransportError> {
-        let transport = KubectlExecTransport::new(target);
+impl CarrierConnection {
+    /// Bootstrap the agent through `transport`.
+    pub async fn connect(transport: &dyn RemoteTransport) -> Result<Self, TransportError> {
         // Capture (discard) the carrier's stderr rather than inheriting it: the
         // editor renders a full-screen ratatui UI on the alternate screen, so
-        // an inherited stderr lets kubectl scribble diagnostics straight over
-        // the rendered UI and corrupt it (the same failure mode as the SSH
-        // path). The EOF error below already explains the likely causes.
-        let (reader, writer, child) = bootstrap_agent(&transport, StderrMode::Null).await?;
+        // an inherited stderr lets the carrier scribble diagnostics straight
+        // over the rendered UI and corrupt it (the same failure mode as the SSH
+        // path). The EOF error in `bootstrap_agent` already explains the likely
+        // causes.
+        let (reader, writer, child) = bootstrap_agent(transport, StderrMode::Null).await?;
         let channel = Arc::new(AgentChannel::new(reader, writer));
         let heartbeat = crate::services::remote::spawn_heartbeat_task(
             &channel,
@@ -298,27 +478,21 @@ impl KubeConnection {
     }
 }
 
-/// Reconnect task for a K8s agent channel: when the channel drops, it
-/// re-bootstraps the agent by re-running `kubectl exec` against the pod and
-/// hot-swaps the transport via `replace_transport`. The K8s analogue of
+/// Reconnect task for a carrier agent channel: when the channel drops, it
+/// re-bootstraps the agent by re-running the carrier and hot-swaps the
+/// transport via `replace_transport`. The carrier analogue of
 /// [`spawn_reconnect_task`](super::spawn_reconnect_task), reusing the generic
 /// [`spawn_reconnect_task_with`](super::spawn_reconnect_task_with).
-///
-/// Reconnects to the *same* `target`. A pod reschedule / eviction changes the
-/// pod name, which this does not yet re-resolve — the plugin "resolve current
-/// pod" callback (`AUTHORITY_DESIGN.md` open question 3) layers on later. A
-/// same-name reconnect still covers transient stream drops (the common idle /
-/// network-blip case).
-pub fn spawn_kube_reconnect_task(
+pub fn spawn_carrier_reconnect_task(
     channel: &Arc<AgentChannel>,
-    target: KubeTarget,
+    transport: Arc<dyn RemoteTransport>,
+    label: &'static str,
 ) -> tokio::task::JoinHandle<()> {
     let connect_fn = move || {
-        let target = target.clone();
+        let transport = Arc::clone(&transport);
         async move {
-            let transport = KubectlExecTransport::new(target);
             // Non-interactive on reconnect (no terminal to prompt on).
-            let (reader, writer, _child) = bootstrap_agent(&transport, StderrMode::Null)
+            let (reader, writer, _child) = bootstrap_agent(transport.as_ref(), StderrMode::Null)
                 .await
                 .map_err(|e| crate::services::remote::SshError::AgentStartFailed(e.to_string()))?;
             let reader: Box<dyn AsyncBufRead + Unpin + Send> = Box::new(reader);
@@ -330,11 +504,30 @@ pub fn spawn_kube_reconnect_task(
         Arc::clone(channel),
         connect_fn,
         crate::services::remote::ReconnectConfig::default(),
+        label,
+    )
+}
+
+/// Reconnect task for a K8s agent channel: re-runs `kubectl exec` against the
+/// pod (see [`spawn_carrier_reconnect_task`]).
+///
+/// Reconnects to the *same* `target`. A pod reschedule / eviction changes the
+/// pod name, which this does not yet re-resolve — the plugin "resolve current
+/// pod" callback (`AUTHORITY_DESIGN.md` open question 3) layers on later. A
+/// same-name reconnect still covers transient stream drops (the common idle /
+/// network-blip case).
+pub fn spawn_kube_reconnect_task(
+    channel: &Arc<AgentChannel>,
+    target: KubeTarget,
+) -> tokio::task::JoinHandle<()> {
+    spawn_carrier_reconnect_task(
+        channel,
+        Arc::new(KubectlExecTransport::new(target)),
         "K8s remote",
     )
 }
 
-impl Drop for KubeConnection {
+impl Drop for CarrierConnection {
     fn drop(&mut self) {
         // Stop pinging a carrier we're about to kill.
         self.heartbeat.abort();
@@ -432,6 +625,88 @@ mod tests {
         assert!(joined.is_ok(), "aborted reconnect task joins promptly");
     }
 
+    fn sv(parts: &[&str]) -> Vec<String> {
+        parts.iter().map(|p| p.to_string()).collect()
+    }
+
+    fn podman() -> ExecCarrier {
+        ExecCarrier {
+            run: sv(&["podman", "exec", "dev", "{cmd}"]),
+            interactive: Some(sv(&["podman", "exec", "-it", "dev", "{cmd}"])),
+            stdio: Some(sv(&["podman", "exec", "-i", "dev", "{cmd}"])),
+            workspace: None,
+            label: None,
+        }
+    }
+
+    #[test]
+    fn exec_template_splices_argv_at_placeholder() {
+        let argv = podman().stdio_argv(&sv(&["rust-analyzer", "--log", "a b"]));
+        assert_eq!(
+            argv,
+            sv(&[
+                "podman",
+                "exec",
+                "-i",
+                "dev",
+                "rust-analyzer",
+                "--log",
+                "a b"
+            ])
+        );
+        assert_eq!(podman().interactive_argv(&sv(&["bash"]))[2], "-it");
+    }
+
+    #[test]
+    fn exec_template_quotes_argv_embedded_in_a_string() {
+        // Carriers that take one shell string get the argv POSIX-quoted.
+        let carrier = ExecCarrier {
+            run: sv(&["toolbox", "run", "sh", "-c", "cd /src && {cmd}"]),
+            interactive: None,
+            stdio: None,
+            workspace: None,
+            label: None,
+        };
+        let argv = carrier.run_argv(&sv(&["echo", "it's"]));
+        assert_eq!(argv[4], "cd /src && 'echo' 'it'\\''s'");
+    }
+
+    #[test]
+    fn exec_template_without_placeholder_appends_and_defaults_to_run() {
+        let carrier = ExecCarrier {
+            run: sv(&["distrobox", "enter", "box", "--"]),
+            interactive: None,
+            stdio: None,
+            workspace: None,
+            label: None,
+        };
+        let expected = sv(&["distrobox", "enter", "box", "--", "ls", "-la"]);
+        assert_eq!(carrier.run_argv(&sv(&["ls", "-la"])), expected);
+        // `interactive` / `stdio` fall back to the `run` template.
+        assert_eq!(carrier.interactive_argv(&sv(&["ls", "-la"])), expected);
+        assert_eq!(carrier.stdio_argv(&sv(&["ls", "-la"])), expected);
+        assert_eq!(carrier.display(), "exec:distrobox enter box --");
+    }
+
+    #[test]
+    fn exec_carrier_validate_rejects_unusable_templates() {
+        assert!(podman().validate().is_ok());
+        let mut empty = podman();
+        empty.stdio = Some(Vec::new());
+        assert!(empty.validate().unwrap_err().contains("stdio"));
+        let mut bare = podman();
+        bare.run = sv(&["{cmd}"]);
+        assert!(bare.validate().unwrap_err().contains("run"));
+    }
+
+    #[test]
+    fn exec_carrier_label_overrides_derived_display() {
+        let mut carrier = podman();
+        assert_eq!(carrier.display(), "exec:podman exec dev");
+        carrier.label = Some("podman:dev".into());
+        assert_eq!(ExecTransport::new(carrier).display(), "podman:dev");
+    }
+
     #[test]
     fn build_command_pipes_stdio_and_targets_kubectl() {
         // We can't introspect a tokio Command's program directly, but we can
@@ -0,0 +1,747 @@
+//! Reader and checker for Hunspell `.aff` / `.dic` dictionaries.
+//!
+//! Covers what word lookup and suggestions need:
+//!
+//! - `SET` encodings UTF-8 and ISO8859-1 (other 8-bit sets are read as
+//!   ISO8859-1),
+//! - every `FLAG` type (single character, `long`, `num`, `UTF-8`) and `AF`
+//!   flag aliases,
+//! - `PFX` / `SFX` classes with their conditions, cross products, and one
+//!   level of continuation classes (twofold suffixes),
+//! - `FORBIDDENWORD`, `NEEDAFFIX`, `ONLYINCOMPOUND`, `NOSUGGEST`, `IGNORE`,
+//!   `TRY` and `REP`.
+//!
+//! Compounding (`COMPOUND*`) and conversion tables (`ICONV` / `OCONV`) are
+//! not implemented, so words a dictionary only builds from compound parts
+//! are reported as misspelled.
+
+use std::collections::{HashMap, HashSet};
+use std::io;
+use std::path::Path;
+
+type Flag = u32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FlagMode {
+    /// One character per flag (the default, and `FLAG UTF-8`).
+    Single,
+    /// Two characters per flag (`FLAG long`).
+    Long,
+    /// Comma-separated numbers (`FLAG num`).
+    Num,
+}
+
+impl FlagMode {
+    fn parse(self, flags: &str) -> Vec<Flag> {
+        match self {
+            FlagMode::Single => flags.chars().map(|c| c as Flag).collect(),
+            FlagMode::Long => {
+                let chars: Vec<char> = flags.chars().collect();
+                chars
+                    .chunks(2)
+                    .map(|pair| {
+                        let low = pair.get(1).map_or(0, |&c| c as Flag & 0xFFFF);
+                        ((pair[0] as Flag) << 16) | low
+                    })
+                    .collect()
+            }
+            FlagMode::Num => flags
+                .split(',')
+                .filter_map(|n| n.trim().parse().ok())
+                .collect(),
+        }
+    }
+}
+
+/// One position of an affix condition.
+#[derive(Debug, Clone)]
+enum CondChar {
+    Any,
+    Is(char),
+    OneOf(Vec<char>),
+    NoneOf(Vec<char>),
+}
+
+impl CondChar {
+    fn matches(&self, c: char) -> bool {
+        match self {
+            CondChar::Any => true,
+            CondChar::Is(expected) => c == *expected,
+            CondChar::OneOf(set) => set.contains(&c),
+            CondChar::NoneOf(set) => !set.contains(&c),
+        }
+    }
+}
+
+/// An affix condition: the characters the stem must start (prefixes) or
+/// end (suffixes) with, written as a tiny regex of literals, `.` and
+/// `[...]` / `[^...]` classes.
+#[derive(Debug, Clone, Default)]
+struct Condition(Vec<CondChar>);
+
+impl Condition {
+    fn parse(pattern: &str) -> Self {
+        let mut items = Vec::new();
+        let mut chars = pattern.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '.' => items.push(CondChar::Any),
+                '[' => {
+                    let mut set = Vec::new();
+                    let mut negated = false;
+                    for (i, c) in chars.by_ref().enumerate() {
+                        match c {
+                            ']' => break,
+                            '^' if i == 0 => negated = true,
+                            c => set.push(c),
+                        }
+                    }
+                    items.push(if negated {
+                        CondChar::NoneOf(set)
+                    } else {
+                        CondChar::OneOf(set)
+                    });
+                }
+                c => items.push(CondChar::Is(c)),
+            }
+        }
+        Self(items)
+    }
+
+    fn matches_start(&self, stem: &str) -> bool {
+        let mut chars = stem.chars();
+        self.0
+            .iter()
+            .all(|cond| chars.next().is_some_and(|c| cond.matches(c)))
+    }
+
+    fn matches_end(&self, stem: &str) -> bool {
+        let mut chars = stem.chars().rev();
+        self.0
+            .iter()
+            .rev()
+            .all(|cond| chars.next().is_some_and(|c| cond.matches(c)))
+    }
+}
+
+/// One rule of a `PFX` or `SFX` class, stored under the text it adds.
+#[derive(Debug, Clone)]
+struct Affix {
+    flag: Flag,
+    /// Text removed from the stem before the affix is added.
+    strip: String,
+    condition: Condition,
+    cross_product: bool,
+    /// Classes that may be applied on top of this affix.
+    continuation: Vec<Flag>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Casing {
+    Lower,
+    /// First letter upper case, the rest lower case.
+    Title,
+    Upper,
+    Mixed,
+}
+
+fn casing(word: &str) -> Casing {
+    let mut letters = word.chars().filter(|c| c.is_alphabetic());
+    let Some(first) = letters.next() else {
+        return Casing::Lower;
+    };
+    let (mut upper, mut lower) = (0usize, 0usize);
+    for c in letters {
+        if c.is_uppercase() {
+            upper += 1;
+        } else if c.is_lowercase() {
+            lower += 1;
+        }
+    }
+    match (first.is_uppercase(), upper, lower) {
+        (false, 0, _) => Casing::Lower,
+        (true, 0, _) => Casing::Title,
+        (true, _, 0) => Casing::Upper,
+        _ => Casing::Mixed,
+    }
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn lowercase_first(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Split a `.dic` line into the word and its flag string. A `\/` is a
+/// literal slash; whitespace ends the entry (morphological fields follow).
+fn split_dic_entry(line: &str) -> (String, &str) {
+    let mut word = String::new();
+    let mut chars = line.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' if chars.peek().is_some_and(|&(_, next)| next == '/') => {
+                word.push('/');
+                chars.next();
+            }
+            '/' => {
+                let rest = &line[i + 1..];
+                let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+                return (word, &rest[..end]);
+            }
+            c if c.is_whitespace() => break,
+            c => word.push(c),
+        }
+    }
+    (word, "")
+}
+
+/// Decode dictionary bytes: UTF-8, or one byte per character for the
+/// 8-bit encodings.
+fn decode(bytes: &[u8], utf8: bool) -> String {
+    let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+    if utf8 {
+        String::from_utf8_lossy(bytes).into_owned()
+    } else {
+        bytes.iter().map(|&b| b as char).collect()
+    }
+}
+
+/// Whether the `.aff` file declares `SET UTF-8`. The declaration itself is
+/// ASCII, so it can be found before the encoding is known.
+fn declares_utf8(aff: &[u8]) -> bool {
+    aff.split(|&b| b == b'\n').any(|line| {
+        let line = String::from_utf8_lossy(line);
+        let mut fields = line.split_whitespace();
+        fields.next() == Some("SET")
+            && fields
+                .next()
+                .is_some_and(|set| set.eq_ignore_ascii_case("UTF-8"))
+    })
+}
+
+/// A loaded Hunspell dictionary.
+#[derive(Debug, Default)]
+pub struct Dictionary {
+    /// Every stem with the flag sets of its homonyms.
+    words: HashMap<String, Vec<Vec<Flag>>>,
+    prefixes: HashMap<String, Vec<Affix>>,
+    suffixes: HashMap<String, Vec<Affix>>,
+    /// Whether any suffix has continuation classes; skips the twofold
+    /// lookup for dictionaries that don't use them.
+    twofold: bool,
+    try_chars: Vec<char>,
+    replacements: Vec<(String, String)>,
+    ignore: Vec<char>,
+    forbidden: Option<Flag>,
+    no_suggest: Option<Flag>,
+    need_affix: Option<Flag>,
+    only_in_compound: Option<Flag>,
+}
+
+impl Dictionary {
+    /// Read a dictionary from its `.aff` and `.dic` files.
+    pub fn load(aff_path: &Path, dic_path: &Path) -> io::Result<Self> {
+        let aff = std::fs::read(aff_path)?;
+        let dic = std::fs::read(dic_path)?;
+        let utf8 = declares_utf8(&aff);
+        Ok(Self::parse(&decode(&aff, utf8), &decode(&dic, utf8)))
+    }
+
+    /// Build a dictionary from the decoded contents of its two files.
+    /// Malformed lines are skipped.
+    pub fn parse(aff: &str, dic: &str) -> Self {
+        let mut dictionary = Self::default();
+        let mut mode = FlagMode::Single;
+        let mut aliases: Vec<Vec<Flag>> = Vec::new();
+        let mut seen_alias_header = false;
+        let mut seen_rep_header = false;
+        // Cross-product setting of each affix class, from its header line.
+        let mut classes: HashMap<(bool, Flag), bool> = HashMap::new();
+
+        for line in aff.lines() {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let Some((&keyword, args)) = fields.split_first() else {
+                continue;
+            };
+            let single_flag =
+                |mode: FlagMode| args.first().and_then(|f| mode.parse(f).first().copied());
+            match keyword {
+                "FLAG" => {
+                    mode = match args.first().copied() {
+                        Some("long") => FlagMode::Long,
+                        Some("num") => FlagMode::Num,
+                        _ => FlagMode::Single,
+                    }
+                }
+                "TRY" => {
+                    dictionary.try_chars = args.first().map_or(Vec::new(), |s| s.chars().collect())
+                }
+                "IGNORE" => {
+                    dictionary.ignore = args.first().map_or(Vec::new(), |s| s.chars().collect())
+                }
+                "FORBIDDENWORD" => dictionary.forbidden = single_flag(mode),
+                "NOSUGGEST" => dictionary.no_suggest = single_flag(mode),
+                "NEEDAFFIX" | "PSEUDOROOT" => dictionary.need_affix = single_flag(mode),
+                "ONLYINCOMPOUND" => dictionary.only_in_compound = single_flag(mode),
+                "AF" => {
+                    // The first `AF` line only carries the alias count.
+                    if seen_alias_header {
+                        aliases.push(args.first().map_or(Vec::new(), |f| mode.parse(f)));
+                    }
+                    seen_alias_header = true;
+                }
+                "REP" => {
+                    if seen_rep_header {
+                        if let [from, to, ..] = args {
+                            dictionary
+                                .replacements
+                                .push((from.replace('_', " "), to.replace('_', " ")));
+                        }
+                    }
+                    seen_rep_header = true;
+                }
+                "PFX" | "SFX" => {
+                    let is_prefix = keyword == "PFX";
+                    let Some(flag) = single_flag(mode) else {
+                        continue;
+                    };
+                    let Some(&cross_product) = classes.get(&(is_prefix, flag)) else {
+                        // Header: `SFX <flag> <Y|N> <count>`.
+                        classes.insert((is_prefix, flag), args.get(1) == Some(&"Y"));
+                        continue;
+                    };
+                    let [_, strip, add, rest @ ..] = args else {
+                        continue;
+                    };
+                    let (add, continuation) = match add.split_once('/') {
+                        Some((add, flags)) => (add, resolve_flags(flags, mode, &aliases)),
+                        None => (*add, Vec::new()),
+                    };
+                    let affix = Affix {
+                        flag,
+                        strip: if *strip == "0" {
+                            String::new()
+                        } else {
+                            dictionary.strip_ignored(strip)
+                        },
+                        condition: Condition::parse(rest.first().copied().unwrap_or(".")),
+                        cross_product,
+                        continuation,
+                    };
+                    let add = if add == "0" {
+                        String::new()
+                    } else {
+                        dictionary.strip_ignored(add)
+                    };
+                    dictionary.twofold |= !is_prefix && !affix.continuation.is_empty();
+                    let table = if is_prefix {
+                        &mut dictionary.prefixes
+                    } else {
+                        &mut dictionary.suffixes
+                    };
+                    table.entry(add).or_default().push(affix);
+                }
+                _ => {}
+            }
+        }
+
+        for (i, line) in dic.lines().enumerate() {
+            if i == 0 && line.trim().parse::<usize>().is_ok() {
+                continue;
+            }
+            let (word, flags) = split_dic_entry(line);
+            if word.is_empty() {
+                continue;
+            }
+            let word = dictionary.strip_ignored(&word);
+            let flags = resolve_flags(flags, mode, &aliases);
+            dictionary.words.entry(word).or_default().push(flags);
+        }
+        dictionary
+    }
+
+    /// Whether `word` is spelled correctly. A capitalized or all-caps word
+    /// is also accepted in the lower-case form the dictionary lists.
+    pub fn check(&self, word: &str) -> bool {
+        let word = self.strip_ignored(word);
+        if word.is_empty() || self.check_exact(&word) {
+            return true;
+        }
+        match casing(&word) {
+            Casing::Title => self.check_exact(&lowercase_first(&word)),
+            Casing::Upper => {
+                let lower = word.to_lowercase();
+                self.check_exact(&lower) || self.check_exact(&capitalize(&lower))
+            }
+            Casing::Lower | Casing::Mixed => false,
+        }
+    }
+
+    /// Up to `limit` corrections for `word`, best first: `REP` table
+    /// replacements, then words one edit away (characters tried in `TRY`
+    /// order), then splits into two words. Suggestions follow the casing
+    /// of `word`.
+    pub fn suggest(&self, word: &str, limit: usize) -> Vec<String> {
+        let word_casing = casing(word);
+        let base = match word_casing {
+            Casing::Title => lowercase_first(word),
+            Casing::Upper => word.to_lowercase(),
+            Casing::Lower | Casing::Mixed => word.to_string(),
+        };
+
+        let mut candidates = Vec::new();
+        if word_casing == Casing::Mixed {
+            candidates.push(word.to_lowercase());
+        }
+        for (from, to) in &self.replacements {
+            for (i, _) in base.match_indices(from.as_str()) {
+                candidates.push(format!("{}{}{}", &base[..i], to, &base[i + from.len()..]));
+            }
+        }
+        candidates.extend(self.edits(&base));
+
+        let mut seen = HashSet::new();
+        seen.insert(base.clone());
+        let mut found: Vec<String> = Vec::new();
+        for candidate in candidates {
+            if found.len() >= limit {
+                break;
+            }
+            if seen.insert(candidate.clone()) && self.suggestable(&candidate) {
+                found.push(candidate);
+            }
+        }
+
+        let boundaries: Vec<usize> = base.char_indices().map(|(i, _)| i).skip(1).collect();
+        for i in boundaries {
+            if found.len() >= limit {
+                break;
+            }
+            let (left, right) = base.split_at(i);
+            if self.suggestable(left) && self.suggestable(right) {
+                found.push(format!("{left} {right}"));
+            }
+        }
+
+        found
+            .into_iter()
+            .map(|s| match word_casing {
+                Casing::Title => capitalize(&s),
+                Casing::Upper => s.to_uppercase(),
+                Casing::Lower | Casing::Mixed => s,
+            })
+            .collect()
+    }
+
+    /// Every string one transposition, replacement, deletion or insertion
+    /// away from `word`.
+    fn edits(&self, word: &str) -> Vec<String> {
+        let chars: Vec<char> = word.chars().collect();
+        let alphabet: Vec<char> = if self.try_chars.is_empty() {
+            let mut letters: Vec<char> = ('a'..='z').collect();
+            letters.extend(chars.iter().filter(|c| !c.is_ascii_lowercase()));
+            letters
+        } else {
+            self.try_chars.clone()
+        };
+        let join = |cs: &[char]| cs.iter().collect::<String>();
+
+        let mut edits = Vec::new();
+        for i in 1..chars.len() {
+            let mut swapped = chars.clone();
+            swapped.swap(i - 1, i);
+            edits.push(join(&swapped));
+        }
+        for i in 0..chars.len() {
+            for &c in &alphabet {
+                if c != chars[i] {
+                    let mut replaced = chars.clone();
+                    replaced[i] = c;
+                    edits.push(join(&replaced));
+                }
+            }
+        }
+        for i in 0..chars.len() {
+            let mut deleted = chars.clone();
+            deleted.remove(i);
+            edits.push(join(&deleted));
+        }
+        for i in 0..=chars.len() {
+            for &c in &alphabet {
+                let mut inserted = chars.clone();
+                inserted.insert(i, c);
+                edits.push(join(&inserted));
+            }
+        }
+        edits
+    }
+
+    fn suggestable(&self, word: &str) -> bool {
+        if !self.check(word) {
+            return false;
+        }
+        let Some(no_suggest) = self.no_suggest else {
+            return true;
+        };
+        !self
+            .words
+            .get(word)
+            .is_some_and(|homonyms| homonyms.iter().all(|flags| flags.contains(&no_suggest)))
+    }
+
+    fn check_exact(&self, word: &str) -> bool {
+        if let Some(homonyms) = self.words.get(word) {
+            if homonyms.iter().any(|flags| has(flags, self.forbidden)) {
+                return false;
+            }
+            if homonyms
+                .iter()
+                .any(|flags| !has(flags, self.need_affix) && !has(flags, self.only_in_compound))
+            {
+                return true;
+            }
+        }
+        self.check_suffixed(word, None) || self.check_prefixed(word, None)
+    }
+
+    /// Whether `word` is a stem plus one suffix. With `outer`, the suffix
+    /// must allow `outer` as a continuation (the inner half of a twofold
+    /// suffix).
+    fn check_suffixed(&self, word: &str, outer: Option<&Affix>) -> bool {
+        let splits = word
+            .char_indices()
+            .map(|(i, _)| i)
+            .skip(1)
+            .chain(std::iter::once(word.len()));
+        for i in splits {
+            let (head, add) = word.split_at(i);
+            let Some(rules) = self.suffixes.get(add) else {
+                continue;
+            };
+            for rule in rules {
+                if outer.is_some_and(|outer| !rule.continuation.contains(&outer.flag)) {
+                    continue;
+                }
+                let stem = format!("{head}{}", rule.strip);
+                if !rule.condition.matches_end(&stem) {
+                    continue;
+                }
+                if self.root_has(&stem, &[rule.flag]) {
+                    return true;
+                }
+                if outer.is_none() {
+                    if self.twofold && self.check_suffixed(&stem, Some(rule)) {
+                        return true;
+                    }
+                    if rule.cross_product && self.check_prefixed(&stem, Some(rule)) {
+                        return true;
+                    }
+                }
+            }
+        }
+        false
+    }
+
+    /// Whether `word` is a stem plus one prefix. With `suffix`, the stem
+    /// must also carry that (already removed) suffix's class.
+    fn check_prefixed(&self, word: &str, suffix: Option<&Affix>) -> bool {
+        for (i, _) in word.char_indices() {
+            let (add, rest) = word.split_at(i);
+            let Some(rules) = self.prefixes.get(add) else {
+                continue;
+            };
+            for rule in rules {
+                if suffix.is_some() && !rule.cross_product {
+                    continue;
+                }
+                let stem = format!("{}{rest}", rule.strip);
+                if !rule.condition.matches_start(&stem) {
+                    continue;
+                }
+                let found = match suffix {
+                    Some(suffix) => self.root_has(&stem, &[rule.flag, suffix.flag]),
+                    None => self.root_has(&stem, &[rule.flag]),
+                };
+                if found {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    /// Whether `stem` is listed with all of `flags` (on one homonym) and is
+    /// not forbidden.
+    fn root_has(&self, stem: &str, flags: &[Flag]) -> bool {
+        self.words.get(stem).is_some_and(|homonyms| {
+            !homonyms.iter().any(|f| has(f, self.forbidden))
+                && homonyms
+                    .iter()
+                    .any(|f| flags.iter().all(|flag| f.contains(flag)))
+        })
+    }
+
+    fn strip_ignored(&self, word: &str) -> String {
+        if self.ignore.is_empty() {
+            word.to_string()
+        } else {
+            word.chars().filter(|c| !self.ignore.contains(c)).collect()
+        }
+    }
+}
+
+fn has(flags: &[Flag], flag: Option<Flag>) -> bool {
+    flag.is_some_and(|flag| flags.contains(&flag))
+}
+
+/// Parse a flag string, resolving an `AF` alias number when the dictionary
+/// defines aliases.
+fn resolve_flags(flags: &str, mode: FlagMode, aliases: &[Vec<Flag>]) -> Vec<Flag> {
+    if flags.is_empty() {
+        return Vec::new();
+    }
+    if aliases.is_empty() {
+        return mode.parse(flags);
+    }
+    flags
+        .parse::<usize>()
+        .ok()
+        .and_then(|n| aliases.get(n.checked_sub(1)?))
+        .cloned()
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AFF: &str = "\
+SET UTF-8
+TRY esianrtolcdugmphbyfvkwz
+FORBIDDENWORD !
+NOSUGGEST ?
+REP 1
+REP f ph
+
+PFX U Y 1
+PFX U 0 un .
+
+SFX S Y 2
+SFX S y ies [^aeiou]y
+SFX S 0 s [^y]
+
+SFX D N 1
+SFX D 0 ed .
+";
+
+    const DIC: &str = "\
+7
+happy/U
+fly/S
+cat/S
+lock/USD
+phone/S
+Paris
+catz/!
+";
+
+    fn dictionary() -> Dictionary {
+        Dictionary::parse(AFF, DIC)
+    }
+
+    #[test]
+    fn checks_stems_and_affixed_forms() {
+        let d = dictionary();
+        assert!(d.check("cat"));
+        assert!(d.check("cats"));
+        assert!(d.check("flies"));
+        assert!(!d.check("flys"));
+        assert!(d.check("unhappy"));
+        assert!(d.check("locked"));
+        assert!(!d.check("dog"));
+    }
+
+    #[test]
+    fn cross_product_needs_both_classes_to_allow_it() {
+        let d = dictionary();
+        // U and S both cross, so "unlocks" is fine; D does not.
+        assert!(d.check("unlocks"));
+        assert!(!d.check("unlocked"));
+    }
+
+    #[test]
+    fn case_variants_of_listed_words() {
+        let d = dictionary();
+        assert!(d.check("Cat"));
+        assert!(d.check("CATS"));
+        assert!(d.check("Paris"));
+        assert!(d.check("PARIS"));
+        assert!(!d.check("paris"));
+        assert!(!d.check("cAt"));
+    }
+
+    #[test]
+    fn forbidden_words_are_rejected() {
+        let d = dictionary();
+        assert!(!d.check("catz"));
+        assert!(!d.suggest("catzz", 10).contains(&"catz".to_string()));
+    }
+
+    #[test]
+    fn suggestions_cover_replacements_edits_and_splits() {
+        let d = dictionary();
+        assert_eq!(
+            d.suggest("fone", 3).first().map(String::as_str),
+            Some("phone")
+        );
+        assert!(d.suggest("cta", 5).contains(&"cat".to_string()));
+        assert!(d.suggest("catts", 5).contains(&"cats".to_string()));
+        assert!(d.suggest("catfly", 5).contains(&"cat fly".to_string()));
+        assert_eq!(d.suggest("Cta", 1), vec!["Cat".to_string()]);
+    }
+
+    #[test]
+    fn long_flags_aliases_and_twofold_suffixes() {
+        let aff = "\
+FLAG long
+AF 2
+AF AaBb
+AF Bb
+SFX Aa Y 1
+SFX Aa 0 ness/2 .
+SFX Bb Y 1
+SFX Bb 0 es .
+";
+        let d = Dictionary::parse(aff, "2\nkind/1\nsad/2\n");
+        assert!(d.check("kindness"));
+        assert!(d.check("kindnesses"));
+        assert!(d.check("kindes"));
+        assert!(!d.check("sadness"));
+    }
+
+    #[test]
+    fn reads_latin1_dictionaries() {
+        let aff = b"SET ISO8859-1\nTRY e\n";
+        assert!(!declares_utf8(aff));
+        let dic = decode(b"1\ncaf\xe9\n", false);
+        let d = Dictionary::parse(&decode(aff, false), &dic);
+        assert!(d.check("café"));
+    }
+
+    #[test]
+    fn dic_entries_keep_escaped_slashes_and_drop_morphology() {
+        assert_eq!(split_dic_entry("and\\/or/S"), ("and/or".to_string(), "S"));
+        assert_eq!(split_dic_entry("cat/S\tpo:noun"), ("cat".to_string(), "S"));
+        assert_eq!(split_dic_entry("word po:noun"), ("word".to_string(), ""));
+    }
+}
@@ -0,0 +1,238 @@
+//! Spell checking for comments, strings and prose.
+//!
+//! [`Dictionary`] reads Hunspell dictionaries. This module adds what the
+//! editor needs around it: finding a dictionary in the config directory,
+//! splitting text into the words worth checking, and the per-project word
+//! list kept in `.fresh/words.txt`.
+
+mod hunspell;
+
+use std::collections::HashSet;
+use std::io::{self, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+pub use hunspell::Dictionary;
+
+/// Load `<language>.aff` / `<language>.dic` from `dir`. Returns `None`,
+/// and logs why, when the files are missing or unreadable.
+pub fn load_dictionary(dir: &Path, language: &str) -> Option<Dictionary> {
+    let aff = dir.join(format!("{language}.aff"));
+    let dic = dir.join(format!("{language}.dic"));
+    if !aff.is_file() || !dic.is_file() {
+        tracing::debug!(
+            "No {} spell-check dictionary in {}",
+            language,
+            dir.display()
+        );
+        return None;
+    }
+    let start = std::time::Instant::now();
+    match Dictionary::load(&aff, &dic) {
+        Ok(dictionary) => {
+            tracing::info!(
+                "Loaded {} spell-check dictionary in {:?}",
+                language,
+                start.elapsed()
+            );
+            Some(dictionary)
+        }
+        Err(e) => {
+            tracing::warn!("Failed to load dictionary {}: {}", dic.display(), e);
+            None
+        }
+    }
+}
+
+/// Words accepted in one project on top of the dictionary, one per line in
+/// `<project>/.fresh/words.txt`.
+#[derive(Debug, Default)]
+pub struct ProjectWordList {
+    path: PathBuf,
+    words: HashSet<String>,
+}
+
+impl ProjectWordList {
+    /// Read the word list of the project rooted at `root`. A missing file
+    /// is an empty list.
+    pub fn load(root: &Path) -> Self {
+        let path = root.join(".fresh").join("words.txt");
+        let words = std::fs::read_to_string(&path)
+            .map(|text| {
+                text.lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self { path, words }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        self.words.contains(word)
+    }
+
+    /// Add `word` to the list and append it to the file.
+    pub fn add(&mut self, word: &str) -> io::Result<()> {
+        if self.words.contains(word) {
+            return Ok(());
+        }
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{word}")?;
+        self.words.insert(word.to_string());
+        Ok(())
+    }
+}
+
+/// Whether `word` is correct for the dictionary or the project word list.
+pub fn is_correct(dictionary: &Dictionary, project_words: &ProjectWordList, word: &str) -> bool {
+    project_words.contains(word) || dictionary.check(word)
+}
+
+/// The words of `text` worth checking, as byte ranges into it.
+///
+/// Runs of letters are split at camelCase boundaries, so identifiers
+/// mentioned in comments are checked part by part. Skipped: anything
+/// containing digits or underscores, all-caps words (acronyms), single
+/// letters, the character after a backslash (string escapes), and
+/// whitespace-separated chunks that look like URLs or e-mail addresses.
+pub fn checkable_words(text: &str) -> Vec<Range<usize>> {
+    let mut words = Vec::new();
+    let mut chunk_start = None;
+    for (i, c) in text
+        .char_indices()
+        .chain(std::iter::once((text.len(), ' ')))
+    {
+        match (c.is_whitespace(), chunk_start) {
+            (false, None) => chunk_start = Some(i),
+            (true, Some(start)) => {
+                chunk_start = None;
+                let chunk = &text[start..i];
+                if !(chunk.contains("://") || chunk.contains('@')) {
+                    tokens(chunk, start, &mut words);
+                }
+            }
+            _ => {}
+        }
+    }
+    words
+}
+
+fn is_apostrophe(c: char) -> bool {
+    c == '\'' || c == '\u{2019}'
+}
+
+/// Push the checkable words of one whitespace-free chunk.
+fn tokens(chunk: &str, offset: usize, words: &mut Vec<Range<usize>>) {
+    let mut token_start = None;
+    let mut escaped = false;
+    for (i, c) in chunk
+        .char_indices()
+        .chain(std::iter::once((chunk.len(), ' ')))
+    {
+        let in_token = !escaped && (c.is_alphanumeric() || c == '_' || is_apostrophe(c));
+        escaped = !escaped && c == '\\';
+        match (in_token, token_start) {
+            (true, None) => token_start = Some(i),
+            (false, Some(start)) => {
+                token_start = None;
+                let token = &chunk[start..i];
+                if token.chars().any(|c| c.is_numeric() || c == '_') {
+                    continue;
+                }
+                let trimmed = token.trim_start_matches(is_apostrophe);
+                let start = start + token.len() - trimmed.len();
+                let trimmed = trimmed.trim_end_matches(is_apostrophe);
+                for part in camel_case_parts(trimmed) {
+                    let word = &trimmed[part.clone()];
+                    let letters = word.chars().filter(|c| c.is_alphabetic()).count();
+                    if letters < 2 || !word.chars().any(|c| c.is_lowercase()) {
+                        continue;
+                    }
+                    words.push(offset + start + part.start..offset + start + part.end);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Split `fooBar` into `foo` / `Bar` and `HTTPServer` into `HTTP` /
+/// `Server`, as byte ranges.
+fn camel_case_parts(word: &str) -> Vec<Range<usize>> {
+    let chars: Vec<(usize, char)> = word.char_indices().collect();
+    let mut parts = Vec::new();
+    let mut start = 0;
+    for k in 1..chars.len() {
+        let (i, c) = chars[k];
+        let prev = chars[k - 1].1;
+        let next_is_lower = chars.get(k + 1).is_some_and(|&(_, n)| n.is_lowercase());
+        let boundary =
+            c.is_uppercase() && (prev.is_lowercase() || (prev.is_uppercase() && next_is_lower));
+        if boundary {
+            parts.push(start..i);
+            start = i;
+        }
+    }
+    parts.push(start..word.len());
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(text: &str) -> Vec<&str> {
+        checkable_words(text)
+            .into_iter()
+            .map(|r| &text[r])
+            .collect()
+    }
+
+    #[test]
+    fn splits_prose_into_words() {
+        assert_eq!(
+            words("Don't  recieve 'quoted' text."),
+            vec!["Don't", "recieve", "quoted", "text"]
+        );
+    }
+
+    #[test]
+    fn splits_identifiers_and_skips_code_like_tokens() {
+        assert_eq!(
+            words("parseHtml HTTPServer foo_bar x86 LSP a"),
+            vec!["parse", "Html", "Server"]
+        );
+    }
+
+    #[test]
+    fn skips_escapes_urls_and_addresses() {
+        assert_eq!(
+            words("\\tindent https://exmaple.com mail@exmaple.com done"),
+            vec!["indent", "done"]
+        );
+    }
+
+    #[test]
+    fn project_word_list_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut list = ProjectWordList::load(dir.path());
+        assert!(!list.contains("fresh"));
+        list.add("fresh").unwrap();
+        list.add("fresh").unwrap();
+        let reloaded = ProjectWordList::load(dir.path());
+        assert!(reloaded.contains("fresh"));
+        assert_eq!(std::fs::read_to_string(reloaded.path()).unwrap(), "fresh\n");
+    }
+}
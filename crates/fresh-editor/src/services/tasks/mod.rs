@@ -0,0 +1,223 @@
+//! Tasks: project commands (builds, tests, linters) run from the editor.
+//!
+//! Tasks come from the `tasks` config list — usually the project's
+//! `.fresh/config.json` — and from `.vscode/tasks.json` when the workspace
+//! has one. A task runs either in a terminal tab or in the background;
+//! when it exits, its output goes through the task's problem matchers and
+//! the problems become diagnostics (see `app/task_actions.rs`).
+//!
+//! # Module Structure
+//!
+//! - **`problem_matcher`**: built-in matchers and VS Code-style multi-line
+//!   matching of task output into [`problem_matcher::TaskProblem`]s.
+
+pub mod problem_matcher;
+
+use std::path::PathBuf;
+
+use serde_json::Value;
+
+use crate::model::event::BufferId;
+use crate::services::terminal::TerminalId;
+use crate::types::{ProblemMatcherConfig, TaskConfig, TaskRunIn};
+
+/// Workspace-relative path of the VS Code task file.
+pub const VSCODE_TASKS_PATH: &str = ".vscode/tasks.json";
+
+/// A task that has been started and has not reported back yet.
+#[derive(Debug, Clone)]
+pub struct RunningTask {
+    pub task: TaskConfig,
+    /// Directory the command runs in; relative file names in its output
+    /// are resolved against it.
+    pub cwd: PathBuf,
+    /// The terminal the task runs in and its buffer, for terminal tasks.
+    pub terminal: Option<(TerminalId, BufferId)>,
+}
+
+/// Diagnostics "server" name under which a task's problems are stored, so
+/// each run replaces the previous run's problems and nothing else.
+pub fn diagnostic_source(label: &str) -> String {
+    format!("task:{label}")
+}
+
+/// The argv that runs `command_line` through the platform shell.
+pub fn shell_argv(command_line: &str) -> Vec<String> {
+    if cfg!(windows) {
+        vec![
+            "cmd".to_string(),
+            "/C".to_string(),
+            command_line.to_string(),
+        ]
+    } else {
+        vec!["sh".to_string(), "-c".to_string(), command_line.to_string()]
+    }
+}
+
+/// The configured tasks followed by the imported ones whose labels are not
+/// already taken.
+pub fn collect_tasks(configured: &[TaskConfig], imported: Vec<TaskConfig>) -> Vec<TaskConfig> {
+    let mut tasks = configured.to_vec();
+    for task in imported {
+        if !tasks.iter().any(|t| t.label == task.label) {
+            tasks.push(task);
+        }
+    }
+    tasks
+}
+
+/// Parse a `.vscode/tasks.json` (JSON with comments) into tasks.
+///
+/// Shell, process and npm tasks are imported; `presentation.reveal:
+/// "never"` runs a task in the background. Fields the editor has no use
+/// for (`group`, `dependsOn`, ...) are ignored.
+pub fn parse_vscode_tasks(contents: &str) -> Result<Vec<TaskConfig>, String> {
+    let root = crate::config::parse_config_jsonc(contents).map_err(|e| e.to_string())?;
+    let Some(entries) = root.get("tasks").and_then(Value::as_array) else {
+        return Ok(Vec::new());
+    };
+    Ok(entries.iter().filter_map(parse_vscode_task).collect())
+}
+
+fn parse_vscode_task(entry: &Value) -> Option<TaskConfig> {
+    let text = |value: &Value| match value {
+        Value::String(s) => Some(s.clone()),
+        // Quoted arguments: `{ "value": "...", "quoting": "escape" }`.
+        Value::Object(o) => o.get("value").and_then(Value::as_str).map(str::to_string),
+        _ => None,
+    };
+
+    let command = match entry.get("type").and_then(Value::as_str) {
+        Some("npm") => format!("npm run {}", entry.get("script")?.as_str()?),
+        _ => entry.get("command").and_then(text)?,
+    };
+    let label = entry
+        .get("label")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .unwrap_or_else(|| command.clone());
+    let args = entry
+        .get("args")
+        .and_then(Value::as_array)
+        .map(|args| args.iter().filter_map(text).collect())
+        .unwrap_or_default();
+    let cwd = entry
+        .pointer("/options/cwd")
+        .and_then(Value::as_str)
+        .map(str::to_string);
+    let run_in = match entry
+        .pointer("/presentation/reveal")
+        .and_then(Value::as_str)
+    {
+        Some("never") => TaskRunIn::Background,
+        _ => TaskRunIn::Terminal,
+    };
+    let problem_matcher = match entry.get("problemMatcher") {
+        Some(Value::Array(items)) => items.iter().filter_map(parse_vscode_matcher).collect(),
+        Some(item) => parse_vscode_matcher(item).into_iter().collect(),
+        None => Vec::new(),
+    };
+
+    Some(TaskConfig {
+        label,
+        command,
+        args,
+        cwd,
+        run_in,
+        problem_matcher,
+    })
+}
+
+fn parse_vscode_matcher(value: &Value) -> Option<ProblemMatcherConfig> {
+    match serde_json::from_value(value.clone()) {
+        Ok(matcher) => Some(matcher),
+        Err(e) => {
+            tracing::warn!("Ignoring problem matcher in {}: {}", VSCODE_TASKS_PATH, e);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn vscode_tasks_are_imported() {
+        let contents = r#"{
+            // Comments and trailing commas are allowed in tasks.json.
+            "version": "2.0.0",
+            "tasks": [
+                {
+                    "label": "build",
+                    "type": "shell",
+                    "command": "cargo",
+                    "args": ["build", { "value": "--features=a b", "quoting": "strong" }],
+                    "options": { "cwd": "${workspaceFolder}/crate" },
+                    "problemMatcher": ["$rustc"],
+                    "group": "build",
+                },
+                {
+                    "label": "lint",
+                    "type": "npm",
+                    "script": "lint",
+                    "presentation": { "reveal": "never" },
+                    "problemMatcher": {
+                        "owner": "eslint",
+                        "fileLocation": ["relative", "${workspaceFolder}"],
+                        "pattern": { "regexp": "^(.*):(\\d+): (.*)$", "file": 1, "line": 2, "message": 3 }
+                    }
+                },
+                { "type": "shell" }
+            ]
+        }"#;
+        let tasks = parse_vscode_tasks(contents).unwrap();
+        assert_eq!(tasks.len(), 2);
+
+        let build = &tasks[0];
+        assert_eq!(build.label, "build");
+        assert_eq!(build.run_in, TaskRunIn::Terminal);
+        assert_eq!(
+            build.command_line(Path::new("/ws"), None),
+            "cargo build '--features=a b'"
+        );
+        assert_eq!(build.working_dir(Path::new("/ws")), Path::new("/ws/crate"));
+        assert!(matches!(
+            build.problem_matcher.as_slice(),
+            [ProblemMatcherConfig::Named(name)] if name == "$rustc"
+        ));
+
+        let lint = &tasks[1];
+        assert_eq!(lint.command, "npm run lint");
+        assert_eq!(lint.run_in, TaskRunIn::Background);
+        match lint.problem_matcher.as_slice() {
+            [ProblemMatcherConfig::Inline(matcher)] => {
+                assert_eq!(matcher.pattern.len(), 1);
+                assert_eq!(matcher.pattern[0].message, Some(3));
+            }
+            other => panic!("expected one inline matcher, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn configured_tasks_win_label_collisions() {
+        let task = |label: &str, command: &str| TaskConfig {
+            label: label.to_string(),
+            command: command.to_string(),
+            ..Default::default()
+        };
+        let tasks = collect_tasks(
+            &[task("test", "cargo nextest run")],
+            vec![task("test", "cargo test"), task("build", "cargo build")],
+        );
+        let summary: Vec<_> = tasks
+            .iter()
+            .map(|t| (t.label.as_str(), t.command.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![("test", "cargo nextest run"), ("build", "cargo build")]
+        );
+    }
+}
@@ -0,0 +1,490 @@
+//! Problem matchers: regular expressions that find compiler and test
+//! failures in task output.
+//!
+//! Matching follows VS Code's rules. A matcher has one pattern per line of
+//! a problem; consecutive output lines must match the patterns in order, and
+//! a line that breaks the sequence starts over at the first pattern. When the
+//! last pattern has `loop` set it keeps matching, each line reporting one
+//! more problem with the fields captured by the earlier patterns.
+
+use std::path::{Path, PathBuf};
+
+use lsp_types::{Diagnostic, DiagnosticSeverity, NumberOrString, Position, Range};
+use regex::{Captures, Regex};
+
+use crate::types::{FileLocation, ProblemMatcher, ProblemMatcherConfig, ProblemPattern};
+
+/// Names of the built-in matchers.
+pub const BUILTIN_MATCHERS: &[&str] = &["$rustc", "$tsc", "$gcc", "$pytest"];
+
+/// One problem found in task output.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskProblem {
+    /// File the problem is in, resolved against the task's working directory.
+    pub path: PathBuf,
+    /// The problem as a diagnostic (0-based positions).
+    pub diagnostic: Diagnostic,
+}
+
+/// A built-in matcher by name (`$rustc`, `$tsc`, `$gcc`, `$pytest`).
+pub fn builtin(name: &str) -> Option<ProblemMatcher> {
+    let pattern = |regexp: &str| ProblemPattern {
+        regexp: regexp.to_string(),
+        ..Default::default()
+    };
+    let matcher = match name {
+        // error[E0308]: mismatched types
+        //   --> src/main.rs:4:18
+        "$rustc" => ProblemMatcher {
+            source: Some("rustc".to_string()),
+            pattern: vec![
+                ProblemPattern {
+                    severity: Some(1),
+                    code: Some(2),
+                    message: Some(3),
+                    ..pattern(r"^(warning|error)(?:\[(\w+)\])?: (.*)$")
+                },
+                ProblemPattern {
+                    file: Some(1),
+                    line: Some(2),
+                    column: Some(3),
+                    ..pattern(r"^\s*--> (.+):(\d+):(\d+)$")
+                },
+            ],
+            ..Default::default()
+        },
+        // src/app.ts(3,7): error TS2322: Type 'string' is not ...
+        // src/app.ts:3:7 - error TS2322: Type 'string' is not ...
+        "$tsc" => ProblemMatcher {
+            source: Some("tsc".to_string()),
+            pattern: vec![ProblemPattern {
+                file: Some(1),
+                line: Some(2),
+                column: Some(3),
+                severity: Some(4),
+                code: Some(5),
+                message: Some(6),
+                ..pattern(
+                    r"^([^\s].*)[(:](\d+)[,:](\d+)(?:\):\s+|\s+-\s+)(error|warning|info)\s+(TS\d+)\s*:\s*(.*)$",
+                )
+            }],
+            ..Default::default()
+        },
+        // main.c:5:3: error: 'x' undeclared (first use in this function)
+        "$gcc" => ProblemMatcher {
+            source: Some("gcc".to_string()),
+            pattern: vec![ProblemPattern {
+                file: Some(1),
+                line: Some(2),
+                column: Some(3),
+                severity: Some(4),
+                message: Some(5),
+                ..pattern(r"^(.*?):(\d+):(\d+):\s+(?:fatal\s+)?(warning|error|note):\s+(.*)$")
+            }],
+            ..Default::default()
+        },
+        // tests/test_app.py:12: AssertionError
+        "$pytest" => ProblemMatcher {
+            source: Some("pytest".to_string()),
+            severity: Some("error".to_string()),
+            pattern: vec![ProblemPattern {
+                file: Some(1),
+                line: Some(2),
+                message: Some(3),
+                ..pattern(r"^([^\s:][^:]*\.py):(\d+): (.*)$")
+            }],
+            ..Default::default()
+        },
+        _ => return None,
+    };
+    Some(matcher)
+}
+
+/// A problem matcher with its patterns compiled.
+#[derive(Debug)]
+pub struct CompiledMatcher {
+    source: Option<String>,
+    file_location: FileLocation,
+    severity: DiagnosticSeverity,
+    patterns: Vec<(Regex, ProblemPattern)>,
+}
+
+/// Fields captured so far for the problem being matched.
+#[derive(Debug, Clone, Default)]
+struct Captured {
+    file: Option<String>,
+    line: Option<u32>,
+    column: Option<u32>,
+    end_line: Option<u32>,
+    end_column: Option<u32>,
+    severity: Option<String>,
+    code: Option<String>,
+    message: Option<String>,
+}
+
+impl Captured {
+    fn fill(&mut self, pattern: &ProblemPattern, caps: &Captures<'_>) {
+        let text = |group: Option<usize>| {
+            group
+                .and_then(|g| caps.get(g))
+                .map(|m| m.as_str().trim().to_string())
+                .filter(|s| !s.is_empty())
+        };
+        let number = |group: Option<usize>| text(group).and_then(|s| s.parse::<u32>().ok());
+        if let Some(file) = text(pattern.file) {
+            self.file = Some(file);
+        }
+        if let Some(line) = number(pattern.line) {
+            self.line = Some(line);
+        }
+        if let Some(column) = number(pattern.column) {
+            self.column = Some(column);
+        }
+        if let Some(end_line) = number(pattern.end_line) {
+            self.end_line = Some(end_line);
+        }
+        if let Some(end_column) = number(pattern.end_column) {
+            self.end_column = Some(end_column);
+        }
+        if let Some(severity) = text(pattern.severity) {
+            self.severity = Some(severity);
+        }
+        if let Some(code) = text(pattern.code) {
+            self.code = Some(code);
+        }
+        if let Some(message) = text(pattern.message) {
+            self.message = Some(message);
+        }
+    }
+}
+
+impl CompiledMatcher {
+    /// Resolve a configured matcher (built-in name or inline definition) and
+    /// compile its patterns.
+    pub fn new(config: &ProblemMatcherConfig) -> Result<Self, String> {
+        let matcher = match config {
+            ProblemMatcherConfig::Named(name) => builtin(name).ok_or_else(|| {
+                format!(
+                    "unknown problem matcher '{name}' (built-in: {})",
+                    BUILTIN_MATCHERS.join(", ")
+                )
+            })?,
+            ProblemMatcherConfig::Inline(matcher) => matcher.clone(),
+        };
+        if matcher.pattern.is_empty() {
+            return Err("problem matcher has no pattern".to_string());
+        }
+        let patterns = matcher
+            .pattern
+            .iter()
+            .map(|pattern| {
+                Regex::new(&pattern.regexp)
+                    .map(|re| (re, pattern.clone()))
+                    .map_err(|e| format!("invalid problem pattern '{}': {e}", pattern.regexp))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            source: matcher.source,
+            file_location: matcher.file_location,
+            severity: matcher
+                .severity
+                .as_deref()
+                .and_then(parse_severity)
+                .unwrap_or(DiagnosticSeverity::ERROR),
+            patterns,
+        })
+    }
+
+    /// Find every problem in `output`. Relative file names are resolved
+    /// against `cwd`; `default_source` labels diagnostics when the matcher
+    /// has no source of its own.
+    pub fn match_output(&self, output: &str, cwd: &Path, default_source: &str) -> Vec<TaskProblem> {
+        let last = self.patterns.len() - 1;
+        let mut problems = Vec::new();
+        // Index of the pattern the next line must match.
+        let mut next = 0;
+        let mut captured = Captured::default();
+        // Fields captured before the last pattern, reused by `loop`.
+        let mut prefix = Captured::default();
+
+        for line in output.lines() {
+            let line = line.trim_end_matches('\r');
+            loop {
+                let (re, pattern) = &self.patterns[next];
+                if let Some(caps) = re.captures(line) {
+                    if next == last {
+                        let mut fields = prefix.clone();
+                        fields.fill(pattern, &caps);
+                        problems.extend(self.problem(fields, cwd, default_source));
+                        if !pattern.repeat {
+                            next = 0;
+                            prefix = Captured::default();
+                        }
+                    } else {
+                        captured.fill(pattern, &caps);
+                        next += 1;
+                        if next == last {
+                            prefix = std::mem::take(&mut captured);
+                        }
+                    }
+                    break;
+                }
+                if next == 0 {
+                    break;
+                }
+                // The sequence broke (or a loop ended): try this line as
+                // the start of a new problem.
+                next = 0;
+                captured = Captured::default();
+                prefix = Captured::default();
+            }
+        }
+        problems
+    }
+
+    fn problem(&self, fields: Captured, cwd: &Path, default_source: &str) -> Option<TaskProblem> {
+        let file = fields.file?;
+        let message = fields.message?;
+        let path = match self.file_location {
+            FileLocation::Absolute => PathBuf::from(&file),
+            FileLocation::Relative => cwd.join(&file),
+            FileLocation::AutoDetect => {
+                let path = PathBuf::from(&file);
+                if path.is_absolute() {
+                    path
+                } else {
+                    cwd.join(path)
+                }
+            }
+        };
+        let line = fields.line.unwrap_or(1).saturating_sub(1);
+        let character = fields.column.unwrap_or(1).saturating_sub(1);
+        let end_line = fields.end_line.map_or(line, |l| l.saturating_sub(1));
+        let end_character = fields
+            .end_column
+            .map_or(character + 1, |c| c.saturating_sub(1));
+        let severity = fields
+            .severity
+            .as_deref()
+            .and_then(parse_severity)
+            .unwrap_or(self.severity);
+        Some(TaskProblem {
+            path,
+            diagnostic: Diagnostic {
+                range: Range::new(
+                    Position::new(line, character),
+                    Position::new(end_line, end_character),
+                ),
+                severity: Some(severity),
+                code: fields.code.map(NumberOrString::String),
+                source: Some(
+                    self.source
+                        .clone()
+                        .unwrap_or_else(|| default_source.to_string()),
+                ),
+                message,
+                ..Default::default()
+            },
+        })
+    }
+}
+
+/// Map severity text from tool output ("error", "Warning", "note", ...).
+fn parse_severity(text: &str) -> Option<DiagnosticSeverity> {
+    let text = text.to_ascii_lowercase();
+    if text.starts_with("err") || text.starts_with("fatal") {
+        Some(DiagnosticSeverity::ERROR)
+    } else if text.starts_with("warn") {
+        Some(DiagnosticSeverity::WARNING)
+    } else if text.starts_with("info") || text.starts_with("note") {
+        Some(DiagnosticSeverity::INFORMATION)
+    } else if text.starts_with("hint") {
+        Some(DiagnosticSeverity::HINT)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(name: &str, output: &str) -> Vec<TaskProblem> {
+        CompiledMatcher::new(&ProblemMatcherConfig::Named(name.to_string()))
+            .unwrap()
+            .match_output(output, Path::new("/project"), "task")
+    }
+
+    fn start(problem: &TaskProblem) -> (u32, u32) {
+        let start = problem.diagnostic.range.start;
+        (start.line, start.character)
+    }
+
+    #[test]
+    fn rustc_matches_across_two_lines() {
+        let output = "\
+   Compiling demo v0.1.0 (/project)
+error[E0308]: mismatched types
+ --> src/main.rs:4:18
+  |
+4 |     let x: u32 = \"a\";
+  |                  ^^^ expected `u32`, found `&str`
+
+warning: unused variable: `y`
+  --> src/lib.rs:10:9
+warning: `demo` (bin \"demo\") generated 1 warning
+error: could not compile `demo`
+";
+        let problems = run("$rustc", output);
+        assert_eq!(problems.len(), 2);
+
+        assert_eq!(problems[0].path, Path::new("/project/src/main.rs"));
+        assert_eq!(start(&problems[0]), (3, 17));
+        assert_eq!(problems[0].diagnostic.message, "mismatched types");
+        assert_eq!(
+            problems[0].diagnostic.code,
+            Some(NumberOrString::String("E0308".to_string()))
+        );
+        assert_eq!(
+            problems[0].diagnostic.severity,
+            Some(DiagnosticSeverity::ERROR)
+        );
+        assert_eq!(problems[0].diagnostic.source.as_deref(), Some("rustc"));
+
+        assert_eq!(problems[1].path, Path::new("/project/src/lib.rs"));
+        assert_eq!(start(&problems[1]), (9, 8));
+        assert_eq!(
+            problems[1].diagnostic.severity,
+            Some(DiagnosticSeverity::WARNING)
+        );
+    }
+
+    #[test]
+    fn tsc_matches_both_output_styles() {
+        let output = "\
+src/app.ts(3,7): error TS2322: Type 'string' is not assignable to type 'number'.
+src/util.ts:12:1 - warning TS6133: 'x' is declared but its value is never read.
+";
+        let problems = run("$tsc", output);
+        assert_eq!(problems.len(), 2);
+        assert_eq!(problems[0].path, Path::new("/project/src/app.ts"));
+        assert_eq!(start(&problems[0]), (2, 6));
+        assert_eq!(
+            problems[0].diagnostic.code,
+            Some(NumberOrString::String("TS2322".to_string()))
+        );
+        assert_eq!(problems[1].path, Path::new("/project/src/util.ts"));
+        assert_eq!(
+            problems[1].diagnostic.severity,
+            Some(DiagnosticSeverity::WARNING)
+        );
+    }
+
+    #[test]
+    fn gcc_matches_errors_and_notes_with_absolute_paths() {
+        let output = "\
+/src/main.c: In function 'main':
+/src/main.c:5:3: error: 'x' undeclared (first use in this function)
+main.h:2:1: note: declared here
+";
+        let problems = run("$gcc", output);
+        assert_eq!(problems.len(), 2);
+        assert_eq!(problems[0].path, Path::new("/src/main.c"));
+        assert_eq!(start(&problems[0]), (4, 2));
+        assert_eq!(
+            problems[0].diagnostic.message,
+            "'x' undeclared (first use in this function)"
+        );
+        assert_eq!(problems[1].path, Path::new("/project/main.h"));
+        assert_eq!(
+            problems[1].diagnostic.severity,
+            Some(DiagnosticSeverity::INFORMATION)
+        );
+    }
+
+    #[test]
+    fn pytest_matches_failure_locations() {
+        let output = "\
+    def test_add():
+>       assert add(1, 1) == 3
+E       assert 2 == 3
+
+tests/test_math.py:12: AssertionError
+";
+        let problems = run("$pytest", output);
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].path, Path::new("/project/tests/test_math.py"));
+        assert_eq!(start(&problems[0]), (11, 0));
+        assert_eq!(problems[0].diagnostic.message, "AssertionError");
+        assert_eq!(
+            problems[0].diagnostic.severity,
+            Some(DiagnosticSeverity::ERROR)
+        );
+    }
+
+    #[test]
+    fn looping_pattern_reports_each_line_with_the_file_header() {
+        // eslint "stylish" style: a file name, then one line per problem.
+        let matcher: ProblemMatcher = serde_json::from_value(serde_json::json!({
+            "fileLocation": ["relative", "${workspaceFolder}"],
+            "pattern": [
+                { "regexp": "^([^\\s].*)$", "file": 1 },
+                {
+                    "regexp": "^\\s+(\\d+):(\\d+)\\s+(error|warning)\\s+(.*)$",
+                    "line": 1, "column": 2, "severity": 3, "message": 4,
+                    "loop": true
+                }
+            ]
+        }))
+        .unwrap();
+        assert_eq!(matcher.file_location, FileLocation::Relative);
+        let output = "\
+src/a.js
+  1:5  error    'x' is not defined
+  3:1  warning  Unexpected console statement
+src/b.js
+  7:2  error    Missing semicolon
+";
+        let problems = CompiledMatcher::new(&ProblemMatcherConfig::Inline(matcher))
+            .unwrap()
+            .match_output(output, Path::new("/project"), "lint");
+        let summary: Vec<_> = problems
+            .iter()
+            .map(|p| (p.path.clone(), start(p), p.diagnostic.message.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    PathBuf::from("/project/src/a.js"),
+                    (0, 4),
+                    "'x' is not defined"
+                ),
+                (
+                    PathBuf::from("/project/src/a.js"),
+                    (2, 0),
+                    "Unexpected console statement"
+                ),
+                (
+                    PathBuf::from("/project/src/b.js"),
+                    (6, 1),
+                    "Missing semicolon"
+                ),
+            ]
+        );
+        assert_eq!(problems[0].diagnostic.source.as_deref(), Some("lint"));
+    }
+
+    #[test]
+    fn unknown_matcher_and_bad_regex_are_errors() {
+        assert!(CompiledMatcher::new(&ProblemMatcherConfig::Named("$nope".to_string())).is_err());
+        let matcher = ProblemMatcher {
+            pattern: vec![ProblemPattern {
+                regexp: "(".to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        assert!(CompiledMatcher::new(&ProblemMatcherConfig::Inline(matcher)).is_err());
+    }
+}
@@ -4,7 +4,8 @@
 //! clicked / hovered, [`detect_link_at`] finds the surrounding file path and
 //! parses any trailing `:line`, `:line:col`, or `(line,col)` location suffix.
 //! This powers Ctrl+Click / Ctrl+hover "open path in Fresh" from the embedded
-//! terminal.
+//! terminal. [`detect_location_links`] scans a whole line instead, for the
+//! `path:line:col` links of ordinary editor buffers.
 //!
 //! The detector is purely textual and host-agnostic: it returns a candidate
 //! path string and optional location, plus the character range it occupies (so
@@ -146,6 +147,30 @@ pub fn detect_link_at(line: &str, col: usize) -> Option<DetectedLink> {
     })
 }
 
+/// Every path link in `line` that carries a location suffix, left to right.
+///
+/// Editor buffers are full of dotted and slashed words that are not file
+/// references (`self.name`, `and/or`), so unlike a Ctrl+Click in the terminal
+/// a bare path is not enough here: only `path:line[:col]` and
+/// `path(line[,col])` forms, as compilers and linters print them, count.
+pub fn detect_location_links(line: &str) -> Vec<DetectedLink> {
+    let mut links: Vec<DetectedLink> = Vec::new();
+    let mut prev_is_space = true;
+    for (col, c) in line.chars().enumerate() {
+        let token_start = prev_is_space && !c.is_whitespace();
+        prev_is_space = c.is_whitespace();
+        if !token_start {
+            continue;
+        }
+        if let Some(link) = detect_link_at(line, col) {
+            if link.line.is_some() && links.last().is_none_or(|last| last.range != link.range) {
+                links.push(link);
+            }
+        }
+    }
+    links
+}
+
 /// Parse a trailing `:line`, `:line:col` (optionally with a trailing `:`, as
 /// gcc/ripgrep emit), or `(line,col)` suffix from `token`.
 ///
@@ -442,6 +467,19 @@ mod tests {
         assert_eq!(&line[got.range], "src/main.rs:2");
     }
 
+    /// A whole-line scan keeps only paths with a location, each once.
+    #[test]
+    fn location_links_in_line() {
+        let line = "error in src/main.rs:12:5 (see docs/notes.md) and lib.rs(3,1)";
+        let links = detect_location_links(line);
+        let found: Vec<_> = links.iter().map(|l| &line[l.range.clone()]).collect();
+        assert_eq!(found, vec!["src/main.rs:12:5", "lib.rs(3,1)"]);
+        assert_eq!(links[0].line, Some(12));
+        assert_eq!(links[0].column, Some(5));
+        assert_eq!(links[1].path, "lib.rs");
+        assert!(detect_location_links("plain prose, and/or self.name").is_empty());
+    }
+
     /// The `name(line,col)` location form must NOT be reinterpreted as a
     /// parenthesised path (the inner `34,12` is numeric, not path-like).
     #[test]
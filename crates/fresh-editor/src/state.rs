@@ -1,3 +1,4 @@
+use crate::input::selection_range::LspSelectionRanges;
 use crate::model::buffer::{Buffer, LineNumber};
 use crate::model::cursor::{Cursor, Cursors};
 use crate::model::document_model::{
@@ -14,6 +15,7 @@ use crate::primitives::grammar::GrammarRegistry;
 use crate::primitives::highlight_engine::HighlightEngine;
 use crate::primitives::indent::IndentCalculator;
 use crate::primitives::reference_highlighter::ReferenceHighlighter;
+use crate::primitives::syntax_tree::SyntaxTreeCache;
 use crate::primitives::text_property::TextPropertyManager;
 use crate::view::bracket_highlight_overlay::BracketHighlightOverlay;
 use crate::view::conceal::ConcealManager;
@@ -25,6 +27,7 @@ use crate::view::popup::{
 };
 use crate::view::reference_highlight_overlay::ReferenceHighlightOverlay;
 use crate::view::soft_break::SoftBreakManager;
+use crate::view::sticky_scroll::StickyScopes;
 use crate::view::virtual_text::VirtualTextManager;
 use anyhow::Result;
 use ratatui::style::{Color, Style};
@@ -159,6 +162,12 @@ pub struct BufferSettings {
     /// `editor.highlight_occurrences` setting. Persisted in the per-file
     /// workspace state.
     pub highlight_occurrences_override: Option<bool>,
+
+    /// Settings resolved from `.editorconfig` when the file was opened. They
+    /// sit between the config and the explicit per-buffer overrides, and are
+    /// re-layered by [`apply_config`](Self::apply_config) so a config reload
+    /// doesn't undo them.
+    pub editorconfig: crate::services::editorconfig::EditorConfigSettings,
 }
 
 impl Default for BufferSettings {
@@ -177,6 +186,7 @@ impl Default for BufferSettings {
             word_characters: String::new(),
             indentation_guide: true,
             highlight_occurrences_override: None,
+            editorconfig: Default::default(),
         }
     }
 }
@@ -193,12 +203,16 @@ impl BufferSettings {
     /// Explicit per-buffer user overrides are preserved: `virtual_space`
     /// keeps following `virtual_space_override`, `use_tabs` follows
     /// `use_tabs_override` and `whitespace` follows `whitespace_override`
-    /// when one is set. (The indentation-guide and fold-indicator pins live
+    /// when one is set. `.editorconfig` values sit in between: they beat the
+    /// config, and lose to an override. (The indentation-guide and fold-indicator pins live
     /// on `BufferViewState` — per split — and are consulted at render time,
     /// so re-stamping the language gate below can't clear them.)
     pub fn apply_config(&mut self, resolved: &crate::config::BufferConfig) {
-        self.tab_size = resolved.tab_size;
-        self.use_tabs = self.use_tabs_override.unwrap_or(resolved.use_tabs);
+        self.tab_size = self.editorconfig.tab_size.unwrap_or(resolved.tab_size);
+        self.use_tabs = self
+            .use_tabs_override
+            .or(self.editorconfig.use_tabs)
+            .unwrap_or(resolved.use_tabs);
         self.auto_close = resolved.auto_close;
         self.auto_surround = resolved.auto_surround;
         self.virtual_space = self
@@ -375,6 +389,17 @@ pub struct EditorState {
     /// (issue #1571).
     pub folding_ranges: LspFoldRanges,
 
+    /// Scope sources for the sticky scroll header: the last LSP document
+    /// outline.
+    pub sticky_scopes: StickyScopes,
+
+    /// Tree-sitter parse of the whole buffer, shared by sticky scroll and
+    /// expand selection.
+    pub syntax_tree: SyntaxTreeCache,
+
+    /// Last LSP selection ranges, used by expand selection.
+    pub selection_ranges: LspSelectionRanges,
+
     /// The detected language ID for this buffer (e.g., "rust", "csharp", "text").
     /// Used for LSP config lookup and internal identification.
     pub language: String,
@@ -454,6 +479,30 @@ impl EditorState {
         self.buffer_settings.apply_config(&resolved);
     }
 
+    /// Write the buffer to its file, first converting it to the
+    /// `.editorconfig` `end_of_line` when one applies — so every save, not
+    /// just a new file's first, lands with the project's line endings.
+    pub fn save(&mut self) -> anyhow::Result<()> {
+        self.apply_editorconfig_line_ending();
+        self.buffer.save()
+    }
+
+    /// [`Self::save`] to an explicit path (Save As, plugin saves). The
+    /// caller re-resolves `buffer_settings.editorconfig` first when the
+    /// target differs from the file the settings were resolved for.
+    pub fn save_to_file(&mut self, path: &std::path::Path) -> anyhow::Result<()> {
+        self.apply_editorconfig_line_ending();
+        self.buffer.save_to_file(path)
+    }
+
+    fn apply_editorconfig_line_ending(&mut self) {
+        if let Some(line_ending) = self.buffer_settings.editorconfig.line_ending {
+            if self.buffer.line_ending() != line_ending {
+                self.buffer.set_line_ending(line_ending);
+            }
+        }
+    }
+
     /// Point this buffer's occurrence highlighting at the editor-wide default,
     /// unless the user pinned it with "Toggle Occurrence Highlight (Current
     /// Buffer)".
@@ -505,6 +554,9 @@ impl EditorState {
             bracket_highlight_overlay: BracketHighlightOverlay::new(),
             semantic_tokens: None,
             folding_ranges: LspFoldRanges::new(),
+            sticky_scopes: StickyScopes::default(),
+            syntax_tree: SyntaxTreeCache::default(),
+            selection_ranges: LspSelectionRanges::default(),
             language: "text".to_string(),
             display_name: "Text".to_string(),
             wrap_indices: crate::view::wrap_index::WrapIndexSet::default(),
@@ -813,7 +865,10 @@ impl EditorState {
             .adjust_for_insert(position, text.len());
 
         // Insert text into buffer
+        let version_before = self.buffer.version();
         self.buffer.insert(position, text);
+        self.syntax_tree
+            .notify_insert(&self.buffer, version_before, position, text);
 
         self.repair_wrap_indices(crate::view::wrap_index::EditDamage {
             start: position,
@@ -907,7 +962,10 @@ impl EditorState {
         self.scrollbar_markers.adjust_for_delete(range.start, len);
 
         // Delete from buffer
+        let version_before = self.buffer.version();
         self.buffer.delete(range.clone());
+        self.syntax_tree
+            .notify_delete(&self.buffer, version_before, range, deleted_text);
 
         self.repair_wrap_indices(crate::view::wrap_index::EditDamage {
             start: range.start,
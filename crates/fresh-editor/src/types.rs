@@ -74,6 +74,8 @@ pub mod context_keys {
     pub const SESSION_MODE: &str = "session_mode";
     pub const VERTICAL_SCROLLBAR: &str = "vertical_scrollbar";
     pub const HORIZONTAL_SCROLLBAR: &str = "horizontal_scrollbar";
+    pub const MINIMAP: &str = "minimap";
+    pub const STICKY_SCROLL: &str = "sticky_scroll";
     pub const SCROLL_SYNC: &str = "scroll_sync";
     pub const HAS_SAME_BUFFER_SPLITS: &str = "has_same_buffer_splits";
     pub const KEYMAP_DEFAULT: &str = "keymap_default";
@@ -180,6 +182,20 @@ pub enum LspFeature {
     SemanticTokens,
     /// Document highlight (exclusive)
     DocumentHighlight,
+    /// Call hierarchy: prepare, incoming and outgoing calls (exclusive)
+    CallHierarchy,
+    /// Type hierarchy: prepare, supertypes and subtypes (exclusive)
+    TypeHierarchy,
+    /// Code lenses and their resolution (exclusive)
+    CodeLens,
+    /// Document links: URLs and file references in the text (exclusive)
+    DocumentLink,
+    /// Linked editing ranges, e.g. matching HTML tag names (exclusive)
+    LinkedEditingRange,
+    /// Formatting while typing trigger characters (exclusive)
+    OnTypeFormatting,
+    /// Selection ranges for expand/shrink selection (exclusive)
+    SelectionRange,
 }
 
 impl LspFeature {
@@ -196,6 +212,20 @@ impl LspFeature {
                 | LspFeature::WorkspaceSymbols
         )
     }
+
+    /// The feature a raw request method belongs to, for requests that are
+    /// sent on behalf of plugins rather than through a typed editor path.
+    pub fn for_request_method(method: &str) -> Option<LspFeature> {
+        match method {
+            "textDocument/prepareCallHierarchy"
+            | "callHierarchy/incomingCalls"
+            | "callHierarchy/outgoingCalls" => Some(LspFeature::CallHierarchy),
+            "textDocument/prepareTypeHierarchy"
+            | "typeHierarchy/supertypes"
+            | "typeHierarchy/subtypes" => Some(LspFeature::TypeHierarchy),
+            _ => None,
+        }
+    }
 }
 
 /// Feature filter for an LSP server, controlling which features it handles.
@@ -470,6 +500,350 @@ impl LspServerConfig {
     }
 }
 
+/// Debug adapter configuration: how to spawn a Debug Adapter Protocol
+/// server that talks over stdio.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[schemars(extend("x-display-field" = "/command"))]
+pub struct DebugAdapterConfig {
+    /// Command to spawn the adapter (e.g., "lldb-dap", "debugpy-adapter").
+    #[serde(default)]
+    #[schemars(extend("x-order" = 1))]
+    pub command: String,
+
+    /// Arguments to pass to the adapter.
+    #[serde(default)]
+    #[schemars(extend("x-order" = 2))]
+    pub args: Vec<String>,
+
+    /// Environment variables to set for the adapter process.
+    /// These are added to (or override) the inherited parent environment.
+    #[serde(default)]
+    #[schemars(extend("x-section" = "Advanced", "x-order" = 10))]
+    pub env: HashMap<String, String>,
+
+    /// Process resource limits (memory and CPU)
+    #[serde(default)]
+    #[schemars(
+        default = "process_limits_schema_default",
+        extend("x-section" = "Advanced", "x-order" = 11)
+    )]
+    pub process_limits: ProcessLimits,
+}
+
+/// Whether a debug configuration starts the debuggee or connects to one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DebugRequest {
+    /// Start the program under the debugger (DAP `launch`)
+    #[default]
+    Launch,
+    /// Connect to an already running program (DAP `attach`)
+    Attach,
+}
+
+impl DebugRequest {
+    /// The DAP request command name.
+    pub fn command(self) -> &'static str {
+        match self {
+            Self::Launch => "launch",
+            Self::Attach => "attach",
+        }
+    }
+}
+
+/// A named launch/attach configuration, offered by "Debug: Start".
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[schemars(extend("x-display-field" = "/name"))]
+pub struct DebugConfiguration {
+    /// Name shown when picking a configuration.
+    #[serde(default)]
+    #[schemars(extend("x-order" = 1))]
+    pub name: String,
+
+    /// Key into `debug_adapters` naming the adapter to spawn.
+    #[serde(default)]
+    #[schemars(extend("x-order" = 2))]
+    pub adapter: String,
+
+    /// Whether to launch the program or attach to a running one.
+    #[serde(default)]
+    #[schemars(extend("x-order" = 3))]
+    pub request: DebugRequest,
+
+    /// Adapter-specific arguments of the launch/attach request (e.g.
+    /// `{"program": "${workspaceFolder}/target/debug/app"}`).
+    /// `${workspaceFolder}` and `${file}` are substituted in string values.
+    #[serde(default)]
+    #[schemars(extend("x-order" = 4))]
+    pub arguments: Option<serde_json::Value>,
+}
+
+impl DebugConfiguration {
+    /// The launch/attach arguments with `${workspaceFolder}` and `${file}`
+    /// substituted in every string value. `file` is the active buffer's
+    /// path, if any; the variable is left untouched without one.
+    pub fn resolved_arguments(
+        &self,
+        workspace_folder: &std::path::Path,
+        file: Option<&std::path::Path>,
+    ) -> serde_json::Value {
+        fn substitute(value: &mut serde_json::Value, vars: &[(&str, String)]) {
+            match value {
+                serde_json::Value::String(s) => {
+                    for (name, replacement) in vars {
+                        if s.contains(name) {
+                            *s = s.replace(name, replacement);
+                        }
+                    }
+                }
+                serde_json::Value::Array(items) => {
+                    items.iter_mut().for_each(|v| substitute(v, vars));
+                }
+                serde_json::Value::Object(map) => {
+                    map.values_mut().for_each(|v| substitute(v, vars));
+                }
+                _ => {}
+            }
+        }
+
+        let mut vars = vec![(
+            "${workspaceFolder}",
+            workspace_folder.to_string_lossy().into_owned(),
+        )];
+        if let Some(file) = file {
+            vars.push(("${file}", file.to_string_lossy().into_owned()));
+        }
+        let mut arguments = self
+            .arguments
+            .clone()
+            .unwrap_or_else(|| serde_json::Value::Object(Default::default()));
+        substitute(&mut arguments, &vars);
+        arguments
+    }
+}
+
+/// A command offered by "Run Task".
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[schemars(extend("x-display-field" = "/label"))]
+pub struct TaskConfig {
+    /// Name shown when picking a task.
+    #[serde(default)]
+    #[schemars(extend("x-order" = 1))]
+    pub label: String,
+
+    /// Shell command line to run (e.g. "cargo build").
+    #[serde(default)]
+    #[schemars(extend("x-order" = 2))]
+    pub command: String,
+
+    /// Arguments appended to the command, quoted for the shell.
+    #[serde(default)]
+    #[schemars(extend("x-order" = 3))]
+    pub args: Vec<String>,
+
+    /// Working directory. Defaults to the workspace root; relative paths
+    /// are resolved against it.
+    #[serde(default)]
+    #[schemars(extend("x-order" = 4))]
+    pub cwd: Option<String>,
+
+    /// Where the task runs: in a terminal tab or in the background.
+    #[serde(default)]
+    #[schemars(extend("x-order" = 5))]
+    pub run_in: TaskRunIn,
+
+    /// Problem matchers that turn the output into diagnostics: built-in
+    /// names (`$rustc`, `$tsc`, `$gcc`, `$pytest`) or inline definitions.
+    #[serde(default, deserialize_with = "one_or_many")]
+    #[schemars(extend("x-order" = 6))]
+    pub problem_matcher: Vec<ProblemMatcherConfig>,
+}
+
+impl TaskConfig {
+    /// The full shell command line, with `${workspaceFolder}` and `${file}`
+    /// substituted in the command and its arguments.
+    pub fn command_line(
+        &self,
+        workspace_folder: &std::path::Path,
+        file: Option<&std::path::Path>,
+    ) -> String {
+        let substitute = |s: &str| {
+            let mut s = s.replace("${workspaceFolder}", &workspace_folder.to_string_lossy());
+            if let Some(file) = file {
+                s = s.replace("${file}", &file.to_string_lossy());
+            }
+            s
+        };
+        let mut line = substitute(&self.command);
+        for arg in &self.args {
+            let arg = substitute(arg);
+            line.push(' ');
+            if !arg.is_empty()
+                && arg
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "-_./=:,+@%".contains(c))
+            {
+                line.push_str(&arg);
+            } else {
+                line.push('\'');
+                line.push_str(&arg.replace('\'', "'\\''"));
+                line.push('\'');
+            }
+        }
+        line
+    }
+
+    /// The working directory, resolved against `workspace_folder`.
+    pub fn working_dir(&self, workspace_folder: &std::path::Path) -> std::path::PathBuf {
+        match &self.cwd {
+            Some(cwd) => workspace_folder
+                .join(cwd.replace("${workspaceFolder}", &workspace_folder.to_string_lossy())),
+            None => workspace_folder.to_path_buf(),
+        }
+    }
+}
+
+/// Where a task's command runs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TaskRunIn {
+    /// In a new terminal tab, where its output stays visible
+    #[default]
+    Terminal,
+    /// Without a terminal; only its problems and exit status are reported
+    Background,
+}
+
+/// A problem matcher: the name of a built-in one, or a definition.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum ProblemMatcherConfig {
+    /// A built-in matcher such as `$rustc`
+    Named(String),
+    /// An inline matcher definition
+    Inline(ProblemMatcher),
+}
+
+/// Regular expressions that recognise problems in task output, in the
+/// shape of VS Code's `problemMatcher`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct ProblemMatcher {
+    /// Source shown with each diagnostic (e.g. "rustc"). Defaults to the
+    /// task label.
+    #[serde(default)]
+    pub source: Option<String>,
+
+    /// How file names in the output are resolved.
+    #[serde(default, alias = "fileLocation")]
+    pub file_location: FileLocation,
+
+    /// Severity for problems whose pattern captures none: "error",
+    /// "warning", "info" or "hint". Defaults to "error".
+    #[serde(default)]
+    pub severity: Option<String>,
+
+    /// One pattern per output line. Several patterns match a problem that
+    /// spans consecutive lines; the last one may repeat with `loop`.
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub pattern: Vec<ProblemPattern>,
+}
+
+/// One line of a problem matcher: a regular expression and the capture
+/// group numbers of the fields it provides.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct ProblemPattern {
+    /// Regular expression matched against one output line.
+    #[serde(default)]
+    pub regexp: String,
+    /// Capture group with the file name.
+    #[serde(default)]
+    pub file: Option<usize>,
+    /// Capture group with the 1-based line.
+    #[serde(default)]
+    pub line: Option<usize>,
+    /// Capture group with the 1-based column.
+    #[serde(default)]
+    pub column: Option<usize>,
+    /// Capture group with the 1-based end line.
+    #[serde(default, alias = "endLine")]
+    pub end_line: Option<usize>,
+    /// Capture group with the 1-based end column.
+    #[serde(default, alias = "endColumn")]
+    pub end_column: Option<usize>,
+    /// Capture group with the severity.
+    #[serde(default)]
+    pub severity: Option<usize>,
+    /// Capture group with the error code.
+    #[serde(default)]
+    pub code: Option<usize>,
+    /// Capture group with the message.
+    #[serde(default)]
+    pub message: Option<usize>,
+    /// On the last pattern: keep matching following lines, each one a
+    /// separate problem.
+    #[serde(default, rename = "loop")]
+    pub repeat: bool,
+}
+
+/// How a problem matcher resolves the file names it captures.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FileLocation {
+    /// Absolute paths are used as-is; others are relative to the task's
+    /// working directory
+    #[default]
+    AutoDetect,
+    /// Always relative to the task's working directory
+    Relative,
+    /// Always absolute
+    Absolute,
+}
+
+/// Accepts `"relative"` as well as VS Code's `["relative", "<base>"]`; the
+/// base is always the task's working directory.
+impl<'de> Deserialize<'de> for FileLocation {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Kind(String),
+            WithBase(Vec<String>),
+        }
+        let kind = match Raw::deserialize(deserializer)? {
+            Raw::Kind(kind) => kind,
+            Raw::WithBase(parts) => parts.into_iter().next().unwrap_or_default(),
+        };
+        match kind.as_str() {
+            "auto_detect" | "autoDetect" => Ok(Self::AutoDetect),
+            "relative" => Ok(Self::Relative),
+            "absolute" => Ok(Self::Absolute),
+            other => Err(serde::de::Error::unknown_variant(
+                other,
+                &["auto_detect", "relative", "absolute"],
+            )),
+        }
+    }
+}
+
+/// Deserialize a list that may also be written as a single element, as
+/// VS Code allows for `problemMatcher` and `pattern`.
+fn one_or_many<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany<T> {
+        Many(Vec<T>),
+        One(T),
+    }
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::Many(items) => items,
+        OneOrMany::One(item) => vec![item],
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -493,6 +867,33 @@ mod tests {
         assert!(!LspFeature::FoldingRange.is_merged());
         assert!(!LspFeature::SemanticTokens.is_merged());
         assert!(!LspFeature::DocumentHighlight.is_merged());
+        assert!(!LspFeature::CallHierarchy.is_merged());
+        assert!(!LspFeature::TypeHierarchy.is_merged());
+        assert!(!LspFeature::CodeLens.is_merged());
+        assert!(!LspFeature::DocumentLink.is_merged());
+        assert!(!LspFeature::LinkedEditingRange.is_merged());
+        assert!(!LspFeature::OnTypeFormatting.is_merged());
+        assert!(!LspFeature::SelectionRange.is_merged());
+    }
+
+    #[test]
+    fn test_lsp_feature_for_request_method() {
+        assert_eq!(
+            LspFeature::for_request_method("textDocument/prepareCallHierarchy"),
+            Some(LspFeature::CallHierarchy)
+        );
+        assert_eq!(
+            LspFeature::for_request_method("callHierarchy/outgoingCalls"),
+            Some(LspFeature::CallHierarchy)
+        );
+        assert_eq!(
+            LspFeature::for_request_method("typeHierarchy/subtypes"),
+            Some(LspFeature::TypeHierarchy)
+        );
+        assert_eq!(
+            LspFeature::for_request_method("textDocument/documentSymbol"),
+            None
+        );
     }
 
     #[test]
@@ -622,4 +1023,31 @@ mod tests {
             serde_json::from_str(r#"{"command":"markdown-oxide","args":[]}"#).unwrap();
         assert_eq!(empty.args, Some(vec![]));
     }
+
+    #[test]
+    fn test_debug_configuration_substitutes_variables() {
+        let config: DebugConfiguration = serde_json::from_str(
+            r#"{
+                "name": "app",
+                "adapter": "lldb",
+                "request": "attach",
+                "arguments": {"program": "${workspaceFolder}/app", "args": ["${file}"], "pid": 7}
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(config.request, DebugRequest::Attach);
+
+        let args = config.resolved_arguments(
+            std::path::Path::new("/work"),
+            Some(std::path::Path::new("/work/main.c")),
+        );
+        assert_eq!(
+            args,
+            serde_json::json!({"program": "/work/app", "args": ["/work/main.c"], "pid": 7})
+        );
+
+        // Without an active file `${file}` is left as-is.
+        let args = config.resolved_arguments(std::path::Path::new("/work"), None);
+        assert_eq!(args["args"][0], "${file}");
+    }
 }
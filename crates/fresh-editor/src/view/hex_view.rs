@@ -0,0 +1,190 @@
+//! View state and layout for the hex view of a buffer.
+//!
+//! A split showing a buffer in hex view draws rows of [`BYTES_PER_ROW`]
+//! bytes — an offset column, the bytes in hex and their decoded text —
+//! instead of text lines. The hex cursor is the split's primary cursor, so
+//! switching back to the text view keeps the position. Only the visible
+//! rows are read from the buffer, which keeps large files lazily loaded.
+
+/// Bytes shown on each row.
+pub const BYTES_PER_ROW: usize = 16;
+
+/// Which column the keyboard edits: the hex digits or the decoded text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HexPane {
+    #[default]
+    Hex,
+    Text,
+}
+
+/// Per-(split, buffer) hex view state.
+#[derive(Debug, Clone, Default)]
+pub struct HexViewState {
+    /// First row shown.
+    pub top_row: usize,
+    /// Rows that fit in the split, updated on every render.
+    pub visible_rows: usize,
+    /// Pane that receives typed input.
+    pub pane: HexPane,
+    /// Typing inserts bytes instead of overwriting them.
+    pub insert_mode: bool,
+    /// The high nibble of the byte under the cursor was just typed; the
+    /// next hex digit sets its low nibble.
+    pub low_nibble: bool,
+    /// Cursor offset the view last scrolled to. The view follows the cursor
+    /// only when it moves, so the mouse wheel can scroll away from it.
+    pub followed_cursor: Option<usize>,
+}
+
+impl HexViewState {
+    /// Scroll so the row holding `cursor` is visible, if the cursor moved
+    /// since the last call.
+    pub fn follow_cursor(&mut self, cursor: usize) {
+        if self.followed_cursor != Some(cursor) {
+            self.followed_cursor = Some(cursor);
+            self.scroll_to_offset(cursor);
+        }
+    }
+
+    /// Scroll by `delta` rows, staying within `total_rows`.
+    pub fn scroll(&mut self, delta: isize, total_rows: usize) {
+        let max_row = total_rows.saturating_sub(1);
+        self.top_row = self.top_row.saturating_add_signed(delta).min(max_row);
+    }
+
+    /// Scroll so the row holding `offset` is visible.
+    pub fn scroll_to_offset(&mut self, offset: usize) {
+        let row = offset / BYTES_PER_ROW;
+        let rows = self.visible_rows.max(1);
+        if row < self.top_row {
+            self.top_row = row;
+        } else if row >= self.top_row + rows {
+            self.top_row = row + 1 - rows;
+        }
+    }
+}
+
+/// Number of rows needed for `len` bytes, plus one for appending when the
+/// last row is full.
+pub fn row_count(len: usize) -> usize {
+    len / BYTES_PER_ROW + 1
+}
+
+/// Column positions of one hex row, relative to the left edge of the split.
+///
+/// ```text
+/// 0000001A  00 01 02 03 04 05 06 07  08 09 0A 0B 0C 0D 0E 0F  ................
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HexLayout {
+    /// Width of the offset column, in hex digits.
+    pub offset_width: usize,
+}
+
+impl HexLayout {
+    /// Layout for a buffer of `len` bytes: at least eight offset digits,
+    /// more when the file is larger than 4 GiB.
+    pub fn for_len(len: usize) -> Self {
+        let digits = (usize::BITS - len.leading_zeros()).div_ceil(4) as usize;
+        Self {
+            offset_width: digits.max(8),
+        }
+    }
+
+    /// Column of the first hex digit of the `index`-th byte of a row. The
+    /// two halves of the row are separated by an extra space.
+    pub fn hex_column(&self, index: usize) -> usize {
+        self.offset_width + 2 + index * 3 + usize::from(index >= BYTES_PER_ROW / 2)
+    }
+
+    /// Column of the `index`-th byte in the decoded text.
+    pub fn text_column(&self, index: usize) -> usize {
+        self.hex_column(BYTES_PER_ROW) + 1 + index
+    }
+
+    /// Total width of a row.
+    pub fn width(&self) -> usize {
+        self.text_column(BYTES_PER_ROW)
+    }
+}
+
+/// The character shown for `byte` in the decoded-text column.
+pub fn display_char(byte: u8) -> Option<char> {
+    (byte.is_ascii_graphic() || byte == b' ').then_some(byte as char)
+}
+
+/// Parse a byte offset typed by the user: decimal, or hexadecimal with a
+/// `0x` prefix. A trailing `B` (bytes) is accepted for decimal input.
+pub fn parse_offset(input: &str) -> Option<usize> {
+    let input = input.trim();
+    if let Some(hex) = input
+        .strip_prefix("0x")
+        .or_else(|| input.strip_prefix("0X"))
+    {
+        return usize::from_str_radix(hex, 16).ok();
+    }
+    input.strip_suffix(['B', 'b']).unwrap_or(input).parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layout_widens_offsets_for_large_files() {
+        assert_eq!(HexLayout::for_len(0).offset_width, 8);
+        assert_eq!(HexLayout::for_len(0xFFFF_FFFF).offset_width, 8);
+        assert_eq!(HexLayout::for_len(0x1_0000_0000).offset_width, 9);
+    }
+
+    #[test]
+    fn layout_columns() {
+        let layout = HexLayout::for_len(100);
+        assert_eq!(layout.hex_column(0), 10);
+        assert_eq!(layout.hex_column(7), 31);
+        assert_eq!(layout.hex_column(8), 35);
+        assert_eq!(layout.text_column(0), 60);
+        assert_eq!(layout.width(), 76);
+    }
+
+    #[test]
+    fn parses_decimal_and_hex_offsets() {
+        assert_eq!(parse_offset("42"), Some(42));
+        assert_eq!(parse_offset(" 42B "), Some(42));
+        assert_eq!(parse_offset("0x2A"), Some(42));
+        assert_eq!(parse_offset("0x"), None);
+        assert_eq!(parse_offset("2A"), None);
+    }
+
+    #[test]
+    fn scrolling_keeps_the_cursor_row_visible() {
+        let mut state = HexViewState {
+            visible_rows: 4,
+            ..Default::default()
+        };
+        state.scroll_to_offset(10 * BYTES_PER_ROW);
+        assert_eq!(state.top_row, 7);
+        state.scroll_to_offset(3 * BYTES_PER_ROW + 5);
+        assert_eq!(state.top_row, 3);
+        state.scroll_to_offset(5 * BYTES_PER_ROW);
+        assert_eq!(state.top_row, 3);
+    }
+
+    #[test]
+    fn wheel_scrolling_is_not_undone_until_the_cursor_moves() {
+        let mut state = HexViewState {
+            visible_rows: 4,
+            ..Default::default()
+        };
+        state.follow_cursor(0);
+        state.scroll(10, 100);
+        state.follow_cursor(0);
+        assert_eq!(state.top_row, 10);
+        state.follow_cursor(1);
+        assert_eq!(state.top_row, 0);
+        state.scroll(-5, 100);
+        assert_eq!(state.top_row, 0);
+        state.scroll(500, 100);
+        assert_eq!(state.top_row, 99);
+    }
+}
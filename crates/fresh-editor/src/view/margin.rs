@@ -511,6 +511,24 @@ impl MarginManager {
         self.indicator_markers.query_range(start, end)
     }
 
+    /// All indicators stored under `namespace`, as `(MarkerId, byte_offset)`
+    /// pairs sorted by their current position.
+    pub fn line_indicators_in_namespace(&self, namespace: &str) -> Vec<(MarkerId, usize)> {
+        let mut found: Vec<(MarkerId, usize)> = self
+            .line_indicators
+            .iter()
+            .filter(|(_, indicators)| indicators.contains_key(namespace))
+            .filter_map(|(&marker_id, _)| {
+                let marker_id = MarkerId(marker_id);
+                self.indicator_markers
+                    .get_position(marker_id)
+                    .map(|pos| (marker_id, pos))
+            })
+            .collect();
+        found.sort_by_key(|&(_, pos)| pos);
+        found
+    }
+
     /// Move a line indicator's marker to a new byte position.
     ///
     /// This is a no-op if the marker doesn't exist in the indicator markers.
@@ -955,6 +973,39 @@ mod tests {
         assert_eq!(breakpoint.unwrap().symbol, "●");
     }
 
+    #[test]
+    fn test_line_indicators_in_namespace() {
+        let mut manager = MarginManager::new();
+
+        let late = manager.set_line_indicator(
+            line_to_byte(7),
+            "breakpoints".to_string(),
+            LineIndicator::new("●", Color::Red, 20),
+        );
+        manager.set_line_indicator(
+            line_to_byte(4),
+            "git-gutter".to_string(),
+            LineIndicator::new("│", Color::Green, 10),
+        );
+        let early = manager.set_line_indicator(
+            line_to_byte(2),
+            "breakpoints".to_string(),
+            LineIndicator::new("●", Color::Red, 20),
+        );
+
+        assert_eq!(
+            manager.line_indicators_in_namespace("breakpoints"),
+            vec![(early, line_to_byte(2)), (late, line_to_byte(7))]
+        );
+
+        manager.adjust_for_insert(0, 10);
+        assert_eq!(
+            manager.line_indicators_in_namespace("breakpoints"),
+            vec![(early, line_to_byte(3)), (late, line_to_byte(8))]
+        );
+        assert!(manager.line_indicators_in_namespace("missing").is_empty());
+    }
+
     #[test]
     fn test_line_indicator_remove_specific() {
         let mut manager = MarginManager::new();
@@ -0,0 +1,201 @@
+//! Geometry for the minimap: a zoomed-out overview of a buffer drawn next
+//! to the vertical scrollbar.
+//!
+//! Each minimap cell is a braille character holding a 2×4 grid of dots, so
+//! one cell covers [`CHARS_PER_CELL`] characters of [`LINES_PER_ROW`] lines,
+//! with a dot for every non-whitespace character. Buffers too large to
+//! index by line are sampled instead: each minimap row shows the lines at an
+//! evenly spaced byte offset, so drawing never scans the whole file.
+
+use unicode_width::UnicodeWidthChar;
+
+/// Characters of a line covered by one minimap column.
+pub const CHARS_PER_CELL: usize = 2;
+
+/// Lines covered by one minimap row.
+pub const LINES_PER_ROW: usize = 4;
+
+/// Bit of the braille dot at column `x` (`0..2`) and row `y` (`0..4`) of a
+/// cell, following the Unicode braille dot numbering.
+fn dot_bit(x: usize, y: usize) -> u8 {
+    const BITS: [[u8; LINES_PER_ROW]; CHARS_PER_CELL] =
+        [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+    BITS[x][y]
+}
+
+/// The braille character with the given dots set. An empty cell is a plain
+/// space: some fonts draw the blank braille pattern as a visible box.
+pub fn braille(dots: u8) -> char {
+    if dots == 0 {
+        return ' ';
+    }
+    char::from_u32(0x2800 + u32::from(dots)).unwrap_or(' ')
+}
+
+/// Dots plotted for a minimap area, and where each cell's text came from.
+#[derive(Debug, Clone)]
+pub struct MinimapCanvas {
+    width: usize,
+    height: usize,
+    dots: Vec<u8>,
+    /// Byte offset of the first character plotted into each cell, used to
+    /// colour the cell by syntax highlighting.
+    sources: Vec<Option<usize>>,
+}
+
+impl MinimapCanvas {
+    /// An empty canvas of `width` × `height` cells.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            dots: vec![0; width * height],
+            sources: vec![None; width * height],
+        }
+    }
+
+    /// Plot `text`, a line starting at byte `line_start`, on dot row
+    /// `dot_row`. Tabs advance to the next multiple of `tab_size` columns;
+    /// text past the right edge is dropped.
+    pub fn plot_line(&mut self, dot_row: usize, line_start: usize, text: &str, tab_size: usize) {
+        let row = dot_row / LINES_PER_ROW;
+        if row >= self.height {
+            return;
+        }
+        let max_columns = self.width * CHARS_PER_CELL;
+        let mut column = 0;
+        for (offset, c) in text.char_indices() {
+            if column >= max_columns {
+                break;
+            }
+            if c == '\n' || c == '\r' {
+                break;
+            }
+            let width = char_columns(c, column, tab_size);
+            if !c.is_whitespace() {
+                for dot_column in column..(column + width).min(max_columns) {
+                    let cell = row * self.width + dot_column / CHARS_PER_CELL;
+                    self.dots[cell] |=
+                        dot_bit(dot_column % CHARS_PER_CELL, dot_row % LINES_PER_ROW);
+                    self.sources[cell].get_or_insert(line_start + offset);
+                }
+            }
+            column += width;
+        }
+    }
+
+    /// Glyph of the cell at (`column`, `row`), and the byte offset of the
+    /// first character drawn into it.
+    pub fn cell(&self, column: usize, row: usize) -> (char, Option<usize>) {
+        let index = row * self.width + column;
+        (braille(self.dots[index]), self.sources[index])
+    }
+}
+
+/// Columns taken by `c` when it starts at `column`. Tabs advance to the next
+/// multiple of `tab_size`.
+fn char_columns(c: char, column: usize, tab_size: usize) -> usize {
+    if c == '\t' {
+        let tab_size = tab_size.max(1);
+        tab_size - column % tab_size
+    } else {
+        c.width().unwrap_or(0)
+    }
+}
+
+/// Minimap column of byte `offset` in `text`, a line as passed to
+/// [`MinimapCanvas::plot_line`].
+pub fn cell_column(text: &str, offset: usize, tab_size: usize) -> usize {
+    let column = text
+        .char_indices()
+        .take_while(|&(i, _)| i < offset)
+        .fold(0, |column, (_, c)| {
+            column + char_columns(c, column, tab_size)
+        });
+    column / CHARS_PER_CELL
+}
+
+/// First buffer line shown at the top of a minimap that holds `capacity`
+/// lines.
+///
+/// When the buffer does not fit, the minimap scrolls proportionally to the
+/// editor: at the top of the buffer both show the first line, at the bottom
+/// both show the last, so the viewport always stays inside the minimap.
+pub fn first_line(
+    total_lines: usize,
+    top_line: usize,
+    viewport_lines: usize,
+    capacity: usize,
+) -> usize {
+    if total_lines <= capacity {
+        return 0;
+    }
+    let scroll_range = total_lines.saturating_sub(viewport_lines).max(1);
+    let top_line = top_line.min(scroll_range);
+    (top_line * (total_lines - capacity) / scroll_range).min(total_lines - capacity)
+}
+
+/// Byte offset sampled for minimap row `row` of `rows`, in a buffer of `len`
+/// bytes.
+pub fn sample_offset(len: usize, row: usize, rows: usize) -> usize {
+    (len as u128 * row as u128 / rows.max(1) as u128) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn braille_encodes_dot_positions() {
+        assert_eq!(braille(0), ' ');
+        assert_eq!(braille(dot_bit(0, 0)), '⠁');
+        assert_eq!(braille(dot_bit(1, 3)), '⢀');
+        assert_eq!(braille(0xFF), '⣿');
+    }
+
+    #[test]
+    fn plots_non_whitespace_two_characters_per_cell() {
+        let mut canvas = MinimapCanvas::new(3, 1);
+        canvas.plot_line(0, 100, "ab c", 4);
+        canvas.plot_line(3, 200, "\tx", 4);
+        assert_eq!(canvas.cell(0, 0), ('⠉', Some(100)));
+        // "c" is the second character of the second cell
+        assert_eq!(canvas.cell(1, 0), ('⠈', Some(103)));
+        // The tab moves "x" to column 4
+        assert_eq!(canvas.cell(2, 0), ('⡀', Some(201)));
+    }
+
+    #[test]
+    fn drops_text_past_the_edge() {
+        let mut canvas = MinimapCanvas::new(1, 1);
+        canvas.plot_line(0, 0, "abcdef", 4);
+        canvas.plot_line(4, 0, "ghij", 4);
+        assert_eq!(canvas.cell(0, 0), ('⠉', Some(0)));
+    }
+
+    #[test]
+    fn cell_column_expands_tabs() {
+        assert_eq!(cell_column("abcdef", 0, 4), 0);
+        assert_eq!(cell_column("abcdef", 5, 4), 2);
+        assert_eq!(cell_column("\tx", 1, 4), 2);
+        assert_eq!(cell_column("ab", 10, 4), 1);
+    }
+
+    #[test]
+    fn first_line_scrolls_proportionally() {
+        // Fits: never scrolls
+        assert_eq!(first_line(50, 30, 20, 80), 0);
+        // 1000 lines, 20 visible, 80 in the minimap
+        assert_eq!(first_line(1000, 0, 20, 80), 0);
+        assert_eq!(first_line(1000, 490, 20, 80), 460);
+        assert_eq!(first_line(1000, 980, 20, 80), 920);
+        assert_eq!(first_line(1000, 999, 20, 80), 920);
+    }
+
+    #[test]
+    fn samples_are_evenly_spaced() {
+        assert_eq!(sample_offset(1000, 0, 10), 0);
+        assert_eq!(sample_offset(1000, 5, 10), 500);
+        assert_eq!(sample_offset(usize::MAX, 1, 2), usize::MAX / 2);
+    }
+}
@@ -29,10 +29,14 @@ pub mod dimming;
 #[cfg(any(feature = "runtime", feature = "wasm"))]
 pub mod folding;
 #[cfg(any(feature = "runtime", feature = "wasm"))]
+pub mod hex_view;
+#[cfg(any(feature = "runtime", feature = "wasm"))]
 pub mod line_wrap_cache;
 #[cfg(any(feature = "runtime", feature = "wasm"))]
 pub mod margin;
 #[cfg(any(feature = "runtime", feature = "wasm"))]
+pub mod minimap;
+#[cfg(any(feature = "runtime", feature = "wasm"))]
 pub mod overlay;
 #[cfg(any(feature = "runtime", feature = "wasm"))]
 pub mod scroll_sync;
@@ -91,4 +95,6 @@ pub mod scene;
 #[cfg(feature = "runtime")]
 pub mod split;
 #[cfg(feature = "runtime")]
+pub mod sticky_scroll;
+#[cfg(feature = "runtime")]
 pub mod stream;
@@ -128,6 +128,13 @@ pub enum PopupResolver {
     /// through `handle_update_menu_action`; "update" opens a local terminal that
     /// runs the update.
     Update,
+    /// Options of a snippet choice placeholder (`${1|a,b|}`). Confirm
+    /// replaces the placeholder with the selected row's `data` through
+    /// `apply_snippet_choice`.
+    SnippetChoice,
+    /// Clipboard history entries. Confirm pastes the entry whose history
+    /// index is the selected row's `data` through `paste_history_entry`.
+    ClipboardHistory,
     /// "Couldn't save settings" error popup. Acknowledging it (confirm or
     /// cancel) opens the offending config file for `layer` in a buffer so the
     /// user can fix the syntax error that blocked the save.
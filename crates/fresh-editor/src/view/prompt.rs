@@ -170,6 +170,20 @@ pub enum PromptType {
     ConfirmLargeFileEncoding { path: std::path::PathBuf },
     /// Switch to a tab by name (from the current split's open buffers)
     SwitchToTab,
+    /// Pick a debug configuration to start (value is its index)
+    SelectDebugConfiguration,
+    /// Pick a task to run (value is its label)
+    SelectTask,
+    /// Pick a link in the current file to open (value is its target)
+    SelectDocumentLink,
+    /// Approve a capability a package plugin asked for: (y)es or (n)o
+    ConfirmPluginCapability {
+        plugin: String,
+        capability: fresh_core::capability::PluginCapability,
+    },
+    /// Pick a recorded plugin permission to revoke
+    /// (value is `"<plugin>\t<capability>"`)
+    RevokePluginPermission,
     /// Run shell command on buffer/selection
     /// If replace is true, replace the input with the output
     /// If replace is false, output goes to a new buffer
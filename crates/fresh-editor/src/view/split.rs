@@ -29,6 +29,7 @@ use crate::model::event::{BufferId, ContainerId, LeafId, SplitDirection, SplitId
 use crate::model::marker::MarkerList;
 use crate::state::ViewMode;
 use crate::view::folding::FoldManager;
+use crate::view::hex_view::HexViewState;
 use crate::view::ui::view_pipeline::Layout;
 use crate::view::viewport::Viewport;
 use ratatui::layout::Rect;
@@ -205,6 +206,10 @@ pub struct BufferViewState {
 
     /// Collapsed folding ranges for this buffer/view.
     pub folds: FoldManager,
+
+    /// When set, this split shows the buffer's bytes in hex view instead of
+    /// its text. The hex cursor is the primary cursor in `cursors`.
+    pub hex_view: Option<HexViewState>,
 }
 
 /// Editor display settings applied to a fresh [`BufferViewState`] via
@@ -256,6 +261,7 @@ impl BufferViewState {
             fold_indicators_override: None,
             plugin_state: std::collections::HashMap::new(),
             folds: FoldManager::new(),
+            hex_view: None,
         }
     }
 
@@ -333,6 +339,7 @@ impl Clone for BufferViewState {
             plugin_state: self.plugin_state.clone(),
             // Fold markers are per-view; clones start with no folded ranges.
             folds: FoldManager::new(),
+            hex_view: self.hex_view.clone(),
         }
     }
 }
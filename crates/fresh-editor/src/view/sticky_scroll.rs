@@ -0,0 +1,342 @@
+//! Sticky scroll: the lines opening the scopes that enclose the top of a
+//! view — functions, impls, classes — pinned as a header over the first rows
+//! of a split.
+//!
+//! Scopes come from, in order of preference:
+//!
+//! 1. **Tree-sitter**: the syntax tree of buffers whose language has a
+//!    grammar compiled in, parsed whole and cached per buffer version. Every
+//!    multi-line node enclosing the top line counts, so brace blocks, calls
+//!    and literals spread over several lines are scopes too.
+//! 2. **LSP document symbols**: the outline last received from the language
+//!    server, tracked by markers so it follows edits until a fresh outline
+//!    arrives.
+//! 3. **Indentation**: each less-indented line above the top line opens a
+//!    scope, as with indent-based folding.
+
+use crate::model::buffer::Buffer;
+use crate::model::marker::{MarkerId, MarkerList};
+use crate::state::EditorState;
+use crate::view::folding::indent_folding::slice_indent;
+
+/// Lines scanned above the top line for less-indented scope headers.
+const MAX_INDENT_SCAN_LINES: usize = 2000;
+
+/// Lines scanned below a blank top line for the indentation it belongs to.
+const MAX_BLANK_LOOKAHEAD_LINES: usize = 16;
+
+/// Scope sources for one buffer that outlive a frame.
+#[derive(Default)]
+pub struct StickyScopes {
+    /// LSP document symbols as (header, end) markers: the start of the line
+    /// naming the symbol, and the start of its last line.
+    lsp: Vec<(MarkerId, MarkerId)>,
+}
+
+impl StickyScopes {
+    /// Replace the LSP outline with a `textDocument/documentSymbol` response.
+    /// Symbols on a single line open no scope and are skipped.
+    pub fn set_from_lsp(
+        &mut self,
+        buffer: &Buffer,
+        marker_list: &mut MarkerList,
+        symbols: lsp_types::DocumentSymbolResponse,
+    ) {
+        self.clear_lsp(marker_list);
+        let mut lines = Vec::new();
+        match symbols {
+            lsp_types::DocumentSymbolResponse::Flat(symbols) => {
+                for symbol in symbols {
+                    let range = symbol.location.range;
+                    lines.push((range.start.line, range.end.line));
+                }
+            }
+            lsp_types::DocumentSymbolResponse::Nested(symbols) => {
+                let mut stack = symbols;
+                while let Some(symbol) = stack.pop() {
+                    // The full range also covers doc comments and attributes;
+                    // the header is the line naming the symbol.
+                    lines.push((symbol.selection_range.start.line, symbol.range.end.line));
+                    stack.extend(symbol.children.unwrap_or_default());
+                }
+            }
+        }
+        for (header_line, end_line) in lines {
+            if end_line <= header_line {
+                continue;
+            }
+            let (Some(header), Some(end)) = (
+                buffer.line_start_offset(header_line as usize),
+                buffer.line_start_offset(end_line as usize),
+            ) else {
+                continue;
+            };
+            self.lsp.push((
+                marker_list.create(header, false),
+                marker_list.create(end, false),
+            ));
+        }
+    }
+
+    /// Drop the LSP outline and release its markers.
+    pub fn clear_lsp(&mut self, marker_list: &mut MarkerList) {
+        for (header, end) in self.lsp.drain(..) {
+            marker_list.delete(header);
+            marker_list.delete(end);
+        }
+    }
+
+    /// Headers of the LSP symbols enclosing the line starting at `top`.
+    fn lsp_scopes(&self, marker_list: &MarkerList, top: usize) -> Vec<usize> {
+        let mut headers: Vec<usize> = self
+            .lsp
+            .iter()
+            .filter_map(|&(header, end)| {
+                let header = marker_list.get_position(header)?;
+                let end = marker_list.get_position(end)?;
+                (header < top && end > top).then_some(header)
+            })
+            .collect();
+        headers.sort_unstable();
+        headers.dedup();
+        headers
+    }
+}
+
+/// Headers of the syntax nodes enclosing the line starting at `top`, or
+/// `None` when the buffer has no grammar or is too large to parse.
+#[cfg(feature = "tree-sitter")]
+fn syntax_scopes(state: &mut EditorState, top: usize) -> Option<Vec<usize>> {
+    let language = state.highlighter.language().copied();
+    let buffer = &state.buffer;
+    let tree = state.syntax_tree.tree(buffer, language.as_ref())?;
+
+    // Start from the first character of the top line, so an indented
+    // line resolves to the statement on it rather than its block.
+    let line = buffer.slice_bytes(top..buffer.len().min(top + 256));
+    let indent = line
+        .iter()
+        .take_while(|b| **b == b' ' || **b == b'\t')
+        .count();
+    let top_row = buffer.get_line_number(top);
+    let mut node = tree
+        .root_node()
+        .descendant_for_byte_range(top + indent, top + indent);
+
+    // Walking up yields the innermost scope first; headers of nested
+    // nodes never move down, so duplicates are always adjacent.
+    let mut headers = Vec::new();
+    while let Some(current) = node {
+        let start = current.start_position();
+        if current.parent().is_some() && start.row < top_row && current.end_position().row > top_row
+        {
+            let header = current.start_byte() - start.column;
+            if headers.last() != Some(&header) {
+                headers.push(header);
+            }
+        }
+        node = current.parent();
+    }
+    headers.reverse();
+    Some(headers)
+}
+
+#[cfg(not(feature = "tree-sitter"))]
+fn syntax_scopes(_state: &mut EditorState, _top: usize) -> Option<Vec<usize>> {
+    None
+}
+
+/// Headers of the scopes enclosing the top line of the view, which starts at
+/// `top_byte`: the start of each scope's first line, outermost first, at most
+/// `max_lines` of them.
+pub fn enclosing_scopes(state: &mut EditorState, top_byte: usize, max_lines: usize) -> Vec<usize> {
+    if max_lines == 0 || top_byte == 0 {
+        return Vec::new();
+    }
+    let estimated_line_length = state.buffer.estimated_line_length();
+    let top = state
+        .buffer
+        .line_iterator(top_byte, estimated_line_length)
+        .current_position();
+
+    let mut headers = match syntax_scopes(state, top) {
+        Some(headers) => headers,
+        None if !state.sticky_scopes.lsp.is_empty() => {
+            state.sticky_scopes.lsp_scopes(&state.marker_list, top)
+        }
+        None => indent_scopes(&mut state.buffer, top, state.buffer_settings.tab_size),
+    };
+    headers.truncate(max_lines);
+    headers
+}
+
+/// Header lines to pin over a view whose first line starts at `top_byte`,
+/// outermost first, at most `max_lines` of them.
+///
+/// The header covers the rows it is drawn over, so it shows the scopes of
+/// the first line left visible beneath it rather than of the top line.
+pub fn sticky_headers(state: &mut EditorState, top_byte: usize, max_lines: usize) -> Vec<usize> {
+    let estimated_line_length = state.buffer.estimated_line_length();
+    let mut covered = 0;
+    loop {
+        let mut lines = state.buffer.line_iterator(top_byte, estimated_line_length);
+        let mut line = lines.current_position();
+        for _ in 0..covered {
+            if lines.next_line().is_none() {
+                break;
+            }
+            line = lines.current_position();
+        }
+        let headers = enclosing_scopes(state, line, max_lines);
+        if headers.len() <= covered {
+            return headers;
+        }
+        covered = headers.len();
+    }
+}
+
+/// Headers of the indentation levels enclosing the line starting at `top`:
+/// every line above it that is less indented than everything after it.
+pub fn indent_scopes(buffer: &mut Buffer, top: usize, tab_size: usize) -> Vec<usize> {
+    let estimated_line_length = buffer.estimated_line_length();
+
+    // A blank line belongs to the indentation of the code after it.
+    let mut lines = buffer.line_iterator(top, estimated_line_length);
+    let mut level = None;
+    for _ in 0..MAX_BLANK_LOOKAHEAD_LINES {
+        let Some((_, text)) = lines.next_line() else {
+            break;
+        };
+        let (indent, blank) = slice_indent(text.as_bytes(), tab_size);
+        if !blank {
+            level = Some(indent);
+            break;
+        }
+    }
+    let Some(mut level) = level else {
+        return Vec::new();
+    };
+
+    let mut headers = Vec::new();
+    let mut lines = buffer.line_iterator(top, estimated_line_length);
+    for _ in 0..MAX_INDENT_SCAN_LINES {
+        if level == 0 {
+            break;
+        }
+        let Some((start, text)) = lines.prev() else {
+            break;
+        };
+        let (indent, blank) = slice_indent(text.as_bytes(), tab_size);
+        if !blank && indent < level {
+            headers.push(start);
+            level = indent;
+        }
+    }
+    headers.reverse();
+    headers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "\
+mod outer {
+    fn f() {
+        let x = 1;
+
+        if x > 0 {
+            body();
+        }
+    }
+}
+";
+
+    fn line_start(text: &str, line: usize) -> usize {
+        text.split_inclusive('\n').take(line).map(str::len).sum()
+    }
+
+    #[test]
+    fn indent_scopes_list_less_indented_lines_outermost_first() {
+        let mut buffer = Buffer::from_str_test(SOURCE);
+        let top = line_start(SOURCE, 5);
+        assert_eq!(
+            indent_scopes(&mut buffer, top, 4),
+            vec![
+                line_start(SOURCE, 0),
+                line_start(SOURCE, 1),
+                line_start(SOURCE, 4)
+            ]
+        );
+    }
+
+    #[test]
+    fn indent_scopes_of_blank_line_follow_the_next_line() {
+        let mut buffer = Buffer::from_str_test(SOURCE);
+        let top = line_start(SOURCE, 3);
+        assert_eq!(
+            indent_scopes(&mut buffer, top, 4),
+            vec![line_start(SOURCE, 0), line_start(SOURCE, 1)]
+        );
+    }
+
+    #[test]
+    fn indent_scopes_are_empty_at_top_level() {
+        let mut buffer = Buffer::from_str_test(SOURCE);
+        assert!(indent_scopes(&mut buffer, line_start(SOURCE, 8), 4).is_empty());
+        assert!(indent_scopes(&mut buffer, 0, 4).is_empty());
+    }
+
+    #[test]
+    fn lsp_scopes_follow_nested_symbols() {
+        #[allow(deprecated)]
+        fn symbol(
+            name: &str,
+            lines: (u32, u32),
+            children: Vec<lsp_types::DocumentSymbol>,
+        ) -> lsp_types::DocumentSymbol {
+            let range = lsp_types::Range::new(
+                lsp_types::Position::new(lines.0, 0),
+                lsp_types::Position::new(lines.1, 1),
+            );
+            lsp_types::DocumentSymbol {
+                name: name.to_string(),
+                detail: None,
+                kind: lsp_types::SymbolKind::FUNCTION,
+                tags: None,
+                deprecated: None,
+                range,
+                selection_range: range,
+                children: Some(children),
+            }
+        }
+
+        let buffer = Buffer::from_str_test(SOURCE);
+        let mut marker_list = MarkerList::new();
+        marker_list.adjust_for_insert(0, buffer.len());
+        let mut scopes = StickyScopes::default();
+        scopes.set_from_lsp(
+            &buffer,
+            &mut marker_list,
+            lsp_types::DocumentSymbolResponse::Nested(vec![symbol(
+                "outer",
+                (0, 8),
+                vec![symbol("f", (1, 7), vec![symbol("x", (2, 2), Vec::new())])],
+            )]),
+        );
+        assert_eq!(
+            scopes.lsp_scopes(&marker_list, line_start(SOURCE, 5)),
+            vec![line_start(SOURCE, 0), line_start(SOURCE, 1)]
+        );
+        // The closing line of `f` is no longer inside it
+        assert_eq!(
+            scopes.lsp_scopes(&marker_list, line_start(SOURCE, 7)),
+            vec![line_start(SOURCE, 0)]
+        );
+
+        scopes.clear_lsp(&mut marker_list);
+        assert!(scopes
+            .lsp_scopes(&marker_list, line_start(SOURCE, 5))
+            .is_empty());
+    }
+}
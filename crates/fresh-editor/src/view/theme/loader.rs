@@ -565,9 +565,15 @@ impl ThemeLoader {
 impl Theme {
     /// Set the terminal cursor color using OSC 12 escape sequence.
     /// This makes the hardware cursor visible on any background.
+    ///
+    /// Skipped when stdout is not a terminal: a headless editor's stdout is
+    /// a report or a pipe, where the escape is only noise.
     pub fn set_terminal_cursor_color(&self) {
         use super::types::color_to_rgb;
-        use std::io::Write;
+        use std::io::{IsTerminal, Write};
+        if !std::io::stdout().is_terminal() {
+            return;
+        }
         if let Some((r, g, b)) = color_to_rgb(self.cursor) {
             // OSC 12 sets cursor color: \x1b]12;#RRGGBB\x07
             // Best-effort terminal escape writes
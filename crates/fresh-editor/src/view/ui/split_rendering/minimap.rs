@@ -0,0 +1,242 @@
+//! Minimap rendering: a zoomed-out overview of the buffer, drawn between the
+//! content area and the vertical scrollbar.
+//!
+//! Small buffers draw the lines around the viewport, four per row and two
+//! characters per column (see [`crate::view::minimap`]), coloured by syntax
+//! highlighting. Buffers over the large-file threshold are sampled: each row
+//! shows the four lines at an evenly spaced byte offset, so a frame reads
+//! O(rows) lines however large the file is.
+
+use super::layout::SplitLayout;
+use super::scrollbar::resolve_marker_color;
+use crate::primitives::line_iterator::MAX_LINE_BYTES;
+use crate::state::EditorState;
+use crate::view::minimap::{
+    cell_column, first_line, sample_offset, MinimapCanvas, CHARS_PER_CELL, LINES_PER_ROW,
+};
+use crate::view::theme::Theme;
+use crate::view::viewport::Viewport;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Style};
+
+/// Narrowest content area the minimap is carved from; narrower splits keep
+/// all their columns for text.
+const MIN_CONTENT_WIDTH: u16 = 40;
+
+/// Columns of the minimap to carve from a content area `content_width` wide,
+/// or 0 when the split is too narrow for one.
+pub(super) fn minimap_width(content_width: u16, configured_width: u16) -> u16 {
+    if configured_width == 0 || content_width < MIN_CONTENT_WIDTH + configured_width {
+        0
+    } else {
+        configured_width
+    }
+}
+
+/// Carve a minimap of `width` columns off the right edge of the content
+/// area of `layout`, returning it. The horizontal scrollbar shrinks with
+/// the content area it scrolls.
+pub(super) fn carve_minimap(layout: &mut SplitLayout, width: u16) -> Rect {
+    let content_rect = &mut layout.content_rect;
+    content_rect.width -= width;
+    layout.horizontal_scrollbar_rect.width = content_rect.width;
+    Rect::new(
+        content_rect.right(),
+        content_rect.y,
+        width,
+        content_rect.height,
+    )
+}
+
+/// Render the minimap of `state` into `area`. `view_end` is the byte just
+/// past the last line shown in the content area.
+///
+/// Returns, for each row of the minimap, the byte offset of the first line
+/// it shows — the scroll target when that row is clicked.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn render_minimap(
+    buf: &mut ratatui::buffer::Buffer,
+    area: Rect,
+    state: &mut EditorState,
+    viewport: &Viewport,
+    view_end: usize,
+    theme: &Theme,
+    use_terminal_bg: bool,
+    large_file_threshold_bytes: u64,
+    highlight_context_bytes: usize,
+) -> Vec<usize> {
+    let width = area.width as usize;
+    let height = area.height as usize;
+    if width == 0 || height == 0 {
+        return Vec::new();
+    }
+    let len = state.buffer.len();
+    let tab_size = state.buffer_settings.tab_size;
+    let estimated_line_length = state.buffer.estimated_line_length();
+    // Only the first few cells' worth of a line is ever drawn.
+    let max_line_bytes = (width * CHARS_PER_CELL * 4).min(MAX_LINE_BYTES);
+    let sampled = len > large_file_threshold_bytes as usize;
+
+    // (dot row, line start, text) of every line drawn.
+    let mut lines: Vec<(usize, usize, String)> = Vec::with_capacity(height * LINES_PER_ROW);
+    let mut row_targets = vec![len; height];
+    if sampled {
+        for (row, target) in row_targets.iter_mut().enumerate() {
+            let mut iter = state
+                .buffer
+                .line_iterator(sample_offset(len, row, height), estimated_line_length)
+                .with_max_line_bytes(max_line_bytes);
+            for line in 0..LINES_PER_ROW {
+                let Some((start, text)) = iter.next_line() else {
+                    break;
+                };
+                if line == 0 {
+                    *target = start;
+                }
+                lines.push((row * LINES_PER_ROW + line, start, text));
+            }
+        }
+    } else {
+        let total_lines = state.buffer.get_line_number(len.saturating_sub(1)) + 1;
+        let top_line = state.buffer.get_line_number(viewport.top_byte().min(len));
+        let first = first_line(
+            total_lines,
+            top_line,
+            viewport.height as usize,
+            height * LINES_PER_ROW,
+        );
+        let start = state.buffer.line_start_offset(first).unwrap_or(0);
+        let mut iter = state
+            .buffer
+            .line_iterator(start, estimated_line_length)
+            .with_max_line_bytes(max_line_bytes);
+        for dot_row in 0..height * LINES_PER_ROW {
+            let Some((start, text)) = iter.next_line() else {
+                break;
+            };
+            if dot_row % LINES_PER_ROW == 0 {
+                row_targets[dot_row / LINES_PER_ROW] = start;
+            }
+            lines.push((dot_row, start, text));
+        }
+    }
+
+    let mut canvas = MinimapCanvas::new(width, height);
+    for (dot_row, start, text) in &lines {
+        canvas.plot_line(*dot_row, *start, text, tab_size);
+    }
+
+    // Syntax colours. Sampled rows are scattered across the file, and
+    // highlighting them would parse far more than the rows show.
+    let spans = match (sampled, lines.first(), lines.last()) {
+        (false, Some((_, first, _)), Some((_, last, text))) => {
+            state.highlighter.highlight_viewport(
+                &state.buffer,
+                *first,
+                last + text.len(),
+                theme,
+                highlight_context_bytes,
+            )
+        }
+        _ => Vec::new(),
+    };
+    let color_at = |offset: usize| {
+        let index = spans.partition_point(|s| s.range.start <= offset);
+        index
+            .checked_sub(1)
+            .map(|i| &spans[i])
+            .filter(|s| s.range.contains(&offset))
+            .map_or(theme.editor_fg, |s| s.color)
+    };
+
+    let editor_bg = if use_terminal_bg {
+        Color::Reset
+    } else {
+        theme.editor_bg
+    };
+    let mut backgrounds = vec![editor_bg; width * height];
+
+    // The visible region: every row showing a line in the viewport, and at
+    // least the row holding its first line.
+    let top_byte = viewport.top_byte().min(len);
+    let rows_drawn = lines
+        .last()
+        .map_or(1, |(dot_row, _, _)| dot_row / LINES_PER_ROW + 1);
+    for row in 0..rows_drawn {
+        let row_end = if row + 1 < rows_drawn {
+            row_targets[row + 1]
+        } else {
+            len + 1
+        };
+        if row_targets[row] < view_end.max(top_byte + 1) && row_end > top_byte {
+            backgrounds[row * width..(row + 1) * width].fill(theme.scrollbar_track_fg);
+        }
+    }
+
+    // Cell of a byte offset, if it falls on a line drawn in the minimap.
+    let cell_of = |offset: usize| {
+        let index = lines.partition_point(|(_, start, _)| *start <= offset);
+        let (dot_row, start, text) = lines.get(index.checked_sub(1)?)?;
+        if offset > start + text.len() {
+            return None;
+        }
+        let column = cell_column(text, offset - start, tab_size).min(width - 1);
+        Some((dot_row / LINES_PER_ROW) * width + column)
+    };
+    let (range_start, range_end) = match (lines.first(), lines.last()) {
+        (Some((_, first, _)), Some((_, last, text))) => (*first, last + text.len()),
+        _ => (0, 0),
+    };
+
+    // Search hits.
+    for (overlay, range) in
+        state
+            .overlays
+            .query_viewport(range_start, range_end, &state.marker_list)
+    {
+        if overlay.namespace.as_ref().map(|ns| ns.as_str()) != Some("search") {
+            continue;
+        }
+        if let Some(cell) = cell_of(range.start) {
+            backgrounds[cell] = theme.search_match_bg;
+        }
+    }
+
+    // Diagnostics and plugin scrollbar markers, as a stripe down the left
+    // edge of the rows they fall on.
+    let mut stripe: Vec<Option<(i32, Color)>> = vec![None; height];
+    for marker in state.scrollbar_markers.resolved() {
+        let end = marker.end.unwrap_or(marker.start).max(marker.start);
+        if end < range_start || marker.start > range_end {
+            continue;
+        }
+        let first_row = row_targets
+            .partition_point(|&target| target <= marker.start)
+            .saturating_sub(1);
+        let last_row = row_targets
+            .partition_point(|&target| target <= end)
+            .saturating_sub(1);
+        for slot in &mut stripe[first_row..=last_row.max(first_row)] {
+            if slot.is_none_or(|(priority, _)| marker.priority > priority) {
+                *slot = Some((marker.priority, resolve_marker_color(&marker.color, theme)));
+            }
+        }
+    }
+
+    for row in 0..height {
+        for column in 0..width {
+            let (glyph, source) = canvas.cell(column, row);
+            let fg = source.map_or(theme.editor_fg, color_at);
+            let bg = match stripe[row] {
+                Some((_, color)) if column == 0 => color,
+                _ => backgrounds[row * width + column],
+            };
+            if let Some(cell) = buf.cell_mut((area.x + column as u16, area.y + row as u16)) {
+                cell.set_char(glyph)
+                    .set_style(Style::default().fg(fg).bg(bg));
+            }
+        }
+    }
+
+    row_targets
+}
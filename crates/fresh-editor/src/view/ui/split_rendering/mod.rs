@@ -18,12 +18,14 @@ mod folding;
 pub(crate) use folding::fold_skip_set;
 mod gutter;
 mod layout;
+mod minimap;
 mod orchestration;
 
 pub(crate) use orchestration::render_buffer::wrap_index_geometry_for;
 mod post_pass;
 pub(crate) mod scrollbar;
 mod spans;
+mod sticky_scroll;
 mod style;
 pub(crate) mod transforms;
 pub(crate) mod view_data;
@@ -64,6 +66,10 @@ pub struct EditorRenderConfig<'a> {
     pub use_terminal_bg: bool,
     pub show_vertical_scrollbar: bool,
     pub show_horizontal_scrollbar: bool,
+    pub show_minimap: bool,
+    pub minimap_width: u16,
+    pub sticky_scroll: bool,
+    pub sticky_scroll_max_lines: u16,
     pub diagnostics_inline_text: bool,
     pub show_tilde: bool,
     pub highlight_current_column: bool,
@@ -95,6 +101,10 @@ impl<'a> EditorRenderConfig<'a> {
             use_terminal_bg: editor.use_terminal_bg,
             show_vertical_scrollbar: editor.show_vertical_scrollbar,
             show_horizontal_scrollbar: editor.show_horizontal_scrollbar,
+            show_minimap: editor.show_minimap,
+            minimap_width: editor.minimap_width,
+            sticky_scroll: editor.sticky_scroll,
+            sticky_scroll_max_lines: editor.sticky_scroll_max_lines,
             diagnostics_inline_text: editor.diagnostics_inline_text,
             show_tilde: editor.show_tilde,
             highlight_current_column: editor.highlight_current_column,
@@ -180,6 +190,8 @@ impl SplitRenderer {
             u16,
             u16,
         )>,
+        Vec<(LeafId, BufferId, Rect, Vec<usize>)>,
+        Vec<(LeafId, BufferId, Rect, Vec<usize>)>,
     ) {
         orchestration::render_content(
             buf,
@@ -215,6 +227,7 @@ impl SplitRenderer {
         area: Rect,
         split_manager: &SplitManager,
         buffers: &mut HashMap<BufferId, EditorState>,
+        buffer_metadata: &HashMap<BufferId, BufferMetadata>,
         split_view_states: &mut HashMap<LeafId, crate::view::split::SplitViewState>,
         theme: &crate::view::theme::Theme,
         lsp_waiting: bool,
@@ -227,6 +240,7 @@ impl SplitRenderer {
         tab_bar_visible: bool,
         show_vertical_scrollbar: bool,
         show_horizontal_scrollbar: bool,
+        minimap_width: u16,
         diagnostics_inline_text: bool,
         show_tilde: bool,
         bracket_highlight: BracketHighlightSettings,
@@ -235,6 +249,7 @@ impl SplitRenderer {
             area,
             split_manager,
             buffers,
+            buffer_metadata,
             split_view_states,
             theme,
             lsp_waiting,
@@ -247,6 +262,7 @@ impl SplitRenderer {
             tab_bar_visible,
             show_vertical_scrollbar,
             show_horizontal_scrollbar,
+            minimap_width,
             diagnostics_inline_text,
             show_tilde,
             bracket_highlight,
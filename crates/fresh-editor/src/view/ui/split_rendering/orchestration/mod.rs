@@ -15,6 +15,7 @@ pub(super) mod overlay_sweep;
 pub(super) mod overlays;
 pub(super) mod render_buffer;
 pub(super) mod render_composite;
+pub(super) mod render_hex;
 pub(super) mod render_line;
 pub(super) mod selection_sweep;
 pub(super) mod tail_fill;
@@ -24,10 +25,12 @@ use super::layout::{
     render_separator, resolve_view_preferences, split_buffers_for_tabs, split_layout,
     sync_viewport_to_content, SplitLayout,
 };
+use super::minimap::{carve_minimap, minimap_width, render_minimap};
 use super::scrollbar::{
     compute_max_line_length, project_scrollbar_markers, render_composite_scrollbar,
     render_horizontal_scrollbar, render_scrollbar, scrollbar_line_counts,
 };
+use super::sticky_scroll::render_sticky_scroll;
 use super::EditorRenderConfig;
 use crate::app::types::ViewLineMapping;
 use crate::app::BufferMetadata;
@@ -49,6 +52,7 @@ use render_buffer::compute_buffer_layout;
 // pipeline. Stays crate-private; callers use the façade.
 pub(super) use render_buffer::render_buffer_in_split;
 use render_composite::render_composite_buffer;
+use render_hex::render_hex_buffer;
 use std::collections::HashMap;
 
 /// How a single visible split should be rendered. Computed up-front by
@@ -130,6 +134,8 @@ pub(crate) fn render_content(
         u16,
         u16,
     )>, // hit areas for separators inside active Grouped subtrees
+    Vec<(LeafId, BufferId, Rect, Vec<usize>)>, // minimap areas (rect + scroll target byte per row)
+    Vec<(LeafId, BufferId, Rect, Vec<usize>)>, // sticky scroll areas (rect + header line start per row)
 ) {
     let _span = tracing::trace_span!("render_content").entered();
 
@@ -144,6 +150,11 @@ pub(crate) fn render_content(
         use_terminal_bg,
         show_vertical_scrollbar,
         show_horizontal_scrollbar,
+        show_minimap,
+        minimap_width: configured_minimap_width,
+        sticky_scroll,
+        sticky_scroll_max_lines,
+        highlight_context_bytes,
         show_tilde,
         highlight_current_column,
         hide_current_line_on_selection,
@@ -172,6 +183,8 @@ pub(crate) fn render_content(
     let mut close_split_areas = Vec::new();
     let mut maximize_split_areas = Vec::new();
     let mut view_line_mappings: HashMap<LeafId, Vec<ViewLineMapping>> = HashMap::new();
+    let mut minimap_areas = Vec::new();
+    let mut sticky_scroll_areas = Vec::new();
 
     // Render each split.
     for (main_split_id, split_id, buffer_id, split_area, kind) in visible_buffers {
@@ -237,7 +250,7 @@ pub(crate) fn render_content(
         let panel_show_vscroll =
             show_vertical_scrollbar && !is_non_scrollable && !terminal_showing_live_grid;
 
-        let layout = if is_inner_group_leaf {
+        let mut layout = if is_inner_group_leaf {
             // Inner leaf: split_area IS the content rect already.
             SplitLayout {
                 tabs_rect: Rect::new(split_area.x, split_area.y, 0, 0),
@@ -332,6 +345,31 @@ pub(crate) fn render_content(
             continue;
         }
 
+        // A buffer shown in hex view draws bytes instead of text lines.
+        let hex_view_active = split_view_states
+            .as_deref()
+            .and_then(|vs| vs.get(&split_id))
+            .and_then(|vs| vs.keyed_states.get(&buffer_id))
+            .is_some_and(|bvs| bvs.hex_view.is_some());
+        if hex_view_active {
+            render_hex_split(
+                buf,
+                &layout,
+                split_id,
+                buffer_id,
+                buffers,
+                split_view_states.as_deref_mut(),
+                theme,
+                use_terminal_bg,
+                split_show_tilde,
+                show_vertical_scrollbar && !is_non_scrollable,
+                is_active,
+                &mut split_areas,
+            );
+            view_line_mappings.insert(split_id, Vec::new());
+            continue;
+        }
+
         // Composite buffers (side-by-side diff/compare panes) render through a
         // separate pipeline; dispatch them to their own helper.
         if buffers
@@ -362,6 +400,21 @@ pub(crate) fn render_content(
             continue;
         }
 
+        // The minimap overviews source text: not shown for buffer-group
+        // panels, fixed panels or virtual buffers (terminals included).
+        let minimap_rect = if show_minimap
+            && !is_inner_group_leaf
+            && !is_non_scrollable
+            && !buffer_metadata
+                .get(&buffer_id)
+                .is_some_and(|m| m.is_virtual())
+        {
+            let width = minimap_width(layout.content_rect.width, configured_minimap_width);
+            (width > 0).then(|| carve_minimap(&mut layout, width))
+        } else {
+            None
+        };
+
         // Get references separately to avoid double borrow
         let state_opt = buffers.get_mut(&buffer_id);
         let event_log_opt = event_logs.get_mut(&buffer_id);
@@ -510,6 +563,7 @@ pub(crate) fn render_content(
                 viewport.left_column = viewport.left_column.min(max_scroll);
             }
 
+            let is_page_view = matches!(view_prefs.view_mode, crate::state::ViewMode::PageView);
             let split_view_mappings = render_buffer_in_split(
                 buf,
                 state,
@@ -540,6 +594,56 @@ pub(crate) fn render_content(
 
             drop(_render_buf_span);
 
+            if let Some(minimap_rect) = minimap_rect {
+                let _span = tracing::trace_span!("render_minimap").entered();
+                let view_end = split_view_mappings
+                    .iter()
+                    .map(|m| m.line_end_byte)
+                    .max()
+                    .unwrap_or_else(|| viewport.top_byte());
+                let row_targets = render_minimap(
+                    buf,
+                    minimap_rect,
+                    state,
+                    &viewport,
+                    view_end,
+                    theme,
+                    use_terminal_bg,
+                    large_file_threshold_bytes,
+                    highlight_context_bytes,
+                );
+                minimap_areas.push((split_id, buffer_id, minimap_rect, row_targets));
+            }
+
+            // Like the minimap, the sticky header is for source text only.
+            if sticky_scroll
+                && !is_inner_group_leaf
+                && !is_non_scrollable
+                && !is_virtual_buffer
+                && !is_page_view
+            {
+                let _span = tracing::trace_span!("render_sticky_scroll").entered();
+                let headers = render_sticky_scroll(
+                    buf,
+                    layout.content_rect,
+                    state,
+                    &viewport,
+                    sticky_scroll_max_lines as usize,
+                    theme,
+                    use_terminal_bg,
+                    highlight_context_bytes,
+                );
+                if !headers.is_empty() {
+                    let rect = Rect::new(
+                        layout.content_rect.x,
+                        layout.content_rect.y,
+                        layout.content_rect.width,
+                        headers.len() as u16,
+                    );
+                    sticky_scroll_areas.push((split_id, buffer_id, rect, headers));
+                }
+            }
+
             // Store view line mappings for mouse click handling
             view_line_mappings.insert(split_id, split_view_mappings);
 
@@ -681,6 +785,8 @@ pub(crate) fn render_content(
         view_line_mappings,
         horizontal_scrollbar_areas,
         grouped_separator_areas,
+        minimap_areas,
+        sticky_scroll_areas,
     )
 }
 
@@ -1056,6 +1162,70 @@ fn render_composite_split(
     }
 }
 
+/// Render a buffer in hex view for one split, plus its scrollbar, and
+/// record the content/scrollbar areas for mouse handling.
+#[allow(clippy::too_many_arguments)]
+fn render_hex_split(
+    buf: &mut ratatui::buffer::Buffer,
+    layout: &SplitLayout,
+    split_id: LeafId,
+    buffer_id: BufferId,
+    buffers: &mut HashMap<BufferId, EditorState>,
+    split_view_states: Option<&mut HashMap<LeafId, crate::view::split::SplitViewState>>,
+    theme: &crate::view::theme::Theme,
+    use_terminal_bg: bool,
+    split_show_tilde: bool,
+    show_vertical_scrollbar: bool,
+    is_active: bool,
+    split_areas: &mut Vec<(LeafId, BufferId, Rect, Rect, usize, usize)>,
+) {
+    let Some(buffer_view) = split_view_states
+        .and_then(|vs| vs.get_mut(&split_id))
+        .and_then(|vs| vs.keyed_states.get_mut(&buffer_id))
+    else {
+        return;
+    };
+    let Some(state) = buffers.get_mut(&buffer_id) else {
+        return;
+    };
+    let Some(hex_view) = buffer_view.hex_view.as_mut() else {
+        return;
+    };
+    render_hex_buffer(
+        buf,
+        layout.content_rect,
+        state,
+        &buffer_view.cursors,
+        hex_view,
+        theme,
+        is_active,
+        use_terminal_bg,
+        split_show_tilde,
+    );
+
+    let (thumb_start, thumb_end) = if show_vertical_scrollbar {
+        render_composite_scrollbar(
+            buf,
+            layout.scrollbar_rect,
+            crate::view::hex_view::row_count(state.buffer.len()),
+            hex_view.top_row,
+            hex_view.visible_rows,
+            is_active,
+            theme,
+        )
+    } else {
+        (0, 0)
+    };
+    split_areas.push((
+        split_id,
+        buffer_id,
+        layout.content_rect,
+        layout.scrollbar_rect,
+        thumb_start,
+        thumb_end,
+    ));
+}
+
 /// Render the internal separators of any active buffer groups and return their
 /// hit areas (with container IDs) so the hit-test path can wire up dragging.
 /// A group's Split nodes live in the side-map, not the main split tree, so
@@ -1148,6 +1318,7 @@ pub(crate) fn compute_content_layout(
     area: Rect,
     split_manager: &SplitManager,
     buffers: &mut HashMap<BufferId, EditorState>,
+    buffer_metadata: &HashMap<BufferId, BufferMetadata>,
     split_view_states: &mut HashMap<LeafId, crate::view::split::SplitViewState>,
     theme: &crate::view::theme::Theme,
     lsp_waiting: bool,
@@ -1160,6 +1331,7 @@ pub(crate) fn compute_content_layout(
     tab_bar_visible: bool,
     show_vertical_scrollbar: bool,
     show_horizontal_scrollbar: bool,
+    minimap_width: u16,
     diagnostics_inline_text: bool,
     show_tilde: bool,
     bracket_highlight: BracketHighlightSettings,
@@ -1177,7 +1349,7 @@ pub(crate) fn compute_content_layout(
                 .get(&split_id)
                 .is_some_and(|vs| vs.suppress_chrome);
 
-        let layout = split_layout(
+        let mut layout = split_layout(
             split_area,
             split_tab_bar_visible,
             show_vertical_scrollbar,
@@ -1195,6 +1367,19 @@ pub(crate) fn compute_content_layout(
             continue;
         }
 
+        // Same content width as `render_content`, which carves the minimap
+        // from every scrollable, non-virtual buffer.
+        if state.scrollable
+            && !buffer_metadata
+                .get(&buffer_id)
+                .is_some_and(|m| m.is_virtual())
+        {
+            let width = self::minimap_width(layout.content_rect.width, minimap_width);
+            if width > 0 {
+                carve_minimap(&mut layout, width);
+            }
+        }
+
         // Get viewport from SplitViewState (authoritative source)
         let viewport_clone = split_view_states
             .get(&split_id)
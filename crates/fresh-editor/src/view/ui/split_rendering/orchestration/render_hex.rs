@@ -0,0 +1,209 @@
+//! Hex view rendering.
+//!
+//! Draws a buffer as rows of bytes — offset, hex digits, decoded text —
+//! under a one-row column header. Only the visible rows are read from the
+//! buffer, so large files stay lazily loaded.
+use crate::model::cursor::Cursors;
+use crate::state::EditorState;
+use crate::view::hex_view::{
+    display_char, row_count, HexLayout, HexPane, HexViewState, BYTES_PER_ROW,
+};
+use crate::view::theme::Theme;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::widgets::{Clear, Widget};
+use rust_i18n::t;
+use unicode_width::UnicodeWidthStr;
+
+/// Render `state` in hex view into `area`, scrolling `view_state` to the
+/// primary cursor when it moved.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn render_hex_buffer(
+    buf: &mut ratatui::buffer::Buffer,
+    area: Rect,
+    state: &mut EditorState,
+    cursors: &Cursors,
+    view_state: &mut HexViewState,
+    theme: &Theme,
+    is_active: bool,
+    use_terminal_bg: bool,
+    show_tilde: bool,
+) {
+    Clear.render(area, buf);
+    if area.height == 0 || area.width == 0 {
+        return;
+    }
+    let editor_bg = if use_terminal_bg {
+        Color::Reset
+    } else {
+        theme.editor_bg
+    };
+    let normal = Style::default().fg(theme.editor_fg).bg(editor_bg);
+    let gutter = Style::default()
+        .fg(theme.line_number_fg)
+        .bg(theme.line_number_bg);
+
+    let len = state.buffer.len();
+    let layout = HexLayout::for_len(len);
+    let header_height = 1u16;
+    let content_height = area.height.saturating_sub(header_height);
+    view_state.visible_rows = content_height as usize;
+    let cursor = cursors.primary().position.min(len);
+    view_state.follow_cursor(cursor);
+    view_state.top_row = view_state.top_row.min(row_count(len) - 1);
+    let selection = cursors.primary().selection_range();
+
+    // Fill the whole area first so short rows and the gap after the decoded
+    // text share the editor background.
+    for y in area.top()..area.bottom() {
+        buf.set_stringn(
+            area.x,
+            y,
+            " ".repeat(area.width as usize),
+            area.width as usize,
+            normal,
+        );
+    }
+
+    let put = |buf: &mut ratatui::buffer::Buffer, y: u16, column: usize, text: &str, style| {
+        if column < area.width as usize {
+            let max_width = area.width as usize - column;
+            buf.set_stringn(area.x + column as u16, y, text, max_width, style);
+        }
+    };
+
+    // Header: column indices over the bytes, and the edit mode. Labels are
+    // translated, so pad by display width and let a long label push the
+    // columns right rather than overlap them.
+    let pad_to = |header: &mut String, column: usize| {
+        let padding = column.saturating_sub(header.width()).max(1);
+        header.push_str(&" ".repeat(padding));
+    };
+    let mut header = t!("hex.offset_header").to_string();
+    for index in 0..BYTES_PER_ROW {
+        pad_to(&mut header, layout.hex_column(index));
+        header.push_str(&format!("{index:02X}"));
+    }
+    pad_to(&mut header, layout.text_column(0));
+    header.push_str(&t!("hex.text_header"));
+    let mode = if view_state.insert_mode {
+        t!("hex.mode_insert")
+    } else {
+        t!("hex.mode_overwrite")
+    };
+    let mode = format!(" {mode} ");
+    let width = area.width as usize;
+    let header_width = header.width();
+    if header_width + mode.width() < width {
+        header.push_str(&" ".repeat(width - header_width - mode.width()));
+        header.push_str(&mode);
+    }
+    put(buf, area.y, 0, &header, gutter);
+
+    let start = view_state.top_row * BYTES_PER_ROW;
+    let wanted = (view_state.visible_rows * BYTES_PER_ROW).min(len.saturating_sub(start));
+    let bytes = match state.buffer.get_text_range_mut(start, wanted) {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            tracing::warn!(
+                "Hex view failed to read bytes {}..{}: {}",
+                start,
+                start + wanted,
+                e
+            );
+            Vec::new()
+        }
+    };
+
+    let cursor_style = Style::default().fg(editor_bg).bg(theme.editor_fg);
+    let style_at = |offset: usize, pane: HexPane| {
+        if offset == cursor {
+            if is_active && pane == view_state.pane {
+                return cursor_style;
+            }
+            return normal.add_modifier(Modifier::UNDERLINED);
+        }
+        if selection.as_ref().is_some_and(|r| r.contains(&offset)) {
+            return normal.bg(theme.selection_bg);
+        }
+        normal
+    };
+
+    for view_row in 0..view_state.visible_rows {
+        let y = area.y + header_height + view_row as u16;
+        let row = view_state.top_row + view_row;
+        let row_start = row * BYTES_PER_ROW;
+        if row >= row_count(len) {
+            if show_tilde {
+                put(buf, y, 0, "~", Style::default().fg(theme.line_number_fg));
+            }
+            continue;
+        }
+        put(
+            buf,
+            y,
+            0,
+            &format!("{:0width$X}", row_start, width = layout.offset_width),
+            gutter,
+        );
+        for index in 0..BYTES_PER_ROW {
+            let offset = row_start + index;
+            let Some(&byte) = bytes.get(offset - start) else {
+                // One slot past the end, so the cursor can sit there to append.
+                if offset == len && offset == cursor {
+                    put(
+                        buf,
+                        y,
+                        layout.hex_column(index),
+                        "  ",
+                        style_at(offset, HexPane::Hex),
+                    );
+                    put(
+                        buf,
+                        y,
+                        layout.text_column(index),
+                        " ",
+                        style_at(offset, HexPane::Text),
+                    );
+                }
+                break;
+            };
+            let hex = format!("{byte:02X}");
+            let hex_style = style_at(offset, HexPane::Hex);
+            if offset == cursor && view_state.low_nibble && hex_style == cursor_style {
+                // Only the low digit is under the cursor mid-byte.
+                put(buf, y, layout.hex_column(index), &hex[..1], normal);
+                put(
+                    buf,
+                    y,
+                    layout.hex_column(index) + 1,
+                    &hex[1..],
+                    cursor_style,
+                );
+            } else {
+                put(buf, y, layout.hex_column(index), &hex, hex_style);
+            }
+            let text_style = style_at(offset, HexPane::Text);
+            match display_char(byte) {
+                Some(c) => put(
+                    buf,
+                    y,
+                    layout.text_column(index),
+                    &c.to_string(),
+                    text_style,
+                ),
+                None => put(
+                    buf,
+                    y,
+                    layout.text_column(index),
+                    ".",
+                    text_style.fg(if text_style == cursor_style {
+                        editor_bg
+                    } else {
+                        theme.line_number_fg
+                    }),
+                ),
+            }
+        }
+    }
+}
@@ -428,7 +428,10 @@ pub(super) fn compute_max_line_length(state: &mut EditorState, viewport: &mut Vi
 ///
 /// Theme keys are resolved here, at paint time, rather than when the plugin
 /// sets the marker — so markers follow a theme switch with no invalidation.
-fn resolve_marker_color(spec: &fresh_core::api::OverlayColorSpec, theme: &Theme) -> Color {
+pub(super) fn resolve_marker_color(
+    spec: &fresh_core::api::OverlayColorSpec,
+    theme: &Theme,
+) -> Color {
     match spec {
         fresh_core::api::OverlayColorSpec::Rgb(r, g, b) => Color::Rgb(*r, *g, *b),
         fresh_core::api::OverlayColorSpec::ThemeKey(key) => {
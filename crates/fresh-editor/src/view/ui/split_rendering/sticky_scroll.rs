@@ -0,0 +1,167 @@
+//! Sticky scroll rendering: the lines opening the scopes around the top of
+//! the view, drawn over the first rows of the content area (see
+//! [`crate::view::sticky_scroll`] for where the scopes come from).
+//!
+//! Each header row repeats the gutter and text of its line as it would be
+//! drawn in place, scrolled by the same horizontal offset, and the last row
+//! is underlined to set the header apart from the text beneath it.
+
+use crate::primitives::line_iterator::MAX_LINE_BYTES;
+use crate::primitives::syntax_tree::MAX_SYNTAX_PARSE_BYTES;
+use crate::state::EditorState;
+use crate::view::sticky_scroll::sticky_headers;
+use crate::view::theme::Theme;
+use crate::view::viewport::Viewport;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use unicode_width::UnicodeWidthChar;
+
+/// Render the sticky scroll header of `state` over the top of `area`, the
+/// content rect of a split showing `viewport`.
+///
+/// Returns the line start of each header row, top to bottom — the scope to
+/// jump to when that row is clicked.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn render_sticky_scroll(
+    buf: &mut ratatui::buffer::Buffer,
+    area: Rect,
+    state: &mut EditorState,
+    viewport: &Viewport,
+    max_lines: usize,
+    theme: &Theme,
+    use_terminal_bg: bool,
+    highlight_context_bytes: usize,
+) -> Vec<usize> {
+    // Leave at least half of the split for the text it scrolls.
+    let max_lines = max_lines.min(area.height as usize / 2);
+    // Without a line index there are no line numbers to show in the gutter.
+    if max_lines == 0 || area.width == 0 || state.buffer.line_count().is_none() {
+        return Vec::new();
+    }
+    let top_byte = viewport.top_byte().min(state.buffer.len());
+    let headers = sticky_headers(state, top_byte, max_lines);
+    if headers.is_empty() {
+        return headers;
+    }
+
+    let estimated_line_length = state.buffer.estimated_line_length();
+    let lines: Vec<(usize, String)> = headers
+        .iter()
+        .map(|&start| {
+            let text = state
+                .buffer
+                .line_iterator(start, estimated_line_length)
+                .with_max_line_bytes(MAX_LINE_BYTES)
+                .next_line()
+                .map(|(_, text)| text)
+                .unwrap_or_default();
+            (start, text)
+        })
+        .collect();
+
+    // Syntax colours, when the highlighter can provide them without
+    // re-parsing far from the viewport.
+    let first = headers[0];
+    let spans = if top_byte - first <= highlight_context_bytes
+        || state.buffer.len() <= MAX_SYNTAX_PARSE_BYTES
+    {
+        let (last, text) = &lines[lines.len() - 1];
+        state.highlighter.highlight_viewport(
+            &state.buffer,
+            first,
+            last + text.len(),
+            theme,
+            highlight_context_bytes,
+        )
+    } else {
+        Vec::new()
+    };
+    let color_at = |offset: usize| {
+        let index = spans.partition_point(|s| s.range.start <= offset);
+        index
+            .checked_sub(1)
+            .map(|i| &spans[i])
+            .filter(|s| s.range.contains(&offset))
+            .map_or(theme.editor_fg, |s| s.color)
+    };
+
+    let editor_bg = if use_terminal_bg {
+        Color::Reset
+    } else {
+        theme.editor_bg
+    };
+    let margins = &state.margins.left_config;
+    let tab_size = state.buffer_settings.tab_size.max(1);
+    let right = area.x + area.width;
+
+    for (row, (start, text)) in lines.iter().enumerate() {
+        let y = area.y + row as u16;
+        let mut base = Style::default().bg(editor_bg);
+        if row + 1 == lines.len() {
+            base = base.add_modifier(Modifier::UNDERLINED);
+        }
+        for x in area.x..right {
+            if let Some(cell) = buf.cell_mut((x, y)) {
+                cell.reset();
+                cell.set_char(' ').set_style(base.fg(theme.editor_fg));
+            }
+        }
+
+        let mut x = area.x;
+        let mut put = |x: &mut u16, c: char, style: Style| {
+            if *x < right {
+                if let Some(cell) = buf.cell_mut((*x, y)) {
+                    cell.set_char(c).set_style(style);
+                }
+                *x += 1;
+            }
+        };
+
+        // Gutter: the indicator column, the line number and the separator.
+        if margins.enabled {
+            let gutter = base.fg(theme.line_number_fg);
+            let number = state.buffer.get_line_number(*start) + 1;
+            let mut gutter_text = format!(" {number:>width$}", width = margins.width);
+            if margins.show_separator {
+                gutter_text.push_str(&margins.separator);
+            }
+            for c in gutter_text.chars() {
+                put(&mut x, c, gutter);
+            }
+        }
+
+        // Text, scrolled horizontally like the lines beneath it.
+        let mut column = 0;
+        for (offset, c) in text.char_indices() {
+            if c == '\n' || c == '\r' || x >= right {
+                break;
+            }
+            let style = base.fg(color_at(start + offset));
+            if c == '\t' {
+                let width = tab_size - column % tab_size;
+                for _ in 0..width {
+                    if column >= viewport.left_column {
+                        put(&mut x, ' ', style);
+                    }
+                    column += 1;
+                }
+                continue;
+            }
+            let width = c.width().unwrap_or(0);
+            if width == 0 {
+                continue;
+            }
+            if column >= viewport.left_column {
+                if x as usize + width > right as usize {
+                    break;
+                }
+                put(&mut x, c, style);
+                // Wide characters cover the next cell too.
+                x += width as u16 - 1;
+            }
+            column += width;
+        }
+    }
+
+    headers
+}
@@ -551,6 +551,28 @@ impl VirtualTextManager {
         }
     }
 
+    /// Remove every inline (`BeforeChar` / `AfterChar`) entry, leaving
+    /// virtual lines in place. Inlay hints are rebuilt wholesale with this so
+    /// a refresh doesn't take code lens or plugin header lines with it.
+    pub fn clear_inline(&mut self, marker_list: &mut MarkerList) {
+        let to_remove: Vec<VirtualTextId> = self
+            .texts
+            .iter()
+            .filter(|(_, vtext)| vtext.position.is_inline())
+            .map(|(id, _)| *id)
+            .collect();
+
+        let removed = !to_remove.is_empty();
+        for id in to_remove {
+            if let Some(vtext) = self.texts.remove(&id) {
+                marker_list.delete(vtext.marker_id);
+            }
+        }
+        if removed {
+            self.bump_version();
+        }
+    }
+
     /// Remove all virtual text entries whose marker position lies within the
     /// half-open byte range `[start, end)`.
     ///
@@ -1217,4 +1239,38 @@ mod tests {
         assert_eq!(before.unwrap().text, "/*param=*/");
         assert_eq!(after.unwrap().text, ": Type");
     }
+
+    #[test]
+    fn test_clear_inline_keeps_virtual_lines() {
+        let mut marker_list = MarkerList::new();
+        let mut manager = VirtualTextManager::new();
+
+        manager.add(
+            &mut marker_list,
+            10,
+            ": i32".to_string(),
+            hint_style(),
+            VirtualTextPosition::AfterChar,
+            0,
+        );
+        manager.add_line(
+            &mut marker_list,
+            0,
+            "Run | Debug".to_string(),
+            hint_style(),
+            VirtualTextPosition::LineAbove,
+            VirtualTextNamespace::from_string("lsp-code-lens".to_string()),
+            0,
+        );
+
+        manager.clear_inline(&mut marker_list);
+
+        assert_eq!(manager.len(), 1);
+        assert!(manager
+            .query_inline_in_range(&marker_list, 0, 20)
+            .is_empty());
+        let lines = manager.query_lines_in_range(&marker_list, 0, 20);
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].1.text, "Run | Debug");
+    }
 }
@@ -98,6 +98,11 @@ pub struct Workspace {
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub plugin_global_state: HashMap<String, HashMap<String, serde_json::Value>>,
 
+    /// Clipboard history, most recent first. Only written when
+    /// `clipboard.persist_history` is enabled.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub clipboard_history: Vec<crate::services::clipboard::ClipboardEntry>,
+
     /// Timestamp when workspace was saved (Unix epoch seconds)
     pub saved_at: u64,
 
@@ -1355,6 +1360,7 @@ impl Workspace {
             read_only_files: Vec::new(),
             unnamed_buffers: Vec::new(),
             plugin_global_state: HashMap::new(),
+            clipboard_history: Vec::new(),
             saved_at: SystemTime::now()
                 .duration_since(UNIX_EPOCH)
                 .unwrap_or_default()
@@ -0,0 +1,137 @@
+//! Fake debug adapter for E2E testing
+//!
+//! A Bash script that speaks just enough of the Debug Adapter Protocol over
+//! stdio to drive a session: it stops at the first breakpoint it is given,
+//! in the file passed as the launch configuration's `program`, and reports a
+//! single local variable `x = 42`. Continuing ends the session.
+
+/// Script content of the fake adapter.
+const FAKE_DAP_SCRIPT: &str = r#"#!/bin/bash
+
+# Function to read a message
+read_message() {
+    local content_length=0
+    while IFS= read -r line; do
+        line="${line%$'\r'}"
+        if [ -z "$line" ]; then
+            break
+        fi
+        case "$line" in
+            Content-Length:*)
+                content_length="${line#Content-Length:}"
+                content_length="${content_length// /}"
+                ;;
+        esac
+    done
+
+    if [ "$content_length" -gt 0 ] 2>/dev/null; then
+        dd bs=1 count="$content_length" 2>/dev/null
+    fi
+}
+
+# Function to send a message
+out_seq=0
+send_message() {
+    local message="$1"
+    local length=${#message}
+    printf "Content-Length: %d\r\n\r\n%s" "$length" "$message"
+}
+
+respond() {
+    out_seq=$((out_seq + 1))
+    send_message '{"seq":'$out_seq',"type":"response","request_seq":'$req_seq',"success":true,"command":"'$command'","body":'"$1"'}'
+}
+
+event() {
+    out_seq=$((out_seq + 1))
+    send_message '{"seq":'$out_seq',"type":"event","event":"'$1'","body":'"$2"'}'
+}
+
+program=""
+bp_line=1
+
+while true; do
+    msg=$(read_message)
+
+    if [ -z "$msg" ]; then
+        break
+    fi
+
+    command=$(echo "$msg" | grep -o '"command":"[^"]*"' | head -1 | cut -d'"' -f4)
+    req_seq=$(echo "$msg" | grep -o '"seq":[0-9]*' | head -1 | cut -d':' -f2)
+
+    case "$command" in
+        "initialize")
+            respond '{"supportsConfigurationDoneRequest":true}'
+            ;;
+        "launch")
+            program=$(echo "$msg" | grep -o '"program":"[^"]*"' | head -1 | cut -d'"' -f4)
+            respond '{}'
+            event "initialized" '{}'
+            ;;
+        "setBreakpoints")
+            line=$(echo "$msg" | grep -o '"line":[0-9]*' | head -1 | cut -d':' -f2)
+            if [ -n "$line" ]; then
+                bp_line=$line
+            fi
+            respond '{"breakpoints":[{"verified":true,"line":'$bp_line'}]}'
+            ;;
+        "configurationDone")
+            respond '{}'
+            event "output" '{"category":"stdout","output":"fake debuggee started\n"}'
+            event "stopped" '{"reason":"breakpoint","threadId":1,"allThreadsStopped":true}'
+            ;;
+        "threads")
+            respond '{"threads":[{"id":1,"name":"main"}]}'
+            ;;
+        "stackTrace")
+            respond '{"stackFrames":[{"id":1000,"name":"main","source":{"name":"main.py","path":"'$program'"},"line":'$bp_line',"column":1}],"totalFrames":1}'
+            ;;
+        "scopes")
+            respond '{"scopes":[{"name":"Locals","variablesReference":1,"expensive":false}]}'
+            ;;
+        "variables")
+            respond '{"variables":[{"name":"x","value":"42","type":"int","variablesReference":0}]}'
+            ;;
+        "continue")
+            respond '{"allThreadsContinued":true}'
+            event "continued" '{"threadId":1}'
+            event "exited" '{"exitCode":0}'
+            event "terminated" '{}'
+            ;;
+        "disconnect")
+            respond '{}'
+            break
+            ;;
+        *)
+            respond '{}'
+            ;;
+    esac
+done
+"#;
+
+/// Scripted debug adapter for E2E tests.
+pub struct FakeDapAdapter;
+
+impl FakeDapAdapter {
+    /// Write the adapter script into `dir` and return its path.
+    pub fn write(dir: &std::path::Path) -> anyhow::Result<std::path::PathBuf> {
+        let script_path = Self::script_path(dir);
+        std::fs::write(&script_path, FAKE_DAP_SCRIPT)?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mut perms = std::fs::metadata(&script_path)?.permissions();
+            perms.set_mode(0o755);
+            std::fs::set_permissions(&script_path, perms)?;
+        }
+
+        Ok(script_path)
+    }
+
+    /// Path of the adapter script inside `dir`.
+    pub fn script_path(dir: &std::path::Path) -> std::path::PathBuf {
+        dir.join("fake_dap_adapter.sh")
+    }
+}
@@ -3044,6 +3044,97 @@ done
         Ok(Self { handle, stop_tx })
     }
 
+    /// Spawn a fake LSP server that answers every `workspace/symbol` request
+    /// with `symbols` (a JSON array of `SymbolInformation`), whatever the
+    /// query. `name` keeps the scripts of several servers apart.
+    pub fn spawn_with_workspace_symbols(
+        dir: &std::path::Path,
+        name: &str,
+        symbols: &str,
+    ) -> anyhow::Result<Self> {
+        let (stop_tx, stop_rx) = mpsc::channel();
+
+        let script = format!(
+            r#"#!/bin/bash
+
+SYMBOLS='{symbols}'
+
+read_message() {{
+    local content_length=0
+    while IFS= read -r line; do
+        line="${{line%$'\r'}}"
+        if [ -z "$line" ]; then break; fi
+        case "$line" in
+            Content-Length:*)
+                content_length="${{line#Content-Length:}}"
+                content_length="${{content_length// /}}"
+                ;;
+        esac
+    done
+    if [ "$content_length" -gt 0 ] 2>/dev/null; then
+        dd bs=1 count="$content_length" 2>/dev/null
+    fi
+}}
+
+send_message() {{
+    local message="$1"
+    local length=${{#message}}
+    printf "Content-Length: %d\r\n\r\n%s" "$length" "$message"
+}}
+
+while true; do
+    msg=$(read_message)
+    if [ -z "$msg" ]; then break; fi
+
+    method=$(echo "$msg" | grep -o '"method":"[^"]*"' | cut -d'"' -f4)
+    msg_id=$(echo "$msg" | grep -o '"id":[0-9]*' | cut -d':' -f2)
+
+case "$method" in
+    "initialize")
+        send_message '{{"jsonrpc":"2.0","id":'$msg_id',"result":{{"capabilities":{{"textDocumentSync":1,"workspaceSymbolProvider":true}}}}}}'
+        ;;
+    "workspace/symbol")
+        send_message '{{"jsonrpc":"2.0","id":'$msg_id',"result":'"$SYMBOLS"'}}'
+        ;;
+    "textDocument/didOpen"|"textDocument/didChange"|"textDocument/didClose"|"initialized")
+        ;;
+    "shutdown")
+        send_message '{{"jsonrpc":"2.0","id":'$msg_id',"result":null}}'
+        break
+        ;;
+    *)
+        if [ -n "$msg_id" ]; then
+            send_message '{{"jsonrpc":"2.0","id":'$msg_id',"result":null}}'
+        fi
+        ;;
+esac
+done
+"#
+        );
+
+        let script_path = Self::workspace_symbols_script_path(dir, name);
+        std::fs::write(&script_path, script)?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mut perms = std::fs::metadata(&script_path)?.permissions();
+            perms.set_mode(0o755);
+            std::fs::set_permissions(&script_path, perms)?;
+        }
+
+        let handle = Some(thread::spawn(move || {
+            let _ = stop_rx.recv();
+        }));
+
+        Ok(Self { handle, stop_tx })
+    }
+
+    /// Get the path to a workspace symbols fake LSP server script
+    pub fn workspace_symbols_script_path(dir: &std::path::Path, name: &str) -> std::path::PathBuf {
+        dir.join(format!("fake_lsp_server_workspace_symbols_{name}.sh"))
+    }
+
     /// Stop the server
     pub fn stop(&mut self) {
         let _ = self.stop_tx.send(());
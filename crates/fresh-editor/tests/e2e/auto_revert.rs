@@ -534,3 +534,30 @@ fn test_auto_revert_forced_off_for_terminal_buffers() {
         "terminal-backed buffers must have auto-revert forced off"
     );
 }
+
+/// Once the file's directory is watched, an external change reverts the
+/// buffer from the watch event. Auto-revert polling is pushed out to an hour
+/// so it can't be what picks the change up; the short file-tree interval is
+/// only there to install the watch.
+#[test]
+#[cfg_attr(target_os = "macos", ignore)] // FSEvents coalescing can cause flaky timing
+fn test_auto_revert_from_watch_event_without_polling() {
+    let mut config = fresh::config::Config::default();
+    config.editor.auto_revert_poll_interval_ms = 3_600_000;
+    config.editor.file_tree_poll_interval_ms = 50;
+    let mut harness = EditorTestHarness::with_temp_project_and_config(80, 24, config).unwrap();
+    let project_dir = harness.project_dir().unwrap();
+    let file_path = project_dir.join("watched.txt");
+    write_and_sync(&file_path, "before");
+
+    harness.open_file(&file_path).unwrap();
+    harness.assert_buffer_content("before");
+    harness
+        .wait_until(|h| h.editor().is_dir_watched_for_test(&project_dir))
+        .expect("the open file's directory should get watched");
+
+    write_and_sync(&file_path, "after the watch");
+    harness
+        .wait_until(|h| h.get_buffer_content().unwrap() == "after the watch")
+        .expect("the watch event should revert the buffer");
+}
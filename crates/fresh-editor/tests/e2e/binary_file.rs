@@ -1,6 +1,7 @@
 use crate::common::harness::EditorTestHarness;
 use fresh::config::Config;
 use fresh::config_io::DirectoryContext;
+use fresh::input::keybindings::Action;
 use tempfile::TempDir;
 
 /// Files under the app data dir (e.g. terminal scrollback backing files
@@ -296,6 +297,10 @@ fn test_binary_bytes_rendered_as_hex() {
 
     let mut harness = EditorTestHarness::new(120, 24).unwrap();
     harness.open_file(&bin_path).unwrap();
+    // Binary files open in hex view; check the text view's rendering
+    harness
+        .editor_mut()
+        .dispatch_action_for_tests(Action::ToggleHexView);
     harness.render().unwrap();
 
     // The screen should contain <89> for the first byte (high byte, not valid UTF-8)
@@ -350,6 +355,10 @@ fn test_binary_file_scrolling_no_artifacts() {
     // Use a standard terminal size
     let mut harness = EditorTestHarness::new(80, 24).unwrap();
     harness.open_file(&png_path).unwrap();
+    // Binary files open in hex view; scroll the text view
+    harness
+        .editor_mut()
+        .dispatch_action_for_tests(Action::ToggleHexView);
 
     // Use render_real() which processes through VT100 parser for accurate terminal simulation
     harness.render_real().unwrap();
@@ -0,0 +1,180 @@
+//! E2E tests for the clipboard history: Paste from History, Yank Pop,
+//! per-cursor entries, and persistence through the workspace file.
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use fresh::input::keybindings::Action;
+use fresh::model::cursor::Cursor;
+use tempfile::TempDir;
+
+/// Copy line `line` (0-based) of the buffer by selecting it.
+fn copy_line(harness: &mut EditorTestHarness, line: usize) {
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    for _ in 0..line {
+        harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    }
+    harness.send_key(KeyCode::End, KeyModifiers::SHIFT).unwrap();
+    harness
+        .send_key(KeyCode::Char('c'), KeyModifiers::CONTROL)
+        .unwrap();
+}
+
+/// A buffer holding three lines, each copied in turn (so "third" is the
+/// current clipboard), with the cursor on the empty last line. Wide enough
+/// for the status bar to show whole messages.
+fn harness_with_history() -> EditorTestHarness {
+    let mut harness = EditorTestHarness::new(140, 24).unwrap();
+    // Internal-only clipboard: no interference from the host clipboard.
+    harness.editor_mut().set_clipboard_for_test(String::new());
+    harness.type_text("first\nsecond\nthird\n").unwrap();
+    for line in 0..3 {
+        copy_line(&mut harness, line);
+    }
+    harness
+        .send_key(KeyCode::End, KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+}
+
+fn yank_pop(harness: &mut EditorTestHarness) {
+    harness
+        .editor_mut()
+        .dispatch_action_for_tests(Action::YankPop);
+    harness.render().unwrap();
+}
+
+#[test]
+fn test_paste_from_history_picks_older_entry() {
+    let mut harness = harness_with_history();
+    harness
+        .editor_mut()
+        .dispatch_action_for_tests(Action::PasteFromHistory);
+    harness.render().unwrap();
+    harness.assert_screen_contains("Clipboard History");
+    harness.assert_screen_contains("first");
+
+    // Most recent first: "third", "second", "first".
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    harness.assert_buffer_content("first\nsecond\nthird\nsecond");
+    // The picked entry is now the current clipboard.
+    assert_eq!(harness.editor().clipboard_content_for_test(), "second");
+}
+
+#[test]
+fn test_yank_pop_cycles_pasted_text() {
+    let mut harness = harness_with_history();
+    harness
+        .send_key(KeyCode::Char('v'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content("first\nsecond\nthird\nthird");
+
+    yank_pop(&mut harness);
+    harness.assert_buffer_content("first\nsecond\nthird\nsecond");
+    yank_pop(&mut harness);
+    harness.assert_buffer_content("first\nsecond\nthird\nfirst");
+    // Past the oldest entry it wraps around.
+    yank_pop(&mut harness);
+    harness.assert_buffer_content("first\nsecond\nthird\nthird");
+
+    // Undo takes back one replacement at a time.
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content("first\nsecond\nthird\nfirst");
+}
+
+#[test]
+fn test_yank_pop_needs_a_paste_just_before() {
+    let mut harness = harness_with_history();
+    yank_pop(&mut harness);
+    harness.assert_buffer_content("first\nsecond\nthird\n");
+
+    harness
+        .send_key(KeyCode::Char('v'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("!").unwrap();
+    yank_pop(&mut harness);
+    harness.assert_buffer_content("first\nsecond\nthird\nthird!");
+    harness.assert_screen_contains("right after a paste");
+}
+
+#[test]
+fn test_multi_cursor_copy_pastes_back_per_cursor() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.editor_mut().set_clipboard_for_test(String::new());
+    harness.type_text("a1\na2\nb1\nb2\nX\nY").unwrap();
+
+    // One cursor per two-line block.
+    {
+        let cursors = harness.editor_mut().active_cursors_mut();
+        *cursors.primary_mut() = Cursor::with_selection(0, 5);
+        cursors.add(Cursor::with_selection(6, 11));
+    }
+    harness
+        .send_key(KeyCode::Char('c'), KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(
+        harness.editor().clipboard_content_for_test(),
+        "a1\na2\nb1\nb2"
+    );
+
+    // Two cursors again: each gets its own block back, even though the
+    // clipboard text has four lines.
+    {
+        let cursors = harness.editor_mut().active_cursors_mut();
+        cursors.remove_secondary();
+        *cursors.primary_mut() = Cursor::new(13);
+        cursors.add(Cursor::new(15));
+    }
+    harness
+        .send_key(KeyCode::Char('v'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content("a1\na2\nb1\nb2\nXa1\na2\nYb1\nb2");
+}
+
+#[test]
+fn test_clipboard_history_persists_in_workspace() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path().join("project");
+    std::fs::create_dir(&project_dir).unwrap();
+    let file = project_dir.join("notes.txt");
+    std::fs::write(&file, "kept line\nother line\n").unwrap();
+
+    let mut config = Config::default();
+    config.clipboard.persist_history = true;
+
+    {
+        let mut harness = EditorTestHarness::with_config_and_working_dir(
+            80,
+            24,
+            config.clone(),
+            project_dir.clone(),
+        )
+        .unwrap();
+        harness.editor_mut().set_clipboard_for_test(String::new());
+        harness.open_file(&file).unwrap();
+        copy_line(&mut harness, 0);
+        copy_line(&mut harness, 1);
+        harness.editor_mut().save_workspace().unwrap();
+    }
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(80, 24, config, project_dir).unwrap();
+    harness.editor_mut().set_clipboard_for_test(String::new());
+    assert!(harness.editor_mut().try_restore_workspace().unwrap());
+    harness
+        .editor_mut()
+        .dispatch_action_for_tests(Action::PasteFromHistory);
+    harness.render().unwrap();
+    harness.assert_screen_contains("Clipboard History");
+    harness.assert_screen_contains("kept line");
+    harness.assert_screen_contains("other line");
+}
@@ -0,0 +1,108 @@
+//! E2E tests for the Debug Adapter Protocol client: breakpoints in the
+//! margin, stopping at them, inline variable values and the debug panel,
+//! driven by the scripted adapter in `common::fake_dap`.
+
+use crate::common::fake_dap::FakeDapAdapter;
+use crate::common::harness::{EditorTestHarness, HarnessOptions};
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::types::{DebugAdapterConfig, DebugConfiguration, DebugRequest};
+
+fn debug_config(temp: &std::path::Path) -> anyhow::Result<fresh::config::Config> {
+    let script = FakeDapAdapter::write(temp)?;
+    let mut config = fresh::config::Config::default();
+    config.debug_adapters.insert(
+        "fake".to_string(),
+        DebugAdapterConfig {
+            command: script.to_string_lossy().to_string(),
+            ..Default::default()
+        },
+    );
+    config.debug_configurations = vec![DebugConfiguration {
+        name: "fake app".to_string(),
+        adapter: "fake".to_string(),
+        request: DebugRequest::Launch,
+        arguments: Some(serde_json::json!({ "program": "${file}" })),
+    }];
+    Ok(config)
+}
+
+fn harness_with_file(
+    temp: &std::path::Path,
+    content: &str,
+) -> anyhow::Result<(EditorTestHarness, std::path::PathBuf)> {
+    let config = debug_config(temp)?;
+    let file = temp.join("main.py");
+    std::fs::write(&file, content)?;
+    let mut harness = EditorTestHarness::create(
+        100,
+        30,
+        HarnessOptions::new()
+            .with_config(config)
+            .with_working_dir(temp.to_path_buf()),
+    )?;
+    harness.open_file(&file)?;
+    harness.render()?;
+    Ok((harness, file))
+}
+
+#[test]
+fn test_toggle_breakpoint_marks_margin() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let (mut harness, _file) = harness_with_file(temp_dir.path(), "a = 1\nb = 2\n")?;
+    harness.assert_screen_not_contains("●");
+
+    harness.run_palette_command("Debug: Toggle Breakpoint")?;
+    harness.render()?;
+    harness.assert_screen_contains("●");
+
+    harness.run_palette_command("Debug: Toggle Breakpoint")?;
+    harness.render()?;
+    harness.assert_screen_not_contains("●");
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(target_os = "windows", ignore = "FakeDapAdapter uses Bash")]
+fn test_debug_session_stops_at_breakpoint() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let (mut harness, _file) =
+        harness_with_file(temp_dir.path(), "x = compute()\ny = x + 1\nprint(y)\n")?;
+
+    // Breakpoint on line 2.
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE)?;
+    harness.run_palette_command("Debug: Toggle Breakpoint")?;
+    harness.render()?;
+    harness.assert_screen_contains("●");
+
+    harness.run_palette_command("Debug: Start")?;
+
+    // The adapter stops at the breakpoint and reports `x = 42`: the stop
+    // line is marked and the lines using `x` show its value.
+    harness.wait_for_screen_contains("x = 42")?;
+    harness.assert_screen_contains("▶");
+    harness.assert_screen_contains("x = compute()  x = 42");
+    let cursor = harness.cursor_position();
+    assert_eq!(
+        harness
+            .editor()
+            .active_state()
+            .buffer
+            .get_line_number(cursor),
+        1
+    );
+
+    // The panel lists the stack, the variables and the debuggee output.
+    harness.run_palette_command("Debug: Show Panel")?;
+    harness.wait_for_screen_contains("fake debuggee started")?;
+    harness.assert_screen_contains("main  main.py:2");
+    harness.assert_screen_contains("Locals");
+    harness.assert_screen_contains("x: int = 42");
+
+    // Continuing lets the fake debuggee run to completion.
+    harness.run_palette_command("Debug: Continue")?;
+    harness.wait_until(|h| h.editor().active_window().dap.session().is_none())?;
+    harness.render()?;
+    harness.assert_screen_contains("No debug session");
+    harness.assert_screen_not_contains("x: int = 42");
+    Ok(())
+}
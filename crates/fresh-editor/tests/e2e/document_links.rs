@@ -0,0 +1,123 @@
+//! E2E tests for document links in editor buffers: URLs and `path:line:col`
+//! references are underlined, Ctrl+Click opens a file reference at its
+//! position, and "List Links in File" picks among the file's links.
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::style::Modifier;
+use tempfile::TempDir;
+
+const NOTES: &str = "See https://example.com/docs for more.\n\
+                     Build failed at src/lib.rs:3:2 today.\n\
+                     plain words here\n\
+                     Stale ref gone/away.rs:9:1 here\n";
+
+/// A project with `notes.txt` (the file under test) and the `src/lib.rs` it
+/// refers to, with `notes.txt` open.
+fn harness_with_notes(temp: &TempDir) -> EditorTestHarness {
+    let project = temp.path().to_path_buf();
+    std::fs::create_dir_all(project.join("src")).unwrap();
+    std::fs::write(project.join("src/lib.rs"), "one\ntwo\nthree TARGET\n").unwrap();
+    std::fs::write(project.join("notes.txt"), NOTES).unwrap();
+    let mut harness = EditorTestHarness::with_working_dir(100, 24, project.clone()).unwrap();
+    harness.open_file(&project.join("notes.txt")).unwrap();
+    harness.render().unwrap();
+    harness
+}
+
+fn is_underlined(harness: &EditorTestHarness, text: &str) -> bool {
+    harness
+        .find_text_on_screen(text)
+        .and_then(|(x, y)| harness.get_cell_style(x, y))
+        .is_some_and(|style| style.add_modifier.contains(Modifier::UNDERLINED))
+}
+
+fn ctrl_left_click(harness: &mut EditorTestHarness, col: u16, row: u16) {
+    for kind in [
+        MouseEventKind::Down(MouseButton::Left),
+        MouseEventKind::Up(MouseButton::Left),
+    ] {
+        harness
+            .send_mouse(MouseEvent {
+                kind,
+                column: col,
+                row,
+                modifiers: KeyModifiers::CONTROL,
+            })
+            .unwrap();
+    }
+}
+
+#[test]
+fn test_urls_and_file_references_are_underlined() {
+    let temp = TempDir::new().unwrap();
+    let mut harness = harness_with_notes(&temp);
+
+    harness
+        .wait_until(|h| is_underlined(h, "https://example.com/docs"))
+        .unwrap();
+    assert!(is_underlined(&harness, "src/lib.rs:3:2"));
+    assert!(!is_underlined(&harness, "plain words"));
+}
+
+#[test]
+fn test_ctrl_click_on_file_reference_opens_it_at_position() {
+    let temp = TempDir::new().unwrap();
+    let mut harness = harness_with_notes(&temp);
+    harness
+        .wait_until(|h| is_underlined(h, "src/lib.rs:3:2"))
+        .unwrap();
+
+    let (col, row) = harness.find_text_on_screen("src/lib.rs:3:2").unwrap();
+    ctrl_left_click(&mut harness, col + 4, row);
+    harness.render().unwrap();
+
+    harness.assert_screen_contains("three TARGET");
+    harness.assert_screen_contains("Ln 3");
+}
+
+#[test]
+fn test_ctrl_click_on_reference_to_missing_file_is_inert() {
+    let temp = TempDir::new().unwrap();
+    let mut harness = harness_with_notes(&temp);
+    // Detection is textual, so the reference is underlined; it is only
+    // resolved when clicked, and names no file.
+    harness
+        .wait_until(|h| is_underlined(h, "gone/away.rs:9:1"))
+        .unwrap();
+
+    let (col, row) = harness.find_text_on_screen("gone/away.rs:9:1").unwrap();
+    ctrl_left_click(&mut harness, col + 2, row);
+    harness.render().unwrap();
+
+    // Still on notes.txt: nothing was opened.
+    harness.assert_screen_contains("Stale ref");
+    harness.assert_screen_contains("plain words here");
+}
+
+#[test]
+fn test_list_links_in_file_opens_picked_link() {
+    let temp = TempDir::new().unwrap();
+    let mut harness = harness_with_notes(&temp);
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("List Links in File").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    harness.assert_screen_contains("https://example.com/docs");
+    harness.assert_screen_contains("line 2");
+
+    harness.type_text("lib.rs").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    harness.assert_screen_contains("three TARGET");
+    harness.assert_screen_contains("Ln 3");
+}
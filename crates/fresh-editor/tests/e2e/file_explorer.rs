@@ -3179,12 +3179,15 @@ fn test_external_gitignore_edit_and_delete_reload_via_poll() {
     );
 }
 
-/// Creating a .gitignore externally in an already-expanded directory must
-/// be picked up by the poll: the dir's mtime bumps, refresh_node re-lists,
-/// and the rules for that dir get loaded for the first time.
+/// Creating a .gitignore externally in an already-expanded directory that
+/// isn't watched must be picked up by the poll: the dir's mtime bumps,
+/// refresh_node re-lists, and the rules for that dir get loaded for the
+/// first time. The watched case is
+/// `test_file_explorer_refreshes_from_watch_event`.
 #[test]
 fn test_externally_creating_gitignore_loads_rules_via_poll() {
     let mut harness = EditorTestHarness::with_temp_project(120, 40).unwrap();
+    harness.editor_mut().disable_file_watches_for_test();
     let project_root = harness.project_dir().unwrap().canonicalize().unwrap();
 
     fs::write(project_root.join("foo.txt"), "").unwrap();
@@ -4521,3 +4524,29 @@ fn test_respect_gitignore_true_hides_ignored_files() {
         screen
     );
 }
+
+/// Expanded explorer directories are watched: a file created on disk shows
+/// up from the watch event, with the file-tree poll pushed out to an hour.
+#[test]
+#[cfg_attr(target_os = "macos", ignore)] // FSEvents coalescing can cause flaky timing
+fn test_file_explorer_refreshes_from_watch_event() {
+    let mut config = Config::default();
+    config.editor.file_tree_poll_interval_ms = 3_600_000;
+    // The auto-revert interval is what installs the watches here.
+    config.editor.auto_revert_poll_interval_ms = 50;
+    let mut harness = EditorTestHarness::with_temp_project_and_config(120, 30, config).unwrap();
+    let project_root = harness.project_dir().unwrap();
+    fs::write(project_root.join("existing.txt"), "x").unwrap();
+
+    harness.editor_mut().focus_file_explorer();
+    harness.wait_for_file_explorer().unwrap();
+    harness.wait_for_file_explorer_item("existing.txt").unwrap();
+    harness
+        .wait_until(|h| h.editor().is_dir_watched_for_test(&project_root))
+        .expect("the expanded root should get watched");
+
+    fs::write(project_root.join("created_outside.txt"), "y").unwrap();
+    harness
+        .wait_for_file_explorer_item("created_outside.txt")
+        .unwrap();
+}
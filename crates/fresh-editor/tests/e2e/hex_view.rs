@@ -0,0 +1,290 @@
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::input::keybindings::Action;
+use tempfile::TempDir;
+
+/// PNG signature followed by an IHDR chunk header: 24 bytes, two hex rows.
+const PNG_BYTES: &[u8] = &[
+    0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 0x00, 0x00, 0x00, 0x0D, 0x49, 0x48, 0x44, 0x52,
+    0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01,
+];
+
+/// Open `PNG_BYTES` as a binary file, with editing unlocked.
+fn open_editable_binary(temp_dir: &TempDir) -> (EditorTestHarness, std::path::PathBuf) {
+    let path = temp_dir.path().join("image.png");
+    std::fs::write(&path, PNG_BYTES).unwrap();
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    harness.open_file(&path).unwrap();
+    harness
+        .editor_mut()
+        .dispatch_action_for_tests(Action::ToggleReadOnly);
+    harness.render().unwrap();
+    (harness, path)
+}
+
+fn save(harness: &mut EditorTestHarness) {
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+/// Binary files open in hex view: offsets, hex bytes and decoded text.
+#[test]
+fn test_binary_file_opens_in_hex_view() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("image.png");
+    std::fs::write(&path, PNG_BYTES).unwrap();
+
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    harness.open_file(&path).unwrap();
+    harness.render().unwrap();
+
+    harness.assert_screen_contains("Offset");
+    harness.assert_screen_contains("00 01 02 03 04 05 06 07  08 09 0A 0B 0C 0D 0E 0F");
+    harness.assert_screen_contains(
+        "00000000  89 50 4E 47 0D 0A 1A 0A  00 00 00 0D 49 48 44 52  .PNG........IHDR",
+    );
+    harness.assert_screen_contains("00000010  00 00 00 01 00 00 00 01");
+    // The text view renders invalid bytes as <XX>
+    harness.assert_screen_not_contains("<89>");
+
+    // Still read-only: hex digits do not edit the file
+    harness
+        .send_key(KeyCode::Char('f'), KeyModifiers::NONE)
+        .unwrap();
+    assert_eq!(harness.buffer_len(), PNG_BYTES.len());
+}
+
+/// Hex digits overwrite the byte under the cursor, one nibble at a time,
+/// and the edit is undone as one step per keystroke.
+#[test]
+fn test_hex_view_overwrite_and_undo() {
+    let temp_dir = TempDir::new().unwrap();
+    let (mut harness, path) = open_editable_binary(&temp_dir);
+
+    harness
+        .send_key(KeyCode::Right, KeyModifiers::NONE)
+        .unwrap();
+    harness.type_text("ab").unwrap();
+    assert_eq!(harness.cursor_position(), 2);
+    harness.render().unwrap();
+    harness.assert_screen_contains("00000000  89 AB 4E 47");
+
+    save(&mut harness);
+    let mut expected = PNG_BYTES.to_vec();
+    expected[1] = 0xAB;
+    assert_eq!(std::fs::read(&path).unwrap(), expected);
+
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    save(&mut harness);
+    assert_eq!(std::fs::read(&path).unwrap(), PNG_BYTES);
+}
+
+/// Tab switches typing to the decoded text; Insert switches to inserting
+/// bytes instead of overwriting them.
+#[test]
+fn test_hex_view_text_pane_and_insert_mode() {
+    let temp_dir = TempDir::new().unwrap();
+    let (mut harness, path) = open_editable_binary(&temp_dir);
+
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE).unwrap();
+    harness.type_text("Hi").unwrap();
+    harness
+        .send_key(KeyCode::Insert, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("INS");
+    harness.type_text("!").unwrap();
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE).unwrap();
+    harness.type_text("00").unwrap();
+
+    save(&mut harness);
+    let mut expected = b"Hi!\x00".to_vec();
+    expected.extend_from_slice(&PNG_BYTES[2..]);
+    assert_eq!(std::fs::read(&path).unwrap(), expected);
+}
+
+/// Go To prompts for a byte offset in hex view, accepting hex input.
+#[test]
+fn test_hex_view_goto_offset() {
+    let temp_dir = TempDir::new().unwrap();
+    let (mut harness, _path) = open_editable_binary(&temp_dir);
+
+    harness
+        .send_key(KeyCode::Char('g'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Go to offset");
+    harness.type_text("0x12").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    assert_eq!(harness.cursor_position(), 0x12);
+
+    harness
+        .send_key(KeyCode::End, KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(harness.cursor_position(), PNG_BYTES.len());
+    harness.send_key(KeyCode::Up, KeyModifiers::NONE).unwrap();
+    assert_eq!(harness.cursor_position(), PNG_BYTES.len() - 16);
+}
+
+/// Any buffer can be switched into hex view and back.
+#[test]
+fn test_toggle_hex_view_on_text_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("notes.txt");
+    std::fs::write(&path, "héllo\n").unwrap();
+
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    harness.open_file(&path).unwrap();
+    harness
+        .editor_mut()
+        .dispatch_action_for_tests(Action::ToggleHexView);
+    harness.render().unwrap();
+    harness.assert_screen_contains("00000000  68 C3 A9 6C 6C 6F 0A");
+    harness.assert_screen_contains("h..llo.");
+
+    // Leaving hex view from inside a multi-byte character snaps the cursor
+    // to a character boundary.
+    harness
+        .send_key_repeat(KeyCode::Right, KeyModifiers::NONE, 2)
+        .unwrap();
+    harness
+        .editor_mut()
+        .dispatch_action_for_tests(Action::ToggleHexView);
+    harness.render().unwrap();
+    harness.assert_screen_not_contains("00000000");
+    harness.assert_screen_contains("héllo");
+    assert_eq!(harness.cursor_position(), 1);
+}
+
+/// A fake language server that appends the body of every `didChange` to the
+/// log file given as its first argument.
+#[cfg(unix)]
+const DID_CHANGE_LOGGING_LSP: &str = r##"#!/bin/bash
+LOG_FILE="$1"
+> "$LOG_FILE"
+
+read_message() {
+    local content_length=0
+    while IFS= read -r line; do
+        line="${line%$'\r'}"
+        if [ -z "$line" ]; then
+            break
+        fi
+        case "$line" in
+            Content-Length:*)
+                content_length="${line#Content-Length:}"
+                content_length="${content_length// /}"
+                ;;
+        esac
+    done
+    if [ "$content_length" -gt 0 ] 2>/dev/null; then
+        dd bs=1 count="$content_length" 2>/dev/null
+    fi
+}
+
+send_message() {
+    local message="$1"
+    printf "Content-Length: %d\r\n\r\n%s" "${#message}" "$message"
+}
+
+while true; do
+    msg=$(read_message)
+    if [ -z "$msg" ]; then break; fi
+    method=$(echo "$msg" | grep -o '"method":"[^"]*"' | cut -d'"' -f4)
+    msg_id=$(echo "$msg" | grep -o '"id":[0-9]*' | cut -d':' -f2)
+    case "$method" in
+        "initialize")
+            send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":{"capabilities":{"textDocumentSync":2}}}'
+            ;;
+        "textDocument/didChange")
+            echo "$msg" >> "$LOG_FILE"
+            ;;
+        "shutdown")
+            send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":null}'
+            break
+            ;;
+        *)
+            if [ -n "$msg_id" ]; then
+                send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":null}'
+            fi
+            ;;
+    esac
+done
+"##;
+
+/// Editing a text file in hex view tells its language server about the
+/// edited line only, not the whole document.
+#[test]
+#[cfg(unix)]
+fn test_hex_edit_sends_incremental_lsp_change() -> anyhow::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp_dir = TempDir::new()?;
+    let script_path = temp_dir.path().join("fake_lsp.sh");
+    std::fs::write(&script_path, DID_CHANGE_LOGGING_LSP)?;
+    std::fs::set_permissions(&script_path, std::fs::Permissions::from_mode(0o755))?;
+    let log_file = temp_dir.path().join("did_change.log");
+    let path = temp_dir.path().join("main.rs");
+    std::fs::write(&path, "fn main() {}\nfn other() {}\n")?;
+
+    let mut config = fresh::config::Config::default();
+    config.lsp.insert(
+        "rust".to_string(),
+        fresh::types::LspLanguageConfig::Multi(vec![fresh::services::lsp::LspServerConfig {
+            command: script_path.to_string_lossy().to_string(),
+            args: Some(vec![log_file.to_string_lossy().to_string()]),
+            enabled: true,
+            auto_start: true,
+            process_limits: fresh::services::process_limits::ProcessLimits::default(),
+            initialization_options: None,
+            env: Default::default(),
+            language_id_overrides: Default::default(),
+            root_markers: Default::default(),
+            name: None,
+            only_features: None,
+            except_features: None,
+        }]),
+    );
+    let mut harness = EditorTestHarness::create(
+        100,
+        24,
+        crate::common::harness::HarnessOptions::new()
+            .with_config(config)
+            .with_working_dir(temp_dir.path().to_path_buf()),
+    )?;
+    harness.open_file(&path)?;
+    harness.render()?;
+    harness.wait_for_screen_contains("LSP (on)")?;
+
+    harness
+        .editor_mut()
+        .dispatch_action_for_tests(Action::ToggleHexView);
+    // 0x66 'f' becomes 0x46 'F'.
+    harness.type_text("4")?;
+    harness.render()?;
+
+    harness.wait_until(|_| {
+        std::fs::read_to_string(&log_file)
+            .unwrap_or_default()
+            .contains("didChange")
+    })?;
+    let log = std::fs::read_to_string(&log_file)?;
+    assert!(
+        log.contains(
+            r#""range":{"start":{"line":0,"character":0},"end":{"line":1,"character":0}}"#
+        ),
+        "didChange should replace the first line only: {log}"
+    );
+    assert!(log.contains(r#""text":"Fn main() {}\n""#), "{log}");
+    assert!(!log.contains("other"), "{log}");
+    Ok(())
+}
@@ -6047,7 +6047,19 @@ fn test_completion_snippet_with_default() -> anyhow::Result<()> {
         "Snippet should expand with default text"
     );
 
-    // Verify cursor is at $0 position (after the 4 spaces on line 2)
+    // The first placeholder is selected, ready to be typed over
+    let cursor = *harness.editor().active_cursors().primary();
+    assert_eq!(
+        cursor.selection_range(),
+        Some(3..7),
+        "$1 should be selected"
+    );
+
+    // Tab steps to the empty $2, then to $0 (after the 4 spaces on line 2)
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE)?;
+    let cursor_pos = harness.editor().active_cursors().primary().position;
+    assert_eq!(cursor_pos, 8, "Cursor should be at $2 position");
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE)?;
     let cursor_pos = harness.editor().active_cursors().primary().position;
     assert_eq!(cursor_pos, 16, "Cursor should be at $0 position");
 
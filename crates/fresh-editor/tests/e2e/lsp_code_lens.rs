@@ -0,0 +1,173 @@
+//! E2E tests for LSP code lenses: lenses are drawn as a virtual line above
+//! the line they annotate, unresolved lenses are resolved once on screen,
+//! and clicking a title sends its command via `workspace/executeCommand`.
+
+use crate::common::harness::EditorTestHarness;
+
+/// Fake server returning two lenses for line 0: a resolved "Run" lens and a
+/// reference-count lens that needs `codeLens/resolve`. Every method is logged
+/// to the file passed as the first argument.
+const FAKE_LSP_SCRIPT: &str = r##"#!/bin/bash
+
+LOG_FILE="$1"
+> "$LOG_FILE"
+
+read_message() {
+    local content_length=0
+    while IFS=: read -r key value; do
+        key=$(echo "$key" | tr -d '\r\n')
+        value=$(echo "$value" | tr -d '\r\n ')
+        if [ "$key" = "Content-Length" ]; then
+            content_length=$value
+        fi
+        if [ -z "$key" ]; then
+            break
+        fi
+    done
+    if [ $content_length -gt 0 ]; then
+        dd bs=1 count=$content_length 2>/dev/null
+    fi
+}
+
+send_message() {
+    local message="$1"
+    local length=${#message}
+    printf "Content-Length: $length\r\n\r\n%s" "$message"
+}
+
+RANGE='{"start":{"line":0,"character":3},"end":{"line":0,"character":7}}'
+
+while true; do
+    msg=$(read_message)
+    if [ -z "$msg" ]; then break; fi
+
+    method=$(echo "$msg" | grep -o '"method":"[^"]*"' | cut -d'"' -f4)
+    msg_id=$(echo "$msg" | grep -o '"id":[0-9]*' | head -1 | cut -d':' -f2)
+
+    echo "METHOD:$method" >> "$LOG_FILE"
+    if [ "$method" = "workspace/executeCommand" ]; then
+        echo "BODY:$msg" >> "$LOG_FILE"
+    fi
+
+    case "$method" in
+        "initialize")
+            send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":{"capabilities":{"textDocumentSync":1,"codeLensProvider":{"resolveProvider":true},"executeCommandProvider":{"commands":["test.run","test.refs"]}}}}'
+            ;;
+        "textDocument/codeLens")
+            send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":[{"range":'"$RANGE"',"command":{"title":"Run","command":"test.run","arguments":["main"]}},{"range":'"$RANGE"',"data":{"kind":"refs"}}]}'
+            ;;
+        "codeLens/resolve")
+            send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":{"range":'"$RANGE"',"command":{"title":"3 references","command":"test.refs"}}}'
+            ;;
+        "shutdown")
+            send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":null}'
+            break
+            ;;
+        *)
+            if [ -n "$msg_id" ]; then
+                send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":null}'
+            fi
+            ;;
+    esac
+done
+"##;
+
+fn setup_editor(
+    temp_dir: &tempfile::TempDir,
+    log_file: &std::path::Path,
+) -> anyhow::Result<EditorTestHarness> {
+    let script_path = temp_dir.path().join("fake_lsp_code_lens.sh");
+    std::fs::write(&script_path, FAKE_LSP_SCRIPT)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = std::fs::metadata(&script_path)?.permissions();
+        perms.set_mode(0o755);
+        std::fs::set_permissions(&script_path, perms)?;
+    }
+
+    let test_file = temp_dir.path().join("test.rs");
+    std::fs::write(&test_file, "fn main() {\n    let x = 5;\n}\n")?;
+
+    let mut config = fresh::config::Config::default();
+    config.lsp.insert(
+        "rust".to_string(),
+        fresh::types::LspLanguageConfig::Multi(vec![fresh::services::lsp::LspServerConfig {
+            command: script_path.to_string_lossy().to_string(),
+            args: Some(vec![log_file.to_string_lossy().to_string()]),
+            enabled: true,
+            auto_start: true,
+            process_limits: fresh::services::process_limits::ProcessLimits::default(),
+            initialization_options: None,
+            env: Default::default(),
+            language_id_overrides: Default::default(),
+            root_markers: Default::default(),
+            name: None,
+            only_features: None,
+            except_features: None,
+        }]),
+    );
+
+    let mut harness = EditorTestHarness::create(
+        80,
+        24,
+        crate::common::harness::HarnessOptions::new()
+            .with_config(config)
+            .with_working_dir(temp_dir.path().to_path_buf()),
+    )?;
+
+    harness.open_file(&test_file)?;
+    harness.render()?;
+    Ok(harness)
+}
+
+#[test]
+#[cfg_attr(target_os = "windows", ignore)]
+fn test_code_lenses_render_above_line_after_resolve() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let log_file = temp_dir.path().join("lsp_code_lens_log.txt");
+    let mut harness = setup_editor(&temp_dir, &log_file)?;
+
+    // The reference count only appears once the lens was resolved.
+    harness.wait_for_screen_contains("Run | 3 references")?;
+
+    let (_, lens_row) = harness
+        .find_text_on_screen("Run | 3 references")
+        .expect("lens line on screen");
+    let (_, code_row) = harness
+        .find_text_on_screen("fn main()")
+        .expect("code line on screen");
+    assert_eq!(lens_row + 1, code_row, "lens sits directly above its line");
+
+    let log = std::fs::read_to_string(&log_file)?;
+    assert!(log.contains("METHOD:codeLens/resolve"));
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(target_os = "windows", ignore)]
+fn test_clicking_code_lens_executes_its_command() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let log_file = temp_dir.path().join("lsp_code_lens_log.txt");
+    let mut harness = setup_editor(&temp_dir, &log_file)?;
+
+    harness.wait_for_screen_contains("Run | 3 references")?;
+    let (col, row) = harness
+        .find_text_on_screen("3 references")
+        .expect("lens title on screen");
+
+    harness.mouse_click(col + 1, row)?;
+    harness.wait_until(|_| {
+        let log = std::fs::read_to_string(&log_file).unwrap_or_default();
+        log.contains("METHOD:workspace/executeCommand")
+    })?;
+
+    let log = std::fs::read_to_string(&log_file)?;
+    assert!(log.contains(r#""command":"test.refs""#), "log: {log}");
+    assert!(!log.contains(r#""command":"test.run""#));
+
+    // The click ran the lens; the cursor stays where it was.
+    assert_eq!(harness.cursor_position(), 0);
+    Ok(())
+}
@@ -0,0 +1,173 @@
+//! E2E tests for LSP linked editing ranges and on-type formatting: typing in
+//! an HTML tag name mirrors into its closing tag through a temporary
+//! multi-cursor session, and the server's on-type edits undo together with
+//! the keystroke that triggered them.
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+/// Fake server linking the names of `<div>x</div>` (columns 1..4 and 8..11)
+/// for positions inside the opening tag's name, and indenting the line by two spaces whenever `;` is typed. Every method
+/// is logged to the file passed as the first argument.
+const FAKE_LSP_SCRIPT: &str = r##"#!/bin/bash
+
+LOG_FILE="$1"
+> "$LOG_FILE"
+
+read_message() {
+    local content_length=0
+    while IFS=: read -r key value; do
+        key=$(echo "$key" | tr -d '\r\n')
+        value=$(echo "$value" | tr -d '\r\n ')
+        if [ "$key" = "Content-Length" ]; then
+            content_length=$value
+        fi
+        if [ -z "$key" ]; then
+            break
+        fi
+    done
+    if [ $content_length -gt 0 ]; then
+        dd bs=1 count=$content_length 2>/dev/null
+    fi
+}
+
+send_message() {
+    local message="$1"
+    local length=${#message}
+    printf "Content-Length: $length\r\n\r\n%s" "$message"
+}
+
+while true; do
+    msg=$(read_message)
+    if [ -z "$msg" ]; then break; fi
+
+    method=$(echo "$msg" | grep -o '"method":"[^"]*"' | cut -d'"' -f4)
+    msg_id=$(echo "$msg" | grep -o '"id":[0-9]*' | head -1 | cut -d':' -f2)
+
+    echo "METHOD:$method" >> "$LOG_FILE"
+
+    case "$method" in
+        "initialize")
+            send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":{"capabilities":{"textDocumentSync":1,"linkedEditingRangeProvider":true,"documentOnTypeFormattingProvider":{"firstTriggerCharacter":";","moreTriggerCharacter":["}"]}}}}'
+            ;;
+        "textDocument/linkedEditingRange")
+            # Only the opening tag's name is linked in this fixture.
+            character=$(echo "$msg" | grep -o '"character":[0-9]*' | head -1 | cut -d':' -f2)
+            if [ "$character" -le 4 ]; then
+                send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":{"ranges":[{"start":{"line":0,"character":1},"end":{"line":0,"character":4}},{"start":{"line":0,"character":8},"end":{"line":0,"character":11}}]}}'
+            else
+                send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":null}'
+            fi
+            ;;
+        "textDocument/onTypeFormatting")
+            send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":[{"range":{"start":{"line":0,"character":0},"end":{"line":0,"character":0}},"newText":"  "}]}'
+            ;;
+        "shutdown")
+            send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":null}'
+            break
+            ;;
+        *)
+            if [ -n "$msg_id" ]; then
+                send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":null}'
+            fi
+            ;;
+    esac
+done
+"##;
+
+fn setup_editor(
+    temp_dir: &tempfile::TempDir,
+    log_file: &std::path::Path,
+) -> anyhow::Result<EditorTestHarness> {
+    let script_path = temp_dir.path().join("fake_lsp_linked_editing.sh");
+    std::fs::write(&script_path, FAKE_LSP_SCRIPT)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = std::fs::metadata(&script_path)?.permissions();
+        perms.set_mode(0o755);
+        std::fs::set_permissions(&script_path, perms)?;
+    }
+
+    let test_file = temp_dir.path().join("test.html");
+    std::fs::write(&test_file, "<div>x</div>\n")?;
+
+    let mut config = fresh::config::Config::default();
+    config.lsp.insert(
+        "html".to_string(),
+        fresh::types::LspLanguageConfig::Multi(vec![fresh::services::lsp::LspServerConfig {
+            command: script_path.to_string_lossy().to_string(),
+            args: Some(vec![log_file.to_string_lossy().to_string()]),
+            enabled: true,
+            auto_start: true,
+            process_limits: fresh::services::process_limits::ProcessLimits::default(),
+            initialization_options: None,
+            env: Default::default(),
+            language_id_overrides: Default::default(),
+            root_markers: Default::default(),
+            name: None,
+            only_features: None,
+            except_features: None,
+        }]),
+    );
+
+    let mut harness = EditorTestHarness::create(
+        80,
+        24,
+        crate::common::harness::HarnessOptions::new()
+            .with_config(config)
+            .with_working_dir(temp_dir.path().to_path_buf()),
+    )?;
+
+    harness.open_file(&test_file)?;
+    harness.wait_until(|_| {
+        let log = std::fs::read_to_string(log_file).unwrap_or_default();
+        log.contains("METHOD:textDocument/didOpen")
+    })?;
+    Ok(harness)
+}
+
+#[test]
+#[cfg_attr(target_os = "windows", ignore)]
+fn test_typing_in_tag_name_updates_closing_tag() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let log_file = temp_dir.path().join("lsp_linked_editing_log.txt");
+    let mut harness = setup_editor(&temp_dir, &log_file)?;
+
+    // Park the cursor at the end of the opening tag's name.
+    harness.send_key_repeat(KeyCode::Right, KeyModifiers::NONE, 4)?;
+    harness.wait_until(|h| h.cursor_count() == 2)?;
+    assert_eq!(harness.cursor_position(), 4, "view stays on the typed tag");
+
+    harness.type_text("ider")?;
+    harness.assert_buffer_content("<divider>x</divider>\n");
+
+    // A space starts an attribute: the session ends before it is typed, so
+    // the closing tag is left alone.
+    harness.type_text(" id")?;
+    harness.assert_buffer_content("<divider id>x</divider>\n");
+    assert_eq!(harness.cursor_count(), 1);
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(target_os = "windows", ignore)]
+fn test_on_type_formatting_undoes_with_its_keystroke() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let log_file = temp_dir.path().join("lsp_on_type_log.txt");
+    let mut harness = setup_editor(&temp_dir, &log_file)?;
+
+    harness.send_key(KeyCode::End, KeyModifiers::NONE)?;
+    harness.type_text(";")?;
+    harness.wait_until(|h| h.get_buffer_content().is_some_and(|c| c.starts_with("  ")))?;
+    harness.assert_buffer_content("  <div>x</div>;\n");
+
+    let log = std::fs::read_to_string(&log_file)?;
+    assert!(log.contains("METHOD:textDocument/onTypeFormatting"));
+
+    // One undo reverts the formatting together with the `;`.
+    harness.send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)?;
+    harness.assert_buffer_content("<div>x</div>\n");
+    Ok(())
+}
@@ -0,0 +1,192 @@
+//! E2E tests for Expand / Shrink Selection backed by LSP selection ranges:
+//! the server is asked once for every cursor's chain of enclosing ranges,
+//! and each cursor then steps through its own chain.
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+/// Fake server answering `textDocument/selectionRange` with the same chain
+/// for each requested line of `<p>one two</p>`: the element's text
+/// (columns 3..10), then the whole element (columns 0..14). Every method
+/// and each selection range request is logged to the file passed as the
+/// first argument.
+const FAKE_LSP_SCRIPT: &str = r##"#!/bin/bash
+
+LOG_FILE="$1"
+> "$LOG_FILE"
+
+read_message() {
+    local content_length=0
+    while IFS=: read -r key value; do
+        key=$(echo "$key" | tr -d '\r\n')
+        value=$(echo "$value" | tr -d '\r\n ')
+        if [ "$key" = "Content-Length" ]; then
+            content_length=$value
+        fi
+        if [ -z "$key" ]; then
+            break
+        fi
+    done
+    if [ $content_length -gt 0 ]; then
+        dd bs=1 count=$content_length 2>/dev/null
+    fi
+}
+
+send_message() {
+    local message="$1"
+    local length=${#message}
+    printf "Content-Length: $length\r\n\r\n%s" "$message"
+}
+
+while true; do
+    msg=$(read_message)
+    if [ -z "$msg" ]; then break; fi
+
+    method=$(echo "$msg" | grep -o '"method":"[^"]*"' | cut -d'"' -f4)
+    msg_id=$(echo "$msg" | grep -o '"id":[0-9]*' | head -1 | cut -d':' -f2)
+
+    echo "METHOD:$method" >> "$LOG_FILE"
+
+    case "$method" in
+        "initialize")
+            send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":{"capabilities":{"textDocumentSync":1,"selectionRangeProvider":true}}}'
+            ;;
+        "textDocument/selectionRange")
+            lines=$(echo "$msg" | grep -o '"line":[0-9]*' | cut -d':' -f2)
+            echo "LINES:$(echo $lines)" >> "$LOG_FILE"
+            result=""
+            for line in $lines; do
+                inner='{"start":{"line":'$line',"character":3},"end":{"line":'$line',"character":10}}'
+                outer='{"start":{"line":'$line',"character":0},"end":{"line":'$line',"character":14}}'
+                [ -n "$result" ] && result="$result,"
+                result="$result"'{"range":'$inner',"parent":{"range":'$outer'}}'
+            done
+            send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":['"$result"']}'
+            ;;
+        "shutdown")
+            send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":null}'
+            break
+            ;;
+        *)
+            if [ -n "$msg_id" ]; then
+                send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":null}'
+            fi
+            ;;
+    esac
+done
+"##;
+
+fn setup_editor(
+    temp_dir: &tempfile::TempDir,
+    log_file: &std::path::Path,
+) -> anyhow::Result<EditorTestHarness> {
+    let script_path = temp_dir.path().join("fake_lsp_selection_range.sh");
+    std::fs::write(&script_path, FAKE_LSP_SCRIPT)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = std::fs::metadata(&script_path)?.permissions();
+        perms.set_mode(0o755);
+        std::fs::set_permissions(&script_path, perms)?;
+    }
+
+    let test_file = temp_dir.path().join("test.html");
+    std::fs::write(&test_file, "<p>one two</p>\n<p>six ten</p>\n")?;
+
+    let mut config = fresh::config::Config::default();
+    config.lsp.insert(
+        "html".to_string(),
+        fresh::types::LspLanguageConfig::Multi(vec![fresh::services::lsp::LspServerConfig {
+            command: script_path.to_string_lossy().to_string(),
+            args: Some(vec![log_file.to_string_lossy().to_string()]),
+            enabled: true,
+            auto_start: true,
+            process_limits: fresh::services::process_limits::ProcessLimits::default(),
+            initialization_options: None,
+            env: Default::default(),
+            language_id_overrides: Default::default(),
+            root_markers: Default::default(),
+            name: None,
+            only_features: None,
+            except_features: None,
+        }]),
+    );
+
+    let mut harness = EditorTestHarness::create(
+        80,
+        24,
+        crate::common::harness::HarnessOptions::new()
+            .with_config(config)
+            .with_working_dir(temp_dir.path().to_path_buf()),
+    )?;
+
+    harness.open_file(&test_file)?;
+    harness.wait_until(|_| {
+        let log = std::fs::read_to_string(log_file).unwrap_or_default();
+        log.contains("METHOD:textDocument/didOpen")
+    })?;
+    Ok(harness)
+}
+
+/// Selected text of every cursor, in buffer order.
+fn selections(harness: &mut EditorTestHarness) -> Vec<String> {
+    let mut ranges: Vec<_> = harness
+        .editor()
+        .active_cursors()
+        .iter()
+        .map(|(_, c)| c.selection_range().unwrap_or(c.position..c.position))
+        .collect();
+    ranges.sort_by_key(|r| r.start);
+    ranges
+        .into_iter()
+        .map(|r| {
+            harness
+                .editor_mut()
+                .active_state_mut()
+                .get_text_range(r.start, r.end)
+        })
+        .collect()
+}
+
+#[test]
+#[cfg_attr(target_os = "windows", ignore)]
+fn test_expand_selection_uses_lsp_ranges_for_each_cursor() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let log_file = temp_dir.path().join("lsp_selection_range_log.txt");
+    let mut harness = setup_editor(&temp_dir, &log_file)?;
+
+    // A cursor on "one" and one on "six" below it.
+    harness.send_key_repeat(KeyCode::Right, KeyModifiers::NONE, 4)?;
+    harness.send_key(KeyCode::Down, KeyModifiers::CONTROL | KeyModifiers::ALT)?;
+    assert_eq!(harness.cursor_count(), 2);
+
+    // The word alone would be "one"; the server's first range is the
+    // element's whole text.
+    harness.run_palette_command("Expand Selection")?;
+    harness.wait_until(|h| {
+        h.editor()
+            .active_cursors()
+            .iter()
+            .all(|(_, c)| c.selection_range().is_some())
+    })?;
+    assert_eq!(selections(&mut harness), vec!["one two", "six ten"]);
+
+    harness.run_palette_command("Expand Selection")?;
+    harness.render()?;
+    assert_eq!(
+        selections(&mut harness),
+        vec!["<p>one two</p>", "<p>six ten</p>"]
+    );
+
+    harness.run_palette_command("Shrink Selection")?;
+    harness.render()?;
+    assert_eq!(selections(&mut harness), vec!["one two", "six ten"]);
+
+    // One request covered both cursors, and the cached chains served the
+    // second expansion.
+    let log = std::fs::read_to_string(&log_file)?;
+    let requests: Vec<_> = log.lines().filter(|l| l.starts_with("LINES:")).collect();
+    assert_eq!(requests, vec!["LINES:0 1"]);
+    Ok(())
+}
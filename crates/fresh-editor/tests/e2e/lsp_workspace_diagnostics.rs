@@ -0,0 +1,190 @@
+//! Workspace diagnostics pull (`workspace/diagnostic`).
+//!
+//! A server that advertises `workspaceDiagnostics` reports problems in files
+//! that are not open. The fake server below streams one such report as a
+//! partial result (`$/progress` on the request's `partialResultToken`) and
+//! then answers the request itself with nothing more. The editor must keep
+//! the streamed report, list it with the other diagnostics, and let
+//! `JumpToNextError` open the file and land on it.
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+/// Fake server: answers document pulls with no diagnostics and workspace
+/// pulls with one warning in `other.rs`, streamed as a partial result.
+fn create_workspace_diagnostic_server_script(dir: &std::path::Path) -> std::path::PathBuf {
+    let script = r##"#!/bin/bash
+LOG_FILE="${1:-/tmp/fake_workspace_diag_log.txt}"
+OTHER_URI="${2}"
+> "$LOG_FILE"
+
+read_message() {
+    local content_length=0
+    while IFS=: read -r key value; do
+        key=$(echo "$key" | tr -d '\r\n')
+        value=$(echo "$value" | tr -d '\r\n ')
+        if [ "$key" = "Content-Length" ]; then
+            content_length=$value
+        fi
+        if [ -z "$key" ]; then
+            break
+        fi
+    done
+    if [ $content_length -gt 0 ]; then
+        dd bs=1 count=$content_length 2>/dev/null
+    fi
+}
+
+send_message() {
+    local message="$1"
+    local length=${#message}
+    printf "Content-Length: $length\r\n\r\n%s" "$message"
+}
+
+while true; do
+    msg=$(read_message)
+    if [ -z "$msg" ]; then
+        break
+    fi
+    method=$(echo "$msg" | grep -o '"method":"[^"]*"' | cut -d'"' -f4)
+    msg_id=$(echo "$msg" | grep -o '"id":[0-9]*' | cut -d':' -f2)
+    echo "RECV: method=$method id=$msg_id" >> "$LOG_FILE"
+
+    case "$method" in
+        "initialize")
+            send_message '{"jsonrpc":"2.0","id":'"$msg_id"',"result":{"capabilities":{"positionEncoding":"utf-16","textDocumentSync":{"openClose":true,"change":2},"diagnosticProvider":{"interFileDependencies":true,"workspaceDiagnostics":true}}}}'
+            ;;
+        "initialized"|"textDocument/didOpen"|"textDocument/didChange"|"$/cancelRequest")
+            ;;
+        "textDocument/diagnostic")
+            send_message '{"jsonrpc":"2.0","id":'"$msg_id"',"result":{"kind":"full","resultId":"doc","items":[]}}'
+            ;;
+        "workspace/diagnostic")
+            TOKEN=$(echo "$msg" | grep -o '"partialResultToken":"[^"]*"' | cut -d'"' -f4)
+            echo "WORKSPACE: token=$TOKEN" >> "$LOG_FILE"
+            send_message '{"jsonrpc":"2.0","method":"$/progress","params":{"token":"'"$TOKEN"'","value":{"items":[{"kind":"full","uri":"'"$OTHER_URI"'","version":null,"resultId":"ws-1","items":[{"range":{"start":{"line":1,"character":4},"end":{"line":1,"character":5}},"severity":2,"message":"unused variable from workspace pull"}]}]}}}'
+            send_message '{"jsonrpc":"2.0","id":'"$msg_id"',"result":{"items":[]}}'
+            ;;
+        "shutdown")
+            send_message '{"jsonrpc":"2.0","id":'"$msg_id"',"result":null}'
+            break
+            ;;
+        *)
+            if [ -n "$method" ] && [ -n "$msg_id" ]; then
+                send_message '{"jsonrpc":"2.0","id":'"$msg_id"',"result":null}'
+            fi
+            ;;
+    esac
+done
+"##;
+
+    let script_path = dir.join("fake_workspace_diag_server.sh");
+    std::fs::write(&script_path, script).expect("Failed to write fake workspace diagnostic server");
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = std::fs::metadata(&script_path).unwrap().permissions();
+        perms.set_mode(0o755);
+        std::fs::set_permissions(&script_path, perms).unwrap();
+    }
+
+    script_path
+}
+
+/// Diagnostics streamed by a workspace pull for a closed file show up in the
+/// stored diagnostics, and F8 opens that file at the diagnostic.
+#[test]
+#[cfg_attr(target_os = "windows", ignore)]
+fn test_workspace_diagnostics_for_closed_file_reach_next_error() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let main_file = temp_dir.path().join("main.rs");
+    std::fs::write(&main_file, "fn main() {}\n")?;
+    let other_file = temp_dir.path().join("other.rs");
+    std::fs::write(&other_file, "fn a() {}\nlet x = 1;\n")?;
+    let other_uri = format!("file://{}", other_file.to_str().unwrap());
+
+    let script_path = create_workspace_diagnostic_server_script(temp_dir.path());
+    let log_file = temp_dir.path().join("workspace_diag_log.txt");
+
+    let mut config = fresh::config::Config::default();
+    config.lsp.insert(
+        "rust".to_string(),
+        fresh::types::LspLanguageConfig::Multi(vec![fresh::services::lsp::LspServerConfig {
+            command: script_path.to_string_lossy().to_string(),
+            args: Some(vec![
+                log_file.to_string_lossy().to_string(),
+                other_uri.clone(),
+            ]),
+            enabled: true,
+            auto_start: true,
+            process_limits: fresh::services::process_limits::ProcessLimits::default(),
+            initialization_options: None,
+            env: Default::default(),
+            language_id_overrides: Default::default(),
+            root_markers: Default::default(),
+            name: None,
+            only_features: None,
+            except_features: None,
+        }]),
+    );
+
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        100,
+        24,
+        config,
+        temp_dir.path().to_path_buf(),
+    )?;
+    harness.open_file(&main_file)?;
+    harness.render()?;
+
+    // The streamed report for the closed file lands in the stored
+    // diagnostics that feed the diagnostics panel.
+    harness.wait_until(|h| {
+        h.editor()
+            .get_stored_diagnostics()
+            .get(&other_uri)
+            .is_some_and(|d| !d.is_empty())
+    })?;
+    let log = std::fs::read_to_string(&log_file)?;
+    assert!(
+        log.contains("WORKSPACE: token=fresh/workspaceDiagnostic/"),
+        "workspace pull should carry a partial result token.\nLog:\n{log}"
+    );
+
+    // main.rs has no diagnostics of its own, so F8 moves into other.rs.
+    harness.send_key(KeyCode::F(8), KeyModifiers::NONE)?;
+    harness.render()?;
+
+    let active_path = harness
+        .editor()
+        .active_state()
+        .buffer
+        .file_path()
+        .map(|p| p.to_path_buf());
+    assert_eq!(
+        active_path.as_deref().and_then(|p| p.file_name()),
+        Some(std::ffi::OsStr::new("other.rs"))
+    );
+    // Line 1, character 4: "fn a() {}\n" is 10 bytes, then "let ".
+    assert_eq!(harness.cursor_position(), 14);
+    assert_eq!(
+        harness.editor().get_status_message().map(String::as_str),
+        Some("unused variable from workspace pull")
+    );
+
+    // The newly opened buffer shows the stored diagnostic as an overlay.
+    let has_overlay = harness
+        .editor()
+        .active_state()
+        .overlays
+        .all()
+        .iter()
+        .any(|o| o.message.as_deref() == Some("unused variable from workspace pull"));
+    assert!(
+        has_overlay,
+        "opened file should show the workspace diagnostic"
+    );
+
+    Ok(())
+}
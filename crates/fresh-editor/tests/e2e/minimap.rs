@@ -0,0 +1,168 @@
+//! End-to-end coverage for the minimap: the braille overview drawn between
+//! the content area and the vertical scrollbar.
+
+use crate::common::harness::EditorTestHarness;
+use fresh::config::Config;
+use fresh::input::keybindings::Action;
+
+const WIDTH: u16 = 100;
+/// Default minimap width, left of the one-column scrollbar.
+const MINIMAP_WIDTH: u16 = 12;
+const MINIMAP_X: u16 = WIDTH - 1 - MINIMAP_WIDTH;
+
+fn long_content(num_lines: usize) -> String {
+    (0..num_lines)
+        .map(|i| format!("line {i:04}: lorem ipsum dolor sit amet"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn minimap_config() -> Config {
+    let mut config = Config::default();
+    config.editor.show_minimap = true;
+    config
+}
+
+fn is_braille(cell: &str) -> bool {
+    cell.chars()
+        .next()
+        .is_some_and(|c| ('\u{2801}'..='\u{28FF}').contains(&c))
+}
+
+/// Rows of the minimap that draw any text.
+fn minimap_rows_with_text(harness: &EditorTestHarness) -> usize {
+    let (first, last) = harness.content_area_rows();
+    (first..=last)
+        .filter(|&row| {
+            (MINIMAP_X..MINIMAP_X + MINIMAP_WIDTH).any(|col| {
+                harness
+                    .get_cell(col, row as u16)
+                    .is_some_and(|c| is_braille(&c))
+            })
+        })
+        .count()
+}
+
+/// The minimap is off by default, and when enabled draws every line as a
+/// row of braille dots, four lines per row.
+#[test]
+fn test_minimap_draws_braille_overview() {
+    let mut harness = EditorTestHarness::new(WIDTH, 24).unwrap();
+    let _fixture = harness.load_buffer_from_text(&long_content(400)).unwrap();
+    harness.render().unwrap();
+    assert_eq!(minimap_rows_with_text(&harness), 0);
+
+    let mut harness = EditorTestHarness::with_config(WIDTH, 24, minimap_config()).unwrap();
+    let _fixture = harness.load_buffer_from_text(&long_content(400)).unwrap();
+    harness.render().unwrap();
+    let (first, last) = harness.content_area_rows();
+    assert_eq!(minimap_rows_with_text(&harness), last - first + 1);
+    // "line 0000: lorem" fills all four dot rows of the first cells
+    assert_eq!(
+        harness.get_cell(MINIMAP_X, first as u16).as_deref(),
+        Some("⣿")
+    );
+}
+
+/// Clicking a minimap row scrolls the editor to the lines it shows, and
+/// dragging keeps scrolling with the mouse.
+#[test]
+fn test_minimap_click_and_drag_scroll() {
+    let mut harness = EditorTestHarness::with_config(WIDTH, 24, minimap_config()).unwrap();
+    let _fixture = harness.load_buffer_from_text(&long_content(400)).unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.top_byte(), 0);
+
+    let (first, last) = harness.content_area_rows();
+    harness.mouse_click(MINIMAP_X + 2, last as u16).unwrap();
+    let after_click = harness.top_byte();
+    assert!(after_click > 0, "clicking the last row should scroll down");
+    // The cursor is moved into the new viewport
+    assert!(harness.cursor_position() >= after_click);
+
+    harness
+        .mouse_drag(MINIMAP_X + 2, last as u16, MINIMAP_X + 2, first as u16)
+        .unwrap();
+    assert!(
+        harness.top_byte() < after_click,
+        "dragging up should scroll back up"
+    );
+}
+
+/// Files over the large-file threshold are sampled evenly across the whole
+/// file, so the last row of the minimap jumps close to the end.
+#[test]
+fn test_minimap_samples_large_files() {
+    let mut config = minimap_config();
+    config.editor.large_file_threshold_bytes = 500;
+    let content = long_content(2000);
+    let mut harness = EditorTestHarness::with_config(WIDTH, 24, config).unwrap();
+    let _fixture = harness.load_buffer_from_text(&content).unwrap();
+    harness.render().unwrap();
+    let (first, last) = harness.content_area_rows();
+    assert_eq!(minimap_rows_with_text(&harness), last - first + 1);
+
+    harness.mouse_click(MINIMAP_X, last as u16).unwrap();
+    assert!(
+        harness.top_byte() > content.len() * 3 / 4,
+        "top byte {} should be near the end of {} bytes",
+        harness.top_byte(),
+        content.len()
+    );
+}
+
+/// Scrollbar markers — diagnostics and plugin marks — show as a coloured
+/// stripe down the left edge of the minimap.
+#[cfg(feature = "plugins")]
+#[test]
+fn test_minimap_shows_scrollbar_markers() {
+    use fresh_core::api::{OverlayColorSpec, PluginCommand, ScrollbarMarker};
+    use fresh_core::BufferId;
+    use ratatui::style::Color;
+
+    let mut harness = EditorTestHarness::with_config(WIDTH, 24, minimap_config()).unwrap();
+    let _fixture = harness.load_buffer_from_text(&long_content(400)).unwrap();
+    harness
+        .editor_mut()
+        .handle_plugin_command(PluginCommand::SetScrollbarMarkers {
+            buffer_id: BufferId(1),
+            namespace: "test".into(),
+            markers: vec![ScrollbarMarker {
+                position: None,
+                line: Some(5),
+                end: None,
+                end_line: None,
+                color: OverlayColorSpec::Rgb(200, 30, 30),
+                priority: None,
+            }],
+        })
+        .expect("plugin command accepted");
+    harness.render().unwrap();
+
+    let (first, _) = harness.content_area_rows();
+    // Line 5 is on the second row of the minimap (four lines per row)
+    let row = first as u16 + 1;
+    let bg = |col, row| harness.get_cell_style(col, row).and_then(|s| s.bg);
+    assert_eq!(bg(MINIMAP_X, row), Some(Color::Rgb(200, 30, 30)));
+    assert_ne!(bg(MINIMAP_X + 1, row), Some(Color::Rgb(200, 30, 30)));
+    assert_ne!(bg(MINIMAP_X, row + 1), Some(Color::Rgb(200, 30, 30)));
+}
+
+/// The minimap toggles at runtime, giving its columns back to the text.
+#[test]
+fn test_toggle_minimap() {
+    let mut harness = EditorTestHarness::new(WIDTH, 24).unwrap();
+    let _fixture = harness.load_buffer_from_text(&long_content(400)).unwrap();
+    harness
+        .editor_mut()
+        .dispatch_action_for_tests(Action::ToggleMinimap);
+    harness.render().unwrap();
+    assert!(minimap_rows_with_text(&harness) > 0);
+    harness.assert_screen_contains("Minimap shown");
+
+    harness
+        .editor_mut()
+        .dispatch_action_for_tests(Action::ToggleMinimap);
+    harness.render().unwrap();
+    assert_eq!(minimap_rows_with_text(&harness), 0);
+}
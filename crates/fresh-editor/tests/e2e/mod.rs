@@ -17,6 +17,7 @@ pub mod buffer_lifecycle;
 pub mod buffer_settings_commands;
 pub mod capslock_shortcuts;
 pub mod cargo_config_editing;
+pub mod clipboard_history;
 pub mod code_tour_dock;
 pub mod command_palette;
 pub mod config_language_selector;
@@ -27,6 +28,7 @@ pub mod csi_u_session_input;
 pub mod ctrl_slash_legacy_terminal;
 pub mod cursor_style_rendering;
 pub mod dabbrev_completion;
+pub mod debugger;
 pub mod dock_create_terminal_resize;
 #[cfg(feature = "plugins")]
 pub mod dock_dropdown_mouse;
@@ -35,6 +37,7 @@ pub mod dock_focus_stuck_born_attached;
 pub mod dock_panel_routing;
 #[cfg(feature = "plugins")]
 pub mod dock_switch_wipes_outgoing_window;
+pub mod document_links;
 pub mod document_model;
 pub mod emacs_actions;
 pub mod encoding;
@@ -58,6 +61,7 @@ pub mod folding;
 pub mod glob_language_detection;
 #[cfg(feature = "gui")]
 pub mod gui;
+pub mod hex_view;
 pub mod hot_exit_flows;
 pub mod hot_exit_recovery_lsp_sync;
 pub mod indent_dedent;
@@ -135,6 +139,7 @@ pub mod lsp_bulk_edit_undo_desync;
 pub mod lsp_code_action_diagnostic_context;
 pub mod lsp_code_action_modal;
 pub mod lsp_code_action_resolve_and_commands;
+pub mod lsp_code_lens;
 pub mod lsp_completion_duplicate_entries_1514;
 pub mod lsp_completion_duplicate_label_import_2952;
 pub mod lsp_completion_dynamic_registration;
@@ -155,6 +160,7 @@ pub mod lsp_indicator_click_to_open;
 pub mod lsp_inlay_hints_capability;
 pub mod lsp_inlay_hints_refresh_on_edit;
 pub mod lsp_lifecycle_visibility;
+pub mod lsp_linked_editing;
 pub mod lsp_missing_binary_and_dismiss;
 pub mod lsp_multi_semantic_tokens;
 pub mod lsp_multi_server_diagnostic_pull;
@@ -162,12 +168,14 @@ pub mod lsp_no_config;
 pub mod lsp_order;
 pub mod lsp_popup_focus_keybinding;
 pub mod lsp_publish_diagnostics_capability;
+pub mod lsp_selection_range;
 pub mod lsp_server_lifecycle_cleanup;
 pub mod lsp_stop_stale_indicator;
 pub mod lsp_toggle_desync;
 pub mod lsp_unified_code_actions;
 pub mod lsp_unified_hover;
 pub mod lsp_unresponsive_capability_does_not_block;
+pub mod lsp_workspace_diagnostics;
 pub mod macros;
 pub mod mark_mode_actions;
 pub mod markdown_compose;
@@ -183,6 +191,7 @@ pub mod menu_cursor_bleed;
 pub mod menu_render_golden;
 pub mod menu_tab_color_bleed;
 pub mod merge_conflict;
+pub mod minimap;
 pub mod mouse;
 pub mod movement;
 pub mod multi_file_opening;
@@ -202,6 +211,7 @@ pub mod panel_mode_window_switch_leak;
 pub mod paste;
 pub mod per_buffer_view_settings;
 pub mod per_session_authority;
+pub mod persistent_undo;
 #[cfg(feature = "plugins")]
 pub mod plugins;
 pub mod popup_selection;
@@ -268,6 +278,8 @@ pub mod shift_backspace;
 pub mod slow_filesystem;
 pub mod smart_editing;
 pub mod smart_home;
+pub mod snippet_session;
+pub mod spell_check;
 pub mod split_close_confirm;
 pub mod split_focus_tab_click;
 pub mod split_tabs;
@@ -279,6 +291,7 @@ pub mod status_bar_config;
 pub mod status_bar_message_click;
 pub mod stdin_input;
 pub mod sticky_column_units;
+pub mod sticky_scroll;
 pub mod sudo_save_prompt;
 #[cfg(unix)]
 pub mod symlinks;
@@ -290,6 +303,7 @@ pub mod tab_config;
 pub mod tab_drag;
 pub mod tab_new_button;
 pub mod tab_path_disambiguation;
+pub mod tasks;
 pub mod terminal;
 pub mod terminal_close;
 pub mod terminal_link;
@@ -305,6 +319,7 @@ pub mod toggle_comment;
 pub mod trust_activation_cancellable;
 pub mod undo_bulk_edit_after_save;
 pub mod undo_redo_marker_roundtrip;
+pub mod undo_tree;
 pub mod unicode_cursor;
 pub mod unicode_prompt_bugs;
 pub mod universal_lsp;
@@ -325,3 +340,4 @@ pub mod widget_panel_ownership;
 pub mod window_switch_explorer_first_frame;
 #[cfg(feature = "plugins")]
 pub mod workspace;
+pub mod workspace_symbols;
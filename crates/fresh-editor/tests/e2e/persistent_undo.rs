@@ -0,0 +1,127 @@
+//! E2E coverage for `editor.persistent_undo`: edits saved in one session
+//! can be undone after a restart, and a file changed outside the editor
+//! drops its stored history instead of replaying it over new content.
+
+use crate::common::harness::{EditorTestHarness, HarnessOptions};
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use fresh::config_io::DirectoryContext;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn harness(project_dir: &Path, dir_context: &DirectoryContext) -> EditorTestHarness {
+    let mut config = Config::default();
+    config.editor.persistent_undo = true;
+    EditorTestHarness::create(
+        80,
+        24,
+        HarnessOptions::new()
+            .with_config(config)
+            .with_working_dir(project_dir.to_path_buf())
+            .with_shared_dir_context(dir_context.clone())
+            .without_empty_plugins_dir(),
+    )
+    .unwrap()
+}
+
+/// Open `file`, append "X", save and shut down cleanly.
+fn edit_and_save(project_dir: &Path, dir_context: &DirectoryContext, file: &Path) {
+    let mut harness = harness(project_dir, dir_context);
+    harness.open_file(file).unwrap();
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    harness.type_text("X").unwrap();
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    harness.shutdown(false).unwrap();
+}
+
+#[test]
+fn undo_survives_restart() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path().join("project");
+    std::fs::create_dir(&project_dir).unwrap();
+    let file = project_dir.join("notes.txt");
+    std::fs::write(&file, "abc").unwrap();
+    let dir_context = DirectoryContext::for_testing(temp_dir.path());
+
+    edit_and_save(&project_dir, &dir_context, &file);
+    assert_eq!(std::fs::read_to_string(&file).unwrap(), "abcX");
+
+    let mut harness = harness(&project_dir, &dir_context);
+    harness.open_file(&file).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("abcX");
+
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_buffer_content("abc");
+
+    harness
+        .send_key(KeyCode::Char('y'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_buffer_content("abcX");
+}
+
+#[test]
+fn external_change_discards_stored_undo() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path().join("project");
+    std::fs::create_dir(&project_dir).unwrap();
+    let file = project_dir.join("notes.txt");
+    std::fs::write(&file, "abc").unwrap();
+    let dir_context = DirectoryContext::for_testing(temp_dir.path());
+
+    edit_and_save(&project_dir, &dir_context, &file);
+    std::fs::write(&file, "rewritten").unwrap();
+
+    let mut harness = harness(&project_dir, &dir_context);
+    harness.open_file(&file).unwrap();
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_buffer_content("rewritten");
+}
+
+/// Number of undo histories stored anywhere under `dir`.
+fn stored_histories(dir: &Path) -> usize {
+    std::fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .map(|path| {
+            if !path.is_dir() {
+                0
+            } else if path.file_name().is_some_and(|name| name == "undo") {
+                std::fs::read_dir(&path).unwrap().count()
+            } else {
+                stored_histories(&path)
+            }
+        })
+        .sum()
+}
+
+#[test]
+fn startup_prunes_history_of_deleted_files() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path().join("project");
+    std::fs::create_dir(&project_dir).unwrap();
+    let kept = project_dir.join("kept.txt");
+    let deleted = project_dir.join("deleted.txt");
+    std::fs::write(&kept, "abc").unwrap();
+    std::fs::write(&deleted, "abc").unwrap();
+    let dir_context = DirectoryContext::for_testing(temp_dir.path());
+
+    edit_and_save(&project_dir, &dir_context, &kept);
+    edit_and_save(&project_dir, &dir_context, &deleted);
+    assert_eq!(stored_histories(temp_dir.path()), 2);
+    std::fs::remove_file(&deleted).unwrap();
+
+    let mut harness = harness(&project_dir, &dir_context);
+    harness.startup(false, &[]).unwrap();
+    assert_eq!(stored_histories(temp_dir.path()), 1);
+}
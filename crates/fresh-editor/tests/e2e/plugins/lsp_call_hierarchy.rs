@@ -0,0 +1,213 @@
+//! E2E tests for the call_hierarchy plugin: call and type hierarchy trees
+//! in the Utility Dock, with children fetched as nodes are expanded.
+
+use crate::common::harness::{copy_plugin, copy_plugin_lib, EditorTestHarness};
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::fs;
+
+/// Answers the hierarchy requests for `TEST_FILE_CONTENT`: `target` is
+/// called by `caller`, which is called by `main`; `Dog` extends `Animal`.
+/// Every item lives in the file the prepare request was made for.
+const FAKE_LSP_SCRIPT: &str = r#"#!/bin/bash
+read_message() {
+    local content_length=0
+    while IFS=: read -r key value; do
+        key=$(echo "$key" | tr -d '\r\n')
+        value=$(echo "$value" | tr -d '\r\n ')
+        if [ "$key" = "Content-Length" ]; then
+            content_length=$value
+        fi
+        if [ -z "$key" ]; then
+            break
+        fi
+    done
+    if [ $content_length -gt 0 ]; then
+        dd bs=1 count=$content_length 2>/dev/null
+    fi
+}
+send_message() {
+    local message="$1"
+    local length=${#message}
+    echo -en "Content-Length: $length\r\n\r\n$message"
+}
+range() {
+    echo '{"start":{"line":'$1',"character":'$2'},"end":{"line":'$1',"character":'$3'}}'
+}
+item() {
+    echo '{"name":"'$1'","kind":'$2',"uri":"'$uri'","range":'$(range $3 0 20)',"selectionRange":'$(range $3 $4 $5)'}'
+}
+uri=""
+while true; do
+    msg=$(read_message)
+    if [ -z "$msg" ]; then
+        break
+    fi
+    method=$(echo "$msg" | grep -o '"method":"[^"]*"' | cut -d'"' -f4)
+    msg_id=$(echo "$msg" | grep -o '"id":[0-9]*' | cut -d':' -f2)
+    name=$(echo "$msg" | grep -o '"name":"[^"]*"' | head -1 | cut -d'"' -f4)
+    case "$method" in
+        "initialize")
+            send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":{"capabilities":{"callHierarchyProvider":true,"typeHierarchyProvider":true,"textDocumentSync":1}}}'
+            ;;
+        "initialized") ;;
+        "textDocument/didOpen"|"textDocument/didChange"|"textDocument/didSave") ;;
+        "textDocument/prepareCallHierarchy")
+            uri=$(echo "$msg" | grep -o '"uri":"[^"]*"' | head -1 | cut -d'"' -f4)
+            send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":['"$(item target 12 0 9 15)"']}'
+            ;;
+        "callHierarchy/incomingCalls")
+            case "$name" in
+                "target") result='[{"from":'"$(item caller 12 1 9 15)"',"fromRanges":['"$(range 2 2 8)"']}]' ;;
+                "caller") result='[{"from":'"$(item main 12 4 9 13)"',"fromRanges":['"$(range 5 2 8)"']}]' ;;
+                *) result='[]' ;;
+            esac
+            send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":'"$result"'}'
+            ;;
+        "textDocument/prepareTypeHierarchy")
+            uri=$(echo "$msg" | grep -o '"uri":"[^"]*"' | head -1 | cut -d'"' -f4)
+            send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":['"$(item Dog 5 8 6 9)"']}'
+            ;;
+        "typeHierarchy/supertypes")
+            case "$name" in
+                "Dog") result='['"$(item Animal 11 7 10 16)"']' ;;
+                *) result='[]' ;;
+            esac
+            send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":'"$result"'}'
+            ;;
+        "shutdown")
+            send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":null}'
+            break
+            ;;
+        *)
+            if [ -n "$msg_id" ]; then
+                send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":null}'
+            fi
+            ;;
+    esac
+done
+"#;
+
+const TEST_FILE_CONTENT: &str = r#"function target() {}
+function caller() {
+  target();
+}
+function main() {
+  caller();
+}
+interface Animal {}
+class Dog implements Animal {}
+"#;
+
+fn setup_hierarchy_test() -> anyhow::Result<(EditorTestHarness, tempfile::TempDir)> {
+    let temp_dir = tempfile::TempDir::new()?;
+    let project_root = temp_dir.path().to_path_buf();
+
+    let plugins_dir = project_root.join("plugins");
+    fs::create_dir(&plugins_dir)?;
+    copy_plugin(&plugins_dir, "call_hierarchy");
+    copy_plugin_lib(&plugins_dir);
+
+    let script_path = project_root.join("fake_lsp.sh");
+    fs::write(&script_path, FAKE_LSP_SCRIPT)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = fs::metadata(&script_path)?.permissions();
+        perms.set_mode(0o755);
+        fs::set_permissions(&script_path, perms)?;
+    }
+
+    let test_file = project_root.join("test.ts");
+    fs::write(&test_file, TEST_FILE_CONTENT)?;
+
+    let mut config = fresh::config::Config::default();
+    config.lsp.insert(
+        "typescript".to_string(),
+        fresh::types::LspLanguageConfig::Multi(vec![fresh::services::lsp::LspServerConfig {
+            command: script_path.to_string_lossy().to_string(),
+            args: Some(vec![]),
+            enabled: true,
+            auto_start: true,
+            process_limits: fresh::services::process_limits::ProcessLimits::default(),
+            initialization_options: None,
+            env: Default::default(),
+            language_id_overrides: Default::default(),
+            root_markers: Default::default(),
+            name: None,
+            only_features: None,
+            except_features: None,
+        }]),
+    );
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(100, 30, config, project_root)?;
+
+    harness.open_file(&test_file)?;
+    harness.process_async_and_render()?;
+    harness.wait_until(|h| h.screen_to_string().contains("LSP (on)"))?;
+
+    Ok((harness, temp_dir))
+}
+
+fn run_command(harness: &mut EditorTestHarness, name: &str) -> anyhow::Result<()> {
+    harness.send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)?;
+    harness.process_async_and_render()?;
+    harness.type_text(name)?;
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    harness.process_async_and_render()?;
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(windows, ignore)] // Uses bash script for fake LSP server
+fn test_incoming_calls_expand_lazily_and_open_call_site() -> anyhow::Result<()> {
+    let (mut harness, _temp_dir) = setup_hierarchy_test()?;
+
+    run_command(&mut harness, "Show Incoming Calls")?;
+
+    // The root's callers are fetched straight away; deeper levels are not.
+    harness.wait_until(|h| {
+        let screen = h.screen_to_string();
+        screen.contains("Incoming calls to target") && screen.contains("caller  test.ts:2")
+    })?;
+    harness.assert_screen_contains("target  test.ts:1");
+    harness.assert_screen_not_contains("main  test.ts:5");
+
+    // Expanding `caller` asks the server for its callers.
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE)?;
+    harness.send_key(KeyCode::Right, KeyModifiers::NONE)?;
+    harness.wait_until(|h| h.screen_to_string().contains("main  test.ts:5"))?;
+
+    // Enter on an incoming call jumps to the call site inside the caller.
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE)?;
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    harness.wait_until(|h| {
+        let state = h.editor().active_state();
+        state.buffer.get_line_number(h.cursor_position()) == 5
+    })?;
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(windows, ignore)] // Uses bash script for fake LSP server
+fn test_supertypes_tree() -> anyhow::Result<()> {
+    let (mut harness, _temp_dir) = setup_hierarchy_test()?;
+
+    // Cursor on `Dog`.
+    for _ in 0..8 {
+        harness.send_key(KeyCode::Down, KeyModifiers::NONE)?;
+    }
+    for _ in 0..6 {
+        harness.send_key(KeyCode::Right, KeyModifiers::NONE)?;
+    }
+
+    run_command(&mut harness, "Show Supertypes")?;
+
+    harness.wait_until(|h| {
+        let screen = h.screen_to_string();
+        screen.contains("Supertypes of Dog") && screen.contains("Animal  test.ts:8")
+    })?;
+    harness.assert_screen_contains("Dog  test.ts:9");
+    Ok(())
+}
@@ -48,6 +48,7 @@ pub mod init_script;
 pub mod language_pack;
 pub mod live_diff;
 pub mod load_from_buffer;
+pub mod lsp_call_hierarchy;
 pub mod lsp_find_references;
 pub mod lsp_navigation;
 pub mod markdown_source;
@@ -59,9 +60,11 @@ pub mod orchestrator_plugin_api;
 pub mod package_manager;
 pub mod plugin;
 pub mod plugin_authoring;
+pub mod plugin_capabilities;
 pub mod plugin_config_changed_hook;
 pub mod plugin_config_registration;
 pub mod plugin_keybinding_execution;
+pub mod plugin_limits;
 pub mod plugins_dir_in_working_dir;
 pub mod review_diff_hunk_parity;
 pub mod review_diff_line_staging;
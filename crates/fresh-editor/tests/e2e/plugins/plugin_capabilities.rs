@@ -0,0 +1,104 @@
+//! E2E test: package plugins are held to the capabilities their manifest
+//! declares.
+//!
+//! A declared capability is approved once through a prompt; an undeclared
+//! one fails without asking. "Plugin Permissions" revokes an approval so the
+//! next use asks again.
+
+use crate::common::harness::{copy_plugin_lib, EditorTestHarness};
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::fs;
+
+const MANIFEST: &str = r#"{
+    "name": "probe",
+    "type": "plugin",
+    "fresh": { "entry": "probe.ts", "capabilities": ["process"] }
+}"#;
+
+const PLUGIN_SOURCE: &str = r#"
+const editor = getEditor();
+
+globalThis.probe_spawn = async function(): Promise<void> {
+    const r = await editor.spawnProcess("echo", ["probe-ok"]);
+    editor.setStatus("spawn exit " + r.exit_code + " " + r.stdout.trim());
+};
+editor.registerCommand("Probe: Spawn", "Run echo", "probe_spawn", null);
+
+globalThis.probe_fetch = async function(): Promise<void> {
+    const r = await editor.httpFetch("http://127.0.0.1:9/", editor.getCwd() + "/fetched");
+    editor.setStatus("fetch exit " + r.exit_code);
+};
+editor.registerCommand("Probe: Fetch", "Fetch a URL", "probe_fetch", null);
+
+editor.setStatus("probe loaded");
+"#;
+
+fn run_command(harness: &mut EditorTestHarness, name: &str) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text(name).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+#[test]
+#[cfg_attr(windows, ignore)] // relies on `echo` being a program on PATH
+fn test_package_plugin_capabilities_are_prompted_and_revocable() {
+    let temp = tempfile::TempDir::new().unwrap();
+    let project_root = temp.path().join("project_root");
+    let plugins_dir = project_root.join("plugins");
+    let package_dir = plugins_dir.join("packages").join("probe");
+    fs::create_dir_all(&package_dir).unwrap();
+    copy_plugin_lib(&plugins_dir);
+    fs::write(package_dir.join("package.json"), MANIFEST).unwrap();
+    fs::write(package_dir.join("probe.ts"), PLUGIN_SOURCE).unwrap();
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(120, 30, Default::default(), project_root)
+            .unwrap();
+    harness
+        .wait_until(|h| h.screen_to_string().contains("probe loaded"))
+        .unwrap();
+
+    // Declared but undecided: the spawn waits on the approval prompt.
+    run_command(&mut harness, "Probe: Spawn");
+    harness
+        .wait_until(|h| {
+            h.screen_to_string()
+                .contains("wants to run external programs")
+        })
+        .unwrap();
+    harness.type_text("y").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .wait_until(|h| h.screen_to_string().contains("spawn exit 0 probe-ok"))
+        .unwrap();
+
+    // Undeclared: refused outright, no prompt.
+    run_command(&mut harness, "Probe: Fetch");
+    harness
+        .wait_until(|h| h.screen_to_string().contains("fetch exit -1"))
+        .unwrap();
+
+    // Revoking the approval makes the next spawn ask again.
+    run_command(&mut harness, "Plugin Permissions");
+    harness.assert_screen_contains("probe: process");
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("will ask again");
+
+    run_command(&mut harness, "Probe: Spawn");
+    harness
+        .wait_until(|h| {
+            h.screen_to_string()
+                .contains("wants to run external programs")
+        })
+        .unwrap();
+}
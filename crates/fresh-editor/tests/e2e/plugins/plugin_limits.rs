@@ -0,0 +1,73 @@
+//! E2E test: a plugin that never yields is interrupted and disabled.
+//!
+//! All plugins share one JavaScript runtime on the plugin thread, so a
+//! `while (true) {}` in one command used to wedge every plugin for the rest
+//! of the session. The watchdog interrupts a run that outlives
+//! `plugin_execution_timeout_ms`, and the plugin thread disables the
+//! offender. The time it burned stays visible in "Plugin Profiler".
+
+use crate::common::harness::{copy_plugin_lib, EditorTestHarness};
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use std::fs;
+
+const PLUGIN_SOURCE: &str = r#"
+/// <reference path="./lib/fresh.d.ts" />
+const editor = getEditor();
+
+globalThis.runaway_spin = function(): void {
+    while (true) { }
+};
+editor.registerCommand("Runaway: Spin", "Never returns", "runaway_spin", null);
+
+editor.setStatus("runaway loaded");
+"#;
+
+fn run_command(harness: &mut EditorTestHarness, name: &str) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text(name).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+fn runaway_enabled(harness: &EditorTestHarness) -> Option<bool> {
+    harness
+        .editor()
+        .plugin_manager()
+        .list_plugins()
+        .into_iter()
+        .find(|p| p.name == "runaway")
+        .map(|p| p.enabled)
+}
+
+#[test]
+fn test_runaway_command_is_interrupted_and_plugin_disabled() {
+    let temp = tempfile::TempDir::new().unwrap();
+    let project_root = temp.path().join("project_root");
+    let plugins_dir = project_root.join("plugins");
+    fs::create_dir_all(&plugins_dir).unwrap();
+    copy_plugin_lib(&plugins_dir);
+    fs::write(plugins_dir.join("runaway.ts"), PLUGIN_SOURCE).unwrap();
+
+    let mut config = Config::default();
+    config.editor.plugin_execution_timeout_ms = 200;
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(120, 30, config, project_root).unwrap();
+    harness
+        .wait_until(|h| h.screen_to_string().contains("runaway loaded"))
+        .unwrap();
+    assert_eq!(runaway_enabled(&harness), Some(true));
+
+    run_command(&mut harness, "Runaway: Spin");
+    harness
+        .wait_until(|h| runaway_enabled(h) == Some(false))
+        .unwrap();
+
+    run_command(&mut harness, "Plugin Profiler");
+    harness.assert_screen_contains("── runaway ──");
+    harness.assert_screen_contains("runaway_spin");
+}
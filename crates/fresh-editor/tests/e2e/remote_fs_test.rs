@@ -409,3 +409,60 @@ fn test_remote_large_file_edits_beginning_middle_end() {
         );
     }
 }
+
+/// A remote buffer reverts from the agent's watch events. Auto-revert
+/// polling is pushed out to an hour so it can't be what picks the change
+/// up; the short file-tree interval is only there to install the watch.
+///
+/// The project is reached through a symlink (like `/home` → `/var/home`),
+/// which the editor resolves when it opens the file. The agent's side of
+/// that contract — reporting changes under the path it was asked to watch —
+/// is covered in `remote_filesystem_tests`.
+#[cfg(unix)]
+#[test]
+#[cfg_attr(not(target_os = "linux"), ignore)] // the agent's watch uses inotify
+fn test_remote_buffer_auto_reverts_from_agent_watch() {
+    let Some((fs, temp_dir, _rt)) = create_test_filesystem() else {
+        eprintln!("Skipping test: could not create test filesystem");
+        return;
+    };
+    let fs_arc: Arc<dyn fresh::model::filesystem::FileSystem + Send + Sync> = Arc::new(fs);
+    let real_dir = temp_dir.path().join("real");
+    std::fs::create_dir(&real_dir).unwrap();
+    let project_dir = temp_dir.path().join("linked");
+    std::os::unix::fs::symlink(&real_dir, &project_dir).unwrap();
+    let file_path = project_dir.join("remote.txt");
+    std::fs::write(&file_path, "before").unwrap();
+
+    let mut config = fresh::config::Config::default();
+    config.editor.auto_revert_poll_interval_ms = 3_600_000;
+    config.editor.file_tree_poll_interval_ms = 50;
+    let mut harness = EditorTestHarness::create(
+        80,
+        24,
+        HarnessOptions::new()
+            .with_config(config)
+            .with_working_dir(project_dir.clone())
+            .with_filesystem(fs_arc),
+    )
+    .unwrap();
+
+    harness.open_file(&file_path).unwrap();
+    harness.assert_buffer_content("before");
+    let buffer_dir = harness
+        .editor()
+        .active_state()
+        .buffer
+        .file_path()
+        .and_then(|p| p.parent())
+        .expect("the buffer has a file")
+        .to_path_buf();
+    harness
+        .wait_until(|h| h.editor().is_dir_watched_for_test(&buffer_dir))
+        .expect("the agent should watch the open file's directory");
+
+    std::fs::write(&file_path, "after the watch").unwrap();
+    harness
+        .wait_until(|h| h.get_buffer_content().unwrap() == "after the watch")
+        .expect("the agent's watch event should revert the buffer");
+}
@@ -2880,3 +2880,62 @@ fn test_search_replace_positions_track_bulk_edits() {
         .wait_until(|h| cursor_sits_on(h, "ZZNEEDLE two"))
         .unwrap();
 }
+
+/// Structural mode matches the pattern in the syntax tree of files in the
+/// source buffer's language, previews each rewrite, and writes the
+/// rewrite with the captures substituted.
+#[test]
+fn test_search_replace_structural_rewrite() {
+    let (_temp_dir, project_root) = setup_search_replace_project();
+    fs::write(
+        project_root.join("main.ts"),
+        "console.log(\"saving\", path);\nconst s = \"console.log(x)\";\n",
+    )
+    .unwrap();
+    fs::write(
+        project_root.join("util.ts"),
+        "// console.log(y)\nconsole.log(1 + 2);\n",
+    )
+    .unwrap();
+    fs::write(project_root.join("other.js"), "console.log(z);\n").unwrap();
+
+    let start_file = project_root.join("main.ts");
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        120,
+        30,
+        Default::default(),
+        project_root.clone(),
+    )
+    .unwrap();
+    harness.open_file(&start_file).unwrap();
+    harness.render().unwrap();
+
+    open_search_replace_via_palette(&mut harness);
+    harness
+        .send_key(KeyCode::Char('t'), KeyModifiers::ALT)
+        .unwrap();
+    enter_search_and_replace(
+        &mut harness,
+        "console.log($$$ARGS)",
+        "logger.debug($$$ARGS)",
+    );
+
+    harness
+        .wait_until_stable(|h| {
+            let s = h.screen_to_string();
+            s.contains("→ logger.debug(\"saving\", path)") && s.contains("→ logger.debug(1 + 2)")
+        })
+        .unwrap();
+
+    confirm_replace_all(&mut harness);
+
+    let main = fs::read_to_string(project_root.join("main.ts")).unwrap();
+    assert_eq!(
+        main,
+        "logger.debug(\"saving\", path);\nconst s = \"console.log(x)\";\n"
+    );
+    let util = fs::read_to_string(project_root.join("util.ts")).unwrap();
+    assert_eq!(util, "// console.log(y)\nlogger.debug(1 + 2);\n");
+    let other = fs::read_to_string(project_root.join("other.js")).unwrap();
+    assert_eq!(other, "console.log(z);\n");
+}
@@ -0,0 +1,181 @@
+//! E2E tests for snippet sessions and user snippets: a snippet accepted from
+//! the completion popup is stepped through with Tab / Shift+Tab, mirrored
+//! placeholders change together, and choice placeholders offer a picker.
+
+use crate::common::harness::{EditorTestHarness, HarnessOptions};
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use fresh::config_io::DirectoryContext;
+use fresh::input::keybindings::Action;
+use tempfile::TempDir;
+
+const RUST_SNIPPETS: &str = r#"{
+    // Comments are allowed, as in VS Code.
+    "For loop": {
+        "prefix": "forr",
+        "body": ["for ${1:item} in ${2:items} {", "    $0", "}"],
+        "description": "Loop over a collection"
+    },
+    "Struct with impl": {
+        "prefix": "structi",
+        "body": ["struct ${1:Name};", "impl $1 {}"]
+    },
+    "Visibility": {
+        "prefix": "vis",
+        "body": "${1|pub,pub(crate),pub(super)|} fn $0"
+    },
+}"#;
+
+/// A harness whose config directory holds `snippets/rust.json`, with an
+/// empty `main.rs` open. No language servers are configured, so completion
+/// falls back to buffer words and snippets.
+fn harness_with_snippets(temp: &TempDir) -> EditorTestHarness {
+    let dir_context = DirectoryContext::for_testing(temp.path());
+    let snippets = dir_context.snippets_dir();
+    std::fs::create_dir_all(&snippets).unwrap();
+    std::fs::write(snippets.join("rust.json"), RUST_SNIPPETS).unwrap();
+
+    let project = temp.path().join("project");
+    std::fs::create_dir_all(&project).unwrap();
+    let file = project.join("main.rs");
+    std::fs::write(&file, "").unwrap();
+
+    let mut config = Config::default();
+    config.lsp.clear();
+    let mut harness = EditorTestHarness::create(
+        100,
+        24,
+        HarnessOptions::new()
+            .with_config(config)
+            .with_working_dir(project)
+            .with_shared_dir_context(dir_context),
+    )
+    .unwrap();
+    harness.open_file(&file).unwrap();
+    harness
+}
+
+/// Type `prefix`, open the completion popup and accept its first row.
+fn expand(harness: &mut EditorTestHarness, prefix: &str) {
+    harness.type_text(prefix).unwrap();
+    harness
+        .editor_mut()
+        .dispatch_action_for_tests(Action::LspCompletion);
+    harness.render().unwrap();
+    assert!(
+        harness.editor().active_state().popups.is_visible(),
+        "completion popup should offer the snippet"
+    );
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+}
+
+fn selected_text(harness: &mut EditorTestHarness) -> String {
+    let range = harness
+        .editor()
+        .active_cursors()
+        .primary()
+        .selection_range()
+        .expect("placeholder should be selected");
+    harness.get_buffer_content().unwrap()[range].to_string()
+}
+
+#[test]
+fn test_snippet_tab_steps_through_placeholders() {
+    let temp = TempDir::new().unwrap();
+    let mut harness = harness_with_snippets(&temp);
+    harness.type_text("forr").unwrap();
+    harness
+        .editor_mut()
+        .dispatch_action_for_tests(Action::LspCompletion);
+    harness.render().unwrap();
+    harness.assert_screen_contains("Loop over a collection");
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "for item in items {\n    \n}"
+    );
+    assert_eq!(selected_text(&mut harness), "item");
+
+    harness.type_text("x").unwrap();
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE).unwrap();
+    assert_eq!(selected_text(&mut harness), "items");
+    harness.type_text("xs").unwrap();
+
+    // Shift+Tab goes back to the first placeholder, now holding "x".
+    harness
+        .send_key(KeyCode::BackTab, KeyModifiers::NONE)
+        .unwrap();
+    assert_eq!(selected_text(&mut harness), "x");
+
+    // Past the last placeholder, the cursor lands on `$0` and the session
+    // ends: Tab indents again.
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE).unwrap();
+    let content = harness.get_buffer_content().unwrap();
+    assert_eq!(content, "for x in xs {\n    \n}");
+    assert_eq!(harness.cursor_position(), content.find("\n}").unwrap());
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE).unwrap();
+    assert_ne!(harness.get_buffer_content().unwrap(), content);
+}
+
+#[test]
+fn test_snippet_mirrors_edit_in_sync() {
+    let temp = TempDir::new().unwrap();
+    let mut harness = harness_with_snippets(&temp);
+    expand(&mut harness, "structi");
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "struct Name;\nimpl Name {}"
+    );
+
+    harness.type_text("Point").unwrap();
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "struct Point;\nimpl Point {}"
+    );
+}
+
+#[test]
+fn test_snippet_choice_offers_picker() {
+    let temp = TempDir::new().unwrap();
+    let mut harness = harness_with_snippets(&temp);
+    expand(&mut harness, "vis");
+    assert_eq!(harness.get_buffer_content().unwrap(), "pub fn ");
+    harness.assert_screen_contains("pub(crate)");
+
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    assert!(!harness.editor().active_state().popups.is_visible());
+    assert_eq!(harness.get_buffer_content().unwrap(), "pub(crate) fn ");
+    assert_eq!(selected_text(&mut harness), "pub(crate)");
+
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE).unwrap();
+    assert_eq!(harness.cursor_position(), "pub(crate) fn ".len());
+}
+
+#[test]
+fn test_escape_ends_snippet_session() {
+    let temp = TempDir::new().unwrap();
+    let mut harness = harness_with_snippets(&temp);
+    expand(&mut harness, "forr");
+    harness.send_key(KeyCode::Esc, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE).unwrap();
+    // Tab no longer jumps to the next placeholder.
+    let selection = harness
+        .editor()
+        .active_cursors()
+        .primary()
+        .selection_range();
+    let content = harness.get_buffer_content().unwrap();
+    assert_ne!(
+        selection.map(|range| &content[range]),
+        Some("items"),
+        "content: {content:?}"
+    );
+}
@@ -0,0 +1,117 @@
+//! E2E tests for spell checking: misspelled words in comments and prose are
+//! underlined, and the code-action popup replaces them with a suggestion or
+//! adds them to the project word list.
+
+use crate::common::harness::{EditorTestHarness, HarnessOptions};
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use fresh::config_io::DirectoryContext;
+use ratatui::style::Modifier;
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+const AFF: &str = "SET UTF-8\nTRY esianrtolcdugmphbyfvkwz\n\nSFX S Y 1\nSFX S 0 s .\n";
+const DIC: &str = "7\nthis\ncomment/S\nis\nwrong\nhello\nworld\nmain\n";
+
+/// A harness whose config directory holds a small `en_US` dictionary,
+/// working in `<temp>/project`.
+fn harness_with_dictionary(temp: &TempDir) -> (EditorTestHarness, PathBuf) {
+    let dir_context = DirectoryContext::for_testing(temp.path());
+    let dictionaries = dir_context.dictionaries_dir();
+    std::fs::create_dir_all(&dictionaries).unwrap();
+    std::fs::write(dictionaries.join("en_US.aff"), AFF).unwrap();
+    std::fs::write(dictionaries.join("en_US.dic"), DIC).unwrap();
+
+    let project = temp.path().join("project");
+    std::fs::create_dir_all(&project).unwrap();
+    let harness = EditorTestHarness::create(
+        100,
+        24,
+        HarnessOptions::new()
+            .with_config(Config::default())
+            .with_working_dir(project.clone())
+            .with_shared_dir_context(dir_context)
+            .with_full_grammar_registry(),
+    )
+    .unwrap();
+    (harness, project)
+}
+
+fn is_underlined(harness: &EditorTestHarness, text: &str) -> bool {
+    harness
+        .find_text_on_screen(text)
+        .and_then(|(x, y)| harness.get_cell_style(x, y))
+        .is_some_and(|style| style.add_modifier.contains(Modifier::UNDERLINED))
+}
+
+#[test]
+fn test_spell_check_underlines_comments_but_not_code() {
+    let temp = TempDir::new().unwrap();
+    let (mut harness, project) = harness_with_dictionary(&temp);
+    let file = project.join("main.rs");
+    std::fs::write(&file, "// this coment is wrong\nfn mian() {}\n").unwrap();
+    harness.open_file(&file).unwrap();
+
+    harness.wait_until(|h| is_underlined(h, "coment")).unwrap();
+    assert!(!is_underlined(&harness, "this"));
+    assert!(!is_underlined(&harness, "wrong"));
+    assert!(
+        !is_underlined(&harness, "mian"),
+        "identifiers in code are not spell checked"
+    );
+}
+
+#[test]
+fn test_spell_check_code_action_replaces_word() {
+    let temp = TempDir::new().unwrap();
+    let (mut harness, project) = harness_with_dictionary(&temp);
+    let file = project.join("notes.md");
+    std::fs::write(&file, "hello wrold\n").unwrap();
+    harness.open_file(&file).unwrap();
+    harness.wait_until(|h| is_underlined(h, "wrold")).unwrap();
+
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Char('.'), KeyModifiers::ALT)
+        .unwrap();
+    harness
+        .wait_until(|h| h.screen_to_string().contains("Change to 'world'"))
+        .unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+
+    harness
+        .wait_until(|h| h.get_buffer_content().unwrap() == "hello world\n")
+        .unwrap();
+    harness.render().unwrap();
+    assert!(!is_underlined(&harness, "world"));
+}
+
+#[test]
+fn test_spell_check_code_action_adds_project_word() {
+    let temp = TempDir::new().unwrap();
+    let (mut harness, project) = harness_with_dictionary(&temp);
+    let file = project.join("notes.md");
+    std::fs::write(&file, "hello fresh\n").unwrap();
+    harness.open_file(&file).unwrap();
+    harness.wait_until(|h| is_underlined(h, "fresh")).unwrap();
+
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Char('.'), KeyModifiers::ALT)
+        .unwrap();
+    harness
+        .wait_until(|h| h.screen_to_string().contains("to project words"))
+        .unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+
+    harness.wait_until(|h| !is_underlined(h, "fresh")).unwrap();
+    assert_eq!(
+        std::fs::read_to_string(project.join(".fresh").join("words.txt")).unwrap(),
+        "fresh\n"
+    );
+    assert_eq!(harness.get_buffer_content().unwrap(), "hello fresh\n");
+}
@@ -0,0 +1,119 @@
+//! End-to-end coverage for sticky scroll: the lines of the scopes enclosing
+//! the top of the view, pinned over the first rows of a split.
+
+use crate::common::harness::EditorTestHarness;
+use fresh::config::Config;
+use fresh::input::keybindings::Action;
+
+/// A two-level scope at the top of the file, then filler.
+fn nested_source(opener: &str, inner: &str, close: &str) -> String {
+    let mut text = format!("{opener}\n    {inner}\n");
+    for i in 0..60 {
+        text.push_str(&format!("        let value_{i} = {i};\n"));
+    }
+    text.push_str(&format!("    {close}\n{close}\n"));
+    text
+}
+
+fn sticky_config() -> Config {
+    let mut config = Config::default();
+    config.editor.sticky_scroll = true;
+    config
+}
+
+/// Scroll the view down by whole wheel steps until its top byte is at least
+/// `min_top`.
+fn scroll_past(harness: &mut EditorTestHarness, min_top: usize) {
+    let (first, _) = harness.content_area_rows();
+    for _ in 0..20 {
+        if harness.top_byte() >= min_top {
+            return;
+        }
+        harness.mouse_scroll_down(20, first as u16 + 2).unwrap();
+    }
+}
+
+/// Without a grammar, the less-indented lines above the top of the view
+/// are pinned, outermost first, with their line numbers.
+#[test]
+fn test_sticky_scroll_uses_indentation() {
+    let content = nested_source("section outer:", "part inner:", "end");
+    let mut harness = EditorTestHarness::with_config(80, 24, sticky_config()).unwrap();
+    let _fixture = harness.load_buffer_from_text(&content).unwrap();
+    harness.render().unwrap();
+    let (first, _) = harness.content_area_rows();
+    // Nothing to pin at the top of the file
+    assert!(harness.get_screen_row(first + 2).contains("value_0 "));
+
+    scroll_past(&mut harness, content.find("value_10").unwrap());
+    let row0 = harness.get_screen_row(first);
+    let row1 = harness.get_screen_row(first + 1);
+    assert!(row0.contains("section outer:"), "row 0: {row0:?}");
+    assert!(row0.trim_start().starts_with('1'), "row 0: {row0:?}");
+    assert!(row1.contains("part inner:"), "row 1: {row1:?}");
+    assert!(row1.trim_start().starts_with('2'), "row 1: {row1:?}");
+    assert!(!harness.get_screen_row(first + 2).contains("inner:"));
+}
+
+/// With a tree-sitter grammar, the header comes from the syntax tree.
+#[test]
+fn test_sticky_scroll_uses_syntax_tree() {
+    let content = nested_source("function outer() {", "if (ready) {", "}")
+        .replace("let value_", "const value_");
+    let mut harness = EditorTestHarness::with_config(80, 24, sticky_config()).unwrap();
+    let _fixture = harness
+        .load_buffer_from_text_named("sticky.js", &content)
+        .unwrap();
+    harness.render().unwrap();
+
+    scroll_past(&mut harness, content.find("value_10").unwrap());
+    let (first, _) = harness.content_area_rows();
+    let row0 = harness.get_screen_row(first);
+    let row1 = harness.get_screen_row(first + 1);
+    assert!(row0.contains("function outer() {"), "row 0: {row0:?}");
+    assert!(row1.contains("if (ready) {"), "row 1: {row1:?}");
+}
+
+/// Clicking a header line moves the cursor to it and scrolls it back into
+/// place under the mouse.
+#[test]
+fn test_sticky_scroll_click_jumps_to_scope() {
+    let content = nested_source("section outer:", "part inner:", "end");
+    let mut harness = EditorTestHarness::with_config(80, 24, sticky_config()).unwrap();
+    let _fixture = harness.load_buffer_from_text(&content).unwrap();
+    harness.render().unwrap();
+    scroll_past(&mut harness, content.find("value_10").unwrap());
+
+    let (first, _) = harness.content_area_rows();
+    harness.mouse_click(20, first as u16 + 1).unwrap();
+    harness.render().unwrap();
+    let inner = content.find("    part inner:").unwrap();
+    assert_eq!(harness.cursor_position(), inner);
+    // The header line sits on the row that was clicked, below the first line
+    assert_eq!(harness.top_byte(), 0);
+}
+
+/// Sticky scroll is off by default and toggles at runtime.
+#[test]
+fn test_toggle_sticky_scroll() {
+    let content = nested_source("section outer:", "part inner:", "end");
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    let _fixture = harness.load_buffer_from_text(&content).unwrap();
+    harness.render().unwrap();
+    scroll_past(&mut harness, content.find("value_10").unwrap());
+    let (first, _) = harness.content_area_rows();
+    assert!(!harness.get_screen_row(first).contains("section outer:"));
+
+    harness
+        .editor_mut()
+        .dispatch_action_for_tests(Action::ToggleStickyScroll);
+    harness.render().unwrap();
+    assert!(harness.get_screen_row(first).contains("section outer:"));
+    harness.assert_screen_contains("Sticky scroll enabled");
+
+    harness
+        .editor_mut()
+        .dispatch_action_for_tests(Action::ToggleStickyScroll);
+    harness.render().unwrap();
+    assert!(!harness.get_screen_row(first).contains("section outer:"));
+}
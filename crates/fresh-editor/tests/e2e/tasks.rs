@@ -0,0 +1,170 @@
+//! E2E tests for tasks: "Run Task" runs a command from the config or from
+//! `.vscode/tasks.json`, and its problem matchers turn the output into
+//! diagnostics that `JumpToNextError` can reach.
+
+use crate::common::harness::{EditorTestHarness, HarnessOptions};
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::types::{ProblemMatcherConfig, TaskConfig, TaskRunIn};
+use portable_pty::{native_pty_system, PtySize};
+
+fn pty_available() -> bool {
+    native_pty_system()
+        .openpty(PtySize {
+            rows: 1,
+            cols: 1,
+            pixel_width: 0,
+            pixel_height: 0,
+        })
+        .is_ok()
+}
+
+fn harness(
+    config: fresh::config::Config,
+    working_dir: &std::path::Path,
+) -> anyhow::Result<EditorTestHarness> {
+    EditorTestHarness::create(
+        100,
+        30,
+        HarnessOptions::new()
+            .with_config(config)
+            .with_working_dir(working_dir.to_path_buf()),
+    )
+}
+
+fn file_uri(path: &std::path::Path) -> String {
+    format!("file://{}", path.to_str().unwrap())
+}
+
+/// A background task's gcc-style error becomes a diagnostic, and F8 opens
+/// the file at it.
+#[test]
+#[cfg_attr(target_os = "windows", ignore = "task command uses sh")]
+fn test_background_task_problems_reach_next_error() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let source = temp_dir.path().join("main.c");
+    std::fs::write(&source, "int main() {\n    return x;\n}\n")?;
+    let notes = temp_dir.path().join("notes.txt");
+    std::fs::write(&notes, "notes\n")?;
+
+    let config = fresh::config::Config {
+        tasks: vec![TaskConfig {
+            label: "build".to_string(),
+            command: "echo".to_string(),
+            args: vec!["main.c:2:12: error: 'x' undeclared".to_string()],
+            run_in: TaskRunIn::Background,
+            problem_matcher: vec![ProblemMatcherConfig::Named("$gcc".to_string())],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let mut harness = harness(config, temp_dir.path())?;
+    harness.open_file(&notes)?;
+    harness.render()?;
+
+    harness.run_palette_command("Run Task")?;
+    harness.render()?;
+    harness.assert_screen_contains("build");
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+
+    let uri = file_uri(&source);
+    harness.wait_until(|h| {
+        h.editor()
+            .get_stored_diagnostics()
+            .get(&uri)
+            .is_some_and(|d| d.iter().any(|d| d.message == "'x' undeclared"))
+    })?;
+    let diagnostic = harness.editor().get_stored_diagnostics()[&uri][0].clone();
+    assert_eq!(diagnostic.source.as_deref(), Some("gcc"));
+    assert_eq!(
+        diagnostic.severity,
+        Some(lsp_types::DiagnosticSeverity::ERROR)
+    );
+
+    // notes.txt has no diagnostics, so F8 moves to the task's problem.
+    harness.send_key(KeyCode::F(8), KeyModifiers::NONE)?;
+    harness.render()?;
+    let active_path = harness
+        .editor()
+        .active_state()
+        .buffer
+        .file_path()
+        .map(|p| p.to_path_buf());
+    assert_eq!(active_path.as_deref(), Some(source.as_path()));
+    // Line 2, column 12: "int main() {\n" is 13 bytes, then 11 more.
+    assert_eq!(harness.cursor_position(), 24);
+    Ok(())
+}
+
+/// A terminal task imported from `.vscode/tasks.json` runs in its own tab;
+/// when it exits, the two-line rustc errors it printed become diagnostics,
+/// and running it again replaces them.
+#[test]
+#[cfg_attr(target_os = "windows", ignore = "task command uses sh")]
+fn test_vscode_terminal_task_matches_rustc_output() -> anyhow::Result<()> {
+    if !pty_available() {
+        eprintln!("Skipping: PTY not available");
+        return Ok(());
+    }
+    let temp_dir = tempfile::tempdir()?;
+    let source = temp_dir.path().join("src").join("main.rs");
+    std::fs::create_dir_all(source.parent().unwrap())?;
+    std::fs::write(&source, "fn main() {\n    let x: u32 = \"a\";\n}\n")?;
+    let output = temp_dir.path().join("output.txt");
+    std::fs::write(
+        &output,
+        "error[E0308]: mismatched types\n --> src/main.rs:2:18\n  |\n",
+    )?;
+    std::fs::create_dir_all(temp_dir.path().join(".vscode"))?;
+    std::fs::write(
+        temp_dir.path().join(".vscode").join("tasks.json"),
+        r#"{
+            // Runs in the integrated terminal.
+            "version": "2.0.0",
+            "tasks": [
+                {
+                    "label": "check",
+                    "type": "shell",
+                    "command": "cat",
+                    "args": ["output.txt"],
+                    "problemMatcher": "$rustc",
+                },
+            ],
+        }"#,
+    )?;
+
+    let mut harness = harness(fresh::config::Config::default(), temp_dir.path())?;
+    harness.open_file(&source)?;
+    harness.render()?;
+
+    harness.run_palette_command("Run Task")?;
+    harness.render()?;
+    harness.assert_screen_contains("check");
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+
+    let uri = file_uri(&source);
+    harness.wait_until(|h| {
+        h.editor()
+            .get_stored_diagnostics()
+            .get(&uri)
+            .is_some_and(|d| !d.is_empty())
+    })?;
+    let diagnostics = harness.editor().get_stored_diagnostics()[&uri].clone();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].message, "mismatched types");
+    assert_eq!(diagnostics[0].range.start, lsp_types::Position::new(1, 17));
+    assert_eq!(
+        diagnostics[0].code,
+        Some(lsp_types::NumberOrString::String("E0308".to_string()))
+    );
+
+    // A clean rerun clears the previous run's problems.
+    std::fs::write(&output, "Finished\n")?;
+    harness.run_palette_command("Rerun Last Task")?;
+    harness.wait_until(|h| {
+        h.editor()
+            .get_stored_diagnostics()
+            .get(&uri)
+            .is_none_or(|d| d.is_empty())
+    })?;
+    Ok(())
+}
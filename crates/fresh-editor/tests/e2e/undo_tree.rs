@@ -0,0 +1,50 @@
+//! E2E coverage for the undo-tree browser: editing after an undo keeps the
+//! undone branch, the browser lists both branches, and `Enter` on a row
+//! restores that state in the source buffer.
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+#[test]
+fn undo_tree_recovers_branch_discarded_by_linear_undo() {
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+
+    harness.type_text("x").unwrap();
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("y").unwrap();
+    harness.assert_buffer_content("y");
+
+    harness.run_palette_command("Show Undo Tree").unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Insert \"x\"");
+    harness.assert_screen_contains("Insert \"y\"");
+
+    // The browser opens on the current state ("y"); the older "x" branch
+    // is listed directly above it.
+    harness.send_key(KeyCode::Up, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    harness.assert_buffer_content("x");
+    harness.assert_screen_contains("Jumped to undo state");
+}
+
+#[test]
+fn undo_tree_preview_lists_recorded_edit() {
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+
+    harness.type_text("q").unwrap();
+    harness.run_palette_command("Show Undo Tree").unwrap();
+    harness.render().unwrap();
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Undo state #");
+    harness.assert_screen_contains("+ q");
+}
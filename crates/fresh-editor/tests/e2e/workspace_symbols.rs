@@ -0,0 +1,158 @@
+//! E2E tests for workspace symbol search in Quick Open (`@` prefix): results
+//! from every language server are merged and fuzzy-ranked, the selected
+//! symbol is previewed, and Enter jumps to it.
+
+use crate::common::fake_lsp::FakeLspServer;
+use crate::common::harness::{EditorTestHarness, HarnessOptions};
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::input::keybindings::Action;
+use std::path::Path;
+
+fn symbol(name: &str, path: &Path, line: u32) -> String {
+    let uri = format!("file://{}", path.display());
+    format!(
+        r#"{{"name":"{name}","kind":12,"location":{{"uri":"{uri}","range":{{"start":{{"line":{line},"character":3}},"end":{{"line":{line},"character":9}}}}}}}}"#
+    )
+}
+
+fn server_config(script: &Path, name: &str) -> fresh::services::lsp::LspServerConfig {
+    fresh::services::lsp::LspServerConfig {
+        command: script.to_string_lossy().to_string(),
+        args: Some(vec![]),
+        enabled: true,
+        auto_start: true,
+        process_limits: fresh::services::process_limits::ProcessLimits::default(),
+        initialization_options: None,
+        env: Default::default(),
+        language_id_overrides: Default::default(),
+        root_markers: Default::default(),
+        name: Some(name.to_string()),
+        only_features: None,
+        except_features: None,
+    }
+}
+
+/// Two servers for Rust, each knowing the symbols of one file, with
+/// `main.rs` open and both servers initialized.
+fn harness_with_two_servers(dir: &Path) -> anyhow::Result<(EditorTestHarness, [FakeLspServer; 2])> {
+    let config_rs = dir.join("config.rs");
+    let render_rs = dir.join("render.rs");
+    std::fs::write(&config_rs, "struct Config;\n\nfn parse_config() {}\n")?;
+    std::fs::write(&render_rs, "// Drawing\n\nfn render_frame() {}\n")?;
+    let main_rs = dir.join("main.rs");
+    std::fs::write(&main_rs, "fn main() {}\n")?;
+
+    let servers = [
+        FakeLspServer::spawn_with_workspace_symbols(
+            dir,
+            "a",
+            &format!(
+                "[{},{}]",
+                symbol("Config", &config_rs, 0),
+                symbol("parse_config", &config_rs, 2)
+            ),
+        )?,
+        FakeLspServer::spawn_with_workspace_symbols(
+            dir,
+            "b",
+            &format!("[{}]", symbol("render_frame", &render_rs, 2)),
+        )?,
+    ];
+
+    let mut config = fresh::config::Config::default();
+    config.lsp.insert(
+        "rust".to_string(),
+        fresh::types::LspLanguageConfig::Multi(vec![
+            server_config(
+                &FakeLspServer::workspace_symbols_script_path(dir, "a"),
+                "server-a",
+            ),
+            server_config(
+                &FakeLspServer::workspace_symbols_script_path(dir, "b"),
+                "server-b",
+            ),
+        ]),
+    );
+
+    // Wide enough for the overlay to show its preview pane.
+    let mut harness = EditorTestHarness::create(
+        180,
+        30,
+        HarnessOptions::new()
+            .with_config(config)
+            .with_working_dir(dir.to_path_buf()),
+    )?;
+    harness.open_file(&main_rs)?;
+    harness.render()?;
+    harness.wait_until(|h| {
+        h.editor()
+            .active_window()
+            .initialized_lsp_server_count("rust")
+            >= 2
+    })?;
+    Ok((harness, servers))
+}
+
+#[test]
+#[cfg_attr(
+    target_os = "windows",
+    ignore = "FakeLspServer uses a Bash script which is not available on Windows"
+)]
+fn test_workspace_symbols_merged_and_ranked() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let dir = temp_dir.path().canonicalize()?;
+    let (mut harness, _servers) = harness_with_two_servers(&dir)?;
+
+    harness
+        .editor_mut()
+        .dispatch_action_for_tests(Action::QuickOpenSymbols);
+    harness.render()?;
+    harness.assert_screen_contains("Type a symbol name to search the workspace");
+
+    // Symbols from both servers show up in one list.
+    harness.type_text("f")?;
+    harness.wait_for_screen_contains("parse_config")?;
+    harness.wait_for_screen_contains("render_frame")?;
+    harness.assert_screen_contains("config.rs:3");
+    harness.assert_screen_contains("render.rs:3");
+
+    // Refining the query ranks locally: "fra" only matches one symbol.
+    harness.type_text("ra")?;
+    harness.wait_until(|h| !h.screen_to_string().contains("parse_config"))?;
+    harness.assert_screen_contains("render_frame");
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(
+    target_os = "windows",
+    ignore = "FakeLspServer uses a Bash script which is not available on Windows"
+)]
+fn test_workspace_symbol_preview_and_jump() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let dir = temp_dir.path().canonicalize()?;
+    let (mut harness, _servers) = harness_with_two_servers(&dir)?;
+
+    harness
+        .editor_mut()
+        .dispatch_action_for_tests(Action::QuickOpenSymbols);
+    harness.type_text("render")?;
+    harness.wait_for_screen_contains("render_frame")?;
+
+    // The selected symbol's file is previewed before anything is opened.
+    harness.wait_for_screen_contains("// Drawing")?;
+    assert!(harness
+        .get_buffer_content()
+        .unwrap()
+        .starts_with("fn main()"));
+
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    harness.render()?;
+    let content = harness.get_buffer_content().unwrap();
+    assert!(content.starts_with("// Drawing"), "content: {content:?}");
+    assert_eq!(
+        harness.cursor_position(),
+        content.find("render_frame").unwrap()
+    );
+    Ok(())
+}
@@ -9,8 +9,10 @@
 //
 // Files use a language-less extension (`.dat` / `.zzz`) so the assertion
 // isolates `.editorconfig` behavior from any language-specific Tab handling
-// (e.g. Markdown list indentation). Non-visible details (parsing, glob
-// matching, size mapping, precedence) are covered by unit tests on the
+// (e.g. Markdown list indentation). The file-format and on-save properties are
+// observed where they land: the bytes written to disk, the decoded text on
+// screen, and the Show Buffer Settings popup. Non-visible details (parsing,
+// glob matching, size mapping, precedence) are covered by unit tests on the
 // resolver in `services::editorconfig`.
 
 mod common;
@@ -19,6 +21,7 @@ use common::harness::EditorTestHarness;
 use crossterm::event::{KeyCode, KeyModifiers};
 use fresh::config::Config;
 use std::fs;
+use std::path::Path;
 
 const MARK: &str = "ZZMARK";
 const TAB_GLYPH: char = '→';
@@ -123,3 +126,169 @@ fn editorconfig_section_glob_scopes_settings() {
         "non-matching files keep the default space indentation; got row {row:?}"
     );
 }
+
+/// Save the active buffer with Ctrl+S and return the bytes on disk.
+fn save_and_read(harness: &mut EditorTestHarness, file: &Path) -> Vec<u8> {
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    fs::read(file).unwrap()
+}
+
+/// `trim_trailing_whitespace` and `insert_final_newline` turn the on-save
+/// cleanups on for a project whose user config leaves them off.
+#[test]
+fn editorconfig_enables_on_save_cleanup() {
+    let mut harness = EditorTestHarness::with_temp_project(80, 24).unwrap();
+    let dir = harness.temp_dir_path().unwrap().to_path_buf();
+
+    fs::write(
+        dir.join(".editorconfig"),
+        "root = true\n[*]\ntrim_trailing_whitespace = true\ninsert_final_newline = true\n",
+    )
+    .unwrap();
+    let file = dir.join("notes.dat");
+    fs::write(&file, "one   \ntwo\t").unwrap();
+
+    harness.open_file(&file).unwrap();
+    assert_eq!(save_and_read(&mut harness, &file), b"one\ntwo\n");
+}
+
+/// `trim_trailing_whitespace = false` keeps trailing whitespace even when the
+/// user config trims on save.
+#[test]
+fn editorconfig_disables_configured_trim() {
+    let mut config = Config::default();
+    config.editor.trim_trailing_whitespace_on_save = true;
+
+    let mut harness = EditorTestHarness::with_temp_project_and_config(80, 24, config).unwrap();
+    let dir = harness.temp_dir_path().unwrap().to_path_buf();
+
+    fs::write(
+        dir.join(".editorconfig"),
+        "root = true\n[*.dat]\ntrim_trailing_whitespace = false\n",
+    )
+    .unwrap();
+    let file = dir.join("table.dat");
+    fs::write(&file, "cell  \n").unwrap();
+
+    harness.open_file(&file).unwrap();
+    assert_eq!(save_and_read(&mut harness, &file), b"cell  \n");
+}
+
+/// `end_of_line = crlf` converts an LF file's line endings when it is saved.
+#[test]
+fn editorconfig_end_of_line_converts_on_save() {
+    let mut harness = EditorTestHarness::with_temp_project(80, 24).unwrap();
+    let dir = harness.temp_dir_path().unwrap().to_path_buf();
+
+    fs::write(
+        dir.join(".editorconfig"),
+        "root = true\n[*]\nend_of_line = crlf\n",
+    )
+    .unwrap();
+    let file = dir.join("notes.dat");
+    fs::write(&file, "one\ntwo\n").unwrap();
+
+    harness.open_file(&file).unwrap();
+    assert_eq!(save_and_read(&mut harness, &file), b"one\r\ntwo\r\n");
+}
+
+/// Save As applies the `end_of_line` of the `.editorconfig` section matching
+/// the new path, even for a buffer that had no file (and so no settings) yet.
+#[test]
+fn editorconfig_end_of_line_applies_on_save_as() {
+    let mut harness = EditorTestHarness::with_temp_project(80, 24).unwrap();
+    let dir = harness.project_dir().unwrap();
+
+    fs::write(
+        dir.join(".editorconfig"),
+        "root = true\n[*.dat]\nend_of_line = crlf\n",
+    )
+    .unwrap();
+
+    harness.new_buffer().unwrap();
+    harness.type_text("one").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.type_text("two").unwrap();
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Save as:");
+
+    let file = dir.join("notes.dat");
+    harness.type_text(file.to_str().unwrap()).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    assert_eq!(fs::read(&file).unwrap(), b"one\r\ntwo");
+}
+
+/// `charset = latin1` decodes the file as Latin-1 on load, so a byte that is
+/// not valid UTF-8 renders as the character it stands for.
+#[test]
+fn editorconfig_charset_decodes_on_load() {
+    let mut harness = EditorTestHarness::with_temp_project(80, 24).unwrap();
+    let dir = harness.temp_dir_path().unwrap().to_path_buf();
+
+    fs::write(
+        dir.join(".editorconfig"),
+        "root = true\n[*]\ncharset = latin1\n",
+    )
+    .unwrap();
+    let file = dir.join("menu.dat");
+    fs::write(&file, b"caf\xe9 cr\xe8me\n").unwrap();
+
+    harness.open_file(&file).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("café crème");
+}
+
+/// `max_line_length` becomes the wrap column and a ruler, and Show Buffer
+/// Settings names the `.editorconfig` it came from.
+#[test]
+fn editorconfig_max_line_length_shows_in_buffer_settings() {
+    let mut harness = EditorTestHarness::with_temp_project(120, 30).unwrap();
+    let dir = harness.temp_dir_path().unwrap().to_path_buf();
+
+    fs::write(
+        dir.join(".editorconfig"),
+        "root = true\n[*]\nmax_line_length = 72\n",
+    )
+    .unwrap();
+    let file = dir.join("notes.dat");
+    fs::write(&file, "text\n").unwrap();
+
+    harness.open_file(&file).unwrap();
+    harness.run_palette_command("Show Buffer Settings").unwrap();
+    harness
+        .wait_until(|h| h.screen_to_string().contains("Buffer Settings"))
+        .unwrap();
+
+    let screen = harness.screen_to_string();
+    let row = |label: &str| {
+        screen
+            .lines()
+            .find(|line| line.contains(label))
+            .unwrap_or_else(|| panic!("no {label:?} row in the popup:\n{screen}"))
+            .to_string()
+    };
+    for label in ["Wrap column", "Rulers"] {
+        let row = row(label);
+        assert!(
+            row.contains("72") && row.contains(".editorconfig"),
+            "{label} should be 72 from .editorconfig; got row {row:?}"
+        );
+    }
+    let row = row("Tab size");
+    assert!(
+        row.contains("(config)"),
+        "an unset indent_size comes from config; got row {row:?}"
+    );
+}
@@ -0,0 +1,227 @@
+//! End-to-end exec-carrier tests over a plain `sh -c` carrier — no Podman,
+//! Incus or Distrobox needed.
+//!
+//! The generic exec carrier is "expand an argv template around the command
+//! and run it." A template of `sh -c 'exec "$@"' sh {cmd}` carries the command
+//! straight back onto this machine, so these tests drive the *real* production
+//! paths — template expansion, the agent bootstrap over the `stdio` template,
+//! `RemoteFileSystem`, `RemoteProcessSpawner`, the exec-carrier spawners and
+//! path translation — with only the sandbox substituted.
+
+#![cfg(unix)]
+
+use std::path::Path;
+use std::sync::Arc;
+
+use fresh::services::authority::{
+    connect_exec_authority, Authority, AuthorityPayload, RemoteAgentSpec, RemoteTransportSpec,
+};
+use fresh::services::env_provider::EnvProvider;
+use fresh::services::remote::ExecCarrier;
+use fresh::services::workspace_trust::WorkspaceTrust;
+
+fn python3_available() -> bool {
+    let Some(path) = std::env::var_os("PATH") else {
+        return false;
+    };
+    std::env::split_paths(&path).any(|d| d.join("python3").exists())
+}
+
+fn multi_thread_rt() -> tokio::runtime::Runtime {
+    tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(2)
+        .enable_all()
+        .build()
+        .expect("runtime")
+}
+
+/// The local stand-in carrier: `sh` re-execs its positional args.
+fn sh_template() -> serde_json::Value {
+    serde_json::json!(["sh", "-c", "exec \"$@\"", "sh", "{cmd}"])
+}
+
+fn sh_carrier(workspace: &Path) -> ExecCarrier {
+    serde_json::from_value(serde_json::json!({
+        "run": sh_template(),
+        "workspace": workspace.to_string_lossy(),
+        "label": "sh:local",
+    }))
+    .expect("carrier parses")
+}
+
+/// Read one line from a spawned stdio child, echo `pong` through `cat`, and
+/// return `(first line, echoed line)`.
+fn stdio_round_trip(
+    rt: &tokio::runtime::Runtime,
+    child: &mut fresh::services::remote::StdioChild,
+) -> (String, String) {
+    let mut stdin = child.take_stdin().expect("stdin piped");
+    let stdout = child.take_stdout().expect("stdout piped");
+    rt.block_on(async move {
+        use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+        let mut reader = BufReader::new(stdout);
+        let mut header = String::new();
+        reader.read_line(&mut header).await.expect("read header");
+        stdin.write_all(b"pong\n").await.expect("write stdin");
+        stdin.flush().await.expect("flush stdin");
+        drop(stdin);
+        let mut echoed = String::new();
+        reader.read_line(&mut echoed).await.expect("read echo");
+        (header, echoed)
+    })
+}
+
+#[test]
+fn exec_agent_authority_round_trips_through_sh_carrier() {
+    if !python3_available() {
+        eprintln!("skipping: python3 not found on PATH");
+        return;
+    }
+    let workspace = tempfile::tempdir().expect("tempdir");
+    let ws = workspace.path().to_path_buf();
+
+    // Parsed from the exact `attachRemoteAgent` wire shape.
+    let spec: RemoteAgentSpec = serde_json::from_value(serde_json::json!({
+        "transport": {
+            "kind": "exec",
+            "run": sh_template(),
+            "workspace": ws.to_string_lossy(),
+            "label": "sh:local",
+        },
+        "base_env": [["E2E_BASE", "base"]],
+    }))
+    .expect("attach spec parses");
+    let RemoteTransportSpec::Exec { carrier } = spec.transport else {
+        panic!("expected exec transport");
+    };
+
+    // Attach on a runtime that is then dropped, as the attach-restart does:
+    // the keepalive must own the runtime the agent channel rides on.
+    let attach_rt = multi_thread_rt();
+    let (authority, _keepalive) = attach_rt
+        .block_on(connect_exec_authority(
+            carrier,
+            spec.base_env,
+            Arc::new(WorkspaceTrust::permissive()),
+            Arc::new(EnvProvider::inactive()),
+            None,
+        ))
+        .expect("connect over the sh carrier");
+    drop(attach_rt);
+
+    assert_eq!(authority.display_label, "sh:local");
+    assert_eq!(authority.terminal_wrapper.command, "sh");
+    assert!(authority.terminal_wrapper.manages_cwd);
+
+    // Filesystem over the agent channel.
+    let f = ws.join("hello.txt");
+    authority.filesystem.write_file(&f, b"via carrier").unwrap();
+    assert_eq!(authority.filesystem.read_file(&f).unwrap(), b"via carrier");
+    assert_eq!(std::fs::read(&f).unwrap(), b"via carrier");
+
+    let rt = multi_thread_rt();
+
+    // One-shot spawns go through the agent's `exec`.
+    let r = rt
+        .block_on(authority.process_spawner.spawn(
+            "sh".to_string(),
+            vec!["-c".to_string(), "printf one-shot-ok".to_string()],
+            None,
+        ))
+        .expect("one-shot spawn");
+    assert_eq!((r.exit_code, r.stdout.as_str()), (0, "one-shot-ok"));
+
+    // `command_exists` and LSP-style servers go through the carrier templates.
+    assert!(rt.block_on(authority.long_running_spawner.command_exists("sh")));
+    assert!(!rt.block_on(
+        authority
+            .long_running_spawner
+            .command_exists("definitely-not-a-real-binary-xyz")
+    ));
+    let mut child = rt
+        .block_on(authority.long_running_spawner.spawn_stdio(
+            "sh",
+            &[
+                "-c".to_string(),
+                "echo \"$E2E_VAR|$E2E_BASE|$(pwd)\"; cat".to_string(),
+            ],
+            vec![("E2E_VAR".to_string(), "call".to_string())],
+            Some(ws.as_path()),
+            None,
+        ))
+        .expect("spawn_stdio");
+    let (header, echoed) = stdio_round_trip(&rt, &mut child);
+    assert!(header.starts_with("call|base|"), "env applied: {header:?}");
+    assert!(
+        header.contains(ws.to_string_lossy().as_ref()),
+        "cwd applied: {header:?}"
+    );
+    assert_eq!(echoed, "pong\n");
+    rt.block_on(child.kill()).ok();
+}
+
+#[test]
+fn exec_spawner_payload_translates_host_cwd_to_the_far_side() {
+    // The far side sees the workspace at a different path (a symlink here,
+    // a bind mount for a real container); `path_translation` maps host cwds
+    // onto it before the carrier's `cd`.
+    let root = tempfile::tempdir().expect("tempdir");
+    let host_ws = root.path().join("host");
+    std::fs::create_dir_all(host_ws.join("sub")).unwrap();
+    let far_ws = root.path().join("far");
+    std::os::unix::fs::symlink(&host_ws, &far_ws).unwrap();
+
+    let mut spawner = serde_json::to_value(sh_carrier(&far_ws)).unwrap();
+    spawner["kind"] = "exec".into();
+    let payload: AuthorityPayload = serde_json::from_value(serde_json::json!({
+        "filesystem": { "kind": "local" },
+        "spawner": spawner,
+        "terminal_wrapper": { "kind": "host-shell" },
+        "path_translation": {
+            "host_root": host_ws.to_string_lossy(),
+            "remote_root": far_ws.to_string_lossy(),
+        },
+    }))
+    .expect("payload parses");
+    let authority = Authority::from_plugin_payload(
+        payload,
+        Arc::new(WorkspaceTrust::permissive()),
+        Arc::new(EnvProvider::inactive()),
+    )
+    .expect("payload is valid");
+
+    let rt = multi_thread_rt();
+    let pwd = |cwd: Option<&Path>| {
+        let r = rt
+            .block_on(authority.process_spawner.spawn(
+                "sh".to_string(),
+                vec!["-c".to_string(), "pwd".to_string()],
+                cwd.map(|p| p.to_string_lossy().into_owned()),
+            ))
+            .expect("one-shot spawn through the run template");
+        assert_eq!(r.exit_code, 0, "stderr: {}", r.stderr);
+        r.stdout.trim_end().to_string()
+    };
+    // A host cwd under the workspace runs at its far-side counterpart…
+    assert_eq!(
+        pwd(Some(&host_ws.join("sub"))),
+        far_ws.join("sub").to_string_lossy()
+    );
+    // …and no cwd lands in the carrier's workspace.
+    assert_eq!(pwd(None), far_ws.to_string_lossy());
+
+    // Long-running spawns take the same translated cwd.
+    let mut child = rt
+        .block_on(authority.long_running_spawner.spawn_stdio(
+            "sh",
+            &["-c".to_string(), "pwd; cat".to_string()],
+            Vec::new(),
+            Some(host_ws.as_path()),
+            None,
+        ))
+        .expect("spawn_stdio");
+    let (header, echoed) = stdio_round_trip(&rt, &mut child);
+    assert_eq!(header.trim_end(), far_ws.to_string_lossy());
+    assert_eq!(echoed, "pong\n");
+    rt.block_on(child.kill()).ok();
+}
@@ -17,7 +17,9 @@ use std::sync::{Arc, Once};
 use fresh::model::filesystem::FileSystem;
 use fresh::services::authority::{connect_kube_authority, RemoteAgentSpec};
 use fresh::services::env_provider::EnvProvider;
-use fresh::services::remote::{KubeConnection, KubeTarget, RemoteFileSystem};
+use fresh::services::remote::{
+    CarrierConnection, KubeTarget, KubectlExecTransport, RemoteFileSystem,
+};
 use fresh::services::workspace_trust::WorkspaceTrust;
 
 static PATH_INIT: Once = Once::new();
@@ -79,8 +81,10 @@ fn kube_connection_round_trips_a_file_through_fake_kubectl() {
     // The real connect: real argv, real agent bootstrap, real channel — only
     // the cluster is faked.
     let connection = rt
-        .block_on(KubeConnection::connect(target(&ws)))
-        .expect("KubeConnection::connect over fake kubectl");
+        .block_on(CarrierConnection::connect(&KubectlExecTransport::new(
+            target(&ws),
+        )))
+        .expect("CarrierConnection::connect over fake kubectl");
     assert!(connection.is_connected(), "agent channel is live");
 
     // A write+read round-trip over the real RemoteFileSystem exercises the
@@ -9,9 +9,10 @@
 //! - RemoteFileSystem for file operations
 
 use fresh::model::buffer::TextBuffer;
-use fresh::model::filesystem::{FileSystem, WriteOp};
+use fresh::model::filesystem::{DirWatchEvent, FileSystem, WriteOp};
 use fresh::services::remote::{
-    spawn_local_agent, spawn_local_agent_with_capacity, RemoteFileSystem, TEST_RECV_DELAY_US,
+    spawn_local_agent, spawn_local_agent_with_capacity, unwatch_params, RemoteFileSystem,
+    TEST_RECV_DELAY_US,
 };
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
@@ -1812,3 +1813,122 @@ for line in sys.stdin:
 
     let _ = child.start_kill();
 }
+
+/// Collect watch events until one matches `want`.
+fn wait_for_watch_event(
+    rx: &std::sync::mpsc::Receiver<DirWatchEvent>,
+    want: impl Fn(&DirWatchEvent) -> bool,
+) {
+    loop {
+        let event = rx.recv().expect("watch callback dropped before the event");
+        if want(&event) {
+            return;
+        }
+    }
+}
+
+/// `watch_dir` goes through the agent: ready once the watch is in place,
+/// then each changed entry, until unwatched. A directory that doesn't exist
+/// stops the watch instead.
+#[test]
+fn test_watch_dir_streams_changes_until_unwatched() {
+    let Some((fs, temp_dir, _rt)) = create_test_filesystem() else {
+        eprintln!("Skipping test: could not create test filesystem");
+        return;
+    };
+    let dir = temp_dir.path().to_path_buf();
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    let id = fs
+        .watch_dir(&dir, Box::new(move |event| if tx.send(event).is_err() {}))
+        .unwrap();
+    assert_eq!(rx.recv().unwrap(), DirWatchEvent::Ready);
+
+    let file = dir.join("created.txt");
+    std::fs::write(&file, "new").unwrap();
+    wait_for_watch_event(&rx, |e| *e == DirWatchEvent::Changed(file.clone()));
+
+    fs.unwatch_dir(id);
+    wait_for_watch_event(&rx, |e| *e == DirWatchEvent::Stopped);
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    fs.watch_dir(
+        &dir.join("missing"),
+        Box::new(move |event| if tx.send(event).is_err() {}),
+    )
+    .unwrap();
+    assert_eq!(rx.recv().unwrap(), DirWatchEvent::Stopped);
+}
+
+/// A directory watched through a symlink reports its changes under the
+/// symlinked path the client asked about, not the directory's realpath.
+#[cfg(unix)]
+#[test]
+fn test_watch_dir_reports_paths_under_a_symlinked_dir() {
+    let Some((fs, temp_dir, _rt)) = create_test_filesystem() else {
+        eprintln!("Skipping test: could not create test filesystem");
+        return;
+    };
+    let real = temp_dir.path().join("real");
+    std::fs::create_dir(&real).unwrap();
+    let linked = temp_dir.path().join("linked");
+    std::os::unix::fs::symlink(&real, &linked).unwrap();
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    let id = fs
+        .watch_dir(
+            &linked,
+            Box::new(move |event| if tx.send(event).is_err() {}),
+        )
+        .unwrap();
+    assert_eq!(rx.recv().unwrap(), DirWatchEvent::Ready);
+
+    std::fs::write(real.join("through.txt"), "new").unwrap();
+    wait_for_watch_event(&rx, |e| {
+        *e == DirWatchEvent::Changed(linked.join("through.txt"))
+    });
+    fs.unwatch_dir(id);
+}
+
+/// Where inotify isn't available the agent falls back to scanning the
+/// directory; forcing that mode must report the same changes.
+#[test]
+fn test_agent_watch_stat_polling_fallback() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let dir = temp_dir.path().to_path_buf();
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let Ok(channel) = rt.block_on(spawn_local_agent()) else {
+        eprintln!("Skipping test: could not spawn agent");
+        return;
+    };
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    let params = serde_json::json!({
+        "path": dir.to_string_lossy(),
+        "poll": true,
+        "interval": 0.05,
+    });
+    let id = channel
+        .request_detached(
+            "watch",
+            params,
+            move |data| if tx.send(data).is_err() {},
+            |_| {},
+        )
+        .unwrap();
+    assert_eq!(rx.recv().unwrap()["ready"], "poll");
+
+    let file = dir.join("polled.txt");
+    std::fs::write(&file, "new").unwrap();
+    let file = file.to_string_lossy().into_owned();
+    loop {
+        let data = rx.recv().unwrap();
+        let paths = data["paths"].as_array().cloned().unwrap_or_default();
+        if paths.iter().any(|p| p.as_str() == Some(file.as_str())) {
+            break;
+        }
+    }
+
+    rt.block_on(channel.request("unwatch", unwatch_params(id)))
+        .unwrap();
+}
@@ -0,0 +1,96 @@
+//! `fresh --cmd script batch`, driven against the real binary.
+//!
+//! The headless editor, the per-file save-or-discard and the exit-code mapping
+//! all sit between the CLI and the plugin runtime, so the only honest check is
+//! a real run over real files. HOME and the XDG directories point into the
+//! test's temp dir, so the user's own config, plugins and `init.ts` stay out.
+
+#![cfg(unix)]
+
+use std::path::Path;
+use std::process::{Command, Output};
+
+/// Edits every file, then throws on `bad.txt` — after editing it, so a
+/// discard is the only way its contents survive.
+const SCRIPT: &str = r#"
+editor.insertText(FRESH_BATCH_BUFFER_ID, 0, "// batch\n");
+if (FRESH_BATCH_FILE.endsWith("bad.txt")) {
+  throw new Error("refusing bad.txt");
+}
+return "done";
+"#;
+
+fn run_batch(dir: &Path, files: &[&str]) -> Output {
+    let home = dir.join("home");
+    std::fs::create_dir_all(&home).unwrap();
+    Command::new(env!("CARGO_BIN_EXE_fresh"))
+        .current_dir(dir.join("project"))
+        .env("HOME", &home)
+        .env("XDG_CONFIG_HOME", home.join(".config"))
+        .env("XDG_DATA_HOME", home.join(".local/share"))
+        .env("XDG_STATE_HOME", home.join(".local/state"))
+        .env("FRESH_CMD_TIMEOUT_MS", "60000")
+        .args(["--cmd", "script", "batch", "../edit.ts"])
+        .args(files)
+        .output()
+        .expect("run fresh")
+}
+
+#[test]
+fn batch_saves_on_success_discards_on_throw_and_fails_missing_files() {
+    let temp = tempfile::tempdir().unwrap();
+    let project = temp.path().join("project");
+    std::fs::create_dir_all(&project).unwrap();
+    std::fs::write(temp.path().join("edit.ts"), SCRIPT).unwrap();
+    std::fs::write(project.join("good.txt"), "good\n").unwrap();
+    std::fs::write(project.join("bad.txt"), "bad\n").unwrap();
+
+    let out = run_batch(temp.path(), &["good.txt", "bad.txt", "missing.txt"]);
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    let report = format!("stdout:\n{stdout}\nstderr:\n{stderr}");
+
+    assert_eq!(
+        std::fs::read_to_string(project.join("good.txt")).unwrap(),
+        "// batch\ngood\n",
+        "a settled script's edits are saved\n{report}"
+    );
+    assert_eq!(
+        std::fs::read_to_string(project.join("bad.txt")).unwrap(),
+        "bad\n",
+        "a throwing script's edits are discarded\n{report}"
+    );
+    assert!(
+        !project.join("missing.txt").exists(),
+        "a missing file is reported, not created\n{report}"
+    );
+
+    assert!(stdout.contains("ok      good.txt  (saved)"), "{report}");
+    assert!(
+        stdout.contains("FAILED  bad.txt") && stdout.contains("refusing bad.txt"),
+        "{report}"
+    );
+    assert!(
+        stdout.contains("FAILED  missing.txt  no such file"),
+        "{report}"
+    );
+    assert!(stdout.contains("3 files: 1 ok, 2 failed"), "{report}");
+    assert_eq!(out.status.code(), Some(1), "{report}");
+}
+
+#[test]
+fn batch_exits_zero_when_every_file_succeeds() {
+    let temp = tempfile::tempdir().unwrap();
+    let project = temp.path().join("project");
+    std::fs::create_dir_all(&project).unwrap();
+    std::fs::write(temp.path().join("edit.ts"), SCRIPT).unwrap();
+    std::fs::write(project.join("good.txt"), "good\n").unwrap();
+
+    let out = run_batch(temp.path(), &["good.txt"]);
+    assert!(
+        out.status.success(),
+        "stdout:\n{}\nstderr:\n{}",
+        String::from_utf8_lossy(&out.stdout),
+        String::from_utf8_lossy(&out.stderr)
+    );
+}
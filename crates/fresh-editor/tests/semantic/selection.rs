@@ -3,8 +3,9 @@
 //! The originals drive Ctrl-W / Ctrl-L / Ctrl-Shift-Right and observe
 //! the selection through `harness.editor().active_cursors().primary()`.
 //! The semantic versions dispatch `Action::SelectWord` /
-//! `Action::SelectLine` / `Action::ExpandSelection` and observe via
-//! `expected_selection_text` on the theorem.
+//! `Action::SelectLine` / `Action::SelectWordEnd` and observe via
+//! `expected_selection_text` on the theorem. `ExpandSelection` /
+//! `ShrinkSelection` have no e2e original; their theorems are at the end.
 //!
 //! Skipped (deferred):
 //!   * `test_selection_visual_rendering` — needs a `RenderSnapshot`-
@@ -259,27 +260,27 @@ fn theorem_select_line_last_line_no_trailing_newline() {
 }
 
 // ─────────────────────────────────────────────────────────────────────────
-// ExpandSelection (Ctrl+Shift+Right) — incremental word-boundary growth
+// SelectWordEnd (Ctrl+Shift+Right) — incremental word-boundary growth
 // ─────────────────────────────────────────────────────────────────────────
 
 #[test]
-fn theorem_expand_selection_grows_in_three_steps() {
+fn theorem_select_word_end_grows_in_three_steps() {
     // Replaces test_expand_selection.
     // Cursor at position 3 (inside "hello") in "hello world test".
     // 1st expand → "lo" (cursor → end of current word)
     // 2nd expand → "lo world"
     // 3rd expand → "lo world test"
     assert_buffer_scenario(BufferScenario {
-        description: "ExpandSelection grows by one word at a time".into(),
+        description: "SelectWordEnd grows by one word at a time".into(),
         initial_text: "hello world test".into(),
         actions: vec![
             Action::MoveDocumentStart,
             Action::MoveRight,
             Action::MoveRight,
             Action::MoveRight,
-            Action::ExpandSelection,
-            Action::ExpandSelection,
-            Action::ExpandSelection,
+            Action::SelectWordEnd,
+            Action::SelectWordEnd,
+            Action::SelectWordEnd,
         ],
         expected_text: "hello world test".into(),
         expected_primary: CursorExpect::range(3, 16),
@@ -290,12 +291,12 @@ fn theorem_expand_selection_grows_in_three_steps() {
 }
 
 #[test]
-fn theorem_expand_selection_with_no_initial_selection_picks_word_tail() {
+fn theorem_select_word_end_with_no_initial_selection_picks_word_tail() {
     // Replaces test_expand_selection_no_initial_selection.
     // "foo bar baz", cursor at position 5 (on 'a' in "bar").
     // First expand → "ar".
     assert_buffer_scenario(BufferScenario {
-        description: "ExpandSelection with no prior selection picks cursor-to-word-end".into(),
+        description: "SelectWordEnd with no prior selection picks cursor-to-word-end".into(),
         initial_text: "foo bar baz".into(),
         actions: vec![
             Action::MoveDocumentStart,
@@ -304,7 +305,7 @@ fn theorem_expand_selection_with_no_initial_selection_picks_word_tail() {
             Action::MoveRight,
             Action::MoveRight,
             Action::MoveRight,
-            Action::ExpandSelection,
+            Action::SelectWordEnd,
         ],
         expected_text: "foo bar baz".into(),
         expected_primary: CursorExpect::range(5, 7),
@@ -315,13 +316,13 @@ fn theorem_expand_selection_with_no_initial_selection_picks_word_tail() {
 }
 
 #[test]
-fn theorem_expand_selection_crosses_line_boundary() {
+fn theorem_select_word_end_crosses_line_boundary() {
     // Replaces test_expand_selection_across_lines.
     // After first expand, "ending" is selected; second expand crosses
     // the newline and selects through "second"; third expand grows
     // through "line".
     assert_buffer_scenario(BufferScenario {
-        description: "ExpandSelection crosses line boundaries word-by-word".into(),
+        description: "SelectWordEnd crosses line boundaries word-by-word".into(),
         initial_text: "first line ending\nsecond line starting here".into(),
         // Move to start of "ending" (position 11 = 5+1+5 = "first line " then 'e' at 11)
         actions: vec![
@@ -333,9 +334,9 @@ fn theorem_expand_selection_crosses_line_boundary() {
             Action::MoveLeft,
             Action::MoveLeft,
             Action::MoveLeft,
-            Action::ExpandSelection,
-            Action::ExpandSelection,
-            Action::ExpandSelection,
+            Action::SelectWordEnd,
+            Action::SelectWordEnd,
+            Action::SelectWordEnd,
         ],
         expected_text: "first line ending\nsecond line starting here".into(),
         expected_primary: CursorExpect::range(11, 29),
@@ -346,14 +347,14 @@ fn theorem_expand_selection_crosses_line_boundary() {
 }
 
 #[test]
-fn theorem_expand_selection_on_word_char_picks_current_word() {
+fn theorem_select_word_end_on_word_char_picks_current_word() {
     // Replaces test_expand_selection_on_word_char.
-    // Cursor at byte 0 in "hello world" — first ExpandSelection picks
+    // Cursor at byte 0 in "hello world" — first SelectWordEnd picks
     // the entire current word.
     assert_buffer_scenario(BufferScenario {
-        description: "ExpandSelection from start of word selects the whole word".into(),
+        description: "SelectWordEnd from start of word selects the whole word".into(),
         initial_text: "hello world".into(),
-        actions: vec![Action::MoveDocumentStart, Action::ExpandSelection],
+        actions: vec![Action::MoveDocumentStart, Action::SelectWordEnd],
         expected_text: "hello world".into(),
         expected_primary: CursorExpect::range(0, 5),
         expected_extra_cursors: vec![],
@@ -363,45 +364,37 @@ fn theorem_expand_selection_on_word_char_picks_current_word() {
 }
 
 #[test]
-fn theorem_expand_selection_on_punctuation_run() {
+fn theorem_select_word_end_on_punctuation_run() {
     // Replaces test_expand_selection_on_non_word_char.
-    // FINDING: the e2e original asserts ExpandSelection stops at the
-    // first word boundary ("**-"), but in the semantic harness the
-    // selection grows through the entire token ("**-word"). The
-    // discrepancy is a harness configuration gap: the e2e harness
-    // creates buffers via `EditorTestHarness::new(...)` and types
-    // text in (no on-disk file, no language), while the semantic
-    // runner loads a `.txt` fixture which presumably resolves a
-    // different word-character set. Captured here as the *semantic
-    // harness's* behavior so a future fix that aligns the two will
-    // surface as a deliberate update to this theorem rather than a
-    // silent change.
+    // From punctuation, Ctrl+Shift+Right consumes the punctuation run
+    // and stops at the word boundary, as the e2e original asserts.
+    // (Dispatching `ExpandSelection` here used to select "**-word",
+    // which was recorded as a harness discrepancy; the key is bound to
+    // `SelectWordEnd`.)
     assert_buffer_scenario(BufferScenario {
-        description: "ExpandSelection from punctuation grows through the token (semantic harness)"
-            .into(),
+        description: "SelectWordEnd from punctuation stops at the word boundary".into(),
         initial_text: "**-word".into(),
-        actions: vec![Action::MoveDocumentStart, Action::ExpandSelection],
+        actions: vec![Action::MoveDocumentStart, Action::SelectWordEnd],
         expected_text: "**-word".into(),
-        expected_primary: CursorExpect::range(0, 7),
+        expected_primary: CursorExpect::range(0, 3),
         expected_extra_cursors: vec![],
-        expected_selection_text: Some("**-word".into()),
+        expected_selection_text: Some("**-".into()),
         ..Default::default()
     });
 }
 
 #[test]
-fn theorem_expand_selection_from_middle_of_word_picks_tail() {
+fn theorem_select_word_end_from_middle_of_word_picks_tail() {
     // Replaces test_expand_selection_from_middle_of_word.
-    // Buffer "Event", cursor at position 1 ('v'). ExpandSelection
+    // Buffer "Event", cursor at position 1 ('v'). SelectWordEnd
     // selects from cursor to word end — "vent", not the whole word.
     assert_buffer_scenario(BufferScenario {
-        description: "ExpandSelection from mid-word selects only the tail of the current word"
-            .into(),
+        description: "SelectWordEnd from mid-word selects only the tail of the current word".into(),
         initial_text: "Event".into(),
         actions: vec![
             Action::MoveDocumentStart,
             Action::MoveRight,
-            Action::ExpandSelection,
+            Action::SelectWordEnd,
         ],
         expected_text: "Event".into(),
         expected_primary: CursorExpect::range(1, 5),
@@ -410,3 +403,129 @@ fn theorem_expand_selection_from_middle_of_word_picks_tail() {
         ..Default::default()
     });
 }
+
+// ─────────────────────────────────────────────────────────────────────────
+// ExpandSelection / ShrinkSelection — syntax-aware ranges
+// ─────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn theorem_expand_selection_grows_through_quotes_and_brackets() {
+    // Plain text has no syntax tree, so ranges come from the word, quote
+    // and bracket pairs around the cursor: "x" → "x y" → "\"x y\"" →
+    // "a, \"x y\"" → "(a, \"x y\")".
+    assert_buffer_scenario(BufferScenario {
+        description: "ExpandSelection steps out through word, quotes and brackets".into(),
+        initial_text: "call(a, \"x y\") + 1".into(),
+        actions: vec![
+            Action::MoveDocumentStart,
+            Action::MoveRight,
+            Action::MoveRight,
+            Action::MoveRight,
+            Action::MoveRight,
+            Action::MoveRight,
+            Action::MoveRight,
+            Action::MoveRight,
+            Action::MoveRight,
+            Action::MoveRight,
+            Action::ExpandSelection,
+            Action::ExpandSelection,
+            Action::ExpandSelection,
+            Action::ExpandSelection,
+            Action::ExpandSelection,
+        ],
+        expected_text: "call(a, \"x y\") + 1".into(),
+        expected_primary: CursorExpect::range(4, 14),
+        expected_extra_cursors: vec![],
+        expected_selection_text: Some("(a, \"x y\")".into()),
+        ..Default::default()
+    });
+}
+
+#[test]
+fn theorem_shrink_selection_retraces_expansions() {
+    // Two expansions then two shrinks return to the bare cursor; a third
+    // shrink has nothing left to undo.
+    assert_buffer_scenario(BufferScenario {
+        description: "ShrinkSelection undoes ExpandSelection step by step".into(),
+        initial_text: "f(abc)".into(),
+        actions: vec![
+            Action::MoveDocumentStart,
+            Action::MoveRight,
+            Action::MoveRight,
+            Action::MoveRight,
+            Action::ExpandSelection,
+            Action::ExpandSelection,
+            Action::ShrinkSelection,
+        ],
+        expected_text: "f(abc)".into(),
+        expected_primary: CursorExpect::range(2, 5),
+        expected_extra_cursors: vec![],
+        expected_selection_text: Some("abc".into()),
+        ..Default::default()
+    });
+    assert_buffer_scenario(BufferScenario {
+        description: "ShrinkSelection past the first expansion restores the cursor".into(),
+        initial_text: "f(abc)".into(),
+        actions: vec![
+            Action::MoveDocumentStart,
+            Action::MoveRight,
+            Action::MoveRight,
+            Action::MoveRight,
+            Action::ExpandSelection,
+            Action::ExpandSelection,
+            Action::ShrinkSelection,
+            Action::ShrinkSelection,
+            Action::ShrinkSelection,
+        ],
+        expected_text: "f(abc)".into(),
+        expected_primary: CursorExpect::at(3),
+        expected_extra_cursors: vec![],
+        ..Default::default()
+    });
+}
+
+#[test]
+fn theorem_expand_selection_is_per_cursor() {
+    // Each cursor expands within its own brackets.
+    assert_buffer_scenario(BufferScenario {
+        description: "ExpandSelection expands every cursor independently".into(),
+        initial_text: "f(a)\ng(bb)".into(),
+        actions: vec![
+            Action::MoveDocumentStart,
+            Action::MoveRight,
+            Action::MoveRight,
+            Action::AddCursorBelow,
+            Action::ExpandSelection,
+            Action::ExpandSelection,
+        ],
+        expected_text: "f(a)\ng(bb)".into(),
+        expected_primary: CursorExpect::range(6, 10),
+        expected_extra_cursors: vec![CursorExpect::range(1, 4)],
+        ..Default::default()
+    });
+}
+
+#[test]
+fn theorem_expand_selection_follows_the_syntax_tree() {
+    // JavaScript has a bundled grammar, so ranges are syntax nodes:
+    // "a" → "a + b" → "(a + b)" → "f(a + b)".
+    assert_buffer_scenario(BufferScenario {
+        description: "ExpandSelection walks up tree-sitter nodes".into(),
+        initial_text: "f(a + b);\n".into(),
+        language: Some("x.js".into()),
+        actions: vec![
+            Action::MoveDocumentStart,
+            Action::MoveRight,
+            Action::MoveRight,
+            Action::ExpandSelection,
+            Action::ExpandSelection,
+            Action::ExpandSelection,
+            Action::ExpandSelection,
+        ],
+        expected_text: "f(a + b);\n".into(),
+        expected_primary: CursorExpect::range(0, 8),
+        expected_extra_cursors: vec![],
+        expected_selection_text: Some("f(a + b)".into()),
+        ..Default::default()
+    });
+}
@@ -86,6 +86,7 @@
 //!
 //! This validates TypeScript syntax and writes `plugins/lib/fresh.d.ts`.
 
+use super::watchdog::{self, PluginLimits, PluginProfileEntry, PluginWatchdog, RunKind};
 use anyhow::{anyhow, Result};
 use fresh_core::api::{
     ActionSpec, BufferInfo, CompositeHunk, CreateCompositeBufferOptions, EditorStateSnapshot,
@@ -93,6 +94,7 @@ use fresh_core::api::{
     PluginCommand, PluginMarker, PluginResponse, ScrollbarMarker, SearchHandleRegistry,
     SearchHandleState, SearchTakeResult, SplitWindowOptions,
 };
+use fresh_core::capability::PluginCapability;
 use fresh_core::command::Command;
 use fresh_core::overlay::OverlayNamespace;
 use fresh_core::text_property::TextPropertyEntry;
@@ -387,16 +389,26 @@ fn js_value_to_string(ctx: &rquickjs::Ctx<'_>, val: &Value<'_>) -> String {
     }
 }
 
-/// Format a JavaScript error with full details including stack trace
+/// Format a JavaScript error with full details including stack trace.
+/// Also returns whether the error is one of the runtime's limits rather than
+/// a plugin bug (see [`watchdog::note_js_exception`]).
 fn format_js_error(
     ctx: &rquickjs::Ctx<'_>,
     err: rquickjs::Error,
     source_name: &str,
-) -> anyhow::Error {
+) -> (anyhow::Error, bool) {
+    if matches!(err, rquickjs::Error::Allocation) {
+        watchdog::note_out_of_memory();
+        return (
+            anyhow::anyhow!("JS error in {}: {}", source_name, err),
+            true,
+        );
+    }
     // Check if this is an exception that we can catch for more details
     if err.is_exception() {
         // Try to catch the exception to get the full error object
         let exc = ctx.catch();
+        let limit_error = watchdog::note_js_exception(&exc);
         if !exc.is_undefined() && !exc.is_null() {
             // Try to get error message and stack from the exception object
             if let Some(exc_obj) = exc.as_object() {
@@ -409,15 +421,21 @@ fn format_js_error(
                     .unwrap_or_else(|_| "Error".to_string());
 
                 if !stack.is_empty() {
-                    return anyhow::anyhow!(
-                        "JS error in {}: {}: {}\nStack trace:\n{}",
-                        source_name,
-                        name,
-                        message,
-                        stack
+                    return (
+                        anyhow::anyhow!(
+                            "JS error in {}: {}: {}\nStack trace:\n{}",
+                            source_name,
+                            name,
+                            message,
+                            stack
+                        ),
+                        limit_error,
                     );
                 } else {
-                    return anyhow::anyhow!("JS error in {}: {}: {}", source_name, name, message);
+                    return (
+                        anyhow::anyhow!("JS error in {}: {}: {}", source_name, name, message),
+                        limit_error,
+                    );
                 }
             } else {
                 // Exception is not an object, try to convert to string
@@ -425,23 +443,29 @@ fn format_js_error(
                     .as_string()
                     .and_then(|s: &rquickjs::String| s.to_string().ok())
                     .unwrap_or_else(|| format!("{:?}", exc));
-                return anyhow::anyhow!("JS error in {}: {}", source_name, exc_str);
+                return (
+                    anyhow::anyhow!("JS error in {}: {}", source_name, exc_str),
+                    limit_error,
+                );
             }
         }
     }
 
     // Fall back to the basic error message
-    anyhow::anyhow!("JS error in {}: {}", source_name, err)
+    (
+        anyhow::anyhow!("JS error in {}: {}", source_name, err),
+        false,
+    )
 }
 
 /// Log a JavaScript error with full details
 /// If panic_on_js_errors is enabled, this will panic to surface JS errors immediately
 fn log_js_error(ctx: &rquickjs::Ctx<'_>, err: rquickjs::Error, context: &str) {
-    let error = format_js_error(ctx, err, context);
+    let (error, limit_error) = format_js_error(ctx, err, context);
     tracing::error!("{}", error);
 
     // When enabled, panic on JS errors to make them visible and fail fast
-    if should_panic_on_js_errors() {
+    if should_panic_on_js_errors() && !limit_error {
         panic!("JavaScript error in {}: {}", context, error);
     }
 }
@@ -514,8 +538,9 @@ fn run_pending_jobs_checked(ctx: &rquickjs::Ctx<'_>, context: &str) -> usize {
             } else {
                 format!("{:?}", exc)
             };
+            let limit_error = watchdog::note_js_exception(&exc);
             tracing::error!("Unhandled JS exception during {}: {}", context, error_msg);
-            if should_panic_on_js_errors() {
+            if should_panic_on_js_errors() && !limit_error {
                 panic!("Unhandled JS exception during {}: {}", context, error_msg);
             }
         }
@@ -538,12 +563,13 @@ fn run_pending_jobs_checked(ctx: &rquickjs::Ctx<'_>, context: &str) -> usize {
         } else {
             format!("{:?}", exc)
         };
+        let limit_error = watchdog::note_js_exception(&exc);
         tracing::error!(
             "Unhandled JS exception after running jobs in {}: {}",
             context,
             error_msg
         );
-        if should_panic_on_js_errors() {
+        if should_panic_on_js_errors() && !limit_error {
             panic!(
                 "Unhandled JS exception after running jobs in {}: {}",
                 context, error_msg
@@ -997,6 +1023,46 @@ fn check_range<'js>(
     Ok(())
 }
 
+/// Resolve `.` and `..` components without touching the filesystem, so a
+/// path that doesn't exist yet (the target of a write) can still be checked
+/// against an allowed root.
+fn normalize_lexically(path: &Path) -> PathBuf {
+    use std::path::Component;
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                out.pop();
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+/// Where `path` really lands: the nearest ancestor that exists is
+/// canonicalized through `fs` and the rest appended, so a write through a
+/// symlink is checked against its destination, not the link's location.
+fn resolve_through_links(fs: &dyn fresh_core::services::PluginFilesystem, path: &Path) -> PathBuf {
+    let path = normalize_lexically(path);
+    let mut existing = path.as_path();
+    let mut rest = Vec::new();
+    loop {
+        if let Some(mut resolved) = fs.canonicalize(existing) {
+            resolved.extend(rest.iter().rev());
+            return resolved;
+        }
+        match (existing.parent(), existing.file_name()) {
+            (Some(parent), Some(name)) => {
+                rest.push(name);
+                existing = parent;
+            }
+            _ => return path,
+        }
+    }
+}
+
 // Internal helpers used by the macro-processed `impl JsEditorApi` below.
 // Kept in a plain impl block so they don't get exported as JS methods.
 impl JsEditorApi {
@@ -1027,6 +1093,82 @@ impl JsEditorApi {
         }
     }
 
+    /// Whether this plugin may use `capability` right now. Only package
+    /// plugins are restricted; see `fresh_core::capability`.
+    fn has_capability(&self, capability: PluginCapability) -> bool {
+        self.state_snapshot
+            .read()
+            .map(|s| s.plugin_capabilities.allows(&self.plugin_name, capability))
+            .unwrap_or(true)
+    }
+
+    /// Send a command that needs `capability`. If the plugin doesn't hold it
+    /// the command is handed to the editor wrapped in a
+    /// `RequestPluginCapability`, which asks the user (once) and then runs or
+    /// fails it — so the JS promise still settles either way.
+    fn send_gated(&self, capability: PluginCapability, command: PluginCommand) {
+        let command = if self.has_capability(capability) {
+            command
+        } else {
+            PluginCommand::RequestPluginCapability {
+                plugin_name: self.plugin_name.clone(),
+                capability,
+                command: Some(Box::new(command)),
+            }
+        };
+        let _ = self.command_sender.send(command);
+    }
+
+    /// Whether a synchronous write to `path` may go ahead. Writes inside the
+    /// workspace and the OS temp directory are always allowed; anything else
+    /// needs the `filesystem` capability. Fresh's data directory never is,
+    /// even when it sits under one of those roots: it holds the capability
+    /// store, so writing there would let a plugin approve itself. Symlinks
+    /// are resolved first, so a link in the workspace is no way out of it. A
+    /// refused write still asks the editor to prompt, so the plugin's next
+    /// attempt can succeed once the user approves.
+    fn may_write(&self, path: &fresh_core::api::PluginPath, method: &str) -> bool {
+        if self.has_capability(PluginCapability::Filesystem) {
+            return true;
+        }
+        let fs = self.fs_for(path);
+        let working_dir = self
+            .state_snapshot
+            .read()
+            .map(|s| s.working_dir.clone())
+            .unwrap_or_default();
+        let target = resolve_through_links(fs.as_ref(), &working_dir.join(path.as_str()));
+        let data_dir = resolve_through_links(fs.as_ref(), &self.services.data_dir());
+        let roots = [working_dir, std::env::temp_dir()];
+        if !target.starts_with(&data_dir)
+            && roots
+                .iter()
+                .any(|root| target.starts_with(resolve_through_links(fs.as_ref(), root)))
+        {
+            return true;
+        }
+        self.refuse_filesystem(method, &target);
+        false
+    }
+
+    /// Log a write refused for lack of the `filesystem` capability and ask
+    /// the editor to prompt for it.
+    fn refuse_filesystem(&self, method: &str, target: &Path) {
+        tracing::warn!(
+            "{} refused: plugin '{}' has not been granted the filesystem capability to write {:?}",
+            method,
+            self.plugin_name,
+            target
+        );
+        let _ = self
+            .command_sender
+            .send(PluginCommand::RequestPluginCapability {
+                plugin_name: self.plugin_name.clone(),
+                capability: PluginCapability::Filesystem,
+                command: None,
+            });
+    }
+
     /// Send an AddPluginConfigField command to the host.
     fn send_field_registration(&self, field_name: &str, field_schema: serde_json::Value) {
         let _ = self
@@ -1618,6 +1760,11 @@ impl JsEditorApi {
     /// Save a buffer to a specific file path
     /// Used by :w filename to save unnamed buffers or save-as
     pub fn save_buffer_to_path(&self, buffer_id: u32, path: String) -> bool {
+        let target = fresh_core::api::PluginPath::Authority { window: None, path };
+        if !self.may_write(&target, "saveBufferToPath") {
+            return false;
+        }
+        let path = target.as_str().to_string();
         self.command_sender
             .send(PluginCommand::SaveBufferToPath {
                 buffer_id: BufferId(buffer_id as usize),
@@ -2476,6 +2623,9 @@ impl JsEditorApi {
         path: fresh_core::api::PluginPath,
         content: String,
     ) -> bool {
+        if !self.may_write(&path, "writeFile") {
+            return false;
+        }
         self.fs_for(&path)
             .write_file(Path::new(path.as_str()), content.as_bytes())
     }
@@ -2501,12 +2651,17 @@ impl JsEditorApi {
         #[plugin_api(ts_type = "string | LocalPath | WindowPath | AuthorityPath")]
         path: fresh_core::api::PluginPath,
     ) -> bool {
+        if !self.may_write(&path, "createDir") {
+            return false;
+        }
         self.fs_for(&path).create_dir_all(Path::new(path.as_str()))
     }
 
     /// Permanently remove a file or directory on the path's filesystem
     /// (recursively for directories). For safety, the path must be under the OS
-    /// temp directory or the Fresh config directory. Returns true on success.
+    /// temp directory or the Fresh config directory; anything outside the temp
+    /// directory, and Fresh's own config and data directories wherever they
+    /// are, also needs the `filesystem` capability. Returns true on success.
     pub fn remove_path(
         &self,
         #[plugin_api(ts_type = "string | LocalPath | WindowPath | AuthorityPath")]
@@ -2548,6 +2703,15 @@ impl JsEditorApi {
             return false;
         }
 
+        let data_dir = resolve_through_links(fs.as_ref(), &self.services.data_dir());
+        let scratch = target.starts_with(&temp_dir)
+            && !target.starts_with(&config_dir)
+            && !target.starts_with(&data_dir);
+        if !scratch && !self.has_capability(PluginCapability::Filesystem) {
+            self.refuse_filesystem("removePath", &target);
+            return false;
+        }
+
         fs.remove_path(&target)
     }
 
@@ -2560,7 +2724,11 @@ impl JsEditorApi {
         #[plugin_api(ts_type = "string | LocalPath | WindowPath | AuthorityPath")]
         to: fresh_core::api::PluginPath,
     ) -> bool {
-        if !Self::same_backend(&from, &to) {
+        // A move deletes its source, so both ends are writes.
+        if !Self::same_backend(&from, &to)
+            || !self.may_write(&from, "renamePath")
+            || !self.may_write(&to, "renamePath")
+        {
             return false;
         }
         self.fs_for(&from)
@@ -2576,7 +2744,7 @@ impl JsEditorApi {
         #[plugin_api(ts_type = "string | LocalPath | WindowPath | AuthorityPath")]
         to: fresh_core::api::PluginPath,
     ) -> bool {
-        if !Self::same_backend(&from, &to) {
+        if !Self::same_backend(&from, &to) || !self.may_write(&to, "copyPath") {
             return false;
         }
         self.fs_for(&from)
@@ -6719,13 +6887,16 @@ impl JsEditorApi {
             stdout_to_path,
             id
         );
-        let _ = self.command_sender.send(PluginCommand::SpawnProcess {
-            callback_id: JsCallbackId::new(id),
-            command,
-            args,
-            cwd: effective_cwd,
-            stdout_to: stdout_to_path,
-        });
+        self.send_gated(
+            PluginCapability::Process,
+            PluginCommand::SpawnProcess {
+                callback_id: JsCallbackId::new(id),
+                command,
+                args,
+                cwd: effective_cwd,
+                stdout_to: stdout_to_path,
+            },
+        );
         id
     }
 
@@ -6755,12 +6926,15 @@ impl JsEditorApi {
                 .ok()
                 .map(|s| s.working_dir.to_string_lossy().to_string())
         });
-        let _ = self.command_sender.send(PluginCommand::SpawnHostProcess {
-            callback_id: JsCallbackId::new(id),
-            command,
-            args,
-            cwd: effective_cwd,
-        });
+        self.send_gated(
+            PluginCapability::Process,
+            PluginCommand::SpawnHostProcess {
+                callback_id: JsCallbackId::new(id),
+                command,
+                args,
+                cwd: effective_cwd,
+            },
+        );
         id
     }
 
@@ -6795,9 +6969,10 @@ impl JsEditorApi {
         #[plugin_api(ts_type = "AuthorityPayload")] payload: rquickjs::Value<'_>,
     ) -> bool {
         let json = js_to_json(&ctx, payload);
-        let _ = self
-            .command_sender
-            .send(PluginCommand::SetAuthority { payload: json });
+        self.send_gated(
+            PluginCapability::Authority,
+            PluginCommand::SetAuthority { payload: json },
+        );
         true
     }
 
@@ -6805,7 +6980,7 @@ impl JsEditorApi {
     /// `setAuthority`.
     #[plugin_api(js_name = "clearAuthority")]
     pub fn clear_authority(&self) {
-        let _ = self.command_sender.send(PluginCommand::ClearAuthority);
+        self.send_gated(PluginCapability::Authority, PluginCommand::ClearAuthority);
     }
 
     /// Attach to a remote agent that needs a live connection (an SSH host or a
@@ -6832,10 +7007,13 @@ impl JsEditorApi {
     ) -> u64 {
         let json = js_to_json(&ctx, payload);
         let id = self.alloc_request_id();
-        let _ = self.command_sender.send(PluginCommand::AttachRemoteAgent {
-            payload: json,
-            request_id: id,
-        });
+        self.send_gated(
+            PluginCapability::Authority,
+            PluginCommand::AttachRemoteAgent {
+                payload: json,
+                request_id: id,
+            },
+        );
         id
     }
 
@@ -6929,11 +7107,14 @@ impl JsEditorApi {
             target_path,
             id
         );
-        let _ = self.command_sender.send(PluginCommand::HttpFetch {
-            url,
-            target_path: std::path::PathBuf::from(target_path),
-            callback_id: JsCallbackId::new(id),
-        });
+        self.send_gated(
+            PluginCapability::Network,
+            PluginCommand::HttpFetch {
+                url,
+                target_path: std::path::PathBuf::from(target_path),
+                callback_id: JsCallbackId::new(id),
+            },
+        );
         id
     }
 
@@ -7311,6 +7492,40 @@ impl JsEditorApi {
         id
     }
 
+    /// Project-wide structural search (async)
+    /// Matches a code pattern with `$NAME` / `$$$NAME` metavariables against
+    /// the syntax tree of every project file in `language`. With `rewrite`,
+    /// each match also carries its replacement text.
+    #[plugin_api(
+        async_promise,
+        js_name = "structuralSearch",
+        ts_return = "StructuralMatch[]"
+    )]
+    #[qjs(rename = "_structuralSearchStart")]
+    pub fn structural_search_start(
+        &self,
+        _ctx: rquickjs::Ctx<'_>,
+        pattern: String,
+        language: String,
+        rewrite: Option<String>,
+        file_glob: Option<String>,
+        max_results: Option<u32>,
+    ) -> u64 {
+        let id = self.alloc_request_id();
+        let _ = self
+            .command_sender
+            .send(PluginCommand::StructuralSearchProject {
+                plugin_name: self.plugin_name.clone(),
+                pattern,
+                language,
+                rewrite,
+                file_glob: file_glob.unwrap_or_default(),
+                max_results: max_results.unwrap_or(200) as usize,
+                callback_id: JsCallbackId::new(id),
+            });
+        id
+    }
+
     /// Begin a streaming project-wide search and return a `SearchHandle`.
     /// The producer (host) writes matches at full speed into shared state;
     /// the consumer drains via `handle.take()` at its own cadence. Call
@@ -7449,6 +7664,39 @@ impl JsEditorApi {
             buffer_id: buffer_id.0.unwrap_or(0) as usize,
             matches: match_pairs,
             replacement,
+            replacements: Vec::new(),
+            callback_id: JsCallbackId::new(id),
+        });
+        id
+    }
+
+    /// Replace matches in a file's buffer, each with its own text (async)
+    /// Like `replaceInFile`, but `replacements[i]` replaces `matches[i]`.
+    #[plugin_api(
+        async_promise,
+        js_name = "replaceMatchesInFile",
+        ts_raw = "replaceMatchesInFile(filePath: string, matches: number[][], replacements: string[], bufferId?: number): Promise<ReplaceResult>"
+    )]
+    #[qjs(rename = "_replaceMatchesInFileStart")]
+    pub fn replace_matches_in_file_start(
+        &self,
+        _ctx: rquickjs::Ctx<'_>,
+        file_path: String,
+        matches: Vec<Vec<u32>>,
+        replacements: Vec<String>,
+        buffer_id: rquickjs::function::Opt<u32>,
+    ) -> u64 {
+        let id = self.alloc_request_id();
+        let match_pairs: Vec<(usize, usize)> = matches
+            .iter()
+            .map(|m| (m[0] as usize, m[1] as usize))
+            .collect();
+        let _ = self.command_sender.send(PluginCommand::ReplaceInBuffer {
+            file_path: PathBuf::from(file_path),
+            buffer_id: buffer_id.0.unwrap_or(0) as usize,
+            matches: match_pairs,
+            replacement: String::new(),
+            replacements,
             callback_id: JsCallbackId::new(id),
         });
         id
@@ -7504,15 +7752,16 @@ impl JsEditorApi {
             .background_process_ids
             .push(process_id);
         // Match `spawn_process_start`: empty-string cwd == omitted.
-        let _ = self
-            .command_sender
-            .send(PluginCommand::SpawnBackgroundProcess {
+        self.send_gated(
+            PluginCapability::Process,
+            PluginCommand::SpawnBackgroundProcess {
                 process_id,
                 command,
                 args,
                 cwd: cwd.0.filter(|s| !s.is_empty()),
                 callback_id: JsCallbackId::new(id),
-            });
+            },
+        );
         id
     }
 
@@ -7557,7 +7806,10 @@ impl JsEditorApi {
         if let Ok(mut owners) = self.async_resource_owners.lock() {
             owners.insert(id, self.plugin_name.clone());
         }
-        let _ = self.command_sender.send(PluginCommand::CreateTerminal {
+        // A terminal that runs a plugin-chosen command is a process spawn; a
+        // plain shell is the user's own.
+        let runs_command = opts.command.is_some() || opts.resume.is_some();
+        let command = PluginCommand::CreateTerminal {
             cwd: opts.cwd,
             direction: opts.direction,
             ratio: opts.ratio,
@@ -7573,7 +7825,12 @@ impl JsEditorApi {
             env: opts.env,
             allow_script: opts.allow_script.unwrap_or(false),
             request_id: id,
-        });
+        };
+        if runs_command {
+            self.send_gated(PluginCapability::Process, command);
+        } else {
+            let _ = self.command_sender.send(command);
+        }
         Ok(id)
     }
 
@@ -7597,20 +7854,24 @@ impl JsEditorApi {
         if let Ok(mut owners) = self.async_resource_owners.lock() {
             owners.insert(id, self.plugin_name.clone());
         }
-        let _ = self
-            .command_sender
-            .send(PluginCommand::CreateWindowWithTerminal {
-                root: std::path::PathBuf::from(opts.root),
-                label: opts.label,
-                cwd: opts.cwd,
-                command: opts.command,
-                title: opts.title,
-                resume: opts.resume,
-                env: opts.env,
-                allow_script: opts.allow_script.unwrap_or(false),
-                adopt_window: opts.adopt_window.map(fresh_core::WindowId),
-                request_id: id,
-            });
+        let runs_command = opts.command.is_some() || opts.resume.is_some();
+        let command = PluginCommand::CreateWindowWithTerminal {
+            root: std::path::PathBuf::from(opts.root),
+            label: opts.label,
+            cwd: opts.cwd,
+            command: opts.command,
+            title: opts.title,
+            resume: opts.resume,
+            env: opts.env,
+            allow_script: opts.allow_script.unwrap_or(false),
+            adopt_window: opts.adopt_window.map(fresh_core::WindowId),
+            request_id: id,
+        };
+        if runs_command {
+            self.send_gated(PluginCapability::Process, command);
+        } else {
+            let _ = self.command_sender.send(command);
+        }
         Ok(id)
     }
 
@@ -7934,6 +8195,9 @@ pub struct QuickJsBackend {
     plugin_api_exports: PluginApiExports,
     /// Streaming-search handle registry shared with the editor thread.
     search_handles: SearchHandleRegistry,
+    /// Which plugin is running and for how long: interrupts runaway code,
+    /// notices the memory limit and profiles hook and command time.
+    watchdog: Rc<PluginWatchdog>,
 }
 
 impl Drop for QuickJsBackend {
@@ -8386,6 +8650,7 @@ impl QuickJsBackend {
                         format!("{:?}", reason)
                     };
 
+                    let limit_error = watchdog::note_js_exception(&reason);
                     tracing::error!("Unhandled Promise rejection: {}", error_msg);
 
                     // An agent-submitted script has a caller waiting on a
@@ -8409,7 +8674,7 @@ impl QuickJsBackend {
                         });
                     }
 
-                    if should_panic_on_js_errors() {
+                    if should_panic_on_js_errors() && !limit_error {
                         // Don't panic here - we're inside an FFI callback and rquickjs catches panics.
                         // Instead, set a fatal error flag that the plugin thread loop will check.
                         let full_msg = format!("Unhandled Promise rejection: {}", error_msg);
@@ -8419,6 +8684,14 @@ impl QuickJsBackend {
             },
         )));
 
+        // The interrupt handler runs every few thousand bytecode ops; it
+        // aborts a run of plugin code that outlived the execution timeout.
+        let watchdog = Rc::new(PluginWatchdog::default());
+        let interrupt_watchdog = Rc::clone(&watchdog);
+        runtime.set_interrupt_handler(Some(Box::new(move || {
+            interrupt_watchdog.should_interrupt()
+        })));
+
         let main_context = Context::full(&runtime)
             .map_err(|e| anyhow!("Failed to create QuickJS context: {}", e))?;
 
@@ -8454,7 +8727,9 @@ impl QuickJsBackend {
             registered_lsp_servers,
             plugin_api_exports,
             search_handles,
+            watchdog,
         };
+        backend.set_limits(PluginLimits::default());
 
         // Initialize main context (for internal utilities if needed)
         backend.setup_context_api(&backend.main_context.clone(), "internal")?;
@@ -8463,7 +8738,31 @@ impl QuickJsBackend {
         Ok(backend)
     }
 
-    /// Set up the editor API in a specific JavaScript context
+    /// Apply execution and memory limits. The memory limit covers the one
+    /// runtime every plugin shares.
+    pub fn set_limits(&self, limits: PluginLimits) {
+        self.watchdog.set_limits(limits);
+        // QuickJS treats 0 as "no limit".
+        self.runtime
+            .set_memory_limit(limits.memory_limit_bytes.unwrap_or(0));
+    }
+
+    /// Cumulative time per plugin and per hook or command, slowest first.
+    pub fn profile(&self) -> Vec<PluginProfileEntry> {
+        self.watchdog.profile()
+    }
+
+    /// Plugins that ran into an execution or memory limit since the last
+    /// call. The plugin thread disables them.
+    pub(crate) fn take_tripped_plugins(&self) -> Vec<(String, watchdog::TrippedLimit)> {
+        self.watchdog.take_tripped()
+    }
+
+    /// Collect garbage, e.g. after dropping a plugin that ran out of memory.
+    pub(crate) fn run_gc(&self) {
+        self.runtime.run_gc();
+    }
+
     /// Build a fresh [`JsEditorApi`] handle for `plugin_name`, cloning the
     /// shared runtime state this backend hands to every plugin context.
     fn build_editor_api(&self, plugin_name: &str) -> JsEditorApi {
@@ -8613,6 +8912,7 @@ impl QuickJsBackend {
         let wrapped_code = format!("(function() {{ {} }})();", code);
         let wrapped = wrapped_code.as_str();
 
+        let _run = self.watchdog.enter(plugin_name, RunKind::Load, "");
         context.with(|ctx| {
             tracing::debug!("execute_js: executing plugin code for '{}'", plugin_name);
 
@@ -8622,7 +8922,7 @@ impl QuickJsBackend {
             eval_options.filename = Some(source_name.to_string());
             let result = ctx
                 .eval_with_options::<(), _>(wrapped.as_bytes(), eval_options)
-                .map_err(|e| format_js_error(&ctx, e, source_name));
+                .map_err(|e| format_js_error(&ctx, e, source_name).0);
 
             tracing::debug!(
                 "execute_js: plugin code execution finished for '{}', result: {:?}",
@@ -8955,9 +9255,15 @@ impl QuickJsBackend {
                 if target.is_some_and(|t| t != handler.plugin_name) {
                     continue;
                 }
+                if self.watchdog.has_tripped(&handler.plugin_name) {
+                    continue;
+                }
                 let Some(context) = plugin_contexts.get(&handler.plugin_name) else {
                     continue;
                 };
+                let _run = self
+                    .watchdog
+                    .enter(&handler.plugin_name, RunKind::Hook, event_name);
                 context.with(|ctx| {
                     call_handler(&ctx, &handler.handler_name, event_data);
                 });
@@ -9099,6 +9405,9 @@ impl QuickJsBackend {
         };
 
         tracing::info!("start_action: evaluating JS code");
+        let run = self
+            .watchdog
+            .enter(&plugin_name, RunKind::Command, action_name);
         context.with(|ctx| {
             if let Err(e) = ctx.eval::<rquickjs::Value, _>(code.as_bytes()) {
                 log_js_error(&ctx, e, &format!("action {}", action_name));
@@ -9109,6 +9418,7 @@ impl QuickJsBackend {
             tracing::info!("start_action: executed {} pending jobs", count);
         });
 
+        drop(run);
         tracing::info!("start_action: END '{}'", action_name);
 
         // Clear execution state (action started, may still be running async)
@@ -9161,6 +9471,9 @@ impl QuickJsBackend {
             action = action_name
         );
 
+        let _run = self
+            .watchdog
+            .enter(&plugin_name, RunKind::Command, action_name);
         context.with(|ctx| {
             // Eval returns a Promise for the async IIFE, which we need to drive
             match ctx.eval::<rquickjs::Value, _>(code.as_bytes()) {
@@ -9204,12 +9517,14 @@ impl QuickJsBackend {
         // Poll all plugin contexts
         let contexts = self.plugin_contexts.borrow().clone();
         for (name, context) in contexts {
-            context.with(|ctx| {
-                let count = run_pending_jobs_checked(&ctx, &format!("poll_event_loop {}", name));
-                if count > 0 {
-                    had_work = true;
-                }
-            });
+            let run = self.watchdog.enter(&name, RunKind::Async, "");
+            let count = context
+                .with(|ctx| run_pending_jobs_checked(&ctx, &format!("poll_event_loop {}", name)));
+            if count > 0 {
+                had_work = true;
+            } else {
+                run.discard();
+            }
         }
         had_work
     }
@@ -9295,6 +9610,7 @@ impl QuickJsBackend {
             return;
         };
 
+        let _run = self.watchdog.enter(&name, RunKind::Async, "");
         context.with(|ctx| {
             // Parse JSON string to serde_json::Value
             let json_value: serde_json::Value = match serde_json::from_str(result_json) {
@@ -9372,6 +9688,7 @@ impl QuickJsBackend {
             return;
         };
 
+        let _run = self.watchdog.enter(&name, RunKind::Async, "");
         context.with(|ctx| {
             // Get _rejectCallback function from globalThis
             let globals = ctx.globals();
@@ -9631,6 +9948,184 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_api_gates_calls_on_plugin_capabilities() {
+        let (tx, rx) = mpsc::channel();
+        let mut snapshot = EditorStateSnapshot::new();
+        snapshot
+            .plugin_capabilities
+            .restrict("test", [PluginCapability::Network]);
+        let state_snapshot = Arc::new(RwLock::new(snapshot));
+        let services = Arc::new(TestServiceBridge::new());
+        let mut backend = QuickJsBackend::with_state(state_snapshot, tx, services).unwrap();
+
+        backend
+            .execute_js(
+                r#"
+            const editor = getEditor();
+            editor.spawnProcess("ls", []);
+            editor.httpFetch("https://example.com", "/tmp/out");
+            globalThis.wrote = editor.writeFile("/not-the-workspace/x.txt", "x");
+            globalThis.selfGranted = editor.writeFile(
+                editor.getDataDir() + "/plugin_capabilities.json",
+                '{"plugins":{"test":{"process":"granted"}}}'
+            );
+            globalThis.savedAs = editor.saveBufferToPath(1, "/not-the-workspace/y.txt");
+            editor.createTerminal({ command: ["sh", "-c", "true"] });
+            editor.createWindowWithTerminal({ root: "/w", command: ["sh"] });
+            editor.createTerminal({});
+        "#,
+                "test.js",
+            )
+            .unwrap();
+
+        let commands: Vec<PluginCommand> = rx.try_iter().collect();
+        assert!(
+            matches!(
+                &commands[0],
+                PluginCommand::RequestPluginCapability {
+                    plugin_name,
+                    capability: PluginCapability::Process,
+                    command: Some(inner),
+                } if plugin_name == "test"
+                    && matches!(**inner, PluginCommand::SpawnProcess { .. })
+            ),
+            "spawnProcess without the process capability must ask first: {:?}",
+            commands[0]
+        );
+        assert!(matches!(commands[1], PluginCommand::HttpFetch { .. }));
+        let fs_refusal = |c: &PluginCommand| {
+            matches!(
+                c,
+                PluginCommand::RequestPluginCapability {
+                    capability: PluginCapability::Filesystem,
+                    command: None,
+                    ..
+                }
+            )
+        };
+        assert!(fs_refusal(&commands[2]));
+        // The data directory holds the capability store: a plugin writing it
+        // must not be able to grant itself anything.
+        assert!(
+            fs_refusal(&commands[3]),
+            "writing the capability store must be refused: {:?}",
+            commands[3]
+        );
+        assert!(fs_refusal(&commands[4]), "saveBufferToPath is gated");
+        let gated_process = |c: &PluginCommand| match c {
+            PluginCommand::RequestPluginCapability {
+                capability: PluginCapability::Process,
+                command: Some(inner),
+                ..
+            } => Some(inner.as_ref().clone()),
+            _ => None,
+        };
+        assert!(matches!(
+            gated_process(&commands[5]),
+            Some(PluginCommand::CreateTerminal { .. })
+        ));
+        assert!(matches!(
+            gated_process(&commands[6]),
+            Some(PluginCommand::CreateWindowWithTerminal { .. })
+        ));
+        // A plain shell runs nothing the plugin chose.
+        assert!(matches!(commands[7], PluginCommand::CreateTerminal { .. }));
+        assert_eq!(commands.len(), 8);
+
+        backend
+            .plugin_contexts
+            .borrow()
+            .get("test")
+            .unwrap()
+            .clone()
+            .with(|ctx| {
+                for name in ["wrote", "selfGranted", "savedAs"] {
+                    let ok: bool = ctx.globals().get(name).unwrap();
+                    assert!(!ok, "{name} should have been refused");
+                }
+            });
+    }
+
+    /// The filesystem gate looks where a path really lands: through
+    /// symlinks, at both ends of a move, and at removals of Fresh's own
+    /// directories.
+    #[cfg(unix)]
+    #[test]
+    fn test_filesystem_gate_follows_links_moves_and_removals() {
+        use fresh_core::services::PluginServiceBridge;
+        let ws = std::env::temp_dir().join(format!("fresh_fs_gate_{}", std::process::id()));
+        std::fs::create_dir_all(&ws).unwrap();
+        let ws = ws.canonicalize().unwrap();
+        std::os::unix::fs::symlink("/proc", ws.join("out")).unwrap();
+        let services = Arc::new(TestServiceBridge::new());
+        std::fs::create_dir_all(services.config_dir()).unwrap();
+        let config_file = services
+            .config_dir()
+            .join(format!("remove_gate_{}.json", std::process::id()));
+        std::fs::write(&config_file, "{}").unwrap();
+
+        let (tx, rx) = mpsc::channel();
+        let mut snapshot = EditorStateSnapshot::new();
+        snapshot
+            .plugin_capabilities
+            .restrict("test", [PluginCapability::Network]);
+        snapshot.working_dir = ws.clone();
+        let state_snapshot = Arc::new(RwLock::new(snapshot));
+        let mut backend = QuickJsBackend::with_state(state_snapshot, tx, services).unwrap();
+
+        backend
+            .execute_js(
+                &format!(
+                    r#"
+            const editor = getEditor();
+            globalThis.inside = editor.writeFile("{ws}/inside.txt", "x");
+            globalThis.throughLink = editor.writeFile("{ws}/out/x", "x");
+            globalThis.movedIn = editor.renamePath("/not-the-workspace/secret", "{ws}/stolen");
+            globalThis.removedConfig = editor.removePath("{config}");
+            globalThis.removedScratch = editor.removePath("{ws}/inside.txt");
+        "#,
+                    ws = ws.display(),
+                    config = config_file.display()
+                ),
+                "test.js",
+            )
+            .unwrap();
+
+        let refusals = rx
+            .try_iter()
+            .filter(|c| {
+                matches!(
+                    c,
+                    PluginCommand::RequestPluginCapability {
+                        capability: PluginCapability::Filesystem,
+                        command: None,
+                        ..
+                    }
+                )
+            })
+            .count();
+        assert_eq!(refusals, 3, "link, move source and config removal ask");
+        assert!(config_file.exists(), "the config file must survive");
+        std::fs::remove_file(&config_file).unwrap();
+        std::fs::remove_dir_all(&ws).unwrap();
+
+        backend
+            .plugin_contexts
+            .borrow()
+            .get("test")
+            .unwrap()
+            .clone()
+            .with(|ctx| {
+                let get = |name: &str| -> bool { ctx.globals().get(name).unwrap() };
+                assert!(get("inside"), "writes inside the workspace go ahead");
+                assert!(get("removedScratch"), "scratch files can be removed");
+                for name in ["throughLink", "movedIn", "removedConfig"] {
+                    assert!(!get(name), "{name} should have been refused");
+                }
+            });
+    }
+
     #[test]
     fn test_api_register_command() {
         let (mut backend, rx) = create_test_backend();
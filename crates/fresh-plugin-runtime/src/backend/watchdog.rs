@@ -0,0 +1,386 @@
+//! Execution watchdog, memory cap and profiler for plugin JavaScript.
+//!
+//! Every plugin shares one QuickJS runtime. The backend brackets each
+//! synchronous run of plugin code — loading it, a hook handler, a command,
+//! an async continuation — with [`PluginWatchdog::enter`], so the watchdog
+//! always knows which plugin is running and since when.
+//!
+//! - The runtime's interrupt handler asks [`PluginWatchdog::should_interrupt`],
+//!   which aborts a run that outlived the execution timeout (a `while (true)`).
+//! - Allocations past the runtime's memory limit fail inside QuickJS and
+//!   surface as "out of memory" errors; the backend's error paths report them
+//!   through [`note_js_exception`], and they are charged to the running plugin.
+//!
+//! Either way the plugin is recorded as tripped, and the plugin thread
+//! disables it once control is back on the Rust side. The same brackets feed
+//! the profiler: call count and cumulative time per plugin and per hook or
+//! command, listed by "Plugin Profiler".
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+/// Default for [`PluginLimits::execution_timeout`].
+pub const DEFAULT_EXECUTION_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Default for [`PluginLimits::memory_limit_bytes`].
+pub const DEFAULT_MEMORY_LIMIT_BYTES: usize = 512 * 1024 * 1024;
+
+/// Resource limits applied to plugin JavaScript.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PluginLimits {
+    /// Longest a single synchronous run of plugin code may take before it is
+    /// interrupted. `None` disables the watchdog.
+    pub execution_timeout: Option<Duration>,
+    /// Heap cap of the QuickJS runtime all plugins share. `None` leaves it
+    /// unlimited.
+    pub memory_limit_bytes: Option<usize>,
+}
+
+impl Default for PluginLimits {
+    fn default() -> Self {
+        Self {
+            execution_timeout: Some(DEFAULT_EXECUTION_TIMEOUT),
+            memory_limit_bytes: Some(DEFAULT_MEMORY_LIMIT_BYTES),
+        }
+    }
+}
+
+/// The limit a plugin ran into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrippedLimit {
+    /// A synchronous run took longer than this.
+    Timeout(Duration),
+    /// An allocation failed against the runtime's memory limit.
+    OutOfMemory,
+}
+
+impl fmt::Display for TrippedLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Timeout(limit) => write!(f, "ran for more than {} ms", limit.as_millis()),
+            Self::OutOfMemory => write!(f, "exceeded the plugin memory limit"),
+        }
+    }
+}
+
+/// What a run of plugin code was doing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum RunKind {
+    /// Top-level plugin code, run once when the plugin loads.
+    Load,
+    /// A handler of an editor hook (`render_line`, `cursor_moved`, …).
+    Hook,
+    /// A registered command or action.
+    Command,
+    /// Promise continuations: code after an `await`, resolved callbacks.
+    Async,
+}
+
+impl fmt::Display for RunKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Load => "load",
+            Self::Hook => "hook",
+            Self::Command => "command",
+            Self::Async => "async",
+        })
+    }
+}
+
+/// Cumulative time one plugin spent in one hook, command or other kind of
+/// run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginProfileEntry {
+    pub plugin: String,
+    pub kind: RunKind,
+    /// Hook or command name; empty for [`RunKind::Load`] and
+    /// [`RunKind::Async`].
+    pub name: String,
+    pub calls: u64,
+    pub total: Duration,
+    pub max: Duration,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct RunStats {
+    calls: u64,
+    total: Duration,
+    max: Duration,
+}
+
+#[derive(Debug)]
+struct Run {
+    plugin: String,
+    kind: RunKind,
+    name: String,
+    started: Instant,
+    tripped: bool,
+}
+
+thread_local! {
+    /// Set when QuickJS reported an "out of memory" error on this thread and
+    /// not yet charged to a run. See [`note_js_exception`].
+    static OUT_OF_MEMORY: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Report a JS exception the backend caught. QuickJS raises both limits as an
+/// `InternalError` with a fixed message — "out of memory" when an allocation
+/// fails against the memory limit, "interrupted" when the interrupt handler
+/// aborts a run — so only those exact exceptions count; a plugin error that
+/// merely mentions either phrase does not. An out-of-memory error is charged
+/// to the plugin running when the current run ends.
+///
+/// Returns whether the exception came from a limit rather than a bug in the
+/// plugin: the plugin thread deals with those by disabling the plugin, so
+/// they are not escalated like other errors when tests run with
+/// `set_panic_on_js_errors`.
+pub(crate) fn note_js_exception(exception: &rquickjs::Value<'_>) -> bool {
+    let Some(object) = exception.as_object() else {
+        return false;
+    };
+    let name = object.get::<_, String>("name").unwrap_or_default();
+    let message = object.get::<_, String>("message").unwrap_or_default();
+    match (name.as_str(), message.as_str()) {
+        ("InternalError", "out of memory") => {
+            note_out_of_memory();
+            true
+        }
+        ("InternalError", "interrupted") => true,
+        _ => false,
+    }
+}
+
+/// Record an allocation that failed outside a JS exception (rquickjs reports
+/// those as `Error::Allocation`), charged like [`note_js_exception`]'s.
+pub(crate) fn note_out_of_memory() {
+    OUT_OF_MEMORY.with(|flag| flag.set(true));
+}
+
+/// Tracks the plugin code running on the plugin thread. See the module docs.
+#[derive(Debug, Default)]
+pub struct PluginWatchdog {
+    limits: Cell<PluginLimits>,
+    /// Runs in progress, innermost last. Plugin code that calls back into
+    /// the host can start a nested run.
+    runs: RefCell<Vec<Run>>,
+    tripped: RefCell<Vec<(String, TrippedLimit)>>,
+    profile: RefCell<HashMap<(String, RunKind, String), RunStats>>,
+}
+
+impl PluginWatchdog {
+    pub fn limits(&self) -> PluginLimits {
+        self.limits.get()
+    }
+
+    pub(crate) fn set_limits(&self, limits: PluginLimits) {
+        self.limits.set(limits);
+    }
+
+    /// Start a run of `plugin`'s code. The run ends, and is added to the
+    /// profile, when the returned guard drops.
+    pub(crate) fn enter(self: &Rc<Self>, plugin: &str, kind: RunKind, name: &str) -> RunGuard {
+        self.runs.borrow_mut().push(Run {
+            plugin: plugin.to_string(),
+            kind,
+            name: name.to_string(),
+            started: Instant::now(),
+            tripped: false,
+        });
+        RunGuard {
+            watchdog: Rc::clone(self),
+            record: true,
+        }
+    }
+
+    /// Called by the QuickJS interrupt handler: whether the innermost run
+    /// has outlived the execution timeout. The first time it has, its
+    /// plugin is recorded as tripped.
+    pub(crate) fn should_interrupt(&self) -> bool {
+        let Some(timeout) = self.limits.get().execution_timeout else {
+            return false;
+        };
+        let mut runs = self.runs.borrow_mut();
+        let Some(run) = runs.last_mut() else {
+            return false;
+        };
+        if run.started.elapsed() <= timeout {
+            return false;
+        }
+        if !run.tripped {
+            run.tripped = true;
+            self.tripped
+                .borrow_mut()
+                .push((run.plugin.clone(), TrippedLimit::Timeout(timeout)));
+        }
+        true
+    }
+
+    /// Whether `plugin` ran into a limit that has not been dealt with yet.
+    /// Its remaining handlers are skipped rather than each being allowed
+    /// the full timeout.
+    pub(crate) fn has_tripped(&self, plugin: &str) -> bool {
+        self.tripped.borrow().iter().any(|(p, _)| p == plugin)
+    }
+
+    /// Plugins that ran into a limit since the last call, each once.
+    pub(crate) fn take_tripped(&self) -> Vec<(String, TrippedLimit)> {
+        let mut tripped = std::mem::take(&mut *self.tripped.borrow_mut());
+        let mut seen = std::collections::HashSet::new();
+        tripped.retain(|(plugin, _)| seen.insert(plugin.clone()));
+        tripped
+    }
+
+    /// Cumulative time per plugin and per hook, command or kind of run,
+    /// slowest first.
+    pub fn profile(&self) -> Vec<PluginProfileEntry> {
+        let mut entries: Vec<PluginProfileEntry> = self
+            .profile
+            .borrow()
+            .iter()
+            .map(|((plugin, kind, name), stats)| PluginProfileEntry {
+                plugin: plugin.clone(),
+                kind: *kind,
+                name: name.clone(),
+                calls: stats.calls,
+                total: stats.total,
+                max: stats.max,
+            })
+            .collect();
+        entries.sort_by(|a, b| b.total.cmp(&a.total).then_with(|| a.plugin.cmp(&b.plugin)));
+        entries
+    }
+
+    fn exit(&self, record: bool) {
+        let Some(mut run) = self.runs.borrow_mut().pop() else {
+            return;
+        };
+        let elapsed = run.started.elapsed();
+        if OUT_OF_MEMORY.with(|flag| flag.replace(false)) && !run.tripped {
+            run.tripped = true;
+            self.tripped
+                .borrow_mut()
+                .push((run.plugin.clone(), TrippedLimit::OutOfMemory));
+        }
+        if record {
+            let mut profile = self.profile.borrow_mut();
+            let stats = profile.entry((run.plugin, run.kind, run.name)).or_default();
+            stats.calls += 1;
+            stats.total += elapsed;
+            stats.max = stats.max.max(elapsed);
+        }
+    }
+}
+
+/// A run in progress; see [`PluginWatchdog::enter`].
+#[must_use]
+pub(crate) struct RunGuard {
+    watchdog: Rc<PluginWatchdog>,
+    record: bool,
+}
+
+impl RunGuard {
+    /// Leave this run out of the profile — for event-loop polls that turned
+    /// out to have nothing to run.
+    pub(crate) fn discard(mut self) {
+        self.record = false;
+    }
+}
+
+impl Drop for RunGuard {
+    fn drop(&mut self) {
+        self.watchdog.exit(self.record);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn watchdog(timeout_ms: u64) -> Rc<PluginWatchdog> {
+        let watchdog = Rc::new(PluginWatchdog::default());
+        watchdog.set_limits(PluginLimits {
+            execution_timeout: Some(Duration::from_millis(timeout_ms)),
+            memory_limit_bytes: None,
+        });
+        watchdog
+    }
+
+    #[test]
+    fn interrupts_only_runs_past_the_timeout() {
+        let watchdog = watchdog(20);
+        assert!(!watchdog.should_interrupt(), "nothing is running");
+
+        let run = watchdog.enter("slow", RunKind::Hook, "render_line");
+        assert!(!watchdog.should_interrupt());
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(watchdog.should_interrupt());
+        assert!(watchdog.should_interrupt(), "keeps interrupting the run");
+        drop(run);
+
+        assert_eq!(
+            watchdog.take_tripped(),
+            vec![(
+                "slow".to_string(),
+                TrippedLimit::Timeout(Duration::from_millis(20))
+            )]
+        );
+        assert!(watchdog.take_tripped().is_empty());
+    }
+
+    #[test]
+    fn out_of_memory_is_charged_to_the_running_plugin() {
+        let watchdog = watchdog(1000);
+        let outer = watchdog.enter("a", RunKind::Command, "build");
+        let inner = watchdog.enter("b", RunKind::Async, "");
+        let runtime = rquickjs::Runtime::new().unwrap();
+        let context = rquickjs::Context::full(&runtime).unwrap();
+        runtime.set_memory_limit(4 * 1024 * 1024);
+        context.with(|ctx| {
+            let _ = ctx.eval::<(), _>("'x'.repeat(64 * 1024 * 1024)");
+            assert!(note_js_exception(&ctx.catch()));
+        });
+        drop(inner);
+        drop(outer);
+        assert_eq!(
+            watchdog.take_tripped(),
+            vec![("b".to_string(), TrippedLimit::OutOfMemory)]
+        );
+    }
+
+    #[test]
+    fn plugin_errors_mentioning_a_limit_are_not_limits() {
+        let runtime = rquickjs::Runtime::new().unwrap();
+        let context = rquickjs::Context::full(&runtime).unwrap();
+        context.with(|ctx| {
+            for script in [
+                "throw new Error('out of memory')",
+                "throw new Error('request interrupted by the server')",
+                "throw 'InternalError: out of memory'",
+            ] {
+                let _ = ctx.eval::<(), _>(script);
+                assert!(!note_js_exception(&ctx.catch()), "{script}");
+            }
+        });
+        assert!(!OUT_OF_MEMORY.with(Cell::get));
+    }
+
+    #[test]
+    fn profile_accumulates_per_plugin_and_name() {
+        let watchdog = watchdog(1000);
+        for _ in 0..3 {
+            let _run = watchdog.enter("p", RunKind::Hook, "cursor_moved");
+        }
+        watchdog.enter("p", RunKind::Async, "").discard();
+
+        let profile = watchdog.profile();
+        assert_eq!(profile.len(), 1);
+        assert_eq!(profile[0].plugin, "p");
+        assert_eq!(profile[0].kind, RunKind::Hook);
+        assert_eq!(profile[0].name, "cursor_moved");
+        assert_eq!(profile[0].calls, 3);
+        assert!(profile[0].max <= profile[0].total);
+    }
+}
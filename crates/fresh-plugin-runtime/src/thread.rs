@@ -10,7 +10,7 @@
 //! - Async operations complete naturally without runtime destruction
 
 use crate::backend::quickjs_backend::{AsyncResourceOwners, PendingResponses, TsPluginInfo};
-use crate::backend::QuickJsBackend;
+use crate::backend::{PluginLimits, PluginProfileEntry, QuickJsBackend};
 use anyhow::{anyhow, Result};
 use fresh_core::api::{EditorStateSnapshot, JsCallbackId, PluginCommand, SearchHandleRegistry};
 use fresh_core::hooks::HookArgs;
@@ -130,6 +130,14 @@ pub enum PluginRequest {
         response: oneshot::Sender<Vec<TsPluginInfo>>,
     },
 
+    /// Apply new execution and memory limits to plugin code
+    SetLimits { limits: PluginLimits },
+
+    /// Cumulative hook and command time per plugin
+    Profile {
+        response: oneshot::Sender<Vec<PluginProfileEntry>>,
+    },
+
     /// Track an async resource (buffer/terminal) that was just created.
     /// Sent by deliver_response when the editor confirms resource creation.
     TrackAsyncResource {
@@ -764,6 +772,29 @@ impl PluginThreadHandle {
         rx.recv().unwrap_or_default()
     }
 
+    /// Apply new execution and memory limits to plugin code. Fire-and-forget.
+    pub fn set_limits(&self, limits: PluginLimits) {
+        if let Some(sender) = self.request_sender.as_ref() {
+            fire_and_forget(sender.send(PluginRequest::SetLimits { limits }));
+        }
+    }
+
+    /// Cumulative hook and command time per plugin, slowest first (blocking)
+    pub fn profile(&self) -> Vec<PluginProfileEntry> {
+        let (tx, rx) = oneshot::channel();
+        let Some(sender) = self.request_sender.as_ref() else {
+            return vec![];
+        };
+        if sender
+            .send(PluginRequest::Profile { response: tx })
+            .is_err()
+        {
+            return vec![];
+        }
+
+        rx.recv().unwrap_or_default()
+    }
+
     /// Submit a "load plugins from dir with config" request without blocking.
     /// Returns the response receiver for the caller to await elsewhere
     /// (typically a forwarder thread that bridges to `AsyncBridge`).
@@ -1074,6 +1105,8 @@ async fn plugin_thread_loop(
             }
         }
 
+        disable_tripped_plugins(&runtime, plugins);
+
         tokio::select! {
             biased; // Prefer handling requests over polling
 
@@ -1120,6 +1153,37 @@ async fn plugin_thread_loop(
     }
 }
 
+/// Disable the plugins that ran into an execution or memory limit: unload
+/// them, keeping their entry (marked disabled) so a reload can bring them
+/// back, and say so in the warnings log.
+fn disable_tripped_plugins(
+    runtime: &Rc<RefCell<QuickJsBackend>>,
+    plugins: &mut HashMap<String, TsPluginInfo>,
+) {
+    let tripped = runtime.borrow().take_tripped_plugins();
+    if tripped.is_empty() {
+        return;
+    }
+    for (name, limit) in tripped {
+        tracing::warn!("Plugin '{}' {} and was disabled", name, limit);
+        match plugins.get(&name).cloned() {
+            Some(info) => {
+                fire_and_forget(unload_plugin_internal(Rc::clone(runtime), plugins, &name));
+                plugins.insert(
+                    name,
+                    TsPluginInfo {
+                        enabled: false,
+                        ..info
+                    },
+                );
+            }
+            // Tripped while loading, so never registered.
+            None => runtime.borrow().cleanup_plugin(&name),
+        }
+    }
+    runtime.borrow().run_gc();
+}
+
 /// Run a hook with Rc<RefCell<QuickJsBackend>>
 ///
 /// # Safety (clippy::await_holding_refcell_ref)
@@ -1287,6 +1351,14 @@ async fn handle_request(
             fire_and_forget(response.send(plugin_list));
         }
 
+        PluginRequest::SetLimits { limits } => {
+            runtime.borrow().set_limits(limits);
+        }
+
+        PluginRequest::Profile { response } => {
+            fire_and_forget(response.send(runtime.borrow().profile()));
+        }
+
         PluginRequest::ResolveCallback {
             callback_id,
             result_json,
@@ -24,9 +24,9 @@ use fresh_core::api::{
     KeyEventPayload, LanguagePackConfig, LayoutHints, LineDiffHunk, LspServerPackConfig,
     OverlayColorSpec, OverlayOptions, PluginAnimationEdge, PluginAnimationKind,
     ProcessLimitsPackConfig, RemoteBackendInfo, ReplaceResult, ScreenSize, ScrollbarMarker,
-    SearchTakeResult, SpawnResult, SplitSnapshot, TerminalResult, TextPropertiesAtCursor,
-    TokenColor, TsHighlightSpan, ViewTokenStyle, ViewTokenWire, ViewTokenWireKind, ViewportInfo,
-    VirtualBufferResult, WindowInfo,
+    SearchTakeResult, SpawnResult, SplitSnapshot, StructuralMatch, TerminalResult,
+    TextPropertiesAtCursor, TokenColor, TsHighlightSpan, ViewTokenStyle, ViewTokenWire,
+    ViewTokenWireKind, ViewportInfo, VirtualBufferResult, WindowInfo,
 };
 use fresh_core::command::Suggestion;
 use fresh_core::file_explorer::{
@@ -79,6 +79,7 @@ fn get_type_decl(type_name: &str) -> Option<String> {
         "GrepMatch" => Some(GrepMatch::decl(&cfg)),
         "ReplaceResult" => Some(ReplaceResult::decl(&cfg)),
         "SearchTakeResult" => Some(SearchTakeResult::decl(&cfg)),
+        "StructuralMatch" => Some(StructuralMatch::decl(&cfg)),
         // SearchHandle is the JS-side wrapper over a numeric handle id.
         // The Rust type can't be exported (non-serializable runtime state).
         "SearchHandle" => Some(
@@ -243,7 +244,8 @@ type AuthoritySpawner =
       user?: string | null;
       workspace?: string | null;
       env?: [string, string][];
-    };
+    }
+  | (ExecCarrier & { kind: "exec"; env?: [string, string][] });
 
 type AuthorityTerminalWrapper =
   | { kind: "host-shell" }
@@ -252,7 +254,28 @@ type AuthorityTerminalWrapper =
       command: string;
       args: string[];
       manages_cwd?: boolean;
-    };
+    }
+  | (ExecCarrier & { kind: "exec"; env?: [string, string][] });
+
+/**
+* Generic exec carrier (Podman, Incus, Distrobox, a custom sandbox, …):
+* argv templates with a `{cmd}` placeholder for the carried command. An
+* element that is exactly `{cmd}` is replaced by the command's argv; an
+* element containing it gets the shell-quoted command string; a template
+* without it has the argv appended.
+*/
+type ExecCarrier = {
+  /** One-shot runs, e.g. `["podman", "exec", "dev", "{cmd}"]`. */
+  run: string[];
+  /** Integrated-terminal runs (needs a TTY). Defaults to `run`. */
+  interactive?: string[] | null;
+  /** Long-lived piped runs: the remote agent and LSP servers. Defaults to `run`. */
+  stdio?: string[] | null;
+  /** Far-side workspace root (terminal `cd` target, default spawn cwd). */
+  workspace?: string | null;
+  /** Status-bar label; derived from `run` when omitted. */
+  label?: string | null;
+};
 
 type AuthorityPayload = {
   filesystem: AuthorityFilesystem;
@@ -273,8 +296,9 @@ type PathTranslationSpec = {
 };"#;
 
 /// Hand-written declaration for `RemoteAgentSpec` (the
-/// `editor.attachRemoteAgent(...)` payload), covering both transports
-/// (`kubectl-exec` and `ssh`) and the window-mode fields. Keep in sync with
+/// `editor.attachRemoteAgent(...)` payload), covering every transport
+/// (`kubectl-exec`, `ssh` and `exec`) and the window-mode fields. `exec`
+/// reuses the `ExecCarrier` type declared with `AuthorityPayload`. Keep in sync with
 /// `crates/fresh-editor/src/services/authority/mod.rs`'s `RemoteAgentSpec` /
 /// `RemoteTransportSpec` — this crate must not depend on `fresh-editor`, so the
 /// shape is mirrored by hand.
@@ -301,7 +325,9 @@ const REMOTE_AGENT_SPEC_DECL: &str = r#"type RemoteAgentTransport = {
   /** Extra `ssh` arguments (e.g. `-J jump`, `-o ProxyCommand=…`) applied to
   * every ssh invocation for this session. */
   extra_args?: string[];
-};
+} | (ExecCarrier & {
+  kind: "exec";
+});
 
 type RemoteAgentSpec = {
   transport: RemoteAgentTransport;
@@ -71,6 +71,8 @@ export default defineConfig({
           { text: "Guided Code Tours", link: "/features/code-tours" },
           { text: "Language Support", link: "/features/languages" },
           { text: "LSP Integration", link: "/features/lsp" },
+          { text: "Debugger", link: "/features/debugger" },
+          { text: "Tasks", link: "/features/tasks" },
           { text: "Git", link: "/features/git" },
           { text: "Themes", link: "/features/themes" },
           { text: "Encoding", link: "/features/encoding" },